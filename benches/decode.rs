@@ -0,0 +1,41 @@
+use bollard::bench_utils::{
+    decode_json_lines, demux_log_output, events_fixture, log_frames_fixture, stats_fixture,
+};
+use bollard::container::Stats;
+use bollard::models::EventMessage;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+fn json_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("json_decode");
+
+    let stats = stats_fixture(1000);
+    group.throughput(Throughput::Bytes(stats.len() as u64));
+    group.bench_function("stats", |b| {
+        b.iter(|| decode_json_lines::<Stats>(&stats).unwrap())
+    });
+
+    let events = events_fixture(1000);
+    group.throughput(Throughput::Bytes(events.len() as u64));
+    group.bench_function("events", |b| {
+        b.iter(|| decode_json_lines::<EventMessage>(&events).unwrap())
+    });
+
+    group.finish();
+}
+
+fn log_demux(c: &mut Criterion) {
+    let mut group = c.benchmark_group("log_demux");
+
+    for line_len in [64, 4096] {
+        let frames = log_frames_fixture(1000, line_len);
+        group.throughput(Throughput::Bytes(frames.len() as u64));
+        group.bench_function(format!("{line_len}b_lines"), |b| {
+            b.iter(|| demux_log_output(&frames).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, json_decode, log_demux);
+criterion_main!(benches);
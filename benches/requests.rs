@@ -0,0 +1,143 @@
+use bollard::bench_utils::{
+    log_frames_fixture, stats_fixture, tar_fixture, MockDaemon, MockResponse,
+};
+use bollard::container::{
+    DownloadFromContainerOptions, LogsOptions, StatsOptions, UploadToContainerOptions,
+};
+use bollard::{body_full, Docker};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use futures_util::future::try_join_all;
+use futures_util::TryStreamExt;
+use tokio::runtime::Runtime;
+
+use std::collections::HashMap;
+
+const CONTAINER: &str = "bench";
+
+fn start_daemon(runtime: &Runtime) -> (MockDaemon, Docker) {
+    let archive = tar_fixture(64, 16 * 1024).unwrap();
+
+    let mut routes = HashMap::new();
+    routes.insert(
+        format!("/containers/{CONTAINER}/json"),
+        MockResponse::json(r#"{"Id":"bench","Name":"/bench"}"#),
+    );
+    routes.insert(
+        format!("/containers/{CONTAINER}/stats"),
+        MockResponse::json(stats_fixture(1000)),
+    );
+    routes.insert(
+        format!("/containers/{CONTAINER}/logs"),
+        MockResponse::raw(log_frames_fixture(1000, 128)),
+    );
+    routes.insert(
+        format!("/containers/{CONTAINER}/archive"),
+        MockResponse::raw(archive),
+    );
+
+    let daemon = runtime.block_on(MockDaemon::start(routes)).unwrap();
+    let docker = daemon.docker().unwrap();
+    (daemon, docker)
+}
+
+fn streaming(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (_daemon, docker) = start_daemon(&runtime);
+    let mut group = c.benchmark_group("streaming");
+
+    group.throughput(Throughput::Elements(1000));
+    group.bench_function("stats", |b| {
+        b.to_async(&runtime).iter(|| async {
+            docker
+                .stats(
+                    CONTAINER,
+                    Some(StatsOptions {
+                        stream: true,
+                        one_shot: false,
+                        idle_timeout: None,
+                    }),
+                )
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap()
+        })
+    });
+
+    group.bench_function("logs", |b| {
+        b.to_async(&runtime).iter(|| async {
+            docker
+                .logs(
+                    CONTAINER,
+                    Some(LogsOptions::<String> {
+                        stdout: true,
+                        stderr: true,
+                        ..Default::default()
+                    }),
+                )
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+fn tar_streaming(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (_daemon, docker) = start_daemon(&runtime);
+    let archive = tar_fixture(64, 16 * 1024).unwrap();
+    let mut group = c.benchmark_group("tar_streaming");
+    group.throughput(Throughput::Bytes(archive.len() as u64));
+
+    group.bench_function("upload", |b| {
+        b.to_async(&runtime).iter(|| async {
+            docker
+                .upload_to_container(
+                    CONTAINER,
+                    Some(UploadToContainerOptions {
+                        path: "/",
+                        ..Default::default()
+                    }),
+                    body_full(archive.clone()),
+                )
+                .await
+                .unwrap()
+        })
+    });
+
+    group.bench_function("download", |b| {
+        b.to_async(&runtime).iter(|| async {
+            docker
+                .download_from_container(
+                    CONTAINER,
+                    Some(DownloadFromContainerOptions { path: "/" }),
+                )
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+fn concurrent_requests(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (_daemon, docker) = start_daemon(&runtime);
+    let mut group = c.benchmark_group("concurrent_requests");
+
+    for concurrency in [1, 16, 64] {
+        group.throughput(Throughput::Elements(concurrency));
+        group.bench_function(format!("inspect_container_x{concurrency}"), |b| {
+            b.to_async(&runtime).iter(|| {
+                try_join_all((0..concurrency).map(|_| docker.inspect_container(CONTAINER, None)))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, streaming, tar_streaming, concurrent_requests);
+criterion_main!(benches);
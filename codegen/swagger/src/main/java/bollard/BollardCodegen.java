@@ -56,6 +56,108 @@ public class BollardCodegen extends RustServerCodegen {
         enumToString.add("HostConfigLogConfig");
     }
 
+    // Required fields that are omitted by some daemons, e.g. with the containerd image store
+    // enabled, and fall back to their default value
+    private static ArrayList<String> defaultModelFields;
+    static {
+        defaultModelFields = new ArrayList();
+        defaultModelFields.add("DriverData.name");
+        defaultModelFields.add("DriverData.data");
+        defaultModelFields.add("ContainerCreateResponse.warnings");
+    }
+
+    // Fields whose wire format differs from the spec, deserialized through a lenient function
+    // declared in the models template
+    private static HashMap<String, String> deserializeWithModelFields;
+    static {
+        deserializeWithModelFields = new HashMap<String, String>();
+        deserializeWithModelFields.put("PluginConfigInterface.types", "deserialize_plugin_interface_types");
+    }
+
+    // Request fields introduced after API v1.40, omitted when serializing for an older negotiated
+    // version. The spec carries no per-field version annotations, so this list is taken by hand
+    // from the API version history and is partial: fields missing here are still sent to older
+    // daemons. Keep it in sync with `test_min_api_version_fields` in bollard's src/docker.rs.
+    private static HashMap<String, String> minApiVersionModelFields;
+    static {
+        minApiVersionModelFields = new HashMap<String, String>();
+        minApiVersionModelFields.put("HostConfig.cgroupns_mode", "1.41");
+        minApiVersionModelFields.put("HostConfig.console_size", "1.42");
+        minApiVersionModelFields.put("HostConfig.annotations", "1.43");
+        minApiVersionModelFields.put("HealthConfig.start_interval", "1.44");
+        minApiVersionModelFields.put("EndpointSettings.mac_address", "1.44");
+        minApiVersionModelFields.put("MountBindOptions.create_mountpoint", "1.42");
+        minApiVersionModelFields.put("MountBindOptions.read_only_non_recursive", "1.44");
+        minApiVersionModelFields.put("MountBindOptions.read_only_force_recursive", "1.44");
+        minApiVersionModelFields.put("MountTmpfsOptions.options", "1.46");
+        minApiVersionModelFields.put("MountVolumeOptions.subpath", "1.45");
+    }
+
+    // Declare properties that newer daemons return, ahead of the spec version in use
+    private static HashMap<String, Map<String, Property>> patchModelProperties;
+    static {
+        patchModelProperties = new HashMap<String, Map<String, Property>>();
+        Map<String, Property> imageInspectProperties = new LinkedHashMap<String, Property>();
+
+        RefProperty descriptor = new RefProperty("OCIDescriptor");
+        descriptor.setDescription("Descriptor is an OCI descriptor of the image target. In case of a multi-platform image, this descriptor points to the OCI index or a manifest list.  This field is only present if the daemon provides a multi-platform image store.");
+        imageInspectProperties.put("Descriptor", descriptor);
+
+        ArrayProperty manifests = new ArrayProperty(new RefProperty("ImageManifestSummary"));
+        manifests.setDescription("Manifests is a list of image manifests available in this image. It provides a more detailed view of the platform-specific image manifests or other image-attached data like build attestations.  Only available if the daemon provides a multi-platform image store.");
+        imageInspectProperties.put("Manifests", manifests);
+
+        patchModelProperties.put("ImageInspect", imageInspectProperties);
+    }
+
+    // Request models, and the models nested in them, that get a companion builder
+    private static ArrayList<String> builderModels;
+    static {
+        builderModels = new ArrayList();
+        builderModels.add("ContainerConfig");
+        builderModels.add("HostConfig");
+        builderModels.add("Resources");
+        builderModels.add("ResourcesUlimits");
+        builderModels.add("RestartPolicy");
+        builderModels.add("HealthConfig");
+        builderModels.add("HostConfigLogConfig");
+        builderModels.add("DeviceMapping");
+        builderModels.add("DeviceRequest");
+        builderModels.add("PortBinding");
+        builderModels.add("Mount");
+        builderModels.add("MountBindOptions");
+        builderModels.add("MountVolumeOptions");
+        builderModels.add("MountVolumeOptionsDriverConfig");
+        builderModels.add("MountTmpfsOptions");
+        builderModels.add("NetworkingConfig");
+        builderModels.add("EndpointSettings");
+        builderModels.add("EndpointIpamConfig");
+        builderModels.add("NetworkCreateRequest");
+        builderModels.add("NetworkConnectRequest");
+        builderModels.add("NetworkDisconnectRequest");
+        builderModels.add("Ipam");
+        builderModels.add("IpamConfig");
+        builderModels.add("VolumeCreateOptions");
+        builderModels.add("ServiceSpec");
+        builderModels.add("ServiceSpecMode");
+        builderModels.add("ServiceSpecUpdateConfig");
+        builderModels.add("EndpointSpec");
+        builderModels.add("EndpointPortConfig");
+        builderModels.add("TaskSpec");
+        builderModels.add("TaskSpecContainerSpec");
+        builderModels.add("TaskSpecResources");
+        builderModels.add("TaskSpecRestartPolicy");
+        builderModels.add("TaskSpecPlacement");
+        builderModels.add("TaskSpecLogDriver");
+        builderModels.add("NetworkAttachmentConfig");
+        builderModels.add("SwarmSpec");
+        builderModels.add("SwarmInitRequest");
+        builderModels.add("SwarmJoinRequest");
+        builderModels.add("SecretSpec");
+        builderModels.add("ConfigSpec");
+        builderModels.add("NodeSpec");
+    }
+
     private static ArrayList<String> upperCaseModelFields;
     static {
         upperCaseModelFields = new ArrayList();
@@ -70,6 +172,18 @@ public class BollardCodegen extends RustServerCodegen {
 
         info.setVersion(StringUtils.join(versionComponents, "."));
 
+        for (Entry<String, Map<String, Property>> entry : patchModelProperties.entrySet()) {
+            Model model = swagger.getDefinitions().get(entry.getKey());
+            Map<String, Property> properties = new LinkedHashMap<String, Property>();
+            for (Entry<String, Property> property : model.getProperties().entrySet()) {
+                properties.put(property.getKey(), property.getValue());
+                if (property.getKey().equals("Id")) {
+                    properties.putAll(entry.getValue());
+                }
+            }
+            model.setProperties(properties);
+        }
+
         super.preprocessSwagger(swagger);
     }
 
@@ -121,6 +235,10 @@ public class BollardCodegen extends RustServerCodegen {
                 model.vendorExtensions.put("x-rustgen-upper-case", true);
             }
 
+            if (builderModels.contains(model.classname)) {
+                model.vendorExtensions.put("x-rustgen-builder", true);
+            }
+
             // Special case for numeric Enums
             if (model.isEnum && model.dataType != null && (model.dataType.equals("i8") || model.dataType.equals("i16") || model.dataType.equals("i32") || model.dataType.equals("i64"))) {
                 model.vendorExtensions.put("x-rustgen-numeric-enum", true);
@@ -155,6 +273,38 @@ public class BollardCodegen extends RustServerCodegen {
                     prop.vendorExtensions.put("x-rustgen-upper-case", true);
                 }
 
+                if (!prop.required || prop.isEnum) {
+                    // the field is an Option, set through the builder with Some
+                    prop.vendorExtensions.put("x-rustgen-builder-some", true);
+                }
+
+                if (defaultModelFields.contains(model.classname + "." + prop.name)) {
+                    prop.vendorExtensions.put("x-rustgen-default", true);
+                }
+
+                String deserializeWith = deserializeWithModelFields.get(model.classname + "." + prop.name);
+                if (deserializeWith != null) {
+                    prop.vendorExtensions.put("x-rustgen-deserialize-with", deserializeWith);
+                }
+
+                CodegenModel propModel = prop.complexType == null ? null : allModels.get(prop.complexType);
+                if (!prop.required && (prop.isEnum || (propModel != null && propModel.isEnum))) {
+                    // unknown enum values deserialize into None, rather than failing the response
+                    prop.vendorExtensions.put("x-rustgen-lenient-enum", true);
+                }
+
+                String minApiVersion = minApiVersionModelFields.get(model.classname + "." + prop.name);
+                if (minApiVersion != null) {
+                    // omit the field when serializing for an older API version
+                    String[] version = minApiVersion.split("\\.");
+                    HashMap<String, String> minApiVersionExtension = new HashMap<String, String>();
+                    minApiVersionExtension.put("skip", "skip_" + underscore(model.classname) + "_" + prop.name);
+                    minApiVersionExtension.put("field", model.classname + "." + prop.baseName);
+                    minApiVersionExtension.put("major", version[0]);
+                    minApiVersionExtension.put("minor", version[1]);
+                    prop.vendorExtensions.put("x-rustgen-min-api-version", minApiVersionExtension);
+                }
+
                 if (prop.dataFormat != null && (prop.dataFormat.equals("dateTime") || prop.datatype.equals("BollardDate"))) {
                     // set DateTime format on properties where appropriate
                     prop.vendorExtensions.put("x-rustgen-is-datetime", true);
@@ -29,6 +29,101 @@ fn deserialize_nonoptional_map<'de, D: Deserializer<'de>, T: DeserializeOwned>(
     serde::Deserialize::deserialize(d).map(|x: Option<_>| x.unwrap_or(HashMap::new()))
 }
 
+/// Plugin interface types are documented as objects, but the docker daemon sends them in their
+/// string form, e.g. `docker.volumedriver/1.0`, while other engines send objects.
+fn deserialize_plugin_interface_types<'de, D: Deserializer<'de>>(
+    d: D,
+) -> Result<Vec<PluginInterfaceType>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        String(String),
+        Object(PluginInterfaceType),
+    }
+
+    let types: Option<Vec<Repr>> = serde::Deserialize::deserialize(d)?;
+    Ok(types
+        .unwrap_or_default()
+        .into_iter()
+        .map(|repr| match repr {
+            Repr::Object(typ) => typ,
+            Repr::String(typ) => {
+                let (name, version) = typ.split_once('/').unwrap_or((&typ, ""));
+                let (prefix, capability) = name.rsplit_once('.').unwrap_or(("", name));
+                PluginInterfaceType {
+                    prefix: String::from(prefix),
+                    capability: String::from(capability),
+                    version: String::from(version),
+                }
+            }
+        })
+        .collect())
+}
+
+/// Enum values that this version of the API does not know, as sent by newer daemons, deserialize
+/// into `None` rather than failing the whole response.
+fn deserialize_lenient_enum<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    d: D,
+) -> Result<Option<T>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Lenient<T> {
+        Known(T),
+        Unknown(serde::de::IgnoredAny),
+    }
+
+    let value: Option<Lenient<T>> = serde::Deserialize::deserialize(d)?;
+    Ok(match value {
+        Some(Lenient::Known(value)) => Some(value),
+        _ => None,
+    })
+}
+
+/// Like `deserialize_lenient_enum`, for required enum fields that are empty when unset.
+fn deserialize_nonempty_lenient_enum<'de, D: Deserializer<'de>, T: std::str::FromStr>(
+    d: D,
+) -> Result<Option<T>, D::Error> {
+    let value: Option<String> = serde::Deserialize::deserialize(d)?;
+    Ok(value
+        .filter(|value| !value.is_empty())
+        .and_then(|value| value.parse().ok()))
+}
+
+thread_local! {
+    static SERIALIZE_API_VERSION: std::cell::RefCell<Option<((usize, usize), Vec<&'static str>)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Serialize models for the given API version: while `f` runs, fields that were introduced in a
+/// later version of the API are omitted. Returns the result of `f`, along with the fields that
+/// were set but omitted.
+pub fn with_api_version<R>(
+    version: (usize, usize),
+    f: impl FnOnce() -> R,
+) -> (R, Vec<&'static str>) {
+    let previous = SERIALIZE_API_VERSION.with(|state| state.replace(Some((version, Vec::new()))));
+    let result = f();
+    let skipped = SERIALIZE_API_VERSION
+        .with(|state| state.replace(previous))
+        .map(|(_, skipped)| skipped)
+        .unwrap_or_default();
+    (result, skipped)
+}
+
+fn skip_unsupported_field(is_none: bool, field: &'static str, introduced: (usize, usize)) -> bool {
+    is_none
+        || SERIALIZE_API_VERSION.with(|state| match state.borrow_mut().as_mut() {
+            Some((version, skipped)) if *version < introduced => {
+                // serde checks each field twice, once to count them and once to serialize it
+                if !skipped.contains(&field) {
+                    skipped.push(field);
+                }
+                true
+            }
+            _ => false,
+        })
+}
+
 #[cfg(feature = "time")]
 pub type BollardDate = time::OffsetDateTime;
 #[cfg(all(feature = "chrono", not(feature = "time")))]
@@ -164,6 +259,7 @@ pub struct BuildCache {
     /// Cache record type. 
     #[serde(rename = "Type")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub typ: Option<BuildCacheTypeEnum>,
 
     /// Description of the build-step that produced the build cache. 
@@ -502,6 +598,7 @@ pub struct ClusterVolumePublishStatus {
     /// The published state of the volume. * `pending-publish` The volume should be published to this node, but the call to the controller plugin to do so has not yet been successfully completed. * `published` The volume is published successfully to the node. * `pending-node-unpublish` The volume should be unpublished from the node, and the manager is awaiting confirmation from the worker that it has done so. * `pending-controller-unpublish` The volume is successfully unpublished from the node, but has not yet been successfully unpublished on the controller. 
     #[serde(rename = "State")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub state: Option<ClusterVolumePublishStatusStateEnum>,
 
     /// A map of strings to strings returned by the CSI controller plugin when a volume is published. 
@@ -585,11 +682,13 @@ pub struct ClusterVolumeSpecAccessMode {
     /// The set of nodes this volume can be used on at one time. - `single` The volume may only be scheduled to one node at a time. - `multi` the volume may be scheduled to any supported number of nodes at a time. 
     #[serde(rename = "Scope")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub scope: Option<ClusterVolumeSpecAccessModeScopeEnum>,
 
     /// The number and way that different tasks can use this volume at one time. - `none` The volume may only be used by one task at a time. - `readonly` The volume may be used by any number of tasks, but they all must mount the volume as readonly - `onewriter` The volume may be used by any number of tasks, but only one may mount it as read/write. - `all` The volume may have any number of readers and writers. 
     #[serde(rename = "Sharing")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub sharing: Option<ClusterVolumeSpecAccessModeSharingEnum>,
 
     /// Options for using this volume as a Mount-type volume.      Either MountVolume or BlockVolume, but not both, must be     present.   properties:     FsType:       type: \"string\"       description: |         Specifies the filesystem type for the mount volume.         Optional.     MountFlags:       type: \"array\"       description: |         Flags to pass when mounting the volume. Optional.       items:         type: \"string\" BlockVolume:   type: \"object\"   description: |     Options for using this volume as a Block-type volume.     Intentionally empty. 
@@ -613,6 +712,7 @@ pub struct ClusterVolumeSpecAccessMode {
     /// The availability of the volume for use in tasks. - `active` The volume is fully available for scheduling on the cluster - `pause` No new workloads should use the volume, but existing workloads are not stopped. - `drain` All workloads using this volume should be stopped and rescheduled, and no new ones should be started. 
     #[serde(rename = "Availability")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub availability: Option<ClusterVolumeSpecAccessModeAvailabilityEnum>,
 
 }
@@ -892,6 +992,56 @@ pub struct ConfigSpec {
 
 }
 
+impl ConfigSpec {
+    /// Start building a [ConfigSpec](ConfigSpec), with every field unset.
+    pub fn builder() -> ConfigSpecBuilder {
+        ConfigSpecBuilder::default()
+    }
+}
+
+/// Builder for [ConfigSpec](ConfigSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigSpecBuilder {
+    inner: ConfigSpec,
+}
+
+impl ConfigSpecBuilder {
+    /// Set the [`name`](ConfigSpec::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`labels`](ConfigSpec::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`data`](ConfigSpec::data) field.
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.inner.data = Some(data.into());
+        self
+    }
+
+    /// Set the [`templating`](ConfigSpec::templating) field.
+    pub fn templating(mut self, templating: impl Into<Driver>) -> Self {
+        self.inner.templating = Some(templating.into());
+        self
+    }
+
+    /// Build the [ConfigSpec](ConfigSpec).
+    pub fn build(self) -> ConfigSpec {
+        self.inner
+    }
+}
+
+impl From<ConfigSpecBuilder> for ConfigSpec {
+    fn from(builder: ConfigSpecBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Configuration for a container that is portable between hosts. 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ContainerConfig {
@@ -1021,6 +1171,182 @@ pub struct ContainerConfig {
 
 }
 
+impl ContainerConfig {
+    /// Start building a [ContainerConfig](ContainerConfig), with every field unset.
+    pub fn builder() -> ContainerConfigBuilder {
+        ContainerConfigBuilder::default()
+    }
+}
+
+/// Builder for [ContainerConfig](ContainerConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerConfigBuilder {
+    inner: ContainerConfig,
+}
+
+impl ContainerConfigBuilder {
+    /// Set the [`hostname`](ContainerConfig::hostname) field.
+    pub fn hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.inner.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Set the [`domainname`](ContainerConfig::domainname) field.
+    pub fn domainname(mut self, domainname: impl Into<String>) -> Self {
+        self.inner.domainname = Some(domainname.into());
+        self
+    }
+
+    /// Set the [`user`](ContainerConfig::user) field.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.inner.user = Some(user.into());
+        self
+    }
+
+    /// Set the [`attach_stdin`](ContainerConfig::attach_stdin) field.
+    pub fn attach_stdin(mut self, attach_stdin: impl Into<bool>) -> Self {
+        self.inner.attach_stdin = Some(attach_stdin.into());
+        self
+    }
+
+    /// Set the [`attach_stdout`](ContainerConfig::attach_stdout) field.
+    pub fn attach_stdout(mut self, attach_stdout: impl Into<bool>) -> Self {
+        self.inner.attach_stdout = Some(attach_stdout.into());
+        self
+    }
+
+    /// Set the [`attach_stderr`](ContainerConfig::attach_stderr) field.
+    pub fn attach_stderr(mut self, attach_stderr: impl Into<bool>) -> Self {
+        self.inner.attach_stderr = Some(attach_stderr.into());
+        self
+    }
+
+    /// Set the [`exposed_ports`](ContainerConfig::exposed_ports) field.
+    pub fn exposed_ports(mut self, exposed_ports: impl Into<HashMap<String, HashMap<(), ()>>>) -> Self {
+        self.inner.exposed_ports = Some(exposed_ports.into());
+        self
+    }
+
+    /// Set the [`tty`](ContainerConfig::tty) field.
+    pub fn tty(mut self, tty: impl Into<bool>) -> Self {
+        self.inner.tty = Some(tty.into());
+        self
+    }
+
+    /// Set the [`open_stdin`](ContainerConfig::open_stdin) field.
+    pub fn open_stdin(mut self, open_stdin: impl Into<bool>) -> Self {
+        self.inner.open_stdin = Some(open_stdin.into());
+        self
+    }
+
+    /// Set the [`stdin_once`](ContainerConfig::stdin_once) field.
+    pub fn stdin_once(mut self, stdin_once: impl Into<bool>) -> Self {
+        self.inner.stdin_once = Some(stdin_once.into());
+        self
+    }
+
+    /// Set the [`env`](ContainerConfig::env) field.
+    pub fn env(mut self, env: impl Into<Vec<String>>) -> Self {
+        self.inner.env = Some(env.into());
+        self
+    }
+
+    /// Set the [`cmd`](ContainerConfig::cmd) field.
+    pub fn cmd(mut self, cmd: impl Into<Vec<String>>) -> Self {
+        self.inner.cmd = Some(cmd.into());
+        self
+    }
+
+    /// Set the [`healthcheck`](ContainerConfig::healthcheck) field.
+    pub fn healthcheck(mut self, healthcheck: impl Into<HealthConfig>) -> Self {
+        self.inner.healthcheck = Some(healthcheck.into());
+        self
+    }
+
+    /// Set the [`args_escaped`](ContainerConfig::args_escaped) field.
+    pub fn args_escaped(mut self, args_escaped: impl Into<bool>) -> Self {
+        self.inner.args_escaped = Some(args_escaped.into());
+        self
+    }
+
+    /// Set the [`image`](ContainerConfig::image) field.
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.inner.image = Some(image.into());
+        self
+    }
+
+    /// Set the [`volumes`](ContainerConfig::volumes) field.
+    pub fn volumes(mut self, volumes: impl Into<HashMap<String, HashMap<(), ()>>>) -> Self {
+        self.inner.volumes = Some(volumes.into());
+        self
+    }
+
+    /// Set the [`working_dir`](ContainerConfig::working_dir) field.
+    pub fn working_dir(mut self, working_dir: impl Into<String>) -> Self {
+        self.inner.working_dir = Some(working_dir.into());
+        self
+    }
+
+    /// Set the [`entrypoint`](ContainerConfig::entrypoint) field.
+    pub fn entrypoint(mut self, entrypoint: impl Into<Vec<String>>) -> Self {
+        self.inner.entrypoint = Some(entrypoint.into());
+        self
+    }
+
+    /// Set the [`network_disabled`](ContainerConfig::network_disabled) field.
+    pub fn network_disabled(mut self, network_disabled: impl Into<bool>) -> Self {
+        self.inner.network_disabled = Some(network_disabled.into());
+        self
+    }
+
+    /// Set the [`mac_address`](ContainerConfig::mac_address) field.
+    pub fn mac_address(mut self, mac_address: impl Into<String>) -> Self {
+        self.inner.mac_address = Some(mac_address.into());
+        self
+    }
+
+    /// Set the [`on_build`](ContainerConfig::on_build) field.
+    pub fn on_build(mut self, on_build: impl Into<Vec<String>>) -> Self {
+        self.inner.on_build = Some(on_build.into());
+        self
+    }
+
+    /// Set the [`labels`](ContainerConfig::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`stop_signal`](ContainerConfig::stop_signal) field.
+    pub fn stop_signal(mut self, stop_signal: impl Into<String>) -> Self {
+        self.inner.stop_signal = Some(stop_signal.into());
+        self
+    }
+
+    /// Set the [`stop_timeout`](ContainerConfig::stop_timeout) field.
+    pub fn stop_timeout(mut self, stop_timeout: impl Into<i64>) -> Self {
+        self.inner.stop_timeout = Some(stop_timeout.into());
+        self
+    }
+
+    /// Set the [`shell`](ContainerConfig::shell) field.
+    pub fn shell(mut self, shell: impl Into<Vec<String>>) -> Self {
+        self.inner.shell = Some(shell.into());
+        self
+    }
+
+    /// Build the [ContainerConfig](ContainerConfig).
+    pub fn build(self) -> ContainerConfig {
+        self.inner
+    }
+}
+
+impl From<ContainerConfigBuilder> for ContainerConfig {
+    fn from(builder: ContainerConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// OK response to ContainerCreate operation
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ContainerCreateResponse {
@@ -1030,6 +1356,7 @@ pub struct ContainerCreateResponse {
 
     /// Warnings encountered when creating the container
     #[serde(rename = "Warnings")]
+    #[serde(default)]
     #[serde(deserialize_with = "deserialize_nonoptional_vec")]
     pub warnings: Vec<String>,
 
@@ -1167,6 +1494,7 @@ pub struct ContainerState {
     /// String representation of the container state. Can be one of \"created\", \"running\", \"paused\", \"restarting\", \"removing\", \"exited\", or \"dead\". 
     #[serde(rename = "Status")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub status: Option<ContainerStateStatusEnum>,
 
     /// Whether this container is running.  Note that a running container can be _paused_. The `Running` and `Paused` booleans are not mutually exclusive:  When pausing a container (on Linux), the freezer cgroup is used to suspend all processes in the container. Freezing the process requires the process to be running. As a result, paused containers are both `Running` _and_ `Paused`.  Use the `Status` field instead to determine if a container's state is \"running\". 
@@ -1528,6 +1856,50 @@ pub struct DeviceMapping {
 
 }
 
+impl DeviceMapping {
+    /// Start building a [DeviceMapping](DeviceMapping), with every field unset.
+    pub fn builder() -> DeviceMappingBuilder {
+        DeviceMappingBuilder::default()
+    }
+}
+
+/// Builder for [DeviceMapping](DeviceMapping).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceMappingBuilder {
+    inner: DeviceMapping,
+}
+
+impl DeviceMappingBuilder {
+    /// Set the [`path_on_host`](DeviceMapping::path_on_host) field.
+    pub fn path_on_host(mut self, path_on_host: impl Into<String>) -> Self {
+        self.inner.path_on_host = Some(path_on_host.into());
+        self
+    }
+
+    /// Set the [`path_in_container`](DeviceMapping::path_in_container) field.
+    pub fn path_in_container(mut self, path_in_container: impl Into<String>) -> Self {
+        self.inner.path_in_container = Some(path_in_container.into());
+        self
+    }
+
+    /// Set the [`cgroup_permissions`](DeviceMapping::cgroup_permissions) field.
+    pub fn cgroup_permissions(mut self, cgroup_permissions: impl Into<String>) -> Self {
+        self.inner.cgroup_permissions = Some(cgroup_permissions.into());
+        self
+    }
+
+    /// Build the [DeviceMapping](DeviceMapping).
+    pub fn build(self) -> DeviceMapping {
+        self.inner
+    }
+}
+
+impl From<DeviceMappingBuilder> for DeviceMapping {
+    fn from(builder: DeviceMappingBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// A request for devices to be sent to device drivers
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct DeviceRequest {
@@ -1555,6 +1927,62 @@ pub struct DeviceRequest {
 
 }
 
+impl DeviceRequest {
+    /// Start building a [DeviceRequest](DeviceRequest), with every field unset.
+    pub fn builder() -> DeviceRequestBuilder {
+        DeviceRequestBuilder::default()
+    }
+}
+
+/// Builder for [DeviceRequest](DeviceRequest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceRequestBuilder {
+    inner: DeviceRequest,
+}
+
+impl DeviceRequestBuilder {
+    /// Set the [`driver`](DeviceRequest::driver) field.
+    pub fn driver(mut self, driver: impl Into<String>) -> Self {
+        self.inner.driver = Some(driver.into());
+        self
+    }
+
+    /// Set the [`count`](DeviceRequest::count) field.
+    pub fn count(mut self, count: impl Into<i64>) -> Self {
+        self.inner.count = Some(count.into());
+        self
+    }
+
+    /// Set the [`device_ids`](DeviceRequest::device_ids) field.
+    pub fn device_ids(mut self, device_ids: impl Into<Vec<String>>) -> Self {
+        self.inner.device_ids = Some(device_ids.into());
+        self
+    }
+
+    /// Set the [`capabilities`](DeviceRequest::capabilities) field.
+    pub fn capabilities(mut self, capabilities: impl Into<Vec<Vec<String>>>) -> Self {
+        self.inner.capabilities = Some(capabilities.into());
+        self
+    }
+
+    /// Set the [`options`](DeviceRequest::options) field.
+    pub fn options(mut self, options: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.options = Some(options.into());
+        self
+    }
+
+    /// Build the [DeviceRequest](DeviceRequest).
+    pub fn build(self) -> DeviceRequest {
+        self.inner
+    }
+}
+
+impl From<DeviceRequestBuilder> for DeviceRequest {
+    fn from(builder: DeviceRequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Describes the result obtained from contacting the registry to retrieve image metadata. 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct DistributionInspect {
@@ -1587,10 +2015,12 @@ pub struct Driver {
 pub struct DriverData {
     /// Name of the storage driver.
     #[serde(rename = "Name")]
+    #[serde(default)]
     pub name: String,
 
     /// Low-level storage metadata, provided as key/value pairs.  This information is driver-specific, and depends on the storage-driver in use, and should be used for informational purposes only. 
     #[serde(rename = "Data")]
+    #[serde(default)]
     #[serde(deserialize_with = "deserialize_nonoptional_map")]
     pub data: HashMap<String, String>,
 
@@ -1613,6 +2043,50 @@ pub struct EndpointIpamConfig {
 
 }
 
+impl EndpointIpamConfig {
+    /// Start building a [EndpointIpamConfig](EndpointIpamConfig), with every field unset.
+    pub fn builder() -> EndpointIpamConfigBuilder {
+        EndpointIpamConfigBuilder::default()
+    }
+}
+
+/// Builder for [EndpointIpamConfig](EndpointIpamConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointIpamConfigBuilder {
+    inner: EndpointIpamConfig,
+}
+
+impl EndpointIpamConfigBuilder {
+    /// Set the [`ipv4_address`](EndpointIpamConfig::ipv4_address) field.
+    pub fn ipv4_address(mut self, ipv4_address: impl Into<String>) -> Self {
+        self.inner.ipv4_address = Some(ipv4_address.into());
+        self
+    }
+
+    /// Set the [`ipv6_address`](EndpointIpamConfig::ipv6_address) field.
+    pub fn ipv6_address(mut self, ipv6_address: impl Into<String>) -> Self {
+        self.inner.ipv6_address = Some(ipv6_address.into());
+        self
+    }
+
+    /// Set the [`link_local_ips`](EndpointIpamConfig::link_local_ips) field.
+    pub fn link_local_ips(mut self, link_local_ips: impl Into<Vec<String>>) -> Self {
+        self.inner.link_local_ips = Some(link_local_ips.into());
+        self
+    }
+
+    /// Build the [EndpointIpamConfig](EndpointIpamConfig).
+    pub fn build(self) -> EndpointIpamConfig {
+        self.inner
+    }
+}
+
+impl From<EndpointIpamConfigBuilder> for EndpointIpamConfig {
+    fn from(builder: EndpointIpamConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct EndpointPortConfig {
     #[serde(rename = "Name")]
@@ -1621,6 +2095,7 @@ pub struct EndpointPortConfig {
 
     #[serde(rename = "Protocol")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub protocol: Option<EndpointPortConfigProtocolEnum>,
 
     /// The port inside the container.
@@ -1636,10 +2111,67 @@ pub struct EndpointPortConfig {
     /// The mode in which port is published.  <p><br /></p>  - \"ingress\" makes the target port accessible on every node,   regardless of whether there is a task for the service running on   that node or not. - \"host\" bypasses the routing mesh and publish the port directly on   the swarm node where that service is running. 
     #[serde(rename = "PublishMode")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub publish_mode: Option<EndpointPortConfigPublishModeEnum>,
 
 }
 
+impl EndpointPortConfig {
+    /// Start building a [EndpointPortConfig](EndpointPortConfig), with every field unset.
+    pub fn builder() -> EndpointPortConfigBuilder {
+        EndpointPortConfigBuilder::default()
+    }
+}
+
+/// Builder for [EndpointPortConfig](EndpointPortConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointPortConfigBuilder {
+    inner: EndpointPortConfig,
+}
+
+impl EndpointPortConfigBuilder {
+    /// Set the [`name`](EndpointPortConfig::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`protocol`](EndpointPortConfig::protocol) field.
+    pub fn protocol(mut self, protocol: impl Into<EndpointPortConfigProtocolEnum>) -> Self {
+        self.inner.protocol = Some(protocol.into());
+        self
+    }
+
+    /// Set the [`target_port`](EndpointPortConfig::target_port) field.
+    pub fn target_port(mut self, target_port: impl Into<i64>) -> Self {
+        self.inner.target_port = Some(target_port.into());
+        self
+    }
+
+    /// Set the [`published_port`](EndpointPortConfig::published_port) field.
+    pub fn published_port(mut self, published_port: impl Into<i64>) -> Self {
+        self.inner.published_port = Some(published_port.into());
+        self
+    }
+
+    /// Set the [`publish_mode`](EndpointPortConfig::publish_mode) field.
+    pub fn publish_mode(mut self, publish_mode: impl Into<EndpointPortConfigPublishModeEnum>) -> Self {
+        self.inner.publish_mode = Some(publish_mode.into());
+        self
+    }
+
+    /// Build the [EndpointPortConfig](EndpointPortConfig).
+    pub fn build(self) -> EndpointPortConfig {
+        self.inner
+    }
+}
+
+impl From<EndpointPortConfigBuilder> for EndpointPortConfig {
+    fn from(builder: EndpointPortConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum EndpointPortConfigProtocolEnum { 
@@ -1746,7 +2278,7 @@ pub struct EndpointSettings {
 
     /// MAC address for the endpoint on this network. The network driver might ignore this parameter. 
     #[serde(rename = "MacAddress")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_endpoint_settings_mac_address")]
     pub mac_address: Option<String>,
 
     #[serde(rename = "Aliases")]
@@ -1805,12 +2337,127 @@ pub struct EndpointSettings {
 
 }
 
+impl EndpointSettings {
+    /// Start building a [EndpointSettings](EndpointSettings), with every field unset.
+    pub fn builder() -> EndpointSettingsBuilder {
+        EndpointSettingsBuilder::default()
+    }
+}
+
+/// Builder for [EndpointSettings](EndpointSettings).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointSettingsBuilder {
+    inner: EndpointSettings,
+}
+
+impl EndpointSettingsBuilder {
+    /// Set the [`ipam_config`](EndpointSettings::ipam_config) field.
+    pub fn ipam_config(mut self, ipam_config: impl Into<EndpointIpamConfig>) -> Self {
+        self.inner.ipam_config = Some(ipam_config.into());
+        self
+    }
+
+    /// Set the [`links`](EndpointSettings::links) field.
+    pub fn links(mut self, links: impl Into<Vec<String>>) -> Self {
+        self.inner.links = Some(links.into());
+        self
+    }
+
+    /// Set the [`mac_address`](EndpointSettings::mac_address) field.
+    pub fn mac_address(mut self, mac_address: impl Into<String>) -> Self {
+        self.inner.mac_address = Some(mac_address.into());
+        self
+    }
+
+    /// Set the [`aliases`](EndpointSettings::aliases) field.
+    pub fn aliases(mut self, aliases: impl Into<Vec<String>>) -> Self {
+        self.inner.aliases = Some(aliases.into());
+        self
+    }
+
+    /// Set the [`driver_opts`](EndpointSettings::driver_opts) field.
+    pub fn driver_opts(mut self, driver_opts: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.driver_opts = Some(driver_opts.into());
+        self
+    }
+
+    /// Set the [`network_id`](EndpointSettings::network_id) field.
+    pub fn network_id(mut self, network_id: impl Into<String>) -> Self {
+        self.inner.network_id = Some(network_id.into());
+        self
+    }
+
+    /// Set the [`endpoint_id`](EndpointSettings::endpoint_id) field.
+    pub fn endpoint_id(mut self, endpoint_id: impl Into<String>) -> Self {
+        self.inner.endpoint_id = Some(endpoint_id.into());
+        self
+    }
+
+    /// Set the [`gateway`](EndpointSettings::gateway) field.
+    pub fn gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.inner.gateway = Some(gateway.into());
+        self
+    }
+
+    /// Set the [`ip_address`](EndpointSettings::ip_address) field.
+    pub fn ip_address(mut self, ip_address: impl Into<String>) -> Self {
+        self.inner.ip_address = Some(ip_address.into());
+        self
+    }
+
+    /// Set the [`ip_prefix_len`](EndpointSettings::ip_prefix_len) field.
+    pub fn ip_prefix_len(mut self, ip_prefix_len: impl Into<i64>) -> Self {
+        self.inner.ip_prefix_len = Some(ip_prefix_len.into());
+        self
+    }
+
+    /// Set the [`ipv6_gateway`](EndpointSettings::ipv6_gateway) field.
+    pub fn ipv6_gateway(mut self, ipv6_gateway: impl Into<String>) -> Self {
+        self.inner.ipv6_gateway = Some(ipv6_gateway.into());
+        self
+    }
+
+    /// Set the [`global_ipv6_address`](EndpointSettings::global_ipv6_address) field.
+    pub fn global_ipv6_address(mut self, global_ipv6_address: impl Into<String>) -> Self {
+        self.inner.global_ipv6_address = Some(global_ipv6_address.into());
+        self
+    }
+
+    /// Set the [`global_ipv6_prefix_len`](EndpointSettings::global_ipv6_prefix_len) field.
+    pub fn global_ipv6_prefix_len(mut self, global_ipv6_prefix_len: impl Into<i64>) -> Self {
+        self.inner.global_ipv6_prefix_len = Some(global_ipv6_prefix_len.into());
+        self
+    }
+
+    /// Set the [`dns_names`](EndpointSettings::dns_names) field.
+    pub fn dns_names(mut self, dns_names: impl Into<Vec<String>>) -> Self {
+        self.inner.dns_names = Some(dns_names.into());
+        self
+    }
+
+    /// Build the [EndpointSettings](EndpointSettings).
+    pub fn build(self) -> EndpointSettings {
+        self.inner
+    }
+}
+
+impl From<EndpointSettingsBuilder> for EndpointSettings {
+    fn from(builder: EndpointSettingsBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn skip_endpoint_settings_mac_address<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "EndpointSettings.MacAddress", (1, 44))
+}
+
 /// Properties that can be configured to access and load balance a service.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct EndpointSpec {
     /// The mode of resolution to use for internal load balancing between tasks. 
     #[serde(rename = "Mode")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub mode: Option<EndpointSpecModeEnum>,
 
     /// List of exposed ports that this service is accessible on from the outside. Ports can only be provided if `vip` resolution mode is used. 
@@ -1820,6 +2467,44 @@ pub struct EndpointSpec {
 
 }
 
+impl EndpointSpec {
+    /// Start building a [EndpointSpec](EndpointSpec), with every field unset.
+    pub fn builder() -> EndpointSpecBuilder {
+        EndpointSpecBuilder::default()
+    }
+}
+
+/// Builder for [EndpointSpec](EndpointSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointSpecBuilder {
+    inner: EndpointSpec,
+}
+
+impl EndpointSpecBuilder {
+    /// Set the [`mode`](EndpointSpec::mode) field.
+    pub fn mode(mut self, mode: impl Into<EndpointSpecModeEnum>) -> Self {
+        self.inner.mode = Some(mode.into());
+        self
+    }
+
+    /// Set the [`ports`](EndpointSpec::ports) field.
+    pub fn ports(mut self, ports: impl Into<Vec<EndpointPortConfig>>) -> Self {
+        self.inner.ports = Some(ports.into());
+        self
+    }
+
+    /// Build the [EndpointSpec](EndpointSpec).
+    pub fn build(self) -> EndpointSpec {
+        self.inner
+    }
+}
+
+impl From<EndpointSpecBuilder> for EndpointSpec {
+    fn from(builder: EndpointSpecBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum EndpointSpecModeEnum { 
@@ -1935,6 +2620,7 @@ pub struct EventMessage {
     /// The type of object emitting the event
     #[serde(rename = "Type")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub typ: Option<EventMessageTypeEnum>,
 
     /// The type of event
@@ -1949,6 +2635,7 @@ pub struct EventMessage {
     /// Scope of the event. Engine events are `local` scope. Cluster (Swarm) events are `swarm` scope. 
     #[serde(rename = "scope")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub scope: Option<EventMessageScopeEnum>,
 
     /// Timestamp of event
@@ -2281,6 +2968,7 @@ pub struct Health {
     /// Status is one of `none`, `starting`, `healthy` or `unhealthy`  - \"none\"      Indicates there is no healthcheck - \"starting\"  Starting indicates that the container is not yet ready - \"healthy\"   Healthy indicates that the container is running correctly - \"unhealthy\" Unhealthy indicates that the container has a problem 
     #[serde(rename = "Status")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub status: Option<HealthStatusEnum>,
 
     /// FailingStreak is the number of consecutive failures
@@ -2379,11 +3067,77 @@ pub struct HealthConfig {
 
     /// The time to wait between checks in nanoseconds during the start period. It should be 0 or at least 1000000 (1 ms). 0 means inherit. 
     #[serde(rename = "StartInterval")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_health_config_start_interval")]
     pub start_interval: Option<i64>,
 
 }
 
+impl HealthConfig {
+    /// Start building a [HealthConfig](HealthConfig), with every field unset.
+    pub fn builder() -> HealthConfigBuilder {
+        HealthConfigBuilder::default()
+    }
+}
+
+/// Builder for [HealthConfig](HealthConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealthConfigBuilder {
+    inner: HealthConfig,
+}
+
+impl HealthConfigBuilder {
+    /// Set the [`test`](HealthConfig::test) field.
+    pub fn test(mut self, test: impl Into<Vec<String>>) -> Self {
+        self.inner.test = Some(test.into());
+        self
+    }
+
+    /// Set the [`interval`](HealthConfig::interval) field.
+    pub fn interval(mut self, interval: impl Into<i64>) -> Self {
+        self.inner.interval = Some(interval.into());
+        self
+    }
+
+    /// Set the [`timeout`](HealthConfig::timeout) field.
+    pub fn timeout(mut self, timeout: impl Into<i64>) -> Self {
+        self.inner.timeout = Some(timeout.into());
+        self
+    }
+
+    /// Set the [`retries`](HealthConfig::retries) field.
+    pub fn retries(mut self, retries: impl Into<i64>) -> Self {
+        self.inner.retries = Some(retries.into());
+        self
+    }
+
+    /// Set the [`start_period`](HealthConfig::start_period) field.
+    pub fn start_period(mut self, start_period: impl Into<i64>) -> Self {
+        self.inner.start_period = Some(start_period.into());
+        self
+    }
+
+    /// Set the [`start_interval`](HealthConfig::start_interval) field.
+    pub fn start_interval(mut self, start_interval: impl Into<i64>) -> Self {
+        self.inner.start_interval = Some(start_interval.into());
+        self
+    }
+
+    /// Build the [HealthConfig](HealthConfig).
+    pub fn build(self) -> HealthConfig {
+        self.inner
+    }
+}
+
+impl From<HealthConfigBuilder> for HealthConfig {
+    fn from(builder: HealthConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn skip_health_config_start_interval<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "HealthConfig.StartInterval", (1, 44))
+}
+
 /// HealthcheckResult stores information about a single run of a healthcheck probe 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct HealthcheckResult {
@@ -2650,12 +3404,12 @@ pub struct HostConfig {
 
     /// Initial console size, as an `[height, width]` array. 
     #[serde(rename = "ConsoleSize")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_host_config_console_size")]
     pub console_size: Option<Vec<i32>>,
 
     /// Arbitrary non-identifying metadata attached to container and provided to the runtime when the container is started. 
     #[serde(rename = "Annotations")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_host_config_annotations")]
     pub annotations: Option<HashMap<String, String>>,
 
     /// A list of kernel capabilities to add to the container. Conflicts with option 'Capabilities'. 
@@ -2670,7 +3424,8 @@ pub struct HostConfig {
 
     /// cgroup namespace mode for the container. Possible values are:  - `\"private\"`: the container runs in its own private cgroup namespace - `\"host\"`: use the host system's cgroup namespace  If not specified, the daemon default is used, which can either be `\"private\"` or `\"host\"`, depending on daemon version, kernel support and configuration. 
     #[serde(rename = "CgroupnsMode")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_host_config_cgroupns_mode")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub cgroupns_mode: Option<HostConfigCgroupnsModeEnum>,
 
     /// A list of DNS servers for the container to use.
@@ -2781,6 +3536,7 @@ pub struct HostConfig {
     /// Isolation technology of the container. (Windows only) 
     #[serde(rename = "Isolation")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub isolation: Option<HostConfigIsolationEnum>,
 
     /// The list of paths to be masked inside the container (this overrides the default set of paths). 
@@ -2795,11 +3551,469 @@ pub struct HostConfig {
 
 }
 
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
-pub enum HostConfigCgroupnsModeEnum { 
-    #[serde(rename = "")]
-    EMPTY,
+impl HostConfig {
+    /// Start building a [HostConfig](HostConfig), with every field unset.
+    pub fn builder() -> HostConfigBuilder {
+        HostConfigBuilder::default()
+    }
+}
+
+/// Builder for [HostConfig](HostConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostConfigBuilder {
+    inner: HostConfig,
+}
+
+impl HostConfigBuilder {
+    /// Set the [`cpu_shares`](HostConfig::cpu_shares) field.
+    pub fn cpu_shares(mut self, cpu_shares: impl Into<i64>) -> Self {
+        self.inner.cpu_shares = Some(cpu_shares.into());
+        self
+    }
+
+    /// Set the [`memory`](HostConfig::memory) field.
+    pub fn memory(mut self, memory: impl Into<i64>) -> Self {
+        self.inner.memory = Some(memory.into());
+        self
+    }
+
+    /// Set the [`cgroup_parent`](HostConfig::cgroup_parent) field.
+    pub fn cgroup_parent(mut self, cgroup_parent: impl Into<String>) -> Self {
+        self.inner.cgroup_parent = Some(cgroup_parent.into());
+        self
+    }
+
+    /// Set the [`blkio_weight`](HostConfig::blkio_weight) field.
+    pub fn blkio_weight(mut self, blkio_weight: impl Into<u16>) -> Self {
+        self.inner.blkio_weight = Some(blkio_weight.into());
+        self
+    }
+
+    /// Set the [`blkio_weight_device`](HostConfig::blkio_weight_device) field.
+    pub fn blkio_weight_device(mut self, blkio_weight_device: impl Into<Vec<ResourcesBlkioWeightDevice>>) -> Self {
+        self.inner.blkio_weight_device = Some(blkio_weight_device.into());
+        self
+    }
+
+    /// Set the [`blkio_device_read_bps`](HostConfig::blkio_device_read_bps) field.
+    pub fn blkio_device_read_bps(mut self, blkio_device_read_bps: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_read_bps = Some(blkio_device_read_bps.into());
+        self
+    }
+
+    /// Set the [`blkio_device_write_bps`](HostConfig::blkio_device_write_bps) field.
+    pub fn blkio_device_write_bps(mut self, blkio_device_write_bps: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_write_bps = Some(blkio_device_write_bps.into());
+        self
+    }
+
+    /// Set the [`blkio_device_read_iops`](HostConfig::blkio_device_read_iops) field.
+    pub fn blkio_device_read_iops(mut self, blkio_device_read_iops: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_read_iops = Some(blkio_device_read_iops.into());
+        self
+    }
+
+    /// Set the [`blkio_device_write_iops`](HostConfig::blkio_device_write_iops) field.
+    pub fn blkio_device_write_iops(mut self, blkio_device_write_iops: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_write_iops = Some(blkio_device_write_iops.into());
+        self
+    }
+
+    /// Set the [`cpu_period`](HostConfig::cpu_period) field.
+    pub fn cpu_period(mut self, cpu_period: impl Into<i64>) -> Self {
+        self.inner.cpu_period = Some(cpu_period.into());
+        self
+    }
+
+    /// Set the [`cpu_quota`](HostConfig::cpu_quota) field.
+    pub fn cpu_quota(mut self, cpu_quota: impl Into<i64>) -> Self {
+        self.inner.cpu_quota = Some(cpu_quota.into());
+        self
+    }
+
+    /// Set the [`cpu_realtime_period`](HostConfig::cpu_realtime_period) field.
+    pub fn cpu_realtime_period(mut self, cpu_realtime_period: impl Into<i64>) -> Self {
+        self.inner.cpu_realtime_period = Some(cpu_realtime_period.into());
+        self
+    }
+
+    /// Set the [`cpu_realtime_runtime`](HostConfig::cpu_realtime_runtime) field.
+    pub fn cpu_realtime_runtime(mut self, cpu_realtime_runtime: impl Into<i64>) -> Self {
+        self.inner.cpu_realtime_runtime = Some(cpu_realtime_runtime.into());
+        self
+    }
+
+    /// Set the [`cpuset_cpus`](HostConfig::cpuset_cpus) field.
+    pub fn cpuset_cpus(mut self, cpuset_cpus: impl Into<String>) -> Self {
+        self.inner.cpuset_cpus = Some(cpuset_cpus.into());
+        self
+    }
+
+    /// Set the [`cpuset_mems`](HostConfig::cpuset_mems) field.
+    pub fn cpuset_mems(mut self, cpuset_mems: impl Into<String>) -> Self {
+        self.inner.cpuset_mems = Some(cpuset_mems.into());
+        self
+    }
+
+    /// Set the [`devices`](HostConfig::devices) field.
+    pub fn devices(mut self, devices: impl Into<Vec<DeviceMapping>>) -> Self {
+        self.inner.devices = Some(devices.into());
+        self
+    }
+
+    /// Set the [`device_cgroup_rules`](HostConfig::device_cgroup_rules) field.
+    pub fn device_cgroup_rules(mut self, device_cgroup_rules: impl Into<Vec<String>>) -> Self {
+        self.inner.device_cgroup_rules = Some(device_cgroup_rules.into());
+        self
+    }
+
+    /// Set the [`device_requests`](HostConfig::device_requests) field.
+    pub fn device_requests(mut self, device_requests: impl Into<Vec<DeviceRequest>>) -> Self {
+        self.inner.device_requests = Some(device_requests.into());
+        self
+    }
+
+    /// Set the [`kernel_memory_tcp`](HostConfig::kernel_memory_tcp) field.
+    pub fn kernel_memory_tcp(mut self, kernel_memory_tcp: impl Into<i64>) -> Self {
+        self.inner.kernel_memory_tcp = Some(kernel_memory_tcp.into());
+        self
+    }
+
+    /// Set the [`memory_reservation`](HostConfig::memory_reservation) field.
+    pub fn memory_reservation(mut self, memory_reservation: impl Into<i64>) -> Self {
+        self.inner.memory_reservation = Some(memory_reservation.into());
+        self
+    }
+
+    /// Set the [`memory_swap`](HostConfig::memory_swap) field.
+    pub fn memory_swap(mut self, memory_swap: impl Into<i64>) -> Self {
+        self.inner.memory_swap = Some(memory_swap.into());
+        self
+    }
+
+    /// Set the [`memory_swappiness`](HostConfig::memory_swappiness) field.
+    pub fn memory_swappiness(mut self, memory_swappiness: impl Into<i64>) -> Self {
+        self.inner.memory_swappiness = Some(memory_swappiness.into());
+        self
+    }
+
+    /// Set the [`nano_cpus`](HostConfig::nano_cpus) field.
+    pub fn nano_cpus(mut self, nano_cpus: impl Into<i64>) -> Self {
+        self.inner.nano_cpus = Some(nano_cpus.into());
+        self
+    }
+
+    /// Set the [`oom_kill_disable`](HostConfig::oom_kill_disable) field.
+    pub fn oom_kill_disable(mut self, oom_kill_disable: impl Into<bool>) -> Self {
+        self.inner.oom_kill_disable = Some(oom_kill_disable.into());
+        self
+    }
+
+    /// Set the [`init`](HostConfig::init) field.
+    pub fn init(mut self, init: impl Into<bool>) -> Self {
+        self.inner.init = Some(init.into());
+        self
+    }
+
+    /// Set the [`pids_limit`](HostConfig::pids_limit) field.
+    pub fn pids_limit(mut self, pids_limit: impl Into<i64>) -> Self {
+        self.inner.pids_limit = Some(pids_limit.into());
+        self
+    }
+
+    /// Set the [`ulimits`](HostConfig::ulimits) field.
+    pub fn ulimits(mut self, ulimits: impl Into<Vec<ResourcesUlimits>>) -> Self {
+        self.inner.ulimits = Some(ulimits.into());
+        self
+    }
+
+    /// Set the [`cpu_count`](HostConfig::cpu_count) field.
+    pub fn cpu_count(mut self, cpu_count: impl Into<i64>) -> Self {
+        self.inner.cpu_count = Some(cpu_count.into());
+        self
+    }
+
+    /// Set the [`cpu_percent`](HostConfig::cpu_percent) field.
+    pub fn cpu_percent(mut self, cpu_percent: impl Into<i64>) -> Self {
+        self.inner.cpu_percent = Some(cpu_percent.into());
+        self
+    }
+
+    /// Set the [`io_maximum_iops`](HostConfig::io_maximum_iops) field.
+    pub fn io_maximum_iops(mut self, io_maximum_iops: impl Into<i64>) -> Self {
+        self.inner.io_maximum_iops = Some(io_maximum_iops.into());
+        self
+    }
+
+    /// Set the [`io_maximum_bandwidth`](HostConfig::io_maximum_bandwidth) field.
+    pub fn io_maximum_bandwidth(mut self, io_maximum_bandwidth: impl Into<i64>) -> Self {
+        self.inner.io_maximum_bandwidth = Some(io_maximum_bandwidth.into());
+        self
+    }
+
+    /// Set the [`binds`](HostConfig::binds) field.
+    pub fn binds(mut self, binds: impl Into<Vec<String>>) -> Self {
+        self.inner.binds = Some(binds.into());
+        self
+    }
+
+    /// Set the [`container_id_file`](HostConfig::container_id_file) field.
+    pub fn container_id_file(mut self, container_id_file: impl Into<String>) -> Self {
+        self.inner.container_id_file = Some(container_id_file.into());
+        self
+    }
+
+    /// Set the [`log_config`](HostConfig::log_config) field.
+    pub fn log_config(mut self, log_config: impl Into<HostConfigLogConfig>) -> Self {
+        self.inner.log_config = Some(log_config.into());
+        self
+    }
+
+    /// Set the [`network_mode`](HostConfig::network_mode) field.
+    pub fn network_mode(mut self, network_mode: impl Into<String>) -> Self {
+        self.inner.network_mode = Some(network_mode.into());
+        self
+    }
+
+    /// Set the [`port_bindings`](HostConfig::port_bindings) field.
+    pub fn port_bindings(mut self, port_bindings: impl Into<PortMap>) -> Self {
+        self.inner.port_bindings = Some(port_bindings.into());
+        self
+    }
+
+    /// Set the [`restart_policy`](HostConfig::restart_policy) field.
+    pub fn restart_policy(mut self, restart_policy: impl Into<RestartPolicy>) -> Self {
+        self.inner.restart_policy = Some(restart_policy.into());
+        self
+    }
+
+    /// Set the [`auto_remove`](HostConfig::auto_remove) field.
+    pub fn auto_remove(mut self, auto_remove: impl Into<bool>) -> Self {
+        self.inner.auto_remove = Some(auto_remove.into());
+        self
+    }
+
+    /// Set the [`volume_driver`](HostConfig::volume_driver) field.
+    pub fn volume_driver(mut self, volume_driver: impl Into<String>) -> Self {
+        self.inner.volume_driver = Some(volume_driver.into());
+        self
+    }
+
+    /// Set the [`volumes_from`](HostConfig::volumes_from) field.
+    pub fn volumes_from(mut self, volumes_from: impl Into<Vec<String>>) -> Self {
+        self.inner.volumes_from = Some(volumes_from.into());
+        self
+    }
+
+    /// Set the [`mounts`](HostConfig::mounts) field.
+    pub fn mounts(mut self, mounts: impl Into<Vec<Mount>>) -> Self {
+        self.inner.mounts = Some(mounts.into());
+        self
+    }
+
+    /// Set the [`console_size`](HostConfig::console_size) field.
+    pub fn console_size(mut self, console_size: impl Into<Vec<i32>>) -> Self {
+        self.inner.console_size = Some(console_size.into());
+        self
+    }
+
+    /// Set the [`annotations`](HostConfig::annotations) field.
+    pub fn annotations(mut self, annotations: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.annotations = Some(annotations.into());
+        self
+    }
+
+    /// Set the [`cap_add`](HostConfig::cap_add) field.
+    pub fn cap_add(mut self, cap_add: impl Into<Vec<String>>) -> Self {
+        self.inner.cap_add = Some(cap_add.into());
+        self
+    }
+
+    /// Set the [`cap_drop`](HostConfig::cap_drop) field.
+    pub fn cap_drop(mut self, cap_drop: impl Into<Vec<String>>) -> Self {
+        self.inner.cap_drop = Some(cap_drop.into());
+        self
+    }
+
+    /// Set the [`cgroupns_mode`](HostConfig::cgroupns_mode) field.
+    pub fn cgroupns_mode(mut self, cgroupns_mode: impl Into<HostConfigCgroupnsModeEnum>) -> Self {
+        self.inner.cgroupns_mode = Some(cgroupns_mode.into());
+        self
+    }
+
+    /// Set the [`dns`](HostConfig::dns) field.
+    pub fn dns(mut self, dns: impl Into<Vec<String>>) -> Self {
+        self.inner.dns = Some(dns.into());
+        self
+    }
+
+    /// Set the [`dns_options`](HostConfig::dns_options) field.
+    pub fn dns_options(mut self, dns_options: impl Into<Vec<String>>) -> Self {
+        self.inner.dns_options = Some(dns_options.into());
+        self
+    }
+
+    /// Set the [`dns_search`](HostConfig::dns_search) field.
+    pub fn dns_search(mut self, dns_search: impl Into<Vec<String>>) -> Self {
+        self.inner.dns_search = Some(dns_search.into());
+        self
+    }
+
+    /// Set the [`extra_hosts`](HostConfig::extra_hosts) field.
+    pub fn extra_hosts(mut self, extra_hosts: impl Into<Vec<String>>) -> Self {
+        self.inner.extra_hosts = Some(extra_hosts.into());
+        self
+    }
+
+    /// Set the [`group_add`](HostConfig::group_add) field.
+    pub fn group_add(mut self, group_add: impl Into<Vec<String>>) -> Self {
+        self.inner.group_add = Some(group_add.into());
+        self
+    }
+
+    /// Set the [`ipc_mode`](HostConfig::ipc_mode) field.
+    pub fn ipc_mode(mut self, ipc_mode: impl Into<String>) -> Self {
+        self.inner.ipc_mode = Some(ipc_mode.into());
+        self
+    }
+
+    /// Set the [`cgroup`](HostConfig::cgroup) field.
+    pub fn cgroup(mut self, cgroup: impl Into<String>) -> Self {
+        self.inner.cgroup = Some(cgroup.into());
+        self
+    }
+
+    /// Set the [`links`](HostConfig::links) field.
+    pub fn links(mut self, links: impl Into<Vec<String>>) -> Self {
+        self.inner.links = Some(links.into());
+        self
+    }
+
+    /// Set the [`oom_score_adj`](HostConfig::oom_score_adj) field.
+    pub fn oom_score_adj(mut self, oom_score_adj: impl Into<i64>) -> Self {
+        self.inner.oom_score_adj = Some(oom_score_adj.into());
+        self
+    }
+
+    /// Set the [`pid_mode`](HostConfig::pid_mode) field.
+    pub fn pid_mode(mut self, pid_mode: impl Into<String>) -> Self {
+        self.inner.pid_mode = Some(pid_mode.into());
+        self
+    }
+
+    /// Set the [`privileged`](HostConfig::privileged) field.
+    pub fn privileged(mut self, privileged: impl Into<bool>) -> Self {
+        self.inner.privileged = Some(privileged.into());
+        self
+    }
+
+    /// Set the [`publish_all_ports`](HostConfig::publish_all_ports) field.
+    pub fn publish_all_ports(mut self, publish_all_ports: impl Into<bool>) -> Self {
+        self.inner.publish_all_ports = Some(publish_all_ports.into());
+        self
+    }
+
+    /// Set the [`readonly_rootfs`](HostConfig::readonly_rootfs) field.
+    pub fn readonly_rootfs(mut self, readonly_rootfs: impl Into<bool>) -> Self {
+        self.inner.readonly_rootfs = Some(readonly_rootfs.into());
+        self
+    }
+
+    /// Set the [`security_opt`](HostConfig::security_opt) field.
+    pub fn security_opt(mut self, security_opt: impl Into<Vec<String>>) -> Self {
+        self.inner.security_opt = Some(security_opt.into());
+        self
+    }
+
+    /// Set the [`storage_opt`](HostConfig::storage_opt) field.
+    pub fn storage_opt(mut self, storage_opt: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.storage_opt = Some(storage_opt.into());
+        self
+    }
+
+    /// Set the [`tmpfs`](HostConfig::tmpfs) field.
+    pub fn tmpfs(mut self, tmpfs: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.tmpfs = Some(tmpfs.into());
+        self
+    }
+
+    /// Set the [`uts_mode`](HostConfig::uts_mode) field.
+    pub fn uts_mode(mut self, uts_mode: impl Into<String>) -> Self {
+        self.inner.uts_mode = Some(uts_mode.into());
+        self
+    }
+
+    /// Set the [`userns_mode`](HostConfig::userns_mode) field.
+    pub fn userns_mode(mut self, userns_mode: impl Into<String>) -> Self {
+        self.inner.userns_mode = Some(userns_mode.into());
+        self
+    }
+
+    /// Set the [`shm_size`](HostConfig::shm_size) field.
+    pub fn shm_size(mut self, shm_size: impl Into<i64>) -> Self {
+        self.inner.shm_size = Some(shm_size.into());
+        self
+    }
+
+    /// Set the [`sysctls`](HostConfig::sysctls) field.
+    pub fn sysctls(mut self, sysctls: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.sysctls = Some(sysctls.into());
+        self
+    }
+
+    /// Set the [`runtime`](HostConfig::runtime) field.
+    pub fn runtime(mut self, runtime: impl Into<String>) -> Self {
+        self.inner.runtime = Some(runtime.into());
+        self
+    }
+
+    /// Set the [`isolation`](HostConfig::isolation) field.
+    pub fn isolation(mut self, isolation: impl Into<HostConfigIsolationEnum>) -> Self {
+        self.inner.isolation = Some(isolation.into());
+        self
+    }
+
+    /// Set the [`masked_paths`](HostConfig::masked_paths) field.
+    pub fn masked_paths(mut self, masked_paths: impl Into<Vec<String>>) -> Self {
+        self.inner.masked_paths = Some(masked_paths.into());
+        self
+    }
+
+    /// Set the [`readonly_paths`](HostConfig::readonly_paths) field.
+    pub fn readonly_paths(mut self, readonly_paths: impl Into<Vec<String>>) -> Self {
+        self.inner.readonly_paths = Some(readonly_paths.into());
+        self
+    }
+
+    /// Build the [HostConfig](HostConfig).
+    pub fn build(self) -> HostConfig {
+        self.inner
+    }
+}
+
+impl From<HostConfigBuilder> for HostConfig {
+    fn from(builder: HostConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn skip_host_config_cgroupns_mode<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "HostConfig.CgroupnsMode", (1, 41))
+}
+
+fn skip_host_config_console_size<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "HostConfig.ConsoleSize", (1, 42))
+}
+
+fn skip_host_config_annotations<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "HostConfig.Annotations", (1, 43))
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
+pub enum HostConfigCgroupnsModeEnum { 
+    #[serde(rename = "")]
+    EMPTY,
     #[serde(rename = "private")]
     PRIVATE,
     #[serde(rename = "host")]
@@ -2901,6 +4115,44 @@ pub struct HostConfigLogConfig {
 
 }
 
+impl HostConfigLogConfig {
+    /// Start building a [HostConfigLogConfig](HostConfigLogConfig), with every field unset.
+    pub fn builder() -> HostConfigLogConfigBuilder {
+        HostConfigLogConfigBuilder::default()
+    }
+}
+
+/// Builder for [HostConfigLogConfig](HostConfigLogConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostConfigLogConfigBuilder {
+    inner: HostConfigLogConfig,
+}
+
+impl HostConfigLogConfigBuilder {
+    /// Set the [`typ`](HostConfigLogConfig::typ) field.
+    pub fn typ(mut self, typ: impl Into<String>) -> Self {
+        self.inner.typ = Some(typ.into());
+        self
+    }
+
+    /// Set the [`config`](HostConfigLogConfig::config) field.
+    pub fn config(mut self, config: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.config = Some(config.into());
+        self
+    }
+
+    /// Build the [HostConfigLogConfig](HostConfigLogConfig).
+    pub fn build(self) -> HostConfigLogConfig {
+        self.inner
+    }
+}
+
+impl From<HostConfigLogConfigBuilder> for HostConfigLogConfig {
+    fn from(builder: HostConfigLogConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Response to an API call that returns just an Id
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -3071,6 +4323,16 @@ pub struct ImageInspect {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 
+    /// Descriptor is an OCI descriptor of the image target. In case of a multi-platform image, this descriptor points to the OCI index or a manifest list.  This field is only present if the daemon provides a multi-platform image store.
+    #[serde(rename = "Descriptor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptor: Option<OciDescriptor>,
+
+    /// Manifests is a list of image manifests available in this image. It provides a more detailed view of the platform-specific image manifests or other image-attached data like build attestations.  Only available if the daemon provides a multi-platform image store.
+    #[serde(rename = "Manifests")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifests: Option<Vec<ImageManifestSummary>>,
+
     /// List of image names/tags in the local image cache that reference this image.  Multiple image tags can refer to the same image, and this list may be empty if no tags reference the image, in which case the image is \"untagged\", in which case it can still be referenced by its ID. 
     #[serde(rename = "RepoTags")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -3206,7 +4468,10 @@ pub struct ImageManifestSummary {
     /// The kind of the manifest.  kind         | description -------------|----------------------------------------------------------- image        | Image manifest that can be used to start a container. attestation  | Attestation manifest produced by the Buildkit builder for a specific image manifest. 
     #[serde(rename = "Kind")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(with = "::serde_with::As::<::serde_with::NoneAsEmptyString>")]
+    #[serde(
+        serialize_with = "::serde_with::As::<::serde_with::NoneAsEmptyString>::serialize",
+        deserialize_with = "deserialize_nonempty_lenient_enum"
+    )]
     pub kind: Option<ImageManifestSummaryKindEnum>,
 
     #[serde(rename = "ImageData")]
@@ -3450,6 +4715,50 @@ pub struct Ipam {
 
 }
 
+impl Ipam {
+    /// Start building a [Ipam](Ipam), with every field unset.
+    pub fn builder() -> IpamBuilder {
+        IpamBuilder::default()
+    }
+}
+
+/// Builder for [Ipam](Ipam).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IpamBuilder {
+    inner: Ipam,
+}
+
+impl IpamBuilder {
+    /// Set the [`driver`](Ipam::driver) field.
+    pub fn driver(mut self, driver: impl Into<String>) -> Self {
+        self.inner.driver = Some(driver.into());
+        self
+    }
+
+    /// Set the [`config`](Ipam::config) field.
+    pub fn config(mut self, config: impl Into<Vec<IpamConfig>>) -> Self {
+        self.inner.config = Some(config.into());
+        self
+    }
+
+    /// Set the [`options`](Ipam::options) field.
+    pub fn options(mut self, options: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.options = Some(options.into());
+        self
+    }
+
+    /// Build the [Ipam](Ipam).
+    pub fn build(self) -> Ipam {
+        self.inner
+    }
+}
+
+impl From<IpamBuilder> for Ipam {
+    fn from(builder: IpamBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct IpamConfig {
     #[serde(rename = "Subnet")]
@@ -3470,6 +4779,56 @@ pub struct IpamConfig {
 
 }
 
+impl IpamConfig {
+    /// Start building a [IpamConfig](IpamConfig), with every field unset.
+    pub fn builder() -> IpamConfigBuilder {
+        IpamConfigBuilder::default()
+    }
+}
+
+/// Builder for [IpamConfig](IpamConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IpamConfigBuilder {
+    inner: IpamConfig,
+}
+
+impl IpamConfigBuilder {
+    /// Set the [`subnet`](IpamConfig::subnet) field.
+    pub fn subnet(mut self, subnet: impl Into<String>) -> Self {
+        self.inner.subnet = Some(subnet.into());
+        self
+    }
+
+    /// Set the [`ip_range`](IpamConfig::ip_range) field.
+    pub fn ip_range(mut self, ip_range: impl Into<String>) -> Self {
+        self.inner.ip_range = Some(ip_range.into());
+        self
+    }
+
+    /// Set the [`gateway`](IpamConfig::gateway) field.
+    pub fn gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.inner.gateway = Some(gateway.into());
+        self
+    }
+
+    /// Set the [`auxiliary_addresses`](IpamConfig::auxiliary_addresses) field.
+    pub fn auxiliary_addresses(mut self, auxiliary_addresses: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.auxiliary_addresses = Some(auxiliary_addresses.into());
+        self
+    }
+
+    /// Build the [IpamConfig](IpamConfig).
+    pub fn build(self) -> IpamConfig {
+        self.inner
+    }
+}
+
+impl From<IpamConfigBuilder> for IpamConfig {
+    fn from(builder: IpamConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// JoinTokens contains the tokens workers and managers need to join the swarm. 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct JoinTokens {
@@ -3568,6 +4927,7 @@ pub struct ManagerStatus {
 
     #[serde(rename = "Reachability")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub reachability: Option<Reachability>,
 
     /// The IP address and port at which the manager is reachable. 
@@ -3592,6 +4952,7 @@ pub struct Mount {
     /// The mount type. Available types:  - `bind` Mounts a file or directory from the host into the container. Must exist prior to creating the container. - `volume` Creates a volume with the given name and options (or uses a pre-existing volume with the same name and options). These are **not** removed when the container is removed. - `tmpfs` Create a tmpfs with the given options. The mount source cannot be specified for tmpfs. - `npipe` Mounts a named pipe from the host into the container. Must exist prior to creating the container. - `cluster` a Swarm cluster volume 
     #[serde(rename = "Type")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub typ: Option<MountTypeEnum>,
 
     /// Whether the mount should be read-only.
@@ -3618,6 +4979,80 @@ pub struct Mount {
 
 }
 
+impl Mount {
+    /// Start building a [Mount](Mount), with every field unset.
+    pub fn builder() -> MountBuilder {
+        MountBuilder::default()
+    }
+}
+
+/// Builder for [Mount](Mount).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MountBuilder {
+    inner: Mount,
+}
+
+impl MountBuilder {
+    /// Set the [`target`](Mount::target) field.
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.inner.target = Some(target.into());
+        self
+    }
+
+    /// Set the [`source`](Mount::source) field.
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.inner.source = Some(source.into());
+        self
+    }
+
+    /// Set the [`typ`](Mount::typ) field.
+    pub fn typ(mut self, typ: impl Into<MountTypeEnum>) -> Self {
+        self.inner.typ = Some(typ.into());
+        self
+    }
+
+    /// Set the [`read_only`](Mount::read_only) field.
+    pub fn read_only(mut self, read_only: impl Into<bool>) -> Self {
+        self.inner.read_only = Some(read_only.into());
+        self
+    }
+
+    /// Set the [`consistency`](Mount::consistency) field.
+    pub fn consistency(mut self, consistency: impl Into<String>) -> Self {
+        self.inner.consistency = Some(consistency.into());
+        self
+    }
+
+    /// Set the [`bind_options`](Mount::bind_options) field.
+    pub fn bind_options(mut self, bind_options: impl Into<MountBindOptions>) -> Self {
+        self.inner.bind_options = Some(bind_options.into());
+        self
+    }
+
+    /// Set the [`volume_options`](Mount::volume_options) field.
+    pub fn volume_options(mut self, volume_options: impl Into<MountVolumeOptions>) -> Self {
+        self.inner.volume_options = Some(volume_options.into());
+        self
+    }
+
+    /// Set the [`tmpfs_options`](Mount::tmpfs_options) field.
+    pub fn tmpfs_options(mut self, tmpfs_options: impl Into<MountTmpfsOptions>) -> Self {
+        self.inner.tmpfs_options = Some(tmpfs_options.into());
+        self
+    }
+
+    /// Build the [Mount](Mount).
+    pub fn build(self) -> Mount {
+        self.inner
+    }
+}
+
+impl From<MountBuilder> for Mount {
+    fn from(builder: MountBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum MountTypeEnum { 
@@ -3683,6 +5118,7 @@ pub struct MountBindOptions {
     /// A propagation mode with the value `[r]private`, `[r]shared`, or `[r]slave`.
     #[serde(rename = "Propagation")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub propagation: Option<MountBindOptionsPropagationEnum>,
 
     /// Disable recursive bind mount.
@@ -3692,21 +5128,89 @@ pub struct MountBindOptions {
 
     /// Create mount point on host if missing
     #[serde(rename = "CreateMountpoint")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_mount_bind_options_create_mountpoint")]
     pub create_mountpoint: Option<bool>,
 
     /// Make the mount non-recursively read-only, but still leave the mount recursive (unless NonRecursive is set to `true` in conjunction).  Added in v1.44, before that version all read-only mounts were non-recursive by default. To match the previous behaviour this will default to `true` for clients on versions prior to v1.44. 
     #[serde(rename = "ReadOnlyNonRecursive")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_mount_bind_options_read_only_non_recursive")]
     pub read_only_non_recursive: Option<bool>,
 
     /// Raise an error if the mount cannot be made recursively read-only.
     #[serde(rename = "ReadOnlyForceRecursive")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_mount_bind_options_read_only_force_recursive")]
     pub read_only_force_recursive: Option<bool>,
 
 }
 
+impl MountBindOptions {
+    /// Start building a [MountBindOptions](MountBindOptions), with every field unset.
+    pub fn builder() -> MountBindOptionsBuilder {
+        MountBindOptionsBuilder::default()
+    }
+}
+
+/// Builder for [MountBindOptions](MountBindOptions).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MountBindOptionsBuilder {
+    inner: MountBindOptions,
+}
+
+impl MountBindOptionsBuilder {
+    /// Set the [`propagation`](MountBindOptions::propagation) field.
+    pub fn propagation(mut self, propagation: impl Into<MountBindOptionsPropagationEnum>) -> Self {
+        self.inner.propagation = Some(propagation.into());
+        self
+    }
+
+    /// Set the [`non_recursive`](MountBindOptions::non_recursive) field.
+    pub fn non_recursive(mut self, non_recursive: impl Into<bool>) -> Self {
+        self.inner.non_recursive = Some(non_recursive.into());
+        self
+    }
+
+    /// Set the [`create_mountpoint`](MountBindOptions::create_mountpoint) field.
+    pub fn create_mountpoint(mut self, create_mountpoint: impl Into<bool>) -> Self {
+        self.inner.create_mountpoint = Some(create_mountpoint.into());
+        self
+    }
+
+    /// Set the [`read_only_non_recursive`](MountBindOptions::read_only_non_recursive) field.
+    pub fn read_only_non_recursive(mut self, read_only_non_recursive: impl Into<bool>) -> Self {
+        self.inner.read_only_non_recursive = Some(read_only_non_recursive.into());
+        self
+    }
+
+    /// Set the [`read_only_force_recursive`](MountBindOptions::read_only_force_recursive) field.
+    pub fn read_only_force_recursive(mut self, read_only_force_recursive: impl Into<bool>) -> Self {
+        self.inner.read_only_force_recursive = Some(read_only_force_recursive.into());
+        self
+    }
+
+    /// Build the [MountBindOptions](MountBindOptions).
+    pub fn build(self) -> MountBindOptions {
+        self.inner
+    }
+}
+
+impl From<MountBindOptionsBuilder> for MountBindOptions {
+    fn from(builder: MountBindOptionsBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn skip_mount_bind_options_create_mountpoint<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "MountBindOptions.CreateMountpoint", (1, 42))
+}
+
+fn skip_mount_bind_options_read_only_non_recursive<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "MountBindOptions.ReadOnlyNonRecursive", (1, 44))
+}
+
+fn skip_mount_bind_options_read_only_force_recursive<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "MountBindOptions.ReadOnlyForceRecursive", (1, 44))
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum MountBindOptionsPropagationEnum { 
@@ -3777,6 +5281,7 @@ pub struct MountPoint {
     /// The mount type:  - `bind` a mount of a file or directory from the host into the container. - `volume` a docker volume with the given `Name`. - `tmpfs` a `tmpfs`. - `npipe` a named pipe from the host into the container. - `cluster` a Swarm cluster volume 
     #[serde(rename = "Type")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub typ: Option<MountPointTypeEnum>,
 
     /// Name is the name reference to the underlying data defined by `Source` e.g., the volume name. 
@@ -3890,11 +5395,59 @@ pub struct MountTmpfsOptions {
 
     /// The options to be passed to the tmpfs mount. An array of arrays. Flag options should be provided as 1-length arrays. Other types should be provided as as 2-length arrays, where the first item is the key and the second the value. 
     #[serde(rename = "Options")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_mount_tmpfs_options_options")]
     pub options: Option<Vec<Vec<String>>>,
 
 }
 
+impl MountTmpfsOptions {
+    /// Start building a [MountTmpfsOptions](MountTmpfsOptions), with every field unset.
+    pub fn builder() -> MountTmpfsOptionsBuilder {
+        MountTmpfsOptionsBuilder::default()
+    }
+}
+
+/// Builder for [MountTmpfsOptions](MountTmpfsOptions).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MountTmpfsOptionsBuilder {
+    inner: MountTmpfsOptions,
+}
+
+impl MountTmpfsOptionsBuilder {
+    /// Set the [`size_bytes`](MountTmpfsOptions::size_bytes) field.
+    pub fn size_bytes(mut self, size_bytes: impl Into<i64>) -> Self {
+        self.inner.size_bytes = Some(size_bytes.into());
+        self
+    }
+
+    /// Set the [`mode`](MountTmpfsOptions::mode) field.
+    pub fn mode(mut self, mode: impl Into<i64>) -> Self {
+        self.inner.mode = Some(mode.into());
+        self
+    }
+
+    /// Set the [`options`](MountTmpfsOptions::options) field.
+    pub fn options(mut self, options: impl Into<Vec<Vec<String>>>) -> Self {
+        self.inner.options = Some(options.into());
+        self
+    }
+
+    /// Build the [MountTmpfsOptions](MountTmpfsOptions).
+    pub fn build(self) -> MountTmpfsOptions {
+        self.inner
+    }
+}
+
+impl From<MountTmpfsOptionsBuilder> for MountTmpfsOptions {
+    fn from(builder: MountTmpfsOptionsBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn skip_mount_tmpfs_options_options<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "MountTmpfsOptions.Options", (1, 46))
+}
+
 /// Optional configuration for the `volume` type.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct MountVolumeOptions {
@@ -3914,11 +5467,65 @@ pub struct MountVolumeOptions {
 
     /// Source path inside the volume. Must be relative without any back traversals.
     #[serde(rename = "Subpath")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "skip_mount_volume_options_subpath")]
     pub subpath: Option<String>,
 
 }
 
+impl MountVolumeOptions {
+    /// Start building a [MountVolumeOptions](MountVolumeOptions), with every field unset.
+    pub fn builder() -> MountVolumeOptionsBuilder {
+        MountVolumeOptionsBuilder::default()
+    }
+}
+
+/// Builder for [MountVolumeOptions](MountVolumeOptions).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MountVolumeOptionsBuilder {
+    inner: MountVolumeOptions,
+}
+
+impl MountVolumeOptionsBuilder {
+    /// Set the [`no_copy`](MountVolumeOptions::no_copy) field.
+    pub fn no_copy(mut self, no_copy: impl Into<bool>) -> Self {
+        self.inner.no_copy = Some(no_copy.into());
+        self
+    }
+
+    /// Set the [`labels`](MountVolumeOptions::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`driver_config`](MountVolumeOptions::driver_config) field.
+    pub fn driver_config(mut self, driver_config: impl Into<MountVolumeOptionsDriverConfig>) -> Self {
+        self.inner.driver_config = Some(driver_config.into());
+        self
+    }
+
+    /// Set the [`subpath`](MountVolumeOptions::subpath) field.
+    pub fn subpath(mut self, subpath: impl Into<String>) -> Self {
+        self.inner.subpath = Some(subpath.into());
+        self
+    }
+
+    /// Build the [MountVolumeOptions](MountVolumeOptions).
+    pub fn build(self) -> MountVolumeOptions {
+        self.inner
+    }
+}
+
+impl From<MountVolumeOptionsBuilder> for MountVolumeOptions {
+    fn from(builder: MountVolumeOptionsBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn skip_mount_volume_options_subpath<T>(value: &Option<T>) -> bool {
+    skip_unsupported_field(value.is_none(), "MountVolumeOptions.Subpath", (1, 45))
+}
+
 /// Map of driver specific options
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct MountVolumeOptionsDriverConfig {
@@ -3934,6 +5541,44 @@ pub struct MountVolumeOptionsDriverConfig {
 
 }
 
+impl MountVolumeOptionsDriverConfig {
+    /// Start building a [MountVolumeOptionsDriverConfig](MountVolumeOptionsDriverConfig), with every field unset.
+    pub fn builder() -> MountVolumeOptionsDriverConfigBuilder {
+        MountVolumeOptionsDriverConfigBuilder::default()
+    }
+}
+
+/// Builder for [MountVolumeOptionsDriverConfig](MountVolumeOptionsDriverConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MountVolumeOptionsDriverConfigBuilder {
+    inner: MountVolumeOptionsDriverConfig,
+}
+
+impl MountVolumeOptionsDriverConfigBuilder {
+    /// Set the [`name`](MountVolumeOptionsDriverConfig::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`options`](MountVolumeOptionsDriverConfig::options) field.
+    pub fn options(mut self, options: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.options = Some(options.into());
+        self
+    }
+
+    /// Build the [MountVolumeOptionsDriverConfig](MountVolumeOptionsDriverConfig).
+    pub fn build(self) -> MountVolumeOptionsDriverConfig {
+        self.inner
+    }
+}
+
+impl From<MountVolumeOptionsDriverConfigBuilder> for MountVolumeOptionsDriverConfig {
+    fn from(builder: MountVolumeOptionsDriverConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Network {
     /// Name of the network. 
@@ -4046,6 +5691,50 @@ pub struct NetworkAttachmentConfig {
 
 }
 
+impl NetworkAttachmentConfig {
+    /// Start building a [NetworkAttachmentConfig](NetworkAttachmentConfig), with every field unset.
+    pub fn builder() -> NetworkAttachmentConfigBuilder {
+        NetworkAttachmentConfigBuilder::default()
+    }
+}
+
+/// Builder for [NetworkAttachmentConfig](NetworkAttachmentConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkAttachmentConfigBuilder {
+    inner: NetworkAttachmentConfig,
+}
+
+impl NetworkAttachmentConfigBuilder {
+    /// Set the [`target`](NetworkAttachmentConfig::target) field.
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.inner.target = Some(target.into());
+        self
+    }
+
+    /// Set the [`aliases`](NetworkAttachmentConfig::aliases) field.
+    pub fn aliases(mut self, aliases: impl Into<Vec<String>>) -> Self {
+        self.inner.aliases = Some(aliases.into());
+        self
+    }
+
+    /// Set the [`driver_opts`](NetworkAttachmentConfig::driver_opts) field.
+    pub fn driver_opts(mut self, driver_opts: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.driver_opts = Some(driver_opts.into());
+        self
+    }
+
+    /// Build the [NetworkAttachmentConfig](NetworkAttachmentConfig).
+    pub fn build(self) -> NetworkAttachmentConfig {
+        self.inner
+    }
+}
+
+impl From<NetworkAttachmentConfigBuilder> for NetworkAttachmentConfig {
+    fn from(builder: NetworkAttachmentConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct NetworkConnectRequest {
     /// The ID or name of the container to connect to the network.
@@ -4059,6 +5748,44 @@ pub struct NetworkConnectRequest {
 
 }
 
+impl NetworkConnectRequest {
+    /// Start building a [NetworkConnectRequest](NetworkConnectRequest), with every field unset.
+    pub fn builder() -> NetworkConnectRequestBuilder {
+        NetworkConnectRequestBuilder::default()
+    }
+}
+
+/// Builder for [NetworkConnectRequest](NetworkConnectRequest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkConnectRequestBuilder {
+    inner: NetworkConnectRequest,
+}
+
+impl NetworkConnectRequestBuilder {
+    /// Set the [`container`](NetworkConnectRequest::container) field.
+    pub fn container(mut self, container: impl Into<String>) -> Self {
+        self.inner.container = Some(container.into());
+        self
+    }
+
+    /// Set the [`endpoint_config`](NetworkConnectRequest::endpoint_config) field.
+    pub fn endpoint_config(mut self, endpoint_config: impl Into<EndpointSettings>) -> Self {
+        self.inner.endpoint_config = Some(endpoint_config.into());
+        self
+    }
+
+    /// Build the [NetworkConnectRequest](NetworkConnectRequest).
+    pub fn build(self) -> NetworkConnectRequest {
+        self.inner
+    }
+}
+
+impl From<NetworkConnectRequestBuilder> for NetworkConnectRequest {
+    fn from(builder: NetworkConnectRequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct NetworkContainer {
     #[serde(rename = "Name")]
@@ -4151,6 +5878,110 @@ pub struct NetworkCreateRequest {
 
 }
 
+impl NetworkCreateRequest {
+    /// Start building a [NetworkCreateRequest](NetworkCreateRequest), with every field unset.
+    pub fn builder() -> NetworkCreateRequestBuilder {
+        NetworkCreateRequestBuilder::default()
+    }
+}
+
+/// Builder for [NetworkCreateRequest](NetworkCreateRequest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkCreateRequestBuilder {
+    inner: NetworkCreateRequest,
+}
+
+impl NetworkCreateRequestBuilder {
+    /// Set the [`name`](NetworkCreateRequest::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = name.into();
+        self
+    }
+
+    /// Set the [`driver`](NetworkCreateRequest::driver) field.
+    pub fn driver(mut self, driver: impl Into<String>) -> Self {
+        self.inner.driver = Some(driver.into());
+        self
+    }
+
+    /// Set the [`scope`](NetworkCreateRequest::scope) field.
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.inner.scope = Some(scope.into());
+        self
+    }
+
+    /// Set the [`internal`](NetworkCreateRequest::internal) field.
+    pub fn internal(mut self, internal: impl Into<bool>) -> Self {
+        self.inner.internal = Some(internal.into());
+        self
+    }
+
+    /// Set the [`attachable`](NetworkCreateRequest::attachable) field.
+    pub fn attachable(mut self, attachable: impl Into<bool>) -> Self {
+        self.inner.attachable = Some(attachable.into());
+        self
+    }
+
+    /// Set the [`ingress`](NetworkCreateRequest::ingress) field.
+    pub fn ingress(mut self, ingress: impl Into<bool>) -> Self {
+        self.inner.ingress = Some(ingress.into());
+        self
+    }
+
+    /// Set the [`config_only`](NetworkCreateRequest::config_only) field.
+    pub fn config_only(mut self, config_only: impl Into<bool>) -> Self {
+        self.inner.config_only = Some(config_only.into());
+        self
+    }
+
+    /// Set the [`config_from`](NetworkCreateRequest::config_from) field.
+    pub fn config_from(mut self, config_from: impl Into<ConfigReference>) -> Self {
+        self.inner.config_from = Some(config_from.into());
+        self
+    }
+
+    /// Set the [`ipam`](NetworkCreateRequest::ipam) field.
+    pub fn ipam(mut self, ipam: impl Into<Ipam>) -> Self {
+        self.inner.ipam = Some(ipam.into());
+        self
+    }
+
+    /// Set the [`enable_ipv4`](NetworkCreateRequest::enable_ipv4) field.
+    pub fn enable_ipv4(mut self, enable_ipv4: impl Into<bool>) -> Self {
+        self.inner.enable_ipv4 = Some(enable_ipv4.into());
+        self
+    }
+
+    /// Set the [`enable_ipv6`](NetworkCreateRequest::enable_ipv6) field.
+    pub fn enable_ipv6(mut self, enable_ipv6: impl Into<bool>) -> Self {
+        self.inner.enable_ipv6 = Some(enable_ipv6.into());
+        self
+    }
+
+    /// Set the [`options`](NetworkCreateRequest::options) field.
+    pub fn options(mut self, options: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.options = Some(options.into());
+        self
+    }
+
+    /// Set the [`labels`](NetworkCreateRequest::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Build the [NetworkCreateRequest](NetworkCreateRequest).
+    pub fn build(self) -> NetworkCreateRequest {
+        self.inner
+    }
+}
+
+impl From<NetworkCreateRequestBuilder> for NetworkCreateRequest {
+    fn from(builder: NetworkCreateRequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// OK response to NetworkCreate operation
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct NetworkCreateResponse {
@@ -4178,6 +6009,44 @@ pub struct NetworkDisconnectRequest {
 
 }
 
+impl NetworkDisconnectRequest {
+    /// Start building a [NetworkDisconnectRequest](NetworkDisconnectRequest), with every field unset.
+    pub fn builder() -> NetworkDisconnectRequestBuilder {
+        NetworkDisconnectRequestBuilder::default()
+    }
+}
+
+/// Builder for [NetworkDisconnectRequest](NetworkDisconnectRequest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkDisconnectRequestBuilder {
+    inner: NetworkDisconnectRequest,
+}
+
+impl NetworkDisconnectRequestBuilder {
+    /// Set the [`container`](NetworkDisconnectRequest::container) field.
+    pub fn container(mut self, container: impl Into<String>) -> Self {
+        self.inner.container = Some(container.into());
+        self
+    }
+
+    /// Set the [`force`](NetworkDisconnectRequest::force) field.
+    pub fn force(mut self, force: impl Into<bool>) -> Self {
+        self.inner.force = Some(force.into());
+        self
+    }
+
+    /// Build the [NetworkDisconnectRequest](NetworkDisconnectRequest).
+    pub fn build(self) -> NetworkDisconnectRequest {
+        self.inner
+    }
+}
+
+impl From<NetworkDisconnectRequestBuilder> for NetworkDisconnectRequest {
+    fn from(builder: NetworkDisconnectRequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct NetworkPruneResponse {
     /// Networks that were deleted
@@ -4291,6 +6160,38 @@ pub struct NetworkingConfig {
 
 }
 
+impl NetworkingConfig {
+    /// Start building a [NetworkingConfig](NetworkingConfig), with every field unset.
+    pub fn builder() -> NetworkingConfigBuilder {
+        NetworkingConfigBuilder::default()
+    }
+}
+
+/// Builder for [NetworkingConfig](NetworkingConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkingConfigBuilder {
+    inner: NetworkingConfig,
+}
+
+impl NetworkingConfigBuilder {
+    /// Set the [`endpoints_config`](NetworkingConfig::endpoints_config) field.
+    pub fn endpoints_config(mut self, endpoints_config: impl Into<HashMap<String, EndpointSettings>>) -> Self {
+        self.inner.endpoints_config = Some(endpoints_config.into());
+        self
+    }
+
+    /// Build the [NetworkingConfig](NetworkingConfig).
+    pub fn build(self) -> NetworkingConfig {
+        self.inner
+    }
+}
+
+impl From<NetworkingConfigBuilder> for NetworkingConfig {
+    fn from(builder: NetworkingConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Node {
     #[serde(rename = "ID")]
@@ -4376,16 +6277,68 @@ pub struct NodeSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub labels: Option<HashMap<String, String>>,
 
-    /// Role of the node.
-    #[serde(rename = "Role")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub role: Option<NodeSpecRoleEnum>,
+    /// Role of the node.
+    #[serde(rename = "Role")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
+    pub role: Option<NodeSpecRoleEnum>,
+
+    /// Availability of the node.
+    #[serde(rename = "Availability")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
+    pub availability: Option<NodeSpecAvailabilityEnum>,
+
+}
+
+impl NodeSpec {
+    /// Start building a [NodeSpec](NodeSpec), with every field unset.
+    pub fn builder() -> NodeSpecBuilder {
+        NodeSpecBuilder::default()
+    }
+}
+
+/// Builder for [NodeSpec](NodeSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeSpecBuilder {
+    inner: NodeSpec,
+}
+
+impl NodeSpecBuilder {
+    /// Set the [`name`](NodeSpec::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`labels`](NodeSpec::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`role`](NodeSpec::role) field.
+    pub fn role(mut self, role: impl Into<NodeSpecRoleEnum>) -> Self {
+        self.inner.role = Some(role.into());
+        self
+    }
+
+    /// Set the [`availability`](NodeSpec::availability) field.
+    pub fn availability(mut self, availability: impl Into<NodeSpecAvailabilityEnum>) -> Self {
+        self.inner.availability = Some(availability.into());
+        self
+    }
 
-    /// Availability of the node.
-    #[serde(rename = "Availability")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub availability: Option<NodeSpecAvailabilityEnum>,
+    /// Build the [NodeSpec](NodeSpec).
+    pub fn build(self) -> NodeSpec {
+        self.inner
+    }
+}
 
+impl From<NodeSpecBuilder> for NodeSpec {
+    fn from(builder: NodeSpecBuilder) -> Self {
+        builder.build()
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -4534,6 +6487,7 @@ impl std::default::Default for NodeState {
 pub struct NodeStatus {
     #[serde(rename = "State")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub state: Option<NodeState>,
 
     #[serde(rename = "Message")]
@@ -4760,7 +6714,7 @@ pub struct PluginConfigArgs {
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PluginConfigInterface {
     #[serde(rename = "Types")]
-    #[serde(deserialize_with = "deserialize_nonoptional_vec")]
+    #[serde(deserialize_with = "deserialize_plugin_interface_types")]
     pub types: Vec<PluginInterfaceType>,
 
     #[serde(rename = "Socket")]
@@ -4769,6 +6723,7 @@ pub struct PluginConfigInterface {
     /// Protocol to use for clients connecting to the plugin.
     #[serde(rename = "ProtocolScheme")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub protocol_scheme: Option<PluginConfigInterfaceProtocolSchemeEnum>,
 
 }
@@ -5014,7 +6969,10 @@ pub struct Port {
 
     #[serde(rename = "Type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(with = "::serde_with::As::<::serde_with::NoneAsEmptyString>")]
+    #[serde(
+        serialize_with = "::serde_with::As::<::serde_with::NoneAsEmptyString>::serialize",
+        deserialize_with = "deserialize_nonempty_lenient_enum"
+    )]
     pub typ: Option<PortTypeEnum>,
 
 }
@@ -5083,6 +7041,44 @@ pub struct PortBinding {
 
 }
 
+impl PortBinding {
+    /// Start building a [PortBinding](PortBinding), with every field unset.
+    pub fn builder() -> PortBindingBuilder {
+        PortBindingBuilder::default()
+    }
+}
+
+/// Builder for [PortBinding](PortBinding).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortBindingBuilder {
+    inner: PortBinding,
+}
+
+impl PortBindingBuilder {
+    /// Set the [`host_ip`](PortBinding::host_ip) field.
+    pub fn host_ip(mut self, host_ip: impl Into<String>) -> Self {
+        self.inner.host_ip = Some(host_ip.into());
+        self
+    }
+
+    /// Set the [`host_port`](PortBinding::host_port) field.
+    pub fn host_port(mut self, host_port: impl Into<String>) -> Self {
+        self.inner.host_port = Some(host_port.into());
+        self
+    }
+
+    /// Build the [PortBinding](PortBinding).
+    pub fn build(self) -> PortBinding {
+        self.inner
+    }
+}
+
+impl From<PortBindingBuilder> for PortBinding {
+    fn from(builder: PortBindingBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// PortMap describes the mapping of container ports to host ports, using the container's port-number and protocol as key in the format `<port>/<protocol>`, for example, `80/udp`.  If a container's port is mapped for multiple protocols, separate entries are added to the mapping table. 
 // special-casing PortMap, cos swagger-codegen doesn't figure out this type
 pub type PortMap = HashMap<String, Option<Vec<PortBinding>>>;
@@ -5402,6 +7398,218 @@ pub struct Resources {
 
 }
 
+impl Resources {
+    /// Start building a [Resources](Resources), with every field unset.
+    pub fn builder() -> ResourcesBuilder {
+        ResourcesBuilder::default()
+    }
+}
+
+/// Builder for [Resources](Resources).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourcesBuilder {
+    inner: Resources,
+}
+
+impl ResourcesBuilder {
+    /// Set the [`cpu_shares`](Resources::cpu_shares) field.
+    pub fn cpu_shares(mut self, cpu_shares: impl Into<i64>) -> Self {
+        self.inner.cpu_shares = Some(cpu_shares.into());
+        self
+    }
+
+    /// Set the [`memory`](Resources::memory) field.
+    pub fn memory(mut self, memory: impl Into<i64>) -> Self {
+        self.inner.memory = Some(memory.into());
+        self
+    }
+
+    /// Set the [`cgroup_parent`](Resources::cgroup_parent) field.
+    pub fn cgroup_parent(mut self, cgroup_parent: impl Into<String>) -> Self {
+        self.inner.cgroup_parent = Some(cgroup_parent.into());
+        self
+    }
+
+    /// Set the [`blkio_weight`](Resources::blkio_weight) field.
+    pub fn blkio_weight(mut self, blkio_weight: impl Into<u16>) -> Self {
+        self.inner.blkio_weight = Some(blkio_weight.into());
+        self
+    }
+
+    /// Set the [`blkio_weight_device`](Resources::blkio_weight_device) field.
+    pub fn blkio_weight_device(mut self, blkio_weight_device: impl Into<Vec<ResourcesBlkioWeightDevice>>) -> Self {
+        self.inner.blkio_weight_device = Some(blkio_weight_device.into());
+        self
+    }
+
+    /// Set the [`blkio_device_read_bps`](Resources::blkio_device_read_bps) field.
+    pub fn blkio_device_read_bps(mut self, blkio_device_read_bps: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_read_bps = Some(blkio_device_read_bps.into());
+        self
+    }
+
+    /// Set the [`blkio_device_write_bps`](Resources::blkio_device_write_bps) field.
+    pub fn blkio_device_write_bps(mut self, blkio_device_write_bps: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_write_bps = Some(blkio_device_write_bps.into());
+        self
+    }
+
+    /// Set the [`blkio_device_read_iops`](Resources::blkio_device_read_iops) field.
+    pub fn blkio_device_read_iops(mut self, blkio_device_read_iops: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_read_iops = Some(blkio_device_read_iops.into());
+        self
+    }
+
+    /// Set the [`blkio_device_write_iops`](Resources::blkio_device_write_iops) field.
+    pub fn blkio_device_write_iops(mut self, blkio_device_write_iops: impl Into<Vec<ThrottleDevice>>) -> Self {
+        self.inner.blkio_device_write_iops = Some(blkio_device_write_iops.into());
+        self
+    }
+
+    /// Set the [`cpu_period`](Resources::cpu_period) field.
+    pub fn cpu_period(mut self, cpu_period: impl Into<i64>) -> Self {
+        self.inner.cpu_period = Some(cpu_period.into());
+        self
+    }
+
+    /// Set the [`cpu_quota`](Resources::cpu_quota) field.
+    pub fn cpu_quota(mut self, cpu_quota: impl Into<i64>) -> Self {
+        self.inner.cpu_quota = Some(cpu_quota.into());
+        self
+    }
+
+    /// Set the [`cpu_realtime_period`](Resources::cpu_realtime_period) field.
+    pub fn cpu_realtime_period(mut self, cpu_realtime_period: impl Into<i64>) -> Self {
+        self.inner.cpu_realtime_period = Some(cpu_realtime_period.into());
+        self
+    }
+
+    /// Set the [`cpu_realtime_runtime`](Resources::cpu_realtime_runtime) field.
+    pub fn cpu_realtime_runtime(mut self, cpu_realtime_runtime: impl Into<i64>) -> Self {
+        self.inner.cpu_realtime_runtime = Some(cpu_realtime_runtime.into());
+        self
+    }
+
+    /// Set the [`cpuset_cpus`](Resources::cpuset_cpus) field.
+    pub fn cpuset_cpus(mut self, cpuset_cpus: impl Into<String>) -> Self {
+        self.inner.cpuset_cpus = Some(cpuset_cpus.into());
+        self
+    }
+
+    /// Set the [`cpuset_mems`](Resources::cpuset_mems) field.
+    pub fn cpuset_mems(mut self, cpuset_mems: impl Into<String>) -> Self {
+        self.inner.cpuset_mems = Some(cpuset_mems.into());
+        self
+    }
+
+    /// Set the [`devices`](Resources::devices) field.
+    pub fn devices(mut self, devices: impl Into<Vec<DeviceMapping>>) -> Self {
+        self.inner.devices = Some(devices.into());
+        self
+    }
+
+    /// Set the [`device_cgroup_rules`](Resources::device_cgroup_rules) field.
+    pub fn device_cgroup_rules(mut self, device_cgroup_rules: impl Into<Vec<String>>) -> Self {
+        self.inner.device_cgroup_rules = Some(device_cgroup_rules.into());
+        self
+    }
+
+    /// Set the [`device_requests`](Resources::device_requests) field.
+    pub fn device_requests(mut self, device_requests: impl Into<Vec<DeviceRequest>>) -> Self {
+        self.inner.device_requests = Some(device_requests.into());
+        self
+    }
+
+    /// Set the [`kernel_memory_tcp`](Resources::kernel_memory_tcp) field.
+    pub fn kernel_memory_tcp(mut self, kernel_memory_tcp: impl Into<i64>) -> Self {
+        self.inner.kernel_memory_tcp = Some(kernel_memory_tcp.into());
+        self
+    }
+
+    /// Set the [`memory_reservation`](Resources::memory_reservation) field.
+    pub fn memory_reservation(mut self, memory_reservation: impl Into<i64>) -> Self {
+        self.inner.memory_reservation = Some(memory_reservation.into());
+        self
+    }
+
+    /// Set the [`memory_swap`](Resources::memory_swap) field.
+    pub fn memory_swap(mut self, memory_swap: impl Into<i64>) -> Self {
+        self.inner.memory_swap = Some(memory_swap.into());
+        self
+    }
+
+    /// Set the [`memory_swappiness`](Resources::memory_swappiness) field.
+    pub fn memory_swappiness(mut self, memory_swappiness: impl Into<i64>) -> Self {
+        self.inner.memory_swappiness = Some(memory_swappiness.into());
+        self
+    }
+
+    /// Set the [`nano_cpus`](Resources::nano_cpus) field.
+    pub fn nano_cpus(mut self, nano_cpus: impl Into<i64>) -> Self {
+        self.inner.nano_cpus = Some(nano_cpus.into());
+        self
+    }
+
+    /// Set the [`oom_kill_disable`](Resources::oom_kill_disable) field.
+    pub fn oom_kill_disable(mut self, oom_kill_disable: impl Into<bool>) -> Self {
+        self.inner.oom_kill_disable = Some(oom_kill_disable.into());
+        self
+    }
+
+    /// Set the [`init`](Resources::init) field.
+    pub fn init(mut self, init: impl Into<bool>) -> Self {
+        self.inner.init = Some(init.into());
+        self
+    }
+
+    /// Set the [`pids_limit`](Resources::pids_limit) field.
+    pub fn pids_limit(mut self, pids_limit: impl Into<i64>) -> Self {
+        self.inner.pids_limit = Some(pids_limit.into());
+        self
+    }
+
+    /// Set the [`ulimits`](Resources::ulimits) field.
+    pub fn ulimits(mut self, ulimits: impl Into<Vec<ResourcesUlimits>>) -> Self {
+        self.inner.ulimits = Some(ulimits.into());
+        self
+    }
+
+    /// Set the [`cpu_count`](Resources::cpu_count) field.
+    pub fn cpu_count(mut self, cpu_count: impl Into<i64>) -> Self {
+        self.inner.cpu_count = Some(cpu_count.into());
+        self
+    }
+
+    /// Set the [`cpu_percent`](Resources::cpu_percent) field.
+    pub fn cpu_percent(mut self, cpu_percent: impl Into<i64>) -> Self {
+        self.inner.cpu_percent = Some(cpu_percent.into());
+        self
+    }
+
+    /// Set the [`io_maximum_iops`](Resources::io_maximum_iops) field.
+    pub fn io_maximum_iops(mut self, io_maximum_iops: impl Into<i64>) -> Self {
+        self.inner.io_maximum_iops = Some(io_maximum_iops.into());
+        self
+    }
+
+    /// Set the [`io_maximum_bandwidth`](Resources::io_maximum_bandwidth) field.
+    pub fn io_maximum_bandwidth(mut self, io_maximum_bandwidth: impl Into<i64>) -> Self {
+        self.inner.io_maximum_bandwidth = Some(io_maximum_bandwidth.into());
+        self
+    }
+
+    /// Build the [Resources](Resources).
+    pub fn build(self) -> Resources {
+        self.inner
+    }
+}
+
+impl From<ResourcesBuilder> for Resources {
+    fn from(builder: ResourcesBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ResourcesBlkioWeightDevice {
     #[serde(rename = "Path")]
@@ -5433,12 +7641,57 @@ pub struct ResourcesUlimits {
 
 }
 
+impl ResourcesUlimits {
+    /// Start building a [ResourcesUlimits](ResourcesUlimits), with every field unset.
+    pub fn builder() -> ResourcesUlimitsBuilder {
+        ResourcesUlimitsBuilder::default()
+    }
+}
+
+/// Builder for [ResourcesUlimits](ResourcesUlimits).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourcesUlimitsBuilder {
+    inner: ResourcesUlimits,
+}
+
+impl ResourcesUlimitsBuilder {
+    /// Set the [`name`](ResourcesUlimits::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`soft`](ResourcesUlimits::soft) field.
+    pub fn soft(mut self, soft: impl Into<i64>) -> Self {
+        self.inner.soft = Some(soft.into());
+        self
+    }
+
+    /// Set the [`hard`](ResourcesUlimits::hard) field.
+    pub fn hard(mut self, hard: impl Into<i64>) -> Self {
+        self.inner.hard = Some(hard.into());
+        self
+    }
+
+    /// Build the [ResourcesUlimits](ResourcesUlimits).
+    pub fn build(self) -> ResourcesUlimits {
+        self.inner
+    }
+}
+
+impl From<ResourcesUlimitsBuilder> for ResourcesUlimits {
+    fn from(builder: ResourcesUlimitsBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// The behavior to apply when the container exits. The default is not to restart.  An ever increasing delay (double the previous delay, starting at 100ms) is added before each restart to prevent flooding the server. 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct RestartPolicy {
     /// - Empty string means not to restart - `no` Do not automatically restart - `always` Always restart - `unless-stopped` Restart always except when the user has manually stopped the container - `on-failure` Restart only when the container exit code is non-zero 
     #[serde(rename = "Name")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub name: Option<RestartPolicyNameEnum>,
 
     /// If `on-failure` is used, the number of times to retry before giving up. 
@@ -5448,6 +7701,44 @@ pub struct RestartPolicy {
 
 }
 
+impl RestartPolicy {
+    /// Start building a [RestartPolicy](RestartPolicy), with every field unset.
+    pub fn builder() -> RestartPolicyBuilder {
+        RestartPolicyBuilder::default()
+    }
+}
+
+/// Builder for [RestartPolicy](RestartPolicy).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RestartPolicyBuilder {
+    inner: RestartPolicy,
+}
+
+impl RestartPolicyBuilder {
+    /// Set the [`name`](RestartPolicy::name) field.
+    pub fn name(mut self, name: impl Into<RestartPolicyNameEnum>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`maximum_retry_count`](RestartPolicy::maximum_retry_count) field.
+    pub fn maximum_retry_count(mut self, maximum_retry_count: impl Into<i64>) -> Self {
+        self.inner.maximum_retry_count = Some(maximum_retry_count.into());
+        self
+    }
+
+    /// Build the [RestartPolicy](RestartPolicy).
+    pub fn build(self) -> RestartPolicy {
+        self.inner
+    }
+}
+
+impl From<RestartPolicyBuilder> for RestartPolicy {
+    fn from(builder: RestartPolicyBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum RestartPolicyNameEnum { 
@@ -5585,6 +7876,62 @@ pub struct SecretSpec {
 
 }
 
+impl SecretSpec {
+    /// Start building a [SecretSpec](SecretSpec), with every field unset.
+    pub fn builder() -> SecretSpecBuilder {
+        SecretSpecBuilder::default()
+    }
+}
+
+/// Builder for [SecretSpec](SecretSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SecretSpecBuilder {
+    inner: SecretSpec,
+}
+
+impl SecretSpecBuilder {
+    /// Set the [`name`](SecretSpec::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`labels`](SecretSpec::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`data`](SecretSpec::data) field.
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.inner.data = Some(data.into());
+        self
+    }
+
+    /// Set the [`driver`](SecretSpec::driver) field.
+    pub fn driver(mut self, driver: impl Into<Driver>) -> Self {
+        self.inner.driver = Some(driver.into());
+        self
+    }
+
+    /// Set the [`templating`](SecretSpec::templating) field.
+    pub fn templating(mut self, templating: impl Into<Driver>) -> Self {
+        self.inner.templating = Some(templating.into());
+        self
+    }
+
+    /// Build the [SecretSpec](SecretSpec).
+    pub fn build(self) -> SecretSpec {
+        self.inner
+    }
+}
+
+impl From<SecretSpecBuilder> for SecretSpec {
+    fn from(builder: SecretSpecBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Service {
     #[serde(rename = "ID")]
@@ -5758,6 +8105,80 @@ pub struct ServiceSpec {
 
 }
 
+impl ServiceSpec {
+    /// Start building a [ServiceSpec](ServiceSpec), with every field unset.
+    pub fn builder() -> ServiceSpecBuilder {
+        ServiceSpecBuilder::default()
+    }
+}
+
+/// Builder for [ServiceSpec](ServiceSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceSpecBuilder {
+    inner: ServiceSpec,
+}
+
+impl ServiceSpecBuilder {
+    /// Set the [`name`](ServiceSpec::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`labels`](ServiceSpec::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`task_template`](ServiceSpec::task_template) field.
+    pub fn task_template(mut self, task_template: impl Into<TaskSpec>) -> Self {
+        self.inner.task_template = Some(task_template.into());
+        self
+    }
+
+    /// Set the [`mode`](ServiceSpec::mode) field.
+    pub fn mode(mut self, mode: impl Into<ServiceSpecMode>) -> Self {
+        self.inner.mode = Some(mode.into());
+        self
+    }
+
+    /// Set the [`update_config`](ServiceSpec::update_config) field.
+    pub fn update_config(mut self, update_config: impl Into<ServiceSpecUpdateConfig>) -> Self {
+        self.inner.update_config = Some(update_config.into());
+        self
+    }
+
+    /// Set the [`rollback_config`](ServiceSpec::rollback_config) field.
+    pub fn rollback_config(mut self, rollback_config: impl Into<ServiceSpecRollbackConfig>) -> Self {
+        self.inner.rollback_config = Some(rollback_config.into());
+        self
+    }
+
+    /// Set the [`networks`](ServiceSpec::networks) field.
+    pub fn networks(mut self, networks: impl Into<Vec<NetworkAttachmentConfig>>) -> Self {
+        self.inner.networks = Some(networks.into());
+        self
+    }
+
+    /// Set the [`endpoint_spec`](ServiceSpec::endpoint_spec) field.
+    pub fn endpoint_spec(mut self, endpoint_spec: impl Into<EndpointSpec>) -> Self {
+        self.inner.endpoint_spec = Some(endpoint_spec.into());
+        self
+    }
+
+    /// Build the [ServiceSpec](ServiceSpec).
+    pub fn build(self) -> ServiceSpec {
+        self.inner
+    }
+}
+
+impl From<ServiceSpecBuilder> for ServiceSpec {
+    fn from(builder: ServiceSpecBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Scheduling mode for the service.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ServiceSpecMode {
@@ -5780,6 +8201,56 @@ pub struct ServiceSpecMode {
 
 }
 
+impl ServiceSpecMode {
+    /// Start building a [ServiceSpecMode](ServiceSpecMode), with every field unset.
+    pub fn builder() -> ServiceSpecModeBuilder {
+        ServiceSpecModeBuilder::default()
+    }
+}
+
+/// Builder for [ServiceSpecMode](ServiceSpecMode).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceSpecModeBuilder {
+    inner: ServiceSpecMode,
+}
+
+impl ServiceSpecModeBuilder {
+    /// Set the [`replicated`](ServiceSpecMode::replicated) field.
+    pub fn replicated(mut self, replicated: impl Into<ServiceSpecModeReplicated>) -> Self {
+        self.inner.replicated = Some(replicated.into());
+        self
+    }
+
+    /// Set the [`global`](ServiceSpecMode::global) field.
+    pub fn global(mut self, global: impl Into<HashMap<(), ()>>) -> Self {
+        self.inner.global = Some(global.into());
+        self
+    }
+
+    /// Set the [`replicated_job`](ServiceSpecMode::replicated_job) field.
+    pub fn replicated_job(mut self, replicated_job: impl Into<ServiceSpecModeReplicatedJob>) -> Self {
+        self.inner.replicated_job = Some(replicated_job.into());
+        self
+    }
+
+    /// Set the [`global_job`](ServiceSpecMode::global_job) field.
+    pub fn global_job(mut self, global_job: impl Into<HashMap<(), ()>>) -> Self {
+        self.inner.global_job = Some(global_job.into());
+        self
+    }
+
+    /// Build the [ServiceSpecMode](ServiceSpecMode).
+    pub fn build(self) -> ServiceSpecMode {
+        self.inner
+    }
+}
+
+impl From<ServiceSpecModeBuilder> for ServiceSpecMode {
+    fn from(builder: ServiceSpecModeBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ServiceSpecModeReplicated {
     #[serde(rename = "Replicas")]
@@ -5819,6 +8290,7 @@ pub struct ServiceSpecRollbackConfig {
     /// Action to take if an rolled back task fails to run, or stops running during the rollback. 
     #[serde(rename = "FailureAction")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub failure_action: Option<ServiceSpecRollbackConfigFailureActionEnum>,
 
     /// Amount of time to monitor each rolled back task for failures, in nanoseconds. 
@@ -5834,6 +8306,7 @@ pub struct ServiceSpecRollbackConfig {
     /// The order of operations when rolling back a task. Either the old task is shut down before the new task is started, or the new task is started before the old task is shut down. 
     #[serde(rename = "Order")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub order: Option<ServiceSpecRollbackConfigOrderEnum>,
 
 }
@@ -5942,6 +8415,7 @@ pub struct ServiceSpecUpdateConfig {
     /// Action to take if an updated task fails to run, or stops running during the update. 
     #[serde(rename = "FailureAction")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub failure_action: Option<ServiceSpecUpdateConfigFailureActionEnum>,
 
     /// Amount of time to monitor each updated task for failures, in nanoseconds. 
@@ -5957,10 +8431,73 @@ pub struct ServiceSpecUpdateConfig {
     /// The order of operations when rolling out an updated task. Either the old task is shut down before the new task is started, or the new task is started before the old task is shut down. 
     #[serde(rename = "Order")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub order: Option<ServiceSpecUpdateConfigOrderEnum>,
 
 }
 
+impl ServiceSpecUpdateConfig {
+    /// Start building a [ServiceSpecUpdateConfig](ServiceSpecUpdateConfig), with every field unset.
+    pub fn builder() -> ServiceSpecUpdateConfigBuilder {
+        ServiceSpecUpdateConfigBuilder::default()
+    }
+}
+
+/// Builder for [ServiceSpecUpdateConfig](ServiceSpecUpdateConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceSpecUpdateConfigBuilder {
+    inner: ServiceSpecUpdateConfig,
+}
+
+impl ServiceSpecUpdateConfigBuilder {
+    /// Set the [`parallelism`](ServiceSpecUpdateConfig::parallelism) field.
+    pub fn parallelism(mut self, parallelism: impl Into<i64>) -> Self {
+        self.inner.parallelism = Some(parallelism.into());
+        self
+    }
+
+    /// Set the [`delay`](ServiceSpecUpdateConfig::delay) field.
+    pub fn delay(mut self, delay: impl Into<i64>) -> Self {
+        self.inner.delay = Some(delay.into());
+        self
+    }
+
+    /// Set the [`failure_action`](ServiceSpecUpdateConfig::failure_action) field.
+    pub fn failure_action(mut self, failure_action: impl Into<ServiceSpecUpdateConfigFailureActionEnum>) -> Self {
+        self.inner.failure_action = Some(failure_action.into());
+        self
+    }
+
+    /// Set the [`monitor`](ServiceSpecUpdateConfig::monitor) field.
+    pub fn monitor(mut self, monitor: impl Into<i64>) -> Self {
+        self.inner.monitor = Some(monitor.into());
+        self
+    }
+
+    /// Set the [`max_failure_ratio`](ServiceSpecUpdateConfig::max_failure_ratio) field.
+    pub fn max_failure_ratio(mut self, max_failure_ratio: impl Into<f64>) -> Self {
+        self.inner.max_failure_ratio = Some(max_failure_ratio.into());
+        self
+    }
+
+    /// Set the [`order`](ServiceSpecUpdateConfig::order) field.
+    pub fn order(mut self, order: impl Into<ServiceSpecUpdateConfigOrderEnum>) -> Self {
+        self.inner.order = Some(order.into());
+        self
+    }
+
+    /// Build the [ServiceSpecUpdateConfig](ServiceSpecUpdateConfig).
+    pub fn build(self) -> ServiceSpecUpdateConfig {
+        self.inner
+    }
+}
+
+impl From<ServiceSpecUpdateConfigBuilder> for ServiceSpecUpdateConfig {
+    fn from(builder: ServiceSpecUpdateConfigBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum ServiceSpecUpdateConfigFailureActionEnum { 
@@ -6068,6 +8605,7 @@ pub struct ServiceUpdateResponse {
 pub struct ServiceUpdateStatus {
     #[serde(rename = "State")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub state: Option<ServiceUpdateStatusStateEnum>,
 
     #[serde(rename = "StartedAt")]
@@ -6238,6 +8776,7 @@ pub struct SwarmInfo {
 
     #[serde(rename = "LocalNodeState")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub local_node_state: Option<LocalNodeState>,
 
     #[serde(rename = "ControlAvailable")]
@@ -6296,20 +8835,94 @@ pub struct SwarmInitRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_addr_pool: Option<Vec<String>>,
 
-    /// Force creation of a new swarm.
-    #[serde(rename = "ForceNewCluster")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub force_new_cluster: Option<bool>,
+    /// Force creation of a new swarm.
+    #[serde(rename = "ForceNewCluster")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_new_cluster: Option<bool>,
+
+    /// SubnetSize specifies the subnet size of the networks created from the default subnet pool. 
+    #[serde(rename = "SubnetSize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subnet_size: Option<u32>,
+
+    #[serde(rename = "Spec")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec: Option<SwarmSpec>,
+
+}
+
+impl SwarmInitRequest {
+    /// Start building a [SwarmInitRequest](SwarmInitRequest), with every field unset.
+    pub fn builder() -> SwarmInitRequestBuilder {
+        SwarmInitRequestBuilder::default()
+    }
+}
+
+/// Builder for [SwarmInitRequest](SwarmInitRequest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SwarmInitRequestBuilder {
+    inner: SwarmInitRequest,
+}
+
+impl SwarmInitRequestBuilder {
+    /// Set the [`listen_addr`](SwarmInitRequest::listen_addr) field.
+    pub fn listen_addr(mut self, listen_addr: impl Into<String>) -> Self {
+        self.inner.listen_addr = Some(listen_addr.into());
+        self
+    }
+
+    /// Set the [`advertise_addr`](SwarmInitRequest::advertise_addr) field.
+    pub fn advertise_addr(mut self, advertise_addr: impl Into<String>) -> Self {
+        self.inner.advertise_addr = Some(advertise_addr.into());
+        self
+    }
+
+    /// Set the [`data_path_addr`](SwarmInitRequest::data_path_addr) field.
+    pub fn data_path_addr(mut self, data_path_addr: impl Into<String>) -> Self {
+        self.inner.data_path_addr = Some(data_path_addr.into());
+        self
+    }
+
+    /// Set the [`data_path_port`](SwarmInitRequest::data_path_port) field.
+    pub fn data_path_port(mut self, data_path_port: impl Into<u32>) -> Self {
+        self.inner.data_path_port = Some(data_path_port.into());
+        self
+    }
+
+    /// Set the [`default_addr_pool`](SwarmInitRequest::default_addr_pool) field.
+    pub fn default_addr_pool(mut self, default_addr_pool: impl Into<Vec<String>>) -> Self {
+        self.inner.default_addr_pool = Some(default_addr_pool.into());
+        self
+    }
+
+    /// Set the [`force_new_cluster`](SwarmInitRequest::force_new_cluster) field.
+    pub fn force_new_cluster(mut self, force_new_cluster: impl Into<bool>) -> Self {
+        self.inner.force_new_cluster = Some(force_new_cluster.into());
+        self
+    }
 
-    /// SubnetSize specifies the subnet size of the networks created from the default subnet pool. 
-    #[serde(rename = "SubnetSize")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub subnet_size: Option<u32>,
+    /// Set the [`subnet_size`](SwarmInitRequest::subnet_size) field.
+    pub fn subnet_size(mut self, subnet_size: impl Into<u32>) -> Self {
+        self.inner.subnet_size = Some(subnet_size.into());
+        self
+    }
 
-    #[serde(rename = "Spec")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub spec: Option<SwarmSpec>,
+    /// Set the [`spec`](SwarmInitRequest::spec) field.
+    pub fn spec(mut self, spec: impl Into<SwarmSpec>) -> Self {
+        self.inner.spec = Some(spec.into());
+        self
+    }
+
+    /// Build the [SwarmInitRequest](SwarmInitRequest).
+    pub fn build(self) -> SwarmInitRequest {
+        self.inner
+    }
+}
 
+impl From<SwarmInitRequestBuilder> for SwarmInitRequest {
+    fn from(builder: SwarmInitRequestBuilder) -> Self {
+        builder.build()
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -6341,6 +8954,62 @@ pub struct SwarmJoinRequest {
 
 }
 
+impl SwarmJoinRequest {
+    /// Start building a [SwarmJoinRequest](SwarmJoinRequest), with every field unset.
+    pub fn builder() -> SwarmJoinRequestBuilder {
+        SwarmJoinRequestBuilder::default()
+    }
+}
+
+/// Builder for [SwarmJoinRequest](SwarmJoinRequest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SwarmJoinRequestBuilder {
+    inner: SwarmJoinRequest,
+}
+
+impl SwarmJoinRequestBuilder {
+    /// Set the [`listen_addr`](SwarmJoinRequest::listen_addr) field.
+    pub fn listen_addr(mut self, listen_addr: impl Into<String>) -> Self {
+        self.inner.listen_addr = Some(listen_addr.into());
+        self
+    }
+
+    /// Set the [`advertise_addr`](SwarmJoinRequest::advertise_addr) field.
+    pub fn advertise_addr(mut self, advertise_addr: impl Into<String>) -> Self {
+        self.inner.advertise_addr = Some(advertise_addr.into());
+        self
+    }
+
+    /// Set the [`data_path_addr`](SwarmJoinRequest::data_path_addr) field.
+    pub fn data_path_addr(mut self, data_path_addr: impl Into<String>) -> Self {
+        self.inner.data_path_addr = Some(data_path_addr.into());
+        self
+    }
+
+    /// Set the [`remote_addrs`](SwarmJoinRequest::remote_addrs) field.
+    pub fn remote_addrs(mut self, remote_addrs: impl Into<Vec<String>>) -> Self {
+        self.inner.remote_addrs = Some(remote_addrs.into());
+        self
+    }
+
+    /// Set the [`join_token`](SwarmJoinRequest::join_token) field.
+    pub fn join_token(mut self, join_token: impl Into<String>) -> Self {
+        self.inner.join_token = Some(join_token.into());
+        self
+    }
+
+    /// Build the [SwarmJoinRequest](SwarmJoinRequest).
+    pub fn build(self) -> SwarmJoinRequest {
+        self.inner
+    }
+}
+
+impl From<SwarmJoinRequestBuilder> for SwarmJoinRequest {
+    fn from(builder: SwarmJoinRequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// User modifiable swarm configuration.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SwarmSpec {
@@ -6380,6 +9049,80 @@ pub struct SwarmSpec {
 
 }
 
+impl SwarmSpec {
+    /// Start building a [SwarmSpec](SwarmSpec), with every field unset.
+    pub fn builder() -> SwarmSpecBuilder {
+        SwarmSpecBuilder::default()
+    }
+}
+
+/// Builder for [SwarmSpec](SwarmSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SwarmSpecBuilder {
+    inner: SwarmSpec,
+}
+
+impl SwarmSpecBuilder {
+    /// Set the [`name`](SwarmSpec::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`labels`](SwarmSpec::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`orchestration`](SwarmSpec::orchestration) field.
+    pub fn orchestration(mut self, orchestration: impl Into<SwarmSpecOrchestration>) -> Self {
+        self.inner.orchestration = Some(orchestration.into());
+        self
+    }
+
+    /// Set the [`raft`](SwarmSpec::raft) field.
+    pub fn raft(mut self, raft: impl Into<SwarmSpecRaft>) -> Self {
+        self.inner.raft = Some(raft.into());
+        self
+    }
+
+    /// Set the [`dispatcher`](SwarmSpec::dispatcher) field.
+    pub fn dispatcher(mut self, dispatcher: impl Into<SwarmSpecDispatcher>) -> Self {
+        self.inner.dispatcher = Some(dispatcher.into());
+        self
+    }
+
+    /// Set the [`ca_config`](SwarmSpec::ca_config) field.
+    pub fn ca_config(mut self, ca_config: impl Into<SwarmSpecCaConfig>) -> Self {
+        self.inner.ca_config = Some(ca_config.into());
+        self
+    }
+
+    /// Set the [`encryption_config`](SwarmSpec::encryption_config) field.
+    pub fn encryption_config(mut self, encryption_config: impl Into<SwarmSpecEncryptionConfig>) -> Self {
+        self.inner.encryption_config = Some(encryption_config.into());
+        self
+    }
+
+    /// Set the [`task_defaults`](SwarmSpec::task_defaults) field.
+    pub fn task_defaults(mut self, task_defaults: impl Into<SwarmSpecTaskDefaults>) -> Self {
+        self.inner.task_defaults = Some(task_defaults.into());
+        self
+    }
+
+    /// Build the [SwarmSpec](SwarmSpec).
+    pub fn build(self) -> SwarmSpec {
+        self.inner
+    }
+}
+
+impl From<SwarmSpecBuilder> for SwarmSpec {
+    fn from(builder: SwarmSpecBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// CA configuration.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SwarmSpecCaConfig {
@@ -6415,6 +9158,7 @@ pub struct SwarmSpecCaConfigExternalCas {
     /// Protocol for communication with the external CA (currently only `cfssl` is supported). 
     #[serde(rename = "Protocol")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub protocol: Option<SwarmSpecCaConfigExternalCasProtocolEnum>,
 
     /// URL where certificate signing requests should be sent. 
@@ -6742,11 +9486,13 @@ pub struct SystemInfo {
     /// The driver to use for managing cgroups. 
     #[serde(rename = "CgroupDriver")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub cgroup_driver: Option<SystemInfoCgroupDriverEnum>,
 
     /// The version of the cgroup. 
     #[serde(rename = "CgroupVersion")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub cgroup_version: Option<SystemInfoCgroupVersionEnum>,
 
     /// Number of event listeners subscribed.
@@ -6859,6 +9605,7 @@ pub struct SystemInfo {
     /// Represents the isolation technology to use as a default for containers. The supported values are platform-specific.  If no isolation value is specified on daemon start, on Windows client, the default is `hyperv`, and on Windows server, the default is `process`.  This option is currently not used on other platforms. 
     #[serde(rename = "Isolation")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub isolation: Option<SystemInfoIsolationEnum>,
 
     /// Name and, optional, path of the `docker-init` binary.  If the path is omitted, the daemon searches the host's `$PATH` for the binary and uses the first result. 
@@ -7220,6 +9967,7 @@ pub struct Task {
 
     #[serde(rename = "DesiredState")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub desired_state: Option<TaskState>,
 
     /// If the Service this Task belongs to is a job-mode service, contains the JobIteration of the Service this Task was created for. Absent if the Task was created for a Replicated or Global Service. 
@@ -7277,6 +10025,92 @@ pub struct TaskSpec {
 
 }
 
+impl TaskSpec {
+    /// Start building a [TaskSpec](TaskSpec), with every field unset.
+    pub fn builder() -> TaskSpecBuilder {
+        TaskSpecBuilder::default()
+    }
+}
+
+/// Builder for [TaskSpec](TaskSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSpecBuilder {
+    inner: TaskSpec,
+}
+
+impl TaskSpecBuilder {
+    /// Set the [`plugin_spec`](TaskSpec::plugin_spec) field.
+    pub fn plugin_spec(mut self, plugin_spec: impl Into<TaskSpecPluginSpec>) -> Self {
+        self.inner.plugin_spec = Some(plugin_spec.into());
+        self
+    }
+
+    /// Set the [`container_spec`](TaskSpec::container_spec) field.
+    pub fn container_spec(mut self, container_spec: impl Into<TaskSpecContainerSpec>) -> Self {
+        self.inner.container_spec = Some(container_spec.into());
+        self
+    }
+
+    /// Set the [`network_attachment_spec`](TaskSpec::network_attachment_spec) field.
+    pub fn network_attachment_spec(mut self, network_attachment_spec: impl Into<TaskSpecNetworkAttachmentSpec>) -> Self {
+        self.inner.network_attachment_spec = Some(network_attachment_spec.into());
+        self
+    }
+
+    /// Set the [`resources`](TaskSpec::resources) field.
+    pub fn resources(mut self, resources: impl Into<TaskSpecResources>) -> Self {
+        self.inner.resources = Some(resources.into());
+        self
+    }
+
+    /// Set the [`restart_policy`](TaskSpec::restart_policy) field.
+    pub fn restart_policy(mut self, restart_policy: impl Into<TaskSpecRestartPolicy>) -> Self {
+        self.inner.restart_policy = Some(restart_policy.into());
+        self
+    }
+
+    /// Set the [`placement`](TaskSpec::placement) field.
+    pub fn placement(mut self, placement: impl Into<TaskSpecPlacement>) -> Self {
+        self.inner.placement = Some(placement.into());
+        self
+    }
+
+    /// Set the [`force_update`](TaskSpec::force_update) field.
+    pub fn force_update(mut self, force_update: impl Into<i64>) -> Self {
+        self.inner.force_update = Some(force_update.into());
+        self
+    }
+
+    /// Set the [`runtime`](TaskSpec::runtime) field.
+    pub fn runtime(mut self, runtime: impl Into<String>) -> Self {
+        self.inner.runtime = Some(runtime.into());
+        self
+    }
+
+    /// Set the [`networks`](TaskSpec::networks) field.
+    pub fn networks(mut self, networks: impl Into<Vec<NetworkAttachmentConfig>>) -> Self {
+        self.inner.networks = Some(networks.into());
+        self
+    }
+
+    /// Set the [`log_driver`](TaskSpec::log_driver) field.
+    pub fn log_driver(mut self, log_driver: impl Into<TaskSpecLogDriver>) -> Self {
+        self.inner.log_driver = Some(log_driver.into());
+        self
+    }
+
+    /// Build the [TaskSpec](TaskSpec).
+    pub fn build(self) -> TaskSpec {
+        self.inner
+    }
+}
+
+impl From<TaskSpecBuilder> for TaskSpec {
+    fn from(builder: TaskSpecBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Container spec for the service.  <p><br /></p>  > **Note**: ContainerSpec, NetworkAttachmentSpec, and PluginSpec are > mutually exclusive. PluginSpec is only used when the Runtime field > is set to `plugin`. NetworkAttachmentSpec is used when the Runtime > field is set to `attachment`. 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TaskSpecContainerSpec {
@@ -7390,6 +10224,7 @@ pub struct TaskSpecContainerSpec {
     /// Isolation technology of the containers running the service. (Windows only) 
     #[serde(rename = "Isolation")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub isolation: Option<TaskSpecContainerSpecIsolationEnum>,
 
     /// Run an init inside the container that forwards signals and reaps processes. This field is omitted if empty, and the default (as configured on the daemon) is used. 
@@ -7419,6 +10254,200 @@ pub struct TaskSpecContainerSpec {
 
 }
 
+impl TaskSpecContainerSpec {
+    /// Start building a [TaskSpecContainerSpec](TaskSpecContainerSpec), with every field unset.
+    pub fn builder() -> TaskSpecContainerSpecBuilder {
+        TaskSpecContainerSpecBuilder::default()
+    }
+}
+
+/// Builder for [TaskSpecContainerSpec](TaskSpecContainerSpec).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSpecContainerSpecBuilder {
+    inner: TaskSpecContainerSpec,
+}
+
+impl TaskSpecContainerSpecBuilder {
+    /// Set the [`image`](TaskSpecContainerSpec::image) field.
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.inner.image = Some(image.into());
+        self
+    }
+
+    /// Set the [`labels`](TaskSpecContainerSpec::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`command`](TaskSpecContainerSpec::command) field.
+    pub fn command(mut self, command: impl Into<Vec<String>>) -> Self {
+        self.inner.command = Some(command.into());
+        self
+    }
+
+    /// Set the [`args`](TaskSpecContainerSpec::args) field.
+    pub fn args(mut self, args: impl Into<Vec<String>>) -> Self {
+        self.inner.args = Some(args.into());
+        self
+    }
+
+    /// Set the [`hostname`](TaskSpecContainerSpec::hostname) field.
+    pub fn hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.inner.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Set the [`env`](TaskSpecContainerSpec::env) field.
+    pub fn env(mut self, env: impl Into<Vec<String>>) -> Self {
+        self.inner.env = Some(env.into());
+        self
+    }
+
+    /// Set the [`dir`](TaskSpecContainerSpec::dir) field.
+    pub fn dir(mut self, dir: impl Into<String>) -> Self {
+        self.inner.dir = Some(dir.into());
+        self
+    }
+
+    /// Set the [`user`](TaskSpecContainerSpec::user) field.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.inner.user = Some(user.into());
+        self
+    }
+
+    /// Set the [`groups`](TaskSpecContainerSpec::groups) field.
+    pub fn groups(mut self, groups: impl Into<Vec<String>>) -> Self {
+        self.inner.groups = Some(groups.into());
+        self
+    }
+
+    /// Set the [`privileges`](TaskSpecContainerSpec::privileges) field.
+    pub fn privileges(mut self, privileges: impl Into<TaskSpecContainerSpecPrivileges>) -> Self {
+        self.inner.privileges = Some(privileges.into());
+        self
+    }
+
+    /// Set the [`tty`](TaskSpecContainerSpec::tty) field.
+    pub fn tty(mut self, tty: impl Into<bool>) -> Self {
+        self.inner.tty = Some(tty.into());
+        self
+    }
+
+    /// Set the [`open_stdin`](TaskSpecContainerSpec::open_stdin) field.
+    pub fn open_stdin(mut self, open_stdin: impl Into<bool>) -> Self {
+        self.inner.open_stdin = Some(open_stdin.into());
+        self
+    }
+
+    /// Set the [`read_only`](TaskSpecContainerSpec::read_only) field.
+    pub fn read_only(mut self, read_only: impl Into<bool>) -> Self {
+        self.inner.read_only = Some(read_only.into());
+        self
+    }
+
+    /// Set the [`mounts`](TaskSpecContainerSpec::mounts) field.
+    pub fn mounts(mut self, mounts: impl Into<Vec<Mount>>) -> Self {
+        self.inner.mounts = Some(mounts.into());
+        self
+    }
+
+    /// Set the [`stop_signal`](TaskSpecContainerSpec::stop_signal) field.
+    pub fn stop_signal(mut self, stop_signal: impl Into<String>) -> Self {
+        self.inner.stop_signal = Some(stop_signal.into());
+        self
+    }
+
+    /// Set the [`stop_grace_period`](TaskSpecContainerSpec::stop_grace_period) field.
+    pub fn stop_grace_period(mut self, stop_grace_period: impl Into<i64>) -> Self {
+        self.inner.stop_grace_period = Some(stop_grace_period.into());
+        self
+    }
+
+    /// Set the [`health_check`](TaskSpecContainerSpec::health_check) field.
+    pub fn health_check(mut self, health_check: impl Into<HealthConfig>) -> Self {
+        self.inner.health_check = Some(health_check.into());
+        self
+    }
+
+    /// Set the [`hosts`](TaskSpecContainerSpec::hosts) field.
+    pub fn hosts(mut self, hosts: impl Into<Vec<String>>) -> Self {
+        self.inner.hosts = Some(hosts.into());
+        self
+    }
+
+    /// Set the [`dns_config`](TaskSpecContainerSpec::dns_config) field.
+    pub fn dns_config(mut self, dns_config: impl Into<TaskSpecContainerSpecDnsConfig>) -> Self {
+        self.inner.dns_config = Some(dns_config.into());
+        self
+    }
+
+    /// Set the [`secrets`](TaskSpecContainerSpec::secrets) field.
+    pub fn secrets(mut self, secrets: impl Into<Vec<TaskSpecContainerSpecSecrets>>) -> Self {
+        self.inner.secrets = Some(secrets.into());
+        self
+    }
+
+    /// Set the [`oom_score_adj`](TaskSpecContainerSpec::oom_score_adj) field.
+    pub fn oom_score_adj(mut self, oom_score_adj: impl Into<i64>) -> Self {
+        self.inner.oom_score_adj = Some(oom_score_adj.into());
+        self
+    }
+
+    /// Set the [`configs`](TaskSpecContainerSpec::configs) field.
+    pub fn configs(mut self, configs: impl Into<Vec<TaskSpecContainerSpecConfigs>>) -> Self {
+        self.inner.configs = Some(configs.into());
+        self
+    }
+
+    /// Set the [`isolation`](TaskSpecContainerSpec::isolation) field.
+    pub fn isolation(mut self, isolation: impl Into<TaskSpecContainerSpecIsolationEnum>) -> Self {
+        self.inner.isolation = Some(isolation.into());
+        self
+    }
+
+    /// Set the [`init`](TaskSpecContainerSpec::init) field.
+    pub fn init(mut self, init: impl Into<bool>) -> Self {
+        self.inner.init = Some(init.into());
+        self
+    }
+
+    /// Set the [`sysctls`](TaskSpecContainerSpec::sysctls) field.
+    pub fn sysctls(mut self, sysctls: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.sysctls = Some(sysctls.into());
+        self
+    }
+
+    /// Set the [`capability_add`](TaskSpecContainerSpec::capability_add) field.
+    pub fn capability_add(mut self, capability_add: impl Into<Vec<String>>) -> Self {
+        self.inner.capability_add = Some(capability_add.into());
+        self
+    }
+
+    /// Set the [`capability_drop`](TaskSpecContainerSpec::capability_drop) field.
+    pub fn capability_drop(mut self, capability_drop: impl Into<Vec<String>>) -> Self {
+        self.inner.capability_drop = Some(capability_drop.into());
+        self
+    }
+
+    /// Set the [`ulimits`](TaskSpecContainerSpec::ulimits) field.
+    pub fn ulimits(mut self, ulimits: impl Into<Vec<ResourcesUlimits>>) -> Self {
+        self.inner.ulimits = Some(ulimits.into());
+        self
+    }
+
+    /// Build the [TaskSpecContainerSpec](TaskSpecContainerSpec).
+    pub fn build(self) -> TaskSpecContainerSpec {
+        self.inner
+    }
+}
+
+impl From<TaskSpecContainerSpecBuilder> for TaskSpecContainerSpec {
+    fn from(builder: TaskSpecContainerSpecBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum TaskSpecContainerSpecIsolationEnum { 
@@ -7592,6 +10621,7 @@ pub struct TaskSpecContainerSpecPrivileges {
 pub struct TaskSpecContainerSpecPrivilegesAppArmor {
     #[serde(rename = "Mode")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub mode: Option<TaskSpecContainerSpecPrivilegesAppArmorModeEnum>,
 
 }
@@ -7695,6 +10725,7 @@ pub struct TaskSpecContainerSpecPrivilegesSeLinuxContext {
 pub struct TaskSpecContainerSpecPrivilegesSeccomp {
     #[serde(rename = "Mode")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub mode: Option<TaskSpecContainerSpecPrivilegesSeccompModeEnum>,
 
     /// The custom seccomp profile as a json object
@@ -7784,6 +10815,44 @@ pub struct TaskSpecLogDriver {
 
 }
 
+impl TaskSpecLogDriver {
+    /// Start building a [TaskSpecLogDriver](TaskSpecLogDriver), with every field unset.
+    pub fn builder() -> TaskSpecLogDriverBuilder {
+        TaskSpecLogDriverBuilder::default()
+    }
+}
+
+/// Builder for [TaskSpecLogDriver](TaskSpecLogDriver).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSpecLogDriverBuilder {
+    inner: TaskSpecLogDriver,
+}
+
+impl TaskSpecLogDriverBuilder {
+    /// Set the [`name`](TaskSpecLogDriver::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`options`](TaskSpecLogDriver::options) field.
+    pub fn options(mut self, options: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.options = Some(options.into());
+        self
+    }
+
+    /// Build the [TaskSpecLogDriver](TaskSpecLogDriver).
+    pub fn build(self) -> TaskSpecLogDriver {
+        self.inner
+    }
+}
+
+impl From<TaskSpecLogDriverBuilder> for TaskSpecLogDriver {
+    fn from(builder: TaskSpecLogDriverBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Read-only spec type for non-swarm containers attached to swarm overlay networks.  <p><br /></p>  > **Note**: ContainerSpec, NetworkAttachmentSpec, and PluginSpec are > mutually exclusive. PluginSpec is only used when the Runtime field > is set to `plugin`. NetworkAttachmentSpec is used when the Runtime > field is set to `attachment`. 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TaskSpecNetworkAttachmentSpec {
@@ -7818,6 +10887,56 @@ pub struct TaskSpecPlacement {
 
 }
 
+impl TaskSpecPlacement {
+    /// Start building a [TaskSpecPlacement](TaskSpecPlacement), with every field unset.
+    pub fn builder() -> TaskSpecPlacementBuilder {
+        TaskSpecPlacementBuilder::default()
+    }
+}
+
+/// Builder for [TaskSpecPlacement](TaskSpecPlacement).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSpecPlacementBuilder {
+    inner: TaskSpecPlacement,
+}
+
+impl TaskSpecPlacementBuilder {
+    /// Set the [`constraints`](TaskSpecPlacement::constraints) field.
+    pub fn constraints(mut self, constraints: impl Into<Vec<String>>) -> Self {
+        self.inner.constraints = Some(constraints.into());
+        self
+    }
+
+    /// Set the [`preferences`](TaskSpecPlacement::preferences) field.
+    pub fn preferences(mut self, preferences: impl Into<Vec<TaskSpecPlacementPreferences>>) -> Self {
+        self.inner.preferences = Some(preferences.into());
+        self
+    }
+
+    /// Set the [`max_replicas`](TaskSpecPlacement::max_replicas) field.
+    pub fn max_replicas(mut self, max_replicas: impl Into<i64>) -> Self {
+        self.inner.max_replicas = Some(max_replicas.into());
+        self
+    }
+
+    /// Set the [`platforms`](TaskSpecPlacement::platforms) field.
+    pub fn platforms(mut self, platforms: impl Into<Vec<Platform>>) -> Self {
+        self.inner.platforms = Some(platforms.into());
+        self
+    }
+
+    /// Build the [TaskSpecPlacement](TaskSpecPlacement).
+    pub fn build(self) -> TaskSpecPlacement {
+        self.inner
+    }
+}
+
+impl From<TaskSpecPlacementBuilder> for TaskSpecPlacement {
+    fn from(builder: TaskSpecPlacementBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TaskSpecPlacementPreferences {
     #[serde(rename = "Spread")]
@@ -7874,12 +10993,51 @@ pub struct TaskSpecResources {
 
 }
 
+impl TaskSpecResources {
+    /// Start building a [TaskSpecResources](TaskSpecResources), with every field unset.
+    pub fn builder() -> TaskSpecResourcesBuilder {
+        TaskSpecResourcesBuilder::default()
+    }
+}
+
+/// Builder for [TaskSpecResources](TaskSpecResources).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSpecResourcesBuilder {
+    inner: TaskSpecResources,
+}
+
+impl TaskSpecResourcesBuilder {
+    /// Set the [`limits`](TaskSpecResources::limits) field.
+    pub fn limits(mut self, limits: impl Into<Limit>) -> Self {
+        self.inner.limits = Some(limits.into());
+        self
+    }
+
+    /// Set the [`reservations`](TaskSpecResources::reservations) field.
+    pub fn reservations(mut self, reservations: impl Into<ResourceObject>) -> Self {
+        self.inner.reservations = Some(reservations.into());
+        self
+    }
+
+    /// Build the [TaskSpecResources](TaskSpecResources).
+    pub fn build(self) -> TaskSpecResources {
+        self.inner
+    }
+}
+
+impl From<TaskSpecResourcesBuilder> for TaskSpecResources {
+    fn from(builder: TaskSpecResourcesBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Specification for the restart policy which applies to containers created as part of this service. 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TaskSpecRestartPolicy {
     /// Condition for restart.
     #[serde(rename = "Condition")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub condition: Option<TaskSpecRestartPolicyConditionEnum>,
 
     /// Delay between restart attempts.
@@ -7899,6 +11057,56 @@ pub struct TaskSpecRestartPolicy {
 
 }
 
+impl TaskSpecRestartPolicy {
+    /// Start building a [TaskSpecRestartPolicy](TaskSpecRestartPolicy), with every field unset.
+    pub fn builder() -> TaskSpecRestartPolicyBuilder {
+        TaskSpecRestartPolicyBuilder::default()
+    }
+}
+
+/// Builder for [TaskSpecRestartPolicy](TaskSpecRestartPolicy).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSpecRestartPolicyBuilder {
+    inner: TaskSpecRestartPolicy,
+}
+
+impl TaskSpecRestartPolicyBuilder {
+    /// Set the [`condition`](TaskSpecRestartPolicy::condition) field.
+    pub fn condition(mut self, condition: impl Into<TaskSpecRestartPolicyConditionEnum>) -> Self {
+        self.inner.condition = Some(condition.into());
+        self
+    }
+
+    /// Set the [`delay`](TaskSpecRestartPolicy::delay) field.
+    pub fn delay(mut self, delay: impl Into<i64>) -> Self {
+        self.inner.delay = Some(delay.into());
+        self
+    }
+
+    /// Set the [`max_attempts`](TaskSpecRestartPolicy::max_attempts) field.
+    pub fn max_attempts(mut self, max_attempts: impl Into<i64>) -> Self {
+        self.inner.max_attempts = Some(max_attempts.into());
+        self
+    }
+
+    /// Set the [`window`](TaskSpecRestartPolicy::window) field.
+    pub fn window(mut self, window: impl Into<i64>) -> Self {
+        self.inner.window = Some(window.into());
+        self
+    }
+
+    /// Build the [TaskSpecRestartPolicy](TaskSpecRestartPolicy).
+    pub fn build(self) -> TaskSpecRestartPolicy {
+        self.inner
+    }
+}
+
+impl From<TaskSpecRestartPolicyBuilder> for TaskSpecRestartPolicy {
+    fn from(builder: TaskSpecRestartPolicyBuilder) -> Self {
+        builder.build()
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Eq, Ord)]
 pub enum TaskSpecRestartPolicyConditionEnum { 
@@ -8053,6 +11261,7 @@ pub struct TaskStatus {
 
     #[serde(rename = "State")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_lenient_enum")]
     pub state: Option<TaskState>,
 
     #[serde(rename = "Message")]
@@ -8157,7 +11366,10 @@ pub struct Volume {
     /// The level at which the volume exists. Either `global` for cluster-wide, or `local` for machine level. 
     #[serde(rename = "Scope")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(with = "::serde_with::As::<::serde_with::NoneAsEmptyString>")]
+    #[serde(
+        serialize_with = "::serde_with::As::<::serde_with::NoneAsEmptyString>::serialize",
+        deserialize_with = "deserialize_nonempty_lenient_enum"
+    )]
     pub scope: Option<VolumeScopeEnum>,
 
     #[serde(rename = "ClusterVolume")]
@@ -8248,6 +11460,62 @@ pub struct VolumeCreateOptions {
 
 }
 
+impl VolumeCreateOptions {
+    /// Start building a [VolumeCreateOptions](VolumeCreateOptions), with every field unset.
+    pub fn builder() -> VolumeCreateOptionsBuilder {
+        VolumeCreateOptionsBuilder::default()
+    }
+}
+
+/// Builder for [VolumeCreateOptions](VolumeCreateOptions).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VolumeCreateOptionsBuilder {
+    inner: VolumeCreateOptions,
+}
+
+impl VolumeCreateOptionsBuilder {
+    /// Set the [`name`](VolumeCreateOptions::name) field.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    /// Set the [`driver`](VolumeCreateOptions::driver) field.
+    pub fn driver(mut self, driver: impl Into<String>) -> Self {
+        self.inner.driver = Some(driver.into());
+        self
+    }
+
+    /// Set the [`driver_opts`](VolumeCreateOptions::driver_opts) field.
+    pub fn driver_opts(mut self, driver_opts: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.driver_opts = Some(driver_opts.into());
+        self
+    }
+
+    /// Set the [`labels`](VolumeCreateOptions::labels) field.
+    pub fn labels(mut self, labels: impl Into<HashMap<String, String>>) -> Self {
+        self.inner.labels = Some(labels.into());
+        self
+    }
+
+    /// Set the [`cluster_volume_spec`](VolumeCreateOptions::cluster_volume_spec) field.
+    pub fn cluster_volume_spec(mut self, cluster_volume_spec: impl Into<ClusterVolumeSpec>) -> Self {
+        self.inner.cluster_volume_spec = Some(cluster_volume_spec.into());
+        self
+    }
+
+    /// Build the [VolumeCreateOptions](VolumeCreateOptions).
+    pub fn build(self) -> VolumeCreateOptions {
+        self.inner
+    }
+}
+
+impl From<VolumeCreateOptionsBuilder> for VolumeCreateOptions {
+    fn from(builder: VolumeCreateOptionsBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// Volume list response
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct VolumeListResponse {
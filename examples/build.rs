@@ -46,6 +46,8 @@ async fn main() {
         session: None,
         #[cfg(feature = "buildkit")]
         outputs: None,
+        #[cfg(feature = "buildkit")]
+        secrets: HashMap::new(),
         version: bollard::image::BuilderVersion::BuilderV1,
     };
 
@@ -1,8 +1,17 @@
 //! Credentials management, for access to the Docker Hub or a custom Registry.
 
+#[cfg(any(feature = "image", feature = "swarm"))]
+use base64::engine::general_purpose::URL_SAFE;
 use base64::{engine::general_purpose::STANDARD, Engine};
+use log::warn;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::errors::Error;
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[allow(missing_docs)]
@@ -10,22 +19,270 @@ use std::collections::HashMap;
 /// API](crate::Docker::push_image()) or the [Build Image
 /// API](../struct.Docker.html#method.build_image).
 pub struct DockerCredentials {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub auth: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub serveraddress: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub identitytoken: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub registrytoken: Option<String>,
 }
 
+/// Credentials of several registries, keyed by registry address, e.g.
+/// `https://index.docker.io/v1/` or `ghcr.io`, sent as the `X-Registry-Config` header of the
+/// [Build Image API](../struct.Docker.html#method.build_image).
+pub type RegistryConfig = HashMap<String, DockerCredentials>;
+
+#[cfg(any(feature = "image", feature = "swarm"))]
 pub(crate) enum DockerCredentialsHeader {
     /// Credentials of a single registry sent as an X-Registry-Auth header
     Auth(Option<DockerCredentials>),
     /// Credentials of multiple registries sent as an X-Registry-Config header
-    Config(Option<HashMap<String, DockerCredentials>>),
+    #[cfg(feature = "image")]
+    Config(Option<RegistryConfig>),
 }
 
+#[cfg(any(feature = "image", feature = "swarm"))]
 pub(crate) fn base64_url_encode(payload: &str) -> String {
-    STANDARD.encode(payload)
+    URL_SAFE.encode(payload)
+}
+
+/// The username a credential helper reports when its secret is an identity token.
+const IDENTITY_TOKEN_USERNAME: &str = "<token>";
+
+/// Registry section of the Docker config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigFile {
+    #[serde(default)]
+    auths: HashMap<String, DockerCredentials>,
+    creds_store: Option<String>,
+    #[serde(default)]
+    cred_helpers: HashMap<String, String>,
+}
+
+/// Response of the `get` command of a credential helper.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct HelperCredentials {
+    username: String,
+    secret: String,
+}
+
+/// The path of the Docker config file: `config.json` in the `DOCKER_CONFIG` directory, or in
+/// `~/.docker`.
+pub fn docker_config_path() -> Result<PathBuf, Error> {
+    if let Some(dir) = env::var_os("DOCKER_CONFIG") {
+        return Ok(PathBuf::from(dir).join("config.json"));
+    }
+    let home = env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })
+        .ok_or(Error::NoHomePathError)?;
+    Ok(PathBuf::from(home).join(".docker").join("config.json"))
+}
+
+/// Load the credentials of every registry of the [Docker config file](docker_config_path()),
+/// for the [Build Image API](../struct.Docker.html#method.build_image). A missing file yields no
+/// credentials.
+///
+/// See [`registry_config_from_file`] for how the entries are resolved.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::auth::registry_config_from_docker_config;
+/// use bollard::image::BuildImageOptions;
+///
+/// let credentials = registry_config_from_docker_config().ok();
+/// docker.build_image(
+///     BuildImageOptions {
+///         dockerfile: "Dockerfile",
+///         t: "my-image",
+///         ..Default::default()
+///     },
+///     credentials,
+///     None,
+/// );
+/// ```
+pub fn registry_config_from_docker_config() -> Result<RegistryConfig, Error> {
+    let path = docker_config_path()?;
+    if !path.exists() {
+        return Ok(RegistryConfig::new());
+    }
+    registry_config_from_file(path)
+}
+
+/// Load the credentials of every registry of a Docker config file.
+///
+/// The base64 `auth` of the `auths` entries is decoded into a username and password, which the
+/// daemon expects. Registries of the `credsStore` and `credHelpers` entries are resolved by
+/// running their `docker-credential-*` helper, which blocks the current thread: a registry whose
+/// helper fails is logged and skipped.
+pub fn registry_config_from_file<P: AsRef<Path>>(path: P) -> Result<RegistryConfig, Error> {
+    let contents = std::fs::read(path)?;
+    let config: ConfigFile = serde_json::from_slice(&contents)?;
+
+    let mut registry_config = RegistryConfig::new();
+    for (registry, credentials) in config.auths {
+        registry_config.insert(registry.clone(), decode_auth(registry, credentials));
+    }
+
+    let mut helpers: Vec<(String, String)> = Vec::new();
+    if let Some(store) = config.creds_store.filter(|store| !store.is_empty()) {
+        match run_helper(&store, "list", "")
+            .and_then(|out| Ok(serde_json::from_slice::<HashMap<String, String>>(&out)?))
+        {
+            Ok(registries) => helpers.extend(
+                registries
+                    .into_keys()
+                    .map(|registry| (registry, store.clone())),
+            ),
+            Err(e) => warn!("unable to list the registries of the credential store: {e}"),
+        }
+        // with a store, the entries of `auths` are usually empty placeholders
+        for registry in registry_config.keys() {
+            if !helpers.iter().any(|(known, _)| known == registry) {
+                helpers.push((registry.clone(), store.clone()));
+            }
+        }
+    }
+    helpers.retain(|(registry, _)| !config.cred_helpers.contains_key(registry));
+    helpers.extend(config.cred_helpers);
+
+    for (registry, helper) in helpers {
+        match helper_credentials(&helper, &registry) {
+            Ok(credentials) => {
+                registry_config.insert(registry, credentials);
+            }
+            Err(e) => warn!("unable to get the credentials of {registry}: {e}"),
+        }
+    }
+
+    Ok(registry_config)
+}
+
+/// Fill the username and password from the base64 `auth` of a config file entry.
+fn decode_auth(registry: String, mut credentials: DockerCredentials) -> DockerCredentials {
+    if let Some(auth) = credentials.auth.take().filter(|auth| !auth.is_empty()) {
+        let decoded = STANDARD
+            .decode(auth.trim())
+            .ok()
+            .and_then(|decoded| String::from_utf8(decoded).ok());
+        match decoded
+            .as_deref()
+            .and_then(|decoded| decoded.split_once(':'))
+        {
+            Some((username, password)) => {
+                credentials.username = Some(String::from(username));
+                credentials.password = Some(String::from(password));
+            }
+            None => {
+                warn!("invalid auth for {registry} in the Docker config file");
+                credentials.auth = Some(auth);
+            }
+        }
+    }
+    credentials.serveraddress.get_or_insert(registry);
+    credentials
+}
+
+fn helper_credentials(helper: &str, registry: &str) -> Result<DockerCredentials, Error> {
+    let out = run_helper(helper, "get", registry)?;
+    let HelperCredentials { username, secret } = serde_json::from_slice(&out)?;
+
+    Ok(if username == IDENTITY_TOKEN_USERNAME {
+        DockerCredentials {
+            identitytoken: Some(secret),
+            serveraddress: Some(String::from(registry)),
+            ..Default::default()
+        }
+    } else {
+        DockerCredentials {
+            username: Some(username),
+            password: Some(secret),
+            serveraddress: Some(String::from(registry)),
+            ..Default::default()
+        }
+    })
+}
+
+fn run_helper(helper: &str, command: &str, input: &str) -> Result<Vec<u8>, Error> {
+    let helper_error = |message: String| Error::CredentialHelperError {
+        helper: String::from(helper),
+        message,
+    };
+
+    let mut child = Command::new(format!("docker-credential-{helper}"))
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| helper_error(e.to_string()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(helper_error(format!("{} {}", stdout.trim(), stderr.trim())));
+    }
+
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{registry_config_from_file, DockerCredentials};
+
+    #[test]
+    fn test_registry_config_from_file() {
+        let dir = std::env::temp_dir().join(format!("bollard-auth-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "auths": {
+                    "https://index.docker.io/v1/": { "auth": "dXNlcjpwYTpzcw==" },
+                    "registry.example.com": { "identitytoken": "token" }
+                },
+                "credHelpers": { "missing.example.com": "bollard-test-missing" }
+            }"#,
+        )
+        .unwrap();
+
+        let config = registry_config_from_file(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.len(), 2);
+        assert_eq!(
+            config["https://index.docker.io/v1/"],
+            DockerCredentials {
+                username: Some(String::from("user")),
+                password: Some(String::from("pa:ss")),
+                serveraddress: Some(String::from("https://index.docker.io/v1/")),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            config["registry.example.com"].identitytoken.as_deref(),
+            Some("token")
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "image", feature = "swarm"))]
+    fn test_base64_url_encode() {
+        assert_eq!(super::base64_url_encode("???"), "Pz8_");
+    }
 }
@@ -0,0 +1,357 @@
+//! Batch: run an operation on many containers or images at once, with a bound on the number of
+//! concurrent requests, and report the result of every item.
+//!
+//! A failing item does not stop the batch: the [Batch Report](BatchReport) lists the items in
+//! the order they were given, each with its own result.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::RemoveContainerOptions;
+//!
+//! async move {
+//!     let report = docker
+//!         .batch()
+//!         .remove_containers(
+//!             ["web-1", "web-2", "web-3"],
+//!             Some(RemoveContainerOptions {
+//!                 force: true,
+//!                 ..Default::default()
+//!             }),
+//!             2,
+//!         )
+//!         .await;
+//!
+//!     for (name, error) in report.failed() {
+//!         eprintln!("failed to remove {name}: {error}");
+//!     }
+//! };
+//! ```
+
+#[cfg(feature = "image")]
+use futures_util::future::ready;
+#[cfg(feature = "image")]
+use futures_util::stream::TryStreamExt;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+
+use std::future::Future;
+
+use super::Docker;
+use crate::container::{RemoveContainerOptions, StopContainerOptions};
+use crate::errors::Error;
+#[cfg(feature = "image")]
+use crate::image::CreateImageOptions;
+#[cfg(feature = "image")]
+use crate::reference::Reference;
+
+/// The result of the operation on one item of a batch.
+#[derive(Debug)]
+pub struct BatchItem<T> {
+    /// The container or image the operation ran on, as it was given.
+    pub name: String,
+    /// The result of the operation.
+    pub result: Result<T, Error>,
+}
+
+/// The results of a batch, in the order of its items.
+#[derive(Debug)]
+pub struct BatchReport<T> {
+    /// The result of every item.
+    pub items: Vec<BatchItem<T>>,
+}
+
+impl<T> BatchReport<T> {
+    /// Whether the operation succeeded on every item.
+    pub fn is_success(&self) -> bool {
+        self.items.iter().all(|item| item.result.is_ok())
+    }
+
+    /// The items the operation succeeded on, with their result.
+    pub fn succeeded(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.items
+            .iter()
+            .filter_map(|item| Some((item.name.as_str(), item.result.as_ref().ok()?)))
+    }
+
+    /// The items the operation failed on, with their error.
+    pub fn failed(&self) -> impl Iterator<Item = (&str, &Error)> {
+        self.items
+            .iter()
+            .filter_map(|item| Some((item.name.as_str(), item.result.as_ref().err()?)))
+    }
+}
+
+/// Run the operation on every item, with at most `concurrency` operations at once.
+pub(crate) async fn run<T, F, Fut>(
+    names: Vec<String>,
+    concurrency: usize,
+    operation: F,
+) -> BatchReport<T>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut results: Vec<Option<BatchItem<T>>> = names.iter().map(|_| None).collect();
+    let mut pending = names.into_iter().enumerate();
+    let mut running = FuturesUnordered::new();
+
+    loop {
+        while running.len() < concurrency.max(1) {
+            let Some((index, name)) = pending.next() else {
+                break;
+            };
+            let operation = operation(name.clone());
+            running.push(async move {
+                (
+                    index,
+                    BatchItem {
+                        name,
+                        result: operation.await,
+                    },
+                )
+            });
+        }
+        match running.next().await {
+            Some((index, item)) => results[index] = Some(item),
+            None => break,
+        }
+    }
+
+    BatchReport {
+        items: results.into_iter().flatten().collect(),
+    }
+}
+
+/// Bulk operations of the [Batch API](Docker::batch()).
+#[derive(Debug, Clone, Copy)]
+pub struct Batch<'a> {
+    docker: &'a Docker,
+}
+
+impl Batch<'_> {
+    /// ---
+    ///
+    /// # Remove Containers
+    ///
+    /// Remove containers, at most `concurrency` at once.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container names or IDs.
+    ///  - Optional [Remove Container Options](RemoveContainerOptions) struct, for every
+    ///    container.
+    ///  - Maximum number of concurrent requests.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch Report](BatchReport), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async move {
+    ///     let report = docker.batch().remove_containers(["a", "b"], None, 4).await;
+    ///     assert!(report.is_success());
+    /// };
+    /// ```
+    pub async fn remove_containers<I, S>(
+        &self,
+        container_names: I,
+        options: Option<RemoveContainerOptions>,
+        concurrency: usize,
+    ) -> BatchReport<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let names = container_names.into_iter().map(Into::into).collect();
+        run(names, concurrency, |name| async move {
+            self.docker.remove_container(&name, options).await
+        })
+        .await
+    }
+
+    /// ---
+    ///
+    /// # Stop Containers
+    ///
+    /// Stop containers, at most `concurrency` at once.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container names or IDs.
+    ///  - Optional [Stop Container Options](StopContainerOptions) struct, for every container.
+    ///  - Maximum number of concurrent requests.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch Report](BatchReport), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::StopContainerOptions;
+    ///
+    /// let options = StopContainerOptions {
+    ///     t: 5,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.batch().stop_containers(["a", "b"], Some(options), 4);
+    /// ```
+    pub async fn stop_containers<I, S>(
+        &self,
+        container_names: I,
+        options: Option<StopContainerOptions>,
+        concurrency: usize,
+    ) -> BatchReport<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let names = container_names.into_iter().map(Into::into).collect();
+        run(names, concurrency, |name| async move {
+            self.docker.stop_container(&name, options).await
+        })
+        .await
+    }
+
+    /// ---
+    ///
+    /// # Pull Images
+    ///
+    /// Pull images, at most `concurrency` at once, through the [Create Image
+    /// API](Docker::create_image()). Malformed [references](Reference) fail without a request,
+    /// and references without a tag pull the `latest` tag.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image references, e.g. `alpine:3` or `ghcr.io/owner/image@sha256:...`.
+    ///  - Maximum number of concurrent pulls.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch Report](BatchReport), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.batch().pull_images(["alpine", "busybox:1.36"], 2);
+    /// ```
+    #[cfg(feature = "image")]
+    pub async fn pull_images<I, S>(&self, references: I, concurrency: usize) -> BatchReport<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let names = references.into_iter().map(Into::into).collect();
+        run(names, concurrency, |name| async move {
+            let reference = Reference::parse(&name)?;
+            self.docker
+                .create_image(Some(CreateImageOptions::from(&reference)), None, None)
+                .try_for_each(|_| ready(Ok(())))
+                .await
+        })
+        .await
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Batch
+    ///
+    /// Bulk operations on containers and images, with bounded concurrency. See the
+    /// [batch](crate::batch) module.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch](Batch) struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.batch().remove_containers(["a", "b"], None, 4);
+    /// ```
+    pub fn batch(&self) -> Batch<'_> {
+        Batch { docker: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[cfg(feature = "image")]
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::run;
+    use crate::errors::Error;
+    #[cfg(feature = "image")]
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    #[tokio::test]
+    async fn test_run_bounded_concurrency() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        let names = (0..10).map(|i| i.to_string()).collect();
+        let report = run(names, 3, |name| {
+            let running = &running;
+            let max_running = &max_running;
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                match name.parse::<usize>().unwrap() % 4 {
+                    0 => Err(Error::MissingImageError {}),
+                    n => Ok(n),
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+        assert!(!report.is_success());
+        let names: Vec<_> = report.items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]);
+        assert_eq!(
+            report.failed().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["0", "4", "8"]
+        );
+        assert_eq!(report.succeeded().count(), 7);
+    }
+
+    #[cfg(feature = "image")]
+    #[tokio::test]
+    async fn test_pull_images_invalid_reference() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            String::from("HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:0\r\n\r\n"),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let report = docker.batch().pull_images(["alpine", "Not/Valid"], 2).await;
+        assert_eq!(report.succeeded().count(), 1);
+        assert!(matches!(
+            report.failed().collect::<Vec<_>>()[..],
+            [("Not/Valid", Error::InvalidReferenceError { .. })]
+        ));
+    }
+}
@@ -0,0 +1,325 @@
+//! Fixtures and a mock daemon for measuring the client's performance.
+//!
+//! The benchmarks in the `benches` directory are built on this module, so downstream users can
+//! replicate the measurements, or benchmark their own workloads against the same mock daemon.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bollard::bench_utils::{stats_fixture, MockDaemon, MockResponse};
+//! use bollard::container::StatsOptions;
+//! use futures_util::TryStreamExt;
+//!
+//! use std::collections::HashMap;
+//!
+//! async move {
+//!     let mut routes = HashMap::new();
+//!     routes.insert(
+//!         String::from("/containers/bench/stats"),
+//!         MockResponse::json(stats_fixture(1000)),
+//!     );
+//!     let daemon = MockDaemon::start(routes).await.unwrap();
+//!     let docker = daemon.docker().unwrap();
+//!
+//!     let stats = docker
+//!         .stats("bench", Some(StatsOptions { stream: true, ..Default::default() }))
+//!         .try_collect::<Vec<_>>()
+//!         .await
+//!         .unwrap();
+//!     assert_eq!(stats.len(), 1000);
+//! };
+//! ```
+
+use bytes::{BufMut, Bytes, BytesMut};
+use log::trace;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_util::codec::Decoder;
+
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::container::LogOutput;
+use crate::errors::Error;
+use crate::read::{JsonLineDecoder, NewlineLogOutputDecoder};
+use crate::{Docker, API_DEFAULT_VERSION};
+
+const STATS_SAMPLE: &str = r#"{"read":"2024-05-02T12:00:01.000000000Z","preread":"2024-05-02T12:00:00.000000000Z","pids_stats":{"current":5,"limit":18446744073709551615},"blkio_stats":{"io_service_bytes_recursive":[{"major":259,"minor":0,"op":"read","value":4096},{"major":259,"minor":0,"op":"write","value":0}],"io_serviced_recursive":null,"io_queue_recursive":null,"io_service_time_recursive":null,"io_wait_time_recursive":null,"io_merged_recursive":null,"io_time_recursive":null,"sectors_recursive":null},"num_procs":0,"storage_stats":{},"cpu_stats":{"cpu_usage":{"total_usage":31234000,"usage_in_kernelmode":10245000,"usage_in_usermode":20989000},"system_cpu_usage":1791270000000,"online_cpus":8,"throttling_data":{"periods":0,"throttled_periods":0,"throttled_time":0}},"precpu_stats":{"cpu_usage":{"total_usage":31200000,"usage_in_kernelmode":10230000,"usage_in_usermode":20970000},"system_cpu_usage":1791262000000,"online_cpus":8,"throttling_data":{"periods":0,"throttled_periods":0,"throttled_time":0}},"memory_stats":{"usage":4603904,"stats":{"active_anon":0,"active_file":0,"anon":1073152,"anon_thp":0,"file":0,"file_dirty":0,"file_mapped":0,"file_writeback":0,"inactive_anon":1060864,"inactive_file":0,"kernel_stack":49152,"pgactivate":0,"pgdeactivate":0,"pgfault":1275,"pglazyfree":0,"pglazyfreed":0,"pgmajfault":0,"pgrefill":0,"pgscan":0,"pgsteal":0,"shmem":0,"slab":164136,"slab_reclaimable":55936,"slab_unreclaimable":108200,"sock":0,"thp_collapse_alloc":0,"thp_fault_alloc":0,"unevictable":0,"workingset_activate":0,"workingset_nodereclaim":0,"workingset_refault":0},"limit":16484458496},"name":"/bench","id":"4b6a5a1c3bf3f9f4c1a6d1e1a3a8f5d2c9e0b7a6f5e4d3c2b1a0f9e8d7c6b5a4","networks":{"eth0":{"rx_bytes":1066,"rx_packets":13,"rx_errors":0,"rx_dropped":0,"tx_bytes":0,"tx_packets":0,"tx_errors":0,"tx_dropped":0}}}"#;
+
+const EVENT_SAMPLE: &str = r#"{"status":"start","id":"4b6a5a1c3bf3f9f4c1a6d1e1a3a8f5d2c9e0b7a6f5e4d3c2b1a0f9e8d7c6b5a4","from":"alpine:latest","Type":"container","Action":"start","Actor":{"ID":"4b6a5a1c3bf3f9f4c1a6d1e1a3a8f5d2c9e0b7a6f5e4d3c2b1a0f9e8d7c6b5a4","Attributes":{"image":"alpine:latest","name":"bench"}},"scope":"local","time":1714651200,"timeNano":1714651200000000000}"#;
+
+/// `count` newline delimited container stats, as emitted by the [Stats
+/// API](Docker::stats()) when streaming.
+pub fn stats_fixture(count: usize) -> Bytes {
+    json_lines(STATS_SAMPLE, count)
+}
+
+/// `count` newline delimited container events, as emitted by the [Events
+/// API](crate::models::EventMessage).
+pub fn events_fixture(count: usize) -> Bytes {
+    json_lines(EVENT_SAMPLE, count)
+}
+
+fn json_lines(sample: &str, count: usize) -> Bytes {
+    let mut buf = BytesMut::with_capacity((sample.len() + 1) * count);
+    for _ in 0..count {
+        buf.put(sample.as_bytes());
+        buf.put_u8(b'\n');
+    }
+    buf.freeze()
+}
+
+/// `count` multiplexed log frames of `line_len` bytes each, alternating between stdout and
+/// stderr, as emitted by the [Logs API](Docker::logs()) for a container without a TTY.
+pub fn log_frames_fixture(count: usize, line_len: usize) -> Bytes {
+    let line_len = line_len.max(1);
+    let mut buf = BytesMut::with_capacity((line_len + 8) * count);
+    for i in 0..count {
+        buf.put_u8(if i % 2 == 0 { 1 } else { 2 });
+        buf.put_bytes(0, 3);
+        buf.put_u32(line_len as u32);
+        buf.put_bytes(b'x', line_len - 1);
+        buf.put_u8(b'\n');
+    }
+    buf.freeze()
+}
+
+/// A tar archive of `files` files of `file_size` bytes each, as consumed by the [Upload To
+/// Container API](Docker::upload_to_container()).
+pub fn tar_fixture(files: usize, file_size: usize) -> Result<Bytes, Error> {
+    let mut tar = tar::Builder::new(Vec::new());
+    let contents = vec![b'x'; file_size];
+    for i in 0..files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(file_size as u64);
+        header.set_mode(0o644);
+        tar.append_data(&mut header, format!("file-{i}"), &contents[..])?;
+    }
+    Ok(tar.into_inner()?.into())
+}
+
+/// Decode newline delimited JSON with the decoder used by the streaming APIs.
+pub fn decode_json_lines<T>(bytes: &[u8]) -> Result<Vec<T>, Error>
+where
+    T: DeserializeOwned,
+{
+    let mut decoder = JsonLineDecoder::<T>::new();
+    decode_all(&mut decoder, bytes)
+}
+
+/// Demultiplex log frames with the decoder used by the [Logs API](Docker::logs()).
+pub fn demux_log_output(bytes: &[u8]) -> Result<Vec<LogOutput>, Error> {
+    let mut decoder = NewlineLogOutputDecoder::new(false);
+    decode_all(&mut decoder, bytes)
+}
+
+fn decode_all<D>(decoder: &mut D, bytes: &[u8]) -> Result<Vec<D::Item>, Error>
+where
+    D: Decoder,
+    Error: From<D::Error>,
+{
+    let mut buf = BytesMut::from(bytes);
+    let mut items = Vec::new();
+    while let Some(item) = decoder.decode(&mut buf)? {
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// A canned response served by the [Mock Daemon](MockDaemon).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The `Content-Type` header of the response.
+    pub content_type: &'static str,
+    /// The body of the response.
+    pub body: Bytes,
+}
+
+impl MockResponse {
+    /// A successful response with a JSON body.
+    pub fn json(body: impl Into<Bytes>) -> Self {
+        MockResponse {
+            status: 200,
+            content_type: "application/json",
+            body: body.into(),
+        }
+    }
+
+    /// A successful response with a raw stream body, such as multiplexed logs or an archive.
+    pub fn raw(body: impl Into<Bytes>) -> Self {
+        MockResponse {
+            status: 200,
+            content_type: "application/vnd.docker.raw-stream",
+            body: body.into(),
+        }
+    }
+}
+
+/// A minimal HTTP server on a local port, answering requests with canned responses.
+///
+/// Responses are looked up by the request path, without the API version prefix and the query
+/// string, e.g. `/containers/bench/json`. Request bodies are read and discarded, and unknown
+/// paths are answered with a `404`. The server stops when the `MockDaemon` is dropped.
+#[derive(Debug)]
+pub struct MockDaemon {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MockDaemon {
+    /// Start serving the given responses, keyed by path.
+    pub async fn start(routes: HashMap<String, MockResponse>) -> Result<MockDaemon, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let routes = Arc::new(routes);
+
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let routes = Arc::clone(&routes);
+                tokio::spawn(async move {
+                    if let Err(e) = serve(stream, &routes).await {
+                        trace!("mock daemon connection closed: {e}");
+                    }
+                });
+            }
+        });
+
+        Ok(MockDaemon { addr, task })
+    }
+
+    /// The address the daemon listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// A client connected to the daemon.
+    pub fn docker(&self) -> Result<Docker, Error> {
+        Docker::connect_with_http(&format!("tcp://{}", self.addr), 120, API_DEFAULT_VERSION)
+    }
+}
+
+impl Drop for MockDaemon {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(stream: TcpStream, routes: &HashMap<String, MockResponse>) -> io::Result<()> {
+    let mut stream = BufReader::new(stream);
+    let not_found = MockResponse {
+        status: 404,
+        content_type: "application/json",
+        body: Bytes::from_static(br#"{"message":"page not found"}"#),
+    };
+
+    loop {
+        let mut request_line = String::new();
+        if stream.read_line(&mut request_line).await? == 0 {
+            return Ok(());
+        }
+
+        let mut content_length = 0;
+        let mut chunked = false;
+        loop {
+            let mut header = String::new();
+            if stream.read_line(&mut header).await? == 0 || header == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                    chunked = value.trim().eq_ignore_ascii_case("chunked");
+                }
+            }
+        }
+
+        if chunked {
+            discard_chunked(&mut stream).await?;
+        } else {
+            discard(&mut stream, content_length).await?;
+        }
+
+        let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+        let response = routes.get(route(path)).unwrap_or(&not_found);
+
+        let head = format!(
+            "HTTP/1.1 {} Mock\r\nServer: bollard-mock\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            response.status,
+            response.content_type,
+            response.body.len()
+        );
+        let writer = stream.get_mut();
+        writer.write_all(head.as_bytes()).await?;
+        writer.write_all(&response.body).await?;
+        writer.flush().await?;
+    }
+}
+
+/// Strip the query string and the `/v1.xx` version prefix from a request path.
+fn route(path: &str) -> &str {
+    let path = path.split('?').next().unwrap_or(path);
+    match path
+        .strip_prefix("/v")
+        .and_then(|rest| rest.split_once('/'))
+    {
+        Some((version, _))
+            if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.') =>
+        {
+            &path[version.len() + 2..]
+        }
+        _ => path,
+    }
+}
+
+async fn discard(stream: &mut BufReader<TcpStream>, len: u64) -> io::Result<()> {
+    tokio::io::copy(&mut stream.take(len), &mut tokio::io::sink()).await?;
+    Ok(())
+}
+
+async fn discard_chunked(stream: &mut BufReader<TcpStream>) -> io::Result<()> {
+    loop {
+        let mut size = String::new();
+        stream.read_line(&mut size).await?;
+        let size = size.trim().split(';').next().unwrap_or_default();
+        let size = u64::from_str_radix(size, 16)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if size == 0 {
+            // trailers, up to the final empty line
+            loop {
+                let mut line = String::new();
+                if stream.read_line(&mut line).await? == 0 || line == "\r\n" {
+                    return Ok(());
+                }
+            }
+        }
+
+        discard(stream, size + 2).await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{demux_log_output, log_frames_fixture, route};
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route("/v1.47/containers/json?all=true"), "/containers/json");
+        assert_eq!(route("/_ping"), "/_ping");
+        assert_eq!(route("/volumes/v1.47"), "/volumes/v1.47");
+    }
+
+    #[test]
+    fn test_demux_log_output() {
+        let frames = demux_log_output(&log_frames_fixture(3, 16)).unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].as_ref().len(), 16);
+    }
+}
@@ -0,0 +1,258 @@
+//! Compatibility with engines that implement the Docker API, such as Podman.
+//!
+//! Some engines deviate from the Docker API in their responses. The models tolerate the known
+//! deviations: e.g. a missing `Warnings` field when creating a container, or plugin interface
+//! types sent as objects rather than in their string form. Deviations in behaviour are left to
+//! the application, which can branch on the [Server Flavor](ServerFlavor).
+//!
+//! Enum values that this version of the API does not know, as sent by newer daemons, deserialize
+//! into `None` in the fields of the models, rather than failing the whole response. The enums
+//! themselves, e.g. through `FromStr`, still reject unknown values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::compat::ServerFlavor;
+//!
+//! async move {
+//!     let docker = docker.negotiate_version().await.unwrap();
+//!     if docker.server_flavor().await.unwrap() == ServerFlavor::Podman {
+//!         println!("connected to podman");
+//!     }
+//! };
+//! ```
+
+use bytes::Bytes;
+use http::request::Builder;
+use http_body_util::Full;
+use hyper::Method;
+
+use std::fmt;
+
+use crate::docker::BodyType;
+use crate::errors::Error;
+use crate::models::SystemVersion;
+use crate::Docker;
+
+/// The engine serving the Docker API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ServerFlavor {
+    /// The Docker engine.
+    Docker,
+    /// [Podman](https://podman.io/), through its Docker compatible API.
+    Podman,
+    /// containerd, through a Docker compatible API such as the one of
+    /// [nerdctl](https://github.com/containerd/nerdctl).
+    Containerd,
+    /// An engine that could not be identified.
+    Unknown,
+}
+
+impl ServerFlavor {
+    /// Whether the engine is the Docker engine.
+    pub fn is_docker(&self) -> bool {
+        matches!(self, ServerFlavor::Docker)
+    }
+}
+
+impl fmt::Display for ServerFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ServerFlavor::Docker => "docker",
+            ServerFlavor::Podman => "podman",
+            ServerFlavor::Containerd => "containerd",
+            ServerFlavor::Unknown => "unknown",
+        })
+    }
+}
+
+impl From<&SystemVersion> for ServerFlavor {
+    /// Identify the engine from the platform and component names of its version response.
+    fn from(version: &SystemVersion) -> Self {
+        let mut names: Vec<String> = version
+            .components
+            .iter()
+            .flatten()
+            .map(|component| component.name.to_lowercase())
+            .collect();
+        if let Some(platform) = &version.platform {
+            names.push(platform.name.to_lowercase());
+        }
+
+        if names.iter().any(|name| name.contains("podman")) {
+            ServerFlavor::Podman
+        } else if names.iter().any(|name| name.contains("nerdctl")) {
+            ServerFlavor::Containerd
+        } else if names
+            .iter()
+            .any(|name| name == "engine" || name.contains("docker"))
+        {
+            ServerFlavor::Docker
+        } else if names.iter().any(|name| name.contains("containerd")) {
+            ServerFlavor::Containerd
+        } else {
+            ServerFlavor::Unknown
+        }
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Server Flavor
+    ///
+    /// Identify the engine serving the API. The flavor is recorded when [negotiating the
+    /// version](Docker::negotiate_version()), otherwise it is queried once and cached.
+    ///
+    /// # Returns
+    ///
+    ///  - [Server Flavor](ServerFlavor), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.server_flavor();
+    /// ```
+    pub async fn server_flavor(&self) -> Result<ServerFlavor, Error> {
+        if let Some(flavor) = self.server_flavor.get() {
+            return Ok(*flavor);
+        }
+
+        let req = self.build_request(
+            "/version",
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        let version: SystemVersion = self.process_into_value(req).await?;
+
+        Ok(*self
+            .server_flavor
+            .get_or_init(|| ServerFlavor::from(&version)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ServerFlavor;
+    use crate::models::{
+        PluginConfigInterface, SystemVersion, SystemVersionComponents, SystemVersionPlatform,
+    };
+
+    fn version(platform: &str, components: &[&str]) -> SystemVersion {
+        SystemVersion {
+            platform: Some(SystemVersionPlatform {
+                name: String::from(platform),
+            }),
+            components: Some(
+                components
+                    .iter()
+                    .map(|name| SystemVersionComponents {
+                        name: String::from(*name),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_server_flavor() {
+        assert_eq!(
+            ServerFlavor::from(&version(
+                "Docker Engine - Community",
+                &["Engine", "containerd", "runc", "docker-init"]
+            )),
+            ServerFlavor::Docker
+        );
+        assert_eq!(
+            ServerFlavor::from(&version("linux/amd64/fedora-40", &["Podman Engine"])),
+            ServerFlavor::Podman
+        );
+        assert_eq!(
+            ServerFlavor::from(&version("", &["nerdctl", "containerd"])),
+            ServerFlavor::Containerd
+        );
+        assert_eq!(
+            ServerFlavor::from(&SystemVersion::default()),
+            ServerFlavor::Unknown
+        );
+    }
+
+    #[test]
+    fn test_response_shims() {
+        let response: crate::models::ContainerCreateResponse =
+            serde_json::from_str(r#"{"Id":"abc"}"#).unwrap();
+        assert_eq!(response.id, "abc");
+        assert!(response.warnings.is_empty());
+
+        let interface: PluginConfigInterface = serde_json::from_str(
+            r#"{
+                "Types": [
+                    "docker.volumedriver/1.0",
+                    {"Prefix": "docker", "Capability": "logdriver", "Version": "1.0"}
+                ],
+                "Socket": "plugin.sock"
+            }"#,
+        )
+        .unwrap();
+        let types: Vec<_> = interface
+            .types
+            .iter()
+            .map(|typ| {
+                (
+                    typ.prefix.as_str(),
+                    typ.capability.as_str(),
+                    typ.version.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("docker", "volumedriver", "1.0"),
+                ("docker", "logdriver", "1.0")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_enum_values() {
+        use crate::models::{MountPoint, MountPointTypeEnum, NodeStatus, Port, PortTypeEnum};
+
+        let json = r#"{"Type":"image","Source":"alpine","Destination":"/mnt"}"#;
+        let mount: MountPoint = serde_json::from_str(json).unwrap();
+        assert_eq!(mount.typ, None);
+        assert_eq!(mount.source.as_deref(), Some("alpine"));
+
+        let mount: MountPoint = serde_json::from_str(r#"{"Type":"bind"}"#).unwrap();
+        assert_eq!(mount.typ, Some(MountPointTypeEnum::BIND));
+        let mount: MountPoint = serde_json::from_str(r#"{"Type":null}"#).unwrap();
+        assert_eq!(mount.typ, None);
+        let mount: MountPoint = serde_json::from_str("{}").unwrap();
+        assert_eq!(mount.typ, None);
+
+        let status: NodeStatus = serde_json::from_str(r#"{"State":"draining"}"#).unwrap();
+        assert_eq!(status.state, None);
+
+        let port: Port = serde_json::from_str(r#"{"PrivatePort":80,"Type":"quic"}"#).unwrap();
+        assert_eq!(port.typ, None);
+        let port: Port = serde_json::from_str(r#"{"PrivatePort":80,"Type":""}"#).unwrap();
+        assert_eq!(port.typ, None);
+        let port: Port = serde_json::from_str(r#"{"PrivatePort":80,"Type":"tcp"}"#).unwrap();
+        assert_eq!(port.typ, Some(PortTypeEnum::TCP));
+        assert_eq!(
+            serde_json::to_value(&port).unwrap()["Type"],
+            serde_json::json!("tcp")
+        );
+
+        assert!("image".parse::<MountPointTypeEnum>().is_err());
+    }
+}
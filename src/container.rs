@@ -6,7 +6,7 @@ use http::header::{CONNECTION, CONTENT_TYPE, UPGRADE};
 use http::request::Builder;
 use http_body_util::Full;
 use hyper::{body::Bytes, Method};
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_derive::Deserialize;
 use tokio::io::AsyncWrite;
 use tokio_util::codec::FramedRead;
@@ -16,12 +16,25 @@ use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
 use std::pin::Pin;
+use std::time::Duration;
 
 use super::Docker;
 use crate::docker::BodyType;
 use crate::errors::Error;
 use crate::models::*;
-use crate::read::NewlineLogOutputDecoder;
+use crate::read::{BodyStream, IdleTimeout, NewlineLogOutputDecoder};
+
+pub mod changes;
+pub mod detach;
+pub mod devices;
+pub mod host_config;
+pub mod log_config;
+pub mod logs;
+pub mod ports;
+pub mod selector;
+pub mod signal;
+pub mod top;
+pub mod update;
 
 /// Parameters used in the [List Container API](Docker::list_containers())
 ///
@@ -101,8 +114,8 @@ where
     /// Assign the specified name to the container.
     pub name: T,
 
-    /// The platform to use for the container.
-    /// Added in API v1.41.
+    /// The platform to use for the container, in the format `os[/arch[/variant]]`, see
+    /// [Platform](crate::platform::Platform). Added in API v1.41.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub platform: Option<T>,
 }
@@ -300,11 +313,16 @@ impl From<ContainerConfig> for Config<String> {
 ///
 /// StopContainerOptions{
 ///     t: 30,
+///     signal: None,
 /// };
 #[derive(Debug, Copy, Clone, Default, PartialEq, Serialize)]
 pub struct StopContainerOptions {
     /// Number of seconds to wait before killing the container
     pub t: i64,
+    /// Signal to stop the container with, rather than its `StopSignal`. Requires API version
+    /// 1.42 or later.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal: Option<signal::Signal>,
 }
 
 /// Parameters used in the [Start Container API](Docker::start_container())
@@ -543,6 +561,19 @@ where
     /// Only return this number of log lines from the end of the logs. Specify as an integer or all
     /// to output `all` log lines.
     pub tail: T,
+    /// Abort the stream with a [Stream Stalled Error](crate::errors::Error::StreamStalledError)
+    /// when no data arrives for this long. Defaults to the timeout set with
+    /// [`with_idle_timeout`](Docker::with_idle_timeout()). Not sent to the daemon.
+    #[serde(skip)]
+    pub idle_timeout: Option<Duration>,
+    /// Remove ANSI escape sequences from the lines of the [Parsed Logs
+    /// API](Docker::parsed_logs()). Not sent to the daemon.
+    #[serde(skip)]
+    pub strip_ansi: bool,
+    /// Parse the timestamps of the lines of the [Parsed Logs API](Docker::parsed_logs()), which
+    /// requires `timestamps`. Not sent to the daemon.
+    #[serde(skip)]
+    pub parse_timestamps: bool,
 }
 
 /// Result type for the [Logs API](Docker::logs())
@@ -600,6 +631,7 @@ impl LogOutput {
 /// StatsOptions{
 ///     stream: false,
 ///     one_shot: false,
+///     idle_timeout: None,
 /// };
 /// ```
 #[derive(Debug, Copy, Clone, Default, PartialEq, Serialize)]
@@ -609,6 +641,11 @@ pub struct StatsOptions {
     /// Only get a single stat instead of waiting for 2 cycles. Must be used with `stream = false`.
     #[serde(rename = "one-shot")]
     pub one_shot: bool,
+    /// Abort the stream with a [Stream Stalled Error](crate::errors::Error::StreamStalledError)
+    /// when no data arrives for this long. Defaults to the timeout set with
+    /// [`with_idle_timeout`](Docker::with_idle_timeout()). Not sent to the daemon.
+    #[serde(skip)]
+    pub idle_timeout: Option<Duration>,
 }
 
 /// Granular memory statistics for the container.
@@ -864,7 +901,8 @@ pub struct KillContainerOptions<T>
 where
     T: Into<String> + Serialize,
 {
-    /// Signal to send to the container as an integer or string (e.g. `SIGINT`)
+    /// Signal to send to the container as an integer or string (e.g. `SIGINT`), or as a
+    /// [Signal](signal::Signal) that is checked before the request is sent.
     pub signal: T,
 }
 
@@ -1191,6 +1229,53 @@ impl Docker {
     ) -> Result<Vec<ContainerSummary>, Error>
     where
         T: Into<String> + Eq + Hash + Serialize,
+    {
+        self.list_containers_as(options).await
+    }
+
+    /// ---
+    ///
+    /// # List Containers As
+    ///
+    /// List the containers, like the [List Containers API](Docker::list_containers()), deserialized into a type of the
+    /// caller, e.g. a slim struct with only the fields it needs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [ListContainersOptions](ListContainersOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of the deserialized items, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::ListContainersOptions;
+    ///
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Container {
+    ///     id: String,
+    ///     state: String,
+    /// }
+    ///
+    /// docker.list_containers_as::<_, Container>(Some(ListContainersOptions::<String> {
+    ///     all: true,
+    ///     ..Default::default()
+    /// }));
+    /// ```
+    pub async fn list_containers_as<T, R>(
+        &self,
+        options: Option<ListContainersOptions<T>>,
+    ) -> Result<Vec<R>, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+        R: DeserializeOwned,
     {
         let url = "/containers/json";
 
@@ -1204,6 +1289,108 @@ impl Docker {
         self.process_into_value(req).await
     }
 
+    /// ---
+    ///
+    /// # List Containers Stream
+    ///
+    /// Returns the containers one at a time, fetching them in pages of `page_size` containers so
+    /// that hosts with many containers never have to send, or decode, the whole list at once.
+    ///
+    /// Pages are requested newest first with a `limit`, and continue with a `before` filter on
+    /// the last container of the previous page. As with a `limit`, non-running containers are
+    /// included: filter on `status` for running containers only. The `limit` of the options, if
+    /// any, caps the number of containers of the stream.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [ListContainersOptions](ListContainersOptions) struct.
+    ///  - Number of containers requested per page.
+    ///
+    /// # Returns
+    ///
+    ///  - Stream of [ContainerSummary](ContainerSummary).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::ListContainersOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async move {
+    ///     let mut containers =
+    ///         docker.list_containers_stream(None::<ListContainersOptions<String>>, 500);
+    ///     while let Some(container) = containers.try_next().await.unwrap() {
+    ///         println!("{:?}", container.names);
+    ///     }
+    /// };
+    /// ```
+    pub fn list_containers_stream<T>(
+        &self,
+        options: Option<ListContainersOptions<T>>,
+        page_size: usize,
+    ) -> impl Stream<Item = Result<ContainerSummary, Error>> + Unpin
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let options = options
+            .map(|options| ListContainersOptions {
+                all: options.all,
+                limit: options.limit,
+                size: options.size,
+                filters: options
+                    .filters
+                    .into_iter()
+                    .map(|(key, values)| (key.into(), values.into_iter().map(Into::into).collect()))
+                    .collect(),
+            })
+            .unwrap_or_default();
+        let total = options
+            .limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .filter(|limit| *limit > 0)
+            .unwrap_or(usize::MAX);
+        let page_size = page_size.max(1);
+        let docker = self.clone();
+
+        Box::pin(
+            futures_util::stream::try_unfold(Some(options), move |options| {
+                let docker = docker.clone();
+                async move {
+                    let Some(mut options) = options else {
+                        return Ok::<_, Error>(None);
+                    };
+                    options.limit = Some(isize::try_from(page_size).unwrap_or(isize::MAX));
+
+                    let req = docker.build_request(
+                        "/containers/json",
+                        Builder::new().method(Method::GET),
+                        Some(options.clone()),
+                        Ok(BodyType::Left(Full::new(Bytes::new()))),
+                    );
+                    let page: Vec<ContainerSummary> =
+                        docker.process_into_array_stream(req).try_collect().await?;
+
+                    let next = match page.last().and_then(|container| container.id.clone()) {
+                        Some(id) if page.len() >= page_size => {
+                            options.filters.insert(String::from("before"), vec![id]);
+                            Some(options)
+                        }
+                        _ => None,
+                    };
+                    Ok(Some((
+                        futures_util::stream::iter(page.into_iter().map(Ok)),
+                        next,
+                    )))
+                }
+            })
+            .try_flatten()
+            .take(total),
+        )
+    }
+
     /// ---
     ///
     /// # Create Container
@@ -1255,7 +1442,7 @@ impl Docker {
             url,
             Builder::new().method(Method::POST),
             options,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_value(req).await
@@ -1330,6 +1517,7 @@ impl Docker {
     ///
     /// let options = Some(StopContainerOptions{
     ///     t: 30,
+    ///     ..Default::default()
     /// });
     ///
     /// docker.stop_container("hello-world", options);
@@ -1531,6 +1719,102 @@ impl Docker {
         })
     }
 
+    /// ---
+    ///
+    /// # Attach Container (WebSocket)
+    ///
+    /// Attach to a container over a WebSocket, for environments where a proxy or load balancer
+    /// in front of the daemon blocks the connection hijack of the [Attach Container
+    /// API](Docker::attach_container()).
+    ///
+    /// The WebSocket endpoint does not multiplex the output streams: every message is reported
+    /// as [`LogOutput::Console`], whether the container has a TTY or not. The input is sent as
+    /// binary messages, and shutting it down closes the WebSocket.
+    ///
+    /// # Arguments
+    ///
+    /// - Container name as string slice.
+    /// - Optional [Attach Container Options](AttachContainerOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [AttachContainerResults](AttachContainerResults) wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::AttachContainerOptions;
+    ///
+    /// let options = Some(AttachContainerOptions::<String>{
+    ///     stdin: Some(true),
+    ///     stdout: Some(true),
+    ///     stderr: Some(true),
+    ///     stream: Some(true),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// docker.attach_container_ws("hello-world", options);
+    /// ```
+    #[cfg(feature = "ws")]
+    pub async fn attach_container_ws<T>(
+        &self,
+        container_name: &str,
+        options: Option<AttachContainerOptions<T>>,
+    ) -> Result<AttachContainerResults, Error>
+    where
+        T: Into<String> + Serialize + Default,
+    {
+        use futures_util::future::ready;
+        use futures_util::SinkExt;
+        use http::header::{ORIGIN, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION};
+        use tokio_tungstenite::tungstenite::handshake::client::generate_key;
+        use tokio_tungstenite::tungstenite::Message;
+        use tokio_util::io::{CopyToBytes, SinkWriter};
+
+        let url = format!("/containers/{container_name}/attach/ws");
+        let key = generate_key();
+
+        let req = self.build_request(
+            &url,
+            Builder::new()
+                .method(Method::GET)
+                .header(CONNECTION, "Upgrade")
+                .header(UPGRADE, "websocket")
+                .header(SEC_WEBSOCKET_VERSION, "13")
+                .header(SEC_WEBSOCKET_KEY, &key)
+                // older daemons reject handshakes without an origin
+                .header(ORIGIN, "http://localhost"),
+            options,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        let (sink, stream) = self.process_upgraded_websocket(req, &key).await?.split();
+
+        let output = stream
+            .try_filter_map(|message| {
+                ready(Ok(match message {
+                    Message::Binary(message) => Some(LogOutput::Console { message }),
+                    Message::Text(message) => Some(LogOutput::Console {
+                        message: message.into(),
+                    }),
+                    _ => None,
+                }))
+            })
+            .map_err(Error::from);
+        let input = SinkWriter::new(CopyToBytes::new(
+            sink.with(|message: Bytes| ready(Ok::<_, Error>(Message::Binary(message))))
+                .sink_map_err(std::io::Error::other),
+        ));
+
+        Ok(AttachContainerResults {
+            output: Box::pin(output),
+            input: Box::pin(input),
+        })
+    }
+
     /// ---
     ///
     /// # Resize container tty
@@ -1653,6 +1937,49 @@ impl Docker {
         container_name: &str,
         options: Option<InspectContainerOptions>,
     ) -> Result<ContainerInspectResponse, Error> {
+        self.inspect_container_as(container_name, options).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Container As
+    ///
+    /// Inspect a container, like the [Inspect Container API](Docker::inspect_container()), deserialized into a type of the
+    /// caller, e.g. a slim struct with only the fields it needs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as a string slice.
+    ///  - Optional [Inspect Container Options](InspectContainerOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - The deserialized response, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Container {
+    ///     id: String,
+    ///     name: String,
+    /// }
+    ///
+    /// docker.inspect_container_as::<Container>("hello-world", None);
+    /// ```
+    pub async fn inspect_container_as<R>(
+        &self,
+        container_name: &str,
+        options: Option<InspectContainerOptions>,
+    ) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+    {
         let url = format!("/containers/{container_name}/json");
 
         let req = self.build_request(
@@ -1717,7 +2044,8 @@ impl Docker {
     ///
     /// # Logs
     ///
-    /// Get container logs.
+    /// Get container logs. See the [logs](logs) module for a builder of the options, and for
+    /// lines with parsed timestamps and without ANSI escape sequences.
     ///
     /// # Arguments
     ///
@@ -1755,6 +2083,10 @@ impl Docker {
         T: Into<String> + Serialize,
     {
         let url = format!("/containers/{container_name}/logs");
+        let idle_timeout = options
+            .as_ref()
+            .and_then(|options| options.idle_timeout)
+            .or(self.idle_timeout);
 
         let req = self.build_request(
             &url,
@@ -1763,14 +2095,15 @@ impl Docker {
             Ok(BodyType::Left(Full::new(Bytes::new()))),
         );
 
-        self.process_into_stream_string(req)
+        IdleTimeout::new(self.process_into_stream_string(req), idle_timeout)
     }
 
     /// ---
     ///
     /// # Container Changes
     ///
-    /// Get changes on a container's filesystem.
+    /// Get changes on a container's filesystem. See the [changes](changes) module for typed
+    /// change kinds, path filtering and downloading the changed files.
     ///
     /// # Arguments
     ///
@@ -1832,6 +2165,7 @@ impl Docker {
     /// let options = Some(StatsOptions{
     ///     stream: false,
     ///     one_shot: true,
+    ///     idle_timeout: None,
     /// });
     ///
     /// docker.stats("hello-world", options);
@@ -1842,6 +2176,9 @@ impl Docker {
         options: Option<StatsOptions>,
     ) -> impl Stream<Item = Result<Stats, Error>> {
         let url = format!("/containers/{container_name}/stats");
+        let idle_timeout = options
+            .and_then(|options| options.idle_timeout)
+            .or(self.idle_timeout);
 
         let req = self.build_request(
             &url,
@@ -1850,7 +2187,48 @@ impl Docker {
             Ok(BodyType::Left(Full::new(Bytes::new()))),
         );
 
-        self.process_into_stream(req)
+        IdleTimeout::new(self.process_into_stream(req), idle_timeout)
+    }
+
+    /// ---
+    ///
+    /// # Stats Once
+    ///
+    /// Get a single sample of the container stats based on resource usage, without waiting for a
+    /// second sample to compute the `precpu_stats`, which are left empty. Requires API version
+    /// 1.41 to skip the second sample.
+    ///
+    /// # Arguments
+    ///
+    /// - Container name as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Stats](Stats) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.stats_once("hello-world");
+    /// ```
+    pub async fn stats_once(&self, container_name: &str) -> Result<Stats, Error> {
+        let url = format!("/containers/{container_name}/stats");
+
+        let req = self.build_request(
+            &url,
+            Builder::new().method(Method::GET),
+            Some(StatsOptions {
+                stream: false,
+                one_shot: true,
+                idle_timeout: None,
+            }),
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        self.process_into_value(req).await
     }
 
     /// ---
@@ -1911,11 +2289,13 @@ impl Docker {
     /// # Arguments
     ///
     ///  - Container name as string slice.
-    ///  - [Update Container Options](UpdateContainerOptions) struct.
+    ///  - [Update Container Options](UpdateContainerOptions) struct, e.g. as built by an
+    ///    [Update Container Builder](update::UpdateContainerBuilder).
     ///
     /// # Returns
     ///
-    ///  - unit type `()`, wrapped in a Future.
+    ///  - [Container Update Response](ContainerUpdateResponse), with the warnings of the daemon,
+    ///    wrapped in a Future.
     ///
     /// # Examples
     ///
@@ -1938,7 +2318,7 @@ impl Docker {
         &self,
         container_name: &str,
         config: UpdateContainerOptions<T>,
-    ) -> Result<(), Error>
+    ) -> Result<ContainerUpdateResponse, Error>
     where
         T: Into<String> + Eq + Hash + Serialize,
     {
@@ -1948,10 +2328,10 @@ impl Docker {
             &url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
-        self.process_into_unit(req).await
+        self.process_into_value(req).await
     }
 
     /// ---
@@ -2292,7 +2672,7 @@ impl Docker {
     /// # Returns
     ///
     ///  - Tar archive compressed with one of the following algorithms: identity (no compression),
-    ///    gzip, bzip2, xz. [Hyper Body](hyper::body::Body).
+    ///    gzip, bzip2, xz, as a [Body Stream](crate::BodyStream).
     ///
     /// # Examples
     ///
@@ -2311,7 +2691,7 @@ impl Docker {
         &self,
         container_name: &str,
         options: Option<DownloadFromContainerOptions<T>>,
-    ) -> impl Stream<Item = Result<Bytes, Error>>
+    ) -> BodyStream
     where
         T: Into<String> + Serialize,
     {
@@ -2339,11 +2719,8 @@ impl Docker {
     /// - The `container_name` string referring to an individual container
     ///
     /// # Returns
-    ///  - An uncompressed TAR archive
-    pub fn export_container(
-        &self,
-        container_name: &str,
-    ) -> impl Stream<Item = Result<Bytes, Error>> {
+    ///  - An uncompressed TAR archive, as a [Body Stream](crate::BodyStream).
+    pub fn export_container(&self, container_name: &str) -> BodyStream {
         let url = format!("/containers/{container_name}/export");
         let req = self.build_request(
             &url,
@@ -2391,6 +2768,113 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_inspect_container_as() {
+        #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Container {
+            id: String,
+            name: String,
+        }
+
+        let body = r#"{"Id":"a1","Name":"/a","State":{"Status":"running"},"Config":{}}"#;
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!(
+                "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let container: Container = docker.inspect_container_as("a", None).await.unwrap();
+        assert_eq!(
+            container,
+            Container {
+                id: String::from("a1"),
+                name: String::from("/a"),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_containers_stream() {
+        let body = r#"[{"Id":"b2","Names":["/b"]},{"Id":"a1","Names":["/a"]}]"#;
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!(
+                "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        // a short page ends the stream
+        let containers: Vec<_> = docker
+            .list_containers_stream(None::<super::ListContainersOptions<String>>, 5)
+            .try_collect()
+            .await
+            .unwrap();
+        let ids: Vec<_> = containers.iter().filter_map(|c| c.id.as_deref()).collect();
+        assert_eq!(ids, ["b2", "a1"]);
+
+        // the limit of the options caps full pages
+        let containers: Vec<_> = docker
+            .list_containers_stream(
+                Some(super::ListContainersOptions::<String> {
+                    limit: Some(3),
+                    ..Default::default()
+                }),
+                2,
+            )
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(containers.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_update_container_warnings() {
+        let body =
+            r#"{"Warnings":["Your kernel does not support memory swappiness capabilities"]}"#;
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!(
+                "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let options = super::update::UpdateContainerBuilder::new()
+            .memory(314572800)
+            .build()
+            .unwrap();
+        let response = docker
+            .update_container("update_container_test", options)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.warnings,
+            Some(vec![String::from(
+                "Your kernel does not support memory swappiness capabilities"
+            )])
+        );
+    }
+
     #[tokio::test]
     async fn test_output_non_json_error() {
         let mut connector = HostToReplyConnector::default();
@@ -2432,10 +2916,100 @@ mod tests {
 
         assert!(matches!(
             result,
-            Err(crate::errors::Error::JsonDataError {
-                message: _,
-                column: 2
-            })
+            Err(crate::errors::Error::JsonDataError { column: 2, .. })
         ));
     }
+
+    #[test]
+    fn test_model_builders() {
+        use crate::models::{HostConfig, RestartPolicy, RestartPolicyNameEnum};
+
+        let host_config = HostConfig::builder()
+            .memory(1024 * 1024)
+            .cap_add(vec![String::from("NET_ADMIN")])
+            .restart_policy(
+                RestartPolicy::builder()
+                    .name(RestartPolicyNameEnum::ON_FAILURE)
+                    .maximum_retry_count(3),
+            )
+            .build();
+
+        assert_eq!(
+            host_config,
+            HostConfig {
+                memory: Some(1024 * 1024),
+                cap_add: Some(vec![String::from("NET_ADMIN")]),
+                restart_policy: Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                    maximum_retry_count: Some(3),
+                }),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[cfg(all(feature = "ws", feature = "http"))]
+    #[tokio::test]
+    #[allow(clippy::result_large_err)]
+    async fn test_attach_container_ws() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio::io::AsyncWriteExt;
+        use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+        use tokio_tungstenite::tungstenite::Message;
+
+        use super::{AttachContainerOptions, LogOutput};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let daemon = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut uri = None;
+            let mut websocket =
+                tokio_tungstenite::accept_hdr_async(stream, |req: &Request, res: Response| {
+                    uri = Some(req.uri().to_string());
+                    Ok(res)
+                })
+                .await
+                .unwrap();
+            websocket
+                .send(Message::binary(&b"hello\n"[..]))
+                .await
+                .unwrap();
+            let input = websocket.next().await.unwrap().unwrap();
+            websocket.close(None).await.unwrap();
+            (uri.unwrap(), input)
+        });
+
+        let docker =
+            Docker::connect_with_http(&format!("tcp://{addr}"), 5, API_DEFAULT_VERSION).unwrap();
+        let mut results = docker
+            .attach_container_ws(
+                "attach_ws_test",
+                Some(AttachContainerOptions::<String> {
+                    stdin: Some(true),
+                    stdout: Some(true),
+                    stream: Some(true),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results.output.next().await.unwrap().unwrap(),
+            LogOutput::Console {
+                message: bytes::Bytes::from_static(b"hello\n")
+            }
+        );
+        results.input.write_all(b"ls\n").await.unwrap();
+        results.input.flush().await.unwrap();
+        assert!(results.output.next().await.is_none());
+
+        let (uri, input) = daemon.await.unwrap();
+        assert_eq!(
+            uri,
+            "/containers/attach_ws_test/attach/ws?stdin=true&stdout=true&stream=true"
+        );
+        assert_eq!(input, Message::binary(&b"ls\n"[..]));
+    }
 }
@@ -0,0 +1,392 @@
+//! Typed filesystem changes for the [Container Changes API](crate::Docker::container_changes()),
+//! with client-side path filtering and retrieval of the changed files through the archive
+//! endpoint.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::changes::{ChangeKind, ChangesFilter};
+//!
+//! use futures_util::stream::TryStreamExt;
+//!
+//! let filter = ChangesFilter::new()
+//!     .include("/etc/**")
+//!     .exclude("**/*.bak")
+//!     .kind(ChangeKind::Added)
+//!     .kind(ChangeKind::Modified);
+//!
+//! async move {
+//!     let files = docker
+//!         .container_changed_files("my-container", filter)
+//!         .try_collect::<Vec<_>>()
+//!         .await
+//!         .unwrap();
+//!     for file in files {
+//!         println!("{} {}: {} bytes", file.change.kind, file.change.path, file.archive.len());
+//!     }
+//! };
+//! ```
+
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+
+use std::fmt;
+
+use super::DownloadFromContainerOptions;
+use crate::errors::Error;
+use crate::models::{ChangeType, FilesystemChange};
+use crate::Docker;
+
+/// Kind of change made to a path in a container's filesystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The path was modified.
+    Modified,
+    /// The path was added.
+    Added,
+    /// The path was deleted.
+    Deleted,
+}
+
+impl From<ChangeType> for ChangeKind {
+    fn from(kind: ChangeType) -> Self {
+        match kind {
+            ChangeType::_0 => ChangeKind::Modified,
+            ChangeType::_1 => ChangeKind::Added,
+            ChangeType::_2 => ChangeKind::Deleted,
+        }
+    }
+}
+
+impl From<ChangeKind> for ChangeType {
+    fn from(kind: ChangeKind) -> Self {
+        match kind {
+            ChangeKind::Modified => ChangeType::_0,
+            ChangeKind::Added => ChangeType::_1,
+            ChangeKind::Deleted => ChangeType::_2,
+        }
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    /// Format the kind the way `docker diff` does: `C`, `A` or `D`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeKind::Modified => "C",
+            ChangeKind::Added => "A",
+            ChangeKind::Deleted => "D",
+        })
+    }
+}
+
+/// A change to a path in a container's filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContainerChange {
+    /// Absolute path inside the container.
+    pub path: String,
+    /// Kind of change.
+    pub kind: ChangeKind,
+}
+
+impl From<FilesystemChange> for ContainerChange {
+    fn from(change: FilesystemChange) -> Self {
+        ContainerChange {
+            path: change.path,
+            kind: change.kind.into(),
+        }
+    }
+}
+
+/// Client-side filter for the [Container Changes
+/// Filtered API](Docker::container_changes_filtered()).
+///
+/// Patterns are matched against the whole absolute path: `?` matches a single character and `*`
+/// any characters within a path segment, while `**` also matches across `/`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangesFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    kinds: Vec<ChangeKind>,
+}
+
+impl ChangesFilter {
+    /// Start with a filter that keeps every change.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keep only changes whose path matches one of the included patterns.
+    pub fn include(mut self, pattern: &str) -> Self {
+        self.include.push(String::from(pattern));
+        self
+    }
+
+    /// Drop changes whose path matches the pattern, even if it is included.
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(String::from(pattern));
+        self
+    }
+
+    /// Keep only changes of the given kinds.
+    pub fn kind(mut self, kind: ChangeKind) -> Self {
+        if !self.kinds.contains(&kind) {
+            self.kinds.push(kind);
+        }
+        self
+    }
+
+    /// Whether the change passes the filter.
+    pub fn matches(&self, change: &ContainerChange) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&change.kind))
+            && (self.include.is_empty()
+                || self
+                    .include
+                    .iter()
+                    .any(|pattern| glob_match(pattern, &change.path)))
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| glob_match(pattern, &change.path))
+    }
+}
+
+fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    glob_match_chars(&pattern, &path)
+}
+
+fn glob_match_chars(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no directories at all
+            (0..=path.len()).any(|i| glob_match_chars(rest, &path[i..]))
+                || matches!(rest, ['/', rest @ ..] if glob_match_chars(rest, path))
+        }
+        ['*', rest @ ..] => {
+            let segment = path.iter().position(|c| *c == '/').unwrap_or(path.len());
+            (0..=segment).any(|i| glob_match_chars(rest, &path[i..]))
+        }
+        ['?', rest @ ..] => {
+            matches!(path.first(), Some(c) if *c != '/') && glob_match_chars(rest, &path[1..])
+        }
+        [c, rest @ ..] => path.first() == Some(c) && glob_match_chars(rest, &path[1..]),
+    }
+}
+
+/// Paths whose contents can be downloaded: deletions are dropped, and so are directories that
+/// are only listed because a change was made beneath them, which are listed separately.
+fn content_changes(changes: Vec<ContainerChange>) -> Vec<ContainerChange> {
+    let parents: Vec<String> = changes
+        .iter()
+        .filter_map(|change| {
+            change
+                .path
+                .rsplit_once('/')
+                .map(|(parent, _)| String::from(parent))
+        })
+        .collect();
+
+    changes
+        .into_iter()
+        .filter(|change| change.kind != ChangeKind::Deleted)
+        .filter(|change| !parents.contains(&change.path))
+        .collect()
+}
+
+/// A changed path and its contents, returned by the [Container Changed Files
+/// API](Docker::container_changed_files()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedFile {
+    /// The change made to the path.
+    pub change: ContainerChange,
+    /// Uncompressed tar archive of the path, as returned by the [Download From Container
+    /// API](Docker::download_from_container()).
+    pub archive: Bytes,
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Container Changes Filtered
+    ///
+    /// Get the changes on a container's filesystem as typed [Container
+    /// Changes](ContainerChange), keeping those that pass the filter.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Changes Filter](ChangesFilter) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Container Change](ContainerChange) structs, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::changes::{ChangeKind, ChangesFilter};
+    ///
+    /// let filter = ChangesFilter::new().include("/var/log/**").kind(ChangeKind::Added);
+    ///
+    /// docker.container_changes_filtered("hello-world", filter);
+    /// ```
+    pub async fn container_changes_filtered(
+        &self,
+        container_name: &str,
+        filter: ChangesFilter,
+    ) -> Result<Vec<ContainerChange>, Error> {
+        Ok(self
+            .container_changes(container_name)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(ContainerChange::from)
+            .filter(|change| filter.matches(change))
+            .collect())
+    }
+
+    /// ---
+    ///
+    /// # Container Changed Files
+    ///
+    /// Download the contents of the added and modified paths in a container's filesystem that
+    /// pass the filter, one archive per path. Directories that are only listed because a change
+    /// was made beneath them are skipped.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - [Changes Filter](ChangesFilter) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Changed File](ChangedFile) structs, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::changes::ChangesFilter;
+    ///
+    /// docker.container_changed_files("hello-world", ChangesFilter::new().include("/tmp/**"));
+    /// ```
+    pub fn container_changed_files(
+        &self,
+        container_name: &str,
+        filter: ChangesFilter,
+    ) -> impl Stream<Item = Result<ChangedFile, Error>> {
+        let docker = self.clone();
+        let container_name = String::from(container_name);
+
+        stream::once(async move {
+            let changes = docker
+                .container_changes_filtered(&container_name, filter)
+                .await?;
+            Ok::<_, Error>(
+                stream::iter(content_changes(changes))
+                    .map(move |change| Ok((docker.clone(), container_name.clone(), change))),
+            )
+        })
+        .try_flatten()
+        .and_then(|(docker, container_name, change)| async move {
+            let archive = docker
+                .download_from_container(
+                    &container_name,
+                    Some(DownloadFromContainerOptions {
+                        path: change.path.as_str(),
+                    }),
+                )
+                .try_collect::<Vec<_>>()
+                .await?
+                .concat();
+
+            Ok(ChangedFile {
+                change,
+                archive: Bytes::from(archive),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_changes, glob_match, ChangeKind, ChangesFilter, ContainerChange};
+    use crate::models::{ChangeType, FilesystemChange};
+
+    fn change(path: &str, kind: ChangeKind) -> ContainerChange {
+        ContainerChange {
+            path: String::from(path),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_change_kind() {
+        let change: ContainerChange = FilesystemChange {
+            path: String::from("/etc/hosts"),
+            kind: ChangeType::_0,
+        }
+        .into();
+        assert_eq!(change.kind, ChangeKind::Modified);
+        assert_eq!(ChangeKind::from(ChangeType::_1), ChangeKind::Added);
+        assert_eq!(ChangeKind::from(ChangeType::_2), ChangeKind::Deleted);
+        assert_eq!(ChangeType::from(ChangeKind::Added), ChangeType::_1);
+        assert_eq!(ChangeKind::Deleted.to_string(), "D");
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("/etc/hosts", "/etc/hosts"));
+        assert!(!glob_match("/etc/hosts", "/etc/hostname"));
+        assert!(glob_match("/etc/host?", "/etc/hosts"));
+        assert!(glob_match("/etc/*", "/etc/hosts"));
+        assert!(!glob_match("/etc/*", "/etc/ssl/certs"));
+        assert!(glob_match("/etc/**", "/etc/ssl/certs"));
+        assert!(glob_match("**/*.conf", "/etc/nginx/nginx.conf"));
+        assert!(glob_match("/var/**/*.log", "/var/app.log"));
+        assert!(!glob_match("/var/**/*.log", "/var/log/app.txt"));
+        assert!(!glob_match("/tmp/?", "/tmp/"));
+    }
+
+    #[test]
+    fn test_changes_filter() {
+        let filter = ChangesFilter::new()
+            .include("/etc/**")
+            .exclude("**/*.bak")
+            .kind(ChangeKind::Added);
+
+        assert!(filter.matches(&change("/etc/app.conf", ChangeKind::Added)));
+        assert!(!filter.matches(&change("/etc/app.conf", ChangeKind::Modified)));
+        assert!(!filter.matches(&change("/etc/app.conf.bak", ChangeKind::Added)));
+        assert!(!filter.matches(&change("/tmp/app.conf", ChangeKind::Added)));
+        assert!(ChangesFilter::new().matches(&change("/tmp", ChangeKind::Deleted)));
+    }
+
+    #[test]
+    fn test_content_changes() {
+        let changes = vec![
+            change("/etc", ChangeKind::Modified),
+            change("/etc/app.conf", ChangeKind::Added),
+            change("/etc/motd", ChangeKind::Deleted),
+            change("/tmp", ChangeKind::Modified),
+        ];
+
+        assert_eq!(
+            content_changes(changes),
+            vec![
+                change("/etc/app.conf", ChangeKind::Added),
+                change("/tmp", ChangeKind::Modified),
+            ]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Client-side detach keys for the input of [attached
+//! containers](crate::Docker::attach_container()) and [execs](crate::Docker::start_exec()), as
+//! with the `--detach-keys` flag of the docker CLI.
+//!
+//! A [Detachable Input](DetachableInput) forwards what is written to it, except for the detach
+//! key sequence: once the sequence is written, the session is detached and the container keeps
+//! running. Bytes that start the sequence are held back until it is clear whether they complete
+//! it, so the daemon never sees the sequence.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::detach::{DetachKeys, DetachableInput};
+//! use bollard::container::AttachContainerOptions;
+//!
+//! use futures_util::stream::StreamExt;
+//! use tokio::io::AsyncWriteExt;
+//!
+//! async move {
+//!     let results = docker
+//!         .attach_container(
+//!             "my-container",
+//!             Some(AttachContainerOptions::<String> {
+//!                 stdin: Some(true),
+//!                 stdout: Some(true),
+//!                 stream: Some(true),
+//!                 ..Default::default()
+//!             }),
+//!         )
+//!         .await
+//!         .unwrap();
+//!
+//!     let mut input = DetachableInput::new(results.input, DetachKeys::default());
+//!     let detached = input.detached();
+//!     tokio::spawn(async move {
+//!         input.write_all(b"ls\n").await.unwrap();
+//!         // ctrl-p ctrl-q
+//!         input.write_all(&[0x10, 0x11]).await.unwrap();
+//!     });
+//!
+//!     let mut output = Box::pin(results.output.take_until(detached.cancelled_owned()));
+//!     while let Some(Ok(output)) = output.next().await {
+//!         print!("{output}");
+//!     }
+//! };
+//! ```
+
+use tokio::io::AsyncWrite;
+use tokio_util::sync::CancellationToken;
+
+use std::io;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+
+use crate::errors::Error;
+
+/// The detach key sequence of the docker CLI.
+pub const DEFAULT_DETACH_KEYS: &str = "ctrl-p,ctrl-q";
+
+/// A sequence of keys that detaches from an attached session.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DetachKeys {
+    bytes: Vec<u8>,
+}
+
+impl DetachKeys {
+    /// Parse a comma-separated sequence of keys, each a single character or `ctrl-<value>`
+    /// where `<value>` is one of `a-z`, `@`, `^`, `[`, `\`, `]` or `_`, e.g. `ctrl-p,ctrl-q`.
+    pub fn parse(keys: &str) -> Result<DetachKeys, Error> {
+        let invalid = || Error::InvalidDetachKeysError {
+            keys: String::from(keys),
+        };
+
+        let bytes = keys
+            .split(',')
+            .map(|key| {
+                let key = key.trim();
+                match key.to_ascii_lowercase().strip_prefix("ctrl-") {
+                    Some(value) => match value.as_bytes() {
+                        [c @ b'a'..=b'z'] => Ok(c - b'a' + 1),
+                        [b'@'] => Ok(0),
+                        [b'['] => Ok(27),
+                        [b'\\'] => Ok(28),
+                        [b']'] => Ok(29),
+                        [b'^'] => Ok(30),
+                        [b'_'] => Ok(31),
+                        _ => Err(invalid()),
+                    },
+                    None => match key.as_bytes() {
+                        [c] if c.is_ascii() => Ok(*c),
+                        _ => Err(invalid()),
+                    },
+                }
+            })
+            .collect::<Result<Vec<u8>, Error>>()?;
+
+        Ok(DetachKeys { bytes })
+    }
+
+    /// The bytes that make up the sequence.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Default for DetachKeys {
+    /// The `ctrl-p,ctrl-q` sequence of the docker CLI.
+    fn default() -> Self {
+        DetachKeys {
+            bytes: vec![0x10, 0x11],
+        }
+    }
+}
+
+impl FromStr for DetachKeys {
+    type Err = Error;
+
+    fn from_str(keys: &str) -> Result<Self, Self::Err> {
+        DetachKeys::parse(keys)
+    }
+}
+
+/// Input of an attached session that detaches when the [Detach Keys](DetachKeys) are written.
+///
+/// Writes after the session detached fail with a [broken pipe](io::ErrorKind::BrokenPipe).
+#[derive(Debug)]
+pub struct DetachableInput<W> {
+    inner: W,
+    keys: DetachKeys,
+    matched: usize,
+    pending: Vec<u8>,
+    detached: CancellationToken,
+}
+
+impl<W> DetachableInput<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Wrap the input of an attached container or exec.
+    pub fn new(inner: W, keys: DetachKeys) -> Self {
+        DetachableInput {
+            inner,
+            keys,
+            matched: 0,
+            pending: Vec::new(),
+            detached: CancellationToken::new(),
+        }
+    }
+
+    /// A token that is cancelled once the detach keys are written, to stop reading the output
+    /// of the session.
+    pub fn detached(&self) -> CancellationToken {
+        self.detached.clone()
+    }
+
+    /// Whether the detach keys were written.
+    pub fn is_detached(&self) -> bool {
+        self.detached.is_cancelled()
+    }
+
+    /// The wrapped input.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.pending.is_empty() {
+            let written = match Pin::new(&mut self.inner).poll_write(cx, &self.pending) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                Poll::Ready(Ok(written)) => written,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            self.pending.drain(..written);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W> AsyncWrite for DetachableInput<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+
+        if this.detached.is_cancelled() {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "detached from the session",
+            )));
+        }
+        if this.poll_write_pending(cx)?.is_pending() {
+            return Poll::Pending;
+        }
+
+        let keys = this.keys.as_bytes();
+        let mut consumed = buf.len();
+        for (i, byte) in buf.iter().enumerate() {
+            if keys.get(this.matched) != Some(byte) && this.matched > 0 {
+                // the held back bytes did not complete the sequence
+                this.pending.extend_from_slice(&keys[..this.matched]);
+                this.matched = 0;
+            }
+            if keys.get(this.matched) == Some(byte) {
+                this.matched += 1;
+                if this.matched == keys.len() {
+                    this.matched = 0;
+                    this.detached.cancel();
+                    consumed = i + 1;
+                    break;
+                }
+            } else {
+                this.pending.push(*byte);
+            }
+        }
+
+        // the bytes are accepted, the rest is forwarded on the next write or flush
+        let _ = this.poll_write_pending(cx)?;
+        Poll::Ready(Ok(consumed))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.poll_write_pending(cx)?.is_pending() {
+            return Poll::Pending;
+        }
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.poll_write_pending(cx)?.is_pending() {
+            return Poll::Pending;
+        }
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncWriteExt;
+
+    use super::{DetachKeys, DetachableInput, DEFAULT_DETACH_KEYS};
+    use crate::errors::Error;
+
+    #[test]
+    fn test_detach_keys() {
+        assert_eq!(
+            DetachKeys::parse(DEFAULT_DETACH_KEYS).unwrap(),
+            DetachKeys::default()
+        );
+        assert_eq!(
+            "ctrl-@, ctrl-[,ctrl-_,x"
+                .parse::<DetachKeys>()
+                .unwrap()
+                .as_bytes(),
+            &[0, 27, 31, b'x']
+        );
+        assert!(matches!(
+            DetachKeys::parse("ctrl-1"),
+            Err(Error::InvalidDetachKeysError { .. })
+        ));
+        assert!(matches!(
+            DetachKeys::parse("ctrl-p,,ctrl-q"),
+            Err(Error::InvalidDetachKeysError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_detachable_input() {
+        let mut input = DetachableInput::new(Vec::new(), DetachKeys::default());
+        let detached = input.detached();
+
+        input.write_all(b"ls\x10").await.unwrap();
+        input.flush().await.unwrap();
+        assert_eq!(input.inner, b"ls");
+
+        // a held back key that does not complete the sequence is forwarded
+        input.write_all(b"\x10\x10").await.unwrap();
+        input.flush().await.unwrap();
+        assert_eq!(input.inner, b"ls\x10\x10");
+        assert!(!detached.is_cancelled());
+
+        input.write_all(b"\x11").await.unwrap();
+        assert!(detached.is_cancelled());
+        assert!(input.is_detached());
+        assert!(input.write_all(b"exit\n").await.is_err());
+        assert_eq!(input.into_inner(), b"ls\x10\x10");
+    }
+}
@@ -0,0 +1,195 @@
+//! Typed device requests for the `device_requests` field of a
+//! [HostConfig](crate::models::HostConfig), such as GPUs, equivalent to the `--gpus` and
+//! `--device` flags of `docker run`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bollard::container::devices::{DeviceRequestBuilder, Gpus};
+//! use bollard::container::Config;
+//! use bollard::models::HostConfig;
+//!
+//! let gpus = DeviceRequestBuilder::nvidia(Gpus::Ids(vec![String::from("0")]))
+//!     .capability("compute")
+//!     .capability("utility")
+//!     .build();
+//!
+//! let cdi = DeviceRequestBuilder::cdi(["vendor.com/device=foo"]).build();
+//!
+//! let config = Config {
+//!     image: Some(String::from("nvidia/cuda")),
+//!     host_config: Some(HostConfig {
+//!         device_requests: Some(vec![gpus, cdi]),
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//! ```
+
+use std::collections::HashMap;
+
+use crate::models::DeviceRequest;
+
+/// Driver name of the [NVIDIA Container
+/// Toolkit](https://github.com/NVIDIA/nvidia-container-toolkit).
+pub const NVIDIA_DRIVER: &str = "nvidia";
+
+/// Driver name for [Container Device Interface](https://github.com/cncf-tags/container-device-interface)
+/// devices.
+pub const CDI_DRIVER: &str = "cdi";
+
+/// Capability the daemon uses to select a GPU driver.
+pub const GPU_CAPABILITY: &str = "gpu";
+
+/// The GPUs to request, as with the `--gpus` flag of `docker run`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Gpus {
+    /// All available GPUs.
+    All,
+    /// The given number of GPUs.
+    Count(u32),
+    /// The GPUs with the given IDs or UUIDs.
+    Ids(Vec<String>),
+}
+
+/// Builder for a [DeviceRequest](DeviceRequest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceRequestBuilder {
+    request: DeviceRequest,
+}
+
+impl DeviceRequestBuilder {
+    /// Request GPUs from whichever driver provides the `gpu` capability.
+    pub fn gpus(gpus: Gpus) -> Self {
+        let mut request = DeviceRequest {
+            capabilities: Some(vec![vec![String::from(GPU_CAPABILITY)]]),
+            ..Default::default()
+        };
+
+        match gpus {
+            Gpus::All => request.count = Some(-1),
+            Gpus::Count(count) => request.count = Some(i64::from(count)),
+            Gpus::Ids(ids) => request.device_ids = Some(ids),
+        }
+
+        DeviceRequestBuilder { request }
+    }
+
+    /// Request GPUs from the NVIDIA driver. Add capabilities such as `compute` or `utility` to
+    /// select the driver libraries mounted into the container.
+    pub fn nvidia(gpus: Gpus) -> Self {
+        Self::gpus(gpus).driver(NVIDIA_DRIVER)
+    }
+
+    /// Request devices by their fully qualified CDI name, such as `nvidia.com/gpu=all`.
+    pub fn cdi<I, T>(devices: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        DeviceRequestBuilder {
+            request: DeviceRequest {
+                driver: Some(String::from(CDI_DRIVER)),
+                device_ids: Some(devices.into_iter().map(Into::into).collect()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Use the given device driver.
+    pub fn driver(mut self, driver: &str) -> Self {
+        self.request.driver = Some(String::from(driver));
+        self
+    }
+
+    /// Require an additional capability from the driver.
+    pub fn capability(mut self, capability: &str) -> Self {
+        let capabilities = self.request.capabilities.get_or_insert_with(Vec::new);
+        if capabilities.is_empty() {
+            capabilities.push(Vec::new());
+        }
+        for set in capabilities.iter_mut() {
+            if !set.iter().any(|c| c == capability) {
+                set.push(String::from(capability));
+            }
+        }
+        self
+    }
+
+    /// Pass a driver-specific option.
+    pub fn option(mut self, key: &str, value: &str) -> Self {
+        self.request
+            .options
+            .get_or_insert_with(HashMap::new)
+            .insert(String::from(key), String::from(value));
+        self
+    }
+
+    /// The device request, for the `device_requests` field of a
+    /// [HostConfig](crate::models::HostConfig).
+    pub fn build(self) -> DeviceRequest {
+        self.request
+    }
+}
+
+impl From<DeviceRequestBuilder> for DeviceRequest {
+    fn from(builder: DeviceRequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{DeviceRequestBuilder, Gpus};
+    use crate::models::DeviceRequest;
+
+    #[test]
+    fn test_device_requests() {
+        assert_eq!(
+            DeviceRequestBuilder::gpus(Gpus::All).build(),
+            DeviceRequest {
+                count: Some(-1),
+                capabilities: Some(vec![vec![String::from("gpu")]]),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            DeviceRequestBuilder::nvidia(Gpus::Count(2))
+                .capability("compute")
+                .capability("compute")
+                .option("key", "value")
+                .build(),
+            DeviceRequest {
+                driver: Some(String::from("nvidia")),
+                count: Some(2),
+                capabilities: Some(vec![vec![String::from("gpu"), String::from("compute")]]),
+                options: Some(HashMap::from([(
+                    String::from("key"),
+                    String::from("value")
+                )])),
+                ..Default::default()
+            }
+        );
+
+        let request: DeviceRequest =
+            DeviceRequestBuilder::gpus(Gpus::Ids(vec![String::from("0"), String::from("1")]))
+                .into();
+        assert_eq!(request.count, None);
+        assert_eq!(
+            request.device_ids,
+            Some(vec![String::from("0"), String::from("1")])
+        );
+
+        assert_eq!(
+            DeviceRequestBuilder::cdi(["nvidia.com/gpu=all"]).build(),
+            DeviceRequest {
+                driver: Some(String::from("cdi")),
+                device_ids: Some(vec![String::from("nvidia.com/gpu=all")]),
+                ..Default::default()
+            }
+        );
+    }
+}
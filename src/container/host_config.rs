@@ -0,0 +1,319 @@
+//! Builder-style helpers for the resource limits, capabilities and kernel parameters of a
+//! [HostConfig](crate::models::HostConfig).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bollard::container::host_config::{Capability, HostConfigExt};
+//! use bollard::models::HostConfig;
+//!
+//! let host_config = HostConfig::default()
+//!     .ulimit("nofile", 1024, 2048)
+//!     .cap_drop(Capability::All)
+//!     .cap_add(Capability::NetAdmin)
+//!     .sysctl("net.ipv4.ip_forward", "1");
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::Error;
+use crate::models::{HostConfig, ResourcesUlimits};
+
+/// A Linux capability, as added to or dropped from a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Capability {
+    /// Every capability.
+    All,
+    /// Make arbitrary changes to file UIDs and GIDs.
+    Chown,
+    /// Bypass file read, write, and execute permission checks.
+    DacOverride,
+    /// Bypass file read permission checks and directory read and execute permission checks.
+    DacReadSearch,
+    /// Bypass permission checks on operations that require the file owner's UID.
+    Fowner,
+    /// Keep set-user-ID and set-group-ID bits when a file is modified.
+    Fsetid,
+    /// Bypass permission checks for sending signals.
+    Kill,
+    /// Make arbitrary manipulations of process GIDs.
+    Setgid,
+    /// Make arbitrary manipulations of process UIDs.
+    Setuid,
+    /// Modify process capabilities.
+    Setpcap,
+    /// Set the immutable and append-only file attributes.
+    LinuxImmutable,
+    /// Bind a socket to privileged ports below 1024.
+    NetBindService,
+    /// Make socket broadcasts and listen to multicasts.
+    NetBroadcast,
+    /// Perform network administration, such as configuring interfaces and routes.
+    NetAdmin,
+    /// Use raw and packet sockets.
+    NetRaw,
+    /// Lock memory.
+    IpcLock,
+    /// Bypass permission checks for System V IPC objects.
+    IpcOwner,
+    /// Load and unload kernel modules.
+    SysModule,
+    /// Perform I/O port operations.
+    SysRawio,
+    /// Use `chroot`.
+    SysChroot,
+    /// Trace arbitrary processes using `ptrace`.
+    SysPtrace,
+    /// Enable and disable process accounting.
+    SysPacct,
+    /// Perform a range of system administration operations.
+    SysAdmin,
+    /// Reboot the system.
+    SysBoot,
+    /// Raise process priority and change the priority of other processes.
+    SysNice,
+    /// Override resource limits.
+    SysResource,
+    /// Set the system clock.
+    SysTime,
+    /// Configure virtual terminals.
+    SysTtyConfig,
+    /// Create special files using `mknod`.
+    Mknod,
+    /// Establish leases on arbitrary files.
+    Lease,
+    /// Write records to the kernel auditing log.
+    AuditWrite,
+    /// Configure kernel auditing.
+    AuditControl,
+    /// Set file capabilities.
+    Setfcap,
+    /// Override Mandatory Access Control.
+    MacOverride,
+    /// Configure Mandatory Access Control.
+    MacAdmin,
+    /// Perform privileged `syslog` operations.
+    Syslog,
+    /// Trigger something that will wake up the system.
+    WakeAlarm,
+    /// Block system suspend.
+    BlockSuspend,
+    /// Read the kernel auditing log.
+    AuditRead,
+    /// Use performance monitoring.
+    Perfmon,
+    /// Use privileged BPF operations.
+    Bpf,
+    /// Use checkpoint and restore operations.
+    CheckpointRestore,
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Capability::All => "ALL",
+            Capability::Chown => "CHOWN",
+            Capability::DacOverride => "DAC_OVERRIDE",
+            Capability::DacReadSearch => "DAC_READ_SEARCH",
+            Capability::Fowner => "FOWNER",
+            Capability::Fsetid => "FSETID",
+            Capability::Kill => "KILL",
+            Capability::Setgid => "SETGID",
+            Capability::Setuid => "SETUID",
+            Capability::Setpcap => "SETPCAP",
+            Capability::LinuxImmutable => "LINUX_IMMUTABLE",
+            Capability::NetBindService => "NET_BIND_SERVICE",
+            Capability::NetBroadcast => "NET_BROADCAST",
+            Capability::NetAdmin => "NET_ADMIN",
+            Capability::NetRaw => "NET_RAW",
+            Capability::IpcLock => "IPC_LOCK",
+            Capability::IpcOwner => "IPC_OWNER",
+            Capability::SysModule => "SYS_MODULE",
+            Capability::SysRawio => "SYS_RAWIO",
+            Capability::SysChroot => "SYS_CHROOT",
+            Capability::SysPtrace => "SYS_PTRACE",
+            Capability::SysPacct => "SYS_PACCT",
+            Capability::SysAdmin => "SYS_ADMIN",
+            Capability::SysBoot => "SYS_BOOT",
+            Capability::SysNice => "SYS_NICE",
+            Capability::SysResource => "SYS_RESOURCE",
+            Capability::SysTime => "SYS_TIME",
+            Capability::SysTtyConfig => "SYS_TTY_CONFIG",
+            Capability::Mknod => "MKNOD",
+            Capability::Lease => "LEASE",
+            Capability::AuditWrite => "AUDIT_WRITE",
+            Capability::AuditControl => "AUDIT_CONTROL",
+            Capability::Setfcap => "SETFCAP",
+            Capability::MacOverride => "MAC_OVERRIDE",
+            Capability::MacAdmin => "MAC_ADMIN",
+            Capability::Syslog => "SYSLOG",
+            Capability::WakeAlarm => "WAKE_ALARM",
+            Capability::BlockSuspend => "BLOCK_SUSPEND",
+            Capability::AuditRead => "AUDIT_READ",
+            Capability::Perfmon => "PERFMON",
+            Capability::Bpf => "BPF",
+            Capability::CheckpointRestore => "CHECKPOINT_RESTORE",
+        })
+    }
+}
+
+impl FromStr for Capability {
+    type Err = Error;
+
+    /// Parse a capability such as `NET_ADMIN`, ignoring case and an optional `CAP_` prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_uppercase();
+        match name.strip_prefix("CAP_").unwrap_or(&name) {
+            "ALL" => Ok(Capability::All),
+            "CHOWN" => Ok(Capability::Chown),
+            "DAC_OVERRIDE" => Ok(Capability::DacOverride),
+            "DAC_READ_SEARCH" => Ok(Capability::DacReadSearch),
+            "FOWNER" => Ok(Capability::Fowner),
+            "FSETID" => Ok(Capability::Fsetid),
+            "KILL" => Ok(Capability::Kill),
+            "SETGID" => Ok(Capability::Setgid),
+            "SETUID" => Ok(Capability::Setuid),
+            "SETPCAP" => Ok(Capability::Setpcap),
+            "LINUX_IMMUTABLE" => Ok(Capability::LinuxImmutable),
+            "NET_BIND_SERVICE" => Ok(Capability::NetBindService),
+            "NET_BROADCAST" => Ok(Capability::NetBroadcast),
+            "NET_ADMIN" => Ok(Capability::NetAdmin),
+            "NET_RAW" => Ok(Capability::NetRaw),
+            "IPC_LOCK" => Ok(Capability::IpcLock),
+            "IPC_OWNER" => Ok(Capability::IpcOwner),
+            "SYS_MODULE" => Ok(Capability::SysModule),
+            "SYS_RAWIO" => Ok(Capability::SysRawio),
+            "SYS_CHROOT" => Ok(Capability::SysChroot),
+            "SYS_PTRACE" => Ok(Capability::SysPtrace),
+            "SYS_PACCT" => Ok(Capability::SysPacct),
+            "SYS_ADMIN" => Ok(Capability::SysAdmin),
+            "SYS_BOOT" => Ok(Capability::SysBoot),
+            "SYS_NICE" => Ok(Capability::SysNice),
+            "SYS_RESOURCE" => Ok(Capability::SysResource),
+            "SYS_TIME" => Ok(Capability::SysTime),
+            "SYS_TTY_CONFIG" => Ok(Capability::SysTtyConfig),
+            "MKNOD" => Ok(Capability::Mknod),
+            "LEASE" => Ok(Capability::Lease),
+            "AUDIT_WRITE" => Ok(Capability::AuditWrite),
+            "AUDIT_CONTROL" => Ok(Capability::AuditControl),
+            "SETFCAP" => Ok(Capability::Setfcap),
+            "MAC_OVERRIDE" => Ok(Capability::MacOverride),
+            "MAC_ADMIN" => Ok(Capability::MacAdmin),
+            "SYSLOG" => Ok(Capability::Syslog),
+            "WAKE_ALARM" => Ok(Capability::WakeAlarm),
+            "BLOCK_SUSPEND" => Ok(Capability::BlockSuspend),
+            "AUDIT_READ" => Ok(Capability::AuditRead),
+            "PERFMON" => Ok(Capability::Perfmon),
+            "BPF" => Ok(Capability::Bpf),
+            "CHECKPOINT_RESTORE" => Ok(Capability::CheckpointRestore),
+            _ => Err(Error::InvalidCapabilityError {
+                capability: String::from(s),
+            }),
+        }
+    }
+}
+
+/// Builder-style extension methods for [HostConfig](HostConfig).
+pub trait HostConfigExt {
+    /// Set the soft and hard limits of a ulimit, such as `nofile`, replacing any previous
+    /// limits of the same name.
+    fn ulimit(self, name: &str, soft: i64, hard: i64) -> Self;
+
+    /// Add a capability to the container.
+    fn cap_add(self, capability: Capability) -> Self;
+
+    /// Drop a capability from the container.
+    fn cap_drop(self, capability: Capability) -> Self;
+
+    /// Set a namespaced kernel parameter, such as `net.ipv4.ip_forward`.
+    fn sysctl(self, key: &str, value: &str) -> Self;
+}
+
+fn push_capability(capabilities: &mut Option<Vec<String>>, capability: Capability) {
+    let capabilities = capabilities.get_or_insert_with(Vec::new);
+    let capability = capability.to_string();
+    if !capabilities.contains(&capability) {
+        capabilities.push(capability);
+    }
+}
+
+impl HostConfigExt for HostConfig {
+    fn ulimit(mut self, name: &str, soft: i64, hard: i64) -> Self {
+        let ulimits = self.ulimits.get_or_insert_with(Vec::new);
+        ulimits.retain(|ulimit| ulimit.name.as_deref() != Some(name));
+        ulimits.push(ResourcesUlimits {
+            name: Some(String::from(name)),
+            soft: Some(soft),
+            hard: Some(hard),
+        });
+        self
+    }
+
+    fn cap_add(mut self, capability: Capability) -> Self {
+        push_capability(&mut self.cap_add, capability);
+        self
+    }
+
+    fn cap_drop(mut self, capability: Capability) -> Self {
+        push_capability(&mut self.cap_drop, capability);
+        self
+    }
+
+    fn sysctl(mut self, key: &str, value: &str) -> Self {
+        self.sysctls
+            .get_or_insert_with(HashMap::new)
+            .insert(String::from(key), String::from(value));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{Capability, HostConfigExt};
+    use crate::models::{HostConfig, ResourcesUlimits};
+
+    #[test]
+    fn test_host_config_ext() {
+        let host_config = HostConfig::default()
+            .ulimit("nofile", 512, 512)
+            .ulimit("nofile", 1024, 2048)
+            .cap_drop(Capability::All)
+            .cap_add(Capability::NetAdmin)
+            .cap_add(Capability::NetAdmin)
+            .sysctl("net.ipv4.ip_forward", "1");
+
+        assert_eq!(
+            host_config.ulimits,
+            Some(vec![ResourcesUlimits {
+                name: Some(String::from("nofile")),
+                soft: Some(1024),
+                hard: Some(2048),
+            }])
+        );
+        assert_eq!(host_config.cap_add, Some(vec![String::from("NET_ADMIN")]));
+        assert_eq!(host_config.cap_drop, Some(vec![String::from("ALL")]));
+        assert_eq!(
+            host_config.sysctls,
+            Some(HashMap::from([(
+                String::from("net.ipv4.ip_forward"),
+                String::from("1")
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_capability() {
+        assert_eq!(
+            "CAP_SYS_ADMIN".parse::<Capability>().unwrap(),
+            Capability::SysAdmin
+        );
+        assert_eq!("net_raw".parse::<Capability>().unwrap(), Capability::NetRaw);
+        assert_eq!(Capability::DacReadSearch.to_string(), "DAC_READ_SEARCH");
+        assert!("NET_FOO".parse::<Capability>().is_err());
+    }
+}
@@ -0,0 +1,593 @@
+//! Typed builders for the `log_config` field of a [HostConfig](crate::models::HostConfig),
+//! covering the options documented for each [logging
+//! driver](https://docs.docker.com/engine/logging/configure/).
+//!
+//! Options are validated when the configuration is built, rather than being rejected by the
+//! daemon or, worse, silently ignored.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bollard::container::log_config::{LogConfigBuilder, LogMode};
+//! use bollard::models::HostConfig;
+//!
+//! let log_config = LogConfigBuilder::json_file()
+//!     .max_size("10m")
+//!     .max_file(3)
+//!     .compress(true)
+//!     .mode(LogMode::NonBlocking)
+//!     .max_buffer_size("4m")
+//!     .build()
+//!     .unwrap();
+//!
+//! let host_config = HostConfig {
+//!     log_config: Some(log_config),
+//!     ..Default::default()
+//! };
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::errors::Error;
+use crate::models::HostConfigLogConfig;
+
+/// A logging driver, selecting the options available on a [Log Config
+/// Builder](LogConfigBuilder).
+pub trait LogDriver {
+    /// Name of the driver, as used in the `Type` of the log configuration.
+    const NAME: &'static str;
+
+    /// Check the driver-specific options, returning a description of the first problem.
+    fn validate(options: &HashMap<String, String>) -> Result<(), String>;
+}
+
+/// The `json-file` logging driver, the daemon's default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonFile;
+
+/// The `local` logging driver, with a compact on-disk format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Local;
+
+/// The `journald` logging driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Journald;
+
+/// The `syslog` logging driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Syslog;
+
+/// The `fluentd` logging driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fluentd;
+
+/// The `awslogs` logging driver, for Amazon CloudWatch Logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AwsLogs;
+
+/// How log messages are delivered from the container to the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogMode {
+    /// Block the container's writes until the driver accepts the message, the default.
+    #[default]
+    Blocking,
+    /// Buffer messages, dropping them when the buffer is full rather than blocking.
+    NonBlocking,
+}
+
+impl fmt::Display for LogMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LogMode::Blocking => "blocking",
+            LogMode::NonBlocking => "non-blocking",
+        })
+    }
+}
+
+/// Message format of the `syslog` logging driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogFormat {
+    /// [RFC 3164](https://tools.ietf.org/html/rfc3164).
+    Rfc3164,
+    /// [RFC 5424](https://tools.ietf.org/html/rfc5424).
+    Rfc5424,
+    /// RFC 5424 with microsecond timestamps.
+    Rfc5424Micro,
+}
+
+impl fmt::Display for SyslogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SyslogFormat::Rfc3164 => "rfc3164",
+            SyslogFormat::Rfc5424 => "rfc5424",
+            SyslogFormat::Rfc5424Micro => "rfc5424micro",
+        })
+    }
+}
+
+const SYSLOG_FACILITIES: &[&str] = &[
+    "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "uucp", "cron", "authpriv",
+    "ftp", "local0", "local1", "local2", "local3", "local4", "local5", "local6", "local7",
+];
+
+/// Whether the value is a size such as `1024`, `10k`, `10m` or `1.5g`.
+fn is_size(value: &str) -> bool {
+    let value = value.trim();
+    let value = value.strip_suffix(['b', 'B']).unwrap_or(value);
+    let number = value
+        .strip_suffix(['k', 'K', 'm', 'M', 'g', 'G'])
+        .unwrap_or(value)
+        .trim_end();
+    !number.is_empty()
+        && number
+            .parse::<f64>()
+            .is_ok_and(|n| n.is_finite() && n >= 0.0)
+}
+
+fn validate_size(options: &HashMap<String, String>, key: &str) -> Result<(), String> {
+    match options.get(key) {
+        Some(value) if !is_size(value) => Err(format!("{key} is not a size: {value}")),
+        _ => Ok(()),
+    }
+}
+
+fn validate_rotation(options: &HashMap<String, String>) -> Result<(), String> {
+    validate_size(options, "max-size")?;
+    match options.get("max-file").map(|value| value.parse::<u32>()) {
+        Some(Ok(0)) | Some(Err(_)) => Err(String::from("max-file must be at least 1")),
+        Some(Ok(_)) if !options.contains_key("max-size") => Err(String::from(
+            "max-file has no effect without max-size, logs would not be rotated",
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl LogDriver for JsonFile {
+    const NAME: &'static str = "json-file";
+
+    fn validate(options: &HashMap<String, String>) -> Result<(), String> {
+        validate_rotation(options)
+    }
+}
+
+impl LogDriver for Local {
+    const NAME: &'static str = "local";
+
+    fn validate(options: &HashMap<String, String>) -> Result<(), String> {
+        validate_rotation(options)
+    }
+}
+
+impl LogDriver for Journald {
+    const NAME: &'static str = "journald";
+
+    fn validate(_options: &HashMap<String, String>) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl LogDriver for Syslog {
+    const NAME: &'static str = "syslog";
+
+    fn validate(options: &HashMap<String, String>) -> Result<(), String> {
+        if let Some(address) = options.get("syslog-address") {
+            match address.split_once("://") {
+                Some(("tcp" | "udp" | "tcp+tls" | "unix" | "unixgram", rest))
+                    if !rest.is_empty() => {}
+                _ => return Err(format!("unsupported syslog-address: {address}")),
+            }
+        }
+        match options.get("syslog-facility") {
+            Some(facility) if !SYSLOG_FACILITIES.contains(&facility.as_str()) => {
+                Err(format!("unknown syslog-facility: {facility}"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl LogDriver for Fluentd {
+    const NAME: &'static str = "fluentd";
+
+    fn validate(options: &HashMap<String, String>) -> Result<(), String> {
+        validate_size(options, "fluentd-buffer-limit")
+    }
+}
+
+impl LogDriver for AwsLogs {
+    const NAME: &'static str = "awslogs";
+
+    fn validate(options: &HashMap<String, String>) -> Result<(), String> {
+        if !options.contains_key("awslogs-group") {
+            return Err(String::from("awslogs-group is required"));
+        }
+        if options.contains_key("awslogs-datetime-format")
+            && options.contains_key("awslogs-multiline-pattern")
+        {
+            return Err(String::from(
+                "awslogs-datetime-format and awslogs-multiline-pattern are mutually exclusive",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Builder for a [HostConfigLogConfig](HostConfigLogConfig), offering the options of the
+/// chosen [Log Driver](LogDriver).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogConfigBuilder<D> {
+    options: HashMap<String, String>,
+    driver: PhantomData<D>,
+}
+
+impl LogConfigBuilder<JsonFile> {
+    /// Configure the `json-file` driver.
+    pub fn json_file() -> Self {
+        Self::new()
+    }
+
+    /// Include the values of the given container labels in the log messages.
+    pub fn label(self, label: &str) -> Self {
+        self.append("labels", label)
+    }
+
+    /// Include the values of the given environment variables in the log messages.
+    pub fn env(self, env: &str) -> Self {
+        self.append("env", env)
+    }
+
+    /// Rotate the log once it reaches the given size, such as `10m`.
+    pub fn max_size(self, size: &str) -> Self {
+        self.set("max-size", size)
+    }
+
+    /// The number of rotated logs to keep, requires a [`max_size`](Self::max_size()).
+    pub fn max_file(self, count: u32) -> Self {
+        self.set("max-file", &count.to_string())
+    }
+
+    /// Compress rotated logs.
+    pub fn compress(self, compress: bool) -> Self {
+        self.set("compress", &compress.to_string())
+    }
+}
+
+impl LogConfigBuilder<Local> {
+    /// Configure the `local` driver.
+    pub fn local() -> Self {
+        Self::new()
+    }
+
+    /// Rotate the log once it reaches the given size, such as `20m`.
+    pub fn max_size(self, size: &str) -> Self {
+        self.set("max-size", size)
+    }
+
+    /// The number of rotated logs to keep, requires a [`max_size`](Self::max_size()).
+    pub fn max_file(self, count: u32) -> Self {
+        self.set("max-file", &count.to_string())
+    }
+
+    /// Compress rotated logs.
+    pub fn compress(self, compress: bool) -> Self {
+        self.set("compress", &compress.to_string())
+    }
+}
+
+impl LogConfigBuilder<Journald> {
+    /// Configure the `journald` driver.
+    pub fn journald() -> Self {
+        Self::new()
+    }
+
+    /// Template for the `CONTAINER_TAG` and `SYSLOG_IDENTIFIER` journal fields, such as
+    /// `{{.Name}}`.
+    pub fn tag(self, tag: &str) -> Self {
+        self.set("tag", tag)
+    }
+
+    /// Include the values of the given container labels in the journal fields.
+    pub fn label(self, label: &str) -> Self {
+        self.append("labels", label)
+    }
+
+    /// Include the values of the given environment variables in the journal fields.
+    pub fn env(self, env: &str) -> Self {
+        self.append("env", env)
+    }
+}
+
+impl LogConfigBuilder<Syslog> {
+    /// Configure the `syslog` driver.
+    pub fn syslog() -> Self {
+        Self::new()
+    }
+
+    /// Address of the syslog server, such as `udp://1.2.3.4:1111` or `unix:///dev/log`.
+    pub fn address(self, address: &str) -> Self {
+        self.set("syslog-address", address)
+    }
+
+    /// Syslog facility, such as `daemon` or `local0`.
+    pub fn facility(self, facility: &str) -> Self {
+        self.set("syslog-facility", facility)
+    }
+
+    /// Message format.
+    pub fn format(self, format: SyslogFormat) -> Self {
+        self.set("syslog-format", &format.to_string())
+    }
+
+    /// CA certificate used to verify a `tcp+tls` server.
+    pub fn tls_ca_cert(self, path: &str) -> Self {
+        self.set("syslog-tls-ca-cert", path)
+    }
+
+    /// Client certificate and key used to authenticate with a `tcp+tls` server.
+    pub fn tls_client_cert(self, cert_path: &str, key_path: &str) -> Self {
+        self.set("syslog-tls-cert", cert_path)
+            .set("syslog-tls-key", key_path)
+    }
+
+    /// Template for the syslog tag, such as `{{.Name}}`.
+    pub fn tag(self, tag: &str) -> Self {
+        self.set("tag", tag)
+    }
+
+    /// Include the values of the given container labels in the log messages.
+    pub fn label(self, label: &str) -> Self {
+        self.append("labels", label)
+    }
+
+    /// Include the values of the given environment variables in the log messages.
+    pub fn env(self, env: &str) -> Self {
+        self.append("env", env)
+    }
+}
+
+impl LogConfigBuilder<Fluentd> {
+    /// Configure the `fluentd` driver.
+    pub fn fluentd() -> Self {
+        Self::new()
+    }
+
+    /// Address of the fluentd daemon, such as `localhost:24224` or `unix:///path/to/socket`.
+    pub fn address(self, address: &str) -> Self {
+        self.set("fluentd-address", address)
+    }
+
+    /// Connect and send in the background, rather than blocking the container.
+    pub fn async_connect(self, enabled: bool) -> Self {
+        self.set("fluentd-async", &enabled.to_string())
+    }
+
+    /// Size of the buffer for messages that could not be sent yet, such as `8m`.
+    pub fn buffer_limit(self, size: &str) -> Self {
+        self.set("fluentd-buffer-limit", size)
+    }
+
+    /// Delay between connection retries, such as `1s`.
+    pub fn retry_wait(self, duration: &str) -> Self {
+        self.set("fluentd-retry-wait", duration)
+    }
+
+    /// Maximum number of connection retries.
+    pub fn max_retries(self, retries: u32) -> Self {
+        self.set("fluentd-max-retries", &retries.to_string())
+    }
+
+    /// Send timestamps with nanosecond precision.
+    pub fn sub_second_precision(self, enabled: bool) -> Self {
+        self.set("fluentd-sub-second-precision", &enabled.to_string())
+    }
+
+    /// Template for the fluentd tag, such as `docker.{{.Name}}`.
+    pub fn tag(self, tag: &str) -> Self {
+        self.set("tag", tag)
+    }
+
+    /// Include the values of the given container labels in the log records.
+    pub fn label(self, label: &str) -> Self {
+        self.append("labels", label)
+    }
+
+    /// Include the values of the given environment variables in the log records.
+    pub fn env(self, env: &str) -> Self {
+        self.append("env", env)
+    }
+}
+
+impl LogConfigBuilder<AwsLogs> {
+    /// Configure the `awslogs` driver, sending to the given log group.
+    pub fn awslogs(group: &str) -> Self {
+        Self::new().set("awslogs-group", group)
+    }
+
+    /// AWS region, otherwise taken from the daemon's environment.
+    pub fn region(self, region: &str) -> Self {
+        self.set("awslogs-region", region)
+    }
+
+    /// Log stream to send to, rather than the container ID.
+    pub fn stream(self, stream: &str) -> Self {
+        self.set("awslogs-stream", stream)
+    }
+
+    /// Create the log group if it does not exist.
+    pub fn create_group(self, create: bool) -> Self {
+        self.set("awslogs-create-group", &create.to_string())
+    }
+
+    /// `strftime` format marking the start of a multiline message, such as `%Y-%m-%d`.
+    pub fn datetime_format(self, format: &str) -> Self {
+        self.set("awslogs-datetime-format", format)
+    }
+
+    /// Regular expression marking the start of a multiline message.
+    pub fn multiline_pattern(self, pattern: &str) -> Self {
+        self.set("awslogs-multiline-pattern", pattern)
+    }
+
+    /// Override the CloudWatch Logs endpoint.
+    pub fn endpoint(self, endpoint: &str) -> Self {
+        self.set("awslogs-endpoint", endpoint)
+    }
+
+    /// Template for the log stream name, such as `{{.Name}}`.
+    pub fn tag(self, tag: &str) -> Self {
+        self.set("tag", tag)
+    }
+}
+
+impl<D: LogDriver> LogConfigBuilder<D> {
+    fn new() -> Self {
+        LogConfigBuilder {
+            options: HashMap::new(),
+            driver: PhantomData,
+        }
+    }
+
+    fn set(mut self, key: &str, value: &str) -> Self {
+        self.options.insert(String::from(key), String::from(value));
+        self
+    }
+
+    fn append(mut self, key: &str, value: &str) -> Self {
+        self.options
+            .entry(String::from(key))
+            .and_modify(|values| {
+                values.push(',');
+                values.push_str(value);
+            })
+            .or_insert_with(|| String::from(value));
+        self
+    }
+
+    /// Deliver messages in the given mode.
+    pub fn mode(self, mode: LogMode) -> Self {
+        self.set("mode", &mode.to_string())
+    }
+
+    /// Size of the buffer used in [non-blocking](LogMode::NonBlocking) mode, such as `4m`.
+    pub fn max_buffer_size(self, size: &str) -> Self {
+        self.set("max-buffer-size", size)
+    }
+
+    /// Validate the options and build the log configuration, for the `log_config` field of a
+    /// [HostConfig](crate::models::HostConfig).
+    pub fn build(self) -> Result<HostConfigLogConfig, Error> {
+        let validate = || {
+            if self.options.contains_key("max-buffer-size") {
+                if self.options.get("mode").map(String::as_str) != Some("non-blocking") {
+                    return Err(String::from(
+                        "max-buffer-size is only used in non-blocking mode",
+                    ));
+                }
+                validate_size(&self.options, "max-buffer-size")?;
+            }
+            D::validate(&self.options)
+        };
+
+        validate().map_err(|message| Error::InvalidLogConfigError {
+            driver: String::from(D::NAME),
+            message,
+        })?;
+
+        Ok(HostConfigLogConfig {
+            typ: Some(String::from(D::NAME)),
+            config: Some(self.options),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{is_size, LogConfigBuilder, LogMode, SyslogFormat};
+
+    #[test]
+    fn test_is_size() {
+        assert!(is_size("1024"));
+        assert!(is_size("1024b"));
+        assert!(is_size("10m"));
+        assert!(is_size("1.5g"));
+        assert!(is_size("10MB"));
+        assert!(is_size("10 kb"));
+        assert!(!is_size("10mm"));
+        assert!(!is_size("ten"));
+        assert!(!is_size("m"));
+        assert!(!is_size("-1m"));
+    }
+
+    #[test]
+    fn test_log_config_builder() {
+        let config = LogConfigBuilder::json_file()
+            .max_size("10m")
+            .max_file(3)
+            .label("app")
+            .label("tier")
+            .build()
+            .unwrap();
+
+        assert_eq!(config.typ.as_deref(), Some("json-file"));
+        assert_eq!(
+            config.config,
+            Some(HashMap::from([
+                (String::from("max-size"), String::from("10m")),
+                (String::from("max-file"), String::from("3")),
+                (String::from("labels"), String::from("app,tier")),
+            ]))
+        );
+
+        // a typo'd size, and rotation without a size, are rejected
+        assert!(LogConfigBuilder::local().max_size("10x").build().is_err());
+        assert!(LogConfigBuilder::local().max_file(3).build().is_err());
+        assert!(LogConfigBuilder::json_file()
+            .max_buffer_size("4m")
+            .build()
+            .is_err());
+        assert!(LogConfigBuilder::journald()
+            .mode(LogMode::NonBlocking)
+            .max_buffer_size("4m")
+            .build()
+            .is_ok());
+
+        assert!(LogConfigBuilder::syslog()
+            .address("tcp+tls://logs:6514")
+            .facility("local0")
+            .format(SyslogFormat::Rfc5424Micro)
+            .build()
+            .is_ok());
+        assert!(LogConfigBuilder::syslog()
+            .address("http://logs")
+            .build()
+            .is_err());
+        assert!(LogConfigBuilder::syslog()
+            .facility("local8")
+            .build()
+            .is_err());
+
+        assert!(LogConfigBuilder::fluentd()
+            .buffer_limit("8m")
+            .build()
+            .is_ok());
+
+        assert!(LogConfigBuilder::awslogs("group")
+            .datetime_format("%Y-%m-%d")
+            .multiline_pattern("^INFO")
+            .build()
+            .is_err());
+        assert_eq!(
+            LogConfigBuilder::awslogs("group")
+                .region("eu-west-1")
+                .build()
+                .unwrap()
+                .typ
+                .as_deref(),
+            Some("awslogs")
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Builder for the [Logs API](crate::Docker::logs()), and log lines with their timestamp parsed
+//! and their ANSI escape sequences, such as colors, removed.
+//!
+//! The [Parsed Logs API](crate::Docker::parsed_logs()) applies the client-side flags of the
+//! [Logs Options](LogsOptions), so that consumers do not have to post-process the raw output.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::logs::LogsOptionsBuilder;
+//!
+//! use futures_util::stream::StreamExt;
+//!
+//! let options = LogsOptionsBuilder::new()
+//!     .stdout(true)
+//!     .stderr(true)
+//!     .tail("100")
+//!     .strip_ansi(true)
+//!     .parse_timestamps(true)
+//!     .build()
+//!     .unwrap();
+//!
+//! async move {
+//!     let mut lines = docker.parsed_logs("my-container", Some(options));
+//!     while let Some(Ok(line)) = lines.next().await {
+//!         print!("{:?} {line}", line.timestamp);
+//!     }
+//! };
+//! ```
+
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::TryStreamExt;
+use serde::Serialize;
+
+use std::fmt;
+use std::time::Duration;
+
+use super::{LogOutput, LogsOptions};
+use crate::errors::Error;
+use crate::models::BollardDate;
+use crate::Docker;
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+
+/// Builder for [Logs Options](LogsOptions).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogsOptionsBuilder {
+    options: LogsOptions<String>,
+}
+
+impl Default for LogsOptionsBuilder {
+    fn default() -> Self {
+        LogsOptionsBuilder {
+            options: LogsOptions {
+                tail: String::from("all"),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl LogsOptionsBuilder {
+    /// Start with the options of `docker logs`, which return all the logs, from neither stream.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keep streaming new logs as the container writes them.
+    pub fn follow(mut self, follow: bool) -> Self {
+        self.options.follow = follow;
+        self
+    }
+
+    /// Return logs from `stdout`.
+    pub fn stdout(mut self, stdout: bool) -> Self {
+        self.options.stdout = stdout;
+        self
+    }
+
+    /// Return logs from `stderr`.
+    pub fn stderr(mut self, stderr: bool) -> Self {
+        self.options.stderr = stderr;
+        self
+    }
+
+    /// Only return logs since this time, as a UNIX timestamp.
+    pub fn since(mut self, since: i64) -> Self {
+        self.options.since = since;
+        self
+    }
+
+    /// Only return logs before this time, as a UNIX timestamp.
+    pub fn until(mut self, until: i64) -> Self {
+        self.options.until = until;
+        self
+    }
+
+    /// Prefix every log line with its timestamp.
+    pub fn timestamps(mut self, timestamps: bool) -> Self {
+        self.options.timestamps = timestamps;
+        self
+    }
+
+    /// Only return this number of lines from the end of the logs, or `all`.
+    pub fn tail(mut self, tail: &str) -> Self {
+        self.options.tail = String::from(tail);
+        self
+    }
+
+    /// Abort the stream when no data arrives for this long, rather than after the timeout set
+    /// on the client.
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.options.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Remove ANSI escape sequences, such as colors and cursor movements, from the lines.
+    pub fn strip_ansi(mut self, strip_ansi: bool) -> Self {
+        self.options.strip_ansi = strip_ansi;
+        self
+    }
+
+    /// Parse the timestamp of every line into a [Bollard Date](BollardDate), and remove it from
+    /// the text. Asks the daemon for [timestamps](LogsOptionsBuilder::timestamps()).
+    pub fn parse_timestamps(mut self, parse_timestamps: bool) -> Self {
+        self.options.parse_timestamps = parse_timestamps;
+        if parse_timestamps {
+            self.options.timestamps = true;
+        }
+        self
+    }
+
+    /// Validate the options and return them for the [Logs API](Docker::logs()) or the [Parsed
+    /// Logs API](Docker::parsed_logs()).
+    pub fn build(self) -> Result<LogsOptions<String>, Error> {
+        let invalid = |message: &str| Error::InvalidLogsOptionsError {
+            message: String::from(message),
+        };
+
+        if !self.options.stdout && !self.options.stderr {
+            return Err(invalid("at least one of stdout or stderr must be selected"));
+        }
+        if self.options.until != 0 && self.options.until < self.options.since {
+            return Err(invalid("until must not be before since"));
+        }
+        Ok(self.options)
+    }
+}
+
+/// A line of the [Parsed Logs API](Docker::parsed_logs()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedLogLine {
+    /// When the container wrote the line, if the timestamps were asked for and parsed.
+    pub timestamp: Option<BollardDate>,
+    /// The stream and text of the line, without the parsed timestamp.
+    pub output: LogOutput,
+}
+
+impl ParsedLogLine {
+    fn new(output: LogOutput, strip_ansi: bool, parse_timestamps: bool) -> Self {
+        let (timestamp, rest) = if parse_timestamps {
+            split_timestamp(output.as_ref())
+        } else {
+            (None, None)
+        };
+        if rest.is_none() && !strip_ansi {
+            return ParsedLogLine { timestamp, output };
+        }
+
+        let message = rest.unwrap_or(output.as_ref());
+        let message = if strip_ansi {
+            strip_ansi_escapes(message)
+        } else {
+            Bytes::copy_from_slice(message)
+        };
+
+        let output = match output {
+            LogOutput::StdErr { .. } => LogOutput::StdErr { message },
+            LogOutput::StdOut { .. } => LogOutput::StdOut { message },
+            LogOutput::StdIn { .. } => LogOutput::StdIn { message },
+            LogOutput::Console { .. } => LogOutput::Console { message },
+        };
+        ParsedLogLine { timestamp, output }
+    }
+}
+
+impl fmt::Display for ParsedLogLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.output.fmt(f)
+    }
+}
+
+/// The timestamp that prefixes a line, and the rest of the line, when the prefix parses.
+fn split_timestamp(line: &[u8]) -> (Option<BollardDate>, Option<&[u8]>) {
+    let Some(space) = line.iter().position(|b| *b == b' ') else {
+        return (None, None);
+    };
+    match std::str::from_utf8(&line[..space])
+        .ok()
+        .and_then(parse_timestamp)
+    {
+        Some(timestamp) => (Some(timestamp), Some(&line[space + 1..])),
+        None => (None, None),
+    }
+}
+
+#[cfg(feature = "time")]
+fn parse_timestamp(timestamp: &str) -> Option<BollardDate> {
+    time::OffsetDateTime::parse(timestamp, &time::format_description::well_known::Rfc3339).ok()
+}
+
+#[cfg(all(feature = "chrono", not(feature = "time")))]
+fn parse_timestamp(timestamp: &str) -> Option<BollardDate> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+}
+
+/// Without a date library, an RFC 3339 looking timestamp is kept as a string.
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+fn parse_timestamp(timestamp: &str) -> Option<BollardDate> {
+    let bytes = timestamp.as_bytes();
+    (bytes.len() >= 20
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[10] == b'T')
+        .then(|| String::from(timestamp))
+}
+
+/// Remove the CSI sequences, e.g. colors, the string sequences, e.g. window titles, and the
+/// other escape sequences from a line.
+fn strip_ansi_escapes(line: &[u8]) -> Bytes {
+    if !line.contains(&ESC) {
+        return Bytes::copy_from_slice(line);
+    }
+
+    let mut stripped = Vec::with_capacity(line.len());
+    let mut bytes = line.iter().copied();
+    while let Some(byte) = bytes.next() {
+        if byte != ESC {
+            stripped.push(byte);
+            continue;
+        }
+        match bytes.next() {
+            // parameters and intermediates up to a final byte
+            Some(b'[') => {
+                for byte in bytes.by_ref() {
+                    if (0x40..=0x7e).contains(&byte) {
+                        break;
+                    }
+                }
+            }
+            // terminated by BEL or `ESC \`
+            Some(b']' | b'P' | b'X' | b'^' | b'_') => {
+                let mut previous = 0;
+                for byte in bytes.by_ref() {
+                    if byte == BEL || (previous == ESC && byte == b'\\') {
+                        break;
+                    }
+                    previous = byte;
+                }
+            }
+            // character set designations
+            Some(b'(' | b')' | b'*' | b'+') => {
+                bytes.next();
+            }
+            _ => {}
+        }
+    }
+    Bytes::from(stripped)
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Parsed Logs
+    ///
+    /// Get container logs, with the [strip ANSI](LogsOptionsBuilder::strip_ansi()) and [parse
+    /// timestamps](LogsOptionsBuilder::parse_timestamps()) flags of the options applied.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Optional [Logs Options](LogsOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Parsed Log Line](ParsedLogLine) structs, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::logs::LogsOptionsBuilder;
+    ///
+    /// let options = LogsOptionsBuilder::new()
+    ///     .stdout(true)
+    ///     .parse_timestamps(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// docker.parsed_logs("hello-world", Some(options));
+    /// ```
+    pub fn parsed_logs<T>(
+        &self,
+        container_name: &str,
+        options: Option<LogsOptions<T>>,
+    ) -> impl Stream<Item = Result<ParsedLogLine, Error>>
+    where
+        T: Into<String> + Serialize,
+    {
+        let (strip_ansi, parse_timestamps) = options
+            .as_ref()
+            .map(|options| (options.strip_ansi, options.parse_timestamps))
+            .unwrap_or_default();
+
+        self.logs(container_name, options)
+            .map_ok(move |output| ParsedLogLine::new(output, strip_ansi, parse_timestamps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::{strip_ansi_escapes, LogsOptionsBuilder};
+    use crate::container::LogOutput;
+    use crate::errors::Error;
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    #[test]
+    fn test_strip_ansi_escapes() {
+        assert_eq!(
+            strip_ansi_escapes(b"\x1b[1;32mINFO\x1b[0m started \x1b]0;title\x07\x1b(Bdone\n"),
+            "INFO started done\n"
+        );
+        assert_eq!(strip_ansi_escapes(b"plain\n"), "plain\n");
+    }
+
+    #[test]
+    fn test_logs_options_builder() {
+        let options = LogsOptionsBuilder::new()
+            .stderr(true)
+            .parse_timestamps(true)
+            .build()
+            .unwrap();
+        assert!(options.timestamps);
+        assert_eq!(options.tail, "all");
+
+        assert!(matches!(
+            LogsOptionsBuilder::new().build(),
+            Err(Error::InvalidLogsOptionsError { .. })
+        ));
+        assert!(matches!(
+            LogsOptionsBuilder::new()
+                .stdout(true)
+                .since(10)
+                .until(5)
+                .build(),
+            Err(Error::InvalidLogsOptionsError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parsed_logs() {
+        let frame = |stream: u8, payload: &str| {
+            let mut frame = vec![stream, 0, 0, 0, 0, 0, 0, payload.len() as u8];
+            frame.extend_from_slice(payload.as_bytes());
+            frame
+        };
+        let body = [
+            frame(1, "2024-01-01T12:00:00.123456789Z \x1b[32mready\x1b[0m\n"),
+            frame(2, "no timestamp\n"),
+        ]
+        .concat();
+
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!(
+                "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/vnd.docker.raw-stream\r\nContent-Length:{}\r\n\r\n{}",
+                body.len(),
+                String::from_utf8(body).unwrap()
+            ),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let options = LogsOptionsBuilder::new()
+            .stdout(true)
+            .stderr(true)
+            .strip_ansi(true)
+            .parse_timestamps(true)
+            .build()
+            .unwrap();
+        let lines: Vec<_> = docker
+            .parsed_logs("hello-world", Some(options))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].timestamp.is_some());
+        assert_eq!(
+            lines[0].output,
+            LogOutput::StdOut {
+                message: "ready\n".into()
+            }
+        );
+        assert_eq!(lines[1].timestamp, None);
+        assert_eq!(lines[1].to_string(), "no timestamp\n");
+    }
+}
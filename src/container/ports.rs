@@ -0,0 +1,374 @@
+//! Typed port publishing for the [Create Container API](crate::Docker::create_container()), and
+//! discovery of the host ports the daemon chose.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::ports::{ContainerPort, PortBindings, Protocol};
+//! use bollard::container::{Config, CreateContainerOptions};
+//! use bollard::models::HostConfig;
+//!
+//! let ports = PortBindings::new()
+//!     .publish(80, Protocol::Tcp)
+//!     .to_random_host_port()
+//!     .publish(53, Protocol::Udp)
+//!     .to_host_port(5353);
+//!
+//! let config = Config {
+//!     image: Some(String::from("nginx:alpine")),
+//!     exposed_ports: Some(ports.exposed_ports()),
+//!     host_config: Some(HostConfig {
+//!         port_bindings: Some(ports.port_map()),
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//!
+//! async move {
+//!     let id = docker
+//!         .create_container(None::<CreateContainerOptions<String>>, config)
+//!         .await
+//!         .unwrap()
+//!         .id;
+//!     docker.start_container::<String>(&id, None).await.unwrap();
+//!     let addr = docker
+//!         .resolve_host_port(&id, ContainerPort::tcp(80))
+//!         .await
+//!         .unwrap();
+//!     println!("listening on {addr}");
+//! };
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+
+use super::InspectContainerOptions;
+use crate::errors::Error;
+use crate::models::{PortBinding, PortMap};
+use crate::Docker;
+
+/// Transport protocol of a container port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Protocol {
+    /// TCP, the default.
+    #[default]
+    Tcp,
+    /// UDP.
+    Udp,
+    /// SCTP.
+    Sctp,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+            Protocol::Sctp => "sctp",
+        })
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tcp" => Ok(Protocol::Tcp),
+            "udp" => Ok(Protocol::Udp),
+            "sctp" => Ok(Protocol::Sctp),
+            _ => Err(Error::InvalidPortError {
+                message: format!("unknown protocol: {s}"),
+            }),
+        }
+    }
+}
+
+/// A container port and its protocol, formatted as `80/tcp` by the docker API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContainerPort {
+    /// Port number inside the container.
+    pub port: u16,
+    /// Transport protocol.
+    pub protocol: Protocol,
+}
+
+impl ContainerPort {
+    /// A TCP port.
+    pub fn tcp(port: u16) -> Self {
+        ContainerPort {
+            port,
+            protocol: Protocol::Tcp,
+        }
+    }
+
+    /// A UDP port.
+    pub fn udp(port: u16) -> Self {
+        ContainerPort {
+            port,
+            protocol: Protocol::Udp,
+        }
+    }
+}
+
+impl fmt::Display for ContainerPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.port, self.protocol)
+    }
+}
+
+impl FromStr for ContainerPort {
+    type Err = Error;
+
+    /// Parse a port such as `80/tcp`, or `80`, which defaults to TCP.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (port, protocol) = match s.split_once('/') {
+            Some((port, protocol)) => (port, protocol.parse()?),
+            None => (s, Protocol::Tcp),
+        };
+        let port = port.parse().map_err(|_| Error::InvalidPortError {
+            message: format!("not a port number: {s}"),
+        })?;
+
+        Ok(ContainerPort { port, protocol })
+    }
+}
+
+/// Builder for the `ExposedPorts` of a container [Config](super::Config) and the
+/// `PortBindings` of its [HostConfig](crate::models::HostConfig).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortBindings {
+    bindings: HashMap<ContainerPort, Vec<PortBinding>>,
+}
+
+impl PortBindings {
+    /// Start without any published ports.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Publish a container port, completed by choosing the host port it maps to.
+    pub fn publish(self, port: u16, protocol: Protocol) -> PublishPort {
+        PublishPort {
+            bindings: self,
+            port: ContainerPort { port, protocol },
+            host_ip: None,
+        }
+    }
+
+    /// The ports to expose, for the `exposed_ports` field of a container
+    /// [Config](super::Config).
+    pub fn exposed_ports(&self) -> HashMap<String, HashMap<(), ()>> {
+        self.bindings
+            .keys()
+            .map(|port| (port.to_string(), HashMap::new()))
+            .collect()
+    }
+
+    /// The port bindings, for the `port_bindings` field of a
+    /// [HostConfig](crate::models::HostConfig).
+    pub fn port_map(&self) -> PortMap {
+        self.bindings
+            .iter()
+            .map(|(port, bindings)| (port.to_string(), Some(bindings.clone())))
+            .collect()
+    }
+}
+
+/// A container port being published, returned by [`PortBindings::publish`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishPort {
+    bindings: PortBindings,
+    port: ContainerPort,
+    host_ip: Option<IpAddr>,
+}
+
+impl PublishPort {
+    /// Only listen on the given host address, rather than on all addresses.
+    pub fn on_host_ip(mut self, host_ip: IpAddr) -> Self {
+        self.host_ip = Some(host_ip);
+        self
+    }
+
+    /// Map the port to the given host port.
+    pub fn to_host_port(self, host_port: u16) -> PortBindings {
+        self.bind(Some(host_port.to_string()))
+    }
+
+    /// Let the daemon choose a free host port, which can be looked up once the container is
+    /// started with [`resolve_host_port`](Docker::resolve_host_port()).
+    pub fn to_random_host_port(self) -> PortBindings {
+        self.bind(None)
+    }
+
+    fn bind(mut self, host_port: Option<String>) -> PortBindings {
+        self.bindings
+            .bindings
+            .entry(self.port)
+            .or_default()
+            .push(PortBinding {
+                host_ip: self.host_ip.map(|ip| ip.to_string()),
+                host_port,
+            });
+        self.bindings
+    }
+}
+
+/// Pick the address to connect to from the host bindings of a port, preferring IPv4 over the
+/// IPv6 duplicate the daemon reports for dual-stack bindings, and the loopback address over an
+/// unspecified address.
+fn host_socket_addr(bindings: &[PortBinding]) -> Option<SocketAddr> {
+    let mut addrs: Vec<SocketAddr> = bindings
+        .iter()
+        .filter_map(|binding| {
+            let port = binding.host_port.as_deref()?.parse().ok()?;
+            let ip = match binding.host_ip.as_deref() {
+                None | Some("") => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                Some(ip) => ip.parse().ok()?,
+            };
+            let ip = match ip {
+                IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+                ip => ip,
+            };
+            Some(SocketAddr::new(ip, port))
+        })
+        .collect();
+
+    addrs.sort_by_key(|addr| addr.is_ipv6());
+    addrs.into_iter().next()
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Resolve Host Port
+    ///
+    /// Look up the host address a started container's port is published on, e.g. after
+    /// publishing it with [`to_random_host_port`](PublishPort::to_random_host_port()).
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as a string slice.
+    ///  - The [Container Port](ContainerPort) to look up.
+    ///
+    /// # Returns
+    ///
+    ///  - A [SocketAddr](SocketAddr), wrapped in a Future. Unspecified host addresses are
+    ///    resolved to the loopback address.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::ports::ContainerPort;
+    ///
+    /// docker.resolve_host_port("nginx", ContainerPort::tcp(80));
+    /// ```
+    pub async fn resolve_host_port(
+        &self,
+        container_name: &str,
+        port: ContainerPort,
+    ) -> Result<SocketAddr, Error> {
+        let container = self
+            .inspect_container(container_name, None::<InspectContainerOptions>)
+            .await?;
+
+        container
+            .network_settings
+            .and_then(|settings| settings.ports)
+            .and_then(|mut ports| ports.remove(&port.to_string()))
+            .flatten()
+            .and_then(|bindings| host_socket_addr(&bindings))
+            .ok_or_else(|| Error::PortNotPublishedError {
+                port: port.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::{host_socket_addr, ContainerPort, PortBindings, Protocol};
+    use crate::models::PortBinding;
+
+    #[test]
+    fn test_port_bindings() {
+        let ports = PortBindings::new()
+            .publish(80, Protocol::Tcp)
+            .to_random_host_port()
+            .publish(53, Protocol::Udp)
+            .on_host_ip("127.0.0.1".parse().unwrap())
+            .to_host_port(5353);
+
+        let exposed = ports.exposed_ports();
+        assert!(exposed.contains_key("80/tcp"));
+        assert!(exposed.contains_key("53/udp"));
+
+        let map = ports.port_map();
+        assert_eq!(
+            map.get("80/tcp").unwrap().as_deref(),
+            Some(
+                &[PortBinding {
+                    host_ip: None,
+                    host_port: None,
+                }][..]
+            )
+        );
+        assert_eq!(
+            map.get("53/udp").unwrap().as_deref(),
+            Some(
+                &[PortBinding {
+                    host_ip: Some(String::from("127.0.0.1")),
+                    host_port: Some(String::from("5353")),
+                }][..]
+            )
+        );
+
+        assert_eq!(
+            "8080/udp".parse::<ContainerPort>().unwrap(),
+            ContainerPort::udp(8080)
+        );
+        assert_eq!(
+            "8080".parse::<ContainerPort>().unwrap(),
+            ContainerPort::tcp(8080)
+        );
+        assert!("http/tcp".parse::<ContainerPort>().is_err());
+    }
+
+    #[test]
+    fn test_host_socket_addr() {
+        let bindings = vec![
+            PortBinding {
+                host_ip: Some(String::from("::")),
+                host_port: Some(String::from("32768")),
+            },
+            PortBinding {
+                host_ip: Some(String::from("0.0.0.0")),
+                host_port: Some(String::from("32768")),
+            },
+        ];
+        assert_eq!(
+            host_socket_addr(&bindings),
+            Some("127.0.0.1:32768".parse::<SocketAddr>().unwrap())
+        );
+
+        let bindings = vec![PortBinding {
+            host_ip: Some(String::from("::")),
+            host_port: Some(String::from("32769")),
+        }];
+        assert_eq!(
+            host_socket_addr(&bindings),
+            Some("[::1]:32769".parse::<SocketAddr>().unwrap())
+        );
+
+        assert_eq!(host_socket_addr(&[]), None);
+    }
+}
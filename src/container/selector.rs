@@ -0,0 +1,412 @@
+//! Label selectors, in the syntax of Kubernetes, e.g. `app=web,env!=prod,tier in (frontend,api)`,
+//! to select containers by their labels.
+//!
+//! The [Select Containers API](crate::Docker::select_containers()) sends the requirements the
+//! daemon can filter on, `key`, `key=value` and `key in (value)`, as `label` filters, and
+//! checks the other requirements, `!key`, `key!=value`, `key in (...)` and `key notin (...)`, on
+//! the listed containers. As in Kubernetes, `!=` and `notin` match containers without the label.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::selector::LabelSelector;
+//!
+//! let selector: LabelSelector = "app=web,env!=prod".parse().unwrap();
+//!
+//! async move {
+//!     for container in docker.select_containers(&selector).await.unwrap() {
+//!         println!("{:?} {:?}", container.id, container.labels);
+//!     }
+//! };
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::ListContainersOptions;
+use crate::errors::Error;
+use crate::models::ContainerSummary;
+use crate::Docker;
+
+/// A requirement on one label of a [Label Selector](LabelSelector).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LabelRequirement {
+    /// `key`: the label is set.
+    Exists(String),
+    /// `!key`: the label is not set.
+    DoesNotExist(String),
+    /// `key=value` or `key==value`: the label is set to the value.
+    Equals(String, String),
+    /// `key!=value`: the label is not set to the value, or not set at all.
+    NotEquals(String, String),
+    /// `key in (value1,value2)`: the label is set to one of the values.
+    In(String, Vec<String>),
+    /// `key notin (value1,value2)`: the label is not set to any of the values, or not set at
+    /// all.
+    NotIn(String, Vec<String>),
+}
+
+impl LabelRequirement {
+    /// Whether the labels meet the requirement.
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        match self {
+            LabelRequirement::Exists(key) => labels.contains_key(key),
+            LabelRequirement::DoesNotExist(key) => !labels.contains_key(key),
+            LabelRequirement::Equals(key, value) => labels.get(key) == Some(value),
+            LabelRequirement::NotEquals(key, value) => labels.get(key) != Some(value),
+            LabelRequirement::In(key, values) => {
+                labels.get(key).is_some_and(|v| values.contains(v))
+            }
+            LabelRequirement::NotIn(key, values) => {
+                !labels.get(key).is_some_and(|v| values.contains(v))
+            }
+        }
+    }
+
+    /// The `label` filter of the daemon that is equivalent to the requirement, if any.
+    fn daemon_filter(&self) -> Option<String> {
+        match self {
+            LabelRequirement::Exists(key) => Some(key.clone()),
+            LabelRequirement::Equals(key, value) => Some(format!("{key}={value}")),
+            LabelRequirement::In(key, values) if values.len() == 1 => {
+                Some(format!("{key}={}", values[0]))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LabelRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelRequirement::Exists(key) => write!(f, "{key}"),
+            LabelRequirement::DoesNotExist(key) => write!(f, "!{key}"),
+            LabelRequirement::Equals(key, value) => write!(f, "{key}={value}"),
+            LabelRequirement::NotEquals(key, value) => write!(f, "{key}!={value}"),
+            LabelRequirement::In(key, values) => write!(f, "{key} in ({})", values.join(",")),
+            LabelRequirement::NotIn(key, values) => write!(f, "{key} notin ({})", values.join(",")),
+        }
+    }
+}
+
+/// Comma-separated [Label Requirements](LabelRequirement), all of which must be met. An empty
+/// selector selects every container.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LabelSelector {
+    requirements: Vec<LabelRequirement>,
+}
+
+impl LabelSelector {
+    /// Parse a selector, e.g. `app=web,env!=prod,!canary,tier in (frontend,api)`.
+    pub fn parse(selector: &str) -> Result<LabelSelector, Error> {
+        let invalid = |message: String| Error::InvalidLabelSelectorError {
+            selector: String::from(selector),
+            message,
+        };
+
+        if selector.trim().is_empty() {
+            return Ok(LabelSelector::default());
+        }
+
+        let mut requirements = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+        // split on the commas outside of the sets of values, up to the end of the selector
+        for (i, c) in selector.char_indices().chain([(selector.len(), ',')]) {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    let requirement = selector[start..i].trim();
+                    requirements.push(parse_requirement(requirement).map_err(invalid)?);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+            if !(0..=1).contains(&depth) {
+                return Err(invalid(String::from("unbalanced parentheses")));
+            }
+        }
+        if depth != 0 {
+            return Err(invalid(String::from("unbalanced parentheses")));
+        }
+
+        Ok(LabelSelector { requirements })
+    }
+
+    /// The requirements of the selector.
+    pub fn requirements(&self) -> &[LabelRequirement] {
+        &self.requirements
+    }
+
+    /// Whether the labels meet all the requirements.
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        self.requirements
+            .iter()
+            .all(|requirement| requirement.matches(labels))
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = Error;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        LabelSelector::parse(selector)
+    }
+}
+
+impl fmt::Display for LabelSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, requirement) in self.requirements.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            requirement.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_key(key: &str) -> Result<String, String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(String::from("empty label key"));
+    }
+    if key
+        .chars()
+        .any(|c| c.is_whitespace() || "=!(),".contains(c))
+    {
+        return Err(format!("invalid label key `{key}`"));
+    }
+    Ok(String::from(key))
+}
+
+fn parse_value(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value
+        .chars()
+        .any(|c| c.is_whitespace() || "=!(),".contains(c))
+    {
+        return Err(format!("invalid label value `{value}`"));
+    }
+    Ok(String::from(value))
+}
+
+fn parse_requirement(requirement: &str) -> Result<LabelRequirement, String> {
+    if let Some(key) = requirement.strip_prefix('!') {
+        return Ok(LabelRequirement::DoesNotExist(parse_key(key)?));
+    }
+
+    if let Some((key, rest)) = requirement.split_once(char::is_whitespace) {
+        for operator in ["notin", "in"] {
+            let Some(values) = rest.trim_start().strip_prefix(operator) else {
+                continue;
+            };
+            let Some(values) = values
+                .trim_start()
+                .strip_prefix('(')
+                .and_then(|values| values.strip_suffix(')'))
+            else {
+                continue;
+            };
+            if values.trim().is_empty() {
+                return Err(format!("empty set of values for `{key}`"));
+            }
+            let key = parse_key(key)?;
+            let values = values
+                .split(',')
+                .map(parse_value)
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(if operator == "in" {
+                LabelRequirement::In(key, values)
+            } else {
+                LabelRequirement::NotIn(key, values)
+            });
+        }
+    }
+
+    if let Some((key, value)) = requirement.split_once("!=") {
+        return Ok(LabelRequirement::NotEquals(
+            parse_key(key)?,
+            parse_value(value)?,
+        ));
+    }
+    if let Some((key, value)) = requirement
+        .split_once("==")
+        .or_else(|| requirement.split_once('='))
+    {
+        return Ok(LabelRequirement::Equals(
+            parse_key(key)?,
+            parse_value(value)?,
+        ));
+    }
+    Ok(LabelRequirement::Exists(parse_key(requirement)?))
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Select Containers
+    ///
+    /// List the containers, running or not, whose labels match a [Label
+    /// Selector](LabelSelector). The requirements the daemon supports are sent as `label`
+    /// filters, the others are checked on the listed containers.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Label Selector](LabelSelector) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Container Summary](ContainerSummary), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::selector::LabelSelector;
+    ///
+    /// let selector = LabelSelector::parse("app=web,tier notin (batch)").unwrap();
+    ///
+    /// async move {
+    ///     docker.select_containers(&selector).await
+    /// };
+    /// ```
+    pub async fn select_containers(
+        &self,
+        selector: &LabelSelector,
+    ) -> Result<Vec<ContainerSummary>, Error> {
+        let labels: Vec<String> = selector
+            .requirements
+            .iter()
+            .filter_map(LabelRequirement::daemon_filter)
+            .collect();
+
+        let mut filters = HashMap::new();
+        if !labels.is_empty() {
+            filters.insert(String::from("label"), labels);
+        }
+
+        let mut containers = self
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters,
+                ..Default::default()
+            }))
+            .await?;
+
+        let no_labels = HashMap::new();
+        containers
+            .retain(|container| selector.matches(container.labels.as_ref().unwrap_or(&no_labels)));
+        Ok(containers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::{LabelRequirement, LabelSelector};
+    use crate::errors::Error;
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    #[test]
+    fn test_parse_label_selector() {
+        let selector = LabelSelector::parse(
+            "app=web, env != prod,!canary,tier in (frontend, api),com.example.team,zone notin (a)",
+        )
+        .unwrap();
+        assert_eq!(
+            selector.requirements(),
+            &[
+                LabelRequirement::Equals(String::from("app"), String::from("web")),
+                LabelRequirement::NotEquals(String::from("env"), String::from("prod")),
+                LabelRequirement::DoesNotExist(String::from("canary")),
+                LabelRequirement::In(
+                    String::from("tier"),
+                    vec![String::from("frontend"), String::from("api")]
+                ),
+                LabelRequirement::Exists(String::from("com.example.team")),
+                LabelRequirement::NotIn(String::from("zone"), vec![String::from("a")]),
+            ]
+        );
+        assert_eq!(
+            selector.to_string(),
+            "app=web,env!=prod,!canary,tier in (frontend,api),com.example.team,zone notin (a)"
+        );
+        assert_eq!(
+            LabelSelector::parse("app==web").unwrap(),
+            LabelSelector::parse("app=web").unwrap()
+        );
+        assert!(LabelSelector::parse("").unwrap().requirements().is_empty());
+
+        for selector in [
+            "app=web,",
+            "tier in ()",
+            "tier in (a",
+            "a b",
+            "=web",
+            "x=(y)",
+        ] {
+            assert!(
+                matches!(
+                    LabelSelector::parse(selector),
+                    Err(Error::InvalidLabelSelectorError { .. })
+                ),
+                "{selector}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_label_selector_matches() {
+        let selector = LabelSelector::parse("app=web,env!=prod,tier notin (batch)").unwrap();
+        let labels = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (String::from(*k), String::from(*v)))
+                .collect::<HashMap<_, _>>()
+        };
+
+        assert!(selector.matches(&labels(&[("app", "web")])));
+        assert!(selector.matches(&labels(&[("app", "web"), ("env", "dev")])));
+        assert!(!selector.matches(&labels(&[("app", "web"), ("env", "prod")])));
+        assert!(!selector.matches(&labels(&[("app", "web"), ("tier", "batch")])));
+        assert!(!selector.matches(&labels(&[("app", "db")])));
+    }
+
+    #[tokio::test]
+    async fn test_select_containers() {
+        let body = r#"[
+            {"Id": "a", "Labels": {"app": "web", "env": "prod"}},
+            {"Id": "b", "Labels": {"app": "web", "env": "dev"}},
+            {"Id": "c", "Labels": {"app": "web"}}
+        ]"#;
+
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!("HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}", body.len()),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let containers = docker
+            .select_containers(&"app=web,env!=prod".parse().unwrap())
+            .await
+            .unwrap();
+        let ids: Vec<_> = containers
+            .iter()
+            .filter_map(|container| container.id.as_deref())
+            .collect();
+        assert_eq!(ids, ["b", "c"]);
+    }
+}
@@ -0,0 +1,235 @@
+//! Typed signals for the [Kill Container API](crate::Docker::kill_container()) and the [Stop
+//! Container API](crate::Docker::stop_container()), parsed before a request is sent rather than
+//! rejected by the daemon, e.g. for a typo such as `SIGKIL`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::signal::Signal;
+//! use bollard::container::{KillContainerOptions, StopContainerOptions};
+//!
+//! let signal: Signal = "HUP".parse().unwrap();
+//! assert_eq!(signal, Signal::Hup);
+//!
+//! docker.kill_container("nginx", Some(KillContainerOptions { signal }));
+//!
+//! docker.stop_container(
+//!     "postgres",
+//!     Some(StopContainerOptions {
+//!         t: 30,
+//!         signal: Some(Signal::Int),
+//!     }),
+//! );
+//! ```
+
+use serde::{Serialize, Serializer};
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::Error;
+
+/// The highest real-time signal number on linux, `SIGRTMAX`.
+const SIGRTMAX: u8 = 64;
+
+/// A signal sent to the main process of a container, formatted as `SIGTERM` or as a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// `SIGHUP`, e.g. to reload the configuration of a service.
+    Hup,
+    /// `SIGINT`, as sent by `Ctrl-C`.
+    Int,
+    /// `SIGQUIT`.
+    Quit,
+    /// `SIGABRT`.
+    Abrt,
+    /// `SIGKILL`, which cannot be handled by the process.
+    Kill,
+    /// `SIGUSR1`.
+    Usr1,
+    /// `SIGUSR2`.
+    Usr2,
+    /// `SIGALRM`.
+    Alrm,
+    /// `SIGTERM`, the default signal to stop a container.
+    Term,
+    /// `SIGCONT`.
+    Cont,
+    /// `SIGSTOP`, which cannot be handled by the process.
+    Stop,
+    /// `SIGWINCH`.
+    Winch,
+    /// A signal by its linux number, e.g. a real-time signal.
+    Number(u8),
+}
+
+/// The named signals with their linux numbers.
+const SIGNALS: [(Signal, &str, u8); 12] = [
+    (Signal::Hup, "HUP", 1),
+    (Signal::Int, "INT", 2),
+    (Signal::Quit, "QUIT", 3),
+    (Signal::Abrt, "ABRT", 6),
+    (Signal::Kill, "KILL", 9),
+    (Signal::Usr1, "USR1", 10),
+    (Signal::Usr2, "USR2", 12),
+    (Signal::Alrm, "ALRM", 14),
+    (Signal::Term, "TERM", 15),
+    (Signal::Cont, "CONT", 18),
+    (Signal::Stop, "STOP", 19),
+    (Signal::Winch, "WINCH", 28),
+];
+
+impl Signal {
+    /// The signal with the given linux number, naming the signal if it is known.
+    pub fn from_number(number: u8) -> Result<Self, Error> {
+        if number == 0 || number > SIGRTMAX {
+            return Err(Error::InvalidSignalError {
+                signal: number.to_string(),
+                message: format!("signal numbers range from 1 to {SIGRTMAX}"),
+            });
+        }
+        Ok(SIGNALS
+            .iter()
+            .find(|(_, _, n)| *n == number)
+            .map(|(signal, _, _)| *signal)
+            .unwrap_or(Signal::Number(number)))
+    }
+
+    /// The linux number of the signal.
+    pub fn number(&self) -> u8 {
+        match self {
+            Signal::Number(number) => *number,
+            signal => SIGNALS
+                .iter()
+                .find(|(s, _, _)| s == signal)
+                .map(|(_, _, number)| *number)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Check the signal can be sent to a container of the given operating system, as reported by
+    /// the `os` of the [Version API](crate::Docker::version()). Windows containers only handle
+    /// `SIGTERM` and `SIGKILL`.
+    pub fn validate(&self, os: &str) -> Result<(), Error> {
+        let number = self.number();
+        let valid = match os {
+            "windows" => matches!(number, 9 | 15),
+            _ => (1..=SIGRTMAX).contains(&number),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignalError {
+                signal: self.to_string(),
+                message: format!("not supported on {os}"),
+            })
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signal::Number(number) => write!(f, "{number}"),
+            signal => {
+                let name = SIGNALS
+                    .iter()
+                    .find(|(s, _, _)| s == signal)
+                    .map(|(_, name, _)| *name)
+                    .unwrap_or_default();
+                write!(f, "SIG{name}")
+            }
+        }
+    }
+}
+
+impl FromStr for Signal {
+    type Err = Error;
+
+    /// Parse a signal such as `SIGTERM`, `TERM` or `15`, as the docker CLI does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(number) = s.parse::<u8>() {
+            return Signal::from_number(number);
+        }
+        let upper = s.to_uppercase();
+        let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+        SIGNALS
+            .iter()
+            .find(|(_, n, _)| *n == name)
+            .map(|(signal, _, _)| *signal)
+            .ok_or_else(|| Error::InvalidSignalError {
+                signal: String::from(s),
+                message: String::from("unknown signal"),
+            })
+    }
+}
+
+impl From<Signal> for String {
+    fn from(signal: Signal) -> Self {
+        signal.to_string()
+    }
+}
+
+impl Serialize for Signal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Signal;
+    use crate::container::{KillContainerOptions, StopContainerOptions};
+
+    #[test]
+    fn test_signal_parse() {
+        assert_eq!("SIGTERM".parse::<Signal>().unwrap(), Signal::Term);
+        assert_eq!("kill".parse::<Signal>().unwrap(), Signal::Kill);
+        assert_eq!("1".parse::<Signal>().unwrap(), Signal::Hup);
+        assert_eq!("34".parse::<Signal>().unwrap(), Signal::Number(34));
+        assert!("SIGKIL".parse::<Signal>().is_err());
+        assert!("0".parse::<Signal>().is_err());
+        assert!("65".parse::<Signal>().is_err());
+
+        assert_eq!(Signal::Usr1.to_string(), "SIGUSR1");
+        assert_eq!(Signal::Number(34).to_string(), "34");
+        assert_eq!(Signal::Winch.number(), 28);
+    }
+
+    #[test]
+    fn test_signal_validate() {
+        assert!(Signal::Hup.validate("linux").is_ok());
+        assert!(Signal::Number(34).validate("linux").is_ok());
+        assert!(Signal::Number(65).validate("linux").is_err());
+        assert!(Signal::Kill.validate("windows").is_ok());
+        assert!(Signal::Hup.validate("windows").is_err());
+    }
+
+    #[test]
+    fn test_signal_options() {
+        let query = serde_urlencoded::to_string(KillContainerOptions {
+            signal: Signal::Kill,
+        })
+        .unwrap();
+        assert_eq!(query, "signal=SIGKILL");
+
+        let query = serde_urlencoded::to_string(StopContainerOptions {
+            t: 10,
+            signal: Some(Signal::Number(34)),
+        })
+        .unwrap();
+        assert_eq!(query, "t=10&signal=34");
+
+        let query = serde_urlencoded::to_string(StopContainerOptions {
+            t: 10,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(query, "t=10");
+    }
+}
@@ -0,0 +1,201 @@
+//! Typed processes for the [Top Processes API](crate::Docker::top_processes()), which returns the
+//! output of `ps` as column titles and rows of strings.
+//!
+//! The well-known columns of `ps`, such as `PID` or `%CPU`, are parsed into the fields of a
+//! [Process Entry](ProcessEntry). Every column, including those of exotic `ps` options, remains
+//! available by its title.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::TopOptions;
+//!
+//! async move {
+//!     let processes = docker
+//!         .top_process_entries("my-container", Some(TopOptions { ps_args: "aux" }))
+//!         .await
+//!         .unwrap();
+//!     for process in processes {
+//!         println!(
+//!             "{:?} {:?}% {:?}",
+//!             process.pid, process.cpu, process.command
+//!         );
+//!     }
+//! };
+//! ```
+
+use serde::Serialize;
+
+use super::TopOptions;
+use crate::errors::Error;
+use crate::models::ContainerTopResponse;
+use crate::Docker;
+
+/// A process running in a container, parsed from a row of the
+/// [Top Processes API](Docker::top_processes()).
+///
+/// Fields are `None` when `ps` was not asked for the column, or when its value does not parse.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessEntry {
+    /// Process ID, from the `PID` column.
+    pub pid: Option<u32>,
+    /// Parent process ID, from the `PPID` column.
+    pub ppid: Option<u32>,
+    /// User running the process, from the `USER`, `UID`, `EUSER` or `RUSER` column.
+    pub user: Option<String>,
+    /// CPU usage in percent, from the `%CPU` or `C` column.
+    pub cpu: Option<f64>,
+    /// Command line of the process, from the `CMD`, `COMMAND` or `ARGS` column, or the `Name`
+    /// column of Windows containers.
+    pub command: Option<String>,
+    /// Every column of the row, as pairs of title and value, in the order of the titles.
+    pub columns: Vec<(String, String)>,
+}
+
+impl ProcessEntry {
+    /// Parse a row of `ps` output, given the column titles.
+    pub fn new(titles: &[String], row: &[String]) -> Self {
+        let columns: Vec<(String, String)> = titles
+            .iter()
+            .zip(row)
+            .map(|(title, value)| (title.clone(), value.clone()))
+            .collect();
+
+        let mut entry = ProcessEntry::default();
+        for (title, value) in &columns {
+            match title.trim().to_ascii_uppercase().as_str() {
+                "PID" => entry.pid = value.trim().parse().ok(),
+                "PPID" => entry.ppid = value.trim().parse().ok(),
+                "USER" | "UID" | "EUSER" | "RUSER" => {
+                    entry.user.get_or_insert_with(|| value.clone());
+                }
+                "%CPU" | "C" => entry.cpu = value.trim().parse().ok(),
+                "CMD" | "COMMAND" | "ARGS" | "NAME" => {
+                    entry.command.get_or_insert_with(|| value.clone());
+                }
+                _ => (),
+            }
+        }
+        entry.columns = columns;
+        entry
+    }
+
+    /// The value of the column with the given title, ignoring case.
+    pub fn get(&self, title: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|(t, _)| t.eq_ignore_ascii_case(title))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse every row of a [Container Top Response](ContainerTopResponse).
+    pub fn from_response(response: &ContainerTopResponse) -> Vec<ProcessEntry> {
+        let titles = response.titles.as_deref().unwrap_or_default();
+        response
+            .processes
+            .iter()
+            .flatten()
+            .map(|row| ProcessEntry::new(titles, row))
+            .collect()
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Top Process Entries
+    ///
+    /// List processes running inside a container, as typed [Process Entries](ProcessEntry).
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as string slice.
+    ///  - Optional [Top Options](TopOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Process Entry](ProcessEntry) structs, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::TopOptions;
+    ///
+    /// let options = Some(TopOptions{
+    ///     ps_args: "-ef",
+    /// });
+    ///
+    /// docker.top_process_entries("fussybeaver/uhttpd", options);
+    /// ```
+    pub async fn top_process_entries<T>(
+        &self,
+        container_name: &str,
+        options: Option<TopOptions<T>>,
+    ) -> Result<Vec<ProcessEntry>, Error>
+    where
+        T: Into<String> + Serialize,
+    {
+        let response = self.top_processes(container_name, options).await?;
+        Ok(ProcessEntry::from_response(&response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProcessEntry;
+    use crate::models::ContainerTopResponse;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| String::from(*value)).collect()
+    }
+
+    #[test]
+    fn test_process_entries() {
+        let response = ContainerTopResponse {
+            titles: Some(strings(&[
+                "USER", "PID", "%CPU", "%MEM", "VSZ", "RSS", "TTY", "STAT", "START", "TIME",
+                "COMMAND",
+            ])),
+            processes: Some(vec![strings(&[
+                "root",
+                "1",
+                "0.5",
+                "0.1",
+                "1624",
+                "964",
+                "?",
+                "Ss",
+                "10:00",
+                "0:00",
+                "nginx -g daemon off;",
+            ])]),
+        };
+
+        let entries = ProcessEntry::from_response(&response);
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.pid, Some(1));
+        assert_eq!(entry.ppid, None);
+        assert_eq!(entry.user.as_deref(), Some("root"));
+        assert_eq!(entry.cpu, Some(0.5));
+        assert_eq!(entry.command.as_deref(), Some("nginx -g daemon off;"));
+        assert_eq!(entry.get("stat"), Some("Ss"));
+        assert_eq!(entry.get("WCHAN"), None);
+
+        let entry = ProcessEntry::new(
+            &strings(&["UID", "PID", "PPID", "C", "STIME", "TTY", "TIME", "CMD"]),
+            &strings(&["999", "42", "1", "0", "10:00", "?", "00:00:00", "sleep 100"]),
+        );
+        assert_eq!(entry.pid, Some(42));
+        assert_eq!(entry.ppid, Some(1));
+        assert_eq!(entry.user.as_deref(), Some("999"));
+        assert_eq!(entry.cpu, Some(0.0));
+        assert_eq!(entry.command.as_deref(), Some("sleep 100"));
+
+        assert!(ProcessEntry::from_response(&ContainerTopResponse::default()).is_empty());
+    }
+}
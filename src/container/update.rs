@@ -0,0 +1,287 @@
+//! Builder for the [Update Container API](crate::Docker::update_container()), which changes the
+//! resources and restart policy of a running container.
+//!
+//! Only the fields that are set are sent, so the other resources of the container are left as
+//! they are. Combinations that the daemon rejects, such as Windows-only and Linux-only resources
+//! in the same update, are reported when the options are built.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::update::UpdateContainerBuilder;
+//! use bollard::models::{RestartPolicy, RestartPolicyNameEnum};
+//!
+//! let options = UpdateContainerBuilder::new()
+//!     .memory(512 * 1024 * 1024)
+//!     .memory_swap(1024 * 1024 * 1024)
+//!     .cpus(1.5)
+//!     .restart_policy(RestartPolicy {
+//!         name: Some(RestartPolicyNameEnum::ON_FAILURE),
+//!         maximum_retry_count: Some(3),
+//!     })
+//!     .build()
+//!     .unwrap();
+//!
+//! async move {
+//!     let response = docker.update_container("my-container", options).await.unwrap();
+//!     for warning in response.warnings.unwrap_or_default() {
+//!         println!("{warning}");
+//!     }
+//! };
+//! ```
+
+use super::UpdateContainerOptions;
+use crate::errors::Error;
+use crate::models::RestartPolicy;
+
+/// Builder for [Update Container Options](UpdateContainerOptions).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateContainerBuilder {
+    options: UpdateContainerOptions<String>,
+}
+
+impl UpdateContainerBuilder {
+    /// Start with an update that changes nothing.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Memory limit in bytes.
+    pub fn memory(mut self, bytes: i64) -> Self {
+        self.options.memory = Some(bytes);
+        self
+    }
+
+    /// Memory soft limit in bytes (Linux only).
+    pub fn memory_reservation(mut self, bytes: i64) -> Self {
+        self.options.memory_reservation = Some(bytes);
+        self
+    }
+
+    /// Total memory limit, memory and swap, in bytes, or `-1` for unlimited swap (Linux only).
+    pub fn memory_swap(mut self, bytes: i64) -> Self {
+        self.options.memory_swap = Some(bytes);
+        self
+    }
+
+    /// Number of CPUs, as with the `--cpus` flag of `docker update`.
+    pub fn cpus(mut self, cpus: f64) -> Self {
+        self.options.nano_cpus = Some((cpus * 1e9).round() as i64);
+        self
+    }
+
+    /// Relative CPU weight versus other containers.
+    pub fn cpu_shares(mut self, shares: isize) -> Self {
+        self.options.cpu_shares = Some(shares);
+        self
+    }
+
+    /// CPU period in microseconds, to limit the CPU quota against (Linux only).
+    pub fn cpu_period(mut self, microseconds: i64) -> Self {
+        self.options.cpu_period = Some(microseconds);
+        self
+    }
+
+    /// Microseconds of CPU time the container can get in a CPU period (Linux only).
+    pub fn cpu_quota(mut self, microseconds: i64) -> Self {
+        self.options.cpu_quota = Some(microseconds);
+        self
+    }
+
+    /// CPUs in which to allow execution, e.g. `0-3` or `0,1` (Linux only).
+    pub fn cpuset_cpus(mut self, cpus: &str) -> Self {
+        self.options.cpuset_cpus = Some(String::from(cpus));
+        self
+    }
+
+    /// Limit of the number of processes, or `-1` for unlimited (Linux only).
+    pub fn pids_limit(mut self, limit: i64) -> Self {
+        self.options.pids_limit = Some(limit);
+        self
+    }
+
+    /// Number of usable CPUs (Windows only).
+    pub fn cpu_count(mut self, count: i64) -> Self {
+        self.options.cpu_count = Some(count);
+        self
+    }
+
+    /// Usable percentage of the available CPUs (Windows only).
+    pub fn cpu_percent(mut self, percent: i64) -> Self {
+        self.options.cpu_percent = Some(percent);
+        self
+    }
+
+    /// Maximum IOps of the system drive (Windows only).
+    pub fn io_maximum_iops(mut self, iops: i64) -> Self {
+        self.options.io_maximum_iops = Some(iops);
+        self
+    }
+
+    /// Maximum bytes per second of the system drive (Windows only).
+    pub fn io_maximum_bandwidth(mut self, bytes_per_second: i64) -> Self {
+        self.options.io_maximum_bandwidth = Some(bytes_per_second);
+        self
+    }
+
+    /// The behavior to apply when the container exits.
+    pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.options.restart_policy = Some(restart_policy);
+        self
+    }
+
+    /// Validate the update and return the options for the [Update Container
+    /// API](crate::Docker::update_container()).
+    pub fn build(self) -> Result<UpdateContainerOptions<String>, Error> {
+        validate(&self.options)
+            .map_err(|message| Error::InvalidContainerUpdateError { message })?;
+        Ok(self.options)
+    }
+}
+
+/// Names of the fields that are set, among the given fields.
+fn set_fields(fields: &[(&'static str, bool)]) -> Vec<&'static str> {
+    fields
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+fn validate(options: &UpdateContainerOptions<String>) -> Result<(), String> {
+    let windows = set_fields(&[
+        ("CpuCount", options.cpu_count.is_some()),
+        ("CpuPercent", options.cpu_percent.is_some()),
+        ("IOMaximumIOps", options.io_maximum_iops.is_some()),
+        ("IOMaximumBandwidth", options.io_maximum_bandwidth.is_some()),
+    ]);
+    let linux = set_fields(&[
+        ("CpuPeriod", options.cpu_period.is_some()),
+        ("CpuQuota", options.cpu_quota.is_some()),
+        ("CpuRealtimePeriod", options.cpu_realtime_period.is_some()),
+        ("CpuRealtimeRuntime", options.cpu_realtime_runtime.is_some()),
+        ("CpusetCpus", options.cpuset_cpus.is_some()),
+        ("CpusetMems", options.cpuset_mems.is_some()),
+        ("MemoryReservation", options.memory_reservation.is_some()),
+        ("MemorySwap", options.memory_swap.is_some()),
+        ("MemorySwappiness", options.memory_swappiness.is_some()),
+        ("KernelMemoryTCP", options.kernel_memory_tcp.is_some()),
+        ("OomKillDisable", options.oom_kill_disable.is_some()),
+        ("PidsLimit", options.pids_limit.is_some()),
+    ]);
+    if !windows.is_empty() && !linux.is_empty() {
+        return Err(format!(
+            "Windows resources {} cannot be combined with Linux resources {}",
+            windows.join(", "),
+            linux.join(", ")
+        ));
+    }
+
+    if options.nano_cpus.is_some() && (options.cpu_period.is_some() || options.cpu_quota.is_some())
+    {
+        return Err(String::from(
+            "CPUs cannot be combined with a CPU period or quota",
+        ));
+    }
+    if options.nano_cpus.is_some_and(|nano_cpus| nano_cpus <= 0) {
+        return Err(String::from("CPUs must be greater than zero"));
+    }
+    if options
+        .cpu_percent
+        .is_some_and(|percent| !(1..=100).contains(&percent))
+    {
+        return Err(String::from("CPU percent must be between 1 and 100"));
+    }
+
+    if let Some(memory) = options.memory.filter(|memory| *memory > 0) {
+        if options
+            .memory_swap
+            .is_some_and(|swap| swap != -1 && swap < memory)
+        {
+            return Err(String::from(
+                "memory swap must be greater than or equal to the memory limit",
+            ));
+        }
+        if options
+            .memory_reservation
+            .is_some_and(|reservation| reservation > memory)
+        {
+            return Err(String::from(
+                "memory reservation must be less than or equal to the memory limit",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UpdateContainerBuilder;
+    use crate::container::UpdateContainerOptions;
+    use crate::errors::Error;
+    use crate::models::{RestartPolicy, RestartPolicyNameEnum};
+
+    #[test]
+    fn test_update_container_builder() {
+        let options = UpdateContainerBuilder::new()
+            .memory(314572800)
+            .memory_swap(-1)
+            .cpus(0.5)
+            .restart_policy(RestartPolicy {
+                name: Some(RestartPolicyNameEnum::ALWAYS),
+                maximum_retry_count: None,
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            options,
+            UpdateContainerOptions {
+                memory: Some(314572800),
+                memory_swap: Some(-1),
+                nano_cpus: Some(500000000),
+                restart_policy: Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::ALWAYS),
+                    maximum_retry_count: None,
+                }),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            serde_json::json!({
+                "Memory": 314572800,
+                "MemorySwap": -1,
+                "NanoCpus": 500000000,
+                "RestartPolicy": { "Name": "always" }
+            })
+        );
+
+        let invalid = [
+            UpdateContainerBuilder::new().cpu_count(2).pids_limit(100),
+            UpdateContainerBuilder::new().cpus(1.0).cpu_quota(50000),
+            UpdateContainerBuilder::new().cpu_percent(150),
+            UpdateContainerBuilder::new()
+                .memory(314572800)
+                .memory_swap(1024),
+            UpdateContainerBuilder::new()
+                .memory(1024)
+                .memory_reservation(2048),
+        ];
+        for builder in invalid {
+            assert!(matches!(
+                builder.build(),
+                Err(Error::InvalidContainerUpdateError { .. })
+            ));
+        }
+
+        assert!(UpdateContainerBuilder::new()
+            .cpu_count(2)
+            .io_maximum_iops(1000)
+            .memory(314572800)
+            .build()
+            .is_ok());
+    }
+}
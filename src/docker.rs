@@ -1,25 +1,40 @@
-#[cfg(feature = "ssl_providerless")]
+#[cfg(any(
+    feature = "ssl_providerless",
+    feature = "native-tls",
+    all(feature = "pipe", unix)
+))]
 use std::fs;
 use std::future::Future;
-#[cfg(feature = "ssl_providerless")]
+#[cfg(any(feature = "ssl_providerless", all(feature = "pipe", unix)))]
 use std::io;
-#[cfg(any(feature = "pipe", feature = "ssl_providerless"))]
+#[cfg(any(
+    all(feature = "pipe", unix),
+    feature = "ssl_providerless",
+    feature = "native-tls"
+))]
 use std::path::Path;
-#[cfg(feature = "ssl_providerless")]
+#[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use std::{cmp, env, fmt};
 
 use futures_core::Stream;
 use futures_util::future::FutureExt;
+#[cfg(any(
+    feature = "http",
+    feature = "pipe",
+    feature = "container",
+    feature = "system",
+    test
+))]
 use futures_util::future::TryFutureExt;
 use futures_util::stream::TryStreamExt;
 use futures_util::StreamExt;
-use http::header::CONTENT_TYPE;
+use http::header::{HeaderMap, CONNECTION, CONTENT_TYPE, UPGRADE};
 use http::request::Builder;
 use http_body_util::{BodyExt, Full, StreamBody};
 use hyper::body::{Frame, Incoming};
@@ -27,31 +42,51 @@ use hyper::{self, body::Bytes, Method, Request, Response, StatusCode};
 #[cfg(feature = "ssl_providerless")]
 use hyper_rustls::HttpsConnector;
 #[cfg(any(feature = "http", test))]
-use hyper_util::{
-    client::legacy::{connect::HttpConnector, Client},
-    rt::TokioExecutor,
-};
+use hyper_util::client::legacy::connect::HttpConnector;
+#[cfg(any(feature = "http", feature = "pipe", test))]
+use hyper_util::{client::legacy::Client, rt::TokioExecutor};
 #[cfg(all(feature = "pipe", unix))]
 use hyperlocal::UnixConnector;
+#[cfg(any(
+    feature = "container",
+    feature = "network",
+    feature = "swarm",
+    feature = "volume"
+))]
+use log::warn;
 use log::{debug, trace};
 #[cfg(feature = "ssl_providerless")]
 use rustls::{crypto::CryptoProvider, sign::CertifiedKey};
 #[cfg(feature = "ssl_providerless")]
 use rustls_pki_types::{CertificateDer, PrivateKeyDer};
 use serde_derive::{Deserialize, Serialize};
+#[cfg(all(feature = "pipe", unix))]
+use std::os::fd::OwnedFd;
 use tokio::io::{split, AsyncRead, AsyncWrite};
+#[cfg(feature = "container")]
 use tokio_util::codec::FramedRead;
+use tokio_util::sync::CancellationToken;
 
+use crate::compat::ServerFlavor;
+#[cfg(feature = "container")]
 use crate::container::LogOutput;
-use crate::errors::Error;
 use crate::errors::Error::*;
-use crate::read::{
-    AsyncUpgraded, IncomingStream, JsonLineDecoder, NewlineLogOutputDecoder, StreamReader,
-};
-use crate::uri::Uri;
+use crate::errors::{DaemonError, Error};
+#[cfg(all(feature = "pipe", unix))]
+use crate::fd::FdConnection;
+#[cfg(any(feature = "container", feature = "system"))]
+use crate::interceptor::Intercepted;
+use crate::interceptor::{CallContext, StreamEnd};
 #[cfg(all(feature = "pipe", windows))]
-use hyper_named_pipe::NamedPipeConnector;
+use crate::named_pipe::{NamedPipeConnector, NamedPipeOptions};
+use crate::read::{json_data_error, AsyncUpgraded, BodyStream, JsonMessageStream};
+#[cfg(any(feature = "container", feature = "system"))]
+use crate::read::{Cancellable, StreamReader};
+#[cfg(feature = "container")]
+use crate::read::{JsonArrayDecoder, NewlineLogOutputDecoder};
+use crate::uri::Uri;
 
+#[cfg(any(feature = "image", feature = "swarm"))]
 use crate::auth::{base64_url_encode, DockerCredentialsHeader};
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
@@ -77,7 +112,7 @@ pub const DEFAULT_DOCKER_HOST: &str = DEFAULT_SOCKET;
 pub const DEFAULT_DOCKER_HOST: &str = DEFAULT_NAMED_PIPE;
 
 /// Default timeout for all requests is 2 minutes.
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", feature = "pipe"))]
 const DEFAULT_TIMEOUT: u64 = 120;
 
 /// Default Client Version to communicate with the server.
@@ -92,7 +127,7 @@ pub(crate) enum ClientType {
     Unix,
     #[cfg(feature = "http")]
     Http,
-    #[cfg(feature = "ssl_providerless")]
+    #[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
     SSL,
     #[cfg(all(feature = "pipe", windows))]
     NamedPipe,
@@ -134,14 +169,26 @@ pub(crate) enum Transport {
     Http {
         client: Client<HttpConnector, BodyType>,
     },
+    #[cfg(feature = "http2")]
+    Http2 {
+        client: Box<H2cClient>,
+    },
     #[cfg(feature = "ssl_providerless")]
     Https {
         client: Client<HttpsConnector<HttpConnector>, BodyType>,
     },
+    #[cfg(all(feature = "native-tls", not(feature = "ssl_providerless")))]
+    NativeTls {
+        client: Client<hyper_tls::HttpsConnector<HttpConnector>, BodyType>,
+    },
     #[cfg(all(feature = "pipe", unix))]
     Unix {
         client: Client<UnixConnector, BodyType>,
     },
+    #[cfg(all(feature = "pipe", unix))]
+    Fd {
+        connection: FdConnection,
+    },
     #[cfg(all(feature = "pipe", windows))]
     NamedPipe {
         client: Client<NamedPipeConnector, BodyType>,
@@ -160,10 +207,16 @@ impl fmt::Debug for Transport {
         match self {
             #[cfg(feature = "http")]
             Transport::Http { .. } => write!(f, "HTTP"),
+            #[cfg(feature = "http2")]
+            Transport::Http2 { .. } => write!(f, "HTTP/2"),
             #[cfg(feature = "ssl_providerless")]
             Transport::Https { .. } => write!(f, "HTTPS(rustls)"),
+            #[cfg(all(feature = "native-tls", not(feature = "ssl_providerless")))]
+            Transport::NativeTls { .. } => write!(f, "HTTPS(native-tls)"),
             #[cfg(all(feature = "pipe", unix))]
             Transport::Unix { .. } => write!(f, "Unix"),
+            #[cfg(all(feature = "pipe", unix))]
+            Transport::Fd { .. } => write!(f, "Fd"),
             #[cfg(all(feature = "pipe", windows))]
             Transport::NamedPipe { .. } => write!(f, "NamedPipe"),
             #[cfg(test)]
@@ -173,6 +226,66 @@ impl fmt::Debug for Transport {
     }
 }
 
+/// Unsecured HTTP clients that multiplex requests over HTTP/2 (h2c), unless the daemon only
+/// speaks HTTP/1.1.
+#[cfg(feature = "http2")]
+pub(crate) struct H2cClient {
+    http2: Client<HttpConnector, BodyType>,
+    http1: Client<HttpConnector, BodyType>,
+    negotiated: OnceLock<bool>,
+}
+
+#[cfg(feature = "http2")]
+impl H2cClient {
+    fn new() -> Self {
+        let mut http2_builder = Client::builder(TokioExecutor::new());
+        http2_builder.http2_only(true);
+
+        let mut http1_builder = Client::builder(TokioExecutor::new());
+        http1_builder.pool_max_idle_per_host(0);
+
+        H2cClient {
+            http2: http2_builder.build(HttpConnector::new()),
+            http1: http1_builder.build(HttpConnector::new()),
+            negotiated: OnceLock::new(),
+        }
+    }
+
+    /// Whether the daemon speaks HTTP/2, found out with a ping the first time.
+    async fn negotiate(&self, uri: &hyper::Uri) -> Result<bool, Error> {
+        if let Some(http2) = self.negotiated.get() {
+            return Ok(*http2);
+        }
+
+        let authority = uri.authority().map(|a| a.as_str()).unwrap_or_default();
+        let probe = Request::get(format!("http://{authority}/_ping"))
+            .body(BodyType::Left(Full::new(Bytes::new())))?;
+        let http2 = match self.http2.request(probe).await {
+            Ok(_) => true,
+            Err(e) if e.is_connect() => return Err(e.into()),
+            Err(e) => {
+                debug!("HTTP/2 not supported by the daemon, falling back to HTTP/1.1: {e}");
+                false
+            }
+        };
+
+        Ok(*self.negotiated.get_or_init(|| http2))
+    }
+
+    async fn request(&self, req: Request<BodyType>) -> Result<Response<Incoming>, Error> {
+        // Hijacking the connection, e.g. to attach to a container, only exists in HTTP/1.1.
+        let client = if req.headers().contains_key(hyper::header::UPGRADE)
+            || !self.negotiate(req.uri()).await?
+        {
+            &self.http1
+        } else {
+            &self.http2
+        };
+
+        Ok(client.request(req).await?)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 /// Advisory version stub to use for communicating with the Server. The docker server will error if
 /// a higher client version is used than is compatible with the server. Beware also, that the
@@ -234,6 +347,13 @@ impl From<&(AtomicUsize, AtomicUsize)> for ClientVersion {
     }
 }
 
+#[cfg(any(
+    feature = "container",
+    feature = "network",
+    feature = "swarm",
+    feature = "system",
+    feature = "volume"
+))]
 pub(crate) fn serialize_as_json<T, S>(t: &T, s: S) -> Result<S::Ok, S::Error>
 where
     T: Serialize,
@@ -244,6 +364,31 @@ where
     )
 }
 
+/// Spawn a removal task on the current tokio runtime, if there is one, for the guards that remove
+/// their resources when dropped.
+#[cfg(any(
+    all(feature = "container", feature = "image"),
+    all(feature = "network", feature = "volume")
+))]
+pub(crate) fn spawn_cleanup<F>(kind: &'static str, name: String, fut: F)
+where
+    F: Future<Output = Result<(), Error>> + Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move {
+                match fut.await {
+                    Ok(()) => debug!("removed {kind} {name}"),
+                    Err(e) if e.kind() == Some(crate::errors::ErrorKind::NotFound) => (),
+                    Err(e) => warn!("failed to remove {kind} {name}: {e}"),
+                }
+            });
+        }
+        Err(_) => warn!("no tokio runtime available to remove {kind} {name}"),
+    }
+}
+
+#[cfg(feature = "image")]
 pub(crate) fn serialize_join_newlines<S>(t: &[&str], s: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
@@ -251,7 +396,7 @@ where
     s.serialize_str(&t.join("\n"))
 }
 
-#[cfg(feature = "time")]
+#[cfg(all(feature = "time", feature = "container"))]
 pub fn deserialize_rfc3339<'de, D: serde::Deserializer<'de>>(
     d: D,
 ) -> Result<time::OffsetDateTime, D::Error> {
@@ -260,7 +405,7 @@ pub fn deserialize_rfc3339<'de, D: serde::Deserializer<'de>>(
         .map_err(|e| serde::de::Error::custom(format!("{:?}", e)))
 }
 
-#[cfg(feature = "time")]
+#[cfg(all(feature = "time", feature = "container"))]
 pub fn serialize_rfc3339<S: serde::Serializer>(
     date: &time::OffsetDateTime,
     s: S,
@@ -272,7 +417,7 @@ pub fn serialize_rfc3339<S: serde::Serializer>(
     )
 }
 
-#[cfg(feature = "time")]
+#[cfg(all(feature = "time", feature = "system"))]
 pub(crate) fn serialize_as_timestamp<S>(
     opt: &Option<crate::models::BollardDate>,
     s: S,
@@ -290,7 +435,7 @@ where
     }
 }
 
-#[cfg(all(feature = "chrono", not(feature = "time")))]
+#[cfg(all(feature = "chrono", not(feature = "time"), feature = "system"))]
 pub(crate) fn serialize_as_timestamp<S>(
     opt: &Option<crate::models::BollardDate>,
     s: S,
@@ -322,6 +467,14 @@ pub struct Docker {
     pub(crate) client_addr: String,
     pub(crate) client_timeout: u64,
     pub(crate) version: Arc<(AtomicUsize, AtomicUsize)>,
+    pub(crate) version_pinned: bool,
+    pub(crate) server_flavor: Arc<OnceLock<ServerFlavor>>,
+    pub(crate) cancellation: Option<CancellationToken>,
+    pub(crate) skip_undecodable: bool,
+    pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) interceptor: Option<crate::interceptor::Interceptor>,
+    #[cfg(feature = "exec")]
+    pub(crate) exec_audit_hook: Option<crate::exec::ExecAuditHook>,
 }
 
 impl Clone for Docker {
@@ -332,6 +485,43 @@ impl Clone for Docker {
             client_addr: self.client_addr.clone(),
             client_timeout: self.client_timeout,
             version: self.version.clone(),
+            version_pinned: self.version_pinned,
+            server_flavor: self.server_flavor.clone(),
+            cancellation: self.cancellation.clone(),
+            skip_undecodable: self.skip_undecodable,
+            idle_timeout: self.idle_timeout,
+            interceptor: self.interceptor.clone(),
+            #[cfg(feature = "exec")]
+            exec_audit_hook: self.exec_audit_hook.clone(),
+        }
+    }
+}
+
+impl Docker {
+    fn new(
+        transport: Transport,
+        client_type: ClientType,
+        client_addr: String,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Docker {
+        Docker {
+            transport: Arc::new(transport),
+            client_type,
+            client_addr,
+            client_timeout: timeout,
+            version: Arc::new((
+                AtomicUsize::new(client_version.major_version),
+                AtomicUsize::new(client_version.minor_version),
+            )),
+            version_pinned: false,
+            server_flavor: Arc::new(OnceLock::new()),
+            cancellation: None,
+            skip_undecodable: false,
+            idle_timeout: None,
+            interceptor: None,
+            #[cfg(feature = "exec")]
+            exec_audit_hook: None,
         }
     }
 }
@@ -349,20 +539,21 @@ struct DockerClientCertResolver {
     ssl_cert: PathBuf,
 }
 
-#[cfg(feature = "ssl_providerless")]
-impl DockerClientCertResolver {
-    /// The default directory in which to look for our Docker certificate
-    /// files.
-    pub fn default_cert_path() -> Result<PathBuf, Error> {
-        let from_env = env::var("DOCKER_CERT_PATH").or_else(|_| env::var("DOCKER_CONFIG"));
-        if let Ok(ref path) = from_env {
-            Ok(Path::new(path).to_owned())
-        } else {
-            let home = home::home_dir().ok_or_else(|| NoHomePathError)?;
-            Ok(home.join(".docker"))
-        }
+/// The default directory in which to look for our Docker certificate
+/// files.
+#[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
+fn default_cert_path() -> Result<PathBuf, Error> {
+    let from_env = env::var("DOCKER_CERT_PATH").or_else(|_| env::var("DOCKER_CONFIG"));
+    if let Ok(ref path) = from_env {
+        Ok(Path::new(path).to_owned())
+    } else {
+        let home = home::home_dir().ok_or_else(|| NoHomePathError)?;
+        Ok(home.join(".docker"))
     }
+}
 
+#[cfg(feature = "ssl_providerless")]
+impl DockerClientCertResolver {
     fn open_buffered(path: &Path) -> Result<io::BufReader<fs::File>, Error> {
         Ok(io::BufReader::new(fs::File::open(path)?))
     }
@@ -408,6 +599,29 @@ impl DockerClientCertResolver {
     }
 }
 
+/// Parse the private key and the certificates of a client, given as PEM.
+#[cfg(feature = "ssl_providerless")]
+pub(crate) fn parse_pem_key_material(
+    ssl_key: &[u8],
+    ssl_cert: &[u8],
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), Error> {
+    let certs = rustls_pemfile::certs(&mut &ssl_cert[..])
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CertBytesParseError {
+            what: "certificate",
+            message: e.to_string(),
+        })?;
+    let key = rustls_pemfile::private_key(&mut &ssl_key[..])
+        .map_err(|e| e.to_string())
+        .and_then(|key| key.ok_or_else(|| String::from("no private key found")))
+        .map_err(|message| CertBytesParseError {
+            what: "key",
+            message,
+        })?;
+
+    Ok((certs, key))
+}
+
 #[cfg(feature = "ssl_providerless")]
 impl rustls::client::ResolvesClientCert for DockerClientCertResolver {
     fn resolve(&self, _: &[&[u8]], _: &[rustls::SignatureScheme]) -> Option<Arc<CertifiedKey>> {
@@ -451,7 +665,7 @@ impl Docker {
     /// or if you are using the `ssl_providerless` feature without installing the custom cryptographic
     /// provider before with [`rustls::crypto::CryptoProvider::install_default()`]
     pub fn connect_with_ssl_defaults() -> Result<Docker, Error> {
-        let cert_path = DockerClientCertResolver::default_cert_path()?;
+        let cert_path = default_cert_path()?;
         Docker::connect_with_ssl(
             if let Ok(ref host) = env::var("DOCKER_HOST") {
                 host
@@ -510,103 +724,81 @@ impl Docker {
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        // This ensures that using docker-machine-esque addresses work with Hyper.
-        let client_addr = addr.replacen("tcp://", "", 1).replacen("https://", "", 1);
-
-        let mut root_store = rustls::RootCertStore::empty();
-
-        #[cfg(not(any(feature = "test_ssl", feature = "webpki")))]
-        let native_certs = rustls_native_certs::load_native_certs();
-
-        #[cfg(not(any(feature = "test_ssl", feature = "webpki")))]
-        if native_certs.errors.is_empty() {
-            for cert in native_certs.certs {
-                root_store
-                    .add(cert)
-                    .map_err(|err| NoNativeCertsError { err })?
-            }
-        } else {
-            return Err(LoadNativeCertsErrors {
-                errors: native_certs.errors,
-            });
-        }
-        #[cfg(any(feature = "test_ssl", feature = "webpki"))]
-        root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-
-        let mut ca_pem = io::Cursor::new(fs::read(ssl_ca).map_err(|_| CertPathError {
+        let ca_pem = fs::read(ssl_ca).map_err(|_| CertPathError {
             path: ssl_ca.to_owned(),
-        })?);
-
-        root_store.add_parsable_certificates(
-            rustls_pemfile::certs(&mut ca_pem).collect::<Result<Vec<_>, _>>()?,
-        );
+        })?;
 
         let config = rustls::ClientConfig::builder()
-            .with_root_certificates(root_store)
+            .with_root_certificates(Docker::ssl_root_store(&ca_pem)?)
             .with_client_cert_resolver(Arc::new(DockerClientCertResolver {
                 ssl_key: ssl_key.to_owned(),
                 ssl_cert: ssl_cert.to_owned(),
             }));
 
-        let mut http_connector = HttpConnector::new();
-        http_connector.enforce_http(false);
-
-        let https_connector: HttpsConnector<HttpConnector> =
-            HttpsConnector::from((http_connector, config));
-
-        let mut client_builder = Client::builder(TokioExecutor::new());
-        client_builder.pool_max_idle_per_host(0);
-
-        let client = client_builder.build(https_connector);
-        let transport = Transport::Https { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::SSL,
-            client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+        Docker::connect_with_ssl_config(addr, config, timeout, client_version)
     }
-}
 
-#[cfg(feature = "http")]
-/// A Docker implementation typed to connect to an unsecure Http connection.
-impl Docker {
-    /// Connect using unsecured HTTP using defaults that are signalled by environment variables.
+    /// Connect using secure HTTPS, with the private key and certificates given in memory as PEM,
+    /// e.g. when they come from a secret store or environment variables rather than from disk.
     ///
-    /// # Defaults
+    /// # Arguments
     ///
-    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable, and defaults
-    ///    to `localhost:2375`.
-    ///  - The number of threads used for the HTTP connection pool defaults to 1.
-    ///  - The request timeout defaults to 2 minutes.
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key PEM.
+    ///  - `ssl_cert`: the server certificate PEM.
+    ///  - `ssl_ca`: the certificate chain PEM.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use bollard::Docker;
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
     ///
-    /// use futures_util::future::TryFutureExt;
+    /// use std::env;
     ///
-    /// let connection = Docker::connect_with_http_defaults().unwrap();
-    /// connection.ping()
-    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let connection = Docker::connect_with_ssl_bytes(
+    ///     "tcp://localhost:2375/",
+    ///     env::var("DOCKER_TLS_KEY").unwrap().as_bytes(),
+    ///     env::var("DOCKER_TLS_CERT").unwrap().as_bytes(),
+    ///     env::var("DOCKER_TLS_CA").unwrap().as_bytes(),
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
     /// ```
-    pub fn connect_with_http_defaults() -> Result<Docker, Error> {
-        let host = env::var("DOCKER_HOST").unwrap_or_else(|_| DEFAULT_TCP_ADDRESS.to_string());
-        Docker::connect_with_http(&host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if neither `ssl` nor `aws-lc-rs` features are activated,
+    /// or if you are using the `ssl_providerless` feature without installing the custom cryptographic
+    /// provider before with [`rustls::crypto::CryptoProvider::install_default()`]
+    pub fn connect_with_ssl_bytes(
+        addr: &str,
+        ssl_key: &[u8],
+        ssl_cert: &[u8],
+        ssl_ca: &[u8],
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let (certs, key) = parse_pem_key_material(ssl_key, ssl_cert)?;
+
+        let config = rustls::ClientConfig::builder()
+            .with_root_certificates(Docker::ssl_root_store(ssl_ca)?)
+            .with_client_auth_cert(certs, key)
+            .map_err(|e| CertBytesParseError {
+                what: "key",
+                message: e.to_string(),
+            })?;
+
+        Docker::connect_with_ssl_config(addr, config, timeout, client_version)
     }
 
-    /// Connect using unsecured HTTP.
+    /// Connect using secure HTTPS, with a `rustls` client configuration built by the caller, for
+    /// full control over the trusted roots, the client authentication and the protocol versions.
     ///
     /// # Arguments
     ///
-    ///  - `addr`: connection url including scheme and port.
+    ///  - `addr`: the connection url.
+    ///  - `config`: the `rustls` client configuration.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
     ///  - `client_version`: the client version to communicate with the server.
     ///
@@ -615,119 +807,548 @@ impl Docker {
     /// ```rust,no_run
     /// use bollard::{API_DEFAULT_VERSION, Docker};
     ///
-    /// use futures_util::future::TryFutureExt;
+    /// # let root_store = rustls::RootCertStore::empty();
+    /// let config = rustls::ClientConfig::builder()
+    ///     .with_root_certificates(root_store)
+    ///     .with_no_client_auth();
     ///
-    /// let connection = Docker::connect_with_http(
-    ///                    "http://my-custom-docker-server:2735", 4, API_DEFAULT_VERSION)
-    ///                    .unwrap();
-    /// connection.ping()
-    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let connection = Docker::connect_with_ssl_config(
+    ///     "tcp://localhost:2375/",
+    ///     config,
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
     /// ```
-    pub fn connect_with_http(
+    pub fn connect_with_ssl_config(
         addr: &str,
+        config: rustls::ClientConfig,
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
         // This ensures that using docker-machine-esque addresses work with Hyper.
-        let client_addr = addr.replacen("tcp://", "", 1).replacen("http://", "", 1);
+        let client_addr = addr.replacen("tcp://", "", 1).replacen("https://", "", 1);
 
-        let http_connector = HttpConnector::new();
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        let https_connector: HttpsConnector<HttpConnector> =
+            HttpsConnector::from((http_connector, config));
 
         let mut client_builder = Client::builder(TokioExecutor::new());
         client_builder.pool_max_idle_per_host(0);
 
-        let client = client_builder.build(http_connector);
-        let transport = Transport::Http { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::Http,
+        let client = client_builder.build(https_connector);
+        let transport = Transport::Https { client };
+        Ok(Docker::new(
+            transport,
+            ClientType::SSL,
             client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+            timeout,
+            client_version,
+        ))
     }
-}
 
-/// A Docker implementation typed to custom connector.
-impl Docker {
-    /// Connect using custom transport implementation.
-    /// It has default implementation for `Fn(Request) -> Future<Output = Result<Response<hyper::body::Incoming>, Error>> + Send + Sync`
+    /// Connect using secure HTTPS, verifying the daemon with a custom verifier rather than a
+    /// certificate chain, e.g. a [Pinned Certificate Verifier](crate::tls::PinnedCertVerifier)
+    /// for a daemon with a self-signed certificate.
     ///
     /// # Arguments
     ///
-    ///  - `transport`: transport.
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key path.
+    ///  - `ssl_cert`: the server certificate path.
+    ///  - `verifier`: the verifier of the daemon certificate.
     ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
     ///  - `client_version`: the client version to communicate with the server.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use bollard::{API_DEFAULT_VERSION, Docker, BollardRequest};
-    /// use futures_util::future::TryFutureExt;
-    /// use futures_util::FutureExt;
-    ///
-    /// let http_connector = hyper_util::client::legacy::connect::HttpConnector::new();
-    ///
-    /// let mut client_builder = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new());
-    /// client_builder.pool_max_idle_per_host(0);
+    /// use bollard::tls::PinnedCertVerifier;
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
     ///
-    /// let client = std::sync::Arc::new(client_builder.build(http_connector));
+    /// use std::path::Path;
+    /// use std::sync::Arc;
     ///
-    /// let connection = Docker::connect_with_custom_transport(
-    ///     move |req: BollardRequest| {
-    ///         let client = std::sync::Arc::clone(&client);
-    ///         Box::pin(async move {
-    ///             let (p, b) = req.into_parts();
-    ///             // let _prev = p.headers.insert("host", host);
-    ///             // let mut uri = p.uri.into_parts();
-    ///             //uri.path_and_query = uri.path_and_query.map(|paq|
-    ///             //   uri::PathAndQuery::try_from("/docker".to_owned() + paq.as_str())
-    ///             // ).transpose().map_err(bollard::errors::Error::from)?;
-    ///             // p.uri = uri.try_into().map_err(bollard::errors::Error::from)?;
-    ///             let req = BollardRequest::from_parts(p, b);
-    ///             client.request(req).await.map_err(bollard::errors::Error::from)
-    ///         })
-    ///     },
-    ///     Some("http://my-custom-docker-server:2735"),
-    ///     4,
-    ///     bollard::API_DEFAULT_VERSION,
-    /// ).unwrap();
+    /// let verifier = PinnedCertVerifier::new([
+    ///     "10:B0:73:0C:D8:2C:50:78:20:07:6E:81:F6:6A:DA:FE:53:15:E1:7A:A9:C1:E0:6B:52:42:A4:8C:49:AC:92:E9",
+    /// ]).unwrap();
     ///
-    /// connection.ping()
-    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// let connection = Docker::connect_with_ssl_verifier(
+    ///     "tcp://localhost:2376/",
+    ///     Path::new("/certs/key.pem"),
+    ///     Path::new("/certs/cert.pem"),
+    ///     Arc::new(verifier),
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
     /// ```
-    pub fn connect_with_custom_transport<S: Into<String>>(
-        transport: impl CustomTransport + 'static,
-        client_addr: Option<S>,
+    pub fn connect_with_ssl_verifier(
+        addr: &str,
+        ssl_key: &Path,
+        ssl_cert: &Path,
+        verifier: Arc<dyn rustls::client::danger::ServerCertVerifier>,
         timeout: u64,
         client_version: &ClientVersion,
     ) -> Result<Docker, Error> {
-        let client_addr = client_addr.map(Into::into).unwrap_or_default();
-        let (scheme, client_addr) = client_addr
-            .split_once("://")
-            .unwrap_or(("", client_addr.as_str()));
-        let client_addr = client_addr.to_owned();
-        let scheme = scheme.to_owned();
-        let transport = Transport::Custom {
-            transport: Box::new(transport),
-        };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::Custom { scheme },
-            client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
+        let config = rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(verifier)
+            .with_client_cert_resolver(Arc::new(DockerClientCertResolver {
+                ssl_key: ssl_key.to_owned(),
+                ssl_cert: ssl_cert.to_owned(),
+            }));
+
+        Docker::connect_with_ssl_config(addr, config, timeout, client_version)
+    }
+
+    /// Connect using secure HTTPS, with key material that a [TLS
+    /// Reloader](crate::tls::TlsReloader) can swap for the connections made afterwards, e.g. when
+    /// certificates are rotated.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: the connection url.
+    ///  - `reloader`: the [TLS Reloader](crate::tls::TlsReloader) holding the key material.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::tls::TlsReloader;
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// # let (key, cert, ca) = (b"", b"", b"");
+    /// let reloader = TlsReloader::from_pem(key, cert, ca).unwrap();
+    /// let connection = Docker::connect_with_ssl_reloader(
+    ///     "tcp://localhost:2376/",
+    ///     &reloader,
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
+    ///
+    /// # let (key, cert, ca) = (b"", b"", b"");
+    /// reloader.update_pem(key, cert, ca).unwrap();
+    /// ```
+    pub fn connect_with_ssl_reloader(
+        addr: &str,
+        reloader: &crate::tls::TlsReloader,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let config = rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(reloader.clone()))
+            .with_client_cert_resolver(Arc::new(reloader.clone()));
+
+        Docker::connect_with_ssl_config(addr, config, timeout, client_version)
+    }
+
+    /// The native, or webpki, roots together with the certificate chain PEM.
+    pub(crate) fn ssl_root_store(ca_pem: &[u8]) -> Result<rustls::RootCertStore, Error> {
+        let mut root_store = rustls::RootCertStore::empty();
+
+        #[cfg(not(any(feature = "test_ssl", feature = "webpki")))]
+        let native_certs = rustls_native_certs::load_native_certs();
+
+        #[cfg(not(any(feature = "test_ssl", feature = "webpki")))]
+        if native_certs.errors.is_empty() {
+            for cert in native_certs.certs {
+                root_store
+                    .add(cert)
+                    .map_err(|err| NoNativeCertsError { err })?
+            }
+        } else {
+            return Err(LoadNativeCertsErrors {
+                errors: native_certs.errors,
+            });
+        }
+        #[cfg(any(feature = "test_ssl", feature = "webpki"))]
+        root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+        root_store.add_parsable_certificates(
+            rustls_pemfile::certs(&mut &ca_pem[..]).collect::<Result<Vec<_>, _>>()?,
+        );
+
+        Ok(root_store)
+    }
+}
+
+/// The PEM blocks of a certificate chain, one per certificate.
+#[cfg(all(feature = "native-tls", not(feature = "ssl_providerless")))]
+fn pem_certificates(pem: &[u8]) -> Vec<&[u8]> {
+    const END: &[u8] = b"-----END CERTIFICATE-----";
+
+    let mut certificates = vec![];
+    let mut rest = pem;
+    while let Some(end) = rest.windows(END.len()).position(|w| w == END) {
+        certificates.push(&rest[..end + END.len()]);
+        rest = &rest[end + END.len()..];
+    }
+    certificates
+}
+
+/// A Docker implementation typed to connect to a secure HTTPS connection using the platform TLS
+/// library, through `native-tls`, when the `rustls` features are not enabled.
+#[cfg(all(feature = "native-tls", not(feature = "ssl_providerless")))]
+impl Docker {
+    /// Connect using secure HTTPS using defaults that are signalled by environment variables.
+    ///
+    /// # Defaults
+    ///
+    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable.
+    ///  - The certificate directory is sourced from the `DOCKER_CERT_PATH` environment variable.
+    ///  - Certificates are named `key.pem`, `cert.pem` and `ca.pem` to indicate the private key,
+    ///    the server certificate and the certificate chain respectively.
+    ///  - The request timeout defaults to 2 minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_ssl_defaults().unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_ssl_defaults() -> Result<Docker, Error> {
+        let cert_path = default_cert_path()?;
+        Docker::connect_with_ssl(
+            if let Ok(ref host) = env::var("DOCKER_HOST") {
+                host
+            } else {
+                DEFAULT_TCP_ADDRESS
+            },
+            &cert_path.join("key.pem"),
+            &cert_path.join("cert.pem"),
+            &cert_path.join("ca.pem"),
+            DEFAULT_TIMEOUT,
+            API_DEFAULT_VERSION,
+        )
+    }
+
+    /// Connect using secure HTTPS.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key path.
+    ///  - `ssl_cert`: the server certificate path.
+    ///  - `ssl_ca`: the certificate chain path.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use std::path::Path;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_ssl(
+    ///     "tcp://localhost:2375/",
+    ///     Path::new("/certs/key.pem"),
+    ///     Path::new("/certs/cert.pem"),
+    ///     Path::new("/certs/ca.pem"),
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_ssl(
+        addr: &str,
+        ssl_key: &Path,
+        ssl_cert: &Path,
+        ssl_ca: &Path,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let read = |path: &Path| {
+            fs::read(path).map_err(|_| CertPathError {
+                path: path.to_owned(),
+            })
+        };
+
+        Docker::connect_with_ssl_bytes(
+            addr,
+            &read(ssl_key)?,
+            &read(ssl_cert)?,
+            &read(ssl_ca)?,
+            timeout,
+            client_version,
+        )
+    }
+
+    /// Connect using secure HTTPS, with the private key and certificates given in memory as PEM,
+    /// e.g. when they come from a secret store or environment variables rather than from disk.
+    ///
+    /// The private key must be in PKCS #8 format on platforms other than those using OpenSSL.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: the connection url.
+    ///  - `ssl_key`: the private key PEM.
+    ///  - `ssl_cert`: the server certificate PEM.
+    ///  - `ssl_ca`: the certificate chain PEM.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use std::env;
+    ///
+    /// let connection = Docker::connect_with_ssl_bytes(
+    ///     "tcp://localhost:2375/",
+    ///     env::var("DOCKER_TLS_KEY").unwrap().as_bytes(),
+    ///     env::var("DOCKER_TLS_CERT").unwrap().as_bytes(),
+    ///     env::var("DOCKER_TLS_CA").unwrap().as_bytes(),
+    ///     120,
+    ///     API_DEFAULT_VERSION).unwrap();
+    /// ```
+    pub fn connect_with_ssl_bytes(
+        addr: &str,
+        ssl_key: &[u8],
+        ssl_cert: &[u8],
+        ssl_ca: &[u8],
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        // This ensures that using docker-machine-esque addresses work with Hyper.
+        let client_addr = addr.replacen("tcp://", "", 1).replacen("https://", "", 1);
+
+        let identity = native_tls::Identity::from_pkcs8(ssl_cert, ssl_key).map_err(|e| {
+            CertBytesParseError {
+                what: "key",
+                message: e.to_string(),
+            }
+        })?;
+        let mut tls = native_tls::TlsConnector::builder();
+        tls.identity(identity);
+        for cert in pem_certificates(ssl_ca) {
+            tls.add_root_certificate(native_tls::Certificate::from_pem(cert).map_err(|e| {
+                CertBytesParseError {
+                    what: "certificate chain",
+                    message: e.to_string(),
+                }
+            })?);
+        }
+
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        let https_connector = hyper_tls::HttpsConnector::from((
+            http_connector,
+            tokio_native_tls::TlsConnector::from(tls.build()?),
+        ));
+
+        let mut client_builder = Client::builder(TokioExecutor::new());
+        client_builder.pool_max_idle_per_host(0);
+
+        let client = client_builder.build(https_connector);
+        let transport = Transport::NativeTls { client };
+        Ok(Docker::new(
+            transport,
+            ClientType::SSL,
+            client_addr,
+            timeout,
+            client_version,
+        ))
+    }
+}
+
+#[cfg(feature = "http")]
+/// A Docker implementation typed to connect to an unsecure Http connection.
+impl Docker {
+    /// Connect using unsecured HTTP using defaults that are signalled by environment variables.
+    ///
+    /// # Defaults
+    ///
+    ///  - The connection url is sourced from the `DOCKER_HOST` environment variable, and defaults
+    ///    to `localhost:2375`.
+    ///  - The number of threads used for the HTTP connection pool defaults to 1.
+    ///  - The request timeout defaults to 2 minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_http_defaults().unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_http_defaults() -> Result<Docker, Error> {
+        let host = env::var("DOCKER_HOST").unwrap_or_else(|_| DEFAULT_TCP_ADDRESS.to_string());
+        Docker::connect_with_http(&host, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+    }
+
+    /// Connect using unsecured HTTP.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: connection url including scheme and port.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_http(
+    ///                    "http://my-custom-docker-server:2735", 4, API_DEFAULT_VERSION)
+    ///                    .unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_http(
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        // This ensures that using docker-machine-esque addresses work with Hyper.
+        let client_addr = addr.replacen("tcp://", "", 1).replacen("http://", "", 1);
+
+        let http_connector = HttpConnector::new();
+
+        let mut client_builder = Client::builder(TokioExecutor::new());
+        client_builder.pool_max_idle_per_host(0);
+
+        let client = client_builder.build(http_connector);
+        let transport = Transport::Http { client };
+        Ok(Docker::new(
+            transport,
+            ClientType::Http,
+            client_addr,
+            timeout,
+            client_version,
+        ))
+    }
+
+    /// Connect using unsecured HTTP, multiplexing concurrent requests over a single HTTP/2
+    /// cleartext (h2c) connection. The first request pings the daemon with HTTP/2, and falls back
+    /// to HTTP/1.1 for the lifetime of the client if the daemon does not speak it. Requests that
+    /// hijack the connection, e.g. to attach to a container, always use HTTP/1.1.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: connection url including scheme and port.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_http2(
+    ///                    "http://my-custom-docker-server:2735", 4, API_DEFAULT_VERSION)
+    ///                    .unwrap();
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    #[cfg(feature = "http2")]
+    pub fn connect_with_http2(
+        addr: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let client_addr = addr.replacen("tcp://", "", 1).replacen("http://", "", 1);
+
+        let transport = Transport::Http2 {
+            client: Box::new(H2cClient::new()),
         };
+        Ok(Docker::new(
+            transport,
+            ClientType::Http,
+            client_addr,
+            timeout,
+            client_version,
+        ))
+    }
+}
 
-        Ok(docker)
+/// A Docker implementation typed to custom connector.
+impl Docker {
+    /// Connect using custom transport implementation.
+    /// It has default implementation for `Fn(Request) -> Future<Output = Result<Response<hyper::body::Incoming>, Error>> + Send + Sync`
+    ///
+    /// # Arguments
+    ///
+    ///  - `transport`: transport.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker, BollardRequest};
+    /// use futures_util::future::TryFutureExt;
+    /// use futures_util::FutureExt;
+    ///
+    /// let http_connector = hyper_util::client::legacy::connect::HttpConnector::new();
+    ///
+    /// let mut client_builder = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new());
+    /// client_builder.pool_max_idle_per_host(0);
+    ///
+    /// let client = std::sync::Arc::new(client_builder.build(http_connector));
+    ///
+    /// let connection = Docker::connect_with_custom_transport(
+    ///     move |req: BollardRequest| {
+    ///         let client = std::sync::Arc::clone(&client);
+    ///         Box::pin(async move {
+    ///             let (p, b) = req.into_parts();
+    ///             // let _prev = p.headers.insert("host", host);
+    ///             // let mut uri = p.uri.into_parts();
+    ///             //uri.path_and_query = uri.path_and_query.map(|paq|
+    ///             //   uri::PathAndQuery::try_from("/docker".to_owned() + paq.as_str())
+    ///             // ).transpose().map_err(bollard::errors::Error::from)?;
+    ///             // p.uri = uri.try_into().map_err(bollard::errors::Error::from)?;
+    ///             let req = BollardRequest::from_parts(p, b);
+    ///             client.request(req).await.map_err(bollard::errors::Error::from)
+    ///         })
+    ///     },
+    ///     Some("http://my-custom-docker-server:2735"),
+    ///     4,
+    ///     bollard::API_DEFAULT_VERSION,
+    /// ).unwrap();
+    ///
+    /// connection.ping()
+    ///   .map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_custom_transport<S: Into<String>>(
+        transport: impl CustomTransport + 'static,
+        client_addr: Option<S>,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        let client_addr = client_addr.map(Into::into).unwrap_or_default();
+        let (scheme, client_addr) = client_addr
+            .split_once("://")
+            .unwrap_or(("", client_addr.as_str()));
+        let client_addr = client_addr.to_owned();
+        let scheme = scheme.to_owned();
+        let transport = Transport::Custom {
+            transport: Box::new(transport),
+        };
+        Ok(Docker::new(
+            transport,
+            ClientType::Custom { scheme },
+            client_addr,
+            timeout,
+            client_version,
+        ))
     }
 }
 
@@ -866,13 +1487,13 @@ impl Docker {
             }
             #[cfg(feature = "http")]
             h if h.starts_with("tcp://") || h.starts_with("http://") => {
-                #[cfg(feature = "ssl_providerless")]
+                #[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
                 if env::var("DOCKER_TLS_VERIFY").is_ok() {
                     return Docker::connect_with_ssl_defaults();
                 }
                 Docker::connect_with_http_defaults()
             }
-            #[cfg(feature = "ssl_providerless")]
+            #[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
             h if h.starts_with("https://") => Docker::connect_with_ssl_defaults(),
             _ => Err(UnsupportedURISchemeError {
                 uri: host.to_string(),
@@ -945,6 +1566,12 @@ impl Docker {
         if !Path::new(&client_addr).exists() {
             return Err(Error::SocketNotFoundError(client_addr));
         }
+        if let Some(diagnostic) = socket_permission_diagnostic(Path::new(&client_addr)) {
+            return Err(Error::SocketPermissionError {
+                path: client_addr,
+                diagnostic,
+            });
+        }
 
         let unix_connector = UnixConnector;
 
@@ -953,21 +1580,128 @@ impl Docker {
 
         let client = client_builder.build(unix_connector);
         let transport = Transport::Unix { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::Unix,
+        Ok(Docker::new(
+            transport,
+            ClientType::Unix,
             client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
+            timeout,
+            client_version,
+        ))
+    }
+
+    /// Connect through a socket inherited from the parent process, e.g. in a sandbox without
+    /// access to the socket file. See the [fd](crate::fd) module.
+    ///
+    /// # Arguments
+    ///
+    ///  - `fd`: a Unix stream socket, connected to the daemon.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every request.
+    ///  - `client_version`: the client version to communicate with the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use futures_util::future::TryFutureExt;
+    /// use std::os::fd::{FromRawFd, OwnedFd};
+    ///
+    /// // SAFETY: the parent process passed the socket as file descriptor 4.
+    /// let fd = unsafe { OwnedFd::from_raw_fd(4) };
+    /// let connection = Docker::connect_with_fd(fd, 120, API_DEFAULT_VERSION).unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_fd(
+        fd: OwnedFd,
+        timeout: u64,
+        client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        use std::os::fd::AsRawFd;
+
+        let client_addr = format!("fd://{}", fd.as_raw_fd());
+        let transport = Transport::Fd {
+            connection: FdConnection::new(fd)?,
         };
+        Ok(Docker::new(
+            transport,
+            ClientType::Unix,
+            client_addr,
+            timeout,
+            client_version,
+        ))
+    }
 
-        Ok(docker)
+    /// Connect through the first socket passed by systemd socket activation, with the
+    /// `LISTEN_FDS` and `LISTEN_PID` environment variables, and the default timeout.
+    ///
+    /// The client takes ownership of the socket and unsets these variables, so only the first
+    /// call succeeds. Clone the client to share the connection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::Docker;
+    ///
+    /// use futures_util::future::TryFutureExt;
+    ///
+    /// let connection = Docker::connect_with_listenfd().unwrap();
+    /// connection.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+    /// ```
+    pub fn connect_with_listenfd() -> Result<Docker, Error> {
+        let fd = crate::fd::take_listen_fd()?;
+        Docker::connect_with_fd(fd, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
     }
 }
 
+/// Explain why the current process may not read and write the socket, if it may not.
+#[cfg(all(feature = "pipe", unix))]
+fn socket_permission_diagnostic(path: &Path) -> Option<crate::errors::SocketDiagnostic> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `c_path` is a NUL terminated string that outlives the call.
+    let result = unsafe {
+        libc::faccessat(
+            libc::AT_FDCWD,
+            c_path.as_ptr(),
+            libc::R_OK | libc::W_OK,
+            libc::AT_EACCESS,
+        )
+    };
+    if result == 0 || io::Error::last_os_error().kind() != io::ErrorKind::PermissionDenied {
+        return None;
+    }
+
+    let metadata = fs::metadata(path).ok()?;
+    // SAFETY: `getgroups` with a size of zero only returns the number of groups.
+    let count = unsafe { libc::getgroups(0, std::ptr::null_mut()) };
+    let mut groups = vec![0; usize::try_from(count).unwrap_or(0)];
+    // SAFETY: `groups` has room for `count` groups, and outlives the call.
+    let count = unsafe { libc::getgroups(count, groups.as_mut_ptr()) };
+    groups.truncate(usize::try_from(count).unwrap_or(0));
+    // SAFETY: `getegid` and `geteuid` always succeed.
+    let (egid, euid) = unsafe { (libc::getegid(), libc::geteuid()) };
+    groups.push(egid);
+
+    Some(crate::errors::SocketDiagnostic {
+        owner_uid: metadata.uid(),
+        owner_gid: metadata.gid(),
+        owner_group: fs::read_to_string("/etc/group").ok().and_then(|groups| {
+            groups.lines().find_map(|line| {
+                let mut fields = line.split(':');
+                let name = fields.next()?;
+                let gid = fields.nth(1)?;
+                (gid.parse() == Ok(metadata.gid())).then(|| name.to_string())
+            })
+        }),
+        mode: metadata.mode() & 0o7777,
+        process_uid: euid,
+        in_owner_group: groups.contains(&metadata.gid()),
+    })
+}
+
 #[cfg(all(feature = "pipe", windows))]
 /// A Docker implementation typed to connect to a Windows Named Pipe, exclusive to the windows
 /// target.
@@ -977,7 +1711,9 @@ impl Docker {
     ///
     /// # Defaults
     ///
-    ///  - The socket location defaults to `//./pipe/docker_engine`.
+    ///  - The socket location is the first [discovered](crate::named_pipe::discover_named_pipe())
+    ///    of `//./pipe/docker_engine` and the `docker_engine_linux` and `docker_engine_windows`
+    ///    pipes of Docker Desktop, and defaults to `//./pipe/docker_engine`.
     ///  - The request timeout defaults to 2 minutes.
     ///
     /// # Examples
@@ -992,7 +1728,11 @@ impl Docker {
     ///
     /// ```
     pub fn connect_with_named_pipe_defaults() -> Result<Docker, Error> {
-        Docker::connect_with_named_pipe(DEFAULT_NAMED_PIPE, DEFAULT_TIMEOUT, API_DEFAULT_VERSION)
+        Docker::connect_with_named_pipe(
+            crate::named_pipe::discover_named_pipe().unwrap_or(DEFAULT_NAMED_PIPE),
+            DEFAULT_TIMEOUT,
+            API_DEFAULT_VERSION,
+        )
     }
 
     /// Connect using a Windows Named Pipe.
@@ -1019,10 +1759,52 @@ impl Docker {
         path: &str,
         timeout: u64,
         client_version: &ClientVersion,
+    ) -> Result<Docker, Error> {
+        Docker::connect_with_named_pipe_options(
+            path,
+            timeout,
+            client_version,
+            NamedPipeOptions::default(),
+        )
+    }
+
+    /// Connect using a Windows Named Pipe, with options for busy pipes and the impersonation
+    /// level.
+    ///
+    /// # Arguments
+    ///
+    ///  - `addr`: socket location.
+    ///  - `timeout`: the read/write timeout (seconds) to use for every hyper connection
+    ///  - `client_version`: the client version to communicate with the server.
+    ///  - `options`: the [Named Pipe Options](NamedPipeOptions) of the connections.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bollard::named_pipe::NamedPipeOptions;
+    /// use bollard::{API_DEFAULT_VERSION, Docker};
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let connection = Docker::connect_with_named_pipe_options(
+    ///     "//./pipe/docker_engine",
+    ///     120,
+    ///     API_DEFAULT_VERSION,
+    ///     NamedPipeOptions {
+    ///         busy_timeout: Some(Duration::from_secs(5)),
+    ///         ..Default::default()
+    ///     },
+    /// ).unwrap();
+    /// ```
+    pub fn connect_with_named_pipe_options(
+        path: &str,
+        timeout: u64,
+        client_version: &ClientVersion,
+        options: NamedPipeOptions,
     ) -> Result<Docker, Error> {
         let client_addr = path.replacen("npipe://", "", 1);
 
-        let named_pipe_connector = NamedPipeConnector;
+        let named_pipe_connector = NamedPipeConnector { options };
 
         let mut client_builder = Client::builder(TokioExecutor::new());
         client_builder.http1_title_case_headers(true);
@@ -1030,18 +1812,13 @@ impl Docker {
 
         let client = client_builder.build(named_pipe_connector);
         let transport = Transport::NamedPipe { client };
-        let docker = Docker {
-            transport: Arc::new(transport),
-            client_type: ClientType::NamedPipe,
+        Ok(Docker::new(
+            transport,
+            ClientType::NamedPipe,
             client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
-
-        Ok(docker)
+            timeout,
+            client_version,
+        ))
     }
 }
 
@@ -1086,46 +1863,256 @@ impl Docker {
 
         let (transport, client_type) = (Transport::Mock { client }, ClientType::Http);
 
-        let docker = Docker {
-            transport: Arc::new(transport),
+        Ok(Docker::new(
+            transport,
             client_type,
             client_addr,
-            client_timeout: timeout,
-            version: Arc::new((
-                AtomicUsize::new(client_version.major_version),
-                AtomicUsize::new(client_version.minor_version),
-            )),
-        };
+            timeout,
+            client_version,
+        ))
+    }
+}
 
-        Ok(docker)
+impl Docker {
+    /// Set the request timeout.
+    ///
+    /// This timeout is shared by all requests to the Docker Engine API.
+    ///
+    /// By default, 2 minutes.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.set_timeout(timeout);
+        self
+    }
+
+    /// Get the current timeout.
+    ///
+    /// This timeout is shared by all requests to the Docker Engine API.
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.client_timeout)
+    }
+
+    /// Set the request timeout.
+    ///
+    /// This timeout is shared by all requests to the Docker Engine API.
+    ///
+    /// By default, 2 minutes.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.client_timeout = timeout.as_secs();
+    }
+
+    /// Describe the transport this client uses to reach the Docker Engine API.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// let docker = Docker::connect_with_http_defaults().unwrap();
+    /// let info = docker.connection_info();
+    /// assert_eq!(info.transport, "HTTP");
+    /// ```
+    pub fn connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            transport: format!("{:?}", self.transport),
+            address: self.client_addr.clone(),
+            timeout: self.timeout(),
+            client_version: self.client_version(),
+        }
+    }
+}
+
+/// Description of the transport of a client, from [connection_info](Docker::connection_info()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionInfo {
+    /// Name of the transport: `HTTP`, `HTTP/2`, `HTTPS(rustls)`, `HTTPS(native-tls)`, `Unix`,
+    /// `Fd`, `NamedPipe` or `Custom`.
+    pub transport: String,
+    /// Address of the daemon: a host and port, the path of a socket or named pipe, or an
+    /// inherited file descriptor, e.g. `fd://3`.
+    pub address: String,
+    /// Timeout of the requests.
+    pub timeout: Duration,
+    /// API version of the requests.
+    pub client_version: ClientVersion,
+}
+
+/// Response of the [Request Raw API](Docker::request_raw()).
+#[derive(Debug)]
+pub struct RawResponse {
+    response: Response<Incoming>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl RawResponse {
+    /// The status code of the response.
+    pub fn status(&self) -> StatusCode {
+        self.response.status()
+    }
+
+    /// The headers of the response.
+    pub fn headers(&self) -> &HeaderMap {
+        self.response.headers()
+    }
+
+    /// Collect the whole body of the response.
+    pub async fn bytes(self) -> Result<Bytes, Error> {
+        Ok(self.response.into_body().collect().await?.to_bytes())
+    }
+
+    /// Stream the body of the response as it arrives.
+    pub fn into_stream(self) -> BodyStream {
+        let mut response = self.response;
+        let end = StreamEnd::from_response(&mut response);
+        BodyStream::from_incoming(response.into_body(), self.cancellation.as_ref()).with_end(end)
+    }
+
+    /// Stream the body of the response as JSON messages, e.g. for endpoints that report their
+    /// progress.
+    pub fn into_json_stream<T>(self) -> JsonMessageStream<T>
+    where
+        T: DeserializeOwned,
+    {
+        JsonMessageStream::from_stream(self.into_stream())
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Request Raw
+    ///
+    /// Send a request to an arbitrary endpoint of the Docker Engine API, for endpoints that have
+    /// no dedicated method yet. The request uses the client's connection and negotiated API
+    /// version, so the path must not include a version prefix.
+    ///
+    /// Unlike the dedicated methods, a response with an error status code is returned as is,
+    /// rather than as an [Error](Error::DockerResponseServerError).
+    ///
+    /// # Arguments
+    ///
+    ///  - The HTTP method.
+    ///  - The path of the endpoint, e.g. `/containers/json`.
+    ///  - Optional query parameters, serialized as an url encoded query string.
+    ///  - Optional request body.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Raw Response](RawResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use http::Method;
+    ///
+    /// async move {
+    ///     let response = docker
+    ///         .request_raw(Method::GET, "/containers/json", Some([("all", "true")]), None)
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     println!("{}: {:?}", response.status(), response.bytes().await.unwrap());
+    /// };
+    /// ```
+    pub async fn request_raw<O>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<O>,
+        body: Option<BodyType>,
+    ) -> Result<RawResponse, Error>
+    where
+        O: Serialize,
+    {
+        let req = self.build_request(
+            path,
+            Builder::new().method(method),
+            query,
+            Ok(body.unwrap_or_else(|| BodyType::Left(Full::new(Bytes::new())))),
+        )?;
+
+        let call = self
+            .interceptor
+            .as_ref()
+            .map(|interceptor| interceptor.request(&req));
+        let response = Docker::execute_intercepted(
+            self.transport.clone(),
+            req,
+            self.client_timeout,
+            self.cancellation.clone(),
+            call,
+        )
+        .await?;
+
+        Ok(RawResponse {
+            response,
+            cancellation: self.cancellation.clone(),
+        })
     }
-}
 
-impl Docker {
-    /// Set the request timeout.
+    /// ---
     ///
-    /// This timeout is shared by all requests to the Docker Engine API.
+    /// # Upgrade
     ///
-    /// By default, 2 minutes.
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.set_timeout(timeout);
-        self
-    }
-
-    /// Get the current timeout.
+    /// Send a `POST` request to an endpoint of the Docker Engine API that hijacks the connection,
+    /// and return the upgraded connection, to speak a protocol the client has no dedicated method
+    /// for, e.g. the `/grpc` and `/session` endpoints, or the endpoints of daemon plugins. The
+    /// request uses the client's connection and negotiated API version, so the path must not
+    /// include a version prefix.
     ///
-    /// This timeout is shared by all requests to the Docker Engine API.
-    pub fn timeout(&self) -> Duration {
-        Duration::from_secs(self.client_timeout)
-    }
-
-    /// Set the request timeout.
+    /// The request carries the `Connection: Upgrade` and `Upgrade: tcp` headers, unless the given
+    /// headers override them, e.g. with `Upgrade: h2c` for the `/grpc` endpoint.
     ///
-    /// This timeout is shared by all requests to the Docker Engine API.
+    /// # Arguments
     ///
-    /// By default, 2 minutes.
-    pub fn set_timeout(&mut self, timeout: Duration) {
-        self.client_timeout = timeout.as_secs();
+    ///  - The path of the endpoint, e.g. `/grpc`.
+    ///  - Headers of the request.
+    ///
+    /// # Returns
+    ///
+    ///  - The [Upgraded Connection](AsyncUpgraded), reading and writing the raw bytes of the
+    ///    hijacked connection, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use http::header::{HeaderMap, HeaderValue, UPGRADE};
+    /// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    ///
+    /// async move {
+    ///     let mut headers = HeaderMap::new();
+    ///     headers.insert(UPGRADE, HeaderValue::from_static("h2c"));
+    ///
+    ///     let mut connection = docker.upgrade("/grpc", headers).await.unwrap();
+    ///     connection.write_all(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n").await.unwrap();
+    ///
+    ///     let mut buf = [0; 1024];
+    ///     let n = connection.read(&mut buf).await.unwrap();
+    ///     println!("{:?}", &buf[..n]);
+    /// };
+    /// ```
+    pub async fn upgrade(&self, path: &str, headers: HeaderMap) -> Result<AsyncUpgraded, Error> {
+        let mut builder = Builder::new().method(Method::POST);
+        if !headers.contains_key(CONNECTION) {
+            builder = builder.header(CONNECTION, "Upgrade");
+        }
+        if !headers.contains_key(UPGRADE) {
+            builder = builder.header(UPGRADE, "tcp");
+        }
+        if let Some(request_headers) = builder.headers_mut() {
+            request_headers.extend(headers);
+        }
+
+        let req = self.build_request(
+            path,
+            builder,
+            None::<String>,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        self.process_upgraded_connection(req).await
     }
 }
 
@@ -1142,6 +2129,7 @@ impl Docker {
         async move { Docker::decode_response(fut.await?).await }
     }
 
+    #[cfg(any(feature = "container", feature = "system"))]
     pub(crate) fn process_into_stream<T>(
         &self,
         req: Result<Request<BodyType>, Error>,
@@ -1149,25 +2137,80 @@ impl Docker {
     where
         T: DeserializeOwned,
     {
-        Box::pin(
-            self.process_request(req)
-                .map_ok(Docker::decode_into_stream::<T>)
-                .into_stream()
-                .try_flatten(),
+        Cancellable::new(
+            Box::pin(
+                self.process_request(req)
+                    .map_ok({
+                        let skip_undecodable = self.skip_undecodable;
+                        move |res| Docker::decode_into_stream::<T>(res, skip_undecodable)
+                    })
+                    .into_stream()
+                    .try_flatten(),
+            ),
+            self.cancellation.as_ref(),
+        )
+    }
+
+    /// Send the request, and stream its response once the daemon answered, f.e. to know that a
+    /// subscription to the events is in place before reading the state it tracks.
+    #[cfg(all(feature = "container", feature = "system"))]
+    pub(crate) async fn process_into_subscribed_stream<T>(
+        &self,
+        req: Result<Request<BodyType>, Error>,
+    ) -> Result<impl Stream<Item = Result<T, Error>> + Unpin, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let res = self.process_request(req).await?;
+        Ok(Cancellable::new(
+            Box::pin(Docker::decode_into_stream::<T>(res, self.skip_undecodable)),
+            self.cancellation.as_ref(),
+        ))
+    }
+
+    #[cfg(feature = "container")]
+    pub(crate) fn process_into_array_stream<T>(
+        &self,
+        req: Result<Request<BodyType>, Error>,
+    ) -> impl Stream<Item = Result<T, Error>> + Unpin
+    where
+        T: DeserializeOwned,
+    {
+        Cancellable::new(
+            Box::pin(
+                self.process_request(req)
+                    .map_ok({
+                        let skip_undecodable = self.skip_undecodable;
+                        move |res| Docker::decode_into_array_stream::<T>(res, skip_undecodable)
+                    })
+                    .into_stream()
+                    .try_flatten(),
+            ),
+            self.cancellation.as_ref(),
         )
     }
 
+    #[cfg(feature = "container")]
     pub(crate) fn process_into_stream_string(
         &self,
         req: Result<Request<BodyType>, Error>,
     ) -> impl Stream<Item = Result<LogOutput, Error>> + Unpin {
-        Box::pin(
-            self.process_request(req)
-                .map_ok(Docker::decode_into_stream_string)
-                .try_flatten_stream(),
+        Cancellable::new(
+            Box::pin(
+                self.process_request(req)
+                    .map_ok(Docker::decode_into_stream_string)
+                    .try_flatten_stream(),
+            ),
+            self.cancellation.as_ref(),
         )
     }
 
+    #[cfg(any(
+        feature = "container",
+        feature = "network",
+        feature = "swarm",
+        feature = "volume"
+    ))]
     pub(crate) fn process_into_unit(
         &self,
         req: Result<Request<BodyType>, Error>,
@@ -1179,18 +2222,12 @@ impl Docker {
         }
     }
 
-    pub(crate) fn process_into_body(
-        &self,
-        req: Result<Request<BodyType>, Error>,
-    ) -> impl Stream<Item = Result<Bytes, Error>> + Unpin {
-        Box::pin(
-            self.process_request(req)
-                .map_ok(|response| IncomingStream::new(response.into_body()))
-                .into_stream()
-                .try_flatten(),
-        )
+    #[cfg(feature = "container")]
+    pub(crate) fn process_into_body(&self, req: Result<Request<BodyType>, Error>) -> BodyStream {
+        BodyStream::new(self.process_request(req), self.cancellation.as_ref())
     }
 
+    #[cfg(feature = "system")]
     pub(crate) fn process_into_string(
         &self,
         req: Result<Request<BodyType>, Error>,
@@ -1202,22 +2239,86 @@ impl Docker {
         }
     }
 
+    #[cfg_attr(not(feature = "container"), allow(dead_code))]
     pub(crate) async fn process_upgraded(
         &self,
         req: Result<Request<BodyType>, Error>,
     ) -> Result<(impl AsyncRead, impl AsyncWrite), Error> {
+        Ok(split(self.process_upgraded_connection(req).await?))
+    }
+
+    async fn process_upgraded_connection(
+        &self,
+        req: Result<Request<BodyType>, Error>,
+    ) -> Result<AsyncUpgraded, Error> {
+        let res = self.process_request(req).await?;
+        let upgraded = hyper::upgrade::on(res).await?;
+
+        Ok(AsyncUpgraded::new(upgraded, self.cancellation.as_ref()))
+    }
+
+    /// Send a WebSocket handshake request, with the given `Sec-WebSocket-Key`, and wrap the
+    /// upgraded connection into a WebSocket client.
+    #[cfg(feature = "ws")]
+    pub(crate) async fn process_upgraded_websocket(
+        &self,
+        req: Result<Request<BodyType>, Error>,
+        key: &str,
+    ) -> Result<tokio_tungstenite::WebSocketStream<AsyncUpgraded>, Error> {
+        use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+        use tokio_tungstenite::tungstenite::protocol::Role;
+
         let res = self.process_request(req).await?;
+        if res.status() != StatusCode::SWITCHING_PROTOCOLS {
+            return Err(WebSocketHandshakeError {
+                message: format!("unexpected status {}", res.status()),
+            });
+        }
+        let accept = derive_accept_key(key.as_bytes());
+        if res
+            .headers()
+            .get(http::header::SEC_WEBSOCKET_ACCEPT)
+            .map(|value| value.as_bytes())
+            != Some(accept.as_bytes())
+        {
+            return Err(WebSocketHandshakeError {
+                message: String::from("missing or invalid Sec-WebSocket-Accept header"),
+            });
+        }
+
         let upgraded = hyper::upgrade::on(res).await?;
-        let tokio_upgraded = AsyncUpgraded::new(upgraded);
+        let tokio_upgraded = AsyncUpgraded::new(upgraded, self.cancellation.as_ref());
 
-        Ok(split(tokio_upgraded))
+        Ok(
+            tokio_tungstenite::WebSocketStream::from_raw_socket(tokio_upgraded, Role::Client, None)
+                .await,
+        )
     }
 
-    pub(crate) fn serialize_payload<S>(body: Option<S>) -> Result<BodyType, Error>
+    /// Serialize a request body for the client's API version, omitting model fields that the
+    /// version does not support.
+    #[cfg(any(
+        feature = "container",
+        feature = "network",
+        feature = "swarm",
+        feature = "volume"
+    ))]
+    pub(crate) fn serialize_payload<S>(&self, body: Option<S>) -> Result<BodyType, Error>
     where
         S: Serialize,
     {
-        match body.map(|inst| serde_json::to_string(&inst)) {
+        let version = self.client_version();
+        let (payload, skipped) =
+            crate::models::with_api_version((version.major_version, version.minor_version), || {
+                body.map(|inst| serde_json::to_string(&inst))
+            });
+        for field in skipped {
+            warn!(
+                "{field} is not supported by API version {version}, omitting it from the request"
+            );
+        }
+
+        match payload {
             Some(Ok(res)) => Ok(Some(res)),
             Some(Err(e)) => Err(e.into()),
             None => Ok(None),
@@ -1235,8 +2336,136 @@ impl Docker {
         self.version.as_ref().into()
     }
 
+    /// Use the given API version for requests, regardless of the version negotiated with the
+    /// server.
+    ///
+    /// The returned client is independent of this one:
+    /// [negotiate_version](Docker::negotiate_version()) leaves its version unchanged, and
+    /// negotiating on this client does not affect it. Use it to pin a client to a version, or to
+    /// override the version of a single call.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::{ClientVersion, Docker};
+    ///
+    ///     let docker = Docker::connect_with_http_defaults().unwrap();
+    ///     let version = ClientVersion {
+    ///         major_version: 1,
+    ///         minor_version: 41,
+    ///     };
+    ///     async move {
+    ///         docker.with_version(version).inspect_container("hello-world", None).await.unwrap();
+    ///     };
+    /// ```
+    pub fn with_version(&self, version: ClientVersion) -> Docker {
+        Docker {
+            version: Arc::new((
+                AtomicUsize::new(version.major_version),
+                AtomicUsize::new(version.minor_version),
+            )),
+            version_pinned: true,
+            ..self.clone()
+        }
+    }
+
+    /// Tie the requests of the returned client to a cancellation token.
+    ///
+    /// Once the token is cancelled, pending requests fail with a
+    /// [Request Cancelled Error](Error::RequestCancelledError), and streams, such as logs or
+    /// events, as well as attached and exec'd connections, end and close their connection to the
+    /// daemon. BuildKit sessions opened through the client shut down their services gracefully.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust,no_run
+    ///     use bollard::Docker;
+    ///     use bollard::container::LogsOptions;
+    ///     use futures_util::stream::StreamExt;
+    ///     use tokio_util::sync::CancellationToken;
+    ///
+    ///     let docker = Docker::connect_with_http_defaults().unwrap();
+    ///     let token = CancellationToken::new();
+    ///     let logs = docker.with_cancellation(token.clone()).logs(
+    ///         "hello-world",
+    ///         Some(LogsOptions::<String> {
+    ///             follow: true,
+    ///             stdout: true,
+    ///             ..Default::default()
+    ///         }),
+    ///     );
+    ///     async move {
+    ///         token.cancel();
+    ///         assert_eq!(logs.count().await, 0);
+    ///     };
+    /// ```
+    pub fn with_cancellation(&self, token: CancellationToken) -> Docker {
+        Docker {
+            cancellation: Some(token),
+            ..self.clone()
+        }
+    }
+
+    /// Skip the messages of JSON streams, such as events or pull progress, that fail to
+    /// deserialize, logging them, rather than ending the stream with a [JSON Data
+    /// Error](Error::JsonDataError).
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    ///     use bollard::Docker;
+    ///
+    ///     let docker = Docker::connect_with_http_defaults()
+    ///         .unwrap()
+    ///         .with_skip_undecodable(true);
+    ///     let events = docker.events::<String>(None);
+    /// ```
+    pub fn with_skip_undecodable(&self, skip: bool) -> Docker {
+        Docker {
+            skip_undecodable: skip,
+            ..self.clone()
+        }
+    }
+
+    /// End the streams of the [Logs API](Docker::logs()), the [Stats API](Docker::stats()) and
+    /// the [Events API](Docker::events()) with a [Stream Stalled
+    /// Error](Error::StreamStalledError) when no data arrives for this long, f.e. when the daemon
+    /// died behind a proxy that keeps the connection open. The `idle_timeout` of the options of
+    /// these APIs overrides this timeout for a single call.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    ///     use bollard::Docker;
+    ///
+    ///     use std::time::Duration;
+    ///
+    ///     let docker = Docker::connect_with_http_defaults().unwrap();
+    ///     let events = docker
+    ///         .with_idle_timeout(Duration::from_secs(60))
+    ///         .events::<String>(None);
+    /// ```
+    pub fn with_idle_timeout(&self, timeout: Duration) -> Docker {
+        Docker {
+            idle_timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// The cancellation token set through [with_cancellation](Docker::with_cancellation()).
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Whether the client's API version was set through [with_version](Docker::with_version()),
+    /// and is left unchanged by [negotiate_version](Docker::negotiate_version()).
+    pub fn is_version_pinned(&self) -> bool {
+        self.version_pinned
+    }
+
     /// Check with the server for a supported version, and downgrade the client version if
-    /// appropriate.
+    /// appropriate. A client with a [pinned version](Docker::with_version()) is returned
+    /// unchanged. The [server flavor](Docker::server_flavor()) is recorded along the way.
     ///
     /// # Examples:
     ///
@@ -1249,6 +2478,10 @@ impl Docker {
     ///     };
     /// ```
     pub async fn negotiate_version(self) -> Result<Self, Error> {
+        if self.version_pinned {
+            return Ok(self);
+        }
+
         let req = self.build_request(
             "/version",
             Builder::new().method(Method::GET),
@@ -1257,9 +2490,11 @@ impl Docker {
         );
 
         let res = self
-            .process_into_value::<crate::system::Version>(req)
+            .process_into_value::<crate::models::SystemVersion>(req)
             .await?;
 
+        let _ = self.server_flavor.set(ServerFlavor::from(&res));
+
         let server_version: ClientVersion = if let Some(api_version) = res.api_version {
             match api_version.into() {
                 MaybeClientVersion::Some(client_version) => client_version,
@@ -1289,6 +2524,8 @@ impl Docker {
     ) -> impl Future<Output = Result<Response<Incoming>, Error>> {
         let transport = self.transport.clone();
         let timeout = self.client_timeout;
+        let cancellation = self.cancellation.clone();
+        let interceptor = self.interceptor.clone();
 
         match request.as_ref().map(|b| b.body()) {
             Ok(http_body_util::Either::Left(bytes)) => trace!("request: {:?}", bytes),
@@ -1298,7 +2535,11 @@ impl Docker {
 
         async move {
             let request = request?;
-            let response = Docker::execute_request(transport, request, timeout).await?;
+            let endpoint = String::from(request.uri().path());
+            let call = interceptor.map(|interceptor| interceptor.request(&request));
+            let response =
+                Docker::execute_intercepted(transport, request, timeout, cancellation, call)
+                    .await?;
 
             let status = response.status();
             match status {
@@ -1323,10 +2564,12 @@ impl Docker {
                                 }
                             })?;
                     }
-                    Err(DockerResponseServerError {
-                        status_code: status.as_u16(),
-                        message,
-                    })
+                    let err = DaemonError::new(status.as_u16(), message).with_endpoint(endpoint);
+                    debug!(
+                        "{:?} error from {:?}: {}",
+                        err.kind, err.endpoint, err.message
+                    );
+                    Err(err.into())
                 }
             }
         }
@@ -1357,6 +2600,7 @@ impl Docker {
             .body(payload?)?)
     }
 
+    #[cfg(any(feature = "image", feature = "swarm"))]
     pub(crate) fn build_request_with_registry_auth<O>(
         &self,
         path: &str,
@@ -1369,6 +2613,7 @@ impl Docker {
         O: Serialize,
     {
         match credentials {
+            #[cfg(feature = "image")]
             DockerCredentialsHeader::Config(config) => {
                 let value = match config {
                     Some(config) => base64_url_encode(&serde_json::to_string(&config)?),
@@ -1390,19 +2635,50 @@ impl Docker {
         self.build_request(path, builder, query, payload)
     }
 
+    /// Execute the request, reporting its response or failure to the interceptor, and carrying
+    /// the call in the extensions of the response until its body is streamed.
+    async fn execute_intercepted(
+        transport: Arc<Transport>,
+        req: Request<BodyType>,
+        timeout: u64,
+        cancellation: Option<CancellationToken>,
+        call: Option<CallContext>,
+    ) -> Result<Response<Incoming>, Error> {
+        let result = Docker::execute_request(transport, req, timeout, cancellation).await;
+        match (result, call) {
+            (Ok(mut response), Some(call)) => {
+                call.response(response.status());
+                response.extensions_mut().insert(call);
+                Ok(response)
+            }
+            (Err(e), Some(call)) => {
+                call.failed(&e);
+                Err(e)
+            }
+            (result, None) => result,
+        }
+    }
+
     async fn execute_request(
         transport: Arc<Transport>,
         req: Request<BodyType>,
         timeout: u64,
+        cancellation: Option<CancellationToken>,
     ) -> Result<Response<Incoming>, Error> {
         // This is where we determine to which transport we issue the request.
         let request = match *transport {
             #[cfg(feature = "http")]
             Transport::Http { ref client } => client.request(req).map_err(Error::from).boxed(),
+            #[cfg(feature = "http2")]
+            Transport::Http2 { ref client } => client.request(req).boxed(),
             #[cfg(feature = "ssl_providerless")]
             Transport::Https { ref client } => client.request(req).map_err(Error::from).boxed(),
+            #[cfg(all(feature = "native-tls", not(feature = "ssl_providerless")))]
+            Transport::NativeTls { ref client } => client.request(req).map_err(Error::from).boxed(),
             #[cfg(all(feature = "pipe", unix))]
             Transport::Unix { ref client } => client.request(req).map_err(Error::from).boxed(),
+            #[cfg(all(feature = "pipe", unix))]
+            Transport::Fd { ref connection } => connection.request(req).boxed(),
             #[cfg(all(feature = "pipe", windows))]
             Transport::NamedPipe { ref client } => client.request(req).map_err(Error::from).boxed(),
             #[cfg(test)]
@@ -1410,27 +2686,68 @@ impl Docker {
             Transport::Custom { ref transport } => transport.request(req).boxed(),
         };
 
-        match tokio::time::timeout(Duration::from_secs(timeout), request).await {
+        let request = tokio::time::timeout(Duration::from_secs(timeout), request);
+        let response = match cancellation {
+            Some(token) => token
+                .run_until_cancelled_owned(request)
+                .await
+                .ok_or(RequestCancelledError)?,
+            None => request.await,
+        };
+
+        match response {
             Ok(v) => Ok(v?),
             Err(_) => Err(RequestTimeoutError),
         }
     }
 
-    fn decode_into_stream<T>(res: Response<Incoming>) -> impl Stream<Item = Result<T, Error>>
+    #[cfg(any(feature = "container", feature = "system"))]
+    fn decode_into_stream<T>(
+        mut res: Response<Incoming>,
+        skip_undecodable: bool,
+    ) -> impl Stream<Item = Result<T, Error>>
+    where
+        T: DeserializeOwned,
+    {
+        let end = StreamEnd::from_response(&mut res);
+        Intercepted::new(
+            JsonMessageStream::from_reader(StreamReader::new(res.into_body()))
+                .skip_undecodable(skip_undecodable),
+            end,
+        )
+    }
+
+    #[cfg(feature = "container")]
+    fn decode_into_array_stream<T>(
+        mut res: Response<Incoming>,
+        skip_undecodable: bool,
+    ) -> impl Stream<Item = Result<T, Error>>
     where
         T: DeserializeOwned,
     {
-        FramedRead::new(StreamReader::new(res.into_body()), JsonLineDecoder::new())
+        let end = StreamEnd::from_response(&mut res);
+        Intercepted::new(
+            FramedRead::new(
+                StreamReader::new(res.into_body()),
+                JsonArrayDecoder::new(skip_undecodable),
+            ),
+            end,
+        )
     }
 
+    #[cfg(feature = "container")]
     fn decode_into_stream_string(
-        res: Response<Incoming>,
+        mut res: Response<Incoming>,
     ) -> impl Stream<Item = Result<LogOutput, Error>> {
-        FramedRead::new(
-            StreamReader::new(res.into_body()),
-            NewlineLogOutputDecoder::new(false),
+        let end = StreamEnd::from_response(&mut res);
+        Intercepted::new(
+            FramedRead::new(
+                StreamReader::new(res.into_body()),
+                NewlineLogOutputDecoder::new(false),
+            )
+            .map_err(Error::from),
+            end,
         )
-        .map_err(Error::from)
     }
 
     async fn decode_into_string(response: Response<Incoming>) -> Result<String, Error> {
@@ -1449,12 +2766,7 @@ impl Docker {
 
         serde_json::from_slice::<T>(&bytes).map_err(|e| {
             if e.is_data() || e.is_syntax() {
-                JsonDataError {
-                    message: e.to_string(),
-                    column: e.column(),
-                    #[cfg(feature = "json_data_content")]
-                    contents: String::from_utf8_lossy(&bytes).to_string(),
-                }
+                json_data_error::<T>(&e, &bytes)
             } else {
                 e.into()
             }
@@ -1484,3 +2796,431 @@ pub fn body_try_stream(
 pub fn body_full(body: Bytes) -> BodyType {
     BodyType::Left(Full::new(body))
 }
+
+#[cfg(not(windows))]
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http::request::Builder;
+    use http::Method;
+    use http_body_util::Full;
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::BodyType;
+    use crate::errors::{DaemonError, Error, ErrorKind};
+    use crate::{ClientVersion, Docker, API_DEFAULT_VERSION};
+
+    #[tokio::test]
+    async fn test_with_version() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:22\r\n\r\n{\"ApiVersion\":\"1.40\"}\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+        let version = ClientVersion {
+            major_version: 1,
+            minor_version: 41,
+        };
+        let pinned = docker.with_version(version);
+        assert!(pinned.is_version_pinned());
+        assert!(!docker.is_version_pinned());
+
+        let docker = docker.negotiate_version().await.unwrap();
+        assert_eq!(docker.client_version().to_string(), "1.40");
+        assert_eq!(pinned.client_version(), version);
+
+        let pinned = pinned.negotiate_version().await.unwrap();
+        assert_eq!(pinned.client_version(), version);
+    }
+
+    #[test]
+    fn test_connection_info() {
+        let docker = Docker::connect_with_mock(
+            HostToReplyConnector::default(),
+            "127.0.0.1".to_string(),
+            5,
+            API_DEFAULT_VERSION,
+        )
+        .unwrap();
+
+        let info = docker.connection_info();
+        assert_eq!(info.transport, "Mock");
+        assert_eq!(info.address, "127.0.0.1");
+        assert_eq!(info.timeout, std::time::Duration::from_secs(5));
+        assert_eq!(&info.client_version, API_DEFAULT_VERSION);
+    }
+
+    #[cfg(all(feature = "pipe", unix))]
+    #[test]
+    fn test_socket_diagnostic() {
+        let diagnostic = crate::errors::SocketDiagnostic {
+            owner_uid: 0,
+            owner_gid: 999,
+            owner_group: Some(String::from("docker")),
+            mode: 0o660,
+            process_uid: 1000,
+            in_owner_group: false,
+        };
+        assert_eq!(
+            diagnostic.to_string(),
+            "owned by uid 0 and group docker (gid 999) with mode 660; uid 1000 is not in group \
+             docker (gid 999): add the user to the group, then log in again"
+        );
+
+        // a socket the process may access has nothing to explain
+        let dir = std::env::temp_dir().join(format!("bollard-socket-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("docker.sock");
+        let _ = std::fs::remove_file(&path);
+        let _listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        assert_eq!(super::socket_permission_diagnostic(&path), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "container")]
+    async fn test_serialize_payload_for_version() {
+        use http_body_util::BodyExt;
+
+        use crate::models::{HostConfig, HostConfigCgroupnsModeEnum};
+
+        let docker = Docker::connect_with_mock(
+            HostToReplyConnector::default(),
+            "127.0.0.1".to_string(),
+            5,
+            &ClientVersion {
+                major_version: 1,
+                minor_version: 41,
+            },
+        )
+        .unwrap();
+
+        let host_config = HostConfig {
+            cgroupns_mode: Some(HostConfigCgroupnsModeEnum::PRIVATE),
+            console_size: Some(vec![24, 80]),
+            ..Default::default()
+        };
+
+        let body = docker
+            .serialize_payload(Some(&host_config))
+            .unwrap()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes();
+        assert_eq!(&body[..], br#"{"CgroupnsMode":"private"}"#);
+
+        let body = docker
+            .with_version(ClientVersion {
+                major_version: 1,
+                minor_version: 42,
+            })
+            .serialize_payload(Some(&host_config))
+            .unwrap()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes();
+        assert_eq!(
+            &body[..],
+            br#"{"ConsoleSize":[24,80],"CgroupnsMode":"private"}"#
+        );
+    }
+
+    #[test]
+    fn test_min_api_version_fields() {
+        use std::collections::HashMap;
+
+        use crate::models::{
+            with_api_version, EndpointSettings, HealthConfig, HostConfig,
+            HostConfigCgroupnsModeEnum, MountBindOptions, MountTmpfsOptions, MountVolumeOptions,
+        };
+
+        // Request fields introduced after API v1.40, from the API version history. Each must be
+        // declared in `minApiVersionModelFields` of the codegen, or it is sent to older daemons.
+        let gated = [
+            ("HostConfig.CgroupnsMode", 41),
+            ("HostConfig.ConsoleSize", 42),
+            ("HostConfig.Annotations", 43),
+            ("HealthConfig.StartInterval", 44),
+            ("EndpointSettings.MacAddress", 44),
+            ("MountBindOptions.CreateMountpoint", 42),
+            ("MountBindOptions.ReadOnlyNonRecursive", 44),
+            ("MountBindOptions.ReadOnlyForceRecursive", 44),
+            ("MountTmpfsOptions.Options", 46),
+            ("MountVolumeOptions.Subpath", 45),
+        ];
+
+        let payload = (
+            HostConfig {
+                cgroupns_mode: Some(HostConfigCgroupnsModeEnum::PRIVATE),
+                console_size: Some(vec![24, 80]),
+                annotations: Some(HashMap::new()),
+                ..Default::default()
+            },
+            HealthConfig {
+                start_interval: Some(1_000_000),
+                ..Default::default()
+            },
+            EndpointSettings {
+                mac_address: Some(String::from("02:42:ac:11:00:02")),
+                ..Default::default()
+            },
+            MountBindOptions {
+                create_mountpoint: Some(true),
+                read_only_non_recursive: Some(true),
+                read_only_force_recursive: Some(true),
+                ..Default::default()
+            },
+            MountTmpfsOptions {
+                options: Some(vec![vec![String::from("exec")]]),
+                ..Default::default()
+            },
+            MountVolumeOptions {
+                subpath: Some(String::from("data")),
+                ..Default::default()
+            },
+        );
+
+        for minor in 40..=API_DEFAULT_VERSION.minor_version {
+            let (_, mut skipped) =
+                with_api_version((1, minor), || serde_json::to_string(&payload).unwrap());
+            skipped.sort_unstable();
+            let mut expected: Vec<_> = gated
+                .iter()
+                .filter(|(_, introduced)| minor < *introduced)
+                .map(|(field, _)| *field)
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(skipped, expected, "serializing for API v1.{minor}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_raw() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 404 Not Found\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:29\r\n\r\n{\"message\":\"page not found\"}\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let response = docker
+            .request_raw(Method::GET, "/experimental", None::<String>, None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 404);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            &response.bytes().await.unwrap()[..],
+            b"{\"message\":\"page not found\"}\n"
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "container")]
+    async fn test_daemon_error() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 409 Conflict\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:32\r\n\r\n{\"message\":\"name already used\"}\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let req = docker.build_request(
+            "/containers/create",
+            Builder::new().method(Method::POST),
+            None::<String>,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+        let err = docker.process_into_unit(req).await.unwrap_err();
+
+        assert_eq!(err.kind(), Some(ErrorKind::Conflict));
+        assert!(!err.is_retryable());
+        let daemon_error = err.daemon_error().unwrap();
+        assert_eq!(daemon_error.status_code, 409);
+        assert_eq!(daemon_error.message, "name already used");
+        assert_eq!(daemon_error.endpoint.as_deref(), Some("/containers/create"));
+        assert!(matches!(
+            err,
+            Error::DockerResponseServerError { endpoint: Some(ref endpoint), .. }
+                if endpoint == "/containers/create"
+        ));
+
+        assert!(DaemonError::new(503, "unavailable").is_retryable());
+        assert!(DaemonError::new(429, "too many requests").is_retryable());
+        assert!(!DaemonError::new(404, "no such container").is_retryable());
+        assert!(Error::RequestTimeoutError.is_retryable());
+        assert_eq!(Error::RequestTimeoutError.kind(), None);
+    }
+
+    #[tokio::test]
+    async fn test_with_cancellation() {
+        use futures_util::StreamExt;
+        use tokio_util::sync::CancellationToken;
+
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:22\r\n\r\n{\"ApiVersion\":\"1.40\"}\n".to_string(),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+        let token = CancellationToken::new();
+        let cancellable = docker.with_cancellation(token.clone());
+        assert!(docker.cancellation_token().is_none());
+
+        assert!(cancellable.version().await.is_ok());
+
+        token.cancel();
+        assert!(matches!(
+            cancellable.version().await,
+            Err(Error::RequestCancelledError)
+        ));
+        assert_eq!(cancellable.export_image("alpine").count().await, 0);
+        assert!(docker.version().await.is_ok());
+    }
+
+    #[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
+    #[test]
+    fn test_connect_with_ssl_bytes_invalid_key() {
+        let result = Docker::connect_with_ssl_bytes(
+            "tcp://localhost:2376",
+            b"not a key",
+            b"",
+            b"",
+            120,
+            API_DEFAULT_VERSION,
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::CertBytesParseError { what: "key", .. })
+        ));
+    }
+
+    #[cfg(feature = "http2")]
+    #[tokio::test]
+    async fn test_connect_with_http2() {
+        use hyper::server::conn::http2;
+        use hyper::service::service_fn;
+        use hyper::{Response, Version};
+        use hyper_util::rt::{TokioExecutor, TokioIo};
+        use std::convert::Infallible;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = Arc::clone(&connections);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                accepted.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(http2::Builder::new(TokioExecutor::new()).serve_connection(
+                    TokioIo::new(stream),
+                    service_fn(|req: hyper::Request<hyper::body::Incoming>| async move {
+                        assert_eq!(req.version(), Version::HTTP_2);
+                        Ok::<_, Infallible>(Response::new(Full::new(Bytes::from("OK"))))
+                    }),
+                ));
+            }
+        });
+
+        let docker =
+            Docker::connect_with_http2(&format!("tcp://{addr}"), 5, API_DEFAULT_VERSION).unwrap();
+        assert_eq!(docker.connection_info().transport, "HTTP/2");
+
+        let pings = futures_util::future::join_all((0..5).map(|_| docker.ping())).await;
+        assert!(pings.iter().all(|ping| ping.as_deref().ok() == Some("OK")));
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "http2")]
+    #[tokio::test]
+    async fn test_connect_with_http2_fallback() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 1024];
+                let n = stream.read(&mut buf).await.unwrap();
+                let response = if buf[..n].starts_with(b"PRI * HTTP/2.0") {
+                    "HTTP/1.1 400 Bad Request\r\nConnection: close\r\nContent-Length: 0\r\n\r\n"
+                } else {
+                    "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 2\r\n\r\nOK"
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let docker =
+            Docker::connect_with_http2(&format!("tcp://{addr}"), 5, API_DEFAULT_VERSION).unwrap();
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_upgrade() {
+        use http::header::{HeaderMap, HeaderValue, UPGRADE};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let daemon = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 101 UPGRADED\r\nConnection: Upgrade\r\nUpgrade: h2c\r\n\r\n")
+                .await
+                .unwrap();
+            // echo the tunnelled bytes
+            let n = stream.read(&mut buf).await.unwrap();
+            stream.write_all(&buf[..n]).await.unwrap();
+            String::from_utf8_lossy(&request).to_lowercase()
+        });
+
+        let docker =
+            Docker::connect_with_http(&format!("tcp://{addr}"), 5, API_DEFAULT_VERSION).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(UPGRADE, HeaderValue::from_static("h2c"));
+        let mut connection = docker.upgrade("/grpc", headers).await.unwrap();
+
+        connection.write_all(b"ping").await.unwrap();
+        let mut echo = [0; 4];
+        connection.read_exact(&mut echo).await.unwrap();
+        assert_eq!(&echo, b"ping");
+
+        let request = daemon.await.unwrap();
+        assert!(request.starts_with("post /grpc http/1.1\r\n"));
+        assert!(request.contains("connection: upgrade\r\n"));
+        assert!(request.contains("upgrade: h2c\r\n"));
+        assert!(!request.contains("upgrade: tcp"));
+    }
+}
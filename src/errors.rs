@@ -1,18 +1,17 @@
 //! Errors for this module.
 
-#[cfg(feature = "ssl_providerless")]
+#[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
 use std::path::PathBuf;
 
 /// Generic Docker errors
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Error emitted during client instantiation when the `DOCKER_CERT_PATH` environment variable
-    /// is invalid.
-    #[cfg(feature = "ssl_providerless")]
+    /// is invalid, or when the Docker config file cannot be located.
     #[error("Could not find home directory")]
     NoHomePathError,
     /// Generic error when reading a certificate from the filesystem
-    #[cfg(feature = "ssl_providerless")]
+    #[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
     #[error("Cannot open/read certificate with path: {path}")]
     CertPathError {
         /// Path for the failing certificate file
@@ -28,12 +27,29 @@ pub enum Error {
         path: PathBuf,
     },
     /// Parse error for RSA encrypted keys
-    #[cfg(feature = "ssl_providerless")]
+    #[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
     #[error("Could not parse key: {path}")]
     CertParseError {
         /// Path for the failing certificate file
         path: PathBuf,
     },
+    /// Parse error for certificates or keys given in memory
+    #[cfg(any(feature = "ssl_providerless", feature = "native-tls"))]
+    #[error("Could not parse {what}: {message}")]
+    CertBytesParseError {
+        /// Which of the key, certificate or certificate chain failed to parse
+        what: &'static str,
+        /// Reason of the failure
+        message: String,
+    },
+    /// Error emitted by the platform TLS library
+    #[cfg(feature = "native-tls")]
+    #[error(transparent)]
+    NativeTlsError {
+        /// The original error emitted.
+        #[from]
+        err: native_tls::Error,
+    },
     /// Error emitted when the client is unable to parse a native pki cert for SSL
     #[cfg(feature = "ssl_providerless")]
     #[error("Could not parse a pki native cert")]
@@ -56,6 +72,8 @@ pub enum Error {
         status_code: u16,
         /// Message returned by the docker server.
         message: String,
+        /// Path of the endpoint that returned the error, if known.
+        endpoint: Option<String>,
     },
     /// Error facilitating debugging failed JSON parsing.
     #[error("Failed to deserialize JSON: {message}")]
@@ -65,6 +83,18 @@ pub enum Error {
         /// Entire JSON payload. This field is toggled with the **json_data_content** feature cargo flag.
         #[cfg(feature = "json_data_content")]
         contents: String,
+        /// Byte offset of the error in the JSON payload. This field is toggled with the
+        /// **json_data_content** feature cargo flag.
+        #[cfg(feature = "json_data_content")]
+        offset: usize,
+        /// Path to the value that failed to deserialize, e.g. `progressDetail.total`. This field
+        /// is toggled with the **json_data_content** feature cargo flag.
+        #[cfg(feature = "json_data_content")]
+        path: String,
+        /// Section of the JSON payload around the error. This field is toggled with the
+        /// **json_data_content** feature cargo flag.
+        #[cfg(feature = "json_data_content")]
+        snippet: String,
         /// Character sequence at error location.
         column: usize,
     },
@@ -74,6 +104,16 @@ pub enum Error {
     /// Error emitted when a request times out.
     #[error("Timeout error")]
     RequestTimeoutError,
+    /// Error emitted when a request is cancelled through its
+    /// [cancellation token](crate::Docker::with_cancellation()).
+    #[error("Request cancelled")]
+    RequestCancelledError,
+    /// Error emitted when a stream receives no data within its idle timeout.
+    #[error("No data received from the stream for {timeout:?}")]
+    StreamStalledError {
+        /// The idle timeout that elapsed.
+        timeout: std::time::Duration,
+    },
     /// Error emitted mid-stream as part of a successful docker operation
     #[error("Docker stream error")]
     DockerStreamError {
@@ -94,6 +134,12 @@ pub enum Error {
     /// Error emitted when a session is not provided to the buildkit engine
     #[error("Buildkit requires a builder version set")]
     MissingVersionBuildkitError {},
+    /// Error emitted when a container is run without an image
+    #[error("Running a container requires an image")]
+    MissingImageError {},
+    /// Error emitted when a swarm is updated without the version of the swarm it is based on
+    #[error("Updating a swarm requires the version of its spec")]
+    MissingSwarmVersionError {},
     /// Error emitted when JSON fails to serialize.
     #[error(transparent)]
     JsonSerdeError {
@@ -165,20 +211,379 @@ pub enum Error {
         err: http::uri::InvalidUriParts,
     },
     /// Error that is never emitted
-    #[cfg(feature = "http")]
+    #[cfg(any(feature = "http", feature = "pipe"))]
     #[error("Error in the hyper legacy client: {}", err)]
     HyperLegacyError {
         /// The original error emitted.
         #[from]
         err: hyper_util::client::legacy::Error,
     },
+    /// Error emitted by the WebSocket protocol of an attached container.
+    #[cfg(feature = "ws")]
+    #[error(transparent)]
+    WebSocketError {
+        /// The original error emitted.
+        err: Box<tokio_tungstenite::tungstenite::Error>,
+    },
+    /// Error emitted when the daemon does not accept the WebSocket handshake.
+    #[cfg(feature = "ws")]
+    #[error("WebSocket handshake failed: {message}")]
+    WebSocketHandshakeError {
+        /// Why the response of the daemon is not a valid handshake.
+        message: String,
+    },
     /// Error emitted when connecting to a URI with an unsupported scheme
     #[error("URI scheme is not supported: {uri}")]
     UnsupportedURISchemeError {
         /// The URI that was attempted to be connected to
         uri: String,
     },
+    /// Error emitted when an image archive cannot be assembled or parsed.
+    #[cfg(feature = "image_builder")]
+    #[error("Invalid image archive: {message}")]
+    ImageArchiveError {
+        /// Description of the problem with the archive.
+        message: String,
+    },
+    /// Error emitted when the digest of a pulled or exported image differs from the expected digest.
+    #[cfg(feature = "verify_digest")]
+    #[error("Image digest mismatch: expected {expected}, got {}", actual.as_deref().unwrap_or("no digest"))]
+    DigestMismatchError {
+        /// The expected digest.
+        expected: String,
+        /// The digest of the image, if one was reported.
+        actual: Option<String>,
+    },
+    /// Error emitted when a git repository cannot be cloned into a build context.
+    #[cfg(feature = "git_context")]
+    #[error("Unable to clone git repository {url}: {message}")]
+    GitContextError {
+        /// The URL of the repository.
+        url: String,
+        /// What went wrong.
+        message: String,
+    },
+    /// Error emitted when a platform fails to parse.
+    #[error("Invalid platform {platform}: {message}")]
+    InvalidPlatformError {
+        /// The platform that failed to parse.
+        platform: String,
+        /// Description of the problem with the platform.
+        message: String,
+    },
+    /// Error emitted when a network configuration fails validation before it is sent.
+    #[error("Invalid network configuration: {message}")]
+    InvalidNetworkConfigError {
+        /// Description of the problem with the configuration.
+        message: String,
+    },
+    /// Error emitted when a network is not removed, as running containers are connected to it.
+    #[error("Network {network} is in use by running containers: {containers:?}")]
+    NetworkInUseError {
+        /// The network name.
+        network: String,
+        /// The IDs of the running containers connected to the network.
+        containers: Vec<String>,
+    },
+    /// Error emitted when a container port fails to parse.
+    #[error("Invalid port: {message}")]
+    InvalidPortError {
+        /// Description of the problem with the port.
+        message: String,
+    },
+    /// Error emitted when a signal fails to parse, or is not supported by the platform.
+    #[error("Invalid signal {signal}: {message}")]
+    InvalidSignalError {
+        /// The signal that failed to parse.
+        signal: String,
+        /// Description of the problem with the signal.
+        message: String,
+    },
+    /// Error emitted when a container log configuration fails validation.
+    #[error("Invalid {driver} log configuration: {message}")]
+    InvalidLogConfigError {
+        /// The logging driver.
+        driver: String,
+        /// Description of the problem with the configuration.
+        message: String,
+    },
+    /// Error emitted when a container update fails validation before it is sent.
+    #[error("Invalid container update: {message}")]
+    InvalidContainerUpdateError {
+        /// Description of the problem with the update.
+        message: String,
+    },
+    /// Error emitted when a detach key sequence fails to parse.
+    #[error("Invalid detach keys: {keys}")]
+    InvalidDetachKeysError {
+        /// The detach key sequence that failed to parse.
+        keys: String,
+    },
+    /// Error emitted when logs options select no stream, or an empty time range.
+    #[error("Invalid logs options: {message}")]
+    InvalidLogsOptionsError {
+        /// The reason the options are invalid.
+        message: String,
+    },
+    /// Error emitted when a label selector fails to parse.
+    #[error("Invalid label selector {selector}: {message}")]
+    InvalidLabelSelectorError {
+        /// The label selector that failed to parse.
+        selector: String,
+        /// The reason the label selector is invalid.
+        message: String,
+    },
+    /// Error emitted when an image reference fails to parse.
+    #[error("Invalid image reference {reference}: {message}")]
+    InvalidReferenceError {
+        /// The image reference that failed to parse.
+        reference: String,
+        /// The reason the image reference is invalid.
+        message: String,
+    },
+    /// Error emitted when a Linux capability fails to parse.
+    #[error("Invalid capability: {capability}")]
+    InvalidCapabilityError {
+        /// The capability that failed to parse.
+        capability: String,
+    },
+    /// Error emitted when a container port is not published on the host.
+    #[error("Port {port} is not published")]
+    PortNotPublishedError {
+        /// The container port, e.g. `80/tcp`.
+        port: String,
+    },
+    /// Error emitted when the service inside a container is not ready, as the container stopped
+    /// running or the wait timed out.
+    #[error("Container {container} is not ready: {message}")]
+    ContainerNotReadyError {
+        /// The container name.
+        container: String,
+        /// Why the container is not ready.
+        message: String,
+    },
+    /// Error emitted when a readiness probe is invalid, e.g. its log pattern fails to compile.
+    #[error("Invalid readiness probe: {message}")]
+    InvalidReadinessProbeError {
+        /// Description of the invalid probe.
+        message: String,
+    },
+    /// Error emitted when a request uses a feature that the chosen API does not support.
+    #[error("Unsupported feature: {message}")]
+    UnsupportedFeatureError {
+        /// Description of the unsupported feature.
+        message: String,
+    },
+    /// Error emitted when a credential helper of the Docker config file fails.
+    #[error("Credential helper docker-credential-{helper} failed: {message}")]
+    CredentialHelperError {
+        /// Name of the helper, e.g. `desktop` or `ecr-login`.
+        helper: String,
+        /// What went wrong.
+        message: String,
+    },
     /// Error emitted when the Docker socket file is not found at the expected location.
     #[error("Socket not found: {0}")]
     SocketNotFoundError(String),
+    /// Error emitted when the Docker socket file exists, but the current user may not connect to
+    /// it.
+    #[cfg(all(feature = "pipe", unix))]
+    #[error("Permission denied on socket {path}: {diagnostic}")]
+    SocketPermissionError {
+        /// Path of the socket.
+        path: String,
+        /// Ownership of the socket, compared with the identity of the current process.
+        diagnostic: SocketDiagnostic,
+    },
+    /// Error emitted when systemd socket activation did not pass a socket to this process.
+    #[cfg(all(feature = "pipe", unix))]
+    #[error("No socket passed by systemd: {message}")]
+    ListenFdsError {
+        /// Why the `LISTEN_FDS` and `LISTEN_PID` environment variables are not usable.
+        message: String,
+    },
+}
+
+/// Ownership and mode of a socket, compared with the identity of the current process, to explain
+/// why the process may not connect to it.
+#[cfg(all(feature = "pipe", unix))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SocketDiagnostic {
+    /// User ID owning the socket.
+    pub owner_uid: u32,
+    /// Group ID owning the socket.
+    pub owner_gid: u32,
+    /// Name of the group owning the socket, e.g. `docker`, if known.
+    pub owner_group: Option<String>,
+    /// Permission bits of the socket, e.g. `0o660`.
+    pub mode: u32,
+    /// Effective user ID of the current process.
+    pub process_uid: u32,
+    /// Whether the current process is a member of the group owning the socket.
+    pub in_owner_group: bool,
+}
+
+#[cfg(all(feature = "pipe", unix))]
+impl std::fmt::Display for SocketDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let group = match &self.owner_group {
+            Some(name) => format!("{name} (gid {})", self.owner_gid),
+            None => format!("gid {}", self.owner_gid),
+        };
+        write!(
+            f,
+            "owned by uid {} and group {group} with mode {:o}; ",
+            self.owner_uid, self.mode
+        )?;
+        if self.in_owner_group {
+            write!(f, "uid {} is in group {group}", self.process_uid)
+        } else {
+            write!(
+                f,
+                "uid {} is not in group {group}: add the user to the group, then log in again",
+                self.process_uid
+            )
+        }
+    }
+}
+
+/// Classification of an error response from the docker server, by status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The object does not exist (404).
+    NotFound,
+    /// The request conflicts with the state of an object, e.g. a name in use (409).
+    Conflict,
+    /// The object is already in the requested state, e.g. a started container (304).
+    NotModified,
+    /// The registry or server rejected the credentials (401, 403).
+    Unauthorized,
+    /// The server failed to process the request (5xx).
+    ServerError,
+    /// Any other status code, usually a bad request (400).
+    Other,
+}
+
+impl From<u16> for ErrorKind {
+    fn from(status_code: u16) -> Self {
+        match status_code {
+            404 => ErrorKind::NotFound,
+            409 => ErrorKind::Conflict,
+            304 => ErrorKind::NotModified,
+            401 | 403 => ErrorKind::Unauthorized,
+            500..=599 => ErrorKind::ServerError,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// An error response from the docker server, with its parsed message and classification.
+///
+/// Obtain one from an [Error] with [`Error::daemon_error`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Docker responded with status code {status_code}: {message}")]
+pub struct DaemonError {
+    /// Status code returned by the docker server.
+    pub status_code: u16,
+    /// Message returned by the docker server.
+    pub message: String,
+    /// Path of the endpoint that returned the error, if known.
+    pub endpoint: Option<String>,
+    /// Classification of the status code.
+    pub kind: ErrorKind,
+}
+
+impl DaemonError {
+    /// Create a daemon error for the given status code and message.
+    pub fn new(status_code: u16, message: impl Into<String>) -> Self {
+        DaemonError {
+            status_code,
+            message: message.into(),
+            endpoint: None,
+            kind: ErrorKind::from(status_code),
+        }
+    }
+
+    /// Set the path of the endpoint that returned the error.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Whether repeating the same request may succeed: server errors, request timeouts (408)
+    /// and rate limiting (429).
+    pub fn is_retryable(&self) -> bool {
+        self.kind == ErrorKind::ServerError || matches!(self.status_code, 408 | 429)
+    }
+}
+
+impl From<DaemonError> for Error {
+    fn from(err: DaemonError) -> Self {
+        Error::DockerResponseServerError {
+            status_code: err.status_code,
+            message: err.message,
+            endpoint: err.endpoint,
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocketError { err: Box::new(err) }
+    }
+}
+
+impl Error {
+    /// The error response from the docker server, if this error is one.
+    pub fn daemon_error(&self) -> Option<DaemonError> {
+        match self {
+            Error::DockerResponseServerError {
+                status_code,
+                message,
+                endpoint,
+            } => Some(DaemonError {
+                endpoint: endpoint.clone(),
+                ..DaemonError::new(*status_code, message.clone())
+            }),
+            _ => None,
+        }
+    }
+
+    /// Classification of the error response from the docker server, if this error is one.
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match self {
+            Error::DockerResponseServerError { status_code, .. } => {
+                Some(ErrorKind::from(*status_code))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether repeating the same request may succeed: retryable
+    /// [daemon errors](DaemonError::is_retryable), request timeouts, stalled streams and connection
+    /// failures.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::DockerResponseServerError {
+                status_code,
+                message,
+                ..
+            } => DaemonError::new(*status_code, message.clone()).is_retryable(),
+            Error::RequestTimeoutError => true,
+            Error::StreamStalledError { .. } => true,
+            #[cfg(feature = "http")]
+            Error::HyperLegacyError { err } => err.is_connect(),
+            Error::IOError { err } => matches!(
+                err.kind(),
+                std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::Interrupted
+            ),
+            _ => false,
+        }
+    }
 }
@@ -16,8 +16,12 @@ use crate::errors::Error;
 use crate::models::ExecInspectResponse;
 use crate::read::NewlineLogOutputDecoder;
 use futures_core::Stream;
+use futures_util::{future, stream, StreamExt};
+use log::debug;
 use std::fmt::{Debug, Formatter};
 use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use tokio::io::AsyncWrite;
 use tokio_util::codec::FramedRead;
 
@@ -70,6 +74,11 @@ pub struct StartExecOptions {
     pub tty: bool,
     /// The maximum size for a line of output. The default is 8 * 1024 (roughly 1024 characters).
     pub output_capacity: Option<usize>,
+    /// The maximum size of a single item of output. Larger frames from the daemon are split into
+    /// several items instead of being buffered whole, so that memory use stays bounded and a slow
+    /// consumer applies backpressure to the daemon. By default, frames are not split.
+    #[serde(skip)]
+    pub max_frame_size: Option<usize>,
 }
 
 /// Result type for the [Start Exec API](Docker::start_exec())
@@ -103,6 +112,156 @@ pub struct ResizeExecOptions {
     pub width: u16,
 }
 
+/// How often to inspect a completed exec that the daemon still reports as running, as the exit
+/// code is recorded shortly after the output ends.
+const EXEC_COMPLETED_INSPECT_ATTEMPTS: u32 = 10;
+
+/// Interval between the inspects of a completed exec that the daemon still reports as running.
+const EXEC_COMPLETED_INSPECT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The stage of an exec reported to an [exec audit hook](Docker::with_exec_audit_hook()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecAuditEvent {
+    /// The exec was created through the [Create Exec API](Docker::create_exec()).
+    Created,
+    /// The exec was started through the [Start Exec API](Docker::start_exec()).
+    Started,
+    /// The output of an attached exec was consumed to the end, and the command exited. Never
+    /// reported for detached execs, as the client does not observe them exiting.
+    Completed,
+}
+
+/// An entry reported to an [exec audit hook](Docker::with_exec_audit_hook()).
+///
+/// On creation, the details of the exec are those of the [Create Exec
+/// Options](CreateExecOptions). On completion, they are read back from the [Inspect Exec
+/// API](Docker::inspect_exec()), and are left empty if the exec can no longer be inspected. A
+/// start only carries the ID of the exec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecAuditRecord {
+    /// The stage of the exec.
+    pub event: ExecAuditEvent,
+    /// The ID of the exec.
+    pub exec_id: String,
+    /// The container the exec runs in: its name or ID as passed to the [Create Exec
+    /// API](Docker::create_exec()) on creation, and its ID on completion.
+    pub container_id: Option<String>,
+    /// The command, with its arguments.
+    pub cmd: Vec<String>,
+    /// The user the command runs as, if not the container's default user.
+    pub user: Option<String>,
+    /// When the event was observed by the client.
+    pub timestamp: SystemTime,
+    /// The exit code of the command, set on [`ExecAuditEvent::Completed`]. Left empty if the
+    /// daemon still reports the exec as running some time after its output ended.
+    pub exit_code: Option<i64>,
+}
+
+impl ExecAuditRecord {
+    fn new(event: ExecAuditEvent, exec_id: &str) -> Self {
+        ExecAuditRecord {
+            event,
+            exec_id: String::from(exec_id),
+            container_id: None,
+            cmd: Vec::new(),
+            user: None,
+            timestamp: SystemTime::now(),
+            exit_code: None,
+        }
+    }
+}
+
+/// Callback registered with [`Docker::with_exec_audit_hook`].
+#[derive(Clone)]
+pub(crate) struct ExecAuditHook(Arc<dyn Fn(&ExecAuditRecord) + Send + Sync>);
+
+impl Debug for ExecAuditHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ExecAuditHook")
+    }
+}
+
+impl Docker {
+    /// Register a hook invoked for every exec created or started through this client, e.g. to
+    /// keep an audit log of the commands run in containers.
+    ///
+    /// The hook receives an [Exec Audit Record](ExecAuditRecord) when an exec is created, when it
+    /// is started, and, for attached execs, when its output has been consumed to the end. Detached
+    /// execs never report a completion, as the client does not observe them exiting. Reading back
+    /// the exit code of a completed exec costs an additional request, repeated for a short while
+    /// if the daemon has not recorded the exit code yet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// let docker = Docker::connect_with_http_defaults()
+    ///     .unwrap()
+    ///     .with_exec_audit_hook(|record| {
+    ///         println!("{:?} {} {:?}", record.event, record.exec_id, record.cmd);
+    ///     });
+    /// ```
+    pub fn with_exec_audit_hook<F>(&self, hook: F) -> Docker
+    where
+        F: Fn(&ExecAuditRecord) + Send + Sync + 'static,
+    {
+        Docker {
+            exec_audit_hook: Some(ExecAuditHook(Arc::new(hook))),
+            ..self.clone()
+        }
+    }
+
+    fn audit_exec(&self, record: &ExecAuditRecord) {
+        if let Some(ExecAuditHook(hook)) = &self.exec_audit_hook {
+            hook(record);
+        }
+    }
+
+    async fn audit_exec_completed(&self, exec_id: &str) {
+        if self.exec_audit_hook.is_some() {
+            let timestamp = SystemTime::now();
+            let mut attempts = 0;
+            let inspect = loop {
+                attempts += 1;
+                match self.inspect_exec(exec_id).await {
+                    Ok(inspect)
+                        if inspect.running == Some(true)
+                            && attempts < EXEC_COMPLETED_INSPECT_ATTEMPTS =>
+                    {
+                        tokio::time::sleep(EXEC_COMPLETED_INSPECT_INTERVAL).await;
+                    }
+                    Ok(inspect) => break inspect,
+                    Err(e) => {
+                        debug!("unable to inspect exec {exec_id} for audit: {e}");
+                        break ExecInspectResponse::default();
+                    }
+                }
+            };
+            let (cmd, user) = match inspect.process_config {
+                Some(config) => (
+                    config
+                        .entrypoint
+                        .into_iter()
+                        .chain(config.arguments.unwrap_or_default())
+                        .collect(),
+                    config.user.filter(|user| !user.is_empty()),
+                ),
+                None => (Vec::new(), None),
+            };
+
+            self.audit_exec(&ExecAuditRecord {
+                event: ExecAuditEvent::Completed,
+                exec_id: String::from(exec_id),
+                container_id: inspect.container_id,
+                cmd,
+                user,
+                timestamp,
+                exit_code: inspect.exit_code,
+            });
+        }
+    }
+}
+
 impl Docker {
     /// ---
     ///
@@ -152,10 +311,26 @@ impl Docker {
             &url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(&config)),
         );
 
-        self.process_into_value(req).await
+        let results: CreateExecResults = self.process_into_value(req).await?;
+        if self.exec_audit_hook.is_some() {
+            self.audit_exec(&ExecAuditRecord {
+                container_id: Some(String::from(container_name)),
+                cmd: config
+                    .cmd
+                    .map(|cmd| cmd.into_iter().map(Into::into).collect())
+                    .unwrap_or_default(),
+                user: config
+                    .user
+                    .map(Into::into)
+                    .filter(|user: &String| !user.is_empty()),
+                ..ExecAuditRecord::new(ExecAuditEvent::Created, &results.id)
+            });
+        }
+
+        Ok(results)
     }
 
     /// ---
@@ -207,10 +382,12 @@ impl Docker {
                     &url,
                     Builder::new().method(Method::POST),
                     None::<String>,
-                    Docker::serialize_payload(config),
+                    self.serialize_payload(config),
                 );
 
                 self.process_into_unit(req).await?;
+                self.audit_exec(&ExecAuditRecord::new(ExecAuditEvent::Started, exec_id));
+
                 Ok(StartExecResults::Detached)
             }
             _ => {
@@ -221,6 +398,7 @@ impl Docker {
                     }) => capacity,
                     _ => 8 * 1024,
                 };
+                let max_frame_size = config.and_then(|config| config.max_frame_size);
 
                 let req = self.build_request(
                     &url,
@@ -229,7 +407,7 @@ impl Docker {
                         .header(CONNECTION, "Upgrade")
                         .header(UPGRADE, "tcp"),
                     None::<String>,
-                    Docker::serialize_payload(config.or_else(|| {
+                    self.serialize_payload(config.or_else(|| {
                         Some(StartExecOptions {
                             ..Default::default()
                         })
@@ -238,12 +416,27 @@ impl Docker {
 
                 let (read, write) = self.process_upgraded(req).await?;
 
-                let log =
-                    FramedRead::with_capacity(read, NewlineLogOutputDecoder::new(true), capacity)
-                        .map_err(|e| e.into());
+                let decoder =
+                    NewlineLogOutputDecoder::new(true).with_max_frame_size(max_frame_size);
+                let log = FramedRead::with_capacity(read, decoder, capacity).map_err(|e| e.into());
+
+                self.audit_exec(&ExecAuditRecord::new(ExecAuditEvent::Started, exec_id));
+
+                let output: Pin<Box<dyn Stream<Item = Result<LogOutput, Error>> + Send>> =
+                    if self.exec_audit_hook.is_some() {
+                        let docker = self.clone();
+                        let exec_id = String::from(exec_id);
+                        let completed = stream::once(async move {
+                            docker.audit_exec_completed(&exec_id).await;
+                        })
+                        .filter_map(|_| future::ready(None));
+                        Box::pin(log.chain(completed))
+                    } else {
+                        Box::pin(log)
+                    };
 
                 Ok(StartExecResults::Attached {
-                    output: Box::pin(log),
+                    output,
                     input: Box::pin(write),
                 })
             }
@@ -348,3 +541,51 @@ impl Docker {
         self.process_into_unit(req).await
     }
 }
+
+#[cfg(not(windows))]
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    use super::{CreateExecOptions, ExecAuditEvent};
+
+    #[tokio::test]
+    async fn test_exec_audit_hook() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:63\r\n\r\n{\"Id\":\"f2f7a1c4\",\"ProcessConfig\":{\"entrypoint\":\"ps\",\"user\":\"\"}}".to_string());
+
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let hook_records = Arc::clone(&records);
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap()
+                .with_exec_audit_hook(move |record| {
+                    hook_records.lock().unwrap().push(record.clone());
+                });
+
+        let results = docker
+            .create_exec(
+                "hello-world",
+                CreateExecOptions {
+                    cmd: Some(vec!["ps"]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, ExecAuditEvent::Created);
+        assert_eq!(records[0].exec_id, results.id);
+        assert_eq!(records[0].container_id.as_deref(), Some("hello-world"));
+        assert_eq!(records[0].cmd, vec!["ps"]);
+        assert_eq!(records[0].user, None);
+    }
+}
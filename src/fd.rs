@@ -0,0 +1,243 @@
+//! File descriptors: connect to the Docker daemon through a socket inherited from the parent
+//! process, e.g. a systemd socket unit or a sandbox without access to the socket file.
+//!
+//! An inherited socket is a single connection: the requests through it run one after the other,
+//! and a request that hijacks the connection, e.g. to attach to a container, ends it.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bollard::Docker;
+//!
+//! use futures_util::future::TryFutureExt;
+//!
+//! // The socket passed by systemd, through `LISTEN_FDS` and `LISTEN_PID`.
+//! let docker = Docker::connect_with_listenfd().unwrap();
+//! docker.ping().map_ok(|_| Ok::<_, ()>(println!("Connected!")));
+//! ```
+
+use futures_util::lock::Mutex;
+use http::header::HOST;
+use http::uri::PathAndQuery;
+use hyper::body::Incoming;
+use hyper::client::conn::http1::{self, SendRequest};
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use log::debug;
+
+use std::env;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::docker::BodyType;
+use crate::errors::Error;
+
+/// The first file descriptor passed by systemd socket activation.
+pub const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// Whether the descriptors passed by systemd are already owned by a client.
+static LISTEN_FDS_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// The file descriptors passed to this process by systemd socket activation, through the
+/// `LISTEN_FDS` and `LISTEN_PID` environment variables.
+pub fn listen_fds() -> Result<Vec<RawFd>, Error> {
+    let pid = env::var("LISTEN_PID").map_err(|_| Error::ListenFdsError {
+        message: String::from("LISTEN_PID is not set"),
+    })?;
+    if pid.parse::<u32>().ok() != Some(std::process::id()) {
+        return Err(Error::ListenFdsError {
+            message: format!("LISTEN_PID {pid} is not the pid of this process"),
+        });
+    }
+    let count = env::var("LISTEN_FDS")
+        .ok()
+        .and_then(|count| count.parse::<RawFd>().ok())
+        .filter(|count| *count > 0)
+        .ok_or_else(|| Error::ListenFdsError {
+            message: String::from("LISTEN_FDS does not pass any file descriptor"),
+        })?;
+
+    Ok((SD_LISTEN_FDS_START..SD_LISTEN_FDS_START + count).collect())
+}
+
+/// Take ownership of the first file descriptor passed by systemd socket activation. The
+/// descriptors are taken once: as `sd_listen_fds(1)` does, this unsets the environment variables
+/// that pass them, and later calls fail.
+pub(crate) fn take_listen_fd() -> Result<OwnedFd, Error> {
+    let already_taken = || Error::ListenFdsError {
+        message: String::from("the passed file descriptors are already taken"),
+    };
+    if LISTEN_FDS_TAKEN.load(Ordering::SeqCst) {
+        return Err(already_taken());
+    }
+    let fd = listen_fds()?[0];
+    if LISTEN_FDS_TAKEN.swap(true, Ordering::SeqCst) {
+        return Err(already_taken());
+    }
+    env::remove_var("LISTEN_PID");
+    env::remove_var("LISTEN_FDS");
+    env::remove_var("LISTEN_FDNAMES");
+
+    // SAFETY: `fcntl` only reads the flags of the descriptor, to check that it is open.
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(io::Error::last_os_error().into());
+    }
+    // SAFETY: systemd passes the descriptors to this process, which owns them from now on.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Check that the descriptor is a connected Unix stream socket.
+fn connected_stream(fd: OwnedFd) -> Result<UnixStream, Error> {
+    let mut socket_type: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    // SAFETY: `socket_type` and `len` outlive the call, and `len` is the size of `socket_type`.
+    let result = unsafe {
+        libc::getsockopt(
+            fd.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_TYPE,
+            std::ptr::addr_of_mut!(socket_type).cast(),
+            &mut len,
+        )
+    };
+    if result == -1 {
+        return Err(io::Error::last_os_error().into());
+    }
+    if socket_type != libc::SOCK_STREAM {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("file descriptor {} is not a stream socket", fd.as_raw_fd()),
+        )
+        .into());
+    }
+
+    let stream = UnixStream::from(fd);
+    stream.peer_addr()?;
+    stream.set_nonblocking(true)?;
+    Ok(stream)
+}
+
+#[derive(Debug)]
+enum FdState {
+    Inherited(UnixStream),
+    Connected(SendRequest<BodyType>),
+}
+
+/// The single HTTP connection over an inherited socket.
+#[derive(Debug)]
+pub(crate) struct FdConnection {
+    state: Mutex<FdState>,
+}
+
+impl FdConnection {
+    pub(crate) fn new(fd: OwnedFd) -> Result<Self, Error> {
+        Ok(FdConnection {
+            state: Mutex::new(FdState::Inherited(connected_stream(fd)?)),
+        })
+    }
+
+    /// Send the request once the previous one has completed.
+    pub(crate) async fn request(
+        &self,
+        mut req: Request<BodyType>,
+    ) -> Result<Response<Incoming>, Error> {
+        let mut state = self.state.lock().await;
+        if let FdState::Inherited(stream) = &*state {
+            let stream = tokio::net::UnixStream::from_std(stream.try_clone()?)?;
+            let (sender, connection) = http1::handshake(TokioIo::new(stream)).await?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.with_upgrades().await {
+                    debug!("inherited connection closed: {e}");
+                }
+            });
+            *state = FdState::Connected(sender);
+        }
+        let FdState::Connected(sender) = &mut *state else {
+            unreachable!()
+        };
+        sender.ready().await?;
+
+        // The connection is already open: send the path only, as the legacy client does.
+        if let Some(authority) = req.uri().authority().cloned() {
+            if let (false, Ok(host)) =
+                (req.headers().contains_key(HOST), authority.as_str().parse())
+            {
+                req.headers_mut().insert(HOST, host);
+            }
+        }
+        let path = req
+            .uri()
+            .path_and_query()
+            .cloned()
+            .unwrap_or_else(|| PathAndQuery::from_static("/"));
+        *req.uri_mut() = path.into();
+
+        let response = sender.send_request(req);
+        drop(state);
+        Ok(response.await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::fd::OwnedFd;
+    use std::os::unix::net::UnixStream;
+
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    #[tokio::test]
+    async fn test_connect_with_fd() {
+        let (client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let server = tokio::net::UnixStream::from_std(server).unwrap();
+
+        let daemon = tokio::spawn(async move {
+            let mut server = BufReader::new(server);
+            let mut request_lines = Vec::new();
+            for _ in 0..2 {
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    server.read_line(&mut line).await.unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    request_lines.push(line.trim_end().to_owned());
+                }
+                server
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Type:text/plain\r\nContent-Length:2\r\n\r\nOK",
+                    )
+                    .await
+                    .unwrap();
+            }
+            request_lines
+        });
+
+        let docker =
+            Docker::connect_with_fd(OwnedFd::from(client), 5, API_DEFAULT_VERSION).unwrap();
+        assert_eq!(docker.connection_info().transport, "Fd");
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+        assert_eq!(docker.ping().await.unwrap(), "OK");
+
+        let request_lines = daemon.await.unwrap();
+        let requests: Vec<_> = request_lines
+            .iter()
+            .filter(|line| line.starts_with("GET"))
+            .collect();
+        assert_eq!(requests, ["GET /_ping HTTP/1.1", "GET /_ping HTTP/1.1"]);
+        assert!(request_lines
+            .iter()
+            .any(|line| line.to_lowercase().starts_with("host:")));
+    }
+
+    #[test]
+    fn test_connect_with_fd_not_connected() {
+        let (client, _server) = std::os::unix::net::UnixDatagram::pair().unwrap();
+        assert!(Docker::connect_with_fd(OwnedFd::from(client), 5, API_DEFAULT_VERSION).is_err());
+    }
+}
@@ -1,15 +1,49 @@
 pub use bollard_buildkit_proto::fsutil;
 pub use bollard_buildkit_proto::health;
 pub use bollard_buildkit_proto::moby;
-use bollard_buildkit_proto::moby::buildkit::v1::CacheOptionsEntry;
+use bollard_buildkit_proto::moby::buildkit::secrets::v1::secrets_server::SecretsServer;
+use bollard_buildkit_proto::moby::buildkit::v1::control_client::ControlClient;
+use bollard_buildkit_proto::moby::buildkit::v1::{
+    CacheOptionsEntry, StatusRequest, StatusResponse,
+};
+use bollard_buildkit_proto::moby::filesync::packet::file_sync_server::FileSyncServer;
+use bollard_buildkit_proto::moby::filesync::v1::auth_server::AuthServer;
+use bollard_buildkit_proto::moby::sshforward::v1::ssh_server::SshServer;
+use bollard_buildkit_proto::moby::upload::v1::upload_server::UploadServer;
+use bollard_buildkit_proto::pb::Definition;
 
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::future::Future;
 use std::net::IpAddr;
 use std::path::Path;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
 
 use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::future::Either;
+use futures_util::StreamExt;
+use log::debug;
+use serde_derive::Deserialize;
+use tokio::sync::{mpsc, Semaphore};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::codegen::InterceptedService;
+use tonic::transport::Channel;
+
+use super::driver::{solve_request, Driver, DriverInterceptor, DriverTearDownHandler, SolveHandle};
+use super::error::GrpcError;
+use super::fsutil::LocalSource;
+use super::registry::ImageRegistryOutput;
+use super::{
+    AuthProvider, GrpcServer, LocalContextProvider, SecretProvider, SshProvider, UploadProvider,
+};
+use crate::auth::DockerCredentials;
+
+const DOCKERFILE_FRONTEND: &str = "dockerfile.v0";
+const GATEWAY_FRONTEND: &str = "gateway.v0";
+const DEFAULT_DOCKERFILE: &str = "Dockerfile";
 
 /// Parameters available for passing frontend options to buildkit when initiating a Solve GRPC
 /// request, f.e. used in associated methods within the [GRPC module](module@crate::grpc)
@@ -27,12 +61,13 @@ use bytes::Bytes;
 pub struct ImageBuildFrontendOptions {
     //pub(crate) cgroupparent: Option<String>,
     //pub(crate) multiplatform: bool,
-    //pub(crate) attests: HashMap<String, String>,
+    pub(crate) attests: HashMap<String, String>,
     pub(crate) cacheto: Vec<CacheOptionsEntry>,
     pub(crate) cachefrom: Vec<CacheOptionsEntry>,
     pub(crate) image_resolve_mode: bool,
     pub(crate) target: Option<String>,
     pub(crate) nocache: bool,
+    pub(crate) no_cache_filter: Vec<String>,
     pub(crate) buildargs: HashMap<String, String>,
     pub(crate) labels: HashMap<String, String>,
     pub(crate) platforms: Vec<ImageBuildPlatform>,
@@ -41,12 +76,18 @@ pub struct ImageBuildFrontendOptions {
     pub(crate) shmsize: u64,
     pub(crate) secrets: HashMap<String, SecretSource>,
     pub(crate) ssh: bool,
+    pub(crate) ssh_sources: HashMap<String, SshSource>,
     pub(crate) named_contexts: HashMap<String, NamedContext>,
     pub(crate) dockerfile: Option<PathBuf>,
+    pub(crate) frontend: Option<String>,
+    pub(crate) frontend_attrs: HashMap<String, String>,
+    pub(crate) frontend_inputs: HashMap<String, Definition>,
+    pub(crate) definition: Option<Definition>,
+    pub(crate) entitlements: Vec<ImageBuildEntitlement>,
     //pub(crate) ulimit: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
 /// Specifies how secrets are populated into the buildkit build without persisting into the final image.
 pub enum SecretSource {
     /// Sets the secret source as a local file, must be associated with appropriate Dockerfile
@@ -55,6 +96,80 @@ pub enum SecretSource {
     /// Sets the secret source as an environment variable, must be associated with appropriate
     /// Dockerfile instruction: `RUN mount=type=secret,id=foo,env=MY_ENV_VAR`
     Env(String),
+    /// Sets the secret source as in-memory bytes, so the secret is never written to disk.
+    Bytes(Vec<u8>),
+    /// Fetches the secret when buildkit requests it during the build, e.g. from a vault or key
+    /// management service.
+    Provider(Arc<dyn SecretFetcher>),
+}
+
+impl std::fmt::Debug for SecretSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretSource::File(path) => f.debug_tuple("File").field(path).finish(),
+            SecretSource::Env(var) => f.debug_tuple("Env").field(var).finish(),
+            SecretSource::Bytes(_) => f.write_str("Bytes(..)"),
+            SecretSource::Provider(_) => f.write_str("Provider(..)"),
+        }
+    }
+}
+
+impl PartialEq for SecretSource {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SecretSource::File(a), SecretSource::File(b)) => a == b,
+            (SecretSource::Env(a), SecretSource::Env(b)) => a == b,
+            (SecretSource::Bytes(a), SecretSource::Bytes(b)) => a == b,
+            (SecretSource::Provider(a), SecretSource::Provider(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+type SecretFetcherReturnTy =
+    Pin<Box<dyn Future<Output = Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>> + Send>>;
+
+/// Fetches the contents of a [provided secret](SecretSource::Provider) by its ID.
+///
+/// Implemented for closures taking the secret ID and returning a future, e.g.
+///
+/// ```rust
+/// use bollard::grpc::build::SecretSource;
+///
+/// use std::sync::Arc;
+///
+/// SecretSource::Provider(Arc::new(|id: String| async move {
+///     // fetch the secret from a vault ...
+///     Ok(format!("secret for {id}").into_bytes())
+/// }));
+/// ```
+pub trait SecretFetcher: Send + Sync {
+    /// Fetch the secret with the given ID, this returns a future
+    fn fetch(&self, id: String) -> SecretFetcherReturnTy;
+}
+
+// auto impl for Fn(String) -> Future<Output = Result<_, _>
+impl<Callback, ReturnTy> SecretFetcher for Callback
+where
+    Callback: Fn(String) -> ReturnTy + Send + Sync,
+    ReturnTy:
+        Future<Output = Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>> + Send + 'static,
+{
+    fn fetch(&self, id: String) -> SecretFetcherReturnTy {
+        Box::pin(self(id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Specifies which ssh agent is forwarded into the buildkit build for a given ID, matching the
+/// `docker buildx build --ssh id=path` semantics.
+pub enum SshSource {
+    /// Forwards the ssh agent listening on the given socket, must be associated with appropriate
+    /// Dockerfile instruction: `RUN --mount=type=ssh,id=foo`
+    Agent(std::path::PathBuf),
+    /// Serves the given unencrypted private key files through an in-process ssh agent, so no
+    /// ssh agent needs to be running. RSA keys only produce `rsa-sha2-512` signatures.
+    Keys(Vec<std::path::PathBuf>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -100,6 +215,47 @@ impl Display for ImageBuildNetworkMode {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+/// Privileges a build can request for `RUN` instructions, e.g. `RUN --network=host` or `RUN
+/// --security=insecure`. The buildkit daemon must allow the entitlement, f.e. through
+/// `--allow-insecure-entitlement` when starting `buildkitd`.
+pub enum ImageBuildEntitlement {
+    /// Run with host networking
+    NetworkHost,
+    /// Run without sandboxing, similar to a privileged container
+    SecurityInsecure,
+}
+
+impl Display for ImageBuildEntitlement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageBuildEntitlement::NetworkHost => write!(f, "network.host"),
+            ImageBuildEntitlement::SecurityInsecure => write!(f, "security.insecure"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+/// Detail of the SLSA provenance attestation attached to the built image, see [buildkit
+/// provenance docs](https://docs.docker.com/build/metadata/attestations/slsa-provenance/)
+pub enum ImageBuildProvenanceMode {
+    /// Record the build's inputs and the build parameters needed to reproduce it
+    Min,
+    /// Also record the build steps, the source of the Dockerfile and the build arguments
+    Max,
+}
+
+impl Display for ImageBuildProvenanceMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageBuildProvenanceMode::Min => write!(f, "mode=min"),
+            ImageBuildProvenanceMode::Max => write!(f, "mode=max"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 /// Describes the platform which the image in the manifest runs on, as defined in the [OCI Image Index Specification](https://github.com/opencontainers/image-spec/blob/v1.0.1/image-index.md).
 pub struct ImageBuildPlatform {
@@ -112,13 +268,14 @@ pub struct ImageBuildPlatform {
 }
 
 impl Display for ImageBuildPlatform {
+    /// Formats the platform as `os/arch[/variant]`, like [`crate::platform::Platform`], on every
+    /// host.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let prefix = Path::new(&self.os).join(Path::new(&self.architecture));
+        write!(f, "{}/{}", self.os, self.architecture)?;
         if let Some(variant) = &self.variant {
-            write!(f, "{}", prefix.join(Path::new(&variant)).display())
-        } else {
-            write!(f, "{}", prefix.display())
+            write!(f, "/{variant}")?;
         }
+        Ok(())
     }
 }
 
@@ -149,18 +306,53 @@ impl Display for ImageBuildOutputCompression {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-/// An additional build context. Can be used to override a named stage in the Dockerfile.
+/// An additional build context, the equivalent of `docker buildx build --build-context
+/// name=source`. Can be used to override a named stage or image in the Dockerfile, e.g. `FROM
+/// name`, or as the source of `COPY --from=name`.
 pub struct NamedContext {
-    /// Container image (with `docker-image://` prefix), Git, or HTTP URL.
+    /// Container image (with `docker-image://` prefix), Git, or HTTP URL, a frontend input (with
+    /// `input:` prefix), or the path of a local directory, which is synced to buildkit through
+    /// the build session.
     pub path: String,
 }
 
+impl NamedContext {
+    /// A named context of a local directory.
+    pub fn local<P: AsRef<Path>>(path: P) -> Self {
+        NamedContext {
+            path: path.as_ref().to_string_lossy().into_owned(),
+        }
+    }
+
+    /// A named context of a container image, e.g. `alpine:3`.
+    pub fn image(reference: &str) -> Self {
+        NamedContext {
+            path: format!("docker-image://{reference}"),
+        }
+    }
+
+    /// Whether the context is a local directory rather than a URL or reference understood by
+    /// the frontend.
+    fn is_local(&self) -> bool {
+        !(self.path.contains("://")
+            || self.path.starts_with("git@")
+            || self.path.starts_with("input:")
+            || self.path.starts_with("local:"))
+    }
+}
+
 pub(crate) struct ImageBuildFrontendOptionsIngest {
     pub cache_to: Vec<CacheOptionsEntry>,
     pub cache_from: Vec<CacheOptionsEntry>,
     pub frontend_attrs: HashMap<String, String>,
+    pub local_contexts: HashMap<String, LocalSource>,
     pub secret_sources: HashMap<String, SecretSource>,
     pub ssh: bool,
+    pub ssh_sources: HashMap<String, SshSource>,
+    pub frontend: String,
+    pub frontend_inputs: HashMap<String, Definition>,
+    pub definition: Option<Definition>,
+    pub entitlements: Vec<String>,
 }
 
 impl ImageBuildFrontendOptions {
@@ -171,6 +363,7 @@ impl ImageBuildFrontendOptions {
 
     pub(crate) fn consume(self) -> ImageBuildFrontendOptionsIngest {
         let mut attrs = HashMap::new();
+        let mut local_contexts = HashMap::new();
 
         if let Some(dockerfile) = self.dockerfile {
             attrs.insert(
@@ -191,6 +384,8 @@ impl ImageBuildFrontendOptions {
 
         if self.nocache {
             attrs.insert(String::from("no-cache"), String::new());
+        } else if !self.no_cache_filter.is_empty() {
+            attrs.insert(String::from("no-cache"), self.no_cache_filter.join(","));
         }
 
         if !self.buildargs.is_empty() {
@@ -247,16 +442,42 @@ impl ImageBuildFrontendOptions {
                 String::from("moby.buildkit.frontend.contexts+forward"),
             );
             for (name, context) in self.named_contexts {
-                attrs.insert(format!("context:{name}"), context.path);
+                if context.is_local() {
+                    // unique names, so that builds sharing a session don't serve each other's files
+                    let dir_name = super::new_id();
+                    attrs.insert(format!("context:{name}"), format!("local:{dir_name}"));
+                    local_contexts.insert(dir_name, LocalSource::Directory(context.path.into()));
+                } else {
+                    attrs.insert(format!("context:{name}"), context.path);
+                }
             }
         }
 
+        for (key, value) in self.attests {
+            attrs.insert(format!("attest:{key}"), value);
+        }
+
+        attrs.extend(self.frontend_attrs);
+
+        // a pre-computed definition is solved directly, unless a frontend is chosen to process it
+        let frontend = match (self.frontend, &self.definition) {
+            (Some(frontend), _) => frontend,
+            (None, Some(_)) => String::new(),
+            (None, None) => String::from(DOCKERFILE_FRONTEND),
+        };
+
         ImageBuildFrontendOptionsIngest {
             cache_to: self.cacheto,
             cache_from: self.cachefrom,
             frontend_attrs: attrs,
+            local_contexts,
             secret_sources: self.secrets,
             ssh: self.ssh,
+            ssh_sources: self.ssh_sources,
+            frontend,
+            frontend_inputs: self.frontend_inputs,
+            definition: self.definition,
+            entitlements: self.entitlements.iter().map(|v| v.to_string()).collect(),
         }
     }
 }
@@ -311,8 +532,8 @@ impl ImageBuildFrontendOptionsBuilder {
         self
     }
 
-    /// A name and optional tag to apply to the image in the `name:tag` format. If you omit the tag
-    /// the default `latest` value is assumed. You can provide several `t` parameters.
+    /// Name of the stage of a multi-stage `Dockerfile` to build, instead of the last stage. Sets
+    /// the `target` frontend attribute.
     pub fn target(mut self, target: &str) -> Self {
         self.inner.target = Some(String::from(target));
         self
@@ -324,9 +545,29 @@ impl ImageBuildFrontendOptionsBuilder {
         self
     }
 
+    /// Do not use the cache for the given stages of a multi-stage `Dockerfile`, the equivalent of
+    /// `docker buildx build --no-cache-filter`. Ignored when [`nocache`](Self::nocache) is set.
+    pub fn no_cache_filter(mut self, stages: &[&str]) -> Self {
+        for stage in stages {
+            if !self
+                .inner
+                .no_cache_filter
+                .iter()
+                .any(|known| known == stage)
+            {
+                self.inner.no_cache_filter.push(String::from(*stage));
+            }
+        }
+        self
+    }
+
     /// Add string pair for build-time variables. Users pass these values at build-time.
     /// Docker uses the buildargs as the environment context for commands run via the `Dockerfile`
-    /// RUN instruction, or for variable expansion in other `Dockerfile` instructions.
+    /// RUN instruction, or for variable expansion in other `Dockerfile` instructions. Sets the
+    /// `build-arg:{key}` frontend attribute.
+    ///
+    /// The platform arguments, e.g. `TARGETPLATFORM`, `TARGETARCH` or `BUILDPLATFORM`, are set by
+    /// the frontend from the [`platforms`](Self::platforms) of the build and need not be passed.
     pub fn buildarg(mut self, key: &str, value: &str) -> Self {
         self.inner
             .buildargs
@@ -334,7 +575,8 @@ impl ImageBuildFrontendOptionsBuilder {
         self
     }
 
-    /// Append arbitrary key/value label to set on the image.
+    /// Append arbitrary key/value label to set on the image. Sets the `label:{key}` frontend
+    /// attribute.
     pub fn label(mut self, key: &str, value: &str) -> Self {
         self.inner
             .labels
@@ -367,7 +609,8 @@ impl ImageBuildFrontendOptionsBuilder {
         self
     }
 
-    /// Set source of a single secret as part of the build, either a file or environment variable.
+    /// Set source of a single secret as part of the build, either a file, an environment
+    /// variable, in-memory bytes or a provider fetching it at build time.
     pub fn set_secret(mut self, key: &str, value: &SecretSource) -> Self {
         self.inner
             .secrets
@@ -375,18 +618,115 @@ impl ImageBuildFrontendOptionsBuilder {
         self
     }
 
+    /// Authenticate to the given git host, f.e. `github.com`, with a token, when buildkit fetches
+    /// a private repository of an [`ImageBuildLoadInput::Remote`] context or a git named context.
+    pub fn git_auth_token(self, host: &str, token: &str) -> Self {
+        let secret = SecretSource::Bytes(Vec::from(token));
+        self.set_secret(&format!("GIT_AUTH_TOKEN.{host}"), &secret)
+    }
+
+    /// Replace the `Authorization` scheme that buildkit sends the [git
+    /// token](ImageBuildFrontendOptionsBuilder::git_auth_token) of the host with, `basic` by
+    /// default, f.e. `bearer`.
+    pub fn git_auth_header(self, host: &str, scheme: &str) -> Self {
+        let secret = SecretSource::Bytes(Vec::from(scheme));
+        self.set_secret(&format!("GIT_AUTH_HEADER.{host}"), &secret)
+    }
+
     /// Enable sshforward to ssh agent.
     pub fn enable_ssh(mut self, value: bool) -> Self {
         self.inner.ssh = value;
         self
     }
 
-    /// Add a named build context.
+    /// Set the ssh agent forwarded for a single ID as part of the build, either an agent socket
+    /// or private key files. Setting the `default` ID replaces the agent on `SSH_AUTH_SOCK`.
+    pub fn set_ssh(mut self, id: &str, value: &SshSource) -> Self {
+        self.inner
+            .ssh_sources
+            .insert(String::from(id), value.to_owned());
+        self
+    }
+
+    /// Request an entitlement for the build, which the buildkit daemon must allow.
+    pub fn entitlement(mut self, value: &ImageBuildEntitlement) -> Self {
+        if !self.inner.entitlements.contains(value) {
+            self.inner.entitlements.push(value.to_owned());
+        }
+        self
+    }
+
+    /// Add a named build context, referenced by a Dockerfile as `FROM key` or `COPY --from=key`.
     pub fn named_context(mut self, key: &str, value: NamedContext) -> Self {
         self.inner.named_contexts.insert(String::from(key), value);
         self
     }
 
+    /// Frontend used to process the build, defaults to the `dockerfile.v0` frontend built into
+    /// buildkit.
+    pub fn frontend(mut self, frontend: &str) -> Self {
+        self.inner.frontend = Some(String::from(frontend));
+        self
+    }
+
+    /// Process the build with a frontend distributed as an image, through the `gateway.v0`
+    /// frontend, e.g. `docker/dockerfile:1`.
+    pub fn gateway_frontend(mut self, image: &str) -> Self {
+        self.inner.frontend = Some(String::from(GATEWAY_FRONTEND));
+        self.inner
+            .frontend_attrs
+            .insert(String::from("source"), String::from(image));
+        self
+    }
+
+    /// Append an arbitrary attribute passed to the frontend, overriding attributes derived from
+    /// other options.
+    pub fn frontend_attr(mut self, key: &str, value: &str) -> Self {
+        self.inner
+            .frontend_attrs
+            .insert(String::from(key), String::from(value));
+        self
+    }
+
+    /// Pass an LLB definition as a named input to the frontend.
+    pub fn frontend_input(mut self, key: &str, definition: &Definition) -> Self {
+        self.inner
+            .frontend_inputs
+            .insert(String::from(key), definition.to_owned());
+        self
+    }
+
+    /// Solve a pre-computed LLB definition. Unless a frontend is also set, the definition is
+    /// solved directly, without a Dockerfile.
+    pub fn definition(mut self, definition: &Definition) -> Self {
+        self.inner.definition = Some(definition.to_owned());
+        self
+    }
+
+    /// Attach a SLSA provenance attestation to the built image, with the given detail. The
+    /// attestation is exported along with the image, and read back from OCI exports into the
+    /// [`ImageBuildResult`].
+    pub fn provenance(mut self, mode: &ImageBuildProvenanceMode) -> Self {
+        self.inner
+            .attests
+            .insert(String::from("provenance"), mode.to_string());
+        self
+    }
+
+    /// Attach an SPDX SBOM attestation, scanning the built image, to the image. The attestation
+    /// is exported along with the image, and read back from OCI exports into the
+    /// [`ImageBuildResult`].
+    pub fn sbom(mut self, sbom: bool) -> Self {
+        if sbom {
+            self.inner
+                .attests
+                .insert(String::from("sbom"), String::new());
+        } else {
+            self.inner.attests.remove("sbom");
+        }
+        self
+    }
+
     /// Consume the builder and emit an [`ImageBuildFrontendOptions`]
     pub fn build(self) -> ImageBuildFrontendOptions {
         self.inner
@@ -398,7 +738,7 @@ impl ImageBuildFrontendOptionsBuilder {
 /// Dockerfile seed implementation to export OCI images as part of the
 /// [`crate::grpc::driver::Export::export`] Docker/buildkit functionality.
 ///
-/// Accepts a compressed Dockerfile as Bytes
+/// Accepts a compressed Dockerfile as Bytes, or a Dockerfile as a string
 ///
 /// ## Examples
 ///
@@ -427,16 +767,543 @@ impl ImageBuildFrontendOptionsBuilder {
 ///
 ///     bollard::grpc::build::ImageBuildLoadInput::Upload(bytes::Bytes::from(compressed));
 ///
+///     // or, without a tarball
+///     bollard::grpc::build::ImageBuildLoadInput::DockerfileContent {
+///         dockerfile,
+///         context: bollard::grpc::build::ContextSource::Empty,
+///     };
+///
 /// ```
 ///
 pub enum ImageBuildLoadInput {
     /// Seed the exporter with a tarball containing the Dockerfile to build
     Upload(Bytes),
+    /// Build a Dockerfile given as a string, e.g. generated on the fly, without packing it into a
+    /// tarball. The Dockerfile and the build context are synced to buildkit through the build
+    /// session, and the frontend's `dockerfile` option is ignored.
+    DockerfileContent {
+        /// Contents of the Dockerfile
+        dockerfile: String,
+        /// Build context that the Dockerfile's `COPY` and `ADD` instructions read from
+        context: ContextSource,
+    },
+    /// Build a context that buildkit fetches itself, f.e. a git repository with an optional
+    /// branch, tag or commit, and subdirectory: `https://github.com/moby/buildkit.git#v0.13.0:examples`,
+    /// or an https tarball. Private repositories authenticate with
+    /// [`ImageBuildFrontendOptionsBuilder::git_auth_token`].
+    Remote(String),
+}
+
+/// Build context of an [`ImageBuildLoadInput::DockerfileContent`] build.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextSource {
+    /// No build context, for Dockerfiles that copy nothing from it.
+    Empty,
+    /// A local directory, honouring its `.dockerignore` file.
+    Directory(PathBuf),
+}
+
+impl ImageBuildLoadInput {
+    /// Register the build context with the session providers, pointing the frontend at it. Returns
+    /// the key of the uploaded tarball, if any, to remove once the build completes.
+    pub(crate) fn load(
+        self,
+        upload_provider: &UploadProvider,
+        frontend_opts: &mut ImageBuildFrontendOptionsIngest,
+    ) -> Option<String> {
+        match self {
+            ImageBuildLoadInput::Upload(payload) => {
+                let context = upload_provider.add(payload.to_vec());
+                frontend_opts
+                    .frontend_attrs
+                    .insert(String::from("context"), String::clone(&context));
+                Some(context)
+            }
+            ImageBuildLoadInput::DockerfileContent {
+                dockerfile,
+                context,
+            } => {
+                let context = match context {
+                    ContextSource::Empty => LocalSource::Files(Vec::new()),
+                    ContextSource::Directory(path) => LocalSource::Directory(path),
+                };
+                let dockerfile = LocalSource::Files(vec![(
+                    String::from(DEFAULT_DOCKERFILE),
+                    Bytes::from(dockerfile),
+                )]);
+
+                // unique names, so that builds sharing a session don't serve each other's files
+                for (key, source) in [("contextkey", context), ("dockerfilekey", dockerfile)] {
+                    let dir_name = super::new_id();
+                    frontend_opts
+                        .frontend_attrs
+                        .insert(String::from(key), String::clone(&dir_name));
+                    frontend_opts.local_contexts.insert(dir_name, source);
+                }
+                frontend_opts
+                    .frontend_attrs
+                    .insert(String::from("filename"), String::from(DEFAULT_DOCKERFILE));
+                None
+            }
+            ImageBuildLoadInput::Remote(url) => {
+                frontend_opts
+                    .frontend_attrs
+                    .insert(String::from("context"), url);
+                None
+            }
+        }
+    }
+}
+
+/// The `in-toto` predicate type of SLSA provenance attestations, prefixed by the version of the
+/// SLSA specification.
+const PROVENANCE_PREDICATE_TYPE: &str = "https://slsa.dev/provenance/";
+/// The `in-toto` predicate type of SPDX SBOM attestations.
+const SBOM_PREDICATE_TYPE: &str = "https://spdx.dev/Document";
+
+/// Outcome of a successful build, f.e. returned by [`crate::grpc::driver::Build::docker_build`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ImageBuildResult {
+    /// Warnings raised by the frontend while solving the build, f.e. deprecated Dockerfile
+    /// syntax.
+    pub warnings: Vec<ImageBuildWarning>,
+    /// Attestations of the built image, read back from the archive of an export to a file with
+    /// the `oci` or `docker` exporter, when requested through
+    /// [`ImageBuildFrontendOptionsBuilder::provenance`] or [`ImageBuildFrontendOptionsBuilder::sbom`].
+    pub attestations: Vec<ImageBuildAttestation>,
+    /// Metadata of the export, f.e. the `containerimage.digest` of the built image.
+    pub exporter_response: HashMap<String, String>,
+}
+
+/// A warning raised while solving a build, f.e. by a check of the Dockerfile frontend.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageBuildWarning {
+    /// Digest of the vertex of the build that raised the warning.
+    pub vertex: String,
+    /// Severity of the warning.
+    pub level: i64,
+    /// Summary of the warning.
+    pub short: String,
+    /// Lines of detail about the warning.
+    pub detail: Vec<String>,
+    /// Link to the documentation of the warning.
+    pub url: String,
+    /// Source file the warning relates to, f.e. the `Dockerfile`.
+    pub filename: Option<String>,
+    /// Line of the source file the warning relates to, starting at 1.
+    pub line: Option<i32>,
+}
+
+impl From<moby::buildkit::v1::VertexWarning> for ImageBuildWarning {
+    fn from(warning: moby::buildkit::v1::VertexWarning) -> Self {
+        ImageBuildWarning {
+            vertex: warning.vertex,
+            level: warning.level,
+            short: String::from_utf8_lossy(&warning.short).into_owned(),
+            detail: warning
+                .detail
+                .iter()
+                .map(|line| String::from_utf8_lossy(line).into_owned())
+                .collect(),
+            url: warning.url,
+            filename: warning
+                .info
+                .map(|info| info.filename)
+                .filter(|filename| !filename.is_empty()),
+            line: warning
+                .ranges
+                .first()
+                .and_then(|range| range.start.as_ref())
+                .map(|start| start.line),
+        }
+    }
+}
+
+/// Subject of an [`ImageBuildAttestation`], i.e. the image manifest it was made for.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ImageBuildAttestationSubject {
+    /// Name of the subject, f.e. the image reference.
+    #[serde(default)]
+    pub name: String,
+    /// Digests of the subject, keyed by algorithm, f.e. `sha256`.
+    #[serde(default)]
+    pub digest: HashMap<String, String>,
+}
+
+/// An `in-toto` attestation statement attached to a built image, f.e. its SLSA provenance or its
+/// SPDX SBOM. The predicate is kept as raw JSON, to be deserialized by the caller into the
+/// types of the predicate's specification.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBuildAttestation {
+    /// Type of the predicate, f.e. `https://slsa.dev/provenance/v0.2`.
+    pub predicate_type: String,
+    /// The image manifests the attestation was made for.
+    #[serde(default)]
+    pub subject: Vec<ImageBuildAttestationSubject>,
+    /// The content of the attestation.
+    #[serde(default)]
+    pub predicate: serde_json::Value,
+}
+
+impl ImageBuildAttestation {
+    /// Whether this is a SLSA provenance attestation.
+    pub fn is_provenance(&self) -> bool {
+        self.predicate_type.starts_with(PROVENANCE_PREDICATE_TYPE)
+    }
+
+    /// Whether this is an SPDX SBOM attestation.
+    pub fn is_sbom(&self) -> bool {
+        self.predicate_type == SBOM_PREDICATE_TYPE
+    }
+}
+
+const DEFAULT_POOL_PARALLELISM: usize = 4;
+
+/// Session-level parameters of a [`BuildPool`], shared by every build in the pool.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::grpc::build::BuildPoolOptions;
+///
+/// BuildPoolOptions::builder().max_parallel(2).build();
+///
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildPoolOptions {
+    pub(crate) max_parallel: usize,
+    pub(crate) credentials: HashMap<String, DockerCredentials>,
+    pub(crate) secrets: HashMap<String, SecretSource>,
+    pub(crate) ssh: bool,
+    pub(crate) ssh_sources: HashMap<String, SshSource>,
+}
+
+impl Default for BuildPoolOptions {
+    fn default() -> Self {
+        Self {
+            max_parallel: DEFAULT_POOL_PARALLELISM,
+            credentials: HashMap::new(),
+            secrets: HashMap::new(),
+            ssh: false,
+            ssh_sources: HashMap::new(),
+        }
+    }
+}
+
+impl BuildPoolOptions {
+    /// Construct a builder for the `BuildPoolOptions`
+    pub fn builder() -> BuildPoolOptionsBuilder {
+        BuildPoolOptionsBuilder::new()
+    }
+}
+
+/// Builder for the associated [`BuildPoolOptions`] type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildPoolOptionsBuilder {
+    inner: BuildPoolOptions,
+}
+
+impl BuildPoolOptionsBuilder {
+    /// Construct a new builder
+    pub fn new() -> Self {
+        Self {
+            inner: BuildPoolOptions::default(),
+        }
+    }
+
+    /// The number of builds solved at the same time, further builds wait for a running build to
+    /// complete. Defaults to 4.
+    pub fn max_parallel(mut self, value: usize) -> Self {
+        self.inner.max_parallel = value.max(1);
+        self
+    }
+
+    /// Registry credentials for the given host, used by every build in the pool.
+    pub fn credentials(mut self, host: &str, value: &DockerCredentials) -> Self {
+        self.inner
+            .credentials
+            .insert(String::from(host), value.to_owned());
+        self
+    }
+
+    /// Set source of a single secret, available to every build in the pool.
+    pub fn set_secret(mut self, key: &str, value: &SecretSource) -> Self {
+        self.inner
+            .secrets
+            .insert(String::from(key), value.to_owned());
+        self
+    }
+
+    /// Enable sshforward to the ssh agent, for every build in the pool.
+    pub fn enable_ssh(mut self, value: bool) -> Self {
+        self.inner.ssh = value;
+        self
+    }
+
+    /// Set the ssh agent forwarded for a single ID, for every build in the pool.
+    pub fn set_ssh(mut self, id: &str, value: &SshSource) -> Self {
+        self.inner
+            .ssh_sources
+            .insert(String::from(id), value.to_owned());
+        self
+    }
+
+    /// Consume the builder and emit a [`BuildPoolOptions`]
+    pub fn build(self) -> BuildPoolOptions {
+        self.inner
+    }
+}
+
+/// Output of a single build in a [`BuildPool`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum BuildPoolOutput {
+    /// Load the image into the docker daemon under the given name, only supported by the
+    /// [`crate::grpc::driver::moby::Moby`] driver.
+    Moby(String),
+    /// Export the image with the `image` exporter, f.e. to push it to a registry. The push
+    /// retries and push progress of the output are not applied within a pool, whose builds report
+    /// their progress through [`BuildPoolJob::take_progress`].
+    Image(ImageRegistryOutput),
+}
+
+type BuildPoolProgress = Pin<Box<dyn Stream<Item = Result<StatusResponse, GrpcError>> + Send>>;
+
+/// Solves several builds concurrently over a single `Buildkit` session, sharing the session's
+/// registry credentials, secrets and ssh forwarding, with bounded parallelism.
+///
+/// Open a pool through the [`crate::grpc::driver::Pool`] trait of a driver, and close it with
+/// [`BuildPool::close`] once its builds complete. Secrets and ssh forwarding are configured for
+/// the whole pool through [`BuildPoolOptions`]; those set on the frontend options of a single
+/// build are not served. Cancelling the driver's [`SolveHandle`] aborts the pool's builds.
+///
+/// <div class="warning">
+///  Warning: Buildkit features in Bollard are currently in Developer Preview and are intended strictly for feedback purposes only.
+/// </div>
+pub struct BuildPool {
+    control_client: ControlClient<InterceptedService<Channel, DriverInterceptor>>,
+    session_id: String,
+    upload_provider: UploadProvider,
+    local_context_provider: LocalContextProvider,
+    permits: Arc<Semaphore>,
+    max_parallel: u32,
+    tear_down_handler: Box<dyn DriverTearDownHandler>,
+    solve_handle: SolveHandle,
+}
+
+impl std::fmt::Debug for BuildPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuildPool")
+            .field("session_id", &self.session_id)
+            .field("available_permits", &self.permits.available_permits())
+            .finish()
+    }
+}
+
+impl BuildPool {
+    pub(crate) async fn open(
+        driver: impl Driver,
+        options: BuildPoolOptions,
+    ) -> Result<BuildPool, GrpcError> {
+        let session_id = super::new_id();
+
+        let mut auth_provider = AuthProvider::new();
+        for (host, docker_credentials) in options.credentials {
+            auth_provider.set_docker_credentials(&host, docker_credentials);
+        }
+
+        let upload_provider = UploadProvider::new();
+        let local_context_provider = LocalContextProvider::new(HashMap::new());
+
+        let mut services = vec![
+            GrpcServer::Auth(AuthServer::new(auth_provider)),
+            GrpcServer::Upload(UploadServer::new(upload_provider.clone())),
+            GrpcServer::FileSync(FileSyncServer::new(local_context_provider.clone())),
+            GrpcServer::Secrets(SecretsServer::new(SecretProvider::new(options.secrets))),
+        ];
+
+        if options.ssh || !options.ssh_sources.is_empty() {
+            let ssh_provider = SshProvider::new(options.ssh, options.ssh_sources);
+            services.push(GrpcServer::Ssh(SshServer::new(ssh_provider)));
+        }
+
+        let tear_down_handler = driver.get_tear_down_handler();
+        let solve_handle = driver.solve_handle();
+        let control_client = driver.grpc_handle(&session_id, services).await?;
+
+        let max_parallel = options.max_parallel.clamp(1, Semaphore::MAX_PERMITS) as u32;
+
+        Ok(BuildPool {
+            control_client,
+            session_id,
+            upload_provider,
+            local_context_provider,
+            permits: Arc::new(Semaphore::new(max_parallel as usize)),
+            max_parallel,
+            tear_down_handler,
+            solve_handle,
+        })
+    }
+
+    /// Queue a build in the pool, which is solved once fewer than the pool's `max_parallel`
+    /// builds are running.
+    ///
+    /// # Returns
+    ///
+    ///  - A [`BuildPoolJob`] handle, to follow the build's progress and wait for its result.
+    pub fn solve(
+        &self,
+        output: BuildPoolOutput,
+        frontend_opts: ImageBuildFrontendOptions,
+        load_input: ImageBuildLoadInput,
+    ) -> BuildPoolJob {
+        let (exporter, exporter_attrs) = match output {
+            BuildPoolOutput::Moby(name) => (
+                "moby",
+                HashMap::from([
+                    (String::from("type"), String::from("docker")),
+                    (String::from("name"), name),
+                ]),
+            ),
+            BuildPoolOutput::Image(output) => ("image", output.into_map()),
+        };
+
+        let mut frontend_opts = frontend_opts.consume();
+        let upload = load_input.load(&self.upload_provider, &mut frontend_opts);
+        let mut local_contexts = Vec::new();
+        for (dir_name, source) in std::mem::take(&mut frontend_opts.local_contexts) {
+            self.local_context_provider.add(&dir_name, source);
+            local_contexts.push(dir_name);
+        }
+
+        let request = solve_request(
+            String::clone(&self.session_id),
+            exporter,
+            exporter_attrs,
+            frontend_opts,
+        );
+        let id = String::clone(&request.r#ref);
+
+        let (tx, rx) = mpsc::channel(32);
+        let mut control_client = self.control_client.clone();
+        let mut status_client = self.control_client.clone();
+        let permits = Arc::clone(&self.permits);
+        let upload_provider = self.upload_provider.clone();
+        let local_context_provider = self.local_context_provider.clone();
+        let solve_handle = self.solve_handle.clone();
+        let status_request = StatusRequest {
+            r#ref: String::clone(&id),
+        };
+
+        let result = tokio::spawn(async move {
+            let _permit = permits.acquire_owned().await;
+
+            let solve = async {
+                match futures_util::future::select(
+                    Box::pin(control_client.solve(request)),
+                    Box::pin(solve_handle.cancelled()),
+                )
+                .await
+                {
+                    Either::Left((res, _)) => res.map_err(GrpcError::from),
+                    Either::Right(((), _)) => Err(GrpcError::SolveCancelled),
+                }
+            };
+            let status = async move {
+                match status_client.status(status_request).await {
+                    Ok(response) => {
+                        let mut stream = response.into_inner();
+                        while let Some(status) = stream.next().await {
+                            if tx.send(status.map_err(GrpcError::from)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        let _ = tx.send(Err(err.into())).await;
+                    }
+                }
+            };
+
+            let (res, ()) = futures_util::future::join(solve, status).await;
+            debug!("pool solve res: {:#?}", res);
+
+            if let Some(upload) = upload {
+                upload_provider.remove(&upload);
+            }
+            for dir_name in local_contexts {
+                local_context_provider.remove(&dir_name);
+            }
+            res?;
+
+            Ok(())
+        });
+
+        BuildPoolJob {
+            id,
+            progress: Some(Box::pin(ReceiverStream::new(rx))),
+            result,
+        }
+    }
+
+    /// Close the pool's session, tearing down the driver once every queued build completes.
+    pub async fn close(self) -> Result<(), GrpcError> {
+        let _permits = self.permits.acquire_many(self.max_parallel).await;
+        drop(self.control_client);
+        self.tear_down_handler.tear_down().await
+    }
+}
+
+/// A build queued in a [`BuildPool`].
+pub struct BuildPoolJob {
+    id: String,
+    progress: Option<BuildPoolProgress>,
+    result: tokio::task::JoinHandle<Result<(), GrpcError>>,
+}
+
+impl std::fmt::Debug for BuildPoolJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuildPoolJob")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl BuildPoolJob {
+    /// The `Buildkit` reference of the build.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Take the stream of progress updates of the build, which completes with the build.
+    pub fn take_progress(&mut self) -> Option<BuildPoolProgress> {
+        self.progress.take()
+    }
+
+    /// Wait for the build to complete.
+    pub async fn wait(self) -> Result<(), GrpcError> {
+        match self.result.await {
+            Ok(res) => res,
+            Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+            Err(_) => Err(tonic::Status::cancelled("build was cancelled").into()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ImageBuildPlatform;
+    use super::{
+        BuildPoolOptions, ContextSource, ImageBuildEntitlement, ImageBuildFrontendOptions,
+        ImageBuildLoadInput, ImageBuildPlatform, ImageBuildProvenanceMode, ImageBuildWarning,
+        LocalSource, NamedContext, UploadProvider,
+    };
+    use bollard_buildkit_proto::moby::buildkit::v1::VertexWarning;
+    use bollard_buildkit_proto::pb::{Definition, Position, Range, SourceInfo};
+    use bytes::Bytes;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn test_imagebuildplatform_display() {
@@ -457,4 +1324,243 @@ mod tests {
         };
         assert_eq!(platform.to_string(), "linux/arm64/v8");
     }
+
+    #[test]
+    fn test_frontend_options() {
+        let ingest = ImageBuildFrontendOptions::builder().build().consume();
+        assert_eq!(ingest.frontend, "dockerfile.v0");
+        assert!(ingest.definition.is_none());
+
+        let ingest = ImageBuildFrontendOptions::builder()
+            .gateway_frontend("docker/dockerfile:1")
+            .frontend_attr("target", "release")
+            .target("debug")
+            .build()
+            .consume();
+        assert_eq!(ingest.frontend, "gateway.v0");
+        assert_eq!(
+            ingest.frontend_attrs.get("source").map(String::as_str),
+            Some("docker/dockerfile:1")
+        );
+        assert_eq!(
+            ingest.frontend_attrs.get("target").map(String::as_str),
+            Some("release")
+        );
+
+        let definition = Definition {
+            def: vec![vec![0x12, 0x00]],
+            ..Default::default()
+        };
+        let ingest = ImageBuildFrontendOptions::builder()
+            .definition(&definition)
+            .frontend_input("base", &definition)
+            .build()
+            .consume();
+        assert_eq!(ingest.frontend, "");
+        assert_eq!(ingest.definition, Some(definition.clone()));
+        assert_eq!(ingest.frontend_inputs.get("base"), Some(&definition));
+    }
+
+    #[test]
+    fn test_frontend_attributes() {
+        let ingest = ImageBuildFrontendOptions::builder()
+            .buildarg("VERSION", "1.2")
+            .label("org.opencontainers.image.title", "bollard")
+            .target("release")
+            .no_cache_filter(&["deps", "test"])
+            .no_cache_filter(&["deps"])
+            .build()
+            .consume();
+
+        let attr = |key: &str| ingest.frontend_attrs.get(key).map(String::as_str);
+        assert_eq!(attr("build-arg:VERSION"), Some("1.2"));
+        assert_eq!(
+            attr("label:org.opencontainers.image.title"),
+            Some("bollard")
+        );
+        assert_eq!(attr("target"), Some("release"));
+        assert_eq!(attr("no-cache"), Some("deps,test"));
+
+        let ingest = ImageBuildFrontendOptions::builder()
+            .no_cache_filter(&["deps"])
+            .nocache(true)
+            .build()
+            .consume();
+        assert_eq!(
+            ingest.frontend_attrs.get("no-cache").map(String::as_str),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn test_named_contexts() {
+        let ingest = ImageBuildFrontendOptions::builder()
+            .named_context("base", NamedContext::image("alpine:3"))
+            .named_context("src", NamedContext::local("../src"))
+            .named_context(
+                "repo",
+                NamedContext {
+                    path: String::from("https://github.com/moby/buildkit.git"),
+                },
+            )
+            .named_context(
+                "stage",
+                NamedContext {
+                    path: String::from("input:stage"),
+                },
+            )
+            .build()
+            .consume();
+
+        let attr = |key: &str| ingest.frontend_attrs.get(key).map(String::as_str);
+        assert_eq!(
+            attr("frontend.caps"),
+            Some("moby.buildkit.frontend.contexts+forward")
+        );
+        assert_eq!(attr("context:base"), Some("docker-image://alpine:3"));
+        let src = attr("context:src").and_then(|src| src.strip_prefix("local:"));
+        assert_eq!(
+            src.and_then(|src| ingest.local_contexts.get(src)),
+            Some(&LocalSource::Directory(PathBuf::from("../src")))
+        );
+        assert_eq!(
+            attr("context:repo"),
+            Some("https://github.com/moby/buildkit.git")
+        );
+        assert_eq!(attr("context:stage"), Some("input:stage"));
+        assert_eq!(ingest.local_contexts.len(), 1);
+    }
+
+    #[test]
+    fn test_load_input() {
+        let upload_provider = UploadProvider::new();
+
+        let mut ingest = ImageBuildFrontendOptions::builder().build().consume();
+        let upload = ImageBuildLoadInput::Upload(Bytes::from_static(b"tarball"))
+            .load(&upload_provider, &mut ingest);
+        assert_eq!(
+            ingest.frontend_attrs.get("context"),
+            Some(&upload.clone().unwrap())
+        );
+        assert!(ingest.local_contexts.is_empty());
+        upload_provider.remove(&upload.unwrap());
+        assert!(upload_provider.store.lock().unwrap().is_empty());
+
+        let mut ingest = ImageBuildFrontendOptions::builder()
+            .dockerfile(Path::new("other/Dockerfile"))
+            .build()
+            .consume();
+        let upload = ImageBuildLoadInput::DockerfileContent {
+            dockerfile: String::from("FROM alpine"),
+            context: ContextSource::Directory(PathBuf::from("src")),
+        }
+        .load(&upload_provider, &mut ingest);
+        assert!(upload.is_none());
+        assert!(!ingest.frontend_attrs.contains_key("context"));
+        assert_eq!(
+            ingest.frontend_attrs.get("filename").map(String::as_str),
+            Some("Dockerfile")
+        );
+
+        let source = |key: &str| &ingest.local_contexts[&ingest.frontend_attrs[key]];
+        assert_eq!(
+            source("contextkey"),
+            &LocalSource::Directory(PathBuf::from("src"))
+        );
+        assert_eq!(
+            source("dockerfilekey"),
+            &LocalSource::Files(vec![(
+                String::from("Dockerfile"),
+                Bytes::from_static(b"FROM alpine")
+            )])
+        );
+
+        let mut ingest = ImageBuildFrontendOptions::builder()
+            .git_auth_token("github.com", "token")
+            .build()
+            .consume();
+        let upload = ImageBuildLoadInput::Remote(String::from(
+            "https://github.com/moby/buildkit.git#v0.13.0:examples",
+        ))
+        .load(&upload_provider, &mut ingest);
+        assert!(upload.is_none());
+        assert_eq!(
+            ingest.frontend_attrs.get("context").map(String::as_str),
+            Some("https://github.com/moby/buildkit.git#v0.13.0:examples")
+        );
+        assert!(ingest
+            .secret_sources
+            .contains_key("GIT_AUTH_TOKEN.github.com"));
+    }
+
+    #[test]
+    fn test_entitlements() {
+        let ingest = ImageBuildFrontendOptions::builder()
+            .entitlement(&ImageBuildEntitlement::NetworkHost)
+            .entitlement(&ImageBuildEntitlement::SecurityInsecure)
+            .entitlement(&ImageBuildEntitlement::NetworkHost)
+            .build()
+            .consume();
+        assert_eq!(
+            ingest.entitlements,
+            vec![
+                String::from("network.host"),
+                String::from("security.insecure")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attestations_and_warnings() {
+        let ingest = ImageBuildFrontendOptions::builder()
+            .provenance(&ImageBuildProvenanceMode::Max)
+            .sbom(true)
+            .build()
+            .consume();
+        assert_eq!(
+            ingest
+                .frontend_attrs
+                .get("attest:provenance")
+                .map(String::as_str),
+            Some("mode=max")
+        );
+        assert_eq!(
+            ingest.frontend_attrs.get("attest:sbom").map(String::as_str),
+            Some("")
+        );
+
+        let warning = ImageBuildWarning::from(VertexWarning {
+            vertex: String::from("sha256:abcd"),
+            level: 1,
+            short: b"FromAsCasing: 'as' and 'FROM' keywords' casing do not match".to_vec(),
+            detail: vec![b"Use consistent casing".to_vec()],
+            url: String::from("https://docs.docker.com/go/dockerfile/rule/from-as-casing/"),
+            info: Some(SourceInfo {
+                filename: String::from("Dockerfile"),
+                ..Default::default()
+            }),
+            ranges: vec![Range {
+                start: Some(Position {
+                    line: 2,
+                    character: 0,
+                }),
+                end: None,
+            }],
+        });
+        assert_eq!(warning.detail, ["Use consistent casing"]);
+        assert_eq!(warning.filename.as_deref(), Some("Dockerfile"));
+        assert_eq!(warning.line, Some(2));
+    }
+
+    #[test]
+    fn test_build_pool_options() {
+        assert_eq!(BuildPoolOptions::builder().build().max_parallel, 4);
+        assert_eq!(
+            BuildPoolOptions::builder()
+                .max_parallel(0)
+                .build()
+                .max_parallel,
+            1
+        );
+    }
 }
@@ -0,0 +1,301 @@
+#![cfg(feature = "buildkit")]
+
+use std::time::Duration;
+
+use bollard_buildkit_proto::google::protobuf::Timestamp;
+use bollard_buildkit_proto::moby::buildkit::v1::{DiskUsageRequest, PruneRequest, UsageRecord};
+use futures_util::TryStreamExt;
+
+use super::driver::{moby::Moby, Driver};
+use super::error::GrpcError;
+use crate::Docker;
+
+/// A record of the `Buildkit` cache, as listed by [`Docker::buildkit_du`] and
+/// [`Docker::buildkit_prune`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct BuildkitUsageRecord {
+    /// The ID of the record.
+    pub id: String,
+    /// Whether the record is mutable, f.e. a local source or a cache mount.
+    pub mutable: bool,
+    /// Whether the record is used by a running build.
+    pub in_use: bool,
+    /// The size of the record in bytes.
+    pub size: i64,
+    /// The IDs of the parent records.
+    pub parents: Vec<String>,
+    /// When the record was created.
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the record was last used.
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The number of times the record was used.
+    pub usage_count: i64,
+    /// Describes the content of the record, f.e. the command of a build step.
+    pub description: String,
+    /// The type of the record, f.e. `regular`, `source.local` or `exec.cachemount`.
+    pub record_type: String,
+    /// Whether the record shares its data with other records.
+    pub shared: bool,
+}
+
+fn date_time(timestamp: Option<Timestamp>) -> Option<chrono::DateTime<chrono::Utc>> {
+    timestamp.and_then(|timestamp| {
+        chrono::DateTime::from_timestamp(timestamp.seconds, u32::try_from(timestamp.nanos).ok()?)
+    })
+}
+
+impl From<UsageRecord> for BuildkitUsageRecord {
+    #[allow(deprecated)]
+    fn from(record: UsageRecord) -> Self {
+        let mut parents = record.parents;
+        // buildkit versions before v0.10 only report a single parent
+        if parents.is_empty() && !record.parent.is_empty() {
+            parents.push(record.parent);
+        }
+        BuildkitUsageRecord {
+            id: record.id,
+            mutable: record.mutable,
+            in_use: record.in_use,
+            size: record.size,
+            parents,
+            created_at: date_time(record.created_at),
+            last_used_at: date_time(record.last_used_at),
+            usage_count: record.usage_count,
+            description: record.description,
+            record_type: record.record_type,
+            shared: record.shared,
+        }
+    }
+}
+
+/// Options of [`Docker::buildkit_prune`], removing the unused records of the `Buildkit` cache.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::grpc::cache::BuildkitPruneOptions;
+///
+/// use std::time::Duration;
+///
+/// BuildkitPruneOptions::builder()
+///     .filter("type==exec.cachemount")
+///     .keep_duration(Duration::from_secs(24 * 60 * 60))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildkitPruneOptions {
+    pub(crate) filters: Vec<String>,
+    pub(crate) all: bool,
+    pub(crate) keep_duration: Option<Duration>,
+    pub(crate) reserved_space: i64,
+    pub(crate) max_used_space: i64,
+    pub(crate) min_free_space: i64,
+}
+
+impl BuildkitPruneOptions {
+    /// Construct a builder for the `BuildkitPruneOptions`
+    pub fn builder() -> BuildkitPruneOptionsBuilder {
+        BuildkitPruneOptionsBuilder::new()
+    }
+
+    pub(crate) fn into_request(self) -> PruneRequest {
+        PruneRequest {
+            filter: self.filters,
+            all: self.all,
+            keep_duration: self
+                .keep_duration
+                .map(|duration| i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX))
+                .unwrap_or_default(),
+            reserved_space: self.reserved_space,
+            max_used_space: self.max_used_space,
+            min_free_space: self.min_free_space,
+        }
+    }
+}
+
+/// Builder for the associated [`BuildkitPruneOptions`] type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildkitPruneOptionsBuilder {
+    inner: BuildkitPruneOptions,
+}
+
+impl BuildkitPruneOptionsBuilder {
+    /// Construct a new builder
+    pub fn new() -> Self {
+        Self {
+            inner: BuildkitPruneOptions::default(),
+        }
+    }
+
+    /// Only prune the records matching a `Buildkit` filter, f.e. `type==regular`, `id==<id>` or
+    /// `description~=apt-get`. Several filters all need to match.
+    pub fn filter(mut self, value: &str) -> Self {
+        self.inner.filters.push(String::from(value));
+        self
+    }
+
+    /// Prune the internal and frontend records as well, rather than only the unused records of
+    /// the build cache.
+    pub fn all(mut self, value: bool) -> Self {
+        self.inner.all = value;
+        self
+    }
+
+    /// Keep the records used within the given duration.
+    pub fn keep_duration(mut self, value: Duration) -> Self {
+        self.inner.keep_duration = Some(value);
+        self
+    }
+
+    /// Amount of disk space in bytes always kept for the cache.
+    pub fn reserved_space(mut self, value: i64) -> Self {
+        self.inner.reserved_space = value;
+        self
+    }
+
+    /// Maximum amount of disk space in bytes used by the cache.
+    pub fn max_used_space(mut self, value: i64) -> Self {
+        self.inner.max_used_space = value;
+        self
+    }
+
+    /// Amount of disk space in bytes kept free on the disk of the cache.
+    pub fn min_free_space(mut self, value: i64) -> Self {
+        self.inner.min_free_space = value;
+        self
+    }
+
+    /// Consume the builder and emit a [`BuildkitPruneOptions`]
+    pub fn build(self) -> BuildkitPruneOptions {
+        self.inner
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Buildkit Disk Usage
+    ///
+    /// List the records of the `Buildkit` cache of the docker daemon, through a session of the
+    /// [`Moby`] driver. Unlike the [List Build Cache API](Docker::list_build_cache()), the records
+    /// include their parents, and the time of their creation.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Buildkit Usage Record](BuildkitUsageRecord), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.buildkit_du();
+    /// ```
+    pub async fn buildkit_du(&self) -> Result<Vec<BuildkitUsageRecord>, GrpcError> {
+        let driver = Moby::new(self);
+        let handle = driver.solve_handle();
+        let mut control_client = driver.grpc_handle(&super::new_id(), vec![]).await?;
+
+        let res = control_client
+            .disk_usage(DiskUsageRequest { filter: vec![] })
+            .await;
+        handle.cancel();
+
+        Ok(res?
+            .into_inner()
+            .record
+            .into_iter()
+            .map(BuildkitUsageRecord::from)
+            .collect())
+    }
+
+    /// ---
+    ///
+    /// # Buildkit Prune
+    ///
+    /// Remove the unused records of the `Buildkit` cache of the docker daemon, through a session
+    /// of the [`Moby`] driver.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Buildkit Prune Options](BuildkitPruneOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of the removed [Buildkit Usage Records](BuildkitUsageRecord), wrapped in a
+    ///    Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::grpc::cache::BuildkitPruneOptions;
+    ///
+    /// docker.buildkit_prune(BuildkitPruneOptions::builder().all(true).build());
+    /// ```
+    pub async fn buildkit_prune(
+        &self,
+        options: BuildkitPruneOptions,
+    ) -> Result<Vec<BuildkitUsageRecord>, GrpcError> {
+        let driver = Moby::new(self);
+        let handle = driver.solve_handle();
+        let mut control_client = driver.grpc_handle(&super::new_id(), vec![]).await?;
+
+        let res = match control_client.prune(options.into_request()).await {
+            Ok(response) => response.into_inner().try_collect::<Vec<_>>().await,
+            Err(status) => Err(status),
+        };
+        handle.cancel();
+
+        Ok(res?.into_iter().map(BuildkitUsageRecord::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildkitPruneOptions, BuildkitUsageRecord};
+    use bollard_buildkit_proto::google::protobuf::Timestamp;
+    use bollard_buildkit_proto::moby::buildkit::v1::UsageRecord;
+    use std::time::Duration;
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_usage_record() {
+        let record = BuildkitUsageRecord::from(UsageRecord {
+            id: String::from("k8p4qj1f3ewn7a9plzx5m0y2c"),
+            size: 4096,
+            parent: String::from("vz0r7hme8x2lwq9dk3sb5n1ty"),
+            created_at: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 500,
+            }),
+            record_type: String::from("regular"),
+            ..Default::default()
+        });
+
+        assert_eq!(record.parents, ["vz0r7hme8x2lwq9dk3sb5n1ty"]);
+        assert_eq!(
+            record.created_at.map(|created_at| created_at.timestamp()),
+            Some(1_700_000_000)
+        );
+        assert_eq!(record.last_used_at, None);
+        assert_eq!(record.record_type, "regular");
+    }
+
+    #[test]
+    fn test_prune_request() {
+        let request = BuildkitPruneOptions::builder()
+            .filter("type==exec.cachemount")
+            .keep_duration(Duration::from_secs(60))
+            .max_used_space(1024)
+            .build()
+            .into_request();
+
+        assert_eq!(request.filter, ["type==exec.cachemount"]);
+        assert_eq!(request.keep_duration, 60_000_000_000);
+        assert_eq!(request.max_used_space, 1024);
+        assert!(!request.all);
+    }
+}
@@ -7,11 +7,10 @@ use std::{
     time::Duration,
 };
 
-use bollard_buildkit_proto::{
-    health::health_server::HealthServer, moby::buildkit::v1::control_client::ControlClient,
-};
+use bollard_buildkit_proto::moby::buildkit::v1::control_client::ControlClient;
 use bollard_stubs::models::{
-    ExecInspectResponse, HostConfig, Mount, MountTypeEnum, SystemInfoCgroupDriverEnum,
+    ContainerInspectResponse, ContainerSummary, ExecInspectResponse, HostConfig, Mount, MountPoint,
+    MountTypeEnum, SystemInfoCgroupDriverEnum,
 };
 use bytes::BytesMut;
 use futures_core::Future;
@@ -21,37 +20,38 @@ use http::{
     request::Builder,
     Method,
 };
-use log::{debug, error, info, trace};
+use log::{debug, info};
 use tonic::transport::Endpoint;
 use tonic::{codegen::InterceptedService, transport::Channel};
 use tower_service::Service;
 
 use crate::{
     auth::DockerCredentials,
-    container::{Config, CreateContainerOptions},
-    exec::{CreateExecOptions, StartExecOptions, StartExecResults},
-    grpc::{
-        build::{ImageBuildFrontendOptions, ImageBuildLoadInput},
-        error::GrpcError,
+    container::{
+        Config, CreateContainerOptions, InspectContainerOptions, ListContainersOptions,
+        RemoveContainerOptions,
     },
+    exec::{CreateExecOptions, StartExecOptions, StartExecResults},
     grpc::{
-        io::{
-            into_async_read::IntoAsyncRead, reader_stream::ReaderStream, GrpcFramedTransport,
-            GrpcTransport,
+        build::{
+            ImageBuildEntitlement, ImageBuildFrontendOptions, ImageBuildLoadInput, ImageBuildResult,
         },
-        registry::ImageRegistryOutput,
-        GrpcServer, HealthServerImpl,
+        error::GrpcError,
     },
+    grpc::{io::GrpcFramedTransport, registry::ImageRegistryOutput, GrpcServer},
     image::CreateImageOptions,
+    volume::RemoveVolumeOptions,
     Docker,
 };
 
-use super::{DriverInterceptor, ImageExporterEnum};
+use super::{DriverInterceptor, ImageExporterEnum, SolveHandle};
 
 /// The default `Buildkit` image to use for the [`DockerContainer] driver.
 pub const DEFAULT_IMAGE: &str = "moby/buildkit:master";
 const DEFAULT_STATE_DIR: &str = "/var/lib/buildkit";
-const DUPLEX_BUF_SIZE: usize = 8 * 1024;
+/// Label set on `Buildkit` containers created by the [`DockerContainer`] driver, keyed on the
+/// builder name. Used to [list](list_builders) the builders on a host.
+pub const BUILDER_LABEL: &str = "com.github.fussybeaver.bollard.buildkit-builder";
 
 impl Service<tonic::transport::Uri> for DockerContainer {
     type Response = GrpcFramedTransport;
@@ -91,7 +91,7 @@ impl Service<tonic::transport::Uri> for DockerContainer {
                     .header(CONNECTION, "Upgrade")
                     .header(UPGRADE, "tcp"),
                 None::<String>,
-                Docker::serialize_payload(Some(StartExecOptions {
+                client.serialize_payload(Some(StartExecOptions {
                     output_capacity: Some(capacity),
                     ..Default::default()
                 })),
@@ -153,11 +153,35 @@ impl DockerContainerBuilder {
                 cgroup_parent: None,
                 env: vec![],
                 args: vec![],
+                state_volume: None,
                 tear_down: true,
+                solve_handle: SolveHandle::new(),
             },
         }
     }
 
+    /// Use a named builder, reusing its container if it already exists, so that its build cache
+    /// is shared across builds. Combine with [`tear_down`](Self::tear_down()) to keep the
+    /// builder running between builds.
+    pub fn name(&mut self, name: &str) -> &mut DockerContainerBuilder {
+        self.inner.name = String::from(name);
+        self
+    }
+
+    /// The volume mounted as the `Buildkit` state directory, holding the build cache. The
+    /// default is a volume named after the builder, with a `_state` suffix.
+    pub fn state_volume(&mut self, volume: &str) -> &mut DockerContainerBuilder {
+        self.inner.state_volume = Some(String::from(volume));
+        self
+    }
+
+    /// Stop the `Buildkit` container after solving a build, the default. Disable to keep a
+    /// long-lived builder running, and remove it with [`remove_builder`] once done.
+    pub fn tear_down(&mut self, tear_down: bool) -> &mut DockerContainerBuilder {
+        self.inner.tear_down = tear_down;
+        self
+    }
+
     /// Consume this builder to construct a [`DockerContainer`]
     pub async fn bootstrap(mut self) -> Result<DockerContainer, GrpcError> {
         debug!("booting buildkit");
@@ -166,16 +190,15 @@ impl DockerContainerBuilder {
             self.network("host");
         }
 
-        if let Err(crate::errors::Error::DockerResponseServerError {
-            status_code: 404,
-            message: _,
-        }) = self
+        if let Err(e) = self
             .inner
             .docker
             .inspect_container(&self.inner.name, None)
             .await
         {
-            self.inner.create().await?
+            if e.kind() == Some(crate::errors::ErrorKind::NotFound) {
+                self.inner.create().await?
+            }
         };
 
         debug!("starting container {}", &self.inner.name);
@@ -217,6 +240,19 @@ impl DockerContainerBuilder {
         self
     }
 
+    /// Allow builds to request an insecure entitlement, e.g.
+    /// [`ImageBuildEntitlement::SecurityInsecure`] for `RUN --security=insecure`. The
+    /// `network.host` entitlement is already allowed when the container uses host networking.
+    pub fn allow_insecure_entitlement(
+        &mut self,
+        entitlement: &ImageBuildEntitlement,
+    ) -> &mut DockerContainerBuilder {
+        self.inner
+            .args
+            .push(format!("--allow-insecure-entitlement={entitlement}"));
+        self
+    }
+
     /// Set a additional run command arguments to the `Buildkit` docker execution.
     pub fn arg(&mut self, arg: &str) -> &mut DockerContainerBuilder {
         self.inner.args.push(String::from(arg));
@@ -241,7 +277,9 @@ pub struct DockerContainer {
     cgroup_parent: Option<String>,
     env: Vec<String>,
     args: Vec<String>,
+    state_volume: Option<String>,
     tear_down: bool,
+    solve_handle: SolveHandle,
 }
 
 impl super::Driver for DockerContainer {
@@ -250,54 +288,17 @@ impl super::Driver for DockerContainer {
         session_id: &str,
         services: Vec<GrpcServer>,
     ) -> Result<ControlClient<InterceptedService<Channel, DriverInterceptor>>, GrpcError> {
+        let cancellation = self.docker.cancellation.clone();
+        let handle = self.solve_handle.clone();
         let channel = Endpoint::try_from("http://[::]:50051")?
             .connect_with_connector(self)
             .await?;
 
-        let metadata_grpc_method: Vec<String> = services.iter().flat_map(|s| s.names()).collect();
-
-        let interceptor = DriverInterceptor {
-            session_id: String::from(session_id),
-            metadata_grpc_method,
-        };
-
-        let mut control_client = ControlClient::with_interceptor(channel, interceptor);
-
-        let (asyncwriter, asyncreader) = tokio::io::duplex(DUPLEX_BUF_SIZE);
-        let streamreader = ReaderStream::new(asyncreader);
-        let stream = control_client.session(streamreader).await?;
-        let stream = stream
-            .into_inner()
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
-
-        let asyncreader = IntoAsyncRead::new(stream);
-        let transport = GrpcTransport {
-            read: Box::pin(asyncreader),
-            write: Box::pin(asyncwriter),
-        };
-
-        tokio::spawn(async {
-            let health = HealthServer::new(HealthServerImpl::new());
-            let mut builder = tonic::transport::Server::builder();
-            let mut router = builder.add_service(health);
-            for service in services {
-                router = service.append(router);
-            }
-            trace!("router: {:#?}", router);
-            if let Err(e) = router
-                .serve_with_incoming(futures_util::stream::iter(vec![Ok::<
-                    _,
-                    tonic::transport::Error,
-                >(
-                    transport
-                )]))
-                .await
-            {
-                error!("Failed to serve grpc connection: {}", e)
-            }
-        });
+        super::control_session(channel, session_id, services, cancellation, handle).await
+    }
 
-        Ok(control_client)
+    fn solve_handle(&self) -> SolveHandle {
+        DockerContainer::solve_handle(self)
     }
 
     fn get_tear_down_handler(&self) -> Box<dyn super::DriverTearDownHandler> {
@@ -307,7 +308,7 @@ impl super::Driver for DockerContainer {
                 docker: Docker::clone(&self.docker),
             })
         } else {
-            Box::new(NoopTearDownHandler {})
+            Box::new(super::NoopTearDownHandler {})
         }
     }
 }
@@ -319,6 +320,11 @@ impl<'a> DockerContainer {
         &self.name
     }
 
+    /// A [`SolveHandle`] to cancel the builds of this driver, which consume it.
+    pub fn solve_handle(&self) -> SolveHandle {
+        self.solve_handle.clone()
+    }
+
     async fn create(&self) -> Result<(), GrpcError> {
         let image_name = if let Some(image) = &self.image {
             image
@@ -377,7 +383,11 @@ impl<'a> DockerContainer {
             privileged: Some(true),
             mounts: Some(vec![Mount {
                 typ: Some(MountTypeEnum::VOLUME),
-                source: Some(format!("{}_state", &self.name)),
+                source: Some(
+                    self.state_volume
+                        .clone()
+                        .unwrap_or_else(|| format!("{}_state", &self.name)),
+                ),
                 target: Some(String::from(DEFAULT_STATE_DIR)),
                 ..Default::default()
             }]),
@@ -390,6 +400,10 @@ impl<'a> DockerContainer {
 
         let container_config = Config {
             image: Some(String::from(image_name)),
+            labels: Some(HashMap::from([(
+                String::from(BUILDER_LABEL),
+                String::from(&self.name),
+            )])),
             env: Some(Vec::clone(&self.env)),
             host_config: Some(host_config),
             cmd: Some(Vec::clone(&self.args)),
@@ -469,6 +483,117 @@ impl<'a> DockerContainer {
     }
 }
 
+/// A `Buildkit` builder container, as reported by [`list_builders`] and [`inspect_builder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuilderInfo {
+    /// The builder's name, which is also its container name.
+    pub name: String,
+    /// The `Buildkit` image the builder runs.
+    pub image: Option<String>,
+    /// Whether the builder container is running.
+    pub running: bool,
+    /// The network mode of the builder container.
+    pub network_mode: Option<String>,
+    /// The volume holding the builder's state and build cache.
+    pub state_volume: Option<String>,
+}
+
+fn state_volume(mounts: Option<&Vec<MountPoint>>) -> Option<String> {
+    mounts?
+        .iter()
+        .find(|mount| mount.destination.as_deref() == Some(DEFAULT_STATE_DIR))
+        .and_then(|mount| mount.name.clone())
+}
+
+impl From<ContainerSummary> for BuilderInfo {
+    fn from(summary: ContainerSummary) -> Self {
+        BuilderInfo {
+            name: summary
+                .names
+                .as_ref()
+                .and_then(|names| names.first())
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_default(),
+            image: summary.image,
+            running: summary.state.as_deref() == Some("running"),
+            network_mode: summary.host_config.and_then(|config| config.network_mode),
+            state_volume: state_volume(summary.mounts.as_ref()),
+        }
+    }
+}
+
+impl From<ContainerInspectResponse> for BuilderInfo {
+    fn from(response: ContainerInspectResponse) -> Self {
+        BuilderInfo {
+            name: response
+                .name
+                .as_deref()
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_default(),
+            image: response.config.and_then(|config| config.image),
+            running: response
+                .state
+                .and_then(|state| state.running)
+                .unwrap_or_default(),
+            network_mode: response.host_config.and_then(|config| config.network_mode),
+            state_volume: state_volume(response.mounts.as_ref()),
+        }
+    }
+}
+
+/// List the `Buildkit` builder containers created by the [`DockerContainer`] driver, whether
+/// running or stopped.
+pub async fn list_builders(docker: &Docker) -> Result<Vec<BuilderInfo>, GrpcError> {
+    let containers = docker
+        .list_containers(Some(ListContainersOptions {
+            all: true,
+            filters: HashMap::from([("label", vec![BUILDER_LABEL])]),
+            ..Default::default()
+        }))
+        .await?;
+
+    Ok(containers.into_iter().map(BuilderInfo::from).collect())
+}
+
+/// Inspect a `Buildkit` builder container by name.
+pub async fn inspect_builder(docker: &Docker, name: &str) -> Result<BuilderInfo, GrpcError> {
+    let response = docker
+        .inspect_container(name, None::<InspectContainerOptions>)
+        .await?;
+
+    Ok(BuilderInfo::from(response))
+}
+
+/// Remove a `Buildkit` builder container by name, stopping it if it is running. If
+/// `remove_state` is set, the volume holding its build cache is removed too.
+pub async fn remove_builder(
+    docker: &Docker,
+    name: &str,
+    remove_state: bool,
+) -> Result<(), GrpcError> {
+    let info = inspect_builder(docker, name).await?;
+
+    docker
+        .remove_container(
+            name,
+            Some(RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    if remove_state {
+        if let Some(volume) = info.state_volume {
+            docker
+                .remove_volume(&volume, None::<RemoveVolumeOptions>)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
 struct DockerContainerTearDownHandler {
     name: String,
     docker: Docker,
@@ -488,14 +613,6 @@ impl super::DriverTearDownHandler for DockerContainerTearDownHandler {
     }
 }
 
-struct NoopTearDownHandler {}
-
-impl super::DriverTearDownHandler for NoopTearDownHandler {
-    fn tear_down(&self) -> Pin<Box<dyn futures_core::Future<Output = Result<(), GrpcError>>>> {
-        Box::pin(futures_util::future::ok(()))
-    }
-}
-
 impl super::Export for DockerContainer {
     async fn export(
         self,
@@ -503,18 +620,18 @@ impl super::Export for DockerContainer {
         frontend_opts: ImageBuildFrontendOptions,
         load_input: ImageBuildLoadInput,
         credentials: Option<HashMap<&str, DockerCredentials>>,
-    ) -> Result<(), GrpcError> {
-        let (exporter, exporter_attrs, path) = match exporter_request {
-            ImageExporterEnum::OCI(request) => ("oci", request.output.into_map(), request.path),
+    ) -> Result<ImageBuildResult, GrpcError> {
+        let (exporter, exporter_attrs, dest) = match exporter_request {
+            ImageExporterEnum::OCI(request) => ("oci", request.output.into_map(), request.dest),
             ImageExporterEnum::Docker(request) => {
-                ("docker", request.output.into_map(), request.path)
+                ("docker", request.output.into_map(), request.dest)
             }
         };
         super::solve(
             self,
             exporter,
             exporter_attrs,
-            Some(path),
+            super::SolveTarget::Dest(dest),
             frontend_opts,
             load_input,
             credentials,
@@ -530,14 +647,15 @@ impl super::Image for DockerContainer {
         frontend_opts: ImageBuildFrontendOptions,
         load_input: ImageBuildLoadInput,
         credentials: Option<HashMap<&str, DockerCredentials>>,
-    ) -> Result<(), GrpcError> {
+    ) -> Result<ImageBuildResult, GrpcError> {
         let exporter = "image";
+        let push = output.push_options();
         let exporter_attrs = output.into_map();
         super::solve(
             self,
             exporter,
             exporter_attrs,
-            None,
+            super::SolveTarget::Push(push),
             frontend_opts,
             load_input,
             credentials,
@@ -545,3 +663,12 @@ impl super::Image for DockerContainer {
         .await
     }
 }
+
+impl super::Pool for DockerContainer {
+    async fn pool(
+        self,
+        options: crate::grpc::build::BuildPoolOptions,
+    ) -> Result<crate::grpc::build::BuildPool, GrpcError> {
+        crate::grpc::build::BuildPool::open(self, options).await
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::Arc;
 
 use bollard_buildkit_proto::{health, moby::buildkit::v1::control_client::ControlClient};
 use bytes::Bytes;
@@ -14,20 +15,22 @@ use tonic::transport::{Channel, Endpoint};
 
 use crate::auth::DockerCredentials;
 use crate::docker::BodyType;
-use crate::grpc::build::{ImageBuildFrontendOptions, ImageBuildLoadInput};
+use crate::grpc::build::{ImageBuildFrontendOptions, ImageBuildLoadInput, ImageBuildResult};
+use crate::grpc::export::ImageExporterOutput;
 use crate::{
     grpc::error::GrpcError,
     grpc::{io::GrpcTransport, GrpcClient, GrpcServer, HealthServerImpl},
     Docker,
 };
 
-use super::{Driver, DriverInterceptor};
+use super::{Driver, DriverInterceptor, SolveHandle};
 
 /// The Moby driver handles a GRPC connection with an upgraded `/session` and `/grpc` endpoints in
 /// Docker itself.
 #[derive(Debug)]
 pub struct Moby {
     pub(crate) docker: Docker,
+    solve_handle: SolveHandle,
 }
 
 impl Moby {
@@ -35,8 +38,14 @@ impl Moby {
     pub fn new(docker: &Docker) -> Self {
         Self {
             docker: Docker::clone(docker),
+            solve_handle: SolveHandle::new(),
         }
     }
+
+    /// A [`SolveHandle`] to cancel the builds of this driver, which consume it.
+    pub fn solve_handle(&self) -> SolveHandle {
+        self.solve_handle.clone()
+    }
 }
 
 impl Driver for Moby {
@@ -92,8 +101,12 @@ impl Driver for Moby {
             write: input,
         };
 
+        let cancellation = self.docker.cancellation.clone();
+        let handle = self.solve_handle;
         tokio::spawn(async {
-            let health = health::health_server::HealthServer::new(HealthServerImpl::new());
+            let health = health::health_server::HealthServer::new(HealthServerImpl::new(
+                Arc::clone(&handle.health),
+            ));
             let mut builder = tonic::transport::Server::builder();
             let mut router = builder.add_service(health);
             for service in services {
@@ -101,12 +114,10 @@ impl Driver for Moby {
             }
             trace!("router: {:#?}", router);
             if let Err(e) = router
-                .serve_with_incoming(futures_util::stream::iter(vec![Ok::<
-                    _,
-                    tonic::transport::Error,
-                >(
-                    transport
-                )]))
+                .serve_with_incoming_shutdown(
+                    futures_util::stream::iter(vec![Ok::<_, tonic::transport::Error>(transport)]),
+                    super::shutdown_signal(cancellation, handle),
+                )
                 .await
             {
                 error!("Failed to serve grpc connection: {}", e)
@@ -119,6 +130,10 @@ impl Driver for Moby {
     fn get_tear_down_handler(&self) -> Box<dyn super::DriverTearDownHandler> {
         Box::new(MobyTearDownHandler {})
     }
+
+    fn solve_handle(&self) -> SolveHandle {
+        Moby::solve_handle(self)
+    }
 }
 
 struct MobyTearDownHandler {}
@@ -136,7 +151,7 @@ impl super::Build for Moby {
         frontend_opts: ImageBuildFrontendOptions,
         load_input: ImageBuildLoadInput,
         credentials: Option<HashMap<&str, DockerCredentials>>,
-    ) -> Result<(), GrpcError> {
+    ) -> Result<ImageBuildResult, GrpcError> {
         let mut exporter_attrs = HashMap::new();
         exporter_attrs.insert(String::from("type"), String::from("docker"));
         exporter_attrs.insert(String::from("name"), String::from(name));
@@ -144,7 +159,59 @@ impl super::Build for Moby {
             self,
             "moby",
             exporter_attrs,
-            None,
+            super::SolveTarget::Store,
+            frontend_opts,
+            load_input,
+            credentials,
+        )
+        .await
+    }
+}
+
+impl Moby {
+    /// Build an image and load it into the docker daemon, like [`Build::docker_build`], with the
+    /// exporter options of the given output, f.e. to unpack a `zstd` compressed image with OCI
+    /// media types when the daemon uses the containerd image store.
+    ///
+    /// [`Build::docker_build`]: super::Build::docker_build
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::grpc::build::{ImageBuildFrontendOptions, ImageBuildOutputCompression};
+    /// use bollard::grpc::driver::moby::Moby;
+    /// use bollard::grpc::export::ImageExporterOutput;
+    ///
+    /// let output = ImageExporterOutput::builder("my-image:latest")
+    ///     .compression(&ImageBuildOutputCompression::Zstd)
+    ///     .oci_mediatypes(true)
+    ///     .unpack(true)
+    ///     .build();
+    ///
+    /// # let load_input = bollard::grpc::build::ImageBuildLoadInput::Upload(bytes::Bytes::new());
+    /// Moby::new(&docker).docker_build_with_output(
+    ///     output,
+    ///     ImageBuildFrontendOptions::builder().build(),
+    ///     load_input,
+    ///     None,
+    /// );
+    /// ```
+    pub async fn docker_build_with_output(
+        self,
+        output: ImageExporterOutput,
+        frontend_opts: ImageBuildFrontendOptions,
+        load_input: ImageBuildLoadInput,
+        credentials: Option<HashMap<&str, DockerCredentials>>,
+    ) -> Result<ImageBuildResult, GrpcError> {
+        let mut exporter_attrs = output.into_map();
+        exporter_attrs.insert(String::from("type"), String::from("docker"));
+        super::solve(
+            self,
+            "moby",
+            exporter_attrs,
+            super::SolveTarget::Store,
             frontend_opts,
             load_input,
             credentials,
@@ -152,3 +219,12 @@ impl super::Build for Moby {
         .await
     }
 }
+
+impl super::Pool for Moby {
+    async fn pool(
+        self,
+        options: crate::grpc::build::BuildPoolOptions,
+    ) -> Result<crate::grpc::build::BuildPool, GrpcError> {
+        crate::grpc::build::BuildPool::open(self, options).await
+    }
+}
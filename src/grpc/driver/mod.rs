@@ -1,15 +1,23 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::SystemTime;
 
+use bollard_buildkit_proto::health::health_server::HealthServer;
 use bollard_buildkit_proto::moby::{
     buildkit::{
         secrets::v1::secrets_server::SecretsServer,
-        v1::{control_client::ControlClient, CacheOptions, SolveRequest},
+        v1::{control_client::ControlClient, CacheOptions, SolveRequest, StatusRequest},
     },
+    filesync::packet::file_sync_server::FileSyncServer,
     filesync::v1::{auth_server::AuthServer, file_send_server::FileSendServer},
     sshforward::v1::ssh_server::SshServer,
     upload::v1::upload_server::UploadServer,
 };
-use log::debug;
+use futures_util::future::{self, Either};
+use futures_util::{StreamExt, TryStreamExt};
+use log::{debug, error, trace};
+use tokio_util::sync::CancellationToken;
 // use tonic::service::Interceptor;
 use tonic::{
     codegen::InterceptedService, metadata::MetadataValue, service::Interceptor, transport::Channel,
@@ -18,19 +26,28 @@ use tonic::{
 use crate::{auth::DockerCredentials, grpc::build::ImageBuildFrontendOptionsIngest};
 
 use super::{
-    build::{ImageBuildFrontendOptions, ImageBuildLoadInput},
+    build::{
+        BuildPool, BuildPoolOptions, ImageBuildFrontendOptions, ImageBuildLoadInput,
+        ImageBuildResult, ImageBuildWarning,
+    },
     error::GrpcError,
-    export::ImageExporterRequest,
-    registry::ImageRegistryOutput,
-    GrpcServer,
+    export::{ImageExporterDest, ImageExporterRequest},
+    io::{into_async_read::IntoAsyncRead, reader_stream::ReaderStream, GrpcTransport},
+    registry::{ImagePushProgress, ImageRegistryOutput, RegistryPush},
+    GrpcServer, HealthServerImpl, SessionHealth,
 };
 
+const DUPLEX_BUF_SIZE: usize = 8 * 1024;
+
 /// The Docker Container driver opens a GRPC connection by instantiating a Buildkit container over
 /// the traditional docker socket, and communicating over a docker execution Stdin/Stdout pipe.
 pub mod docker_container;
 /// The Moby driver opens a bi-directional GRPC connection by upgrading HTTP `/session` and `/grpc`
 /// endpoints over the traditional docker socket.
 pub mod moby;
+/// The Remote driver connects to a standalone `buildkitd` over TCP, optionally secured with TLS,
+/// or over a unix socket, without involving a docker daemon.
+pub mod remote;
 
 pub(crate) trait Driver {
     async fn grpc_handle(
@@ -39,6 +56,68 @@ pub(crate) trait Driver {
         services: Vec<GrpcServer>,
     ) -> Result<ControlClient<InterceptedService<Channel, DriverInterceptor>>, GrpcError>;
     fn get_tear_down_handler(&self) -> Box<dyn DriverTearDownHandler>;
+    fn solve_handle(&self) -> SolveHandle;
+}
+
+/// Cancels the in-flight solves of a driver, and reports the health checks of their session.
+///
+/// Obtain the handle through the `solve_handle` method of a driver before starting a build,
+/// which consumes the driver. Cancelling aborts the solve within buildkit, shuts the session
+/// down and tears the driver down, e.g. stopping the `Buildkit` container of a
+/// [`docker_container::DockerContainer`] driver, rather than leaking it as dropping the build's
+/// future would. The cancelled build returns a [`GrpcError::SolveCancelled`] error.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::grpc::driver::moby::Moby;
+///
+/// let driver = Moby::new(&docker);
+/// let handle = driver.solve_handle();
+///
+/// // abort a runaway build, e.g. on a CI timeout
+/// handle.cancel();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SolveHandle {
+    token: CancellationToken,
+    pub(crate) health: Arc<SessionHealth>,
+}
+
+impl SolveHandle {
+    pub(crate) fn new() -> Self {
+        Self {
+            token: CancellationToken::new(),
+            health: Arc::new(SessionHealth::default()),
+        }
+    }
+
+    /// Cancel the in-flight solves, and any solve started afterwards.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Whether the solves were cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// The number of health checks that buildkit issued to the session.
+    pub fn health_checks(&self) -> u64 {
+        self.health.checks.load(Ordering::Relaxed)
+    }
+
+    /// When buildkit last checked the health of the session. Buildkit checks the session every
+    /// few seconds while it is in use, so a stale check hints that the session was lost.
+    pub fn last_health_check(&self) -> Option<SystemTime> {
+        *self.health.last_check.lock().unwrap()
+    }
+
+    pub(crate) async fn cancelled(&self) {
+        self.token.cancelled().await
+    }
 }
 
 pub(crate) trait DriverTearDownHandler {
@@ -47,6 +126,83 @@ pub(crate) trait DriverTearDownHandler {
     ) -> std::pin::Pin<Box<dyn futures_core::Future<Output = Result<(), GrpcError>> + 'a>>;
 }
 
+pub(crate) struct NoopTearDownHandler {}
+
+impl DriverTearDownHandler for NoopTearDownHandler {
+    fn tear_down(
+        &self,
+    ) -> std::pin::Pin<Box<dyn futures_core::Future<Output = Result<(), GrpcError>>>> {
+        Box::pin(futures_util::future::ok(()))
+    }
+}
+
+/// Open a session with `Buildkit` through its `Control.Session` stream, serving the session's
+/// GRPC services back to `Buildkit` over that stream.
+pub(crate) async fn control_session(
+    channel: Channel,
+    session_id: &str,
+    services: Vec<GrpcServer>,
+    cancellation: Option<CancellationToken>,
+    handle: SolveHandle,
+) -> Result<ControlClient<InterceptedService<Channel, DriverInterceptor>>, GrpcError> {
+    let metadata_grpc_method: Vec<String> = services.iter().flat_map(|s| s.names()).collect();
+
+    let interceptor = DriverInterceptor {
+        session_id: String::from(session_id),
+        metadata_grpc_method,
+    };
+
+    let mut control_client = ControlClient::with_interceptor(channel, interceptor);
+
+    let (asyncwriter, asyncreader) = tokio::io::duplex(DUPLEX_BUF_SIZE);
+    let streamreader = ReaderStream::new(asyncreader);
+    let stream = control_client.session(streamreader).await?;
+    let stream = stream.into_inner().map_err(std::io::Error::other);
+
+    let asyncreader = IntoAsyncRead::new(stream);
+    let transport = GrpcTransport {
+        read: Box::pin(asyncreader),
+        write: Box::pin(asyncwriter),
+    };
+
+    tokio::spawn(async {
+        let health = HealthServer::new(HealthServerImpl::new(Arc::clone(&handle.health)));
+        let mut builder = tonic::transport::Server::builder();
+        let mut router = builder.add_service(health);
+        for service in services {
+            router = service.append(router);
+        }
+        trace!("router: {:#?}", router);
+        if let Err(e) = router
+            .serve_with_incoming_shutdown(
+                futures_util::stream::iter(vec![Ok::<_, tonic::transport::Error>(transport)]),
+                shutdown_signal(cancellation, handle),
+            )
+            .await
+        {
+            error!("Failed to serve grpc connection: {}", e)
+        }
+    });
+
+    Ok(control_client)
+}
+
+/// Resolves once the client's [cancellation token](crate::Docker::with_cancellation()) is
+/// cancelled, or the solves of the session are cancelled through their [`SolveHandle`], so that
+/// the session's services shut down gracefully rather than on drop.
+pub(crate) async fn shutdown_signal(cancellation: Option<CancellationToken>, handle: SolveHandle) {
+    match cancellation {
+        Some(token) => {
+            future::select(
+                Box::pin(token.cancelled_owned()),
+                Box::pin(handle.token.cancelled_owned()),
+            )
+            .await;
+        }
+        None => handle.token.cancelled_owned().await,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct DriverInterceptor {
     session_id: String,
@@ -95,7 +251,7 @@ pub trait Export {
         frontend_opts: ImageBuildFrontendOptions,
         load_input: ImageBuildLoadInput,
         credentials: Option<HashMap<&str, DockerCredentials>>,
-    ) -> Result<(), GrpcError>;
+    ) -> Result<ImageBuildResult, GrpcError>;
 }
 
 /// Trait enabling docker builds.
@@ -107,7 +263,7 @@ pub trait Build {
         frontend_opts: ImageBuildFrontendOptions,
         load_input: ImageBuildLoadInput,
         credentials: Option<HashMap<&str, DockerCredentials>>,
-    ) -> Result<(), GrpcError>;
+    ) -> Result<ImageBuildResult, GrpcError>;
 }
 
 /// Trait enabling registry facilities
@@ -119,34 +275,49 @@ pub trait Image {
         frontend_opts: ImageBuildFrontendOptions,
         load_input: ImageBuildLoadInput,
         credentials: Option<HashMap<&str, DockerCredentials>>,
-    ) -> Result<(), GrpcError>;
+    ) -> Result<ImageBuildResult, GrpcError>;
+}
+
+/// Trait enabling several builds over a single session.
+pub trait Pool {
+    /// Open a [`BuildPool`] session, solving builds concurrently over this driver.
+    async fn pool(self, options: BuildPoolOptions) -> Result<BuildPool, GrpcError>;
+}
+
+/// Where the exporter of a solve sends the build.
+pub(crate) enum SolveTarget {
+    /// Within buildkit, f.e. the image store of the docker daemon.
+    Store,
+    /// To the client, through the `FileSend` service of the session.
+    Dest(ImageExporterDest),
+    /// To a registry, with the push options applied by the client.
+    Push(RegistryPush),
 }
 
 pub(crate) async fn solve(
     driver: impl Driver,
     exporter: &str,
     exporter_attrs: HashMap<String, String>,
-    path: Option<PathBuf>,
+    target: SolveTarget,
     frontend_opts: ImageBuildFrontendOptions,
     load_input: ImageBuildLoadInput,
     credentials: Option<HashMap<&str, DockerCredentials>>,
-) -> Result<(), GrpcError> {
+) -> Result<ImageBuildResult, GrpcError> {
     let session_id = crate::grpc::new_id();
+    let handle = driver.solve_handle();
+    let (dest, push) = match target {
+        SolveTarget::Store => (None, RegistryPush::default()),
+        SolveTarget::Dest(dest) => (Some(dest), RegistryPush::default()),
+        SolveTarget::Push(push) => (None, push),
+    };
 
-    let ImageBuildLoadInput::Upload(payload) = load_input;
-
-    let mut upload_provider = super::UploadProvider::new();
-    let context = upload_provider.add(payload.to_vec());
+    let upload_provider = super::UploadProvider::new();
 
-    let ImageBuildFrontendOptionsIngest {
-        cache_to,
-        cache_from,
-        mut frontend_attrs,
-        secret_sources,
-        ssh,
-    } = frontend_opts.consume();
-
-    frontend_attrs.insert(String::from("context"), context);
+    let mut frontend_opts = frontend_opts.consume();
+    load_input.load(&upload_provider, &mut frontend_opts);
+    let secret_sources = std::mem::take(&mut frontend_opts.secret_sources);
+    let ssh_sources = std::mem::take(&mut frontend_opts.ssh_sources);
+    let local_contexts = std::mem::take(&mut frontend_opts.local_contexts);
 
     let mut auth_provider = super::AuthProvider::new();
     if let Some(creds) = credentials {
@@ -167,14 +338,34 @@ pub(crate) async fn solve(
         GrpcServer::Secrets(secret),
     ];
 
-    if ssh {
-        let ssh_provider = super::SshProvider::new();
+    if frontend_opts.ssh || !ssh_sources.is_empty() {
+        let ssh_provider = super::SshProvider::new(frontend_opts.ssh, ssh_sources);
         let ssh = SshServer::new(ssh_provider);
         services.push(GrpcServer::Ssh(ssh));
     }
 
-    if let Some(path) = path {
-        let filesend = FileSendServer::new(super::FileSendImpl::new(path.as_path()));
+    if !local_contexts.is_empty() {
+        let local_context_provider = super::LocalContextProvider::new(local_contexts);
+        services.push(GrpcServer::FileSync(FileSyncServer::new(
+            local_context_provider,
+        )));
+    }
+
+    // attestations are read back from exports to a file
+    let attestations_archive = match &dest {
+        Some(ImageExporterDest::Path(path) | ImageExporterDest::Directory(path))
+            if frontend_opts
+                .frontend_attrs
+                .keys()
+                .any(|key| key.starts_with("attest:")) =>
+        {
+            Some(path.clone())
+        }
+        _ => None,
+    };
+
+    if let Some(dest) = dest {
+        let filesend = FileSendServer::new(super::FileSendImpl::from_dest(dest));
 
         services.push(GrpcServer::FileSend(filesend));
     }
@@ -182,10 +373,131 @@ pub(crate) async fn solve(
     let tear_down_handler = driver.get_tear_down_handler();
     let mut control_client = driver.grpc_handle(&session_id, services).await?;
 
-    let id = super::new_id();
+    let mut solve_request = solve_request(session_id, exporter, exporter_attrs, frontend_opts);
+
+    let mut attempt = 0;
+    let (res, warnings) = loop {
+        let (res, warnings) = solve_attempt(
+            &mut control_client,
+            SolveRequest::clone(&solve_request),
+            &handle,
+            push.progress.as_ref().map(|sender| &sender.0),
+        )
+        .await;
+        match res {
+            Err(GrpcError::TonicStatus { err })
+                if attempt < push.retries && err.message().contains("failed to push") =>
+            {
+                attempt += 1;
+                debug!(
+                    "retrying push ({attempt}/{}): {}",
+                    push.retries,
+                    err.message()
+                );
+                // buildkit refuses to reuse the ref of a finished solve
+                solve_request.r#ref = super::new_id();
+            }
+            res => break (res, warnings),
+        }
+    };
+    debug!("solve res: {:#?}", res);
+
+    // clean up
+
+    tear_down_handler.tear_down().await?;
+    // tear_down?;
+    let res = res?;
+
+    let attestations = match attestations_archive {
+        Some(path) => super::export::read_attestations(&path)?,
+        None => Vec::new(),
+    };
+
+    Ok(ImageBuildResult {
+        warnings,
+        attestations,
+        exporter_response: res.into_inner().exporter_response,
+    })
+}
+
+/// Send a solve request, collecting the warnings of its status, and the progress of its pushes to
+/// a registry.
+async fn solve_attempt(
+    control_client: &mut ControlClient<InterceptedService<Channel, DriverInterceptor>>,
+    solve_request: SolveRequest,
+    handle: &SolveHandle,
+    progress: Option<&tokio::sync::mpsc::UnboundedSender<ImagePushProgress>>,
+) -> (
+    Result<tonic::Response<bollard_buildkit_proto::moby::buildkit::v1::SolveResponse>, GrpcError>,
+    Vec<ImageBuildWarning>,
+) {
+    let status_request = StatusRequest {
+        r#ref: String::clone(&solve_request.r#ref),
+    };
+    let mut status_client = control_client.clone();
+    let warnings = async move {
+        let mut warnings = Vec::new();
+        if let Ok(response) = status_client.status(status_request).await {
+            let mut stream = response.into_inner();
+            while let Some(Ok(status)) = stream.next().await {
+                if let Some(progress) = progress {
+                    for push in status
+                        .statuses
+                        .iter()
+                        .filter_map(ImagePushProgress::from_status)
+                    {
+                        // the receiver may no longer listen
+                        let _ = progress.send(push);
+                    }
+                }
+                warnings.extend(status.warnings.into_iter().map(ImageBuildWarning::from));
+            }
+        }
+        warnings
+    };
+
+    debug!("sending solve request: {:#?}", solve_request);
+    let solve = async {
+        match future::select(
+            Box::pin(control_client.solve(solve_request)),
+            Box::pin(handle.cancelled()),
+        )
+        .await
+        {
+            Either::Left((res, _)) => res.map_err(GrpcError::from),
+            // dropping the request cancels the solve within buildkit
+            Either::Right(((), _)) => Err(GrpcError::SolveCancelled),
+        }
+    };
+    // the status of a failed solve may never be published, so only wait for it on success
+    match future::select(Box::pin(solve), Box::pin(warnings)).await {
+        Either::Left((res @ Ok(_), warnings)) => (res, warnings.await),
+        Either::Left((res, _)) => (res, Vec::new()),
+        Either::Right((warnings, solve)) => (solve.await, warnings),
+    }
+}
 
-    let solve_request = SolveRequest {
-        r#ref: id,
+/// Assemble the `SolveRequest` of a single build within a session, once its build context is
+/// loaded. The session-level secret and ssh sources of the frontend options are not used.
+pub(crate) fn solve_request(
+    session_id: String,
+    exporter: &str,
+    exporter_attrs: HashMap<String, String>,
+    frontend_opts: ImageBuildFrontendOptionsIngest,
+) -> SolveRequest {
+    let ImageBuildFrontendOptionsIngest {
+        cache_to,
+        cache_from,
+        frontend_attrs,
+        frontend,
+        frontend_inputs,
+        definition,
+        entitlements,
+        ..
+    } = frontend_opts;
+
+    SolveRequest {
+        r#ref: super::new_id(),
         cache: Some(CacheOptions {
             export_ref_deprecated: String::new(),
             import_refs_deprecated: Vec::new(),
@@ -193,28 +505,16 @@ pub(crate) async fn solve(
             exports: cache_to,
             imports: cache_from,
         }),
-        definition: None,
-        entitlements: vec![],
+        definition,
+        entitlements,
         exporter_deprecated: String::from(exporter),
         exporter_attrs_deprecated: exporter_attrs,
-        frontend: String::from("dockerfile.v0"),
+        frontend,
         frontend_attrs,
-        frontend_inputs: HashMap::new(),
+        frontend_inputs,
         session: session_id,
         exporters: vec![],
         internal: false,
         source_policy: None,
-    };
-
-    debug!("sending solve request: {:#?}", solve_request);
-    let res = control_client.solve(solve_request).await;
-    debug!("solve res: {:#?}", res);
-
-    // clean up
-
-    tear_down_handler.tear_down().await?;
-    // tear_down?;
-    res?;
-
-    Ok(())
+    }
 }
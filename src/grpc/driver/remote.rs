@@ -0,0 +1,388 @@
+#![cfg(feature = "buildkit")]
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bollard_buildkit_proto::moby::buildkit::v1::control_client::ControlClient;
+use futures_core::Future;
+use http::Uri;
+use hyper_rustls::{HttpsConnector, MaybeHttpsStream};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::rt::TokioIo;
+use rustls::ClientConfig;
+use tonic::codegen::InterceptedService;
+use tonic::transport::{Channel, Endpoint};
+use tower_service::Service;
+
+use crate::auth::DockerCredentials;
+use crate::errors::Error;
+use crate::grpc::build::{ImageBuildFrontendOptions, ImageBuildLoadInput, ImageBuildResult};
+use crate::grpc::error::GrpcError;
+use crate::grpc::registry::ImageRegistryOutput;
+use crate::grpc::GrpcServer;
+
+use super::{Driver, DriverInterceptor, ImageExporterEnum, SolveHandle};
+
+/// Builder used to create a [`Remote`] driver, connecting to a standalone `buildkitd`.
+///
+/// <div class="warning">
+///  Warning: Buildkit features in Bollard are currently in Developer Preview and are intended strictly for feedback purposes only.
+/// </div>
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::grpc::driver::remote::RemoteBuilder;
+/// use std::path::Path;
+///
+/// let driver = RemoteBuilder::new("tcp://buildkitd:1234")
+///     .ca_cert(Path::new("/certs/ca.pem"))
+///     .client_cert(Path::new("/certs/cert.pem"), Path::new("/certs/key.pem"))
+///     .build();
+///
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct RemoteBuilder {
+    addr: String,
+    ca_cert: Option<PathBuf>,
+    client_cert: Option<(PathBuf, PathBuf)>,
+}
+
+impl RemoteBuilder {
+    /// Construct the builder given the `buildkitd` address, either `tcp://host:port` or
+    /// `unix:///path/to/buildkitd.sock`.
+    pub fn new(addr: &str) -> Self {
+        Self {
+            addr: String::from(addr),
+            ca_cert: None,
+            client_cert: None,
+        }
+    }
+
+    /// The certificate authority used to verify `buildkitd`, connecting with TLS over TCP.
+    pub fn ca_cert(&mut self, path: &Path) -> &mut RemoteBuilder {
+        self.ca_cert = Some(path.to_path_buf());
+        self
+    }
+
+    /// The client certificate and private key presented to `buildkitd`, connecting with TLS over
+    /// TCP.
+    pub fn client_cert(&mut self, cert: &Path, key: &Path) -> &mut RemoteBuilder {
+        self.client_cert = Some((cert.to_path_buf(), key.to_path_buf()));
+        self
+    }
+
+    /// Consume this builder to construct a [`Remote`] driver, loading any TLS certificates.
+    #[allow(clippy::result_large_err)]
+    pub fn build(&self) -> Result<Remote, GrpcError> {
+        let endpoint = if let Some(host) = self.addr.strip_prefix("tcp://") {
+            let tls = if self.ca_cert.is_some() || self.client_cert.is_some() {
+                Some(Arc::new(self.tls_config()?))
+            } else {
+                None
+            };
+            RemoteEndpoint::Tcp {
+                uri: format!("http://{host}"),
+                tls,
+            }
+        } else if let Some(path) = self.addr.strip_prefix("unix://") {
+            RemoteEndpoint::Unix(PathBuf::from(path))
+        } else {
+            return Err(Error::UnsupportedURISchemeError {
+                uri: String::from(&self.addr),
+            }
+            .into());
+        };
+
+        Ok(Remote {
+            endpoint,
+            solve_handle: SolveHandle::new(),
+        })
+    }
+
+    fn tls_config(&self) -> Result<ClientConfig, Error> {
+        let mut root_store = rustls::RootCertStore::empty();
+        if let Some(ca_cert) = &self.ca_cert {
+            root_store.add_parsable_certificates(certs(ca_cert)?);
+        } else {
+            #[cfg(not(any(feature = "test_ssl", feature = "webpki")))]
+            {
+                let native_certs = rustls_native_certs::load_native_certs();
+                if !native_certs.errors.is_empty() {
+                    return Err(Error::LoadNativeCertsErrors {
+                        errors: native_certs.errors,
+                    });
+                }
+                for cert in native_certs.certs {
+                    root_store.add(cert)?;
+                }
+            }
+            #[cfg(any(feature = "test_ssl", feature = "webpki"))]
+            root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+
+        let builder = ClientConfig::builder().with_root_certificates(root_store);
+
+        let mut config = if let Some((cert, key)) = &self.client_cert {
+            let key_der =
+                rustls_pemfile::private_key(&mut io::BufReader::new(fs::File::open(key)?))
+                    .map_err(|_| Error::CertParseError { path: key.clone() })?
+                    .ok_or_else(|| Error::CertParseError { path: key.clone() })?;
+            builder
+                .with_client_auth_cert(certs(cert)?, key_der)
+                .map_err(|_| Error::CertParseError { path: key.clone() })?
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        // buildkitd only serves GRPC over HTTP/2
+        config.alpn_protocols = vec![b"h2".to_vec()];
+
+        Ok(config)
+    }
+}
+
+fn certs(path: &Path) -> Result<Vec<rustls_pki_types::CertificateDer<'static>>, Error> {
+    let file = fs::File::open(path).map_err(|_| Error::CertPathError {
+        path: path.to_path_buf(),
+    })?;
+    Ok(rustls_pemfile::certs(&mut io::BufReader::new(file)).collect::<Result<Vec<_>, _>>()?)
+}
+
+#[derive(Debug)]
+enum RemoteEndpoint {
+    Tcp {
+        uri: String,
+        tls: Option<Arc<ClientConfig>>,
+    },
+    Unix(PathBuf),
+}
+
+/// The Remote driver opens a GRPC connection to a standalone `buildkitd`, such as one run as a
+/// separate service in CI, without a docker daemon.
+///
+/// Images built through the [`crate::grpc::driver::Build`] trait are stored in `buildkitd`'s own
+/// image store, rather than a docker daemon's.
+///
+/// Construct a `Remote` driver using a [`RemoteBuilder`].
+#[derive(Debug)]
+pub struct Remote {
+    endpoint: RemoteEndpoint,
+    solve_handle: SolveHandle,
+}
+
+impl Remote {
+    /// A [`SolveHandle`] to cancel the builds of this driver, which consume it.
+    pub fn solve_handle(&self) -> SolveHandle {
+        self.solve_handle.clone()
+    }
+}
+
+/// Connects with TLS regardless of the endpoint's scheme, so that tonic does not attempt its own
+/// TLS handshake.
+#[derive(Clone)]
+struct TlsConnector {
+    inner: HttpsConnector<HttpConnector>,
+}
+
+impl Service<Uri> for TlsConnector {
+    type Response = MaybeHttpsStream<TokioIo<tokio::net::TcpStream>>;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let mut parts = uri.into_parts();
+        parts.scheme = Some(http::uri::Scheme::HTTPS);
+        match Uri::from_parts(parts) {
+            Ok(uri) => self.inner.call(uri),
+            Err(e) => Box::pin(futures_util::future::err(e.into())),
+        }
+    }
+}
+
+#[cfg(unix)]
+#[derive(Clone)]
+struct UnixConnector {
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl Service<Uri> for UnixConnector {
+    type Response = TokioIo<tokio::net::UnixStream>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let path = self.path.clone();
+        Box::pin(async move { Ok(TokioIo::new(tokio::net::UnixStream::connect(path).await?)) })
+    }
+}
+
+impl Driver for Remote {
+    async fn grpc_handle(
+        self,
+        session_id: &str,
+        services: Vec<GrpcServer>,
+    ) -> Result<ControlClient<InterceptedService<Channel, DriverInterceptor>>, GrpcError> {
+        let channel = match self.endpoint {
+            RemoteEndpoint::Tcp { uri, tls: None } => Endpoint::from_shared(uri)?.connect().await?,
+            RemoteEndpoint::Tcp {
+                uri,
+                tls: Some(config),
+            } => {
+                let mut http_connector = HttpConnector::new();
+                http_connector.enforce_http(false);
+                let inner = HttpsConnector::from((http_connector, config));
+                Endpoint::from_shared(uri)?
+                    .connect_with_connector(TlsConnector { inner })
+                    .await?
+            }
+            #[cfg(unix)]
+            RemoteEndpoint::Unix(path) => {
+                Endpoint::try_from("http://[::]:50051")?
+                    .connect_with_connector(UnixConnector { path })
+                    .await?
+            }
+            #[cfg(not(unix))]
+            RemoteEndpoint::Unix(path) => {
+                return Err(Error::UnsupportedURISchemeError {
+                    uri: format!("unix://{}", path.display()),
+                }
+                .into())
+            }
+        };
+
+        super::control_session(channel, session_id, services, None, self.solve_handle).await
+    }
+
+    fn get_tear_down_handler(&self) -> Box<dyn super::DriverTearDownHandler> {
+        Box::new(super::NoopTearDownHandler {})
+    }
+
+    fn solve_handle(&self) -> SolveHandle {
+        Remote::solve_handle(self)
+    }
+}
+
+impl super::Build for Remote {
+    async fn docker_build(
+        self,
+        name: &str,
+        frontend_opts: ImageBuildFrontendOptions,
+        load_input: ImageBuildLoadInput,
+        credentials: Option<HashMap<&str, DockerCredentials>>,
+    ) -> Result<ImageBuildResult, GrpcError> {
+        let mut exporter_attrs = HashMap::new();
+        exporter_attrs.insert(String::from("name"), String::from(name));
+        super::solve(
+            self,
+            "image",
+            exporter_attrs,
+            super::SolveTarget::Store,
+            frontend_opts,
+            load_input,
+            credentials,
+        )
+        .await
+    }
+}
+
+impl super::Export for Remote {
+    async fn export(
+        self,
+        exporter_request: ImageExporterEnum,
+        frontend_opts: ImageBuildFrontendOptions,
+        load_input: ImageBuildLoadInput,
+        credentials: Option<HashMap<&str, DockerCredentials>>,
+    ) -> Result<ImageBuildResult, GrpcError> {
+        let (exporter, exporter_attrs, dest) = match exporter_request {
+            ImageExporterEnum::OCI(request) => ("oci", request.output.into_map(), request.dest),
+            ImageExporterEnum::Docker(request) => {
+                ("docker", request.output.into_map(), request.dest)
+            }
+        };
+        super::solve(
+            self,
+            exporter,
+            exporter_attrs,
+            super::SolveTarget::Dest(dest),
+            frontend_opts,
+            load_input,
+            credentials,
+        )
+        .await
+    }
+}
+
+impl super::Image for Remote {
+    async fn registry(
+        self,
+        output: ImageRegistryOutput,
+        frontend_opts: ImageBuildFrontendOptions,
+        load_input: ImageBuildLoadInput,
+        credentials: Option<HashMap<&str, DockerCredentials>>,
+    ) -> Result<ImageBuildResult, GrpcError> {
+        let push = output.push_options();
+        super::solve(
+            self,
+            "image",
+            output.into_map(),
+            super::SolveTarget::Push(push),
+            frontend_opts,
+            load_input,
+            credentials,
+        )
+        .await
+    }
+}
+
+impl super::Pool for Remote {
+    async fn pool(
+        self,
+        options: crate::grpc::build::BuildPoolOptions,
+    ) -> Result<crate::grpc::build::BuildPool, GrpcError> {
+        crate::grpc::build::BuildPool::open(self, options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RemoteBuilder, RemoteEndpoint};
+
+    #[test]
+    fn test_remote_endpoint() {
+        let remote = RemoteBuilder::new("tcp://buildkitd:1234").build().unwrap();
+        assert!(matches!(
+            remote.endpoint,
+            RemoteEndpoint::Tcp { ref uri, tls: None } if uri == "http://buildkitd:1234"
+        ));
+
+        let remote = RemoteBuilder::new("unix:///run/buildkit/buildkitd.sock")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            remote.endpoint,
+            RemoteEndpoint::Unix(ref path) if path.to_str() == Some("/run/buildkit/buildkitd.sock")
+        ));
+
+        assert!(RemoteBuilder::new("ssh://buildkitd").build().is_err());
+        assert!(RemoteBuilder::new("tcp://buildkitd:1234")
+            .ca_cert(std::path::Path::new("/nonexistent/ca.pem"))
+            .build()
+            .is_err());
+    }
+}
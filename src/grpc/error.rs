@@ -40,6 +40,16 @@ pub enum GrpcError {
         #[from]
         err: tonic::metadata::errors::InvalidMetadataValue,
     },
+    /// Error emitted when a solve is cancelled through its
+    /// [`SolveHandle`](crate::grpc::driver::SolveHandle)
+    #[error("Solve cancelled")]
+    SolveCancelled,
+    /// Error emitted when the attestations of an exported image archive cannot be read
+    #[error("Invalid attestations in image archive: {message}")]
+    AttestationArchiveError {
+        /// Description of the problem with the archive.
+        message: String,
+    },
 }
 
 /// Errors related to the Grpc Registry authentication functionality
@@ -93,6 +103,13 @@ pub enum GrpcAuthError {
         #[from]
         err: http::Error,
     },
+    /// Error emitted by the hyper library while reading the registry's response
+    #[error("Hyper error reading the registry response during GRPC authentication")]
+    HyperResponseError {
+        /// The source hyper error
+        #[from]
+        err: hyper::Error,
+    },
     /// Error while deserializing the payload emitted by the registry
     #[error("Serde payload deserializing error during GRPC authentication")]
     SerdeJsonError {
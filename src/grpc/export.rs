@@ -4,11 +4,30 @@ pub use bollard_buildkit_proto::fsutil;
 pub use bollard_buildkit_proto::health;
 pub use bollard_buildkit_proto::moby;
 
-use std::collections::HashMap;
-use std::fmt::Display;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Display};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
 
-use super::build::ImageBuildOutputCompression;
+use serde_derive::Deserialize;
+use tokio::io::AsyncWrite;
+use tokio::sync::Mutex;
+
+use super::build::{ImageBuildAttestation, ImageBuildOutputCompression};
+use super::error::GrpcError;
+
+/// Media types of the manifests listing other manifests, f.e. one per platform.
+const INDEX_MEDIA_TYPES: [&str; 2] = [
+    "application/vnd.oci.image.index.v1+json",
+    "application/vnd.docker.distribution.manifest.list.v2+json",
+];
+/// Annotation marking the manifests of attestations in an image index.
+const REFERENCE_TYPE_ANNOTATION: &str = "vnd.docker.reference.type";
+const ATTESTATION_MANIFEST: &str = "attestation-manifest";
+/// Media type of the layers holding an `in-toto` statement.
+const IN_TOTO_MEDIA_TYPE: &str = "application/vnd.in-toto+json";
 
 /// Parameters available for passing exporter output options to buildkit when exporting images
 /// using a Solve GRPC request, f.e. used in associated [GRPC export methods](module@crate::grpc::export)
@@ -29,6 +48,7 @@ pub struct ImageExporterOutput {
     pub(crate) compression_level: Option<u8>,
     pub(crate) force_compression: bool,
     pub(crate) oci_mediatypes: bool,
+    pub(crate) unpack: bool,
     pub(crate) annotation: HashMap<String, String>,
 }
 
@@ -77,7 +97,44 @@ impl Display for ImageExporterOCIOutputCompression {
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct ImageExporterRequest {
     pub(crate) output: ImageExporterOutput,
-    pub(crate) path: std::path::PathBuf,
+    pub(crate) dest: ImageExporterDest,
+}
+
+/// Destination of the exported image tar, served to buildkit through the FileSend session
+/// service.
+#[derive(Clone)]
+pub(crate) enum ImageExporterDest {
+    Path(PathBuf),
+    Writer(Arc<Mutex<Pin<Box<dyn AsyncWrite + Send>>>>),
+    /// The tar is unpacked into the directory as it arrives.
+    Directory(PathBuf),
+}
+
+impl Default for ImageExporterDest {
+    fn default() -> Self {
+        ImageExporterDest::Path(PathBuf::new())
+    }
+}
+
+impl Debug for ImageExporterDest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageExporterDest::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            ImageExporterDest::Writer(_) => f.write_str("Writer"),
+            ImageExporterDest::Directory(path) => f.debug_tuple("Directory").field(path).finish(),
+        }
+    }
+}
+
+impl PartialEq for ImageExporterDest {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ImageExporterDest::Path(a), ImageExporterDest::Path(b)) => a == b,
+            (ImageExporterDest::Writer(a), ImageExporterDest::Writer(b)) => Arc::ptr_eq(a, b),
+            (ImageExporterDest::Directory(a), ImageExporterDest::Directory(b)) => a == b,
+            _ => false,
+        }
+    }
 }
 
 impl ImageExporterOutput {
@@ -116,6 +173,10 @@ impl ImageExporterOutput {
             self.oci_mediatypes.to_string(),
         );
 
+        if self.unpack {
+            attrs.insert(String::from("unpack"), self.unpack.to_string());
+        }
+
         for (key, value) in self.annotation {
             attrs.insert(format!("annotation.{}", key), value);
         }
@@ -193,12 +254,327 @@ impl ImageExporterOutputBuilder {
         self
     }
 
+    /// Unpack the image into the snapshotter once it is stored, when loading it into a docker
+    /// daemon using the containerd image store through
+    /// [`Moby::docker_build_with_output`](crate::grpc::driver::moby::Moby::docker_build_with_output).
+    pub fn unpack(mut self, unpack: bool) -> Self {
+        self.inner.unpack = unpack;
+        self
+    }
+
+    /// Consume this builder to create an [`ImageExporterOutput`], f.e. to load the image into a
+    /// docker daemon through
+    /// [`Moby::docker_build_with_output`](crate::grpc::driver::moby::Moby::docker_build_with_output).
+    pub fn build(self) -> ImageExporterOutput {
+        self.inner
+    }
+
     /// Consume this builder to create an [`ImageExporterRequest`] for the
     /// [`crate::grpc::driver::Export::export`] method
     pub fn dest(self, path: &Path) -> ImageExporterRequest {
         ImageExporterRequest {
             output: self.inner,
-            path: path.to_owned(),
+            dest: ImageExporterDest::Path(path.to_owned()),
+        }
+    }
+
+    /// Consume this builder to create an [`ImageExporterRequest`] that unpacks the exported tar
+    /// into the given directory, e.g. an OCI image layout of `blobs/`, `index.json` and
+    /// `oci-layout` with the `oci` exporter, which tools like `skopeo` or `crane` consume as is.
+    /// The directory is created if missing.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::grpc::export::ImageExporterOutput;
+    /// use std::path::Path;
+    ///
+    /// ImageExporterOutput::builder("docker.io/library/my-image:latest")
+    ///     .dest_dir(Path::new("/tmp/oci-layout"));
+    /// ```
+    pub fn dest_dir(self, path: &Path) -> ImageExporterRequest {
+        ImageExporterRequest {
+            output: self.inner,
+            dest: ImageExporterDest::Directory(path.to_owned()),
+        }
+    }
+
+    /// Consume this builder to create an [`ImageExporterRequest`] that streams the exported tar
+    /// to the given writer, rather than to a file. The writer is shut down once the export
+    /// completes.
+    ///
+    /// To consume the export as a stream, pass one half of a [`tokio::io::duplex`] pipe and
+    /// read from the other half while the export runs.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::grpc::export::ImageExporterOutput;
+    ///
+    /// let (writer, reader) = tokio::io::duplex(64 * 1024);
+    ///
+    /// ImageExporterOutput::builder("docker.io/library/my-image:latest")
+    ///     .dest_writer(writer);
+    /// ```
+    pub fn dest_writer<W>(self, writer: W) -> ImageExporterRequest
+    where
+        W: AsyncWrite + Send + 'static,
+    {
+        ImageExporterRequest {
+            output: self.inner,
+            dest: ImageExporterDest::Writer(Arc::new(Mutex::new(Box::pin(writer)))),
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    #[serde(default)]
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Index {
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+}
+
+fn archive_error(message: String) -> GrpcError {
+    GrpcError::AttestationArchiveError { message }
+}
+
+/// Path of a blob in an OCI image layout.
+fn blob_path(descriptor: &Descriptor) -> Option<String> {
+    let (algorithm, encoded) = descriptor.digest.split_once(':')?;
+    Some(format!("blobs/{algorithm}/{encoded}"))
+}
+
+/// Read the given files of a tar archive, in a single pass, or of an unpacked archive.
+#[allow(clippy::result_large_err)]
+fn read_archive_files(
+    path: &Path,
+    names: &HashSet<String>,
+) -> Result<HashMap<String, Vec<u8>>, GrpcError> {
+    if path.is_dir() {
+        return names
+            .iter()
+            .filter_map(|name| match std::fs::read(path.join(name)) {
+                Ok(contents) => Some(Ok((String::clone(name), contents))),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => Some(Err(archive_error(format!("{name}: {e}")))),
+            })
+            .collect();
+    }
+
+    let file =
+        std::fs::File::open(path).map_err(|e| archive_error(format!("{}: {e}", path.display())))?;
+    let mut archive = tar::Archive::new(file);
+    let mut files = HashMap::new();
+    for entry in archive
+        .entries_with_seek()
+        .map_err(|e| archive_error(e.to_string()))?
+    {
+        let mut entry = entry.map_err(|e| archive_error(e.to_string()))?;
+        let name = String::from_utf8_lossy(&entry.path_bytes())
+            .trim_start_matches("./")
+            .to_owned();
+        if names.contains(&name) {
+            let mut contents = Vec::new();
+            entry
+                .read_to_end(&mut contents)
+                .map_err(|e| archive_error(format!("{name}: {e}")))?;
+            files.insert(name, contents);
+        }
+    }
+    Ok(files)
+}
+
+/// Read the `in-toto` attestations, f.e. the SLSA provenance and SPDX SBOM, of the images in an
+/// archive exported with the `oci` or `docker` exporter, or in a directory the archive was
+/// [unpacked into](ImageExporterOutputBuilder::dest_dir).
+///
+/// Exports to a file read their attestations back into the
+/// [`ImageBuildResult`](super::build::ImageBuildResult) of the build; use this function for
+/// exports to a [writer](ImageExporterOutputBuilder::dest_writer), once the archive is saved.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use bollard::grpc::export::read_attestations;
+/// use std::path::Path;
+///
+/// for attestation in read_attestations(Path::new("/tmp/oci.tar")).unwrap() {
+///     if attestation.is_provenance() {
+///         println!("{}", attestation.predicate);
+///     }
+/// }
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn read_attestations(path: &Path) -> Result<Vec<ImageBuildAttestation>, GrpcError> {
+    let mut attestations = Vec::new();
+    let mut indexes = vec![String::from("index.json")];
+    let mut manifests = Vec::new();
+    let mut statements = Vec::new();
+
+    // every level of the image layout takes a pass over the archive
+    while !(indexes.is_empty() && manifests.is_empty() && statements.is_empty()) {
+        let names: HashSet<String> = indexes
+            .iter()
+            .chain(&manifests)
+            .chain(&statements)
+            .cloned()
+            .collect();
+        let mut files = read_archive_files(path, &names)?;
+        let mut take = |name: &str| {
+            files
+                .remove(name)
+                .ok_or_else(|| archive_error(format!("{name} is missing")))
+        };
+
+        for name in std::mem::take(&mut statements) {
+            let contents = take(&name)?;
+            attestations.push(
+                serde_json::from_slice(&contents)
+                    .map_err(|e| archive_error(format!("{name}: {e}")))?,
+            );
+        }
+
+        for name in std::mem::take(&mut manifests) {
+            let contents = take(&name)?;
+            let manifest: Manifest = serde_json::from_slice(&contents)
+                .map_err(|e| archive_error(format!("{name}: {e}")))?;
+            statements.extend(
+                manifest
+                    .layers
+                    .iter()
+                    .filter(|layer| layer.media_type == IN_TOTO_MEDIA_TYPE)
+                    .filter_map(blob_path),
+            );
+        }
+
+        for name in std::mem::take(&mut indexes) {
+            let contents = take(&name)?;
+            let index: Index = serde_json::from_slice(&contents)
+                .map_err(|e| archive_error(format!("{name}: {e}")))?;
+            for descriptor in &index.manifests {
+                if INDEX_MEDIA_TYPES.contains(&descriptor.media_type.as_str()) {
+                    indexes.extend(blob_path(descriptor));
+                } else if descriptor
+                    .annotations
+                    .get(REFERENCE_TYPE_ANNOTATION)
+                    .is_some_and(|reference_type| reference_type == ATTESTATION_MANIFEST)
+                {
+                    manifests.extend(blob_path(descriptor));
+                }
+            }
+        }
+    }
+
+    Ok(attestations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_attestations, ImageExporterOutput};
+    use crate::grpc::build::ImageBuildOutputCompression;
+
+    #[test]
+    fn test_exporter_output_attrs() {
+        let attrs = ImageExporterOutput::builder("my-image:latest")
+            .compression(&ImageBuildOutputCompression::Zstd)
+            .oci_mediatypes(true)
+            .unpack(true)
+            .build()
+            .into_map();
+        assert_eq!(attrs["name"], "my-image:latest");
+        assert_eq!(attrs["compression"], "zstd");
+        assert_eq!(attrs["oci-mediatypes"], "true");
+        assert_eq!(attrs["unpack"], "true");
+
+        let attrs = ImageExporterOutput::builder("my-image:latest")
+            .build()
+            .into_map();
+        assert!(!attrs.contains_key("unpack"));
+    }
+
+    fn append(archive: &mut tar::Builder<Vec<u8>>, path: &str, contents: &str) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        archive
+            .append_data(&mut header, path, contents.as_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_read_attestations() {
+        let statement = r#"{
+            "_type": "https://in-toto.io/Statement/v0.1",
+            "predicateType": "https://slsa.dev/provenance/v0.2",
+            "subject": [{ "name": "pkg:docker/my-image@latest", "digest": { "sha256": "aaaa" } }],
+            "predicate": { "builder": { "id": "" } }
+        }"#;
+
+        // the blobs precede the index, as in the archives of buildkit
+        let mut archive = tar::Builder::new(Vec::new());
+        append(
+            &mut archive,
+            "oci-layout",
+            r#"{"imageLayoutVersion":"1.0.0"}"#,
+        );
+        append(&mut archive, "blobs/sha256/statement", statement);
+        append(
+            &mut archive,
+            "blobs/sha256/attestation",
+            r#"{"layers":[
+                {"mediaType":"application/vnd.in-toto+json","digest":"sha256:statement"},
+                {"mediaType":"application/octet-stream","digest":"sha256:other"}
+            ]}"#,
+        );
+        append(
+            &mut archive,
+            "blobs/sha256/index",
+            r#"{"manifests":[
+                {"mediaType":"application/vnd.oci.image.manifest.v1+json","digest":"sha256:aaaa"},
+                {"mediaType":"application/vnd.oci.image.manifest.v1+json","digest":"sha256:attestation",
+                 "annotations":{"vnd.docker.reference.type":"attestation-manifest"}}
+            ]}"#,
+        );
+        append(
+            &mut archive,
+            "index.json",
+            r#"{"manifests":[{"mediaType":"application/vnd.oci.image.index.v1+json","digest":"sha256:index"}]}"#,
+        );
+
+        let path = std::env::temp_dir().join(format!("bollard-oci-{}.tar", std::process::id()));
+        let archive = archive.into_inner().unwrap();
+        std::fs::write(&path, &archive).unwrap();
+        let attestations = read_attestations(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let attestations = attestations.unwrap();
+        assert_eq!(attestations.len(), 1);
+        assert!(attestations[0].is_provenance());
+        assert!(!attestations[0].is_sbom());
+        assert_eq!(attestations[0].subject[0].digest["sha256"], "aaaa");
+        assert_eq!(attestations[0].predicate["builder"]["id"], "");
+
+        // an OCI layout directory, as unpacked by `dest_dir`
+        let dir = std::env::temp_dir().join(format!("bollard-oci-{}", std::process::id()));
+        tar::Archive::new(archive.as_slice()).unpack(&dir).unwrap();
+        let unpacked = read_attestations(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(unpacked.unwrap().len(), 1);
+    }
+}
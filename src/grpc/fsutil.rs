@@ -1,4 +1,12 @@
 use bitflags::bitflags;
+use bollard_buildkit_proto::fsutil::types::Stat;
+
+use bytes::Bytes;
+
+use std::collections::HashSet;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
 bitflags! { // source: https://pkg.go.dev/io/fs#FileMode
     pub struct FileMode: u32 {
@@ -24,3 +32,353 @@ bitflags! { // source: https://pkg.go.dev/io/fs#FileMode
         const Perm = 0o777; // Unix permission bits
     }
 }
+
+/// Filters of a local source, sent by buildkit as metadata of the `DiffCopy` request.
+#[derive(Debug, Default)]
+pub(crate) struct WalkFilter {
+    pub(crate) include_patterns: Vec<String>,
+    pub(crate) exclude_patterns: Vec<String>,
+    pub(crate) follow_paths: Vec<String>,
+}
+
+/// A local source served to buildkit through the session's filesync.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum LocalSource {
+    /// A directory on disk.
+    Directory(PathBuf),
+    /// Files held in memory, at the root of the source.
+    Files(Vec<(String, Bytes)>),
+}
+
+/// The contents of a file announced to buildkit.
+#[derive(Debug, Clone)]
+pub(crate) enum LocalContent {
+    Path(PathBuf),
+    Inline(Bytes),
+}
+
+/// A file of a local source, as announced to buildkit.
+#[derive(Debug, Clone)]
+pub(crate) struct LocalEntry {
+    pub(crate) stat: Stat,
+    pub(crate) content: LocalContent,
+}
+
+/// A `.dockerignore` style pattern, matching a path or any of its parents.
+#[derive(Debug)]
+struct Pattern {
+    components: Vec<String>,
+    negated: bool,
+}
+
+impl Pattern {
+    fn new(pattern: &str) -> Option<Self> {
+        let (negated, pattern) = match pattern.trim().strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern.trim()),
+        };
+        let components: Vec<String> = pattern
+            .split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+            .map(String::from)
+            .collect();
+        if components.is_empty() {
+            None
+        } else {
+            Some(Pattern {
+                components,
+                negated,
+            })
+        }
+    }
+
+    fn matches(&self, path: &[&str]) -> bool {
+        (1..=path.len()).any(|len| glob_path(&self.components, &path[..len]))
+    }
+}
+
+fn glob_path(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((component, rest)) if component == "**" => {
+            (0..=path.len()).any(|skip| glob_path(rest, &path[skip..]))
+        }
+        Some((component, rest)) => match path.split_first() {
+            Some((name, path)) => {
+                glob(component.as_bytes(), name.as_bytes()) && glob_path(rest, path)
+            }
+            None => false,
+        },
+    }
+}
+
+fn glob(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            glob(rest, name) || (!name.is_empty() && glob(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name))) => glob(rest, name),
+        (Some((expected, rest)), Some((actual, name))) if expected == actual => glob(rest, name),
+        _ => false,
+    }
+}
+
+/// Walk a local source in the order buildkit expects: depth first, with the entries of each
+/// directory sorted by name. Directories are kept when they hold a selected entry, so that
+/// negated exclude patterns and include patterns reach into them.
+pub(crate) fn walk(source: &LocalSource, filter: &WalkFilter) -> std::io::Result<Vec<LocalEntry>> {
+    let mut entries = Vec::new();
+    match source {
+        LocalSource::Directory(root) => visit(root, "", &mut entries)?,
+        LocalSource::Files(files) => {
+            let mut files = files.clone();
+            files.sort_by(|(a, _), (b, _)| a.cmp(b));
+            entries.extend(files.into_iter().map(|(path, data)| LocalEntry {
+                stat: Stat {
+                    path,
+                    mode: 0o644,
+                    size: data.len() as i64,
+                    ..Default::default()
+                },
+                content: LocalContent::Inline(data),
+            }));
+        }
+    }
+
+    let includes: Vec<Pattern> = filter
+        .include_patterns
+        .iter()
+        .chain(filter.follow_paths.iter())
+        .filter_map(|pattern| Pattern::new(pattern))
+        .collect();
+    let excludes: Vec<Pattern> = filter
+        .exclude_patterns
+        .iter()
+        .filter_map(|pattern| Pattern::new(pattern))
+        .collect();
+
+    let selected: Vec<bool> = entries
+        .iter()
+        .map(|entry| {
+            let path: Vec<&str> = entry.stat.path.split('/').collect();
+            let included = includes.is_empty() || includes.iter().any(|p| p.matches(&path));
+            let excluded = excludes
+                .iter()
+                .rev()
+                .find(|p| p.matches(&path))
+                .is_some_and(|p| !p.negated);
+            included && !excluded
+        })
+        .collect();
+
+    let mut parents = HashSet::new();
+    for (entry, _) in entries
+        .iter()
+        .zip(&selected)
+        .filter(|(_, selected)| **selected)
+    {
+        let mut path = entry.stat.path.as_str();
+        while let Some((parent, _)) = path.rsplit_once('/') {
+            parents.insert(parent.to_owned());
+            path = parent;
+        }
+    }
+
+    Ok(entries
+        .into_iter()
+        .zip(selected)
+        .filter(|(entry, selected)| *selected || parents.contains(&entry.stat.path))
+        .map(|(entry, _)| entry)
+        .collect())
+}
+
+fn visit(dir: &Path, prefix: &str, entries: &mut Vec<LocalEntry>) -> std::io::Result<()> {
+    let mut children = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    children.sort_by_key(|child| child.file_name());
+
+    for child in children {
+        let source = child.path();
+        let metadata = std::fs::symlink_metadata(&source)?;
+        let file_type = metadata.file_type();
+        if !(file_type.is_file() || file_type.is_dir() || file_type.is_symlink()) {
+            continue;
+        }
+
+        let name = child.file_name().to_string_lossy().into_owned();
+        let path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        };
+
+        let stat = Stat {
+            mode: file_mode(&metadata),
+            size: if file_type.is_file() {
+                metadata.len() as i64
+            } else {
+                0
+            },
+            mod_time: metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|elapsed| elapsed.as_nanos() as i64)
+                .unwrap_or_default(),
+            linkname: if file_type.is_symlink() {
+                std::fs::read_link(&source)?.to_string_lossy().into_owned()
+            } else {
+                String::new()
+            },
+            path: path.clone(),
+            ..owner(&metadata)
+        };
+        entries.push(LocalEntry {
+            stat,
+            content: LocalContent::Path(source.clone()),
+        });
+
+        if file_type.is_dir() {
+            visit(&source, &path, entries)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::MetadataExt;
+
+    let mode = metadata.mode();
+    let mut file_mode = FileMode::from_bits_truncate(mode & FileMode::Perm.bits());
+    file_mode.set(FileMode::Setuid, mode & 0o4000 != 0);
+    file_mode.set(FileMode::Setgid, mode & 0o2000 != 0);
+    file_mode.set(FileMode::Sticky, mode & 0o1000 != 0);
+    file_type_mode(metadata, file_mode).bits()
+}
+
+#[cfg(not(unix))]
+fn file_mode(metadata: &Metadata) -> u32 {
+    let perm = if metadata.is_dir() { 0o755 } else { 0o644 };
+    file_type_mode(metadata, FileMode::from_bits_truncate(perm)).bits()
+}
+
+fn file_type_mode(metadata: &Metadata, mut file_mode: FileMode) -> FileMode {
+    file_mode.set(FileMode::Dir, metadata.is_dir());
+    file_mode.set(FileMode::Symlink, metadata.file_type().is_symlink());
+    file_mode
+}
+
+#[cfg(unix)]
+fn owner(metadata: &Metadata) -> Stat {
+    use std::os::unix::fs::MetadataExt;
+
+    Stat {
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+        ..Default::default()
+    }
+}
+
+#[cfg(not(unix))]
+fn owner(_metadata: &Metadata) -> Stat {
+    Stat::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::{walk, FileMode, LocalContent, LocalSource, WalkFilter};
+
+    #[test]
+    fn test_walk() {
+        let root = std::env::temp_dir().join(format!("bollard-fsutil-{}", std::process::id()));
+        for dir in ["a/b", "a/c", "a.d"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "a/b/keep.txt",
+            "a/c/drop.log",
+            "a/c/keep.log",
+            "a.d/file",
+            "z",
+        ] {
+            std::fs::write(root.join(file), file).unwrap();
+        }
+
+        let paths = |filter: &WalkFilter| -> Vec<String> {
+            walk(&LocalSource::Directory(root.clone()), filter)
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.stat.path)
+                .collect()
+        };
+
+        let entries = walk(
+            &LocalSource::Directory(root.clone()),
+            &WalkFilter::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.stat.path.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "a",
+                "a/b",
+                "a/b/keep.txt",
+                "a/c",
+                "a/c/drop.log",
+                "a/c/keep.log",
+                "a.d",
+                "a.d/file",
+                "z"
+            ]
+        );
+        assert_ne!(entries[0].stat.mode & FileMode::Dir.bits(), 0);
+        assert_eq!(entries[2].stat.mode & FileMode::Type.bits(), 0);
+        assert_eq!(entries[2].stat.size, 12);
+
+        let excluded = paths(&WalkFilter {
+            exclude_patterns: vec![
+                String::from("a"),
+                String::from("!a/**/keep.*"),
+                String::from("*.d"),
+            ],
+            ..Default::default()
+        });
+        assert_eq!(
+            excluded,
+            ["a", "a/b", "a/b/keep.txt", "a/c", "a/c/keep.log", "z"]
+        );
+
+        let followed = paths(&WalkFilter {
+            follow_paths: vec![String::from("a/c/keep.log")],
+            ..Default::default()
+        });
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(followed, ["a", "a/c", "a/c/keep.log"]);
+
+        let files = LocalSource::Files(vec![
+            (
+                String::from("Dockerfile"),
+                Bytes::from_static(b"FROM alpine"),
+            ),
+            (String::from(".dockerignore"), Bytes::new()),
+        ]);
+        let entries = walk(
+            &files,
+            &WalkFilter {
+                include_patterns: vec![String::from("Dockerfile")],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].stat.path, "Dockerfile");
+        assert_eq!(entries[0].stat.size, 11);
+        assert!(matches!(&entries[0].content, LocalContent::Inline(data) if data == "FROM alpine"));
+    }
+}
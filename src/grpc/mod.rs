@@ -4,6 +4,8 @@
 
 /// End-user buildkit build functions
 pub mod build;
+/// End-user buildkit cache functions
+pub mod cache;
 /// A package of GRPC buildkit connection implementations
 pub mod driver;
 /// Errors for the GRPC modules
@@ -38,7 +40,10 @@ use std::env;
 use std::fs::OpenOptions;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
+use std::time::SystemTime;
 
 use bollard_buildkit_proto::fsutil::types::packet::PacketType;
 use bollard_buildkit_proto::fsutil::types::Packet;
@@ -47,6 +52,7 @@ use bollard_buildkit_proto::moby::buildkit::secrets::v1::{GetSecretRequest, GetS
 use bollard_buildkit_proto::moby::filesync::packet::file_send_server::{
     FileSend as FileSendPacket, FileSendServer as FileSendPacketServer,
 };
+use bollard_buildkit_proto::moby::filesync::packet::file_sync_server::{FileSync, FileSyncServer};
 use bollard_buildkit_proto::moby::filesync::v1::auth_server::AuthServer;
 use bollard_buildkit_proto::moby::filesync::v1::file_send_server::FileSendServer;
 use bollard_buildkit_proto::moby::sshforward::v1::ssh_server::{Ssh, SshServer};
@@ -64,13 +70,13 @@ use rustls::ALL_VERSIONS;
 use serde_derive::Deserialize;
 use ssh::SshAgentPacketDecoder;
 use tokio::sync::mpsc;
-use tokio_util::codec::FramedRead;
-use tokio_util::io::{ReaderStream, StreamReader};
+use tokio_util::codec::{FramedRead, LengthDelimitedCodec};
+use tokio_util::io::{ReaderStream, StreamReader, SyncIoBridge};
 use tonic::server::NamedService;
 use tonic::{Code, Request, Response, Status, Streaming};
 
 use futures_util::{StreamExt, TryFutureExt};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use http::request::Builder;
 use hyper::Method;
@@ -78,9 +84,11 @@ use std::future::Future;
 use tower_service::Service;
 
 use self::error::GrpcAuthError;
+use self::export::ImageExporterDest;
 use self::io::GrpcTransport;
 
 const MAX_SECRET_SIZE: u64 = 500 * 1024; // 500KB
+const UNPACK_BUF_SIZE: usize = 64 * 1024;
 
 #[derive(Debug)]
 pub(crate) enum GrpcServer {
@@ -88,6 +96,7 @@ pub(crate) enum GrpcServer {
     Upload(UploadServer<UploadProvider>),
     FileSend(FileSendServer<FileSendImpl>),
     FileSendPacket(FileSendPacketServer<FileSendPacketImpl>),
+    FileSync(FileSyncServer<LocalContextProvider>),
     Secrets(SecretsServer<SecretProvider>),
     Ssh(SshServer<SshProvider>),
 }
@@ -104,6 +113,7 @@ impl GrpcServer {
             GrpcServer::FileSendPacket(file_send_packet_server) => {
                 builder.add_service(file_send_packet_server)
             }
+            GrpcServer::FileSync(file_sync_server) => builder.add_service(file_sync_server),
             GrpcServer::Secrets(secret_server) => builder.add_service(secret_server),
             GrpcServer::Ssh(ssh_server) => builder.add_service(ssh_server),
         }
@@ -133,6 +143,12 @@ impl GrpcServer {
                     FileSendPacketServer::<FileSendPacketImpl>::NAME
                 )]
             }
+            GrpcServer::FileSync(_file_sync_server) => {
+                vec![format!(
+                    "/{}/diffcopy",
+                    FileSyncServer::<LocalContextProvider>::NAME
+                )]
+            }
             GrpcServer::Secrets(_secret_server) => {
                 vec![format!(
                     "/{}/GetSecret",
@@ -149,19 +165,28 @@ impl GrpcServer {
     }
 }
 
+/// Health checks of a session, which buildkit issues periodically while the session is in use.
+#[derive(Debug, Default)]
+pub(crate) struct SessionHealth {
+    pub(crate) checks: AtomicU64,
+    pub(crate) last_check: Mutex<Option<SystemTime>>,
+}
+
 #[derive(Debug)]
 pub(crate) struct HealthServerImpl {
     service_map: HashMap<String, ServingStatus>,
     shutdown: bool,
+    health: Arc<SessionHealth>,
 }
 
 impl HealthServerImpl {
-    pub fn new() -> Self {
+    pub fn new(health: Arc<SessionHealth>) -> Self {
         let mut service_map = HashMap::new();
         service_map.insert(String::from(""), ServingStatus::Serving);
         Self {
             service_map,
             shutdown: false,
+            health,
         }
     }
 
@@ -181,6 +206,8 @@ impl Health for HealthServerImpl {
         request: Request<HealthCheckRequest>,
     ) -> Result<Response<HealthCheckResponse>, Status> {
         trace!("Received GRPC Health Request: {:#?}", request);
+        self.health.checks.fetch_add(1, Ordering::Relaxed);
+        *self.health.last_check.lock().unwrap() = Some(SystemTime::now());
         if let Some(status) = self.service_map.get(&request.get_ref().service) {
             Ok(Response::new(HealthCheckResponse {
                 status: *status as i32,
@@ -190,26 +217,29 @@ impl Health for HealthServerImpl {
         }
     }
 
-    #[allow(clippy::diverging_sub_expression)]
     async fn watch(
         &self,
         _request: Request<HealthCheckRequest>,
     ) -> Result<Response<Self::WatchStream>, Status> {
-        unimplemented!();
+        Err(Status::unimplemented("health watch is not supported"))
     }
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct FileSendImpl {
-    pub(crate) dest: PathBuf,
+    pub(crate) dest: ImageExporterDest,
 }
 
 impl FileSendImpl {
     pub fn new(dest: &Path) -> Self {
         Self {
-            dest: dest.to_owned(),
+            dest: ImageExporterDest::Path(dest.to_owned()),
         }
     }
+
+    pub fn from_dest(dest: ImageExporterDest) -> Self {
+        Self { dest }
+    }
 }
 
 #[tonic::async_trait]
@@ -221,18 +251,41 @@ impl FileSend for FileSendImpl {
     ) -> Result<Response<Self::DiffCopyStream>, Status> {
         trace!("Protobuf FileSend diff_copy triggered: {:#?}", request);
 
-        let path = self.dest.as_path();
-
         let mut in_stream = request.into_inner();
 
-        let mut file = tokio::fs::File::create(path).await?;
-
-        while let Some(result) = in_stream.next().await {
-            match result {
-                Ok(v) => {
-                    file.write_all(&v.data).await?;
+        match &self.dest {
+            ImageExporterDest::Path(path) => {
+                let mut file = tokio::fs::File::create(path).await?;
+                while let Some(result) = in_stream.next().await {
+                    file.write_all(&result?.data).await?;
+                }
+            }
+            ImageExporterDest::Writer(writer) => {
+                let mut writer = writer.lock().await;
+                while let Some(result) = in_stream.next().await {
+                    writer.write_all(&result?.data).await?;
                 }
-                Err(err) => return Err(err),
+                writer.shutdown().await?;
+            }
+            ImageExporterDest::Directory(dir) => {
+                tokio::fs::create_dir_all(dir).await?;
+                let (mut writer, reader) = tokio::io::duplex(UNPACK_BUF_SIZE);
+                let dir = dir.clone();
+                let unpack = tokio::task::spawn_blocking(move || {
+                    tar::Archive::new(SyncIoBridge::new(reader)).unpack(dir)
+                });
+                let mut unpacking = true;
+                while let Some(result) = in_stream.next().await {
+                    let data = result?.data;
+                    // an unpack failure drops the reader, and is reported once the stream is drained
+                    if unpacking && writer.write_all(&data).await.is_err() {
+                        unpacking = false;
+                    }
+                }
+                drop(writer);
+                unpack
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))??;
             }
         }
 
@@ -261,7 +314,7 @@ impl FileSendPacket for FileSendPacketImpl {
         request: Request<Streaming<Packet>>,
     ) -> Result<Response<Self::DiffCopyStream>, Status> {
         let base_path = self.dest.clone();
-        std::fs::create_dir_all(&base_path).unwrap();
+        std::fs::create_dir_all(&base_path)?;
         trace!(
             "Protobuf FileSend (packet) diff_copy triggered: {:#?}",
             request
@@ -280,10 +333,10 @@ impl FileSendPacket for FileSendPacketImpl {
                     Ok(PacketType::PacketStat) => {
                         if let Some(stat) = packet.stat {
                             if fsutil::FileMode::Type.bits() & stat.mode == 0 {
-                                std::fs::File::create(base_path.join(&stat.path)).unwrap();
+                                std::fs::File::create(base_path.join(&stat.path))?;
                                 stats.insert(file_id, stat);
                             } else if fsutil::FileMode::Dir.bits() & stat.mode != 0 {
-                                std::fs::create_dir(base_path.join(stat.path)).unwrap()
+                                std::fs::create_dir(base_path.join(stat.path))?
                             };
                             file_id += 1;
                         } else {
@@ -298,20 +351,25 @@ impl FileSendPacket for FileSendPacketImpl {
                             }
                         }
                     },
-                    Ok(PacketType::PacketReq) => panic!("server should not request"),
+                    Ok(PacketType::PacketReq) => {
+                        Err(Status::invalid_argument("server should not request"))?;
+                    },
                     Ok(PacketType::PacketData) => {
                         if packet.data.is_empty() {
                             // all data for file has been received
                             stats.remove(&packet.id);
                         } else {
-                            let stat = stats.get(&packet.id).unwrap();
+                            let stat = stats.get(&packet.id).ok_or_else(|| {
+                                Status::invalid_argument(format!("unknown file id {}", packet.id))
+                            })?;
                             let file_path = base_path.join(stat.path.clone());
-                            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+                            if let Some(parent) = file_path.parent() {
+                                std::fs::create_dir_all(parent)?;
+                            }
                             let mut file = OpenOptions::new()
                                 .append(true)
-                                .open(file_path)
-                                .unwrap();
-                            file.write_all(packet.data.as_slice()).unwrap();
+                                .open(file_path)?;
+                            file.write_all(packet.data.as_slice())?;
                         }
 
                         if stats.is_empty() && received_all_stats {
@@ -324,8 +382,12 @@ impl FileSendPacket for FileSendPacketImpl {
                         }
                     },
                     Ok(PacketType::PacketFin) => return,
-                    Ok(PacketType::PacketErr) => panic!("{}", String::from_utf8(packet.data).unwrap()),
-                    Err(_) => panic!("unhandled packet type")
+                    Ok(PacketType::PacketErr) => {
+                        Err(Status::internal(String::from_utf8_lossy(&packet.data)))?;
+                    },
+                    Err(_) => {
+                        Err(Status::invalid_argument(format!("unhandled packet type {}", packet.r#type)))?;
+                    }
                 }
             }
         };
@@ -334,25 +396,34 @@ impl FileSendPacket for FileSendPacketImpl {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub(crate) struct UploadProvider {
-    pub(crate) store: HashMap<String, Vec<u8>>,
+    pub(crate) store: Arc<Mutex<HashMap<String, Vec<u8>>>>,
 }
 
 impl UploadProvider {
     pub(crate) fn new() -> Self {
         Self {
-            store: HashMap::new(),
+            store: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    pub(crate) fn add(&mut self, reader: Vec<u8>) -> String {
+    pub(crate) fn add(&self, reader: Vec<u8>) -> String {
         let id = new_id();
         let key = format!("http://buildkit-session/{}", id);
 
-        self.store.insert(format!("/{}", id), reader);
+        self.store
+            .lock()
+            .unwrap()
+            .insert(format!("/{}", id), reader);
         key
     }
+
+    pub(crate) fn remove(&self, key: &str) {
+        if let Some(path) = key.strip_prefix("http://buildkit-session") {
+            self.store.lock().unwrap().remove(path);
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -367,12 +438,11 @@ impl Upload for UploadProvider {
             .metadata()
             .get("urlpath")
             .and_then(|key| key.to_str().ok())
-            .map(String::from)
-            .and_then(|str| self.store.get(&str));
+            .and_then(|str| self.store.lock().unwrap().get(str).cloned());
         if let Some(read) = key {
             let out_stream =
                 futures_util::stream::once(futures_util::future::ok(UploadBytesMessage {
-                    data: read.to_owned(),
+                    data: read,
                 }));
 
             Ok(Response::new(Box::pin(out_stream)))
@@ -384,6 +454,162 @@ impl Upload for UploadProvider {
     }
 }
 
+/// Serves local sources, e.g. the directories of named build contexts, which buildkit requests
+/// by their `dir-name`.
+#[derive(Clone, Debug)]
+pub(crate) struct LocalContextProvider {
+    pub(crate) dirs: Arc<Mutex<HashMap<String, fsutil::LocalSource>>>,
+}
+
+impl LocalContextProvider {
+    pub(crate) fn new(dirs: HashMap<String, fsutil::LocalSource>) -> Self {
+        Self {
+            dirs: Arc::new(Mutex::new(dirs)),
+        }
+    }
+
+    pub(crate) fn add(&self, name: &str, source: fsutil::LocalSource) {
+        self.dirs.lock().unwrap().insert(String::from(name), source);
+    }
+
+    pub(crate) fn remove(&self, name: &str) {
+        self.dirs.lock().unwrap().remove(name);
+    }
+}
+
+const LOCAL_CONTEXT_CHUNK_SIZE: usize = 32 * 1024;
+
+#[tonic::async_trait]
+impl FileSync for LocalContextProvider {
+    type DiffCopyStream = Pin<Box<dyn Stream<Item = Result<Packet, Status>> + Send>>;
+    async fn diff_copy(
+        &self,
+        request: Request<Streaming<Packet>>,
+    ) -> Result<Response<Self::DiffCopyStream>, Status> {
+        trace!("Protobuf FileSync diff_copy triggered: {:#?}", request);
+
+        let metadata = request.metadata();
+        let dir_name = metadata
+            .get("dir-name")
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| Status::invalid_argument("missing 'dir-name' in filesync request"))?;
+        let source = self
+            .dirs
+            .lock()
+            .unwrap()
+            .get(dir_name)
+            .cloned()
+            .ok_or_else(|| Status::not_found(format!("unknown local context '{dir_name}'")))?;
+        let values = |key: &str| -> Vec<String> {
+            metadata
+                .get_all(key)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .map(String::from)
+                .collect()
+        };
+        let filter = fsutil::WalkFilter {
+            include_patterns: values("include-patterns"),
+            exclude_patterns: values("exclude-patterns"),
+            follow_paths: values("followpaths"),
+        };
+        let entries = fsutil::walk(&source, &filter)?;
+
+        let mut in_stream = request.into_inner();
+
+        // protocol reference: https://github.com/tonistiigi/fsutil/blob/91a3fc46842c58b62dd4630b688662842364da49/send.go
+        let out_stream = async_stream::try_stream! {
+            for entry in &entries {
+                yield Packet {
+                    r#type: PacketType::PacketStat.into(),
+                    stat: Some(entry.stat.clone()),
+                    id: 0,
+                    data: vec![]
+                };
+            }
+            yield Packet {
+                r#type: PacketType::PacketStat.into(),
+                stat: None,
+                id: 0,
+                data: vec![]
+            };
+
+            while let Some(packet) = in_stream.next().await {
+                let packet = packet?;
+                match PacketType::try_from(packet.r#type) {
+                    Ok(PacketType::PacketReq) => {
+                        let entry = entries.get(packet.id as usize).ok_or_else(|| {
+                            Status::invalid_argument(format!("unknown file id {}", packet.id))
+                        })?;
+                        match &entry.content {
+                            fsutil::LocalContent::Path(path) => {
+                                let mut file = tokio::fs::File::open(path).await?;
+                                let mut buf = vec![0; LOCAL_CONTEXT_CHUNK_SIZE];
+                                loop {
+                                    let read = file.read(&mut buf).await?;
+                                    if read == 0 {
+                                        break;
+                                    }
+                                    yield Packet {
+                                        r#type: PacketType::PacketData.into(),
+                                        stat: None,
+                                        id: packet.id,
+                                        data: buf[..read].to_vec()
+                                    };
+                                }
+                            },
+                            fsutil::LocalContent::Inline(data) => {
+                                for chunk in data.chunks(LOCAL_CONTEXT_CHUNK_SIZE) {
+                                    yield Packet {
+                                        r#type: PacketType::PacketData.into(),
+                                        stat: None,
+                                        id: packet.id,
+                                        data: chunk.to_vec()
+                                    };
+                                }
+                            },
+                        }
+                        // an empty data packet marks the end of the file
+                        yield Packet {
+                            r#type: PacketType::PacketData.into(),
+                            stat: None,
+                            id: packet.id,
+                            data: vec![]
+                        };
+                    },
+                    Ok(PacketType::PacketFin) => {
+                        yield Packet {
+                            r#type: PacketType::PacketFin.into(),
+                            stat: None,
+                            id: 0,
+                            data: vec![]
+                        };
+                        return;
+                    },
+                    Ok(PacketType::PacketErr) => {
+                        Err(Status::internal(String::from_utf8_lossy(&packet.data)))?;
+                    },
+                    _ => {
+                        Err(Status::invalid_argument(format!("unhandled packet type {}", packet.r#type)))?;
+                    }
+                }
+            }
+        };
+
+        Ok(Response::new(Box::pin(out_stream)))
+    }
+
+    type TarStreamStream = Pin<Box<dyn Stream<Item = Result<Packet, Status>> + Send>>;
+    async fn tar_stream(
+        &self,
+        _request: Request<Streaming<Packet>>,
+    ) -> Result<Response<Self::TarStreamStream>, Status> {
+        Err(Status::unimplemented(
+            "filesync tar stream is not supported",
+        ))
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct AuthProvider {
     auth_config_cache: HashMap<String, DockerCredentials>,
@@ -574,7 +800,7 @@ impl AuthProvider {
             });
         }
 
-        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let bytes = response.into_body().collect().await?.to_bytes();
 
         let oauth_token = serde_json::from_slice::<OAuthTokenResponse>(&bytes)?;
 
@@ -663,6 +889,16 @@ impl SecretProvider {
     }
 }
 
+#[allow(clippy::result_large_err)]
+fn check_secret_size(len: usize) -> Result<(), Status> {
+    if len as u64 > MAX_SECRET_SIZE {
+        return Err(Status::failed_precondition(format!(
+            "invalid secret size {len}"
+        )));
+    }
+    Ok(())
+}
+
 #[tonic::async_trait]
 impl Secrets for SecretProvider {
     async fn get_secret(
@@ -704,145 +940,282 @@ impl Secrets for SecretProvider {
                 "env var '{}' does not exist",
                 v
             ))),
+            Some(build::SecretSource::Bytes(data)) => {
+                check_secret_size(data.len())?;
+                Ok(Response::new(GetSecretResponse { data: data.clone() }))
+            }
+            Some(build::SecretSource::Provider(fetcher)) => {
+                trace!("Fetching secret {} from provider", id);
+                let data = fetcher.fetch(String::from(id)).await.map_err(|e| {
+                    Status::unavailable(format!("failed to fetch secret '{id}': {e}"))
+                })?;
+                check_secret_size(data.len())?;
+                Ok(Response::new(GetSecretResponse { data }))
+            }
 
             None => return Err(Status::not_found("secret missing ID")),
         }
     }
 }
 
+// Ref: https://github.com/moby/buildkit/blob/master/session/sshforward/ssh.go
+const SSH_ID_METADATA_KEY: &str = "buildkit.ssh.id";
+
+type SshForwardStream = Pin<
+    Box<
+        dyn Stream<
+                Item = Result<bollard_buildkit_proto::moby::sshforward::v1::BytesMessage, Status>,
+            > + Send
+            + 'static,
+    >,
+>;
+
 #[derive(Default, Debug)]
 pub(crate) struct SshProvider {
-    src: HashMap<String, PathBuf>,
-}
-
-struct SshSource {
-    agent: (),
-    socket: (),
+    default_agent: bool,
+    sources: HashMap<String, build::SshSource>,
 }
 
 impl SshProvider {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(default_agent: bool, sources: HashMap<String, build::SshSource>) -> Self {
         Self {
-            ..Default::default()
+            default_agent,
+            sources,
         }
     }
-}
 
-#[tonic::async_trait]
-impl Ssh for SshProvider {
-    async fn check_agent(
-        &self,
-        request: Request<CheckAgentRequest>,
-    ) -> Result<Response<CheckAgentResponse>, Status> {
-        let id: &str = request.get_ref().id.as_ref();
-        if !id.is_empty() && id != "default" {
-            return Err(Status::from(std::io::Error::other(
-                GrpcSshError::SshAgentSocketInit(String::from("This buildkit server only handles sshforwarding to the ssh-agent running on environment variable SSH_AUTH_SOCK on the host")),
-            )));
+    #[allow(clippy::result_large_err)]
+    fn source(&self, id: &str) -> Result<build::SshSource, Status> {
+        let id = if id.is_empty() { "default" } else { id };
+        match self.sources.get(id) {
+            Some(source) => Ok(source.clone()),
+            None if id == "default" && self.default_agent => env::var_os("SSH_AUTH_SOCK")
+                .map(PathBuf::from)
+                .or_else(default_agent_socket)
+                .map(build::SshSource::Agent)
+                .ok_or_else(|| {
+                    Status::from(std::io::Error::other(GrpcSshError::SshAgentSocketInit(
+                        String::from("The environment variable SSH_AUTH_SOCK is missing, and is required for the sshforwarding functionality"),
+                    )))
+                }),
+            None => Err(Status::from(std::io::Error::other(
+                GrpcSshError::SshAgentSocketInit(format!(
+                    "No ssh agent or keys are configured for the id '{id}'"
+                )),
+            ))),
         }
+    }
+}
 
-        if env::var("SSH_AUTH_SOCK").is_err() {
-            return Err(Status::from(std::io::Error::other(
-                GrpcSshError::SshAgentSocketInit(String::from("The environment variable SSH_AUTH_SOCK is missing, and is required for the sshforwarding functionality")),
+/// The agent used when `SSH_AUTH_SOCK` is not set: the named pipe of the Windows OpenSSH agent
+/// service.
+#[cfg(windows)]
+fn default_agent_socket() -> Option<PathBuf> {
+    Some(PathBuf::from(r"\\.\pipe\openssh-ssh-agent"))
+}
+
+#[cfg(not(windows))]
+fn default_agent_socket() -> Option<PathBuf> {
+    None
+}
+
+async fn load_ssh_keys(paths: &[PathBuf]) -> Result<Vec<ssh_key::PrivateKey>, Status> {
+    let mut keys = Vec::with_capacity(paths.len());
+    for path in paths {
+        let contents = tokio::fs::read(path).await.map_err(|e| {
+            Status::failed_precondition(format!("failed to read ssh key {path:?}: {e}"))
+        })?;
+        let key = ssh_key::PrivateKey::from_openssh(&contents)
+            .map_err(|e| Status::failed_precondition(format!("invalid ssh key {path:?}: {e}")))?;
+        if key.is_encrypted() {
+            return Err(Status::failed_precondition(format!(
+                "ssh key {path:?} is encrypted, add it to an ssh agent instead"
             )));
         }
-        Ok(Response::new(CheckAgentResponse {}))
+        keys.push(key);
     }
+    Ok(keys)
+}
 
-    /// Server streaming response type for the ForwardAgent method.
-    type ForwardAgentStream = Pin<
-        Box<
-            dyn Stream<
-                    Item = Result<
-                        bollard_buildkit_proto::moby::sshforward::v1::BytesMessage,
-                        Status,
-                    >,
-                > + Send
-                + 'static,
-        >,
-    >;
-
-    #[cfg(not(windows))]
-    async fn forward_agent(
-        &self,
-        request: Request<Streaming<bollard_buildkit_proto::moby::sshforward::v1::BytesMessage>>,
-    ) -> Result<Response<Self::ForwardAgentStream>, Status> {
-        let ssh_env_sock = env::var("SSH_AUTH_SOCK").expect("missing SSH_AUTH_SOCK");
-        let sock = tokio::net::UnixStream::connect(&ssh_env_sock).await?;
-
-        let (tx, rx) = mpsc::channel::<Result<Bytes, Status>>(100);
-        let rx_stream = tokio_stream::wrappers::ReceiverStream::new(rx).map(
-            |res: Result<Bytes, _>| match res {
-                Ok(v) => Ok(bollard_buildkit_proto::moby::sshforward::v1::BytesMessage {
-                    data: v.to_vec(),
-                }),
-                Err(e) => Err(Status::from_error(e.into())),
-            },
-        );
-
-        let in_stream = request.into_inner();
-        let mut in_framed = FramedRead::new(
-            StreamReader::new(in_stream.map(|res| match res {
-                Ok(bollard_buildkit_proto::moby::sshforward::v1::BytesMessage { data: bytes }) => {
-                    Ok(Bytes::from(bytes))
-                }
-                Err(e) => Err(std::io::Error::other(e)),
-            })),
-            SshAgentPacketDecoder::new(),
-        );
-
-        let (sock_read, sock_write) = sock.into_split();
+#[allow(clippy::result_large_err)]
+async fn forward_to_keys(
+    paths: &[PathBuf],
+    request: Request<Streaming<bollard_buildkit_proto::moby::sshforward::v1::BytesMessage>>,
+) -> Result<Response<SshForwardStream>, Status> {
+    let agent = ssh::SshKeyAgent::new(load_ssh_keys(paths).await?)
+        .map_err(|e| Status::from(std::io::Error::other(e)))?;
+
+    let in_stream = request.into_inner();
+    let in_framed = FramedRead::new(
+        StreamReader::new(in_stream.map(|res| match res {
+            Ok(bollard_buildkit_proto::moby::sshforward::v1::BytesMessage { data: bytes }) => {
+                Ok(Bytes::from(bytes))
+            }
+            Err(e) => Err(std::io::Error::other(e)),
+        })),
+        LengthDelimitedCodec::builder()
+            .max_frame_length(ssh::MAX_MESSAGE_SIZE as usize)
+            .new_codec(),
+    );
+
+    let output = in_framed.map(move |res| match res {
+        Ok(message) => Ok(bollard_buildkit_proto::moby::sshforward::v1::BytesMessage {
+            data: agent.handle(&message).to_vec(),
+        }),
+        Err(e) => Err(Status::from(e)),
+    });
+
+    Ok(Response::new(Box::pin(output)))
+}
 
-        let output_reader = ReaderStream::new(sock_read).map(|res| match res {
+/// Relay the agent protocol between buildkit and a connected ssh agent.
+#[allow(clippy::result_large_err)]
+fn forward_to_agent<S>(
+    sock: S,
+    request: Request<Streaming<bollard_buildkit_proto::moby::sshforward::v1::BytesMessage>>,
+) -> Response<SshForwardStream>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel::<Result<Bytes, Status>>(100);
+    let rx_stream =
+        tokio_stream::wrappers::ReceiverStream::new(rx).map(|res: Result<Bytes, _>| match res {
             Ok(v) => {
                 Ok(bollard_buildkit_proto::moby::sshforward::v1::BytesMessage { data: v.to_vec() })
             }
             Err(e) => Err(Status::from_error(e.into())),
         });
 
-        tokio::spawn(async move {
-            if let Err(e) = sock_write.writable().await {
-                tx.send(Err(Status::from(e)))
-                    .await
-                    .unwrap_or_else(|e| log::error!("ssh agent socket not writable: {e}"));
-                panic!("ssh agent socket not writable");
+    let in_stream = request.into_inner();
+    let mut in_framed = FramedRead::new(
+        StreamReader::new(in_stream.map(|res| match res {
+            Ok(bollard_buildkit_proto::moby::sshforward::v1::BytesMessage { data: bytes }) => {
+                Ok(Bytes::from(bytes))
             }
-            while let Some(result) = in_framed.next().await {
-                match result {
-                    Ok(data) => {
-                        if let Err(e) = sock_write.try_write(&data) {
-                            tx.send(Err(Status::from(e))).await.unwrap_or_else(|e| {
-                                log::error!("Failed to send error to channel: {e}")
-                            });
-                            break;
-                        }
-                    }
-                    Err(err) => {
-                        tx.send(Err(Status::from(std::io::Error::other(err))))
-                            .await
-                            .unwrap_or_else(|e| {
-                                log::error!("Failed to send error to channel: {e}")
-                            });
+            Err(e) => Err(std::io::Error::other(e)),
+        })),
+        SshAgentPacketDecoder::new(),
+    );
+
+    let (sock_read, mut sock_write) = tokio::io::split(sock);
+
+    let output_reader = ReaderStream::new(sock_read).map(|res| match res {
+        Ok(v) => {
+            Ok(bollard_buildkit_proto::moby::sshforward::v1::BytesMessage { data: v.to_vec() })
+        }
+        Err(e) => Err(Status::from_error(e.into())),
+    });
+
+    tokio::spawn(async move {
+        while let Some(result) = in_framed.next().await {
+            match result {
+                Ok(data) => {
+                    if let Err(e) = sock_write.write_all(&data).await {
+                        tx.send(Err(Status::from(e))).await.unwrap_or_else(|e| {
+                            log::error!("Failed to send error to channel: {e}")
+                        });
                         break;
                     }
                 }
+                Err(err) => {
+                    tx.send(Err(Status::from(std::io::Error::other(err))))
+                        .await
+                        .unwrap_or_else(|e| log::error!("Failed to send error to channel: {e}"));
+                    break;
+                }
             }
-            sock_write.forget();
-        });
+        }
+    });
+
+    let combined_output_stream =
+        futures_util::stream::iter(vec![output_reader.right_stream(), rx_stream.left_stream()])
+            .flatten_unordered(None);
 
-        let combined_output_stream =
-            futures_util::stream::iter(vec![output_reader.right_stream(), rx_stream.left_stream()])
-                .flatten_unordered(None);
+    Response::new(Box::pin(combined_output_stream))
+}
+
+#[cfg(not(windows))]
+async fn forward_to_socket(
+    path: &Path,
+    request: Request<Streaming<bollard_buildkit_proto::moby::sshforward::v1::BytesMessage>>,
+) -> Result<Response<SshForwardStream>, Status> {
+    let sock = tokio::net::UnixStream::connect(path).await?;
+
+    Ok(forward_to_agent(sock, request))
+}
+
+#[cfg(windows)]
+async fn forward_to_socket(
+    path: &Path,
+    request: Request<Streaming<bollard_buildkit_proto::moby::sshforward::v1::BytesMessage>>,
+) -> Result<Response<SshForwardStream>, Status> {
+    // Ref: https://learn.microsoft.com/en-us/windows/win32/debug/system-error-codes--0-499-
+    const ERROR_PIPE_BUSY: i32 = 231;
+    // retry a busy pipe like the named pipe connector, but bounded, so that a stuck agent fails
+    // the forwarding rather than hanging the build
+    const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+    const BUSY_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+    let deadline = tokio::time::Instant::now() + BUSY_TIMEOUT;
+    let pipe = loop {
+        match tokio::net::windows::named_pipe::ClientOptions::new().open(path) {
+            Ok(pipe) => break pipe,
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => (),
+            Err(e) => return Err(Status::from(e)),
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            return Err(Status::unavailable(format!(
+                "ssh agent pipe {path:?} still busy after {BUSY_TIMEOUT:?}"
+            )));
+        }
+        tokio::time::sleep(BUSY_RETRY_INTERVAL).await;
+    };
+
+    Ok(forward_to_agent(pipe, request))
+}
 
-        Ok(Response::new(Box::pin(combined_output_stream)))
+#[tonic::async_trait]
+impl Ssh for SshProvider {
+    async fn check_agent(
+        &self,
+        request: Request<CheckAgentRequest>,
+    ) -> Result<Response<CheckAgentResponse>, Status> {
+        match self.source(&request.get_ref().id)? {
+            // probing a named pipe would take up one of its instances
+            build::SshSource::Agent(path) if cfg!(not(windows)) && !path.exists() => {
+                return Err(Status::from(std::io::Error::other(
+                    GrpcSshError::SshAgentSocketInit(format!(
+                        "The ssh agent socket {path:?} does not exist"
+                    )),
+                )));
+            }
+            build::SshSource::Agent(_) => (),
+            build::SshSource::Keys(paths) => {
+                load_ssh_keys(&paths).await?;
+            }
+        }
+        Ok(Response::new(CheckAgentResponse {}))
     }
 
-    #[cfg(windows)]
+    /// Server streaming response type for the ForwardAgent method.
+    type ForwardAgentStream = SshForwardStream;
+
     async fn forward_agent(
         &self,
         request: Request<Streaming<bollard_buildkit_proto::moby::sshforward::v1::BytesMessage>>,
     ) -> Result<Response<Self::ForwardAgentStream>, Status> {
-        unimplemented!();
+        let id = request
+            .metadata()
+            .get(SSH_ID_METADATA_KEY)
+            .and_then(|id| id.to_str().ok())
+            .unwrap_or_default()
+            .to_owned();
+
+        match self.source(&id)? {
+            build::SshSource::Agent(path) => forward_to_socket(&path, request).await,
+            build::SshSource::Keys(paths) => forward_to_keys(&paths, request).await,
+        }
     }
 }
 
@@ -901,10 +1274,112 @@ pub(crate) fn new_id() -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use bollard_buildkit_proto::moby::buildkit::secrets::v1::secrets_server::Secrets;
+    use bollard_buildkit_proto::moby::buildkit::secrets::v1::GetSecretRequest;
+    use tonic::{Code, Request};
+
+    use super::driver::{shutdown_signal, SolveHandle};
+    use super::{build::SecretSource, HealthServerImpl, SecretProvider, MAX_SECRET_SIZE};
+    use crate::health::health_server::Health;
+    use crate::health::HealthCheckRequest;
+
+    #[test]
+    fn test_upload_provider_remove() {
+        let provider = super::UploadProvider::new();
+        let key = provider.add(vec![1, 2, 3]);
+        assert_eq!(provider.store.lock().unwrap().len(), 1);
+        provider.remove(&key);
+        assert!(provider.store.lock().unwrap().is_empty());
+    }
 
     #[test]
     fn test_new_id() {
         let s = super::new_id();
         assert_eq!(s.len(), 25);
     }
+
+    fn secret_request(id: &str) -> Request<GetSecretRequest> {
+        Request::new(GetSecretRequest {
+            id: String::from(id),
+            annotations: HashMap::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_secret_provider_sources() {
+        let mut store = HashMap::new();
+        store.insert(
+            String::from("bytes"),
+            SecretSource::Bytes(b"hunter2".to_vec()),
+        );
+        store.insert(
+            String::from("provider"),
+            SecretSource::Provider(Arc::new(|id: String| async move {
+                Ok(format!("fetched {id}").into_bytes())
+            })),
+        );
+        store.insert(
+            String::from("failing"),
+            SecretSource::Provider(Arc::new(
+                |_: String| async move { Err("vault sealed".into()) },
+            )),
+        );
+        store.insert(
+            String::from("oversized"),
+            SecretSource::Bytes(vec![0; MAX_SECRET_SIZE as usize + 1]),
+        );
+        let provider = SecretProvider::new(store);
+
+        let response = provider.get_secret(secret_request("bytes")).await.unwrap();
+        assert_eq!(response.get_ref().data, b"hunter2");
+
+        let response = provider
+            .get_secret(secret_request("provider"))
+            .await
+            .unwrap();
+        assert_eq!(response.get_ref().data, b"fetched provider");
+
+        let status = provider
+            .get_secret(secret_request("failing"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Unavailable);
+        assert!(status.message().contains("vault sealed"));
+
+        let status = provider
+            .get_secret(secret_request("oversized"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_solve_handle() {
+        let handle = SolveHandle::new();
+        let health = HealthServerImpl::new(Arc::clone(&handle.health));
+
+        assert_eq!(handle.health_checks(), 0);
+        assert!(handle.last_health_check().is_none());
+        health
+            .check(Request::new(HealthCheckRequest {
+                service: String::new(),
+            }))
+            .await
+            .unwrap();
+        assert_eq!(handle.health_checks(), 1);
+        assert!(handle.last_health_check().is_some());
+
+        let shutdown = tokio::spawn(shutdown_signal(None, handle.clone()));
+        assert!(!handle.is_cancelled());
+        handle.cancel();
+        tokio::time::timeout(Duration::from_secs(5), shutdown)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(handle.is_cancelled());
+    }
 }
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 
+use bollard_buildkit_proto::moby::buildkit::v1::VertexStatus;
+use tokio::sync::mpsc::UnboundedSender;
+
 use super::build::ImageBuildOutputCompression;
 
 /// Consumable Configuration for the Buildkit Registry exporter. Exports the build result into a container image, and pushes it to the specified registry.
@@ -20,6 +23,68 @@ pub struct ImageRegistryOutput {
     pub(crate) unpack: bool,
     pub(crate) store: bool,
     pub(crate) annotation: HashMap<String, String>,
+    pub(crate) push_retries: u32,
+    pub(crate) push_progress: Option<PushProgressSender>,
+}
+
+/// Progress of a push to the registry, as reported by buildkit while exporting an
+/// [`ImageRegistryOutput`], f.e. of `pushing layers`, or of `pushing manifest for <name>`.
+/// Buildkit versions reporting the progress of each layer name the layer's digest.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ImagePushProgress {
+    /// Identifies the progress entry within the build.
+    pub id: String,
+    /// Digest of the pushed blob, when the entry reports the push of a single blob.
+    pub digest: Option<String>,
+    /// Bytes pushed so far, when reported.
+    pub current: i64,
+    /// Size of the pushed blob, when reported.
+    pub total: i64,
+    /// Whether the push of the entry completed.
+    pub completed: bool,
+}
+
+impl ImagePushProgress {
+    /// The push progress of a vertex status, if it reports one.
+    pub(crate) fn from_status(status: &VertexStatus) -> Option<Self> {
+        if !status.id.starts_with("pushing") {
+            return None;
+        }
+        let digest = status
+            .id
+            .split_whitespace()
+            .find(|word| {
+                word.split_once(':').is_some_and(|(algorithm, encoded)| {
+                    !algorithm.is_empty() && encoded.chars().all(|c| c.is_ascii_hexdigit())
+                })
+            })
+            .map(String::from);
+        Some(ImagePushProgress {
+            id: String::clone(&status.id),
+            digest,
+            current: status.current,
+            total: status.total,
+            completed: status.completed.is_some(),
+        })
+    }
+}
+
+/// Receives the [`ImagePushProgress`] of an export, compared by channel.
+#[derive(Debug, Clone)]
+pub(crate) struct PushProgressSender(pub(crate) UnboundedSender<ImagePushProgress>);
+
+impl PartialEq for PushProgressSender {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.same_channel(&other.0)
+    }
+}
+
+/// The options of an [`ImageRegistryOutput`] applied by the client rather than by buildkit.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RegistryPush {
+    pub(crate) retries: u32,
+    pub(crate) progress: Option<PushProgressSender>,
 }
 
 impl ImageRegistryOutput {
@@ -36,6 +101,13 @@ impl ImageRegistryOutput {
         }
     }
 
+    pub(crate) fn push_options(&self) -> RegistryPush {
+        RegistryPush {
+            retries: self.push_retries,
+            progress: self.push_progress.clone(),
+        }
+    }
+
     pub(crate) fn into_map(self) -> HashMap<String, String> {
         let mut attrs = HashMap::new();
 
@@ -195,8 +267,94 @@ impl ImageRegistryOutputBuilder {
         self
     }
 
+    /// Retry the export up to `retries` times when pushing to the registry fails, f.e. on a
+    /// transient network failure. The retries reuse the build session, and buildkit's cache skips
+    /// straight to the push.
+    pub fn push_retries(mut self, retries: u32) -> Self {
+        self.inner.push_retries = retries;
+        self
+    }
+
+    /// Send the [`ImagePushProgress`] of the push to the registry to the given channel, as
+    /// buildkit reports it. Only served by the [`crate::grpc::driver::Image::registry`] method.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use bollard::grpc::registry::ImageRegistryOutput;
+    ///
+    /// let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    ///
+    /// ImageRegistryOutput::builder("localhost:5000/my-image:latest")
+    ///     .push(true)
+    ///     .push_progress(sender)
+    ///     .consume();
+    ///
+    /// // while the build runs: receiver.recv().await
+    /// ```
+    pub fn push_progress(mut self, sender: UnboundedSender<ImagePushProgress>) -> Self {
+        self.inner.push_progress = Some(PushProgressSender(sender));
+        self
+    }
+
     /// Consume this Builder instance to create an [`ImageRegistryOutput`] instance
     pub fn consume(self) -> ImageRegistryOutput {
         self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ImagePushProgress, ImageRegistryOutput};
+    use bollard_buildkit_proto::moby::buildkit::v1::VertexStatus;
+
+    #[test]
+    fn test_push_progress() {
+        let layer = VertexStatus {
+            id: String::from(
+                "pushing sha256:4f4fb700ef54461cfa02571ae0db9a0dc1e0cdb5577484a6d75e68dc38e8acc1",
+            ),
+            current: 512,
+            total: 1024,
+            ..Default::default()
+        };
+        let progress = ImagePushProgress::from_status(&layer).unwrap();
+        assert_eq!(
+            progress.digest.as_deref(),
+            Some("sha256:4f4fb700ef54461cfa02571ae0db9a0dc1e0cdb5577484a6d75e68dc38e8acc1")
+        );
+        assert_eq!((progress.current, progress.total), (512, 1024));
+        assert!(!progress.completed);
+
+        let manifest = VertexStatus {
+            id: String::from("pushing manifest for localhost:5000/my-image:latest"),
+            completed: Some(Default::default()),
+            ..Default::default()
+        };
+        let progress = ImagePushProgress::from_status(&manifest).unwrap();
+        assert_eq!(progress.digest, None);
+        assert!(progress.completed);
+
+        let layer = VertexStatus {
+            id: String::from("exporting layers"),
+            ..Default::default()
+        };
+        assert_eq!(ImagePushProgress::from_status(&layer), None);
+    }
+
+    #[test]
+    fn test_push_options() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let output = ImageRegistryOutput::builder("localhost:5000/my-image:latest")
+            .push(true)
+            .push_retries(3)
+            .push_progress(sender.clone())
+            .consume();
+
+        let push = output.push_options();
+        assert_eq!(push.retries, 3);
+        assert!(push.progress.unwrap().0.same_channel(&sender));
+        let attrs = output.into_map();
+        assert!(!attrs.contains_key("push-retries") && !attrs.contains_key("push-progress"));
+    }
+}
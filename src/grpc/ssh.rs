@@ -1,4 +1,5 @@
-use bytes::{Buf, Bytes, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use ssh_key::{Algorithm, PrivateKey, Signature, SigningKey};
 
 use super::error::GrpcSshError;
 use tokio_util::codec::Decoder;
@@ -8,10 +9,15 @@ type MessageTypeId = u8;
 const SSH_AGENT_FAILURE: MessageTypeId = 5;
 const SSH_AGENT_SUCCESS: MessageTypeId = 6;
 const SSH_AGENTC_REQUEST_IDENTITIES: MessageTypeId = 11;
-const SSH_AGENTC_SIGN_RESPONSE: MessageTypeId = 13;
+const SSH_AGENT_IDENTITIES_ANSWER: MessageTypeId = 12;
+const SSH_AGENTC_SIGN_REQUEST: MessageTypeId = 13;
+const SSH_AGENT_SIGN_RESPONSE: MessageTypeId = 14;
 const SSH_AGENTC_EXTENSION: MessageTypeId = 27;
 
-const MAX_MESSAGE_SIZE: u32 = 1024 * 1024;
+// Ref: https://datatracker.ietf.org/doc/html/draft-miller-ssh-agent-04#section-5.3
+const SSH_AGENT_RSA_SHA2_512: u32 = 4;
+
+pub(crate) const MAX_MESSAGE_SIZE: u32 = 1024 * 1024;
 
 #[derive(Debug, Copy, Clone)]
 enum SshAgentPacketDecoderState {
@@ -65,7 +71,7 @@ impl Decoder for SshAgentPacketDecoder {
                         let message = src.split_to(src.len()).freeze();
 
                         match message_type {
-                            SSH_AGENTC_REQUEST_IDENTITIES | SSH_AGENTC_SIGN_RESPONSE => {
+                            SSH_AGENTC_REQUEST_IDENTITIES | SSH_AGENTC_SIGN_REQUEST => {
                                 self.state = SshAgentPacketDecoderState::WaitingHeader;
                                 return Ok(Some(message));
                             }
@@ -96,15 +102,118 @@ impl Decoder for SshAgentPacketDecoder {
     }
 }
 
+/// An in-process ssh agent holding private keys loaded from files, answering identity and
+/// signature requests without a running `ssh-agent`.
+#[derive(Debug)]
+pub(crate) struct SshKeyAgent {
+    keys: Vec<(Vec<u8>, PrivateKey)>,
+}
+
+fn try_sign<K: SigningKey>(key: &K, data: &[u8]) -> Option<Signature> {
+    key.try_sign(data).ok()
+}
+
+fn get_string<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+    if buf.remaining() < 4 {
+        return None;
+    }
+    let len = buf.get_u32() as usize;
+    if buf.remaining() < len {
+        return None;
+    }
+    let (value, rest) = buf.split_at(len);
+    *buf = rest;
+    Some(value)
+}
+
+fn put_string(buf: &mut BytesMut, value: &[u8]) {
+    buf.put_u32(value.len() as u32);
+    buf.put_slice(value);
+}
+
+impl SshKeyAgent {
+    pub(crate) fn new(keys: Vec<PrivateKey>) -> Result<Self, GrpcSshError> {
+        let keys = keys
+            .into_iter()
+            .map(|key| {
+                let blob = key.public_key().to_bytes().map_err(|e| {
+                    GrpcSshError::SshAgentSocketInit(format!("invalid ssh key: {e}"))
+                })?;
+                Ok((blob, key))
+            })
+            .collect::<Result<_, GrpcSshError>>()?;
+
+        Ok(Self { keys })
+    }
+
+    /// Answer a single agent request, given without its length prefix, with a length prefixed
+    /// response.
+    pub(crate) fn handle(&self, mut request: &[u8]) -> Bytes {
+        let body = match request.first().copied() {
+            Some(SSH_AGENTC_REQUEST_IDENTITIES) => Some(self.identities()),
+            Some(SSH_AGENTC_SIGN_REQUEST) => {
+                request.advance(1);
+                self.sign(request)
+            }
+            _ => None,
+        };
+        let body = body.unwrap_or_else(|| Bytes::from_static(&[SSH_AGENT_FAILURE]));
+
+        let mut response = BytesMut::with_capacity(body.len() + 4);
+        put_string(&mut response, &body);
+        response.freeze()
+    }
+
+    fn identities(&self) -> Bytes {
+        let mut body = BytesMut::new();
+        body.put_u8(SSH_AGENT_IDENTITIES_ANSWER);
+        body.put_u32(self.keys.len() as u32);
+        for (blob, key) in &self.keys {
+            put_string(&mut body, blob);
+            put_string(&mut body, key.comment().as_bytes());
+        }
+        body.freeze()
+    }
+
+    fn sign(&self, mut request: &[u8]) -> Option<Bytes> {
+        let blob = get_string(&mut request)?;
+        let data = get_string(&mut request)?;
+        let flags = if request.remaining() >= 4 {
+            request.get_u32()
+        } else {
+            0
+        };
+
+        let (_, key) = self.keys.iter().find(|(b, _)| b == blob)?;
+
+        // RSA keys are only signed with SHA-512, refuse the SHA-1 and SHA-256 variants rather
+        // than answering with a different algorithm than the one requested
+        if matches!(key.algorithm(), Algorithm::Rsa { .. }) && flags & SSH_AGENT_RSA_SHA2_512 == 0 {
+            log::warn!("sshforward only supports rsa-sha2-512 signatures for rsa keys");
+            return None;
+        }
+
+        let signature = Vec::<u8>::try_from(try_sign(key, data)?).ok()?;
+
+        let mut body = BytesMut::new();
+        body.put_u8(SSH_AGENT_SIGN_RESPONSE);
+        put_string(&mut body, &signature);
+        Some(body.freeze())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use bytes::BytesMut;
     use tokio_util::codec::Decoder;
 
-    use crate::grpc::ssh::{SSH_AGENTC_EXTENSION, SSH_AGENTC_REQUEST_IDENTITIES};
+    use crate::grpc::ssh::{
+        SSH_AGENTC_EXTENSION, SSH_AGENTC_REQUEST_IDENTITIES, SSH_AGENTC_SIGN_REQUEST,
+        SSH_AGENT_FAILURE, SSH_AGENT_IDENTITIES_ANSWER, SSH_AGENT_SIGN_RESPONSE,
+    };
 
-    use super::SshAgentPacketDecoder;
+    use super::{get_string, put_string, SshAgentPacketDecoder, SshKeyAgent};
 
     #[test]
     fn test_sshforward_message_identities_answer() {
@@ -151,4 +260,59 @@ mod tests {
 
         assert!(codec.decode(&mut buf).is_err());
     }
+
+    #[test]
+    fn test_sshforward_arbitrary_bytes() {
+        for seed in 1..2000 {
+            crate::read::tests::decode_all(
+                SshAgentPacketDecoder::new(),
+                crate::read::tests::arbitrary_chunks(seed),
+            );
+        }
+    }
+
+    #[test]
+    fn test_ssh_key_agent() {
+        use bytes::{Buf, BufMut};
+        use ssh_key::{Algorithm, PrivateKey, Signature};
+
+        let key = PrivateKey::random(&mut rand::rngs::OsRng, Algorithm::Ed25519).unwrap();
+        let blob = key.public_key().to_bytes().unwrap();
+        let agent = SshKeyAgent::new(vec![key]).unwrap();
+
+        let mut response = agent.handle(&[SSH_AGENTC_REQUEST_IDENTITIES]);
+        assert_eq!(response.get_u32() as usize, response.remaining());
+        assert_eq!(response.get_u8(), SSH_AGENT_IDENTITIES_ANSWER);
+        assert_eq!(response.get_u32(), 1);
+        let mut rest = &response[..];
+        assert_eq!(get_string(&mut rest), Some(&blob[..]));
+
+        let mut request = BytesMut::new();
+        request.put_u8(SSH_AGENTC_SIGN_REQUEST);
+        put_string(&mut request, &blob);
+        put_string(&mut request, b"session data");
+        request.put_u32(0);
+
+        let mut response = agent.handle(&request);
+        response.advance(4);
+        assert_eq!(response.get_u8(), SSH_AGENT_SIGN_RESPONSE);
+        let mut rest = &response[..];
+        let signature = Signature::try_from(get_string(&mut rest).unwrap()).unwrap();
+        assert_eq!(signature.algorithm(), Algorithm::Ed25519);
+
+        let mut request = BytesMut::new();
+        request.put_u8(SSH_AGENTC_SIGN_REQUEST);
+        put_string(&mut request, b"unknown key");
+        put_string(&mut request, b"session data");
+        request.put_u32(0);
+
+        assert_eq!(
+            &agent.handle(&request)[..],
+            &[0, 0, 0, 1, SSH_AGENT_FAILURE][..]
+        );
+        assert_eq!(
+            &agent.handle(&[SSH_AGENTC_EXTENSION])[..],
+            &[0, 0, 0, 1, SSH_AGENT_FAILURE][..]
+        );
+    }
 }
@@ -5,26 +5,35 @@ use bytes::Bytes;
 use futures_core::Stream;
 #[cfg(feature = "buildkit")]
 use futures_util::future::{Either, FutureExt};
-#[cfg(feature = "buildkit")]
 use futures_util::stream;
-use futures_util::stream::StreamExt;
+use futures_util::stream::{StreamExt, TryStreamExt};
 use http::header::CONTENT_TYPE;
 use http::request::Builder;
 use http_body_util::Full;
 use hyper::Method;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_repr::*;
 
 use super::Docker;
-use crate::auth::{DockerCredentials, DockerCredentialsHeader};
+use crate::auth::{DockerCredentials, DockerCredentialsHeader, RegistryConfig};
 use crate::container::Config;
-use crate::docker::{body_stream, BodyType};
+use crate::docker::{body_stream, body_try_stream, BodyType};
 use crate::errors::Error;
 use crate::models::*;
+use crate::read::BodyStream;
 
 use std::cmp::Eq;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "image_builder")]
+pub mod builder;
+pub mod diff;
+#[cfg(feature = "verify_digest")]
+pub mod digest;
+#[cfg(feature = "git_context")]
+pub mod git;
 
 /// Parameters available for pulling an image, used in the [Create Image
 /// API](Docker::create_image)
@@ -68,7 +77,7 @@ where
     /// Tag or digest. If empty when pulling an image, this causes all tags for the given image to
     /// be pulled.
     pub tag: T,
-    /// Platform in the format `os[/arch[/variant]]`
+    /// Platform in the format `os[/arch[/variant]]`, see [Platform](crate::platform::Platform).
     pub platform: T,
     /// A list of Dockerfile instructions to be applied to the image being created. Changes must be
     /// URL-encoded! This parameter may only be used when importing an image.
@@ -170,6 +179,49 @@ where
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// Parameters to the [Prune Build Cache API](Docker::prune_build_cache())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::image::PruneBuildCacheOptions;
+///
+/// use std::collections::HashMap;
+///
+/// let mut filters = HashMap::new();
+/// filters.insert("until", vec!["24h"]);
+///
+/// PruneBuildCacheOptions{
+///   keep_storage: Some(512 * 1024 * 1024),
+///   all: false,
+///   filters,
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PruneBuildCacheOptions<T>
+where
+    T: Into<String> + Eq + Hash + Serialize,
+{
+    /// Amount of disk space in bytes to keep for the build cache.
+    #[serde(rename = "keep-storage", skip_serializing_if = "Option::is_none")]
+    pub keep_storage: Option<i64>,
+    /// Remove all types of build cache, rather than only unused and internal records.
+    pub all: bool,
+    /// Filters to process on the prune list, encoded as JSON. Available filters:
+    ///  - `until=<timestamp>` Prune build cache last used before this timestamp. The
+    ///    `<timestamp>` can be Unix timestamps, date formatted timestamps, or Go duration strings
+    ///    (e.g. `10m`, `1h30m`) computed relative to the daemon machine’s time.
+    ///  - `id=<id>`
+    ///  - `parent=<id>`
+    ///  - `type=<string>`
+    ///  - `description=<string>`
+    ///  - `inuse`
+    ///  - `shared`
+    ///  - `private`
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    pub filters: HashMap<T, Vec<T>>,
+}
+
 /// Parameters to the [Search Images API](Docker::search_images())
 ///
 /// ## Example
@@ -375,7 +427,10 @@ where
     /// that file. If the URI points to a tarball, the file is downloaded by the daemon and the
     /// contents therein used as the context for the build. If the URI points to a tarball and the
     /// `dockerfile` parameter is also specified, there must be a file with the corresponding path
-    /// inside the tarball.
+    /// inside the tarball. A Git repository URI takes an optional branch, tag or commit, and
+    /// subdirectory, e.g. `https://github.com/moby/buildkit.git#v0.13.0:examples`; credentials of
+    /// private repositories are part of the URI. With the `git_context` feature, `image::git`
+    /// clones the repository locally instead.
     pub remote: T,
     /// Suppress verbose build output.
     pub q: bool,
@@ -421,13 +476,20 @@ where
     /// `bridge`, `host`, `none`, and `container:<name|id>`. Any other value is taken as a custom network's
     /// name to which this container should connect to.
     pub networkmode: T,
-    /// Platform in the format `os[/arch[/variant]]`
+    /// Platform in the format `os[/arch[/variant]]`, see [Platform](crate::platform::Platform).
     pub platform: T,
     /// Target build stage
     pub target: T,
     #[cfg(feature = "buildkit")]
     /// Specify a custom exporter.
     pub outputs: Option<ImageBuildOutput<T>>,
+    #[cfg(feature = "buildkit")]
+    /// Secrets exposed to `RUN --mount=type=secret` instructions, keyed by secret ID. Secrets are
+    /// only supported by the BuildKit builder: a classic build with secrets fails with an
+    /// [`UnsupportedFeatureError`](Error::UnsupportedFeatureError) rather than building without
+    /// them.
+    #[serde(skip)]
+    pub secrets: HashMap<String, crate::grpc::build::SecretSource>,
     /// Builder version to use
     pub version: BuilderVersion,
 }
@@ -519,6 +581,46 @@ pub struct ImportImageOptions {
     pub quiet: bool,
 }
 
+/// Progress of the [Copy Image API](Docker::copy_image_to()).
+#[derive(Debug, Clone, PartialEq)]
+pub enum CopyImageProgress {
+    /// Bytes of the archive streamed from the source daemon to the destination daemon so far.
+    Transferred {
+        /// Total bytes streamed.
+        bytes: u64,
+    },
+    /// A message of the destination daemon while it loads the archive.
+    Loading(Box<BuildInfo>),
+}
+
+/// Extract the image reference from a `Loaded image: ...` message emitted by the [Import Image
+/// API](Docker::import_image()).
+///
+/// Returns the tag for tagged images, or the image ID (`sha256:...`) for untagged images, and
+/// `None` for progress messages.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::image::loaded_image_ref;
+/// use bollard::models::BuildInfo;
+///
+/// let info = BuildInfo {
+///     stream: Some(String::from("Loaded image: hello-world:latest\n")),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(loaded_image_ref(&info), Some("hello-world:latest"));
+/// ```
+pub fn loaded_image_ref(info: &BuildInfo) -> Option<&str> {
+    let message = info.stream.as_deref()?.trim();
+    message
+        .strip_prefix("Loaded image ID: ")
+        .or_else(|| message.strip_prefix("Loaded image: "))
+        .map(str::trim)
+        .filter(|reference| !reference.is_empty())
+}
+
 impl Docker {
     /// ---
     ///
@@ -562,6 +664,50 @@ impl Docker {
     ) -> Result<Vec<ImageSummary>, Error>
     where
         T: Into<String> + Eq + Hash + Serialize,
+    {
+        self.list_images_as(options).await
+    }
+
+    /// ---
+    ///
+    /// # List Images As
+    ///
+    /// List the images, like the [List Images API](Docker::list_images()), deserialized into a type of the
+    /// caller, e.g. a slim struct with only the fields it needs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [List Images Options](ListImagesOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of the deserialized items, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::ListImagesOptions;
+    ///
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Image {
+    ///     id: String,
+    ///     size: i64,
+    /// }
+    ///
+    /// docker.list_images_as::<_, Image>(None::<ListImagesOptions<String>>);
+    /// ```
+    pub async fn list_images_as<T, R>(
+        &self,
+        options: Option<ListImagesOptions<T>>,
+    ) -> Result<Vec<R>, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+        R: DeserializeOwned,
     {
         let url = "/images/json";
 
@@ -575,6 +721,57 @@ impl Docker {
         self.process_into_value(req).await
     }
 
+    /// ---
+    ///
+    /// # List Images Stream
+    ///
+    /// Returns the images on the server one at a time, decoding them as the response arrives
+    /// rather than holding the whole list in memory.
+    ///
+    /// # Arguments
+    ///
+    ///  - An optional [List Images Options](ListImagesOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Stream of [API Images](ImageSummary).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::ListImagesOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async move {
+    ///     let size: i64 = docker
+    ///         .list_images_stream(None::<ListImagesOptions<String>>)
+    ///         .try_fold(0, |size, image| async move { Ok(size + image.size) })
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    pub fn list_images_stream<T>(
+        &self,
+        options: Option<ListImagesOptions<T>>,
+    ) -> impl Stream<Item = Result<ImageSummary, Error>> + Unpin
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/images/json";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            options,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        self.process_into_array_stream(req)
+    }
+
     /// ---
     ///
     /// # Create Image
@@ -672,6 +869,44 @@ impl Docker {
     /// docker.inspect_image("hello-world");
     /// ```
     pub async fn inspect_image(&self, image_name: &str) -> Result<ImageInspect, Error> {
+        self.inspect_image_as(image_name).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Image As
+    ///
+    /// Inspect an image, like the [Inspect Image API](Docker::inspect_image()), deserialized into a type of the
+    /// caller, e.g. a slim struct with only the fields it needs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Image name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - The deserialized response, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Image {
+    ///     id: String,
+    ///     repo_digests: Vec<String>,
+    /// }
+    ///
+    /// docker.inspect_image_as::<Image>("hello-world");
+    /// ```
+    pub async fn inspect_image_as<R>(&self, image_name: &str) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+    {
         let url = format!("/images/{image_name}/json");
 
         let req = self.build_request(
@@ -773,6 +1008,105 @@ impl Docker {
         self.process_into_value(req).await
     }
 
+    /// ---
+    ///
+    /// # Prune Build Cache
+    ///
+    /// Delete the builder cache.
+    ///
+    /// # Arguments
+    ///
+    /// - An optional [Prune Build Cache Options](PruneBuildCacheOptions) struct. The options
+    ///   require API version 1.39.
+    ///
+    /// # Returns
+    ///
+    ///  - a [Build Prune Response](BuildPruneResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::PruneBuildCacheOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("until", vec!["24h"]);
+    ///
+    /// let options = Some(PruneBuildCacheOptions {
+    ///   filters,
+    ///   ..Default::default()
+    /// });
+    ///
+    /// docker.prune_build_cache(options);
+    /// ```
+    pub async fn prune_build_cache<T>(
+        &self,
+        options: Option<PruneBuildCacheOptions<T>>,
+    ) -> Result<BuildPruneResponse, Error>
+    where
+        T: Into<String> + Eq + Hash + Serialize,
+    {
+        let url = "/build/prune";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            options,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # List Build Cache
+    ///
+    /// List the records of the builder cache, as reported by the data usage endpoint. Requires API
+    /// version 1.31; from API version 1.42 only the build cache usage is computed.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Build Cache](BuildCache), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.list_build_cache();
+    /// ```
+    pub async fn list_build_cache(&self) -> Result<Vec<BuildCache>, Error> {
+        let version = self.client_version();
+        let api_version = (version.major_version, version.minor_version);
+        if api_version < (1, 31) {
+            return Err(Error::UnsupportedFeatureError {
+                message: format!(
+                    "listing the build cache requires API version 1.31, not {version}"
+                ),
+            });
+        }
+
+        let query = if api_version >= (1, 42) {
+            Some([("type", "build-cache")])
+        } else {
+            None
+        };
+
+        let req = self.build_request(
+            "/system/df",
+            Builder::new().method(Method::GET),
+            query,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        let usage: SystemDataUsageResponse = self.process_into_value(req).await?;
+        Ok(usage.build_cache.unwrap_or_default())
+    }
+
     /// ---
     ///
     /// # Image History
@@ -1089,7 +1423,7 @@ impl Docker {
             url,
             Builder::new().method(Method::POST),
             Some(options),
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_value(req).await
@@ -1110,7 +1444,9 @@ impl Docker {
     /// # Arguments
     ///
     ///  - [Build Image Options](BuildImageOptions) struct.
-    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///  - Optional [Registry Config](RegistryConfig) map of the credentials of every registry the
+    ///    build pulls from, e.g. loaded with
+    ///    [registry_config_from_docker_config](crate::auth::registry_config_from_docker_config()).
     ///  - Tar archive compressed with one of the following algorithms: identity (no compression),
     ///    gzip, bzip2, xz. Optional [Hyper Body](hyper::body::Body).
     ///
@@ -1180,7 +1516,7 @@ impl Docker {
     pub fn build_image<T>(
         &self,
         options: BuildImageOptions<T>,
-        credentials: Option<HashMap<String, DockerCredentials>>,
+        credentials: Option<RegistryConfig>,
         tar: Option<BodyType>,
     ) -> impl Stream<Item = Result<BuildInfo, Error>> + '_
     where
@@ -1206,6 +1542,7 @@ impl Docker {
             ) => {
                 let session_id = String::clone(sess);
                 let outputs = options.outputs.clone().map(ImageBuildOutput::into_string);
+                let secrets = options.secrets.clone();
 
                 let req = self.build_request(
                     url,
@@ -1213,11 +1550,11 @@ impl Docker {
                         .method(Method::POST)
                         .header(CONTENT_TYPE, "application/x-tar"),
                     Some(options),
-                    Ok(tar.unwrap()),
+                    Ok(tar.unwrap_or_else(|| BodyType::Left(Full::new(Bytes::new())))),
                 );
 
                 let session = stream::once(
-                    self.start_session(session_id, creds, outputs)
+                    self.start_session(session_id, creds, outputs, secrets)
                         .map(|_| Either::Right(()))
                         .fuse(),
                 );
@@ -1244,6 +1581,15 @@ impl Docker {
             (ImageBuildBuildkitEither::Left(_), _) => unimplemented!(
                 "a buildkit enabled build without the 'buildkit' feature should not be possible"
             ),
+            #[cfg(feature = "buildkit")]
+            (ImageBuildBuildkitEither::Right(_), BuildImageOptions { secrets, .. })
+                if !secrets.is_empty() =>
+            {
+                stream::once(futures_util::future::err(Error::UnsupportedFeatureError {
+                    message: String::from("build secrets require the BuildKit builder version"),
+                }))
+                .boxed()
+            }
             (ImageBuildBuildkitEither::Right(creds), _) => {
                 let req = self.build_request_with_registry_auth(
                     url,
@@ -1251,7 +1597,7 @@ impl Docker {
                         .method(Method::POST)
                         .header(CONTENT_TYPE, "application/x-tar"),
                     Some(options),
-                    Ok(tar.unwrap()),
+                    Ok(tar.unwrap_or_else(|| BodyType::Left(Full::new(Bytes::new())))),
                     DockerCredentialsHeader::Config(creds),
                 );
 
@@ -1276,6 +1622,7 @@ impl Docker {
         id: String,
         credentials: Option<HashMap<String, DockerCredentials>>,
         outputs: Option<ImageBuildOutput<String>>,
+        secrets: HashMap<String, crate::grpc::build::SecretSource>,
     ) -> Result<(), crate::grpc::error::GrpcError> {
         let driver = crate::grpc::driver::moby::Moby::new(self);
 
@@ -1310,6 +1657,15 @@ impl Docker {
 
         services.push(crate::grpc::GrpcServer::Auth(auth));
 
+        if !secrets.is_empty() {
+            let secret_provider = crate::grpc::SecretProvider::new(secrets);
+            let secrets =
+                bollard_buildkit_proto::moby::buildkit::secrets::v1::secrets_server::SecretsServer::new(
+                    secret_provider,
+                );
+            services.push(crate::grpc::GrpcServer::Secrets(secrets));
+        }
+
         crate::grpc::driver::Driver::grpc_handle(driver, &id, services).await?;
 
         Ok(())
@@ -1333,8 +1689,8 @@ impl Docker {
     /// - The `image_name` string referring to an individual image and tag (e.g. alpine:latest)
     ///
     /// # Returns
-    ///  - An uncompressed TAR archive
-    pub fn export_image(&self, image_name: &str) -> impl Stream<Item = Result<Bytes, Error>> {
+    ///  - An uncompressed TAR archive, as a [Body Stream](crate::BodyStream).
+    pub fn export_image(&self, image_name: &str) -> BodyStream {
         let url = format!("/images/{image_name}/get");
         let req = self.build_request(
             &url,
@@ -1360,8 +1716,8 @@ impl Docker {
     /// - The `image_names` Vec of image names.
     ///
     /// # Returns
-    ///  - An uncompressed TAR archive
-    pub fn export_images(&self, image_names: &[&str]) -> impl Stream<Item = Result<Bytes, Error>> {
+    ///  - An uncompressed TAR archive, as a [Body Stream](crate::BodyStream).
+    pub fn export_images(&self, image_names: &[&str]) -> BodyStream {
         let options: Vec<_> = image_names.iter().map(|name| ("names", name)).collect();
         let req = self.build_request(
             "/images/get",
@@ -1546,6 +1902,150 @@ impl Docker {
             }
         })
     }
+
+    /// ---
+    ///
+    /// # Copy Image
+    ///
+    /// Copy an image to another daemon, streaming the archive of the [Export Image
+    /// API](Docker::export_image()) of this daemon into the [Import Image
+    /// API](Docker::import_image()) of the other one, without holding the archive in memory or on
+    /// disk, e.g. to promote images between air-gapped hosts.
+    ///
+    /// # Arguments
+    ///
+    ///  - The [Docker](Docker) client of the destination daemon.
+    ///  - The `image_name` string referring to an individual image and tag (e.g. alpine:latest).
+    ///  - [Image Import Options](ImportImageOptions) struct, for the destination daemon.
+    ///
+    /// # Returns
+    ///
+    ///  - [Copy Image Progress](CopyImageProgress), wrapped in an asynchronous Stream. The bytes
+    ///    transferred are reported as the archive is streamed, coalesced when the stream is polled
+    ///    less often than chunks arrive. A failure of the source daemon is reported as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::{CopyImageProgress, ImportImageOptions};
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// let staging = Docker::connect_with_http(
+    ///     "http://staging:2375", 120, bollard::API_DEFAULT_VERSION).unwrap();
+    ///
+    /// async move {
+    ///     let mut progress = docker.copy_image_to(&staging, "my-app:1.2", Default::default());
+    ///     while let Some(event) = progress.try_next().await.unwrap() {
+    ///         if let CopyImageProgress::Transferred { bytes } = event {
+    ///             println!("{bytes} bytes copied");
+    ///         }
+    ///     }
+    /// };
+    /// ```
+    pub fn copy_image_to(
+        &self,
+        other: &Docker,
+        image_name: &str,
+        options: ImportImageOptions,
+    ) -> impl Stream<Item = Result<CopyImageProgress, Error>> + Unpin {
+        let (transferred, watch_transferred) = tokio::sync::watch::channel(0u64);
+        let source_error = Arc::new(Mutex::new(None));
+
+        let archive = self.export_image(image_name).map({
+            let source_error = Arc::clone(&source_error);
+            let mut total = 0;
+            move |chunk| match chunk {
+                Ok(bytes) => {
+                    total += bytes.len() as u64;
+                    let _ = transferred.send(total);
+                    Ok(bytes)
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    *source_error.lock().unwrap() = Some(e);
+                    Err(std::io::Error::other(message))
+                }
+            }
+        });
+
+        let loading = other
+            .import_image(options, body_try_stream(archive), None)
+            .map(move |res| match res {
+                Ok(info) => Ok(CopyImageProgress::Loading(Box::new(info))),
+                Err(e) => Err(source_error.lock().unwrap().take().unwrap_or(e)),
+            });
+
+        let transfer = stream::unfold(watch_transferred, |mut watch_transferred| async move {
+            watch_transferred.changed().await.ok()?;
+            let bytes = *watch_transferred.borrow_and_update();
+            Some((
+                Ok(CopyImageProgress::Transferred { bytes }),
+                watch_transferred,
+            ))
+        });
+
+        stream::select(transfer, loading).boxed()
+    }
+
+    /// ---
+    ///
+    /// # Import Image (loaded references)
+    ///
+    /// Load a set of images and tags into a repository, and resolve the references of the loaded
+    /// images once the load completes. The references are taken from the `Loaded image: ...`
+    /// messages of the daemon, which are emitted regardless of the `quiet` option, so a quiet
+    /// import skips the progress details but still resolves the result.
+    ///
+    /// To observe the progress of the load, use the [Import Image
+    /// API](Docker::import_image()) directly and match each message with
+    /// [`loaded_image_ref`].
+    ///
+    /// # Arguments
+    ///  - [Image Import Options](ImportImageOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of loaded image tags, or image IDs for untagged images, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::ImportImageOptions;
+    /// use bollard::body_full;
+    ///
+    /// use tokio::fs::read;
+    ///
+    /// async move {
+    ///     let archive = read("tarball.tar").await.unwrap();
+    ///
+    ///     let loaded = docker
+    ///         .import_image_loaded(
+    ///             ImportImageOptions { quiet: true },
+    ///             body_full(archive.into()),
+    ///             None,
+    ///         )
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    pub async fn import_image_loaded(
+        &self,
+        options: ImportImageOptions,
+        root_fs: BodyType,
+        credentials: Option<HashMap<String, DockerCredentials>>,
+    ) -> Result<Vec<String>, Error> {
+        self.import_image(options, root_fs, credentials)
+            .try_fold(Vec::new(), |mut loaded, info| async move {
+                loaded.extend(loaded_image_ref(&info).map(String::from));
+                Ok(loaded)
+            })
+            .await
+    }
 }
 
 #[cfg(not(windows))]
@@ -1554,6 +2054,7 @@ mod tests {
 
     use std::io::Write;
 
+    use bytes::Bytes;
     use futures_util::TryStreamExt;
     use yup_hyper_mock::HostToReplyConnector;
 
@@ -1562,7 +2063,7 @@ mod tests {
         Docker, API_DEFAULT_VERSION,
     };
 
-    use super::CreateImageOptions;
+    use super::{CopyImageProgress, CreateImageOptions, ImportImageOptions};
 
     #[tokio::test]
     async fn test_create_image_with_error() {
@@ -1669,4 +2170,125 @@ mod tests {
             Err(crate::errors::Error::DockerStreamError { error: _ })
         ));
     }
+
+    #[tokio::test]
+    async fn test_import_image_loaded() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\n\r\n{\"status\":\"Loading layer\",\"progressDetail\":{\"current\":512,\"total\":1024},\"id\":\"e07ee1baac5f\"}\n{\"stream\":\"Loaded image: hello-world:latest\\n\"}\n{\"stream\":\"Loaded image ID: sha256:d2c94e258dcb3c5ac2798d32e1249e42ef01cba4841c2234249495f87264ac5a\\n\"}\n".to_string());
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let loaded = docker
+            .import_image_loaded(
+                ImportImageOptions { quiet: true },
+                crate::body_full(Bytes::new()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            loaded,
+            vec![
+                "hello-world:latest",
+                "sha256:d2c94e258dcb3c5ac2798d32e1249e42ef01cba4841c2234249495f87264ac5a"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inspect_image_containerd_store() {
+        let inspect: crate::models::ImageInspect = serde_json::from_str(
+            r#"{"Id":"sha256:1b44b5a3e06a9aae883e7bf25e45c100be0bb81a0e01b32de604f3ac44711634","Descriptor":{"mediaType":"application/vnd.oci.image.index.v1+json","digest":"sha256:1b44b5a3e06a9aae883e7bf25e45c100be0bb81a0e01b32de604f3ac44711634","size":10229},"GraphDriver":{"Data":null}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            inspect.descriptor.unwrap().media_type.as_deref(),
+            Some("application/vnd.oci.image.index.v1+json")
+        );
+        assert_eq!(inspect.graph_driver.unwrap().name, "");
+    }
+
+    #[tokio::test]
+    async fn test_export_image_body() {
+        use http_body_util::BodyExt;
+
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/x-tar\r\nContent-Length:7\r\n\r\ntarball".to_string(),
+        );
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let chunks: Vec<Bytes> = docker.export_image("alpine").try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), b"tarball");
+
+        let body = docker
+            .export_image("alpine")
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes();
+        assert_eq!(body, Bytes::from_static(b"tarball"));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_copy_image_to() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let archive = "x".repeat(4096);
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!("HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/x-tar\r\nContent-Length:{}\r\n\r\n{archive}", archive.len()),
+        );
+        let source =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        // the destination reads the whole archive before it answers, as the daemon does
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let daemon = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 8192];
+            while !request.ends_with(b"0\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let body = "{\"stream\":\"Loaded image: alpine:latest\\n\"}";
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}", body.len());
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).into_owned()
+        });
+        let destination =
+            Docker::connect_with_http(&format!("tcp://{addr}"), 5, API_DEFAULT_VERSION).unwrap();
+
+        let progress: Vec<_> = source
+            .copy_image_to(&destination, "alpine", Default::default())
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert!(progress.contains(&CopyImageProgress::Loading(Box::new(
+            crate::models::BuildInfo {
+                stream: Some(String::from("Loaded image: alpine:latest\n")),
+                ..Default::default()
+            }
+        ))));
+        assert!(progress.contains(&CopyImageProgress::Transferred {
+            bytes: archive.len() as u64
+        }));
+        let request = daemon.await.unwrap();
+        assert!(request.starts_with("POST /images/load"));
+    }
 }
@@ -0,0 +1,517 @@
+//! Image builder: assemble an image from a base image and programmatic layers, without running a
+//! Dockerfile build.
+//!
+//! The base image is exported through the [Export Image API](Docker::export_image()), new layers
+//! and configuration are appended, and the result is loaded back through the [Import Image
+//! API](Docker::import_image()). The archives are assembled in memory, so this is intended for
+//! small sidecar or test images.
+
+use bytes::Bytes;
+use futures_util::stream::TryStreamExt;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use super::ImportImageOptions;
+use crate::docker::body_full;
+use crate::errors::Error;
+use crate::Docker;
+
+/// Description attached to the image history for every layer added by the builder.
+const HISTORY_CREATED_BY: &str = "bollard image builder";
+
+/// Internal model: an entry of the `manifest.json` file in a `docker save` archive.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ArchiveManifest {
+    config: String,
+    #[serde(default)]
+    repo_tags: Option<Vec<String>>,
+    layers: Vec<String>,
+}
+
+fn archive_error<S: Into<String>>(message: S) -> Error {
+    Error::ImageArchiveError {
+        message: message.into(),
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+fn append_file(
+    builder: &mut tar::Builder<Vec<u8>>,
+    path: &str,
+    mode: u32,
+    contents: &[u8],
+) -> Result<(), Error> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(mode);
+    header.set_cksum();
+    builder.append_data(&mut header, path, contents)?;
+    Ok(())
+}
+
+/// A single filesystem layer, assembled from individual files.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::image::builder::LayerBuilder;
+///
+/// let layer = LayerBuilder::new()
+///     .file("/etc/motd", "hello")
+///     .file_with_mode("/usr/local/bin/entrypoint.sh", "#!/bin/sh\necho hi\n", 0o755);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LayerBuilder {
+    files: Vec<(String, u32, Bytes)>,
+}
+
+impl LayerBuilder {
+    /// Construct an empty layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a regular file with mode `0644`.
+    pub fn file(self, path: &str, contents: impl Into<Bytes>) -> Self {
+        self.file_with_mode(path, contents, 0o644)
+    }
+
+    /// Add a regular file with the given unix permission bits.
+    pub fn file_with_mode(mut self, path: &str, contents: impl Into<Bytes>, mode: u32) -> Self {
+        self.files.push((
+            String::from(path.trim_start_matches('/')),
+            mode,
+            contents.into(),
+        ));
+        self
+    }
+
+    /// Consume the builder and produce an uncompressed tar archive of the layer.
+    pub fn into_tar(self) -> Result<Bytes, Error> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, mode, contents) in &self.files {
+            append_file(&mut builder, path, *mode, contents)?;
+        }
+        Ok(Bytes::from(builder.into_inner()?))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Layer {
+    Tar(Bytes),
+    Files(LayerBuilder),
+}
+
+/// Assemble an image from a base image (or `scratch`) and programmatic layers, then load it into
+/// the docker daemon.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::image::builder::{ImageBuilder, LayerBuilder};
+///
+/// async move {
+///     let id = ImageBuilder::from_image("alpine")
+///         .layer(LayerBuilder::new().file("/etc/app.conf", "debug = true"))
+///         .env("APP_CONFIG", "/etc/app.conf")
+///         .label("maintainer", "bollard")
+///         .entrypoint(vec!["/bin/sh", "-c"])
+///         .load(&docker, "my-sidecar:latest")
+///         .await
+///         .unwrap();
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ImageBuilder {
+    base: Option<String>,
+    layers: Vec<Layer>,
+    env: Vec<(String, String)>,
+    entrypoint: Option<Vec<String>>,
+    cmd: Option<Vec<String>>,
+    labels: HashMap<String, String>,
+    working_dir: Option<String>,
+    user: Option<String>,
+}
+
+impl ImageBuilder {
+    /// Start from an empty image, equivalent to `FROM scratch`.
+    pub fn scratch() -> Self {
+        Self::default()
+    }
+
+    /// Start from an image that is present on the docker daemon.
+    pub fn from_image(base: &str) -> Self {
+        Self {
+            base: Some(String::from(base)),
+            ..Default::default()
+        }
+    }
+
+    /// Append a layer assembled from individual files.
+    pub fn layer(mut self, layer: LayerBuilder) -> Self {
+        self.layers.push(Layer::Files(layer));
+        self
+    }
+
+    /// Append a layer from an uncompressed tar archive.
+    pub fn layer_tar(mut self, tar: impl Into<Bytes>) -> Self {
+        self.layers.push(Layer::Tar(tar.into()));
+        self
+    }
+
+    /// Set an environment variable, replacing any value inherited from the base image.
+    pub fn env(mut self, key: &str, value: &str) -> Self {
+        self.env.retain(|(k, _)| k != key);
+        self.env.push((String::from(key), String::from(value)));
+        self
+    }
+
+    /// Set the entrypoint. As with a Dockerfile, this resets the command inherited from the base
+    /// image.
+    pub fn entrypoint<T: Into<String>>(mut self, entrypoint: Vec<T>) -> Self {
+        self.entrypoint = Some(entrypoint.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the default command.
+    pub fn cmd<T: Into<String>>(mut self, cmd: Vec<T>) -> Self {
+        self.cmd = Some(cmd.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set a label on the image.
+    pub fn label(mut self, key: &str, value: &str) -> Self {
+        self.labels.insert(String::from(key), String::from(value));
+        self
+    }
+
+    /// Set the working directory.
+    pub fn working_dir(mut self, working_dir: &str) -> Self {
+        self.working_dir = Some(String::from(working_dir));
+        self
+    }
+
+    /// Set the user that the image runs as.
+    pub fn user(mut self, user: &str) -> Self {
+        self.user = Some(String::from(user));
+        self
+    }
+
+    /// Assemble the image into a `docker save` compatible tar archive, tagged with `tag`.
+    ///
+    /// The base image, if any, is exported from `docker`.
+    pub async fn build(self, docker: &Docker, tag: &str) -> Result<Bytes, Error> {
+        let base = self.export_base(docker).await?;
+        Ok(self.assemble(base.as_deref(), tag)?.0)
+    }
+
+    /// Assemble the image and load it into the docker daemon, tagged with `tag`.
+    ///
+    /// # Returns
+    ///
+    ///  - The ID of the loaded image, wrapped in a Future.
+    pub async fn load(self, docker: &Docker, tag: &str) -> Result<String, Error> {
+        let base = self.export_base(docker).await?;
+        let (archive, config_digest) = self.assemble(base.as_deref(), tag)?;
+
+        let results = docker
+            .import_image(ImportImageOptions { quiet: true }, body_full(archive), None)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        if let Some(error) = results.into_iter().find_map(|info| info.error) {
+            return Err(Error::DockerStreamError { error });
+        }
+
+        Ok(format!("sha256:{config_digest}"))
+    }
+
+    async fn export_base(&self, docker: &Docker) -> Result<Option<Vec<u8>>, Error> {
+        match &self.base {
+            Some(base) => Ok(Some(
+                docker
+                    .export_image(base)
+                    .try_fold(Vec::new(), |mut acc, chunk| async move {
+                        acc.extend_from_slice(&chunk);
+                        Ok(acc)
+                    })
+                    .await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the archive, and the digest of the image config which doubles as the image ID.
+    fn assemble(&self, base: Option<&[u8]>, tag: &str) -> Result<(Bytes, String), Error> {
+        let (mut config, mut layers) = match base {
+            Some(base) => read_base_archive(base)?,
+            None => (scratch_config(), vec![]),
+        };
+
+        self.apply_config(&mut config)?;
+
+        for layer in &self.layers {
+            let layer = match layer {
+                Layer::Tar(tar) => tar.clone(),
+                Layer::Files(files) => files.clone().into_tar()?,
+            };
+            let diff_id = format!("sha256:{}", sha256_hex(&layer));
+            config["rootfs"]["diff_ids"]
+                .as_array_mut()
+                .ok_or_else(|| archive_error("missing rootfs.diff_ids in image config"))?
+                .push(Value::String(diff_id));
+            match config.get_mut("history").and_then(Value::as_array_mut) {
+                Some(history) => history.push(json!({ "created_by": HISTORY_CREATED_BY })),
+                None => config["history"] = json!([{ "created_by": HISTORY_CREATED_BY }]),
+            }
+            layers.push(layer);
+        }
+
+        let config = serde_json::to_vec(&config)?;
+        let config_digest = sha256_hex(&config);
+        let config_path = format!("{config_digest}.json");
+
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut layer_paths = Vec::with_capacity(layers.len());
+        for (index, layer) in layers.iter().enumerate() {
+            let path = format!("layers/{index}.tar");
+            append_file(&mut builder, &path, 0o644, layer)?;
+            layer_paths.push(path);
+        }
+        append_file(&mut builder, &config_path, 0o644, &config)?;
+
+        let manifest = vec![ArchiveManifest {
+            config: config_path,
+            repo_tags: Some(vec![String::from(tag)]),
+            layers: layer_paths,
+        }];
+        append_file(
+            &mut builder,
+            "manifest.json",
+            0o644,
+            &serde_json::to_vec(&manifest)?,
+        )?;
+
+        Ok((Bytes::from(builder.into_inner()?), config_digest))
+    }
+
+    fn apply_config(&self, config: &mut Value) -> Result<(), Error> {
+        let container_config = config
+            .as_object_mut()
+            .ok_or_else(|| archive_error("image config is not an object"))?
+            .entry("config")
+            .or_insert_with(|| json!({}));
+        if container_config.is_null() {
+            *container_config = json!({});
+        }
+
+        if !self.env.is_empty() {
+            let mut env: Vec<String> = container_config
+                .get("Env")
+                .and_then(Value::as_array)
+                .map(|env| {
+                    env.iter()
+                        .filter_map(Value::as_str)
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default();
+            for (key, value) in &self.env {
+                env.retain(|e| e.split_once('=').map(|(k, _)| k) != Some(key.as_str()));
+                env.push(format!("{key}={value}"));
+            }
+            container_config["Env"] = json!(env);
+        }
+
+        if let Some(entrypoint) = &self.entrypoint {
+            container_config["Entrypoint"] = json!(entrypoint);
+            container_config["Cmd"] = Value::Null;
+        }
+
+        if let Some(cmd) = &self.cmd {
+            container_config["Cmd"] = json!(cmd);
+        }
+
+        if !self.labels.is_empty() {
+            let labels = container_config
+                .as_object_mut()
+                .ok_or_else(|| archive_error("image config is not an object"))?
+                .entry("Labels")
+                .or_insert_with(|| json!({}));
+            if labels.is_null() {
+                *labels = json!({});
+            }
+            for (key, value) in &self.labels {
+                labels[key] = json!(value);
+            }
+        }
+
+        if let Some(working_dir) = &self.working_dir {
+            container_config["WorkingDir"] = json!(working_dir);
+        }
+
+        if let Some(user) = &self.user {
+            container_config["User"] = json!(user);
+        }
+
+        Ok(())
+    }
+}
+
+fn scratch_config() -> Value {
+    let architecture = match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "x86" => "386",
+        "powerpc64" => "ppc64le",
+        other => other,
+    };
+
+    json!({
+        "architecture": architecture,
+        "os": std::env::consts::OS,
+        "config": {},
+        "rootfs": {
+            "type": "layers",
+            "diff_ids": []
+        },
+        "history": []
+    })
+}
+
+fn read_archive_entries(archive: &[u8]) -> Result<HashMap<String, Vec<u8>>, Error> {
+    let mut entries = HashMap::new();
+    for entry in tar::Archive::new(archive).entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().to_string();
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut contents)?;
+        entries.insert(String::from(path.trim_start_matches("./")), contents);
+    }
+    Ok(entries)
+}
+
+fn read_manifest(entries: &HashMap<String, Vec<u8>>) -> Result<ArchiveManifest, Error> {
+    let manifest = entries
+        .get("manifest.json")
+        .ok_or_else(|| archive_error("missing manifest.json"))?;
+    serde_json::from_slice::<Vec<ArchiveManifest>>(manifest)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| archive_error("empty manifest.json"))
+}
+
+fn read_base_archive(archive: &[u8]) -> Result<(Value, Vec<Bytes>), Error> {
+    let mut entries = read_archive_entries(archive)?;
+    let manifest = read_manifest(&entries)?;
+
+    let config = entries
+        .get(&manifest.config)
+        .ok_or_else(|| archive_error(format!("missing image config {}", manifest.config)))?;
+    let config: Value = serde_json::from_slice(config)?;
+
+    let layers = manifest
+        .layers
+        .iter()
+        .map(|path| {
+            entries
+                .remove(path)
+                .map(Bytes::from)
+                .ok_or_else(|| archive_error(format!("missing layer {path}")))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok((config, layers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assemble_scratch_image() {
+        let archive = ImageBuilder::scratch()
+            .layer(LayerBuilder::new().file("/hello.txt", "hello"))
+            .env("GREETING", "hello")
+            .label("maintainer", "bollard")
+            .entrypoint(vec!["/hello"])
+            .assemble(None, "bollard-scratch:latest")
+            .unwrap()
+            .0;
+
+        let entries = read_archive_entries(&archive).unwrap();
+        let manifest = read_manifest(&entries).unwrap();
+
+        assert_eq!(
+            manifest.repo_tags,
+            Some(vec![String::from("bollard-scratch:latest")])
+        );
+        assert_eq!(manifest.layers.len(), 1);
+
+        let config: Value = serde_json::from_slice(&entries[&manifest.config]).unwrap();
+        let layer = &entries[&manifest.layers[0]];
+
+        assert_eq!(
+            config["rootfs"]["diff_ids"][0],
+            json!(format!("sha256:{}", sha256_hex(layer)))
+        );
+        assert_eq!(config["config"]["Env"], json!(["GREETING=hello"]));
+        assert_eq!(config["config"]["Labels"]["maintainer"], json!("bollard"));
+        assert_eq!(config["config"]["Entrypoint"], json!(["/hello"]));
+        assert_eq!(config["history"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_assemble_on_base_image() {
+        let base = ImageBuilder::scratch()
+            .layer(LayerBuilder::new().file("/base.txt", "base"))
+            .env("PATH", "/bin")
+            .env("KEEP", "1")
+            .cmd(vec!["/bin/sh"])
+            .assemble(None, "base:latest")
+            .unwrap()
+            .0;
+
+        let (archive, config_digest) = ImageBuilder::from_image("base:latest")
+            .layer(LayerBuilder::new().file("/top.txt", "top"))
+            .env("PATH", "/usr/bin")
+            .assemble(Some(&base), "derived:latest")
+            .unwrap();
+
+        let entries = read_archive_entries(&archive).unwrap();
+        let manifest = read_manifest(&entries).unwrap();
+        let config: Value = serde_json::from_slice(&entries[&manifest.config]).unwrap();
+
+        assert_eq!(manifest.layers.len(), 2);
+        assert_eq!(config["rootfs"]["diff_ids"].as_array().unwrap().len(), 2);
+        assert_eq!(config["config"]["Env"], json!(["KEEP=1", "PATH=/usr/bin"]));
+        assert_eq!(config["config"]["Cmd"], json!(["/bin/sh"]));
+        assert_eq!(config_digest, sha256_hex(&entries[&manifest.config]));
+    }
+
+    #[test]
+    fn test_missing_manifest() {
+        let archive = LayerBuilder::new()
+            .file("/hello.txt", "hello")
+            .into_tar()
+            .unwrap();
+
+        assert!(matches!(
+            ImageBuilder::from_image("base").assemble(Some(&archive), "derived"),
+            Err(Error::ImageArchiveError { .. })
+        ));
+    }
+}
@@ -0,0 +1,207 @@
+//! Compare the layers and configuration of two local images, f.e. to find out why a build did
+//! not reuse the cache of a previous build.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! async move {
+//!     let diff = docker.diff_images("my-app:1.0", "my-app:1.1").await.unwrap();
+//!     println!("{} shared layers", diff.common_layers);
+//!     for change in diff.changed_config {
+//!         println!("{}: {:?} -> {:?}", change.field, change.before, change.after);
+//!     }
+//! };
+//! ```
+
+use std::collections::BTreeSet;
+
+use serde_json::{Map, Value};
+
+use crate::errors::Error;
+use crate::models::ImageInspect;
+use crate::Docker;
+
+/// A configuration field that differs between two images, named as in the docker API, e.g.
+/// `Env` or `Cmd`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// The name of the configuration field.
+    pub field: String,
+    /// The field's value in the first image, if set.
+    pub before: Option<Value>,
+    /// The field's value in the second image, if set.
+    pub after: Option<Value>,
+}
+
+/// The differences between two images, as returned by the [Diff Images
+/// API](Docker::diff_images()).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageDiff {
+    /// The number of layers, from the base image up, shared by both images.
+    pub common_layers: usize,
+    /// Layer digests of the second image following the shared layers.
+    pub added_layers: Vec<String>,
+    /// Layer digests of the first image following the shared layers.
+    pub removed_layers: Vec<String>,
+    /// Configuration fields that differ between the images.
+    pub changed_config: Vec<ConfigChange>,
+}
+
+impl ImageDiff {
+    /// Compare two inspected images.
+    pub fn between(a: &ImageInspect, b: &ImageInspect) -> Self {
+        let layers_a = layers(a);
+        let layers_b = layers(b);
+
+        let common_layers = layers_a
+            .iter()
+            .zip(layers_b.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        ImageDiff {
+            common_layers,
+            added_layers: layers_b[common_layers..].to_vec(),
+            removed_layers: layers_a[common_layers..].to_vec(),
+            changed_config: changed_config(config(a), config(b)),
+        }
+    }
+
+    /// Whether both images have the same layers and configuration.
+    pub fn is_empty(&self) -> bool {
+        self.added_layers.is_empty()
+            && self.removed_layers.is_empty()
+            && self.changed_config.is_empty()
+    }
+}
+
+fn layers(image: &ImageInspect) -> &[String] {
+    image
+        .root_fs
+        .as_ref()
+        .and_then(|root_fs| root_fs.layers.as_deref())
+        .unwrap_or_default()
+}
+
+fn config(image: &ImageInspect) -> Map<String, Value> {
+    match image.config.as_ref().map(serde_json::to_value) {
+        Some(Ok(Value::Object(map))) => map,
+        _ => Map::new(),
+    }
+}
+
+fn changed_config(
+    mut before: Map<String, Value>,
+    mut after: Map<String, Value>,
+) -> Vec<ConfigChange> {
+    let fields: BTreeSet<String> = before.keys().chain(after.keys()).cloned().collect();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let before = before.remove(&field).filter(|v| !v.is_null());
+            let after = after.remove(&field).filter(|v| !v.is_null());
+            (before != after).then_some(ConfigChange {
+                field,
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Diff Images
+    ///
+    /// Compare the layers and configuration of two local images.
+    ///
+    /// # Arguments
+    ///
+    ///  - Name of the first image as a string slice.
+    ///  - Name of the second image as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - An [Image Diff](ImageDiff), wrapped in a Future. Layers are compared from the base
+    ///    image up, so that layers following the first differing layer are reported as added or
+    ///    removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.diff_images("my-app:1.0", "my-app:1.1");
+    /// ```
+    pub async fn diff_images(&self, image_a: &str, image_b: &str) -> Result<ImageDiff, Error> {
+        let a = self.inspect_image(image_a).await?;
+        let b = self.inspect_image(image_b).await?;
+
+        Ok(ImageDiff::between(&a, &b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ConfigChange, ImageDiff};
+    use crate::models::{ImageConfig, ImageInspect, ImageInspectRootFs};
+
+    fn image(layers: &[&str], env: Vec<&str>, cmd: Option<Vec<&str>>) -> ImageInspect {
+        ImageInspect {
+            root_fs: Some(ImageInspectRootFs {
+                typ: String::from("layers"),
+                layers: Some(layers.iter().map(|l| String::from(*l)).collect()),
+            }),
+            config: Some(ImageConfig {
+                env: Some(env.into_iter().map(String::from).collect()),
+                cmd: cmd.map(|cmd| cmd.into_iter().map(String::from).collect()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_image_diff() {
+        let a = image(
+            &["sha256:base", "sha256:deps", "sha256:app1"],
+            vec!["PATH=/bin"],
+            Some(vec!["app"]),
+        );
+        let b = image(
+            &["sha256:base", "sha256:deps2", "sha256:app2"],
+            vec!["PATH=/bin", "DEBUG=1"],
+            Some(vec!["app"]),
+        );
+
+        let diff = ImageDiff::between(&a, &b);
+        assert_eq!(diff.common_layers, 1);
+        assert_eq!(diff.added_layers, vec!["sha256:deps2", "sha256:app2"]);
+        assert_eq!(diff.removed_layers, vec!["sha256:deps", "sha256:app1"]);
+        assert_eq!(
+            diff.changed_config,
+            vec![ConfigChange {
+                field: String::from("Env"),
+                before: Some(json!(["PATH=/bin"])),
+                after: Some(json!(["PATH=/bin", "DEBUG=1"])),
+            }]
+        );
+        assert!(!diff.is_empty());
+
+        let c = image(&["sha256:base"], vec!["PATH=/bin"], None);
+        let diff = ImageDiff::between(&a, &c);
+        assert_eq!(diff.common_layers, 1);
+        assert!(diff.added_layers.is_empty());
+        assert_eq!(diff.changed_config[0].field, "Cmd");
+        assert_eq!(diff.changed_config[0].after, None);
+
+        assert!(ImageDiff::between(&a, &a).is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+//! Digest verification: check pulled and exported images against an expected content digest,
+//! e.g. a digest pinned in a lockfile, rather than trusting whatever a mutable tag points to.
+//!
+//! The [Create Image Verified API](Docker::create_image_verified()) pulls the image by its
+//! expected digest, so that the daemon checks the content it downloads, and only then tags it. A
+//! [Digest Stream](DigestStream) computes the `sha256` digest of an exported tarball while it is
+//! read.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::image::CreateImageOptions;
+//!
+//! use futures_util::stream::TryStreamExt;
+//!
+//! async move {
+//!     docker
+//!         .create_image_verified(
+//!             Some(CreateImageOptions {
+//!                 from_image: "alpine:3.20",
+//!                 ..Default::default()
+//!             }),
+//!             None,
+//!             "sha256:beefdbd8a1da6d2915566fde36db9db0b524eb737fc57cd1367effd16dc0d06d",
+//!         )
+//!         .try_collect::<Vec<_>>()
+//!         .await
+//!         .unwrap();
+//! };
+//! ```
+
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::future::Either;
+use futures_util::ready;
+use futures_util::stream::{self, StreamExt};
+use pin_project_lite::pin_project;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::{CreateImageOptions, TagImageOptions};
+use crate::auth::DockerCredentials;
+use crate::errors::Error;
+use crate::models::CreateImageInfo;
+use crate::read::BodyStream;
+use crate::reference::Reference;
+use crate::Docker;
+
+/// Prefix of the progress status carrying the manifest digest of a pulled image.
+const DIGEST_STATUS: &str = "Digest:";
+
+/// Format a digest as `sha256:<hex>`, accepting the bare hex digest as well.
+fn normalize(digest: &str) -> String {
+    let digest = digest.trim().to_lowercase();
+    if digest.contains(':') {
+        digest
+    } else {
+        format!("sha256:{digest}")
+    }
+}
+
+/// The manifest digest the daemon reports in the progress of a pull.
+fn reported_digest(info: &CreateImageInfo) -> Option<&str> {
+    info.status
+        .as_deref()?
+        .strip_prefix(DIGEST_STATUS)
+        .map(str::trim)
+}
+
+/// The reference to pull, and the options to pull its content by the expected digest rather than
+/// by tag.
+fn pin_reference<T>(
+    options: Option<&CreateImageOptions<'_, T>>,
+    expected: &str,
+) -> Result<(Reference, CreateImageOptions<'static, String>), Error>
+where
+    T: Into<String> + Serialize + Clone,
+{
+    let (from_image, tag, platform): (String, String, String) = options
+        .map(|options| {
+            (
+                options.from_image.clone().into(),
+                options.tag.clone().into(),
+                options.platform.clone().into(),
+            )
+        })
+        .unwrap_or_default();
+    let reference = match tag.as_str() {
+        "" => Reference::parse(&from_image)?,
+        digest if digest.contains(':') => Reference::parse(&format!("{from_image}@{digest}"))?,
+        tag => Reference::parse(&format!("{from_image}:{tag}"))?,
+    };
+    if let Some(digest) = reference.digest().map(normalize) {
+        if digest != expected {
+            return Err(Error::DigestMismatchError {
+                expected: String::from(expected),
+                actual: Some(digest),
+            });
+        }
+    }
+
+    let options = CreateImageOptions {
+        from_image: reference.name(),
+        tag: String::from(expected),
+        platform,
+        ..Default::default()
+    };
+    Ok((reference, options))
+}
+
+/// Pass on the progress of a pull, failing once the reported manifest digest does not match the
+/// expected digest, or when the pull ends without a digest being reported.
+fn verify_pull<S>(pull: S, expected: &str) -> impl Stream<Item = Result<CreateImageInfo, Error>>
+where
+    S: Stream<Item = Result<CreateImageInfo, Error>>,
+{
+    let state = (Box::pin(pull), normalize(expected), false);
+    stream::unfold(Some(state), |state| async move {
+        let (mut pull, expected, mut reported) = state?;
+        match pull.next().await {
+            Some(Ok(info)) => {
+                if let Some(digest) = reported_digest(&info) {
+                    let digest = normalize(digest);
+                    if digest != expected {
+                        let err = Error::DigestMismatchError {
+                            expected,
+                            actual: Some(digest),
+                        };
+                        return Some((Err(err), None));
+                    }
+                    reported = true;
+                }
+                Some((Ok(info), Some((pull, expected, reported))))
+            }
+            Some(Err(e)) => Some((Err(e), None)),
+            None if reported => None,
+            None => {
+                let err = Error::DigestMismatchError {
+                    expected,
+                    actual: None,
+                };
+                Some((Err(err), None))
+            }
+        }
+    })
+}
+
+pin_project! {
+    /// A stream of bytes that computes their `sha256` digest as they are read, e.g. of a tarball
+    /// from the [Export Image API](Docker::export_image()).
+    ///
+    /// With an [expected digest](DigestStream::expect()), the stream ends with a
+    /// [DigestMismatchError](Error::DigestMismatchError) if the digest of the bytes differs.
+    #[derive(Debug)]
+    pub struct DigestStream<S> {
+        #[pin]
+        inner: S,
+        hasher: Sha256,
+        expected: Option<String>,
+        digest: Option<String>,
+    }
+}
+
+impl<S> DigestStream<S> {
+    /// Compute the digest of the bytes of the stream.
+    pub fn new(inner: S) -> Self {
+        DigestStream {
+            inner,
+            hasher: Sha256::new(),
+            expected: None,
+            digest: None,
+        }
+    }
+
+    /// Fail at the end of the stream unless the bytes have the given digest, formatted as
+    /// `sha256:<hex>` or as the bare hex digest.
+    pub fn expect(mut self, digest: &str) -> Self {
+        self.expected = Some(normalize(digest));
+        self
+    }
+
+    /// The digest of the bytes, formatted as `sha256:<hex>`, once the stream has ended.
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+}
+
+impl<S> Stream for DigestStream<S>
+where
+    S: Stream<Item = Result<Bytes, Error>>,
+{
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if this.digest.is_some() {
+            return Poll::Ready(None);
+        }
+
+        match ready!(this.inner.poll_next(cx)) {
+            Some(Ok(bytes)) => {
+                this.hasher.update(&bytes);
+                Poll::Ready(Some(Ok(bytes)))
+            }
+            Some(Err(e)) => Poll::Ready(Some(Err(e))),
+            None => {
+                let digest = format!(
+                    "sha256:{}",
+                    hex::encode(std::mem::take(this.hasher).finalize())
+                );
+                *this.digest = Some(digest.clone());
+                match this.expected {
+                    Some(expected) if *expected != digest => {
+                        Poll::Ready(Some(Err(Error::DigestMismatchError {
+                            expected: expected.clone(),
+                            actual: Some(digest),
+                        })))
+                    }
+                    _ => Poll::Ready(None),
+                }
+            }
+        }
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Create Image Verified
+    ///
+    /// Pull an image, as the [Create Image API](Docker::create_image()) does, by an expected
+    /// digest rather than by tag: the daemon fails the pull unless the content matches the digest.
+    /// The requested tag, or `latest`, only points at the image once the pull has completed, and
+    /// the manifest digest the daemon reports is checked as well.
+    ///
+    /// The digest is the one shown by `docker pull` and `docker images --digests`: for a
+    /// multi-platform image, the digest of the image index rather than of the platform's manifest.
+    ///
+    /// # Arguments
+    ///
+    ///  - An optional [Create Image Options](CreateImageOptions) struct.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct.
+    ///  - The expected digest, formatted as `sha256:<hex>` or as the bare hex digest.
+    ///
+    /// # Returns
+    ///
+    ///  - [Create Image Info](CreateImageInfo), wrapped in an asynchronous Stream. The stream
+    ///    ends with a [DigestMismatchError](Error::DigestMismatchError) if the reported digest
+    ///    differs, or if the daemon does not report one, in which case the tag is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::image::CreateImageOptions;
+    ///
+    /// let options = Some(CreateImageOptions {
+    ///     from_image: "hello-world",
+    ///     ..Default::default()
+    /// });
+    ///
+    /// docker.create_image_verified(
+    ///     options,
+    ///     None,
+    ///     "sha256:d211f485f2dd1dee407a80973c8f129f00d54604d2c90732e8e320e5038a0348",
+    /// );
+    /// ```
+    pub fn create_image_verified<T>(
+        &self,
+        options: Option<CreateImageOptions<'_, T>>,
+        credentials: Option<DockerCredentials>,
+        digest: &str,
+    ) -> impl Stream<Item = Result<CreateImageInfo, Error>>
+    where
+        T: Into<String> + Serialize + std::fmt::Debug + Clone,
+    {
+        let expected = normalize(digest);
+        let (reference, options) = match pin_reference(options.as_ref(), &expected) {
+            Ok(pinned) => pinned,
+            Err(e) => return Either::Left(stream::once(async { Err(e) })),
+        };
+        let pinned = format!("{}@{expected}", reference.name());
+        let pull = verify_pull(
+            self.create_image(Some(options), None, credentials),
+            &expected,
+        );
+
+        // tag the image once the pull has completed and the digest has been checked
+        let docker = self.clone();
+        let state = (Box::pin(pull), docker, reference, pinned);
+        Either::Right(stream::unfold(Some(state), |state| async move {
+            let (mut pull, docker, reference, pinned) = state?;
+            match pull.next().await {
+                Some(Ok(info)) => Some((Ok(info), Some((pull, docker, reference, pinned)))),
+                Some(Err(e)) => Some((Err(e), None)),
+                None if reference.tag_or_default().is_none() => None,
+                None => docker
+                    .tag_image(&pinned, Some(TagImageOptions::from(&reference)))
+                    .await
+                    .err()
+                    .map(|e| (Err(e), None)),
+            }
+        }))
+    }
+
+    /// ---
+    ///
+    /// # Export Image With Digest
+    ///
+    /// Get a tarball of an image, as the [Export Image API](Docker::export_image()) does, and
+    /// compute its `sha256` digest while it is read.
+    ///
+    /// # Arguments
+    ///
+    ///  - The `image_name` string referring to an individual image and tag (e.g. alpine:latest)
+    ///
+    /// # Returns
+    ///
+    ///  - An uncompressed TAR archive, as a [Digest Stream](DigestStream) over a [Body
+    ///    Stream](BodyStream), which holds the digest of the archive once it has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use futures_util::stream::StreamExt;
+    ///
+    /// async move {
+    ///     let mut archive = docker.export_image_with_digest("alpine:latest");
+    ///     while let Some(chunk) = archive.next().await {
+    ///         let _ = chunk.unwrap();
+    ///     }
+    ///     println!("{}", archive.digest().unwrap());
+    /// };
+    /// ```
+    pub fn export_image_with_digest(&self, image_name: &str) -> DigestStream<BodyStream> {
+        DigestStream::new(self.export_image(image_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::stream::{self, StreamExt, TryStreamExt};
+    use sha2::{Digest, Sha256};
+
+    use super::{pin_reference, verify_pull, DigestStream};
+    use crate::errors::Error;
+    use crate::image::CreateImageOptions;
+    use crate::models::CreateImageInfo;
+
+    const DIGEST: &str = "sha256:d211f485f2dd1dee407a80973c8f129f00d54604d2c90732e8e320e5038a0348";
+
+    fn status(status: &str) -> Result<CreateImageInfo, Error> {
+        Ok(CreateImageInfo {
+            status: Some(String::from(status)),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_verify_pull() {
+        let pull = || {
+            stream::iter(vec![
+                status("Pulling from library/hello-world"),
+                status(&format!("Digest: {DIGEST}")),
+                status("Status: Downloaded newer image for hello-world:latest"),
+            ])
+        };
+
+        let progress = verify_pull(pull(), DIGEST.trim_start_matches("sha256:"))
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(progress.len(), 3);
+
+        let progress: Vec<_> = verify_pull(pull(), "sha256:beef").collect().await;
+        assert_eq!(progress.len(), 2);
+        assert!(matches!(
+            &progress[1],
+            Err(Error::DigestMismatchError { expected, actual: Some(actual) })
+                if expected == "sha256:beef" && actual == DIGEST
+        ));
+
+        let progress: Vec<_> = verify_pull(stream::iter(vec![status("Pulling fs layer")]), DIGEST)
+            .collect()
+            .await;
+        assert!(matches!(
+            progress.last(),
+            Some(Err(Error::DigestMismatchError { actual: None, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_pin_reference() {
+        let (reference, options) = pin_reference(
+            Some(&CreateImageOptions {
+                from_image: "alpine:3.20",
+                platform: "linux/arm64",
+                ..Default::default()
+            }),
+            DIGEST,
+        )
+        .unwrap();
+        assert_eq!(reference.to_string(), "docker.io/library/alpine:3.20");
+        assert_eq!(options.from_image, "docker.io/library/alpine");
+        assert_eq!(options.tag, DIGEST);
+        assert_eq!(options.platform, "linux/arm64");
+
+        let (reference, _) = pin_reference(
+            Some(&CreateImageOptions {
+                from_image: "hello-world",
+                tag: "linux",
+                ..Default::default()
+            }),
+            DIGEST,
+        )
+        .unwrap();
+        assert_eq!(reference.tag(), Some("linux"));
+
+        let (reference, _) = pin_reference(
+            Some(&CreateImageOptions {
+                from_image: "hello-world",
+                tag: DIGEST,
+                ..Default::default()
+            }),
+            DIGEST,
+        )
+        .unwrap();
+        assert_eq!(reference.tag_or_default(), None);
+
+        assert!(matches!(
+            pin_reference(
+                Some(&CreateImageOptions {
+                    from_image: format!("hello-world@{DIGEST}"),
+                    ..Default::default()
+                }),
+                "sha256:beef",
+            ),
+            Err(Error::DigestMismatchError { actual: Some(actual), .. }) if actual == DIGEST
+        ));
+        assert!(pin_reference(None::<&CreateImageOptions<'_, String>>, DIGEST).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_digest_stream() {
+        let chunks = || {
+            stream::iter(vec![
+                Ok(Bytes::from_static(b"manifest")),
+                Ok(Bytes::from_static(b".json")),
+            ])
+        };
+        let expected = format!("sha256:{}", hex::encode(Sha256::digest(b"manifest.json")));
+
+        let mut archive = DigestStream::new(chunks());
+        assert_eq!(archive.digest(), None);
+        let bytes: Vec<_> = (&mut archive).try_collect().await.unwrap();
+        assert_eq!(bytes.concat(), b"manifest.json");
+        assert_eq!(archive.digest(), Some(expected.as_str()));
+
+        let archive = DigestStream::new(chunks()).expect(&expected.to_uppercase());
+        assert!(archive.try_collect::<Vec<_>>().await.is_ok());
+
+        let archive = DigestStream::new(chunks()).expect(DIGEST);
+        assert!(matches!(
+            archive.try_collect::<Vec<_>>().await,
+            Err(Error::DigestMismatchError { actual: Some(actual), .. }) if actual == expected
+        ));
+    }
+}
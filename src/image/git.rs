@@ -0,0 +1,293 @@
+//! Git build context: shallow-clone a git repository and pack it into a build context tarball,
+//! for daemons that cannot reach the repository given as the `remote` of a build.
+//!
+//! Cloning runs the `git` binary of the local machine, which has to be on the `PATH`.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::errors::Error;
+
+/// Distinguishes the clones of a process.
+static CLONE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A git remote in the format of the `remote` of the [Build Image
+/// API](crate::Docker::build_image()): `<url>#<ref>:<subdir>`, both parts of the fragment being
+/// optional.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GitRemote<'a> {
+    url: &'a str,
+    reference: Option<&'a str>,
+    subdir: Option<&'a str>,
+}
+
+impl<'a> GitRemote<'a> {
+    fn parse(remote: &'a str) -> Self {
+        let (url, fragment) = match remote.split_once('#') {
+            Some((url, fragment)) => (url, fragment),
+            None => (remote, ""),
+        };
+        let (reference, subdir) = match fragment.split_once(':') {
+            Some((reference, subdir)) => (reference, subdir),
+            None => (fragment, ""),
+        };
+        GitRemote {
+            url,
+            reference: Some(reference).filter(|reference| !reference.is_empty()),
+            subdir: Some(subdir.trim_matches('/')).filter(|subdir| !subdir.is_empty()),
+        }
+    }
+}
+
+fn git_error<S: Into<String>>(url: &str, message: S) -> Error {
+    Error::GitContextError {
+        url: String::from(url),
+        message: message.into(),
+    }
+}
+
+/// Run a git command within the directory, passing the token as an `Authorization` header through
+/// the environment rather than the arguments, which other users of the machine can read.
+fn git(url: &str, dir: &Path, token: Option<&str>, args: &[&str]) -> Result<(), Error> {
+    let mut command = Command::new("git");
+    command
+        .current_dir(dir)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(token) = token {
+        let credentials = STANDARD.encode(format!("x-access-token:{token}"));
+        command
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env(
+                "GIT_CONFIG_VALUE_0",
+                format!("Authorization: Basic {credentials}"),
+            );
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| git_error(url, format!("unable to run git: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(git_error(
+            url,
+            format!("git {} failed: {}", args[0], stderr.trim()),
+        ));
+    }
+    Ok(())
+}
+
+/// Append the entries of the directory to the archive, except for the `.git` directory.
+fn append_dir(archive: &mut tar::Builder<Vec<u8>>, root: &Path, dir: &Path) -> Result<(), Error> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        if dir == root && entry.file_name() == ".git" {
+            continue;
+        }
+        let name = path.strip_prefix(root).unwrap_or(&path);
+        archive.append_path_with_name(&path, name)?;
+        if entry.file_type()?.is_dir() {
+            append_dir(archive, root, &path)?;
+        }
+    }
+    Ok(())
+}
+
+fn clone_into(remote: &GitRemote<'_>, dir: &Path, token: Option<&str>) -> Result<Bytes, Error> {
+    let url = remote.url;
+    std::fs::create_dir_all(dir)?;
+    git(url, dir, token, &["init", "--quiet"])?;
+    git(url, dir, token, &["remote", "add", "origin", url])?;
+    // fetching a single ref also resolves tags and commits, unlike `clone --branch`
+    git(
+        url,
+        dir,
+        token,
+        &[
+            "fetch",
+            "--quiet",
+            "--depth",
+            "1",
+            "origin",
+            remote.reference.unwrap_or("HEAD"),
+        ],
+    )?;
+    git(url, dir, token, &["checkout", "--quiet", "FETCH_HEAD"])?;
+    git(
+        url,
+        dir,
+        token,
+        &[
+            "submodule",
+            "update",
+            "--quiet",
+            "--init",
+            "--recursive",
+            "--depth",
+            "1",
+        ],
+    )?;
+
+    let root = match remote.subdir {
+        Some(subdir) => dir.join(subdir),
+        None => PathBuf::from(dir),
+    };
+    if !root.is_dir() {
+        return Err(git_error(
+            url,
+            format!(
+                "no directory {} in the repository",
+                remote.subdir.unwrap_or_default()
+            ),
+        ));
+    }
+
+    let mut archive = tar::Builder::new(Vec::new());
+    archive.follow_symlinks(false);
+    append_dir(&mut archive, &root, &root)?;
+    Ok(Bytes::from(archive.into_inner()?))
+}
+
+/// Shallow-clone a git repository into an uncompressed build context tarball, for the [Build
+/// Image API](crate::Docker::build_image()) or a buildkit
+/// [Upload](crate::grpc::build::ImageBuildLoadInput::Upload) load input.
+///
+/// The remote takes the format of the `remote` of the Build Image API, e.g.
+/// `https://github.com/moby/buildkit.git#v0.13.0:examples`: a git URL, optionally followed by a
+/// branch, tag or commit, and a subdirectory used as the build context. The `.git` directory is
+/// left out of the context.
+///
+/// The token authenticates to the git server over https, as the password of the
+/// `x-access-token` user that GitHub and GitLab accept.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use bollard::Docker;
+/// # let docker = Docker::connect_with_http_defaults().unwrap();
+/// use bollard::image::git::clone_context;
+/// use bollard::image::BuildImageOptions;
+///
+/// async move {
+///     let context = clone_context("https://github.com/docker-library/hello-world.git", None)
+///         .await
+///         .unwrap();
+///
+///     docker.build_image(
+///         BuildImageOptions {
+///             dockerfile: "amd64/hello-world/Dockerfile",
+///             t: "hello-world",
+///             ..Default::default()
+///         },
+///         None,
+///         Some(bollard::body_full(context)),
+///     );
+/// };
+/// ```
+pub async fn clone_context(remote: &str, token: Option<&str>) -> Result<Bytes, Error> {
+    let remote = String::from(remote);
+    let token = token.map(String::from);
+
+    tokio::task::spawn_blocking(move || {
+        let dir = std::env::temp_dir().join(format!(
+            "bollard-git-{}-{}",
+            std::process::id(),
+            CLONE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let res = clone_into(&GitRemote::parse(&remote), &dir, token.as_deref());
+        let _ = std::fs::remove_dir_all(&dir);
+        res
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::process::Command;
+
+    use super::{clone_context, GitRemote};
+
+    #[test]
+    fn test_git_remote() {
+        assert_eq!(
+            GitRemote::parse("https://github.com/moby/buildkit.git#v0.13.0:examples/"),
+            GitRemote {
+                url: "https://github.com/moby/buildkit.git",
+                reference: Some("v0.13.0"),
+                subdir: Some("examples"),
+            }
+        );
+        assert_eq!(
+            GitRemote::parse("git@github.com:moby/buildkit.git#:frontend"),
+            GitRemote {
+                url: "git@github.com:moby/buildkit.git",
+                reference: None,
+                subdir: Some("frontend"),
+            }
+        );
+        assert_eq!(
+            GitRemote::parse("https://github.com/moby/buildkit.git"),
+            GitRemote {
+                url: "https://github.com/moby/buildkit.git",
+                reference: None,
+                subdir: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_context() {
+        let dir = std::env::temp_dir().join(format!("bollard-git-repo-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("app")).unwrap();
+        std::fs::write(dir.join("app").join("Dockerfile"), "FROM scratch\n").unwrap();
+        std::fs::write(dir.join("README"), "readme\n").unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .current_dir(&dir)
+                .args([
+                    "-c",
+                    "user.name=bollard",
+                    "-c",
+                    "user.email=bollard@localhost",
+                ])
+                .args(args)
+                .output()
+                .map(|output| output.status.success());
+            assert!(matches!(status, Ok(true)), "git {args:?} failed");
+        };
+        git(&["init", "--quiet", "--initial-branch=main"]);
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "initial"]);
+
+        let remote = format!("file://{}#main:app", dir.display());
+        let context = clone_context(&remote, None).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let context = context.unwrap();
+        let mut archive = tar::Archive::new(&context[..]);
+        let mut entries: Vec<_> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let mut contents = String::new();
+                entry.read_to_string(&mut contents).unwrap();
+                (entry.path().unwrap().display().to_string(), contents)
+            })
+            .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            [(String::from("Dockerfile"), String::from("FROM scratch\n"))]
+        );
+    }
+}
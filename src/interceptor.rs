@@ -0,0 +1,450 @@
+//! Interceptor: observe every call this client makes to the Docker daemon, e.g. to keep an audit
+//! trail of what a process did to the daemon.
+//!
+//! An interceptor receives an [Intercepted Call](InterceptedCall) when a request is sent, when
+//! its response arrives or the request fails, and, for streaming responses such as logs or
+//! events, when the stream ends or is dropped.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! use bollard::interceptor::{CallStage, CaptureMode};
+//!
+//! let docker = Docker::connect_with_http_defaults()
+//!     .unwrap()
+//!     .with_interceptor(CaptureMode::Metadata, |call| match call.stage {
+//!         CallStage::Request => println!("#{} {} {}", call.id, call.method, call.path),
+//!         CallStage::Response => println!("#{} {:?}", call.id, call.status),
+//!         _ => (),
+//!     });
+//! ```
+
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::FutureExt;
+use http::header::{HeaderMap, HeaderValue};
+use http::{Method, Request, StatusCode};
+use http_body_util::BodyExt;
+use pin_project_lite::pin_project;
+
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::Docker;
+use crate::docker::BodyType;
+use crate::errors::Error;
+
+/// Headers carrying registry credentials, redacted from captured requests.
+const REDACTED_HEADERS: [&str; 2] = ["X-Registry-Auth", "X-Registry-Config"];
+
+/// Whether the body of a request to the path carries secrets, and is redacted from captured
+/// requests: registry credentials, secret and config payloads, and the join tokens, unlock keys
+/// and CA keys of swarm requests.
+fn is_redacted_body(path: &str) -> bool {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let segments = match segments.split_first() {
+        Some((version, rest))
+            if version.starts_with('v')
+                && version[1..].chars().all(|c| c.is_ascii_digit() || c == '.') =>
+        {
+            rest
+        }
+        _ => &segments[..],
+    };
+    matches!(
+        segments,
+        [.., "auth"]
+            | ["secrets" | "configs", ..]
+            | ["swarm", "init" | "join" | "unlock" | "update"]
+    )
+}
+
+/// What an [interceptor](Docker::with_interceptor()) captures of the requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// The method, path and query of the requests only.
+    #[default]
+    Metadata,
+    /// The headers and bodies of the requests as well. The registry credentials of the
+    /// `X-Registry-Auth` and `X-Registry-Config` headers are redacted, as are the bodies sent to
+    /// the `/auth` endpoint, e.g. through [request_raw](Docker::request_raw()), to the secret and
+    /// config endpoints, and to the swarm `init`, `join`, `unlock` and `update` endpoints.
+    /// Streamed bodies, e.g. build contexts, are not captured.
+    Bodies,
+}
+
+/// The stage of a call reported to an [interceptor](Docker::with_interceptor()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallStage {
+    /// The request is about to be sent.
+    Request,
+    /// The status and headers of the response arrived.
+    Response,
+    /// The request failed before a response arrived, e.g. on a timeout.
+    Failed,
+    /// The body of a streaming response was consumed to the end, or dropped.
+    StreamEnded,
+}
+
+/// An entry reported to an [interceptor](Docker::with_interceptor()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterceptedCall {
+    /// The stage of the call.
+    pub stage: CallStage,
+    /// Identifies the call among the calls of the client, and the clones of the client.
+    pub id: u64,
+    /// The method of the request.
+    pub method: Method,
+    /// The path and query of the request, e.g. `/containers/json?all=true`.
+    pub path: String,
+    /// The status of the response, set from the [`CallStage::Response`] stage on.
+    pub status: Option<StatusCode>,
+    /// The reason of the failure, set on [`CallStage::Failed`].
+    pub error: Option<String>,
+    /// The headers of the request, captured on [`CallStage::Request`] with
+    /// [`CaptureMode::Bodies`].
+    pub headers: HeaderMap,
+    /// The body of the request, captured on [`CallStage::Request`] with [`CaptureMode::Bodies`].
+    pub body: Option<Bytes>,
+    /// When the stage was observed by the client.
+    pub timestamp: SystemTime,
+    /// The time since the request was sent.
+    pub elapsed: Duration,
+}
+
+/// Callback registered with [`Docker::with_interceptor`].
+#[derive(Clone)]
+pub(crate) struct Interceptor {
+    hook: Arc<dyn Fn(&InterceptedCall) + Send + Sync>,
+    mode: CaptureMode,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Debug for Interceptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Interceptor({:?})", self.mode)
+    }
+}
+
+impl Interceptor {
+    /// Report the request, and track the rest of the call.
+    pub(crate) fn request(&self, request: &Request<BodyType>) -> CallContext {
+        let call = CallContext {
+            interceptor: self.clone(),
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            method: request.method().clone(),
+            path: request
+                .uri()
+                .path_and_query()
+                .map(|path| path.as_str())
+                .unwrap_or("/")
+                .to_owned(),
+            started: Instant::now(),
+        };
+
+        let mut record = call.record(CallStage::Request);
+        if self.mode == CaptureMode::Bodies {
+            record.headers = request.headers().clone();
+            for name in REDACTED_HEADERS {
+                if record.headers.contains_key(name) {
+                    record
+                        .headers
+                        .insert(name, HeaderValue::from_static("<redacted>"));
+                }
+            }
+            record.body = match request.body() {
+                _ if is_redacted_body(request.uri().path()) => {
+                    Some(Bytes::from_static(b"<redacted>"))
+                }
+                BodyType::Left(full) => full
+                    .clone()
+                    .collect()
+                    .now_or_never()
+                    .and_then(Result::ok)
+                    .map(|collected| collected.to_bytes()),
+                BodyType::Right(_) => None,
+            };
+        }
+        (self.hook)(&record);
+
+        call
+    }
+}
+
+/// A call in progress, carried in the extensions of its response.
+#[derive(Debug, Clone)]
+pub(crate) struct CallContext {
+    interceptor: Interceptor,
+    id: u64,
+    method: Method,
+    path: String,
+    started: Instant,
+}
+
+impl CallContext {
+    fn record(&self, stage: CallStage) -> InterceptedCall {
+        InterceptedCall {
+            stage,
+            id: self.id,
+            method: self.method.clone(),
+            path: self.path.clone(),
+            status: None,
+            error: None,
+            headers: HeaderMap::new(),
+            body: None,
+            timestamp: SystemTime::now(),
+            elapsed: self.started.elapsed(),
+        }
+    }
+
+    pub(crate) fn response(&self, status: StatusCode) {
+        (self.interceptor.hook)(&InterceptedCall {
+            status: Some(status),
+            ..self.record(CallStage::Response)
+        });
+    }
+
+    pub(crate) fn failed(&self, error: &Error) {
+        (self.interceptor.hook)(&InterceptedCall {
+            error: Some(error.to_string()),
+            ..self.record(CallStage::Failed)
+        });
+    }
+}
+
+/// Reports the end of a streaming response when dropped.
+#[derive(Debug)]
+pub(crate) struct StreamEnd {
+    call: CallContext,
+    status: StatusCode,
+}
+
+impl StreamEnd {
+    /// Take the call out of the extensions of a streaming response.
+    pub(crate) fn from_response<B>(response: &mut http::Response<B>) -> Option<Self> {
+        let status = response.status();
+        let call = response.extensions_mut().remove::<CallContext>()?;
+        Some(StreamEnd { call, status })
+    }
+}
+
+impl Drop for StreamEnd {
+    fn drop(&mut self) {
+        (self.call.interceptor.hook)(&InterceptedCall {
+            status: Some(self.status),
+            ..self.call.record(CallStage::StreamEnded)
+        });
+    }
+}
+
+pin_project! {
+    /// A stream that reports its end to the interceptor, once.
+    pub(crate) struct Intercepted<S> {
+        #[pin]
+        stream: S,
+        end: Option<StreamEnd>,
+    }
+}
+
+impl<S> Intercepted<S> {
+    #[cfg(any(feature = "container", feature = "system"))]
+    pub(crate) fn new(stream: S, end: Option<StreamEnd>) -> Self {
+        Intercepted { stream, end }
+    }
+}
+
+impl<S: Stream> Stream for Intercepted<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let item = this.stream.poll_next(cx);
+        if let Poll::Ready(None) = item {
+            this.end.take();
+        }
+        item
+    }
+}
+
+impl Docker {
+    /// Register an interceptor invoked for every call made through this client and its clones,
+    /// e.g. to keep an audit trail of what the process did to the daemon. See the
+    /// [interceptor](crate::interceptor) module.
+    ///
+    /// The interceptor runs on the task making the call, and should return quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// use bollard::interceptor::CaptureMode;
+    ///
+    /// let docker = Docker::connect_with_http_defaults()
+    ///     .unwrap()
+    ///     .with_interceptor(CaptureMode::Bodies, |call| {
+    ///         println!("{:?} {} {} {:?}", call.stage, call.method, call.path, call.body);
+    ///     });
+    /// ```
+    pub fn with_interceptor<F>(mut self, mode: CaptureMode, hook: F) -> Self
+    where
+        F: Fn(&InterceptedCall) + Send + Sync + 'static,
+    {
+        self.interceptor = Some(Interceptor {
+            hook: Arc::new(hook),
+            mode,
+            next_id: Arc::new(AtomicU64::new(1)),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use http::Method;
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::{is_redacted_body, CallStage, CaptureMode, InterceptedCall};
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    fn recording_docker(
+        body: &str,
+        mode: CaptureMode,
+    ) -> (Docker, Arc<Mutex<Vec<InterceptedCall>>>) {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!("HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}", body.len()),
+        );
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap()
+                .with_interceptor(mode, move |call| {
+                    recorded.lock().unwrap().push(call.clone())
+                });
+
+        (docker, calls)
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_metadata() {
+        let (docker, calls) = recording_docker("OK", CaptureMode::Metadata);
+
+        docker.ping().await.unwrap();
+        docker.clone().ping().await.unwrap();
+
+        let calls = calls.lock().unwrap();
+        let stages: Vec<_> = calls.iter().map(|call| (call.id, call.stage)).collect();
+        assert_eq!(
+            stages,
+            [
+                (1, CallStage::Request),
+                (1, CallStage::Response),
+                (2, CallStage::Request),
+                (2, CallStage::Response)
+            ]
+        );
+        assert_eq!(calls[0].method, Method::GET);
+        assert_eq!(calls[0].path, "/_ping");
+        assert!(calls[0].headers.is_empty());
+        assert_eq!(calls[1].status.map(|status| status.as_u16()), Some(200));
+    }
+
+    #[cfg(feature = "image")]
+    #[tokio::test]
+    async fn test_interceptor_bodies_redacted() {
+        use futures_util::TryStreamExt;
+
+        use crate::auth::DockerCredentials;
+        use crate::image::CreateImageOptions;
+
+        let (docker, calls) = recording_docker("{\"status\":\"Pulled\"}", CaptureMode::Bodies);
+
+        docker
+            .create_image(
+                Some(CreateImageOptions {
+                    from_image: "alpine",
+                    ..Default::default()
+                }),
+                None,
+                Some(DockerCredentials {
+                    password: Some(String::from("hunter2")),
+                    ..Default::default()
+                }),
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let calls = calls.lock().unwrap();
+        let stages: Vec<_> = calls.iter().map(|call| call.stage).collect();
+        assert_eq!(
+            stages,
+            [
+                CallStage::Request,
+                CallStage::Response,
+                CallStage::StreamEnded
+            ]
+        );
+        assert_eq!(calls[0].headers["X-Registry-Auth"], "<redacted>");
+        assert_eq!(calls[0].body.as_deref(), Some(&b""[..]));
+        assert!(calls[0].path.starts_with("/images/create?fromImage=alpine"));
+        assert!(calls[1..].iter().all(|call| call.headers.is_empty()));
+    }
+
+    #[test]
+    fn test_redacted_bodies() {
+        for path in [
+            "/auth",
+            "/v1.47/auth",
+            "/secrets/create",
+            "/secrets/my-secret/update",
+            "/configs/create",
+            "/configs/my-config/update",
+            "/swarm/init",
+            "/swarm/join",
+            "/swarm/unlock",
+            "/v1.47/swarm/update",
+        ] {
+            assert!(is_redacted_body(path), "{path}");
+        }
+        for path in [
+            "/containers/create",
+            "/images/create",
+            "/swarm/leave",
+            "/swarm/unlockkey",
+        ] {
+            assert!(!is_redacted_body(path), "{path}");
+        }
+    }
+
+    #[cfg(feature = "swarm")]
+    #[tokio::test]
+    async fn test_interceptor_secret_redacted() {
+        use crate::models::SecretSpec;
+
+        let (docker, calls) = recording_docker("{\"ID\":\"secret\"}", CaptureMode::Bodies);
+
+        docker
+            .create_secret(SecretSpec {
+                name: Some(String::from("password")),
+                data: Some(String::from("aHVudGVyMg==")),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0].path, "/secrets/create");
+        assert_eq!(calls[0].body.as_deref(), Some(&b"<redacted>"[..]));
+    }
+}
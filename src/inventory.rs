@@ -0,0 +1,441 @@
+//! Inventory: keep an in-memory view of the containers of the daemon, updated from its events,
+//! for agents that look containers up often rather than listing them every time.
+//!
+//! [`container_cache`](Docker::container_cache()) subscribes to the events of the daemon before
+//! it lists the containers, so no change between the listing and the events is missed. Every
+//! event of a container inspects it again rather than deriving its state from the event, so
+//! events that the listing already reflects are harmless.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::inventory::ContainerChange;
+//!
+//! async move {
+//!     let cache = docker.container_cache().await.unwrap();
+//!     println!("{:?}", cache.get("web").and_then(|web| web.state));
+//!
+//!     let mut changes = cache.subscribe();
+//!     while let Ok(change) = changes.recv().await {
+//!         if let ContainerChange::Removed(container) = change {
+//!             println!("{} was removed", container.id);
+//!         }
+//!     }
+//! };
+//! ```
+
+use futures_util::stream::{BoxStream, StreamExt};
+use http::request::Builder;
+use http_body_util::Full;
+use hyper::Method;
+use log::{debug, warn};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::Docker;
+use crate::container::{InspectContainerOptions, ListContainersOptions};
+use crate::docker::BodyType;
+use crate::errors::{Error, ErrorKind};
+use crate::models::{
+    ContainerInspectResponse, ContainerSummary, EndpointSettings, EventMessage,
+    EventMessageTypeEnum,
+};
+use crate::system::EventsOptions;
+
+/// Number of changes kept for a [subscriber](ContainerCache::subscribe()) that lags behind.
+const CHANGES_CAPACITY: usize = 256;
+
+/// A container as kept by a [Container Cache](ContainerCache).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CachedContainer {
+    /// The ID of the container.
+    pub id: String,
+    /// The name of the container, without the leading `/`.
+    pub name: Option<String>,
+    /// The image the container was created from, as given on creation.
+    pub image: Option<String>,
+    /// The labels of the container.
+    pub labels: HashMap<String, String>,
+    /// The state of the container, e.g. `running` or `exited`.
+    pub state: Option<String>,
+    /// The IP address of the container on each network it is connected to, keyed by network name.
+    pub ip_addresses: HashMap<String, String>,
+}
+
+fn ip_addresses(networks: Option<HashMap<String, EndpointSettings>>) -> HashMap<String, String> {
+    networks
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(network, endpoint)| {
+            endpoint
+                .ip_address
+                .filter(|ip_address| !ip_address.is_empty())
+                .map(|ip_address| (network, ip_address))
+        })
+        .collect()
+}
+
+impl From<ContainerSummary> for CachedContainer {
+    fn from(summary: ContainerSummary) -> Self {
+        CachedContainer {
+            id: summary.id.unwrap_or_default(),
+            name: summary
+                .names
+                .and_then(|names| names.into_iter().next())
+                .map(|name| name.trim_start_matches('/').to_owned()),
+            image: summary.image,
+            labels: summary.labels.unwrap_or_default(),
+            state: summary.state.filter(|state| !state.is_empty()),
+            ip_addresses: ip_addresses(
+                summary
+                    .network_settings
+                    .and_then(|network_settings| network_settings.networks),
+            ),
+        }
+    }
+}
+
+impl From<ContainerInspectResponse> for CachedContainer {
+    fn from(inspect: ContainerInspectResponse) -> Self {
+        let config = inspect.config.unwrap_or_default();
+        CachedContainer {
+            id: inspect.id.unwrap_or_default(),
+            name: inspect
+                .name
+                .map(|name| name.trim_start_matches('/').to_owned()),
+            image: config.image,
+            labels: config.labels.unwrap_or_default(),
+            state: inspect
+                .state
+                .and_then(|state| state.status)
+                .map(|status| status.to_string())
+                .filter(|state| !state.is_empty()),
+            ip_addresses: ip_addresses(
+                inspect
+                    .network_settings
+                    .and_then(|network_settings| network_settings.networks),
+            ),
+        }
+    }
+}
+
+/// A change of the containers kept by a [Container Cache](ContainerCache).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerChange {
+    /// A container appeared.
+    Added(CachedContainer),
+    /// The state, name, labels or addresses of a container changed.
+    Updated(CachedContainer),
+    /// A container was removed.
+    Removed(CachedContainer),
+}
+
+#[derive(Debug, Default)]
+struct Inventory {
+    containers: HashMap<String, CachedContainer>,
+}
+
+impl Inventory {
+    fn upsert(&mut self, container: CachedContainer) -> Option<ContainerChange> {
+        match self
+            .containers
+            .insert(container.id.clone(), container.clone())
+        {
+            None => Some(ContainerChange::Added(container)),
+            Some(previous) if previous != container => Some(ContainerChange::Updated(container)),
+            Some(_) => None,
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> Option<ContainerChange> {
+        self.containers.remove(id).map(ContainerChange::Removed)
+    }
+
+    fn get(&self, id_or_name: &str) -> Option<&CachedContainer> {
+        let name = id_or_name.trim_start_matches('/');
+        self.containers.get(id_or_name).or_else(|| {
+            self.containers
+                .values()
+                .find(|container| container.name.as_deref() == Some(name))
+        })
+    }
+}
+
+/// In-memory view of the containers of the daemon, from the [Container Cache
+/// API](Docker::container_cache()). Dropping the cache stops watching the events.
+#[derive(Debug)]
+pub struct ContainerCache {
+    inventory: Arc<Mutex<Inventory>>,
+    changes: broadcast::Sender<ContainerChange>,
+    task: JoinHandle<()>,
+}
+
+impl ContainerCache {
+    /// The container with the given ID or name.
+    pub fn get(&self, id_or_name: &str) -> Option<CachedContainer> {
+        self.inventory.lock().unwrap().get(id_or_name).cloned()
+    }
+
+    /// Every container of the daemon.
+    pub fn containers(&self) -> Vec<CachedContainer> {
+        self.inventory
+            .lock()
+            .unwrap()
+            .containers
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Receive the changes of the containers from now on. A subscriber that falls behind by more
+    /// than 256 changes misses the oldest, and should read the cache again.
+    pub fn subscribe(&self) -> broadcast::Receiver<ContainerChange> {
+        self.changes.subscribe()
+    }
+
+    /// Whether the cache still follows the events of the daemon. The events end on an error, e.g.
+    /// when the daemon restarts, after which the cache is stale.
+    pub fn is_watching(&self) -> bool {
+        !self.task.is_finished()
+    }
+}
+
+impl Drop for ContainerCache {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Follow the events of the daemon, inspecting the containers they concern.
+async fn watch(
+    docker: Docker,
+    mut events: BoxStream<'static, Result<EventMessage, Error>>,
+    inventory: Arc<Mutex<Inventory>>,
+    changes: broadcast::Sender<ContainerChange>,
+) {
+    while let Some(event) = events.next().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("container cache stopped following the events: {e}");
+                return;
+            }
+        };
+        let Some(actor) = event.actor else {
+            continue;
+        };
+        let action = event.action.unwrap_or_default();
+        let container_id = match event.typ {
+            Some(EventMessageTypeEnum::CONTAINER) => actor.id,
+            Some(EventMessageTypeEnum::NETWORK)
+                if action == "connect" || action == "disconnect" =>
+            {
+                actor
+                    .attributes
+                    .and_then(|mut attributes| attributes.remove("container"))
+            }
+            _ => None,
+        };
+        let Some(container_id) = container_id else {
+            continue;
+        };
+
+        let change = match action.as_str() {
+            "destroy" => inventory.lock().unwrap().remove(&container_id),
+            // events that leave the container as it is
+            "attach" | "detach" | "resize" | "top" | "export" | "commit" | "copy"
+            | "archive-path" | "extract-to-dir" => None,
+            _ if action.starts_with("exec_") || action.starts_with("health_status") => None,
+            _ => match docker
+                .inspect_container(&container_id, None::<InspectContainerOptions>)
+                .await
+            {
+                Ok(inspect) => inventory
+                    .lock()
+                    .unwrap()
+                    .upsert(CachedContainer::from(inspect)),
+                Err(e) if e.kind() == Some(ErrorKind::NotFound) => {
+                    inventory.lock().unwrap().remove(&container_id)
+                }
+                Err(e) => {
+                    debug!("unable to inspect container {container_id} after {action}: {e}");
+                    None
+                }
+            },
+        };
+        if let Some(change) = change {
+            // no subscriber may be listening
+            let _ = changes.send(change);
+        }
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Container Cache
+    ///
+    /// Keep an in-memory view of every container of the daemon, updated from the events of the
+    /// daemon on a background task. See the [inventory](crate::inventory) module.
+    ///
+    /// # Returns
+    ///
+    ///  - [Container Cache](ContainerCache) struct, once the containers are listed, wrapped in a
+    ///    Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async move {
+    ///     let cache = docker.container_cache().await.unwrap();
+    ///     for container in cache.containers() {
+    ///         println!("{:?} {:?}", container.name, container.ip_addresses);
+    ///     }
+    /// };
+    /// ```
+    pub async fn container_cache(&self) -> Result<ContainerCache, Error> {
+        let options = EventsOptions {
+            filters: HashMap::from([("type", vec!["container", "network"])]),
+            ..Default::default()
+        };
+        let req = self.build_request(
+            "/events",
+            Builder::new().method(Method::GET),
+            Some(options),
+            Ok(BodyType::Left(Full::new(Default::default()))),
+        );
+        // subscribe before listing, so that the events cover every change since the listing
+        let events = self
+            .process_into_subscribed_stream::<EventMessage>(req)
+            .await?
+            .boxed();
+
+        let containers = self
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                ..Default::default()
+            }))
+            .await?;
+        let mut inventory = Inventory::default();
+        for summary in containers {
+            inventory.upsert(CachedContainer::from(summary));
+        }
+
+        let inventory = Arc::new(Mutex::new(inventory));
+        let (changes, _) = broadcast::channel(CHANGES_CAPACITY);
+        let task = tokio::spawn(watch(
+            self.clone(),
+            events,
+            Arc::clone(&inventory),
+            changes.clone(),
+        ));
+
+        Ok(ContainerCache {
+            inventory,
+            changes,
+            task,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{CachedContainer, ContainerChange, Inventory};
+    use crate::models::{
+        ContainerConfig, ContainerInspectResponse, ContainerState, ContainerStateStatusEnum,
+        ContainerSummary, ContainerSummaryNetworkSettings, EndpointSettings, NetworkSettings,
+    };
+
+    #[test]
+    fn test_cached_container() {
+        let networks = HashMap::from([
+            (
+                String::from("bridge"),
+                EndpointSettings {
+                    ip_address: Some(String::from("172.17.0.2")),
+                    ..Default::default()
+                },
+            ),
+            (String::from("none"), EndpointSettings::default()),
+        ]);
+        let listed = CachedContainer::from(ContainerSummary {
+            id: Some(String::from("abc")),
+            names: Some(vec![String::from("/web")]),
+            image: Some(String::from("nginx:alpine")),
+            state: Some(String::from("running")),
+            network_settings: Some(ContainerSummaryNetworkSettings {
+                networks: Some(networks.clone()),
+            }),
+            ..Default::default()
+        });
+        let inspected = CachedContainer::from(ContainerInspectResponse {
+            id: Some(String::from("abc")),
+            name: Some(String::from("/web")),
+            image: Some(String::from("sha256:4f4fb700ef54")),
+            config: Some(ContainerConfig {
+                image: Some(String::from("nginx:alpine")),
+                ..Default::default()
+            }),
+            state: Some(ContainerState {
+                status: Some(ContainerStateStatusEnum::RUNNING),
+                ..Default::default()
+            }),
+            network_settings: Some(NetworkSettings {
+                networks: Some(networks),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        assert_eq!(listed, inspected);
+        assert_eq!(listed.name.as_deref(), Some("web"));
+        assert_eq!(
+            listed.ip_addresses,
+            HashMap::from([(String::from("bridge"), String::from("172.17.0.2"))])
+        );
+    }
+
+    #[test]
+    fn test_inventory() {
+        let web = CachedContainer {
+            id: String::from("abc"),
+            name: Some(String::from("web")),
+            state: Some(String::from("running")),
+            ..Default::default()
+        };
+        let mut inventory = Inventory::default();
+
+        assert_eq!(
+            inventory.upsert(web.clone()),
+            Some(ContainerChange::Added(web.clone()))
+        );
+        assert_eq!(inventory.upsert(web.clone()), None);
+
+        let exited = CachedContainer {
+            state: Some(String::from("exited")),
+            ..web
+        };
+        assert_eq!(
+            inventory.upsert(exited.clone()),
+            Some(ContainerChange::Updated(exited.clone()))
+        );
+        assert_eq!(inventory.get("/web"), Some(&exited));
+        assert_eq!(inventory.get("abc"), Some(&exited));
+
+        assert_eq!(
+            inventory.remove("abc"),
+            Some(ContainerChange::Removed(exited))
+        );
+        assert_eq!(inventory.remove("abc"), None);
+        assert_eq!(inventory.get("web"), None);
+    }
+}
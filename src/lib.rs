@@ -39,8 +39,35 @@
 //!  - `time`: enable [Time 0.3](https://github.com/time-rs/time) for `DateTime` types.
 //!  - `buildkit`: use [Buildkit](https://github.com/moby/buildkit) instead of
 //!    [Docker](https://github.com/moby/moby) when building images.
-//!  - `json_data_content`: Add JSON to errors on serialization failures.
+//!  - `json_data_content`: Add JSON, and the location of the error in it, to errors on
+//!    serialization failures.
 //!  - `webpki`: Use mozilla's root certificates instead of native root certs provided by the OS.
+//!  - `native-tls`: enable SSL support through the platform TLS library (OpenSSL, SChannel or
+//!    Security.framework) instead of [Rustls](https://github.com/rustls/rustls), for the same
+//!    `connect_with_ssl*` API. The `rustls` features take precedence when both are enabled.
+//!  - `http2`: multiplex concurrent requests over a single HTTP/2 cleartext (h2c) connection to
+//!    a TCP daemon, through `Docker::connect_with_http2`.
+//!  - `container`, `exec`, `image`, `network`, `swarm`, `system`, `volume`: the API areas
+//!    compiled into the client, all enabled by default. Disable default features and pick the
+//!    areas you need to reduce compile times and binary size, e.g. `features = ["pipe",
+//!    "container"]` for a stats agent. The `swarm` feature covers the swarm, service and secret
+//!    APIs. The generated [models](crate::models) are always available: they are compiled in the
+//!    separate `bollard-stubs` crate, and the models of one area refer to those of others, e.g. a
+//!    `ContainerSummary` holds its `EndpointSettings`, so they cannot be split along the areas.
+//!  - `image_builder`: assemble images from a base image and programmatic layers, without a
+//!    Dockerfile, through the `image::builder` module.
+//!  - `verify_digest`: check pulled and exported images against an expected content digest,
+//!    through the `image::digest` module.
+//!  - `git_context`: shallow-clone git repositories into build context tarballs, for daemons
+//!    that cannot reach them, through the `image::git` module.
+//!  - `readiness`: wait for the services inside containers to be ready, through a published
+//!    port, an HTTP request or a log line, through the `readiness` module.
+//!  - `bench_utils`: fixtures and a mock daemon for benchmarking the client, through the
+//!    `bench_utils` module.
+//!  - `terminal`: resize attached TTYs along with the local terminal on unix, through the
+//!    `terminal` module.
+//!  - `ws`: attach to containers over a WebSocket, for proxies that block the connection hijack
+//!    of the attach endpoint, through `Docker::attach_container_ws`.
 //!
 //! ## Version
 //!
@@ -71,6 +98,9 @@
 //!
 //! Use the `Docker::connect_with_socket` method API to parameterise this interface.
 //!
+//! On unix, a socket inherited from the parent process, e.g. through systemd socket activation,
+//! can be used with the `Docker::connect_with_listenfd` and `Docker::connect_with_fd` methods.
+//!
 //! ### Local
 //!
 //! The client will connect to the OS specific handler it is compiled for.
@@ -95,7 +125,8 @@
 //! Docker::connect_with_http_defaults();
 //! ```
 //!
-//! Use the `Docker::connect_with_http` method API to parameterise the interface.
+//! Use the `Docker::connect_with_http` method API to parameterise the interface, or the
+//! `Docker::connect_with_http2` method with the `http2` feature to negotiate HTTP/2.
 //!
 //! ### SSL via Rustls
 //!
@@ -112,7 +143,9 @@
 //! Docker::connect_with_ssl_defaults();
 //! ```
 //!
-//! Use the `Docker::connect_with_ssl` method API to parameterise the interface.
+//! Use the `Docker::connect_with_ssl` method API to parameterise the interface,
+//! `Docker::connect_with_ssl_bytes` for key material held in memory, or
+//! `Docker::connect_with_ssl_config` with a pre-built `rustls::ClientConfig`.
 //!
 //! ## Examples
 //!
@@ -264,27 +297,68 @@
 
 // declare modules
 pub mod auth;
+#[cfg(feature = "container")]
+pub mod batch;
+#[cfg(feature = "bench_utils")]
+pub mod bench_utils;
+pub mod compat;
+#[cfg(feature = "container")]
 pub mod container;
 mod docker;
 pub mod errors;
+#[cfg(feature = "exec")]
 pub mod exec;
+#[cfg(all(feature = "pipe", unix))]
+pub mod fd;
+#[cfg(feature = "image")]
 pub mod image;
+pub mod interceptor;
+#[cfg(all(feature = "container", feature = "system"))]
+pub mod inventory;
+#[cfg(all(feature = "container", feature = "image", feature = "volume"))]
+pub mod migrate;
+#[cfg(all(feature = "pipe", windows))]
+pub mod named_pipe;
+#[cfg(feature = "network")]
 pub mod network;
+pub mod platform;
+#[cfg(all(feature = "container", feature = "network", feature = "volume"))]
+pub mod project;
 mod read;
+#[cfg(feature = "readiness")]
+pub mod readiness;
+#[cfg(feature = "image")]
+pub mod reference;
+#[cfg(all(feature = "container", feature = "image"))]
+pub mod run;
+#[cfg(feature = "swarm")]
 pub mod secret;
+#[cfg(feature = "swarm")]
 pub mod service;
+#[cfg(all(feature = "container", feature = "system"))]
+pub mod supervise;
+#[cfg(feature = "swarm")]
 pub mod swarm;
+#[cfg(feature = "system")]
 pub mod system;
+#[cfg(all(feature = "network", feature = "volume"))]
+pub mod temp;
+#[cfg(all(feature = "terminal", unix))]
+pub mod terminal;
+#[cfg(feature = "ssl_providerless")]
+pub mod tls;
 mod uri;
+#[cfg(feature = "volume")]
 pub mod volume;
 
 pub mod grpc;
 
 // publicly re-export
 pub use crate::docker::{
-    body_full, body_stream, body_try_stream, BollardRequest, ClientVersion, Docker,
-    API_DEFAULT_VERSION,
+    body_full, body_stream, body_try_stream, BollardRequest, ClientVersion, ConnectionInfo, Docker,
+    RawResponse, API_DEFAULT_VERSION,
 };
+pub use crate::read::{AsyncUpgraded, BodyStream, JsonMessageStream};
 pub use bollard_stubs::models;
 
 #[cfg(feature = "buildkit")]
@@ -295,3 +369,6 @@ pub use bollard_buildkit_proto::health;
 
 #[cfg(feature = "buildkit")]
 pub use bollard_buildkit_proto::moby;
+
+#[cfg(feature = "buildkit")]
+pub use bollard_buildkit_proto::pb;
@@ -0,0 +1,523 @@
+//! Migrate: move a container from one daemon to another.
+//!
+//! [`migrate_container`](Docker::migrate_container()) snapshots the container's filesystem into an
+//! image, transfers the image to the destination daemon, and recreates the container there with
+//! the same configuration, optionally copying the contents of its named volumes along. Each
+//! phase is reported as a [Migration Progress](MigrationProgress) item.
+//!
+//! The source container is left untouched, and keeps running if it was: stop it first for the
+//! filesystem and the volumes to be copied in a consistent state.
+
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::future::{self, Either};
+use futures_util::stream::{self, StreamExt};
+use log::warn;
+use tokio::sync::mpsc;
+
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::Docker;
+use crate::container::{
+    Config, CreateContainerOptions, DownloadFromContainerOptions, StartContainerOptions,
+    UploadToContainerOptions,
+};
+use crate::docker::{body_try_stream, BodyType};
+use crate::errors::Error;
+use crate::image::{
+    CommitContainerOptions, CopyImageProgress, CreateImageOptions, ImportImageOptions,
+};
+use crate::models::{HostConfig, MountPoint, MountPointTypeEnum};
+use crate::volume::CreateVolumeOptions;
+
+/// Repository of the snapshot image, unless the [Migrate Container
+/// Options](MigrateContainerOptions) name one.
+const SNAPSHOT_REPOSITORY: &str = "bollard-migrate";
+
+/// How the filesystem of the container is carried to the destination daemon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MigrationMode {
+    /// Commit the container to an image on the source daemon, and transfer the image with its
+    /// layers and history, like [Copy Image](Docker::copy_image_to()).
+    #[default]
+    Commit,
+    /// Export the container's filesystem, and import it on the destination daemon as a
+    /// single-layer image. The snapshot is not stored on the source daemon.
+    Export,
+}
+
+/// Parameters used in the [Migrate Container API](Docker::migrate_container())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::migrate::{MigrateContainerOptions, MigrationMode};
+///
+/// use std::default::Default;
+///
+/// MigrateContainerOptions {
+///     mode: MigrationMode::Export,
+///     copy_volumes: true,
+///     start: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrateContainerOptions {
+    /// How the filesystem of the container is carried.
+    pub mode: MigrationMode,
+    /// Name of the container on the destination daemon. Defaults to the name of the source
+    /// container.
+    pub name: Option<String>,
+    /// Repository of the snapshot image. Defaults to `bollard-migrate`.
+    pub repo: Option<String>,
+    /// Tag of the snapshot image. Defaults to the short ID of the source container.
+    pub tag: Option<String>,
+    /// Create the named volumes of the container on the destination daemon, with the driver,
+    /// options and labels of the source volumes, and copy their contents through the archive
+    /// endpoints. Otherwise the destination container starts with empty volumes.
+    pub copy_volumes: bool,
+    /// Start the container on the destination daemon once it is created.
+    pub start: bool,
+}
+
+/// A phase of a migration, reported by the [Migrate Container API](Docker::migrate_container()).
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationProgress {
+    /// The source container was inspected.
+    Inspected {
+        /// ID of the source container.
+        id: String,
+    },
+    /// The filesystem of the container was committed or, with [`MigrationMode::Export`], fully
+    /// imported into the snapshot image.
+    Snapshot {
+        /// Reference of the snapshot image, on both daemons.
+        image: String,
+    },
+    /// The snapshot image is being transferred to the destination daemon.
+    Transferred {
+        /// Number of bytes sent so far.
+        bytes: u64,
+    },
+    /// A named volume was created on the destination daemon.
+    VolumeCreated {
+        /// Name of the volume.
+        name: String,
+    },
+    /// The container was created on the destination daemon.
+    Created {
+        /// ID of the destination container.
+        id: String,
+    },
+    /// The contents of a named volume were copied to the destination daemon.
+    VolumeCopied {
+        /// Name of the volume.
+        name: String,
+        /// Size of the archive sent.
+        bytes: u64,
+    },
+    /// The container was started on the destination daemon.
+    Started,
+    /// The migration completed.
+    Completed {
+        /// ID of the destination container.
+        id: String,
+    },
+}
+
+/// Drop the parts of a host configuration which refer to the source daemon or its host: other
+/// containers, devices and CPU sets, and the container ID file.
+fn translate_host_config(host_config: HostConfig) -> HostConfig {
+    let own_namespace = |mode: Option<String>| mode.filter(|mode| !mode.starts_with("container:"));
+
+    HostConfig {
+        links: None,
+        volumes_from: None,
+        network_mode: own_namespace(host_config.network_mode),
+        pid_mode: own_namespace(host_config.pid_mode),
+        ipc_mode: own_namespace(host_config.ipc_mode),
+        container_id_file: None,
+        devices: None,
+        device_requests: None,
+        cpuset_cpus: None,
+        cpuset_mems: None,
+        blkio_weight_device: None,
+        blkio_device_read_bps: None,
+        blkio_device_write_bps: None,
+        blkio_device_read_iops: None,
+        blkio_device_write_iops: None,
+        ..host_config
+    }
+}
+
+/// The named volumes mounted in a container.
+fn named_volumes(mounts: Option<Vec<MountPoint>>) -> Vec<(String, String)> {
+    mounts
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|mount| mount.typ == Some(MountPointTypeEnum::VOLUME))
+        .filter_map(|mount| Some((mount.name?, mount.destination?)))
+        .collect()
+}
+
+/// An archive streamed from the source daemon into a request to the destination daemon.
+#[derive(Debug, Default)]
+struct SourceArchive {
+    sent: Arc<AtomicU64>,
+    error: Arc<Mutex<Option<Error>>>,
+}
+
+impl SourceArchive {
+    fn body(&self, archive: impl Stream<Item = Result<Bytes, Error>> + Send + 'static) -> BodyType {
+        let sent = Arc::clone(&self.sent);
+        let error = Arc::clone(&self.error);
+        body_try_stream(archive.map(move |chunk| match chunk {
+            Ok(bytes) => {
+                sent.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+                Ok(bytes)
+            }
+            Err(e) => {
+                let message = e.to_string();
+                *error.lock().unwrap() = Some(e);
+                Err(io::Error::other(message))
+            }
+        }))
+    }
+
+    fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// The error of the source daemon, which failed the request to the destination daemon.
+    fn error_or(&self, e: Error) -> Error {
+        self.error.lock().unwrap().take().unwrap_or(e)
+    }
+}
+
+async fn migrate(
+    source: Docker,
+    destination: Docker,
+    container_name: String,
+    options: MigrateContainerOptions,
+    progress: mpsc::UnboundedSender<MigrationProgress>,
+) -> Result<String, Error> {
+    let report = |phase| {
+        let _ = progress.send(phase);
+    };
+
+    let inspect = source.inspect_container(&container_name, None).await?;
+    let id = inspect.id.unwrap_or(container_name);
+    report(MigrationProgress::Inspected { id: id.clone() });
+
+    let repo = options
+        .repo
+        .unwrap_or_else(|| String::from(SNAPSHOT_REPOSITORY));
+    let tag = options.tag.unwrap_or_else(|| id.chars().take(12).collect());
+    let image = format!("{repo}:{tag}");
+
+    match options.mode {
+        MigrationMode::Commit => {
+            source
+                .commit_container(
+                    CommitContainerOptions {
+                        container: id.as_str(),
+                        repo: &repo,
+                        tag: &tag,
+                        pause: true,
+                        ..Default::default()
+                    },
+                    Config::<String>::default(),
+                )
+                .await?;
+            report(MigrationProgress::Snapshot {
+                image: image.clone(),
+            });
+
+            let mut transfer =
+                source.copy_image_to(&destination, &image, ImportImageOptions::default());
+            while let Some(transferred) = transfer.next().await {
+                if let CopyImageProgress::Transferred { bytes } = transferred? {
+                    report(MigrationProgress::Transferred { bytes });
+                }
+            }
+        }
+        MigrationMode::Export => {
+            let archive = SourceArchive::default();
+            let mut import = destination.create_image(
+                Some(CreateImageOptions {
+                    from_src: "-",
+                    repo: &repo,
+                    tag: &tag,
+                    ..Default::default()
+                }),
+                Some(archive.body(source.export_container(&id))),
+                None,
+            );
+            while let Some(info) = import.next().await {
+                info.map_err(|e| archive.error_or(e))?;
+                report(MigrationProgress::Transferred {
+                    bytes: archive.sent(),
+                });
+            }
+            report(MigrationProgress::Snapshot {
+                image: image.clone(),
+            });
+        }
+    }
+
+    let volumes = if options.copy_volumes {
+        named_volumes(inspect.mounts)
+    } else {
+        Vec::new()
+    };
+    for (name, _) in &volumes {
+        let volume = source.inspect_volume(name).await?;
+        destination
+            .create_volume(CreateVolumeOptions {
+                name: volume.name,
+                driver: volume.driver,
+                driver_opts: volume.options,
+                labels: volume.labels,
+            })
+            .await?;
+        report(MigrationProgress::VolumeCreated { name: name.clone() });
+    }
+
+    let mut config = Config::from(inspect.config.unwrap_or_default());
+    config.image = Some(image);
+    config.host_config = inspect.host_config.map(translate_host_config);
+
+    let name = options.name.or_else(|| {
+        inspect
+            .name
+            .map(|name| name.trim_start_matches('/').to_owned())
+    });
+    let created = destination
+        .create_container(
+            name.filter(|name| !name.is_empty())
+                .map(|name| CreateContainerOptions {
+                    name,
+                    platform: None,
+                }),
+            config,
+        )
+        .await?;
+    for warning in &created.warnings {
+        warn!("{warning}");
+    }
+    report(MigrationProgress::Created {
+        id: created.id.clone(),
+    });
+
+    for (name, path) in volumes {
+        // the archive holds the mount point itself, extracted into its parent
+        let parent = match path.trim_end_matches('/').rsplit_once('/') {
+            Some(("", _)) | None => "/",
+            Some((parent, _)) => parent,
+        };
+
+        let archive = SourceArchive::default();
+        destination
+            .upload_to_container(
+                &created.id,
+                Some(UploadToContainerOptions {
+                    path: parent,
+                    ..Default::default()
+                }),
+                archive.body(source.download_from_container(
+                    &id,
+                    Some(DownloadFromContainerOptions {
+                        path: path.as_str(),
+                    }),
+                )),
+            )
+            .await
+            .map_err(|e| archive.error_or(e))?;
+        report(MigrationProgress::VolumeCopied {
+            name,
+            bytes: archive.sent(),
+        });
+    }
+
+    if options.start {
+        destination
+            .start_container(&created.id, None::<StartContainerOptions<String>>)
+            .await?;
+        report(MigrationProgress::Started);
+    }
+
+    Ok(created.id)
+}
+
+/// A migration in progress, with the phases it reported.
+enum MigrationState<F> {
+    Running(Pin<Box<F>>, mpsc::UnboundedReceiver<MigrationProgress>),
+    Finished(
+        Result<String, Error>,
+        mpsc::UnboundedReceiver<MigrationProgress>,
+    ),
+    Done,
+}
+
+/// Report the remaining phases of a finished migration, then its result.
+fn finish<F>(
+    result: Result<String, Error>,
+    mut receiver: mpsc::UnboundedReceiver<MigrationProgress>,
+) -> Option<(Result<MigrationProgress, Error>, MigrationState<F>)> {
+    match receiver.try_recv() {
+        Ok(progress) => Some((Ok(progress), MigrationState::Finished(result, receiver))),
+        Err(_) => Some((
+            result.map(|id| MigrationProgress::Completed { id }),
+            MigrationState::Done,
+        )),
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Migrate Container
+    ///
+    /// Move a container to another daemon: snapshot its filesystem into an image, transfer the
+    /// image, and recreate the container on the destination daemon with the same configuration.
+    /// See the [migrate](crate::migrate) module.
+    ///
+    /// The host configuration is kept, apart from the settings which refer to the source daemon
+    /// or its host: links, volumes from and namespaces of other containers, devices, CPU sets,
+    /// block IO device limits and the container ID file. Bind mounts are kept as they are, and
+    /// should exist on the destination host.
+    ///
+    /// # Arguments
+    ///
+    ///  - The [Docker](Docker) client of the destination daemon.
+    ///  - Container name or ID, on this daemon.
+    ///  - [Migrate Container Options](MigrateContainerOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A Stream of [Migration Progress](MigrationProgress), ending with
+    ///    [`MigrationProgress::Completed`] and the ID of the destination container. A failed
+    ///    phase ends the stream with its error, and leaves what was already created in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::migrate::{MigrateContainerOptions, MigrationProgress};
+    ///
+    /// use futures_util::stream::StreamExt;
+    ///
+    /// let destination = Docker::connect_with_http(
+    ///     "tcp://10.0.0.2:2375", 120, bollard::API_DEFAULT_VERSION).unwrap();
+    ///
+    /// let options = MigrateContainerOptions {
+    ///     copy_volumes: true,
+    ///     start: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// async move {
+    ///     let mut migration = docker.migrate_container(&destination, "web", options);
+    ///     while let Some(progress) = migration.next().await {
+    ///         match progress.unwrap() {
+    ///             MigrationProgress::Transferred { bytes } => println!("{bytes} bytes sent"),
+    ///             MigrationProgress::Completed { id } => println!("migrated to {id}"),
+    ///             phase => println!("{phase:?}"),
+    ///         }
+    ///     }
+    /// };
+    /// ```
+    pub fn migrate_container(
+        &self,
+        destination: &Docker,
+        container_name: &str,
+        options: MigrateContainerOptions,
+    ) -> impl Stream<Item = Result<MigrationProgress, Error>> + Unpin {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let migration = Box::pin(migrate(
+            self.clone(),
+            destination.clone(),
+            String::from(container_name),
+            options,
+            sender,
+        ));
+
+        stream::unfold(
+            MigrationState::Running(migration, receiver),
+            |state| async move {
+                match state {
+                    MigrationState::Running(mut migration, mut receiver) => {
+                        let next =
+                            match future::select(Box::pin(receiver.recv()), migration.as_mut())
+                                .await
+                            {
+                                Either::Left((progress, _)) => Either::Left(progress),
+                                Either::Right((result, _)) => Either::Right(result),
+                            };
+                        match next {
+                            Either::Left(Some(progress)) => {
+                                Some((Ok(progress), MigrationState::Running(migration, receiver)))
+                            }
+                            // the migration holds the sender until it returns
+                            Either::Left(None) => finish(migration.await, receiver),
+                            Either::Right(result) => finish(result, receiver),
+                        }
+                    }
+                    MigrationState::Finished(result, receiver) => finish(result, receiver),
+                    MigrationState::Done => None,
+                }
+            },
+        )
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{named_volumes, translate_host_config};
+    use crate::models::{HostConfig, MountPoint, MountPointTypeEnum};
+
+    #[test]
+    fn test_translate_host_config() {
+        let host_config = translate_host_config(HostConfig {
+            binds: Some(vec![String::from("data:/data")]),
+            links: Some(vec![String::from("/db:/web/db")]),
+            network_mode: Some(String::from("container:0123456789ab")),
+            ipc_mode: Some(String::from("shareable")),
+            cpuset_cpus: Some(String::from("0-3")),
+            memory: Some(1 << 30),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            host_config,
+            HostConfig {
+                binds: Some(vec![String::from("data:/data")]),
+                ipc_mode: Some(String::from("shareable")),
+                memory: Some(1 << 30),
+                ..Default::default()
+            }
+        );
+
+        let volumes = named_volumes(Some(vec![
+            MountPoint {
+                typ: Some(MountPointTypeEnum::VOLUME),
+                name: Some(String::from("data")),
+                destination: Some(String::from("/data")),
+                ..Default::default()
+            },
+            MountPoint {
+                typ: Some(MountPointTypeEnum::BIND),
+                source: Some(String::from("/etc/app")),
+                destination: Some(String::from("/etc/app")),
+                ..Default::default()
+            },
+        ]));
+        assert_eq!(volumes, [(String::from("data"), String::from("/data"))]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Named pipe: options of the connections to the Docker daemon through a Windows named pipe, and
+//! discovery of the pipes of Docker Desktop.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bollard::named_pipe::{ImpersonationLevel, NamedPipeOptions};
+//! use bollard::{Docker, API_DEFAULT_VERSION};
+//!
+//! use std::time::Duration;
+//!
+//! let options = NamedPipeOptions {
+//!     busy_timeout: Some(Duration::from_secs(5)),
+//!     impersonation_level: ImpersonationLevel::Anonymous,
+//!     ..Default::default()
+//! };
+//!
+//! let docker = Docker::connect_with_named_pipe_options(
+//!     "//./pipe/docker_engine_linux",
+//!     120,
+//!     API_DEFAULT_VERSION,
+//!     options,
+//! )
+//! .unwrap();
+//! ```
+
+use hex::FromHex;
+use hyper::rt::ReadBufCursor;
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use hyper_util::rt::TokioIo;
+use pin_project_lite::pin_project;
+use tokio::io::AsyncWrite;
+use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient};
+use tokio::time::{self, Instant};
+use winapi::shared::winerror;
+use winapi::um::winbase;
+
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// The scheme part of a uri that denotes a named pipe connection.
+pub(crate) const NAMED_PIPE_SCHEME: &str = "net.pipe";
+
+/// The pipes a Docker daemon listens on, in order of preference: the default pipe, then the
+/// pipes of the Linux and Windows engines of Docker Desktop.
+pub const DOCKER_NAMED_PIPES: [&str; 3] = [
+    "//./pipe/docker_engine",
+    "//./pipe/docker_engine_linux",
+    "//./pipe/docker_engine_windows",
+];
+
+/// The first of the [Docker named pipes](DOCKER_NAMED_PIPES) that exists on this machine, if any.
+pub fn discover_named_pipe() -> Option<&'static str> {
+    DOCKER_NAMED_PIPES
+        .into_iter()
+        .find(|pipe| Path::new(&pipe.replace('/', "\\")).exists())
+}
+
+/// How much the daemon may act on behalf of the client, through the security quality of service
+/// of the pipe.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImpersonationLevel {
+    /// The daemon can neither identify nor impersonate the client.
+    Anonymous,
+    /// The daemon can identify the client, but not impersonate it.
+    #[default]
+    Identification,
+    /// The daemon can impersonate the client on the local machine.
+    Impersonation,
+    /// The daemon can impersonate the client on the local and remote machines.
+    Delegation,
+}
+
+impl ImpersonationLevel {
+    fn security_qos_flags(self) -> u32 {
+        match self {
+            ImpersonationLevel::Anonymous => winbase::SECURITY_ANONYMOUS,
+            ImpersonationLevel::Identification => winbase::SECURITY_IDENTIFICATION,
+            ImpersonationLevel::Impersonation => winbase::SECURITY_IMPERSONATION,
+            ImpersonationLevel::Delegation => winbase::SECURITY_DELEGATION,
+        }
+    }
+}
+
+/// Options of the connections to a named pipe, for
+/// [`Docker::connect_with_named_pipe_options`](crate::Docker::connect_with_named_pipe_options()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NamedPipeOptions {
+    /// How long to wait for an instance of a busy pipe, or `None` to wait indefinitely.
+    pub busy_timeout: Option<Duration>,
+    /// How long to wait between two attempts to open a busy pipe.
+    pub busy_retry_interval: Duration,
+    /// Impersonation level granted to the daemon.
+    pub impersonation_level: ImpersonationLevel,
+}
+
+impl Default for NamedPipeOptions {
+    fn default() -> Self {
+        NamedPipeOptions {
+            busy_timeout: None,
+            busy_retry_interval: Duration::from_millis(50),
+            impersonation_level: ImpersonationLevel::default(),
+        }
+    }
+}
+
+pin_project! {
+    /// A connection to a named pipe.
+    #[derive(Debug)]
+    pub(crate) struct NamedPipeStream {
+        #[pin]
+        io: NamedPipeClient,
+    }
+}
+
+impl NamedPipeStream {
+    async fn connect(addr: String, options: NamedPipeOptions) -> Result<Self, io::Error> {
+        let mut client_options = ClientOptions::new();
+        client_options.security_qos_flags(options.impersonation_level.security_qos_flags());
+        let deadline = options.busy_timeout.map(|timeout| Instant::now() + timeout);
+
+        let client = loop {
+            match client_options.open(&addr) {
+                Ok(client) => break client,
+                Err(e) if e.raw_os_error() == Some(winerror::ERROR_PIPE_BUSY as i32) => (),
+                Err(e) => return Err(e),
+            };
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!(
+                        "named pipe {addr} still busy after {:?}",
+                        options.busy_timeout
+                    ),
+                ));
+            }
+            time::sleep(options.busy_retry_interval).await;
+        };
+
+        Ok(NamedPipeStream { io: client })
+    }
+}
+
+impl hyper::rt::Read for NamedPipeStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        let mut t = TokioIo::new(self.project().io);
+        Pin::new(&mut t).poll_read(cx, buf)
+    }
+}
+
+impl hyper::rt::Write for NamedPipeStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl Connection for NamedPipeStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+/// Connects a hyper client to the named pipe hex-encoded in the host of the request uri.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NamedPipeConnector {
+    pub(crate) options: NamedPipeOptions,
+}
+
+impl tower_service::Service<hyper::Uri> for NamedPipeConnector {
+    type Response = NamedPipeStream;
+    type Error = io::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, destination: hyper::Uri) -> Self::Future {
+        let options = self.options;
+        let fut = async move {
+            if destination.scheme_str() != Some(NAMED_PIPE_SCHEME) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid scheme {:?}", destination.scheme()),
+                ));
+            }
+            let host = destination.host().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid uri {destination:?}"),
+                )
+            })?;
+            let bytes = Vec::from_hex(host).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid URL, host must be a hex-encoded path",
+                )
+            })?;
+
+            NamedPipeStream::connect(String::from_utf8_lossy(&bytes).into_owned(), options).await
+        };
+
+        Box::pin(fut)
+    }
+}
@@ -4,6 +4,9 @@ use bytes::Bytes;
 use http::request::Builder;
 use http_body_util::Full;
 use hyper::Method;
+#[cfg(feature = "container")]
+use log::debug;
+use serde::de::DeserializeOwned;
 use serde_derive::{Deserialize, Serialize};
 
 use std::cmp::Eq;
@@ -11,11 +14,19 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 use super::Docker;
+#[cfg(feature = "container")]
+use crate::container::{
+    Config, CreateContainerOptions, ListContainersOptions, NetworkingConfig, RemoveContainerOptions,
+};
 use crate::docker::BodyType;
 use crate::errors::Error;
+#[cfg(feature = "container")]
+use crate::errors::ErrorKind;
 
 use crate::models::*;
 
+pub mod builder;
+
 /// Network configuration used in the [Create Network API](Docker::create_network())
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -189,6 +200,178 @@ where
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// The networks of the daemon, and the containers connected to them, as returned by the [Inspect
+/// Topology API](Docker::inspect_topology())
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkTopology {
+    /// All networks, each with its connected containers.
+    pub networks: Vec<NetworkNode>,
+}
+
+impl NetworkTopology {
+    /// Find a network by its ID or name.
+    pub fn network(&self, id_or_name: &str) -> Option<&NetworkNode> {
+        self.networks.iter().find(|node| {
+            node.network.id.as_deref() == Some(id_or_name)
+                || node.network.name.as_deref() == Some(id_or_name)
+        })
+    }
+
+    /// Iterate over the networks a container is connected to, by the container's ID or name.
+    pub fn networks_of<'a>(
+        &'a self,
+        container: &'a str,
+    ) -> impl Iterator<Item = (&'a NetworkNode, &'a NetworkEndpoint)> + 'a {
+        self.networks.iter().flat_map(move |node| {
+            node.endpoints
+                .iter()
+                .filter(move |endpoint| {
+                    endpoint.container_id == container
+                        || endpoint.container_name.as_deref() == Some(container)
+                })
+                .map(move |endpoint| (node, endpoint))
+        })
+    }
+}
+
+/// A network in a [Network Topology](NetworkTopology)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkNode {
+    /// The network, as returned by the [List Networks API](Docker::list_networks()).
+    pub network: Network,
+    /// The containers connected to the network.
+    pub endpoints: Vec<NetworkEndpoint>,
+}
+
+/// A container's connection to a network in a [Network Topology](NetworkTopology)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkEndpoint {
+    /// The container's ID.
+    pub container_id: String,
+    /// The container's name, without the leading slash.
+    pub container_name: Option<String>,
+    /// The endpoint's ID.
+    pub endpoint_id: Option<String>,
+    /// The container's IPv4 address on the network.
+    pub ipv4_address: Option<String>,
+    /// The container's global IPv6 address on the network.
+    pub ipv6_address: Option<String>,
+    /// The container's MAC address on the network.
+    pub mac_address: Option<String>,
+    /// The container's aliases on the network.
+    pub aliases: Vec<String>,
+    /// The container's DNS names on the network.
+    pub dns_names: Vec<String>,
+    /// The IPv4 gateway of the endpoint.
+    pub gateway: Option<String>,
+    /// The IPv6 gateway of the endpoint.
+    pub ipv6_gateway: Option<String>,
+}
+
+#[cfg(feature = "container")]
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.is_empty())
+}
+
+#[cfg(feature = "container")]
+fn network_topology(networks: Vec<Network>, containers: Vec<ContainerSummary>) -> NetworkTopology {
+    let mut networks: Vec<NetworkNode> = networks
+        .into_iter()
+        .map(|network| NetworkNode {
+            network,
+            endpoints: Vec::new(),
+        })
+        .collect();
+
+    for container in containers {
+        let container_id = container.id.unwrap_or_default();
+        let container_name = container
+            .names
+            .and_then(|names| names.into_iter().next())
+            .map(|name| name.trim_start_matches('/').to_string());
+
+        let settings = container
+            .network_settings
+            .and_then(|settings| settings.networks)
+            .unwrap_or_default();
+        for (network_name, settings) in settings {
+            let node = networks.iter_mut().find(|node| match &settings.network_id {
+                Some(id) if !id.is_empty() => node.network.id.as_ref() == Some(id),
+                _ => node.network.name.as_ref() == Some(&network_name),
+            });
+
+            if let Some(node) = node {
+                node.endpoints.push(NetworkEndpoint {
+                    container_id: container_id.clone(),
+                    container_name: container_name.clone(),
+                    endpoint_id: non_empty(settings.endpoint_id),
+                    ipv4_address: non_empty(settings.ip_address),
+                    ipv6_address: non_empty(settings.global_ipv6_address),
+                    mac_address: non_empty(settings.mac_address),
+                    aliases: settings.aliases.unwrap_or_default(),
+                    dns_names: settings.dns_names.unwrap_or_default(),
+                    gateway: non_empty(settings.gateway),
+                    ipv6_gateway: non_empty(settings.ipv6_gateway),
+                });
+            }
+        }
+    }
+
+    NetworkTopology { networks }
+}
+
+/// Networks the daemon creates itself, which are never removed.
+#[cfg(feature = "container")]
+const PREDEFINED_NETWORKS: [&str; 5] = ["bridge", "host", "none", "default", "docker_gwbridge"];
+
+#[cfg(feature = "container")]
+fn is_predefined(network: &Network) -> bool {
+    network.ingress == Some(true)
+        || network
+            .name
+            .as_deref()
+            .is_some_and(|name| PREDEFINED_NETWORKS.contains(&name))
+}
+
+/// Whether the container, running or not, is configured with the network.
+#[cfg(feature = "container")]
+fn is_connected(container: &ContainerSummary, network: &Network) -> bool {
+    container
+        .network_settings
+        .as_ref()
+        .and_then(|settings| settings.networks.as_ref())
+        .is_some_and(|settings| {
+            settings
+                .iter()
+                .any(|(network_name, settings)| match &settings.network_id {
+                    Some(id) if !id.is_empty() => network.id.as_ref() == Some(id),
+                    _ => network.name.as_ref() == Some(network_name),
+                })
+        })
+}
+
+/// The user-defined networks no container is configured with, including stopped containers.
+#[cfg(feature = "container")]
+fn unused_networks(networks: Vec<Network>, containers: &[ContainerSummary]) -> Vec<Network> {
+    networks
+        .into_iter()
+        .filter(|network| {
+            !is_predefined(network)
+                && !containers
+                    .iter()
+                    .any(|container| is_connected(container, network))
+        })
+        .collect()
+}
+
+#[cfg(feature = "container")]
+fn is_active_endpoints(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::DockerResponseServerError { message, .. } if message.contains("active endpoints")
+    )
+}
+
 impl Docker {
     /// ---
     ///
@@ -235,7 +418,7 @@ impl Docker {
             url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_value(req).await
@@ -311,6 +494,54 @@ impl Docker {
     ) -> Result<Network, Error>
     where
         T: Into<String> + serde::ser::Serialize,
+    {
+        self.inspect_network_as(network_name, options).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Network As
+    ///
+    /// Inspect a network, like the [Inspect Network API](Docker::inspect_network()), deserialized into a type of the
+    /// caller, e.g. a slim struct with only the fields it needs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Network name as a string slice.
+    ///  - Optional [Inspect Network Options](InspectNetworkOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - The deserialized response, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::network::InspectNetworkOptions;
+    ///
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Network {
+    ///     id: String,
+    ///     driver: String,
+    /// }
+    ///
+    /// let options = None::<InspectNetworkOptions<String>>;
+    ///
+    /// docker.inspect_network_as::<_, Network>("my_network_name", options);
+    /// ```
+    pub async fn inspect_network_as<T, R>(
+        &self,
+        network_name: &str,
+        options: Option<InspectNetworkOptions<T>>,
+    ) -> Result<R, Error>
+    where
+        T: Into<String> + serde::ser::Serialize,
+        R: DeserializeOwned,
     {
         let url = format!("/networks/{network_name}");
 
@@ -362,6 +593,50 @@ impl Docker {
     ) -> Result<Vec<Network>, Error>
     where
         T: Into<String> + Eq + Hash + serde::ser::Serialize,
+    {
+        self.list_networks_as(options).await
+    }
+
+    /// ---
+    ///
+    /// # List Networks As
+    ///
+    /// List the networks, like the [List Networks API](Docker::list_networks()), deserialized into a type of the
+    /// caller, e.g. a slim struct with only the fields it needs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [List Network Options](ListNetworksOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of the deserialized items, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::network::ListNetworksOptions;
+    ///
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Network {
+    ///     id: String,
+    ///     name: String,
+    /// }
+    ///
+    /// docker.list_networks_as::<_, Network>(None::<ListNetworksOptions<String>>);
+    /// ```
+    pub async fn list_networks_as<T, R>(
+        &self,
+        options: Option<ListNetworksOptions<T>>,
+    ) -> Result<Vec<R>, Error>
+    where
+        T: Into<String> + Eq + Hash + serde::ser::Serialize,
+        R: DeserializeOwned,
     {
         let url = "/networks";
 
@@ -426,7 +701,7 @@ impl Docker {
             &url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_unit(req).await
@@ -475,7 +750,7 @@ impl Docker {
             &url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_unit(req).await
@@ -532,4 +807,451 @@ impl Docker {
 
         self.process_into_value(req).await
     }
+
+    /// ---
+    ///
+    /// # Inspect Topology
+    ///
+    /// Returns all networks together with the running containers connected to them, including
+    /// their addresses, aliases and gateways. The topology is assembled from a single [List
+    /// Networks](Docker::list_networks()) and [List Containers](Docker::list_containers()) call
+    /// each, instead of inspecting every network.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Network Topology](NetworkTopology) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// async move {
+    ///     let topology = docker.inspect_topology().await.unwrap();
+    ///     for node in topology.networks {
+    ///         for endpoint in node.endpoints {
+    ///             println!("{:?} -> {:?}", node.network.name, endpoint.ipv4_address);
+    ///         }
+    ///     }
+    /// };
+    /// ```
+    #[cfg(feature = "container")]
+    pub async fn inspect_topology(&self) -> Result<NetworkTopology, Error> {
+        let networks = self
+            .list_networks(None::<ListNetworksOptions<String>>)
+            .await?;
+        let containers = self
+            .list_containers(None::<ListContainersOptions<String>>)
+            .await?;
+
+        Ok(network_topology(networks, containers))
+    }
+
+    /// ---
+    ///
+    /// # Prune Unused Networks
+    ///
+    /// Remove the user-defined networks no container is configured with. Unlike the [Prune
+    /// Networks API](Docker::prune_networks()), which only keeps the networks of running
+    /// containers, networks of stopped containers are kept, so that the containers can still be
+    /// started. The networks the daemon creates itself are never removed.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [List Networks Options](ListNetworksOptions) struct, selecting the networks to
+    ///    consider, e.g. by label.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Network Prune Response](NetworkPruneResponse) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::network::ListNetworksOptions;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let mut filters = HashMap::new();
+    /// filters.insert("label", vec!["maintainer=some_maintainer"]);
+    ///
+    /// docker.prune_unused_networks(Some(ListNetworksOptions { filters }));
+    /// ```
+    #[cfg(feature = "container")]
+    pub async fn prune_unused_networks<T>(
+        &self,
+        options: Option<ListNetworksOptions<T>>,
+    ) -> Result<NetworkPruneResponse, Error>
+    where
+        T: Into<String> + Eq + Hash + serde::ser::Serialize,
+    {
+        let networks = self.list_networks(options).await?;
+        let containers = self
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                ..Default::default()
+            }))
+            .await?;
+
+        let mut deleted = Vec::new();
+        for network in unused_networks(networks, &containers) {
+            let Some(id) = network.id else {
+                continue;
+            };
+            match self.remove_network(&id).await {
+                Ok(()) => deleted.push(network.name.unwrap_or(id)),
+                // a container connected to the network since it was listed
+                Err(e) if is_active_endpoints(&e) => {
+                    debug!("keeping network {id}: {e}");
+                }
+                Err(e) if e.kind() == Some(ErrorKind::NotFound) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(NetworkPruneResponse {
+            networks_deleted: Some(deleted),
+        })
+    }
+
+    /// ---
+    ///
+    /// # Safe Remove Network
+    ///
+    /// Remove a network after disconnecting the stopped containers configured with it, so that
+    /// they can still be started. Running containers are disconnected as well if
+    /// `disconnect_running` is set, otherwise a [Network In Use
+    /// Error](Error::NetworkInUseError) lists them and the network is kept.
+    ///
+    /// The removal is retried once if a container connects to the network in the meantime and the
+    /// daemon reports active endpoints.
+    ///
+    /// # Arguments
+    ///
+    ///  - Network name as a string slice.
+    ///  - Whether to force-disconnect running containers.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.safe_remove_network("my_network_name", false);
+    /// ```
+    #[cfg(feature = "container")]
+    pub async fn safe_remove_network(
+        &self,
+        network_name: &str,
+        disconnect_running: bool,
+    ) -> Result<(), Error> {
+        let network = self
+            .inspect_network(network_name, None::<InspectNetworkOptions<String>>)
+            .await?;
+        let network_id = network
+            .id
+            .clone()
+            .unwrap_or_else(|| String::from(network_name));
+
+        let mut retried = false;
+        loop {
+            let containers: Vec<ContainerSummary> = self
+                .list_containers(Some(ListContainersOptions::<String> {
+                    all: true,
+                    ..Default::default()
+                }))
+                .await?
+                .into_iter()
+                .filter(|container| is_connected(container, &network))
+                .collect();
+
+            let running: Vec<String> = containers
+                .iter()
+                .filter(|container| container.state.as_deref() == Some("running"))
+                .filter_map(|container| container.id.clone())
+                .collect();
+            if !disconnect_running && !running.is_empty() {
+                return Err(Error::NetworkInUseError {
+                    network: String::from(network_name),
+                    containers: running,
+                });
+            }
+
+            for id in containers.into_iter().filter_map(|container| container.id) {
+                match self
+                    .disconnect_network(
+                        &network_id,
+                        DisconnectNetworkOptions {
+                            container: &id[..],
+                            force: true,
+                        },
+                    )
+                    .await
+                {
+                    Err(e) if e.kind() == Some(ErrorKind::NotFound) => (),
+                    res => res?,
+                }
+                debug!("disconnected container {id} from network {network_name}");
+            }
+
+            match self.remove_network(&network_id).await {
+                Err(e) if is_active_endpoints(&e) && !retried => retried = true,
+                res => return res,
+            }
+        }
+    }
+
+    /// ---
+    ///
+    /// # Create Container With Networks
+    ///
+    /// Create a container connected to several networks, each with its own endpoint settings,
+    /// e.g. aliases or static addresses. Daemons before API version 1.44 only honour one network
+    /// of the `networking_config` at creation, so the container is created on the first network
+    /// and connected to the others before it is returned, and thus before it is first started.
+    /// If a network fails to connect, the container is removed again.
+    ///
+    /// The first network becomes the container's `network_mode`, unless the configuration sets
+    /// one.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [Create Container Options](CreateContainerOptions) struct.
+    ///  - Container [Config](Config) struct. Its `networking_config` is replaced by the networks.
+    ///  - The networks by name or ID, with the [Endpoint Settings](EndpointSettings) of the
+    ///    container on each network.
+    ///
+    /// # Returns
+    ///
+    ///  - [ContainerCreateResponse](ContainerCreateResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::{Config, CreateContainerOptions, StartContainerOptions};
+    /// use bollard::models::{EndpointIpamConfig, EndpointSettings};
+    ///
+    /// let config = Config {
+    ///     image: Some(String::from("nginx:alpine")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let networks = [
+    ///     (
+    ///         "frontend",
+    ///         EndpointSettings {
+    ///             aliases: Some(vec![String::from("web")]),
+    ///             ..Default::default()
+    ///         },
+    ///     ),
+    ///     (
+    ///         "backend",
+    ///         EndpointSettings {
+    ///             ipam_config: Some(EndpointIpamConfig {
+    ///                 ipv4_address: Some(String::from("10.10.10.2")),
+    ///                 ..Default::default()
+    ///             }),
+    ///             ..Default::default()
+    ///         },
+    ///     ),
+    /// ];
+    ///
+    /// async move {
+    ///     let id = docker
+    ///         .create_container_with_networks(
+    ///             None::<CreateContainerOptions<String>>,
+    ///             config,
+    ///             networks,
+    ///         )
+    ///         .await
+    ///         .unwrap()
+    ///         .id;
+    ///     docker
+    ///         .start_container(&id, None::<StartContainerOptions<String>>)
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    #[cfg(feature = "container")]
+    pub async fn create_container_with_networks<T, I, S>(
+        &self,
+        options: Option<CreateContainerOptions<T>>,
+        mut config: Config<String>,
+        networks: I,
+    ) -> Result<ContainerCreateResponse, Error>
+    where
+        T: Into<String> + serde::ser::Serialize,
+        I: IntoIterator<Item = (S, EndpointSettings)>,
+        S: Into<String>,
+    {
+        let mut networks: Vec<(String, EndpointSettings)> = networks
+            .into_iter()
+            .map(|(network, settings)| (network.into(), settings))
+            .collect();
+
+        let version = self.client_version();
+        let remaining = if (version.major_version, version.minor_version) >= (1, 44) {
+            Vec::new()
+        } else {
+            networks.split_off(networks.len().min(1))
+        };
+
+        if let Some((network, _)) = networks.first() {
+            let host_config = config.host_config.get_or_insert_with(Default::default);
+            if host_config.network_mode.is_none() {
+                host_config.network_mode = Some(network.clone());
+            }
+        }
+        config.networking_config = Some(NetworkingConfig {
+            endpoints_config: networks.into_iter().collect(),
+        });
+
+        let response = self.create_container(options, config).await?;
+
+        for (network, settings) in remaining {
+            if let Err(e) = self
+                .connect_network(
+                    &network,
+                    ConnectNetworkOptions {
+                        container: &response.id[..],
+                        endpoint_config: settings,
+                    },
+                )
+                .await
+            {
+                debug!("removing container {}: {e}", response.id);
+                let _ = self
+                    .remove_container(
+                        &response.id,
+                        Some(RemoveContainerOptions {
+                            force: true,
+                            ..Default::default()
+                        }),
+                    )
+                    .await;
+                return Err(e);
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::models::*;
+
+    #[cfg(feature = "container")]
+    use super::{network_topology, unused_networks};
+
+    #[test]
+    #[cfg(feature = "container")]
+    fn test_network_topology() {
+        let networks = vec![
+            Network {
+                id: Some(String::from("1f6b4a")),
+                name: Some(String::from("backend")),
+                ..Default::default()
+            },
+            Network {
+                id: Some(String::from("9c0d2e")),
+                name: Some(String::from("frontend")),
+                ..Default::default()
+            },
+        ];
+
+        let mut settings = HashMap::new();
+        settings.insert(
+            String::from("backend"),
+            EndpointSettings {
+                network_id: Some(String::from("1f6b4a")),
+                ip_address: Some(String::from("10.10.10.2")),
+                global_ipv6_address: Some(String::new()),
+                aliases: Some(vec![String::from("db")]),
+                gateway: Some(String::from("10.10.10.1")),
+                ..Default::default()
+            },
+        );
+        let containers = vec![ContainerSummary {
+            id: Some(String::from("a8c5f0")),
+            names: Some(vec![String::from("/postgres")]),
+            network_settings: Some(ContainerSummaryNetworkSettings {
+                networks: Some(settings),
+            }),
+            ..Default::default()
+        }];
+
+        let topology = network_topology(networks, containers);
+
+        assert!(topology.network("frontend").unwrap().endpoints.is_empty());
+
+        let (node, endpoint) = topology.networks_of("postgres").next().unwrap();
+        assert_eq!(node.network.name.as_deref(), Some("backend"));
+        assert_eq!(endpoint.container_id, "a8c5f0");
+        assert_eq!(endpoint.ipv4_address.as_deref(), Some("10.10.10.2"));
+        assert_eq!(endpoint.ipv6_address, None);
+        assert_eq!(endpoint.aliases, vec!["db"]);
+        assert_eq!(endpoint.gateway.as_deref(), Some("10.10.10.1"));
+    }
+
+    #[cfg(feature = "container")]
+    #[test]
+    fn test_unused_networks() {
+        let network = |id: &str, name: &str| Network {
+            id: Some(String::from(id)),
+            name: Some(String::from(name)),
+            ..Default::default()
+        };
+        let networks = vec![
+            network("b1", "bridge"),
+            network("c2", "backend"),
+            network("d3", "frontend"),
+            network("e4", "dangling"),
+            Network {
+                ingress: Some(true),
+                ..network("f5", "ingress")
+            },
+        ];
+
+        let container = |state: &str, network_name: &str, network_id: &str| {
+            let mut settings = HashMap::new();
+            settings.insert(
+                String::from(network_name),
+                EndpointSettings {
+                    network_id: Some(String::from(network_id)),
+                    ..Default::default()
+                },
+            );
+            ContainerSummary {
+                state: Some(String::from(state)),
+                network_settings: Some(ContainerSummaryNetworkSettings {
+                    networks: Some(settings),
+                }),
+                ..Default::default()
+            }
+        };
+        let containers = vec![
+            container("running", "backend", "c2"),
+            // stopped containers keep the network, matched by name without an ID
+            container("exited", "frontend", ""),
+        ];
+
+        let unused: Vec<_> = unused_networks(networks, &containers)
+            .into_iter()
+            .filter_map(|network| network.name)
+            .collect();
+        assert_eq!(unused, ["dangling"]);
+    }
 }
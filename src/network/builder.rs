@@ -0,0 +1,360 @@
+//! Builder for the [Create Network API](crate::Docker::create_network()) with typed IPAM
+//! configuration.
+//!
+//! Addresses are validated when the options are built, so a malformed subnet or a gateway
+//! outside of its subnet is reported before the request reaches the daemon.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::network::builder::NetworkCreateBuilder;
+//!
+//! let options = NetworkCreateBuilder::new("backend")
+//!     .subnet("10.1.0.0/16")
+//!     .gateway("10.1.0.1")
+//!     .ip_range("10.1.4.0/24")
+//!     .bridge_name("br-backend")
+//!     .internal()
+//!     .build()
+//!     .unwrap();
+//!
+//! docker.create_network(options);
+//! ```
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use super::CreateNetworkOptions;
+use crate::errors::Error;
+use crate::models::{Ipam, IpamConfig};
+
+/// A parsed `address/prefix` network block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cidr {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    fn parse(value: &str) -> Result<Cidr, Error> {
+        let invalid = || Error::InvalidNetworkConfigError {
+            message: format!("invalid CIDR block: {value}"),
+        };
+
+        let (address, prefix) = value.split_once('/').ok_or_else(invalid)?;
+        let address: IpAddr = address.parse().map_err(|_| invalid())?;
+        let prefix: u8 = prefix.parse().map_err(|_| invalid())?;
+        let max = if address.is_ipv4() { 32 } else { 128 };
+        if prefix > max {
+            return Err(invalid());
+        }
+
+        Ok(Cidr { address, prefix })
+    }
+
+    fn contains(&self, address: IpAddr) -> bool {
+        match (self.address, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            }
+            _ => false,
+        }
+    }
+
+    fn contains_block(&self, other: &Cidr) -> bool {
+        other.prefix >= self.prefix && self.contains(other.address)
+    }
+}
+
+/// Builder for [Create Network Options](CreateNetworkOptions).
+///
+/// Each call to [`subnet`](NetworkCreateBuilder::subnet()) starts a new IPAM pool; the
+/// [`gateway`](NetworkCreateBuilder::gateway()), [`ip_range`](NetworkCreateBuilder::ip_range())
+/// and [`aux_address`](NetworkCreateBuilder::aux_address()) calls that follow apply to that pool.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkCreateBuilder {
+    options: CreateNetworkOptions<String>,
+    pools: Vec<IpamConfig>,
+    errors: Vec<String>,
+}
+
+impl NetworkCreateBuilder {
+    /// Start a network with the given name, using the daemon's default driver.
+    pub fn new(name: &str) -> Self {
+        NetworkCreateBuilder {
+            options: CreateNetworkOptions {
+                name: String::from(name),
+                check_duplicate: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Use the given network driver, e.g. `bridge`, `overlay` or `macvlan`.
+    pub fn driver(mut self, driver: &str) -> Self {
+        self.options.driver = String::from(driver);
+        self
+    }
+
+    /// Use the given IPAM driver instead of `default`.
+    pub fn ipam_driver(mut self, driver: &str) -> Self {
+        self.options.ipam.driver = Some(String::from(driver));
+        self
+    }
+
+    /// Add an IPAM pool for the given subnet, in CIDR notation.
+    pub fn subnet(mut self, subnet: &str) -> Self {
+        self.pools.push(IpamConfig {
+            subnet: Some(String::from(subnet)),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Set the gateway of the current subnet.
+    pub fn gateway(mut self, gateway: &str) -> Self {
+        if let Some(pool) = self.current_pool("gateway") {
+            pool.gateway = Some(String::from(gateway));
+        }
+        self
+    }
+
+    /// Allocate container addresses of the current subnet from the given range, in CIDR notation.
+    pub fn ip_range(mut self, ip_range: &str) -> Self {
+        if let Some(pool) = self.current_pool("ip_range") {
+            pool.ip_range = Some(String::from(ip_range));
+        }
+        self
+    }
+
+    /// Reserve an address of the current subnet for use outside of the network, e.g. by a host.
+    pub fn aux_address(mut self, name: &str, address: &str) -> Self {
+        if let Some(pool) = self.current_pool("aux_address") {
+            pool.auxiliary_addresses
+                .get_or_insert_with(HashMap::new)
+                .insert(String::from(name), String::from(address));
+        }
+        self
+    }
+
+    /// Enable IPv6 on the network.
+    pub fn enable_ipv6(mut self) -> Self {
+        self.options.enable_ipv6 = true;
+        self
+    }
+
+    /// Restrict external access to the network.
+    pub fn internal(mut self) -> Self {
+        self.options.internal = true;
+        self
+    }
+
+    /// Allow regular containers to attach to a swarm scoped network.
+    pub fn attachable(mut self) -> Self {
+        self.options.attachable = true;
+        self
+    }
+
+    /// Add a label to the network.
+    pub fn label(mut self, key: &str, value: &str) -> Self {
+        self.options
+            .labels
+            .insert(String::from(key), String::from(value));
+        self
+    }
+
+    /// Add a driver specific option.
+    pub fn option(mut self, key: &str, value: &str) -> Self {
+        self.options
+            .options
+            .insert(String::from(key), String::from(value));
+        self
+    }
+
+    /// Use the `bridge` driver, with the given name for the bridge interface on the host.
+    pub fn bridge_name(self, name: &str) -> Self {
+        self.driver("bridge")
+            .option("com.docker.network.bridge.name", name)
+    }
+
+    /// Use the `bridge` driver, enabling or disabling communication between its containers.
+    pub fn bridge_enable_icc(self, enable: bool) -> Self {
+        self.driver("bridge")
+            .option("com.docker.network.bridge.enable_icc", &enable.to_string())
+    }
+
+    /// Use the `bridge` driver, enabling or disabling IP masquerading of outgoing traffic.
+    pub fn bridge_enable_ip_masquerade(self, enable: bool) -> Self {
+        self.driver("bridge").option(
+            "com.docker.network.bridge.enable_ip_masquerade",
+            &enable.to_string(),
+        )
+    }
+
+    /// Set the MTU of the network's interfaces.
+    pub fn mtu(self, mtu: u32) -> Self {
+        self.option("com.docker.network.driver.mtu", &mtu.to_string())
+    }
+
+    /// Use the `overlay` driver, encrypting the traffic between nodes.
+    pub fn overlay_encrypted(self) -> Self {
+        self.driver("overlay").option("encrypted", "")
+    }
+
+    /// Use the `macvlan` driver, attached to the given host interface.
+    pub fn macvlan_parent(self, parent: &str) -> Self {
+        self.driver("macvlan").option("parent", parent)
+    }
+
+    /// Use the `macvlan` driver in the given mode, e.g. `bridge`, `private`, `vepa` or
+    /// `passthru`.
+    pub fn macvlan_mode(self, mode: &str) -> Self {
+        self.driver("macvlan").option("macvlan_mode", mode)
+    }
+
+    /// Validate the configuration and produce the options for the [Create Network
+    /// API](crate::Docker::create_network()).
+    pub fn build(self) -> Result<CreateNetworkOptions<String>, Error> {
+        if let Some(message) = self.errors.into_iter().next() {
+            return Err(Error::InvalidNetworkConfigError { message });
+        }
+
+        for pool in &self.pools {
+            let subnet = Cidr::parse(pool.subnet.as_deref().unwrap_or_default())?;
+
+            if subnet.address.is_ipv6() && !self.options.enable_ipv6 {
+                return Err(Error::InvalidNetworkConfigError {
+                    message: format!(
+                        "IPv6 subnet {} requires enable_ipv6",
+                        pool.subnet.as_deref().unwrap_or_default()
+                    ),
+                });
+            }
+
+            if let Some(ip_range) = &pool.ip_range {
+                if !subnet.contains_block(&Cidr::parse(ip_range)?) {
+                    return Err(outside_subnet("ip range", ip_range, &subnet));
+                }
+            }
+
+            let addresses = pool.gateway.iter().chain(
+                pool.auxiliary_addresses
+                    .iter()
+                    .flat_map(|addresses| addresses.values()),
+            );
+            for address in addresses {
+                let parsed: IpAddr =
+                    address
+                        .parse()
+                        .map_err(|_| Error::InvalidNetworkConfigError {
+                            message: format!("invalid IP address: {address}"),
+                        })?;
+                if !subnet.contains(parsed) {
+                    return Err(outside_subnet("address", address, &subnet));
+                }
+            }
+        }
+
+        let mut options = self.options;
+        if !self.pools.is_empty() {
+            options.ipam = Ipam {
+                config: Some(self.pools),
+                ..options.ipam
+            };
+        }
+
+        Ok(options)
+    }
+
+    fn current_pool(&mut self, setting: &str) -> Option<&mut IpamConfig> {
+        if self.pools.is_empty() {
+            self.errors
+                .push(format!("{setting} must follow a subnet declaration"));
+        }
+        self.pools.last_mut()
+    }
+}
+
+fn outside_subnet(kind: &str, value: &str, subnet: &Cidr) -> Error {
+    Error::InvalidNetworkConfigError {
+        message: format!(
+            "{kind} {value} is outside of subnet {}/{}",
+            subnet.address, subnet.prefix
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NetworkCreateBuilder;
+    use crate::errors::Error;
+
+    #[test]
+    fn test_network_create_builder() {
+        let options = NetworkCreateBuilder::new("backend")
+            .subnet("10.1.0.0/16")
+            .gateway("10.1.0.1")
+            .ip_range("10.1.4.0/24")
+            .aux_address("host", "10.1.0.2")
+            .subnet("fd00:1::/64")
+            .enable_ipv6()
+            .bridge_name("br-backend")
+            .build()
+            .unwrap();
+
+        assert_eq!(options.driver, "bridge");
+        assert!(options.enable_ipv6);
+        assert_eq!(
+            options.options.get("com.docker.network.bridge.name"),
+            Some(&String::from("br-backend"))
+        );
+
+        let pools = options.ipam.config.unwrap();
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].gateway.as_deref(), Some("10.1.0.1"));
+        assert_eq!(pools[0].ip_range.as_deref(), Some("10.1.4.0/24"));
+        assert_eq!(pools[1].subnet.as_deref(), Some("fd00:1::/64"));
+    }
+
+    #[test]
+    fn test_network_create_builder_validation() {
+        let invalid = |builder: NetworkCreateBuilder| {
+            matches!(
+                builder.build(),
+                Err(Error::InvalidNetworkConfigError { .. })
+            )
+        };
+
+        assert!(invalid(NetworkCreateBuilder::new("n").subnet("10.1.0.0")));
+        assert!(invalid(
+            NetworkCreateBuilder::new("n").subnet("10.1.0.0/33")
+        ));
+        assert!(invalid(NetworkCreateBuilder::new("n").subnet("10.1.0/16")));
+        assert!(invalid(
+            NetworkCreateBuilder::new("n")
+                .subnet("10.1.0.0/16")
+                .gateway("10.2.0.1")
+        ));
+        assert!(invalid(
+            NetworkCreateBuilder::new("n")
+                .subnet("10.1.0.0/16")
+                .ip_range("10.0.0.0/8")
+        ));
+        assert!(invalid(NetworkCreateBuilder::new("n").gateway("10.1.0.1")));
+        assert!(invalid(
+            NetworkCreateBuilder::new("n").subnet("fd00:1::/64")
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+//! Platforms, such as `linux/amd64` or `linux/arm/v7`, parsed and normalized the way containerd
+//! does, so that the same platform is sent in the same format to every API.
+//!
+//! The `platform` of the [Create Image API](crate::Docker::create_image()), the [Create Container
+//! API](crate::Docker::create_container()) and the [Build Image
+//! API](crate::Docker::build_image()) take the [formatted](Platform::to_string()) platform. The
+//! platform converts into the [OCI Platform](OciPlatform) of the models, and into the platforms
+//! of buildkit builds.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::container::{Config, CreateContainerOptions};
+//! use bollard::image::CreateImageOptions;
+//! use bollard::platform::Platform;
+//!
+//! let platform: Platform = "linux/aarch64/v8".parse().unwrap();
+//! assert_eq!(platform.to_string(), "linux/arm64");
+//!
+//! docker.create_image(
+//!     Some(CreateImageOptions {
+//!         from_image: String::from("alpine"),
+//!         platform: platform.to_string(),
+//!         ..Default::default()
+//!     }),
+//!     None,
+//!     None,
+//! );
+//!
+//! docker.create_container(
+//!     Some(CreateContainerOptions {
+//!         name: String::from("alpine-arm64"),
+//!         platform: Some(platform.to_string()),
+//!     }),
+//!     Config {
+//!         image: Some("alpine"),
+//!         ..Default::default()
+//!     },
+//! );
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::Error;
+use crate::models::OciPlatform;
+
+/// A normalized platform: an operating system and a CPU architecture, with an optional variant of
+/// the architecture.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    os: String,
+    architecture: String,
+    variant: Option<String>,
+}
+
+impl Platform {
+    /// Construct a platform from its parts, normalizing them, e.g. `x86_64` into `amd64`.
+    pub fn new(os: &str, architecture: &str, variant: Option<&str>) -> Result<Self, Error> {
+        let platform = match variant {
+            Some(variant) => format!("{os}/{architecture}/{variant}"),
+            None => format!("{os}/{architecture}"),
+        };
+        Self::parse(&platform)
+    }
+
+    /// Parse a platform in the `os/arch[/variant]` format.
+    pub fn parse(platform: &str) -> Result<Self, Error> {
+        let invalid = |message: &str| Error::InvalidPlatformError {
+            platform: String::from(platform),
+            message: String::from(message),
+        };
+
+        let parts: Vec<String> = platform.split('/').map(str::to_lowercase).collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return Err(invalid("expected the format os/arch[/variant]"));
+        }
+        let valid = |part: &String| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+        };
+        if !parts.iter().all(valid) {
+            return Err(invalid("invalid component"));
+        }
+
+        let os = match parts[0].as_str() {
+            "macos" => String::from("darwin"),
+            os => String::from(os),
+        };
+        let variant = parts.get(2).map(String::as_str);
+        let (architecture, variant) = match (parts[1].as_str(), variant) {
+            ("i386", variant) => ("386", variant),
+            ("x86_64" | "x86-64" | "amd64", Some("v1")) => ("amd64", None),
+            ("x86_64" | "x86-64" | "amd64", variant) => ("amd64", variant),
+            ("aarch64" | "arm64", Some("8" | "v8") | None) => ("arm64", None),
+            ("aarch64" | "arm64", variant) => ("arm64", variant),
+            ("armhf", None) => ("arm", Some("v7")),
+            ("armel", None) => ("arm", Some("v6")),
+            ("armhf" | "armel", Some(_)) => return Err(invalid("unexpected variant")),
+            ("arm", None | Some("7")) => ("arm", Some("v7")),
+            ("arm", Some("5")) => ("arm", Some("v5")),
+            ("arm", Some("6")) => ("arm", Some("v6")),
+            ("arm", Some("8")) => ("arm", Some("v8")),
+            (architecture, variant) => (architecture, variant),
+        };
+
+        Ok(Platform {
+            os,
+            architecture: String::from(architecture),
+            variant: variant.map(String::from),
+        })
+    }
+
+    /// The platform of the machine running this process, e.g. to pull the image matching a
+    /// native binary.
+    pub fn host() -> Self {
+        let os = match std::env::consts::OS {
+            "macos" => "darwin",
+            os => os,
+        };
+        let (architecture, variant) = match std::env::consts::ARCH {
+            "x86" => ("386", None),
+            "x86_64" => ("amd64", None),
+            "aarch64" => ("arm64", None),
+            "arm" if cfg!(target_feature = "v7") => ("arm", Some("v7")),
+            "arm" => ("arm", Some("v6")),
+            "powerpc64" if cfg!(target_endian = "little") => ("ppc64le", None),
+            "powerpc64" => ("ppc64", None),
+            "mips64" if cfg!(target_endian = "little") => ("mips64le", None),
+            "loongarch64" => ("loong64", None),
+            architecture => (architecture, None),
+        };
+        Platform {
+            os: String::from(os),
+            architecture: String::from(architecture),
+            variant: variant.map(String::from),
+        }
+    }
+
+    /// The operating system, e.g. `linux` or `windows`.
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    /// The CPU architecture, e.g. `amd64` or `arm64`.
+    pub fn architecture(&self) -> &str {
+        &self.architecture
+    }
+
+    /// The variant of the CPU architecture, e.g. `v7` for `arm`.
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+}
+
+impl FromStr for Platform {
+    type Err = Error;
+
+    fn from_str(platform: &str) -> Result<Self, Self::Err> {
+        Platform::parse(platform)
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.os, self.architecture)?;
+        if let Some(variant) = &self.variant {
+            write!(f, "/{variant}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Platform> for String {
+    fn from(platform: Platform) -> Self {
+        platform.to_string()
+    }
+}
+
+impl From<&Platform> for OciPlatform {
+    fn from(platform: &Platform) -> Self {
+        OciPlatform {
+            architecture: Some(String::clone(&platform.architecture)),
+            os: Some(String::clone(&platform.os)),
+            variant: platform.variant.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<&OciPlatform> for Platform {
+    type Error = Error;
+
+    /// Normalize the platform of an image, e.g. from the [Inspect Image
+    /// API](crate::Docker::inspect_image()), which requires an operating system and architecture.
+    fn try_from(platform: &OciPlatform) -> Result<Self, Self::Error> {
+        Platform::new(
+            platform.os.as_deref().unwrap_or_default(),
+            platform.architecture.as_deref().unwrap_or_default(),
+            platform
+                .variant
+                .as_deref()
+                .filter(|variant| !variant.is_empty()),
+        )
+    }
+}
+
+#[cfg(feature = "buildkit")]
+impl From<&Platform> for crate::grpc::build::ImageBuildPlatform {
+    fn from(platform: &Platform) -> Self {
+        crate::grpc::build::ImageBuildPlatform {
+            architecture: String::clone(&platform.architecture),
+            os: String::clone(&platform.os),
+            variant: platform.variant.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Platform;
+    use crate::errors::Error;
+    use crate::models::OciPlatform;
+
+    #[test]
+    fn test_platform_parse() {
+        let normalized = |platform: &str| Platform::parse(platform).unwrap().to_string();
+        assert_eq!(normalized("linux/amd64"), "linux/amd64");
+        assert_eq!(normalized("Linux/x86_64"), "linux/amd64");
+        assert_eq!(normalized("linux/amd64/v3"), "linux/amd64/v3");
+        assert_eq!(normalized("linux/arm64/v8"), "linux/arm64");
+        assert_eq!(normalized("linux/aarch64"), "linux/arm64");
+        assert_eq!(normalized("linux/arm"), "linux/arm/v7");
+        assert_eq!(normalized("linux/armel"), "linux/arm/v6");
+        assert_eq!(normalized("linux/arm/6"), "linux/arm/v6");
+        assert_eq!(normalized("windows/amd64"), "windows/amd64");
+
+        for platform in [
+            "linux",
+            "linux/",
+            "/amd64",
+            "linux/amd64/v3/extra",
+            "linux/amd 64",
+        ] {
+            assert!(
+                matches!(
+                    Platform::parse(platform),
+                    Err(Error::InvalidPlatformError { .. })
+                ),
+                "{platform}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_platform_conversions() {
+        let platform: Platform = "linux/arm/v7".parse().unwrap();
+        let oci = OciPlatform::from(&platform);
+        assert_eq!(oci.variant.as_deref(), Some("v7"));
+        assert_eq!(Platform::try_from(&oci).unwrap(), platform);
+        assert!(Platform::try_from(&OciPlatform::default()).is_err());
+
+        let host = Platform::host();
+        assert_eq!(Platform::parse(&host.to_string()).unwrap(), host);
+    }
+}
@@ -0,0 +1,492 @@
+//! Projects: groups of containers, networks and volumes that are listed and removed together.
+//!
+//! Resources created through a [`Project`] are named and labelled the way docker compose names
+//! and labels them, so a project can list and tear down resources created by `docker compose`,
+//! and vice versa.
+//!
+//! The containers of a project are paused, unpaused, stopped and started together, in the order
+//! of the dependencies docker compose records between services, with a result for every
+//! container.
+
+use log::debug;
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+use super::Docker;
+use crate::batch::{run, BatchReport};
+use crate::container::{
+    Config, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions,
+    StartContainerOptions, StopContainerOptions,
+};
+use crate::errors::{Error, ErrorKind};
+use crate::models::*;
+use crate::network::{CreateNetworkOptions, ListNetworksOptions};
+use crate::volume::{CreateVolumeOptions, ListVolumesOptions, RemoveVolumeOptions};
+
+/// Label carrying the project name of a resource.
+pub const PROJECT_LABEL: &str = "com.docker.compose.project";
+
+/// Label carrying the service name of a project's container.
+pub const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// Label carrying the services a project's container depends on, as recorded by docker compose,
+/// e.g. `db:service_healthy:false,cache:service_started:false`.
+pub const DEPENDS_ON_LABEL: &str = "com.docker.compose.depends_on";
+
+/// Label carrying the unprefixed name of a project's network.
+pub const NETWORK_LABEL: &str = "com.docker.compose.network";
+
+/// Label carrying the unprefixed name of a project's volume.
+pub const VOLUME_LABEL: &str = "com.docker.compose.volume";
+
+fn service(container: &ContainerSummary) -> Option<&str> {
+    container
+        .labels
+        .as_ref()?
+        .get(SERVICE_LABEL)
+        .map(String::as_str)
+}
+
+/// The name the container is reported under in a [Batch Report](BatchReport), or its ID.
+fn container_name(container: &ContainerSummary) -> Option<String> {
+    container
+        .names
+        .as_ref()
+        .and_then(|names| names.first())
+        .map(|name| String::from(name.trim_start_matches('/')))
+        .or_else(|| container.id.clone())
+}
+
+/// Group the containers into waves, each wave only depending on the services of the previous
+/// waves. Containers in a dependency cycle end up in the last wave.
+fn dependency_waves(containers: Vec<ContainerSummary>) -> Vec<Vec<ContainerSummary>> {
+    let services: HashSet<String> = containers
+        .iter()
+        .filter_map(service)
+        .map(String::from)
+        .collect();
+    let depends_on = |container: &ContainerSummary| -> Vec<String> {
+        container
+            .labels
+            .as_ref()
+            .and_then(|labels| labels.get(DEPENDS_ON_LABEL))
+            .map(|depends_on| {
+                depends_on
+                    .split(',')
+                    .filter_map(|dependency| dependency.split(':').next())
+                    .map(str::trim)
+                    .filter(|dependency| {
+                        services.contains(*dependency) && Some(*dependency) != service(container)
+                    })
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut done: HashSet<String> = HashSet::new();
+    let mut remaining = containers;
+    let mut waves = Vec::new();
+    while !remaining.is_empty() {
+        let (ready, blocked): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|container| {
+            depends_on(container)
+                .iter()
+                .all(|dependency| done.contains(dependency))
+        });
+        if ready.is_empty() {
+            waves.push(blocked);
+            break;
+        }
+        done.extend(ready.iter().filter_map(service).map(String::from));
+        waves.push(ready);
+        remaining = blocked;
+    }
+    waves
+}
+
+/// A named group of resources, scoped by the [`PROJECT_LABEL`] label.
+///
+/// Construct a `Project` using [`project`](Docker::project()).
+#[derive(Debug, Clone)]
+pub struct Project {
+    docker: Docker,
+    name: String,
+}
+
+impl Project {
+    /// The project's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The `label` filter matching the project's resources, for use with the list APIs.
+    pub fn filters(&self) -> HashMap<String, Vec<String>> {
+        let mut filters = HashMap::new();
+        filters.insert(
+            String::from("label"),
+            vec![format!("{PROJECT_LABEL}={}", self.name)],
+        );
+        filters
+    }
+
+    fn labels(
+        &self,
+        mut labels: HashMap<String, String>,
+        kind: &str,
+        name: &str,
+    ) -> HashMap<String, String> {
+        labels.insert(String::from(PROJECT_LABEL), self.name.clone());
+        labels.insert(String::from(kind), String::from(name));
+        labels
+    }
+
+    /// Create a container for the given service, named `<project>-<service>-1`.
+    ///
+    /// # Returns
+    ///
+    ///  - [ContainerCreateResponse](ContainerCreateResponse), wrapped in a Future.
+    pub async fn create_container(
+        &self,
+        service: &str,
+        mut config: Config<String>,
+    ) -> Result<ContainerCreateResponse, Error> {
+        config.labels =
+            Some(self.labels(config.labels.unwrap_or_default(), SERVICE_LABEL, service));
+
+        self.docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: format!("{}-{service}-1", self.name),
+                    platform: None,
+                }),
+                config,
+            )
+            .await
+    }
+
+    /// Create a network, named `<project>_<name>` after the name in the options.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Network Create Response](NetworkCreateResponse) struct, wrapped in a Future.
+    pub async fn create_network(
+        &self,
+        mut config: CreateNetworkOptions<String>,
+    ) -> Result<NetworkCreateResponse, Error> {
+        config.labels = self.labels(config.labels, NETWORK_LABEL, &config.name);
+        config.name = format!("{}_{}", self.name, config.name);
+
+        self.docker.create_network(config).await
+    }
+
+    /// Create a volume, named `<project>_<name>` after the name in the options.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Volume](Volume) struct, wrapped in a Future.
+    pub async fn create_volume(
+        &self,
+        mut config: CreateVolumeOptions<String>,
+    ) -> Result<Volume, Error> {
+        config.labels = self.labels(config.labels, VOLUME_LABEL, &config.name);
+        config.name = format!("{}_{}", self.name, config.name);
+
+        self.docker.create_volume(config).await
+    }
+
+    /// List the project's containers, including stopped containers.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [ContainerSummary](ContainerSummary), wrapped in a Future.
+    pub async fn list_containers(&self) -> Result<Vec<ContainerSummary>, Error> {
+        self.docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters: self.filters(),
+                ..Default::default()
+            }))
+            .await
+    }
+
+    /// List the project's networks.
+    ///
+    /// # Returns
+    ///
+    ///  - A vector of [Network](Network) elements, wrapped in a Future.
+    pub async fn list_networks(&self) -> Result<Vec<Network>, Error> {
+        self.docker
+            .list_networks(Some(ListNetworksOptions {
+                filters: self.filters(),
+            }))
+            .await
+    }
+
+    /// List the project's volumes.
+    ///
+    /// # Returns
+    ///
+    ///  - A vector of [Volume](Volume) elements, wrapped in a Future.
+    pub async fn list_volumes(&self) -> Result<Vec<Volume>, Error> {
+        let response = self
+            .docker
+            .list_volumes(Some(ListVolumesOptions {
+                filters: self.filters(),
+            }))
+            .await?;
+
+        Ok(response.volumes.unwrap_or_default())
+    }
+
+    /// Run the operation on the project's containers in one of the given states, a wave of
+    /// containers at a time, dependencies first unless `reverse` is set.
+    async fn in_dependency_order<F, Fut>(
+        &self,
+        states: &[&str],
+        reverse: bool,
+        operation: F,
+    ) -> Result<BatchReport<()>, Error>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<(), Error>>,
+    {
+        let containers = self
+            .list_containers()
+            .await?
+            .into_iter()
+            .filter(|container| states.contains(&container.state.as_deref().unwrap_or_default()))
+            .collect();
+
+        let mut waves = dependency_waves(containers);
+        if reverse {
+            waves.reverse();
+        }
+
+        let mut report = BatchReport { items: Vec::new() };
+        for wave in waves {
+            let names: Vec<String> = wave.iter().filter_map(container_name).collect();
+            let concurrency = names.len();
+            report
+                .items
+                .extend(run(names, concurrency, &operation).await.items);
+        }
+        Ok(report)
+    }
+
+    /// Pause the project's running containers, dependent services first.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch Report](BatchReport) of the paused containers, wrapped in a Future.
+    pub async fn pause(&self) -> Result<BatchReport<()>, Error> {
+        self.in_dependency_order(&["running"], true, |name| async move {
+            self.docker.pause_container(&name).await
+        })
+        .await
+    }
+
+    /// Unpause the project's paused containers, dependencies first.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch Report](BatchReport) of the unpaused containers, wrapped in a Future.
+    pub async fn unpause(&self) -> Result<BatchReport<()>, Error> {
+        self.in_dependency_order(&["paused"], false, |name| async move {
+            self.docker.unpause_container(&name).await
+        })
+        .await
+    }
+
+    /// Stop the project's running containers, dependent services first.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch Report](BatchReport) of the stopped containers, wrapped in a Future.
+    pub async fn stop(
+        &self,
+        options: Option<StopContainerOptions>,
+    ) -> Result<BatchReport<()>, Error> {
+        self.in_dependency_order(
+            &["running", "paused", "restarting"],
+            true,
+            |name| async move { self.docker.stop_container(&name, options).await },
+        )
+        .await
+    }
+
+    /// Start the project's created and stopped containers, dependencies first.
+    ///
+    /// # Returns
+    ///
+    ///  - [Batch Report](BatchReport) of the started containers, wrapped in a Future.
+    pub async fn start(&self) -> Result<BatchReport<()>, Error> {
+        self.in_dependency_order(&["created", "exited"], false, |name| async move {
+            self.docker
+                .start_container(&name, None::<StartContainerOptions<String>>)
+                .await
+        })
+        .await
+    }
+
+    /// Remove the project's containers and networks, and, if `volumes` is set, its volumes.
+    /// Running containers are killed.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    pub async fn down(&self, volumes: bool) -> Result<(), Error> {
+        for container in self.list_containers().await? {
+            if let Some(id) = container.id {
+                match self
+                    .docker
+                    .remove_container(
+                        &id,
+                        Some(RemoveContainerOptions {
+                            force: true,
+                            ..Default::default()
+                        }),
+                    )
+                    .await
+                {
+                    Err(e) if e.kind() != Some(ErrorKind::NotFound) => return Err(e),
+                    _ => debug!("removed container {id} of project {}", self.name),
+                }
+            }
+        }
+
+        for network in self.list_networks().await? {
+            if let Some(id) = network.id {
+                match self.docker.remove_network(&id).await {
+                    Err(e) if e.kind() != Some(ErrorKind::NotFound) => return Err(e),
+                    _ => debug!("removed network {id} of project {}", self.name),
+                }
+            }
+        }
+
+        if volumes {
+            for volume in self.list_volumes().await? {
+                match self
+                    .docker
+                    .remove_volume(&volume.name, Some(RemoveVolumeOptions { force: true }))
+                    .await
+                {
+                    Err(e) if e.kind() != Some(ErrorKind::NotFound) => return Err(e),
+                    _ => debug!("removed volume {} of project {}", volume.name, self.name),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Project
+    ///
+    /// Scope resource creation, listing and removal to the given project.
+    ///
+    /// # Arguments
+    ///
+    ///  - Project name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Project](Project) handle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::Config;
+    ///
+    /// let project = docker.project("shop");
+    ///
+    /// async move {
+    ///     project
+    ///         .create_container(
+    ///             "web",
+    ///             Config {
+    ///                 image: Some(String::from("nginx:alpine")),
+    ///                 ..Default::default()
+    ///             },
+    ///         )
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     // ...
+    ///
+    ///     project.down(true).await.unwrap();
+    /// };
+    /// ```
+    pub fn project(&self, name: &str) -> Project {
+        Project {
+            docker: self.clone(),
+            name: String::from(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{dependency_waves, DEPENDS_ON_LABEL, SERVICE_LABEL};
+    use crate::models::ContainerSummary;
+
+    fn container(service: &str, depends_on: Option<&str>) -> ContainerSummary {
+        let mut labels = HashMap::new();
+        labels.insert(String::from(SERVICE_LABEL), String::from(service));
+        if let Some(depends_on) = depends_on {
+            labels.insert(String::from(DEPENDS_ON_LABEL), String::from(depends_on));
+        }
+        ContainerSummary {
+            names: Some(vec![format!("/shop-{service}-1")]),
+            labels: Some(labels),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dependency_waves() {
+        let waves = dependency_waves(vec![
+            container("web", Some("api:service_started:false")),
+            container(
+                "api",
+                Some("db:service_healthy:false,cache:service_started:true"),
+            ),
+            container("db", None),
+            container("cache", Some("")),
+            // unknown services are ignored
+            container(
+                "worker",
+                Some("db:service_healthy:false,queue:service_started:false"),
+            ),
+            // a cycle ends up in the last wave
+            container("a", Some("b:service_started:false")),
+            container("b", Some("a:service_started:false")),
+        ]);
+
+        let names: Vec<Vec<&str>> = waves
+            .iter()
+            .map(|wave| {
+                wave.iter()
+                    .map(|container| container.names.as_ref().unwrap()[0].as_str())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                vec!["/shop-db-1", "/shop-cache-1"],
+                vec!["/shop-api-1", "/shop-worker-1"],
+                vec!["/shop-web-1"],
+                vec!["/shop-a-1", "/shop-b-1"],
+            ]
+        );
+    }
+}
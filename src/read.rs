@@ -1,48 +1,75 @@
 use bytes::Buf;
 use bytes::BytesMut;
 use futures_core::Stream;
+use futures_util::ready;
 use hyper::body::Body;
 use hyper::body::Bytes;
+use hyper::body::Frame;
 use hyper::body::Incoming;
+use hyper::body::SizeHint;
 use hyper::upgrade::Upgraded;
+#[cfg(feature = "container")]
+use hyper::Response;
 use log::debug;
+#[cfg(feature = "container")]
 use log::trace;
+use log::warn;
 use pin_project_lite::pin_project;
 use serde::de::DeserializeOwned;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use std::{cmp, io, marker::PhantomData};
+use std::time::Duration;
+use std::{cmp, fmt, io, marker::PhantomData};
 
 use tokio::io::AsyncWrite;
 use tokio::io::{AsyncRead, ReadBuf};
-use tokio_util::codec::Decoder;
+use tokio::time::{Instant, Sleep};
+use tokio_util::codec::{Decoder, FramedRead};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
 
+#[cfg(feature = "container")]
 use crate::container::LogOutput;
 
 use crate::errors::Error;
 use crate::errors::Error::JsonDataError;
+use crate::interceptor::StreamEnd;
 
+#[cfg(feature = "container")]
 #[derive(Debug, Copy, Clone)]
 enum NewlineLogOutputDecoderState {
     WaitingHeader,
     WaitingPayload(u8, usize), // StreamType, Length
 }
 
+#[cfg(feature = "container")]
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct NewlineLogOutputDecoder {
     state: NewlineLogOutputDecoderState,
     is_tcp: bool,
+    max_frame_size: Option<usize>,
 }
 
+#[cfg(feature = "container")]
 impl NewlineLogOutputDecoder {
     pub(crate) fn new(is_tcp: bool) -> NewlineLogOutputDecoder {
         NewlineLogOutputDecoder {
             state: NewlineLogOutputDecoderState::WaitingHeader,
             is_tcp,
+            max_frame_size: None,
         }
     }
+
+    /// Split payloads and lines larger than the given size into several items, rather than
+    /// buffering them whole.
+    #[cfg(feature = "exec")]
+    pub(crate) fn with_max_frame_size(mut self, max_frame_size: Option<usize>) -> Self {
+        self.max_frame_size = max_frame_size.map(|size| size.max(1));
+        self
+    }
 }
 
+#[cfg(feature = "container")]
 impl Decoder for NewlineLogOutputDecoder {
     type Item = LogOutput;
     type Error = io::Error;
@@ -64,7 +91,11 @@ impl Decoder for NewlineLogOutputDecoder {
                                 message: src.split_to(pos + 1).freeze(),
                             }));
                         } else {
-                            return Ok(None);
+                            return Ok(self.max_frame_size.filter(|max| src.len() >= *max).map(
+                                |max| LogOutput::Console {
+                                    message: src.split_to(max).freeze(),
+                                },
+                            ));
                         }
                     }
 
@@ -78,19 +109,32 @@ impl Decoder for NewlineLogOutputDecoder {
                     self.state = NewlineLogOutputDecoderState::WaitingPayload(header[0], length);
                 }
                 NewlineLogOutputDecoderState::WaitingPayload(typ, length) => {
-                    if src.len() < length {
+                    let chunk = match self.max_frame_size {
+                        Some(max) if max < length => max,
+                        _ => length,
+                    };
+                    if src.len() < chunk {
                         return Ok(None);
                     } else {
                         trace!("NewlineLogOutputDecoder: Reading payload");
-                        let message = src.split_to(length).freeze();
+                        let message = src.split_to(chunk).freeze();
                         let item = match typ {
                             0 => LogOutput::StdIn { message },
                             1 => LogOutput::StdOut { message },
                             2 => LogOutput::StdErr { message },
-                            _ => unreachable!(),
+                            _ => {
+                                return Err(io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    format!("invalid log output stream type {typ}"),
+                                ))
+                            }
                         };
 
-                        self.state = NewlineLogOutputDecoderState::WaitingHeader;
+                        self.state = if chunk < length {
+                            NewlineLogOutputDecoderState::WaitingPayload(typ, length - chunk)
+                        } else {
+                            NewlineLogOutputDecoderState::WaitingHeader
+                        };
                         return Ok(Some(item));
                     }
                 }
@@ -103,13 +147,72 @@ pin_project! {
     #[derive(Debug)]
     pub(crate) struct JsonLineDecoder<T> {
         ty: PhantomData<T>,
+        skip_undecodable: bool,
     }
 }
 
 impl<T> JsonLineDecoder<T> {
     #[inline]
     pub(crate) fn new() -> JsonLineDecoder<T> {
-        JsonLineDecoder { ty: PhantomData }
+        JsonLineDecoder {
+            ty: PhantomData,
+            skip_undecodable: false,
+        }
+    }
+}
+
+#[cfg(feature = "json_data_content")]
+const JSON_DATA_SNIPPET_CONTEXT: usize = 32;
+
+/// Error of a JSON payload that failed to deserialize, located in the payload when the
+/// `json_data_content` feature is enabled.
+#[cfg_attr(
+    not(feature = "json_data_content"),
+    allow(unused_variables, clippy::extra_unused_type_parameters)
+)]
+pub(crate) fn json_data_error<T: DeserializeOwned>(
+    err: &serde_json::Error,
+    payload: &[u8],
+) -> Error {
+    #[cfg(feature = "json_data_content")]
+    let offset = json_data_offset(payload, err.line(), err.column());
+
+    JsonDataError {
+        message: err.to_string(),
+        column: err.column(),
+        #[cfg(feature = "json_data_content")]
+        contents: String::from_utf8_lossy(payload).to_string(),
+        #[cfg(feature = "json_data_content")]
+        offset,
+        #[cfg(feature = "json_data_content")]
+        path: json_data_path::<T>(payload),
+        #[cfg(feature = "json_data_content")]
+        snippet: String::from_utf8_lossy(
+            &payload[offset.saturating_sub(JSON_DATA_SNIPPET_CONTEXT)
+                ..cmp::min(payload.len(), offset + JSON_DATA_SNIPPET_CONTEXT)],
+        )
+        .to_string(),
+    }
+}
+
+/// Byte offset of the one-based line and column that serde reports.
+#[cfg(feature = "json_data_content")]
+fn json_data_offset(payload: &[u8], line: usize, column: usize) -> usize {
+    let line_start: usize = payload
+        .split_inclusive(|b| *b == b'\n')
+        .take(line.saturating_sub(1))
+        .map(<[u8]>::len)
+        .sum();
+    cmp::min(payload.len(), line_start + column.saturating_sub(1))
+}
+
+/// Path to the value that failed to deserialize, found by deserializing the payload again.
+#[cfg(feature = "json_data_content")]
+fn json_data_path<T: DeserializeOwned>(payload: &[u8]) -> String {
+    let deserializer = &mut serde_json::Deserializer::from_slice(payload);
+    match serde_path_to_error::deserialize::<_, T>(deserializer) {
+        Ok(_) => String::new(),
+        Err(e) => e.path().to_string(),
     }
 }
 
@@ -121,13 +224,8 @@ fn decode_json_from_slice<T: DeserializeOwned>(slice: &[u8]) -> Result<Option<T>
 
     match serde_json::from_slice(slice) {
         Ok(json) => Ok(json),
-        Err(ref e) if e.is_data() => Err(JsonDataError {
-            message: e.to_string(),
-            column: e.column(),
-            #[cfg(feature = "json_data_content")]
-            contents: String::from_utf8_lossy(slice).to_string(),
-        }),
         Err(e) if e.is_eof() => Ok(None),
+        Err(ref e) if e.is_data() || e.is_syntax() => Err(json_data_error::<T>(e, slice)),
         Err(e) => Err(e.into()),
     }
 }
@@ -159,6 +257,12 @@ where
                         src.advance(pos + 1);
                         Ok(json)
                     }
+                    Err(e) if self.skip_undecodable => {
+                        warn!("Skipping undecodable JSON line from stream: {e:?}");
+                        src.unsplit(remainder);
+                        src.advance(pos + 1);
+                        self.decode(src)
+                    }
                     Err(e) => Err(e),
                 }
             } else {
@@ -169,6 +273,8 @@ where
                         src.clear();
                         Ok(json)
                     }
+                    // wait for the end of the line, or of the stream, before skipping
+                    Err(_) if self.skip_undecodable => Ok(None),
                     Err(e) => Err(e),
                 }
             }
@@ -176,6 +282,161 @@ where
             Ok(None)
         }
     }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(json) => Ok(Some(json)),
+            None if src.is_empty() => Ok(None),
+            None if self.skip_undecodable => {
+                warn!(
+                    "Skipping undecodable JSON at the end of stream: {}",
+                    String::from_utf8_lossy(src)
+                );
+                src.clear();
+                Ok(None)
+            }
+            None => Err(io::Error::other("bytes remaining on stream").into()),
+        }
+    }
+}
+
+/// Position of the [JSON Array Decoder](JsonArrayDecoder) in the array.
+#[cfg(feature = "container")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum JsonArrayState {
+    /// Before the opening bracket.
+    Start,
+    /// Between two elements, or inside one.
+    Elements,
+    /// After the closing bracket.
+    Done,
+}
+
+/// Decodes the elements of a JSON array one at a time, so that a large array never has to be
+/// held in memory as a whole.
+#[cfg(feature = "container")]
+#[derive(Debug)]
+pub(crate) struct JsonArrayDecoder<T> {
+    ty: PhantomData<T>,
+    state: JsonArrayState,
+    skip_undecodable: bool,
+    // scan state of the current element, kept between calls so each byte is only scanned once
+    scanned: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+#[cfg(feature = "container")]
+impl<T> JsonArrayDecoder<T> {
+    #[inline]
+    pub(crate) fn new(skip_undecodable: bool) -> JsonArrayDecoder<T> {
+        JsonArrayDecoder {
+            ty: PhantomData,
+            state: JsonArrayState::Start,
+            skip_undecodable,
+            scanned: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+        }
+    }
+
+    /// Length of the current element, once its end is in the buffer.
+    fn element_len(&mut self, src: &[u8]) -> Option<usize> {
+        for (i, b) in src.iter().enumerate().skip(self.scanned) {
+            if self.in_string {
+                match b {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => (),
+                }
+                continue;
+            }
+            match b {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => self.depth -= 1,
+                b',' | b']' if self.depth == 0 => {
+                    self.scanned = 0;
+                    return Some(i);
+                }
+                _ => (),
+            }
+        }
+        self.scanned = src.len();
+        None
+    }
+}
+
+#[cfg(feature = "container")]
+impl<T> Decoder for JsonArrayDecoder<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = T;
+    type Error = Error;
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if self.scanned == 0 {
+                let skip = src
+                    .iter()
+                    .take_while(|b| {
+                        b.is_ascii_whitespace()
+                            || (**b == b',' && self.state == JsonArrayState::Elements)
+                    })
+                    .count();
+                src.advance(skip);
+            }
+            if src.is_empty() {
+                return Ok(None);
+            }
+
+            match self.state {
+                JsonArrayState::Start if src[0] == b'[' => {
+                    src.advance(1);
+                    self.state = JsonArrayState::Elements;
+                }
+                JsonArrayState::Start => {
+                    return Err(io::Error::other("expected a JSON array on stream").into())
+                }
+                JsonArrayState::Elements if self.scanned == 0 && src[0] == b']' => {
+                    src.advance(1);
+                    self.state = JsonArrayState::Done;
+                }
+                JsonArrayState::Elements => {
+                    let Some(len) = self.element_len(src) else {
+                        return Ok(None);
+                    };
+                    let element = src.split_to(len);
+                    match decode_json_from_slice(&element) {
+                        Ok(Some(json)) => return Ok(Some(json)),
+                        Ok(None) => {
+                            return Err(io::Error::other("empty element in JSON array").into())
+                        }
+                        Err(e) if self.skip_undecodable => {
+                            warn!("Skipping undecodable JSON array element from stream: {e:?}");
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                JsonArrayState::Done => {
+                    return Err(io::Error::other("bytes remaining on stream").into())
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(json) => Ok(Some(json)),
+            None if self.state == JsonArrayState::Elements => {
+                Err(io::Error::other("unterminated JSON array on stream").into())
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -194,6 +455,7 @@ pin_project! {
 }
 
 impl StreamReader {
+    #[cfg(any(feature = "container", feature = "system"))]
     #[inline]
     pub(crate) fn new(stream: Incoming) -> StreamReader {
         StreamReader {
@@ -227,13 +489,14 @@ impl AsyncRead for StreamReader {
                 }
 
                 ReadState::NotReady => match self.as_mut().project().stream.poll_frame(cx) {
-                    Poll::Ready(Some(Ok(frame))) if frame.is_data() => {
-                        *self.as_mut().project().state =
-                            ReadState::Ready(frame.into_data().unwrap(), 0);
+                    Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                        Ok(data) => {
+                            *self.as_mut().project().state = ReadState::Ready(data, 0);
 
-                        continue;
-                    }
-                    Poll::Ready(Some(Ok(_frame))) => return Poll::Ready(Ok(())),
+                            continue;
+                        }
+                        Err(_frame) => return Poll::Ready(Ok(())),
+                    },
                     Poll::Ready(None) => return Poll::Ready(Ok(())),
                     Poll::Pending => {
                         return Poll::Pending;
@@ -254,17 +517,39 @@ impl AsyncRead for StreamReader {
     }
 }
 
+type Cancelled = Pin<Box<WaitForCancellationFutureOwned>>;
+
+fn cancelled(token: Option<&CancellationToken>) -> Option<Cancelled> {
+    token.map(|token| Box::pin(token.clone().cancelled_owned()))
+}
+
+/// Whether the token has been cancelled, registering the task for wake-up otherwise.
+fn poll_cancelled(cancelled: &mut Option<Cancelled>, cx: &mut Context<'_>) -> bool {
+    match cancelled {
+        Some(fut) => fut.as_mut().poll(cx).is_ready(),
+        None => false,
+    }
+}
+
 pin_project! {
+    /// A connection hijacked by the daemon, returned by the [Upgrade API](crate::Docker::upgrade()).
+    ///
+    /// Reads end, and writes fail, once the [cancellation
+    /// token](crate::Docker::with_cancellation()) of the client is cancelled.
     #[derive(Debug)]
-    pub(crate) struct AsyncUpgraded {
+    pub struct AsyncUpgraded {
         #[pin]
         inner: Upgraded,
+        cancelled: Option<Cancelled>,
     }
 }
 
 impl AsyncUpgraded {
-    pub(crate) fn new(upgraded: Upgraded) -> Self {
-        Self { inner: upgraded }
+    pub(crate) fn new(upgraded: Upgraded, token: Option<&CancellationToken>) -> Self {
+        Self {
+            inner: upgraded,
+            cancelled: cancelled(token),
+        }
     }
 }
 
@@ -274,9 +559,15 @@ impl AsyncRead for AsyncUpgraded {
         cx: &mut Context<'_>,
         read_buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
+        let this = self.project();
+        if poll_cancelled(this.cancelled, cx) {
+            // end of file
+            return Poll::Ready(Ok(()));
+        }
+
         let n = {
             let mut hbuf = hyper::rt::ReadBuf::new(read_buf.initialize_unfilled());
-            match hyper::rt::Read::poll_read(self.project().inner, cx, hbuf.unfilled()) {
+            match hyper::rt::Read::poll_read(this.inner, cx, hbuf.unfilled()) {
                 Poll::Ready(Ok(())) => hbuf.filled().len(),
                 other => return other,
             }
@@ -293,7 +584,14 @@ impl AsyncWrite for AsyncUpgraded {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<Result<usize, io::Error>> {
-        hyper::rt::Write::poll_write(self.project().inner, cx, buf)
+        let this = self.project();
+        if poll_cancelled(this.cancelled, cx) {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "cancelled",
+            )));
+        }
+        hyper::rt::Write::poll_write(this.inner, cx, buf)
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
@@ -305,44 +603,346 @@ impl AsyncWrite for AsyncUpgraded {
     }
 }
 
+#[cfg(feature = "container")]
+type PendingResponse = Pin<Box<dyn Future<Output = Result<Response<Incoming>, Error>> + Send>>;
+
+enum BodyStreamState {
+    #[cfg(feature = "container")]
+    Pending(PendingResponse),
+    Streaming(Incoming),
+    Done,
+}
+
+/// The body of a response, handed on chunk by chunk as hyper receives it, without buffering or
+/// copying.
+///
+/// It is a [Stream] of [Bytes], and also a [Body], so it can be forwarded as is to another
+/// request or response, e.g. to pipe an archive from one Docker daemon to another.
+pub struct BodyStream {
+    state: BodyStreamState,
+    cancelled: Option<Cancelled>,
+    end: Option<StreamEnd>,
+}
+
+impl BodyStream {
+    #[cfg(feature = "container")]
+    pub(crate) fn new<F>(response: F, token: Option<&CancellationToken>) -> Self
+    where
+        F: Future<Output = Result<Response<Incoming>, Error>> + Send + 'static,
+    {
+        BodyStream {
+            state: BodyStreamState::Pending(Box::pin(response)),
+            cancelled: cancelled(token),
+            end: None,
+        }
+    }
+
+    pub(crate) fn from_incoming(incoming: Incoming, token: Option<&CancellationToken>) -> Self {
+        BodyStream {
+            state: BodyStreamState::Streaming(incoming),
+            cancelled: cancelled(token),
+            end: None,
+        }
+    }
+
+    /// Report the end of the body to the interceptor of the call.
+    pub(crate) fn with_end(mut self, end: Option<StreamEnd>) -> Self {
+        self.end = end;
+        self
+    }
+}
+
+impl fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state {
+            #[cfg(feature = "container")]
+            BodyStreamState::Pending(_) => "Pending",
+            BodyStreamState::Streaming(_) => "Streaming",
+            BodyStreamState::Done => "Done",
+        };
+        f.debug_struct("BodyStream").field("state", &state).finish()
+    }
+}
+
+impl Body for BodyStream {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if poll_cancelled(&mut self.cancelled, cx) {
+            // dropping the body closes the connection
+            self.state = BodyStreamState::Done;
+            self.end = None;
+        }
+
+        loop {
+            match &mut self.state {
+                #[cfg(feature = "container")]
+                BodyStreamState::Pending(response) => match ready!(response.as_mut().poll(cx)) {
+                    Ok(mut response) => {
+                        self.end = StreamEnd::from_response(&mut response);
+                        self.state = BodyStreamState::Streaming(response.into_body());
+                    }
+                    Err(e) => {
+                        self.state = BodyStreamState::Done;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+                BodyStreamState::Streaming(incoming) => {
+                    let frame = ready!(Pin::new(incoming).poll_frame(cx));
+                    if !matches!(frame, Some(Ok(_))) {
+                        self.state = BodyStreamState::Done;
+                        self.end = None;
+                    }
+                    return Poll::Ready(frame.map(|frame| frame.map_err(Error::from)));
+                }
+                BodyStreamState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.state {
+            #[cfg(feature = "container")]
+            BodyStreamState::Pending(_) => false,
+            BodyStreamState::Streaming(incoming) => incoming.is_end_stream(),
+            BodyStreamState::Done => true,
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.state {
+            #[cfg(feature = "container")]
+            BodyStreamState::Pending(_) => SizeHint::default(),
+            BodyStreamState::Streaming(incoming) => incoming.size_hint(),
+            BodyStreamState::Done => SizeHint::with_exact(0),
+        }
+    }
+}
+
+impl Stream for BodyStream {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    // trailers carry no data, skip them
+                    if let Ok(data) = frame.into_data() {
+                        return Poll::Ready(Some(Ok(data)));
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
 pin_project! {
-    #[derive(Debug)]
-    pub(crate) struct IncomingStream {
+    /// Decodes a stream of JSON messages, such as the progress of the [Create Image
+    /// API](crate::Docker::create_image()) or the [Build Image API](crate::Docker::build_image()),
+    /// from a reader or a stream of bytes.
+    ///
+    /// Messages are separated by newlines, as the daemon sends them, and may be split across
+    /// chunks in any way: a message is decoded once all of it arrived.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bollard::models::CreateImageInfo;
+    /// use bollard::JsonMessageStream;
+    ///
+    /// use futures_util::stream::{self, TryStreamExt};
+    ///
+    /// let chunks = [
+    ///     r#"{"status":"Pulling from library/alpine","id":"latest"}"#,
+    ///     "\n{\"status\":\"Digest: sha256:",
+    ///     "1e42bbe2508154c9126d48c2b8a75420c3544343bf86fd041fb7527e017a4b4a\"}\n",
+    /// ];
+    /// let bytes = stream::iter(chunks.map(|chunk| Ok::<_, std::io::Error>(bytes::Bytes::from(chunk))));
+    ///
+    /// async move {
+    ///     let messages: Vec<CreateImageInfo> = JsonMessageStream::from_stream(bytes)
+    ///         .try_collect()
+    ///         .await
+    ///         .unwrap();
+    ///     assert_eq!(messages.len(), 2);
+    /// };
+    /// ```
+    pub struct JsonMessageStream<T> {
         #[pin]
-        inner: Incoming,
+        inner: FramedRead<Pin<Box<dyn AsyncRead + Send>>, JsonLineDecoder<T>>,
+    }
+}
+
+impl<T> JsonMessageStream<T> {
+    /// Decode the messages read from a reader, e.g. a file of saved progress messages.
+    pub fn from_reader<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        JsonMessageStream {
+            inner: FramedRead::new(Box::pin(reader), JsonLineDecoder::new()),
+        }
+    }
+
+    /// Decode the messages of a stream of bytes, e.g. the [Body Stream](BodyStream) of a [Raw
+    /// Response](crate::RawResponse).
+    pub fn from_stream<S, B, E>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<B, E>> + Send + 'static,
+        B: Buf + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        use futures_util::TryStreamExt;
+
+        Self::from_reader(tokio_util::io::StreamReader::new(
+            stream.map_err(io::Error::other),
+        ))
+    }
+
+    /// Skip the messages that fail to deserialize, logging them, rather than ending the stream
+    /// with a [JSON Data Error](Error::JsonDataError).
+    pub fn skip_undecodable(mut self, skip: bool) -> Self {
+        self.inner.decoder_mut().skip_undecodable = skip;
+        self
     }
 }
 
-impl IncomingStream {
-    pub(crate) fn new(incoming: Incoming) -> Self {
-        Self { inner: incoming }
+impl<T> fmt::Debug for JsonMessageStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonMessageStream").finish_non_exhaustive()
     }
 }
 
-impl Stream for IncomingStream {
-    type Item = Result<Bytes, Error>;
+impl<T> Stream for JsonMessageStream<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(cx)
+    }
+}
+
+/// Ends a stream, and drops it to close its connection, once the token is cancelled.
+#[cfg(any(feature = "container", feature = "system"))]
+#[derive(Debug)]
+pub(crate) struct Cancellable<S> {
+    inner: Option<S>,
+    cancelled: Option<Cancelled>,
+}
+
+#[cfg(any(feature = "container", feature = "system"))]
+impl<S> Cancellable<S> {
+    pub(crate) fn new(inner: S, token: Option<&CancellationToken>) -> Self {
+        Self {
+            inner: Some(inner),
+            cancelled: cancelled(token),
+        }
+    }
+}
+
+#[cfg(any(feature = "container", feature = "system"))]
+impl<S> Stream for Cancellable<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        match futures_util::ready!(self.as_mut().project().inner.poll_frame(cx)?) {
-            Some(frame) => match frame.into_data() {
-                Ok(data) => Poll::Ready(Some(Ok(data))),
-                Err(_) => Poll::Ready(None),
-            },
+        if poll_cancelled(&mut self.cancelled, cx) {
+            self.inner = None;
+        }
+
+        match self.inner.as_mut() {
+            Some(inner) => Pin::new(inner).poll_next(cx),
             None => Poll::Ready(None),
         }
     }
 }
 
+pin_project! {
+    /// Ends a stream with a [StreamStalledError](Error::StreamStalledError) when no item arrives
+    /// within the idle timeout. The timer is only created on the first poll, so that the stream
+    /// can be built outside of a Tokio runtime, and does not stall before it is polled.
+    #[derive(Debug)]
+    pub(crate) struct IdleTimeout<S> {
+        #[pin]
+        inner: S,
+        timeout: Option<Duration>,
+        sleep: Option<Pin<Box<Sleep>>>,
+        stalled: bool,
+    }
+}
+
+impl<S> IdleTimeout<S> {
+    #[cfg(any(feature = "container", feature = "system"))]
+    pub(crate) fn new(inner: S, timeout: Option<Duration>) -> Self {
+        Self {
+            inner,
+            timeout,
+            sleep: None,
+            stalled: false,
+        }
+    }
+}
+
+impl<S, T> Stream for IdleTimeout<S>
+where
+    S: Stream<Item = Result<T, Error>>,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.stalled {
+            return Poll::Ready(None);
+        }
+        if this.sleep.is_none() {
+            *this.sleep = this
+                .timeout
+                .map(|timeout| Box::pin(tokio::time::sleep(timeout)));
+        }
+
+        match this.inner.poll_next(cx) {
+            Poll::Ready(item) => {
+                if let (Some(sleep), Some(timeout)) = (this.sleep.as_mut(), this.timeout) {
+                    sleep.as_mut().reset(Instant::now() + *timeout);
+                }
+                Poll::Ready(item)
+            }
+            Poll::Pending => match (this.sleep.as_mut(), this.timeout) {
+                (Some(sleep), Some(timeout)) => {
+                    ready!(sleep.as_mut().poll(cx));
+                    debug!("no data received from the stream for {:?}", timeout);
+                    *this.stalled = true;
+                    Poll::Ready(Some(Err(Error::StreamStalledError { timeout: *timeout })))
+                }
+                _ => Poll::Pending,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::collections::HashMap;
 
     use bytes::{BufMut, BytesMut};
     use tokio_util::codec::Decoder;
 
+    #[cfg(feature = "container")]
     use crate::container::LogOutput;
 
-    use super::{JsonLineDecoder, NewlineLogOutputDecoder};
+    use super::JsonLineDecoder;
+    #[cfg(feature = "container")]
+    use super::{JsonArrayDecoder, NewlineLogOutputDecoder};
 
     #[test]
     fn json_decode_empty() {
@@ -381,6 +981,42 @@ mod tests {
         assert!(buf.is_empty());
     }
 
+    #[test]
+    #[cfg(feature = "container")]
+    fn json_array_partial_decode() {
+        let mut buf = BytesMut::from(&b" [{\"a\":[\"],\"]}, {\"b"[..]);
+        let mut codec: JsonArrayDecoder<HashMap<String, serde_json::Value>> =
+            JsonArrayDecoder::new(false);
+
+        let first = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(first["a"], serde_json::json!(["],"]));
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.put(&b"\":{}}\n,{\"c\":\"\\\"}\"}\n]\n"[..]);
+        let second = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(second["b"], serde_json::json!({}));
+        let third = codec.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(third["c"], serde_json::json!("\"}"));
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "container")]
+    fn json_array_decode_errors() {
+        let mut codec: JsonArrayDecoder<u32> = JsonArrayDecoder::new(false);
+        assert!(codec.decode(&mut BytesMut::from(&b"{}"[..])).is_err());
+
+        let mut codec: JsonArrayDecoder<u32> = JsonArrayDecoder::new(false);
+        let mut buf = BytesMut::from(&b"[1, 2"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(1));
+        assert!(codec.decode_eof(&mut buf).is_err());
+
+        let mut codec: JsonArrayDecoder<u32> = JsonArrayDecoder::new(true);
+        let mut buf = BytesMut::from(&b"[\"a\", 2]"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(2));
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+    }
+
     #[test]
     fn json_partial_decode_no_newline() {
         let mut buf = BytesMut::from(&b"{\"status\":\"Extracting\",\"progressDetail\":{\"current\":33980416,\"total\":102266715}"[..]);
@@ -442,6 +1078,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "container")]
     fn newline_decode_no_header() {
         let expected = &b"2023-01-14T23:17:27.496421984-05:00 [lighttpd] 2023/01/14 23"[..];
         let mut buf = BytesMut::from(expected);
@@ -472,4 +1109,263 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    #[cfg(feature = "exec")]
+    fn newline_decode_max_frame_size() {
+        let mut buf = BytesMut::from(&b"\x01\0\0\0\0\0\0\x05hello\x02\0\0\0\0\0\0\x02!\n"[..]);
+        let mut codec = NewlineLogOutputDecoder::new(true).with_max_frame_size(Some(2));
+
+        let mut items = vec![];
+        while let Some(item) = codec.decode(&mut buf).unwrap() {
+            items.push(item);
+        }
+        assert_eq!(
+            items,
+            vec![
+                LogOutput::StdOut {
+                    message: bytes::Bytes::from("he")
+                },
+                LogOutput::StdOut {
+                    message: bytes::Bytes::from("ll")
+                },
+                LogOutput::StdOut {
+                    message: bytes::Bytes::from("o")
+                },
+                LogOutput::StdErr {
+                    message: bytes::Bytes::from("!\n")
+                },
+            ]
+        );
+        assert!(buf.is_empty());
+
+        let mut buf = BytesMut::from(&b"a line without newline"[..]);
+        let mut codec = NewlineLogOutputDecoder::new(false).with_max_frame_size(Some(8));
+        assert_eq!(
+            codec.decode(&mut buf).unwrap(),
+            Some(LogOutput::Console {
+                message: bytes::Bytes::from("a line w")
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn json_message_stream_split_chunks() {
+        use futures_util::{stream, TryStreamExt};
+
+        use super::JsonMessageStream;
+        use crate::models::CreateImageInfo;
+
+        let progress = concat!(
+            r#"{"status":"Pulling fs layer","progressDetail":{},"id":"c6a83fedfae6"}"#,
+            "\n",
+            r#"{"status":"Downloading","progressDetail":{"current":1024,"total":3623807},"progress":"[>   ]","id":"c6a83fedfae6"}"#,
+            "\n",
+            r#"{"status":"Status: Downloaded newer image for alpine:latest"}"#,
+            "\n"
+        );
+
+        for split in 0..progress.len() {
+            let chunks = [&progress[..split], &progress[split..]]
+                .map(|chunk| Ok::<_, std::io::Error>(bytes::Bytes::from(chunk.to_owned())));
+            let messages: Vec<CreateImageInfo> =
+                JsonMessageStream::from_stream(stream::iter(chunks))
+                    .try_collect()
+                    .await
+                    .unwrap();
+
+            assert_eq!(messages.len(), 3);
+            assert_eq!(
+                messages[1]
+                    .progress_detail
+                    .as_ref()
+                    .and_then(|detail| detail.total),
+                Some(3623807)
+            );
+            assert_eq!(
+                messages[2].status.as_deref(),
+                Some("Status: Downloaded newer image for alpine:latest")
+            );
+        }
+
+        let messages: Vec<CreateImageInfo> = JsonMessageStream::from_reader(progress.as_bytes())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn json_message_stream_skip_undecodable() {
+        use futures_util::{StreamExt, TryStreamExt};
+
+        use super::JsonMessageStream;
+        use crate::errors::Error;
+        use crate::models::CreateImageInfo;
+
+        let progress = concat!(
+            r#"{"status":"Pulling fs layer","id":"c6a83fedfae6"}"#,
+            "\n",
+            r#"{"status":"Downloading","progressDetail":{"total":"3623807"}}"#,
+            "\n",
+            "not json\n",
+            r#"{"status":"Status: Downloaded newer image for alpine:latest"}"#,
+            "\n",
+            r#"{"status":1}"#,
+        );
+
+        let messages: Vec<CreateImageInfo> = JsonMessageStream::from_reader(progress.as_bytes())
+            .skip_undecodable(true)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[1].status.as_deref(),
+            Some("Status: Downloaded newer image for alpine:latest")
+        );
+
+        let messages: Vec<Result<CreateImageInfo, Error>> =
+            JsonMessageStream::from_reader(progress.as_bytes())
+                .collect()
+                .await;
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[1], Err(Error::JsonDataError { .. })));
+    }
+
+    #[test]
+    #[cfg(feature = "json_data_content")]
+    fn json_decode_data_error_location() {
+        use crate::errors::Error;
+
+        let line =
+            r#"{"status":"Downloading","progressDetail":{"current":1024,"total":"3623807"}}"#;
+        let mut buf = BytesMut::from(format!("{line}\n").as_bytes());
+        let mut codec: JsonLineDecoder<crate::models::CreateImageInfo> = JsonLineDecoder::new();
+
+        match codec.decode(&mut buf) {
+            Err(Error::JsonDataError {
+                contents,
+                offset,
+                path,
+                snippet,
+                ..
+            }) => {
+                assert_eq!(contents, line);
+                assert!(line[..=offset].ends_with(r#""total":"3623807""#));
+                assert_eq!(path, "progressDetail.total");
+                assert!(snippet.ends_with(r#""total":"3623807"}}"#));
+            }
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    /// Chunks of pseudo-random bytes, skewed towards the bytes the decoders branch on.
+    pub(crate) fn arbitrary_chunks(mut seed: u64) -> Vec<Vec<u8>> {
+        const INTERESTING: &[u8] = b"\0\x01\x02\x03\xff\n\r{}[]\"\\:,0";
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        (0..next() % 8 + 1)
+            .map(|_| {
+                (0..next() % 64)
+                    .map(|_| match next() % 4 {
+                        0 => next() as u8,
+                        _ => INTERESTING[next() as usize % INTERESTING.len()],
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Feed the chunks to the decoder the way `FramedRead` does, until it errors.
+    pub(crate) fn decode_all<D: Decoder>(mut codec: D, chunks: Vec<Vec<u8>>) {
+        let mut buf = BytesMut::new();
+        for chunk in chunks {
+            buf.put(&chunk[..]);
+            loop {
+                match codec.decode(&mut buf) {
+                    Ok(Some(_)) => continue,
+                    Ok(None) => break,
+                    Err(_) => return,
+                }
+            }
+        }
+        let _ = codec.decode_eof(&mut buf);
+    }
+
+    #[test]
+    fn json_decode_arbitrary_bytes() {
+        for seed in 1..2000 {
+            decode_all(
+                JsonLineDecoder::<serde_json::Value>::new(),
+                arbitrary_chunks(seed),
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "container")]
+    fn newline_decode_arbitrary_bytes() {
+        for seed in 1..2000 {
+            decode_all(NewlineLogOutputDecoder::new(false), arbitrary_chunks(seed));
+            decode_all(NewlineLogOutputDecoder::new(true), arbitrary_chunks(seed));
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(any(feature = "container", feature = "system"))]
+    async fn idle_timeout_stalled() {
+        use futures_util::{stream, StreamExt};
+        use std::time::Duration;
+
+        use super::IdleTimeout;
+        use crate::errors::Error;
+
+        let items = stream::iter(vec![Ok::<_, Error>(1), Ok(2)]).chain(stream::pending());
+        let results: Vec<_> = IdleTimeout::new(items, Some(Duration::from_millis(20)))
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Ok(1)));
+        assert!(matches!(results[1], Ok(2)));
+        assert!(matches!(
+            results[2],
+            Err(Error::StreamStalledError { timeout }) if timeout == Duration::from_millis(20)
+        ));
+
+        let items = stream::iter(vec![Ok::<_, Error>(1)]);
+        let results: Vec<_> = IdleTimeout::new(items, None).collect().await;
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    #[cfg(any(feature = "container", feature = "system"))]
+    fn idle_timeout_starts_on_first_poll() {
+        use futures_util::{stream, StreamExt};
+        use std::time::Duration;
+
+        use super::IdleTimeout;
+        use crate::errors::Error;
+
+        // built outside of a runtime, and polled well after the timeout would have elapsed
+        let items = stream::iter(vec![Ok::<_, Error>(1)]);
+        let idle = IdleTimeout::new(items, Some(Duration::from_millis(20)));
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let results: Vec<_> = runtime.block_on(async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            idle.collect().await
+        });
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Ok(1)));
+    }
 }
@@ -0,0 +1,373 @@
+//! Readiness: wait until the service inside a started container is ready, rather than only
+//! running, e.g. before a test fixture hands the container over to a test.
+//!
+//! A [Readiness Probe](ReadinessProbe) checks once whether the service is ready, and
+//! [`wait_for_ready`](Docker::wait_for_ready()) repeats it until it succeeds, the container exits
+//! or the timeout elapses. The probes of this module connect to a published TCP port, request a
+//! URL from within the container, or look for a line in the container's logs.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::readiness::LogProbe;
+//!
+//! use std::time::Duration;
+//!
+//! async move {
+//!     let probe = LogProbe::new("database system is ready to accept connections")
+//!         .unwrap()
+//!         .times(2);
+//!     docker
+//!         .wait_for_ready("postgres", &probe, Duration::from_secs(30))
+//!         .await
+//!         .unwrap();
+//! };
+//! ```
+
+use futures_util::{StreamExt, TryStreamExt};
+use log::debug;
+use regex::Regex;
+use tokio::net::TcpStream;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use super::Docker;
+use crate::container::ports::ContainerPort;
+use crate::container::{InspectContainerOptions, LogsOptions};
+use crate::errors::Error;
+use crate::exec::{CreateExecOptions, StartExecResults};
+
+/// Delay between two checks of a probe.
+const PROBE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Future returned by a [Readiness Probe](ReadinessProbe).
+pub type ReadinessProbeReturnTy<'a> =
+    Pin<Box<dyn Future<Output = Result<bool, Error>> + Send + 'a>>;
+
+/// Checks whether the service inside a container is ready, used by
+/// [`wait_for_ready`](Docker::wait_for_ready()).
+///
+/// An error stops the wait, so a probe returns `Ok(false)` for the failures expected while the
+/// service starts up, such as a refused connection.
+pub trait ReadinessProbe: Send + Sync {
+    /// Check once whether the service inside the container is ready, this returns a future
+    fn probe<'a>(
+        &'a self,
+        docker: &'a Docker,
+        container_name: &'a str,
+    ) -> ReadinessProbeReturnTy<'a>;
+}
+
+/// Ready once a TCP connection to the host address a container port is published on succeeds.
+///
+/// The userland proxy of the docker daemon accepts connections to a published port before the
+/// service listens on it, so an [HTTP Probe](HttpProbe) or a [Log Probe](LogProbe) is more
+/// reliable when the userland proxy is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpProbe {
+    port: ContainerPort,
+}
+
+impl TcpProbe {
+    /// Probe the published container port.
+    pub fn new(port: ContainerPort) -> Self {
+        TcpProbe { port }
+    }
+}
+
+impl ReadinessProbe for TcpProbe {
+    fn probe<'a>(
+        &'a self,
+        docker: &'a Docker,
+        container_name: &'a str,
+    ) -> ReadinessProbeReturnTy<'a> {
+        Box::pin(async move {
+            let addr = match docker.resolve_host_port(container_name, self.port).await {
+                Ok(addr) => addr,
+                // the daemon publishes the ports while the container starts
+                Err(Error::PortNotPublishedError { .. }) => return Ok(false),
+                Err(e) => return Err(e),
+            };
+
+            match TcpStream::connect(addr).await {
+                Ok(_) => Ok(true),
+                Err(e) => {
+                    debug!("connection to {addr} failed: {e}");
+                    Ok(false)
+                }
+            }
+        })
+    }
+}
+
+/// Ready once an HTTP GET request from within the container, through `wget` or `curl`, returns a
+/// successful status code.
+///
+/// As the request runs inside the container, the port does not need to be published, but the
+/// image needs to contain a shell and either `wget` or `curl`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpProbe {
+    port: u16,
+    path: String,
+}
+
+impl HttpProbe {
+    /// Probe the path, e.g. `/health`, on the port the service listens on inside the container.
+    pub fn new(port: u16, path: &str) -> Self {
+        HttpProbe {
+            port,
+            path: format!("/{}", path.trim_start_matches('/')),
+        }
+    }
+
+    fn cmd(&self) -> Vec<String> {
+        let url = format!("http://127.0.0.1:{}{}", self.port, self.path);
+        vec![
+            String::from("sh"),
+            String::from("-c"),
+            format!(
+                "if command -v wget >/dev/null; then wget -q -O /dev/null '{url}'; \
+                 else curl -fsS -o /dev/null '{url}'; fi"
+            ),
+        ]
+    }
+}
+
+impl ReadinessProbe for HttpProbe {
+    fn probe<'a>(
+        &'a self,
+        docker: &'a Docker,
+        container_name: &'a str,
+    ) -> ReadinessProbeReturnTy<'a> {
+        Box::pin(async move {
+            let exec = docker
+                .create_exec(
+                    container_name,
+                    CreateExecOptions {
+                        attach_stdout: Some(true),
+                        attach_stderr: Some(true),
+                        cmd: Some(self.cmd()),
+                        ..Default::default()
+                    },
+                )
+                .await?
+                .id;
+
+            if let StartExecResults::Attached { mut output, .. } =
+                docker.start_exec(&exec, None).await?
+            {
+                while let Some(output) = output.next().await {
+                    debug!("{}", output?);
+                }
+            }
+
+            let exit_code = docker.inspect_exec(&exec).await?.exit_code;
+            Ok(exit_code == Some(0))
+        })
+    }
+}
+
+/// Ready once the `stdout` or `stderr` logs of the container contain a number of lines matching
+/// a regular expression.
+#[derive(Debug, Clone)]
+pub struct LogProbe {
+    pattern: Regex,
+    times: usize,
+}
+
+impl LogProbe {
+    /// Probe the logs for a line matching the regular expression.
+    pub fn new(pattern: &str) -> Result<Self, Error> {
+        let pattern = Regex::new(pattern).map_err(|e| Error::InvalidReadinessProbeError {
+            message: e.to_string(),
+        })?;
+        Ok(LogProbe { pattern, times: 1 })
+    }
+
+    /// Wait for the given number of matching lines, e.g. for services that restart once after
+    /// initializing their data.
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = times;
+        self
+    }
+
+    fn matches(&self, logs: &str) -> bool {
+        logs.lines()
+            .filter(|line| self.pattern.is_match(line))
+            .count()
+            >= self.times
+    }
+}
+
+impl ReadinessProbe for LogProbe {
+    fn probe<'a>(
+        &'a self,
+        docker: &'a Docker,
+        container_name: &'a str,
+    ) -> ReadinessProbeReturnTy<'a> {
+        Box::pin(async move {
+            let logs = docker
+                .logs(
+                    container_name,
+                    Some(LogsOptions::<String> {
+                        stdout: true,
+                        stderr: true,
+                        tail: String::from("all"),
+                        ..Default::default()
+                    }),
+                )
+                .try_fold(Vec::new(), |mut logs, output| async move {
+                    logs.extend_from_slice(output.as_ref());
+                    Ok(logs)
+                })
+                .await?;
+
+            Ok(self.matches(&String::from_utf8_lossy(&logs)))
+        })
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Wait For Ready
+    ///
+    /// Repeat a [Readiness Probe](ReadinessProbe) until the service inside a started container
+    /// is ready.
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as a string slice.
+    ///  - The [Readiness Probe](ReadinessProbe) to repeat.
+    ///  - The time to wait for the service.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future. A
+    ///    [ContainerNotReadyError](Error::ContainerNotReadyError) is returned if the container
+    ///    stops running or the timeout elapses before the service is ready.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::readiness::HttpProbe;
+    ///
+    /// use std::time::Duration;
+    ///
+    /// docker.wait_for_ready("nginx", &HttpProbe::new(80, "/"), Duration::from_secs(10));
+    /// ```
+    pub async fn wait_for_ready<P>(
+        &self,
+        container_name: &str,
+        probe: &P,
+        timeout: Duration,
+    ) -> Result<(), Error>
+    where
+        P: ReadinessProbe + ?Sized,
+    {
+        let not_ready = |message: String| Error::ContainerNotReadyError {
+            container: String::from(container_name),
+            message,
+        };
+
+        let wait = async {
+            loop {
+                let state = self
+                    .inspect_container(container_name, None::<InspectContainerOptions>)
+                    .await?
+                    .state
+                    .unwrap_or_default();
+                if state.running != Some(true) {
+                    return Err(not_ready(format!(
+                        "the container is not running, exit code {}",
+                        state.exit_code.unwrap_or_default()
+                    )));
+                }
+
+                if probe.probe(self, container_name).await? {
+                    return Ok(());
+                }
+                tokio::time::sleep(PROBE_INTERVAL).await;
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(res) => res,
+            Err(_) => Err(not_ready(format!("not ready within {timeout:?}"))),
+        }
+    }
+
+    /// ---
+    ///
+    /// # Wait For Container Port
+    ///
+    /// Wait until a TCP connection to the host address a started container's port is published
+    /// on succeeds, through a [TCP Probe](TcpProbe).
+    ///
+    /// # Arguments
+    ///
+    ///  - Container name as a string slice.
+    ///  - The published [Container Port](ContainerPort).
+    ///  - The time to wait for the port.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::ports::ContainerPort;
+    ///
+    /// use std::time::Duration;
+    ///
+    /// docker.wait_for_container_port("redis", ContainerPort::tcp(6379), Duration::from_secs(10));
+    /// ```
+    pub async fn wait_for_container_port(
+        &self,
+        container_name: &str,
+        port: ContainerPort,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        self.wait_for_ready(container_name, &TcpProbe::new(port), timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HttpProbe, LogProbe};
+    use crate::errors::Error;
+
+    #[test]
+    fn test_http_probe_cmd() {
+        let cmd = HttpProbe::new(8080, "health").cmd();
+        assert_eq!(cmd[..2], ["sh", "-c"]);
+        assert!(cmd[2].contains("wget -q -O /dev/null 'http://127.0.0.1:8080/health'"));
+        assert!(cmd[2].contains("curl -fsS -o /dev/null 'http://127.0.0.1:8080/health'"));
+    }
+
+    #[test]
+    fn test_log_probe() {
+        let logs = "starting\nready to accept connections\nrestarting\n\
+                    ready to accept connections\n";
+
+        let probe = LogProbe::new(r"ready to accept \w+").unwrap();
+        assert!(probe.matches(logs));
+        assert!(probe.clone().times(2).matches(logs));
+        assert!(!probe.times(3).matches(logs));
+
+        assert!(matches!(
+            LogProbe::new("ready ("),
+            Err(Error::InvalidReadinessProbeError { .. })
+        ));
+    }
+}
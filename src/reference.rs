@@ -0,0 +1,437 @@
+//! Image references, such as `alpine`, `ghcr.io/owner/image:1.0` or `alpine@sha256:...`, parsed
+//! and normalized the way the Docker CLI does.
+//!
+//! A reference without a registry refers to Docker Hub, where official images live under the
+//! `library/` namespace, and a reference without a tag or digest refers to the `latest` tag.
+//! References convert into the options of the [Create Image API](crate::Docker::create_image()),
+//! the [Tag Image API](crate::Docker::tag_image()) and the [Push Image
+//! API](crate::Docker::push_image()), so that malformed references are rejected before they are
+//! sent.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::image::{CreateImageOptions, PushImageOptions, TagImageOptions};
+//! use bollard::reference::Reference;
+//!
+//! let reference: Reference = "alpine".parse().unwrap();
+//! assert_eq!(reference.domain(), "docker.io");
+//! assert_eq!(reference.path(), "library/alpine");
+//! assert_eq!(reference.to_string(), "docker.io/library/alpine:latest");
+//! assert_eq!(reference.familiar_name(), "alpine");
+//!
+//! docker.create_image(Some(CreateImageOptions::from(&reference)), None, None);
+//!
+//! let target = Reference::parse("localhost:5000/tools/alpine:3").unwrap();
+//! async move {
+//!     docker
+//!         .tag_image(&reference.to_string(), Some(TagImageOptions::from(&target)))
+//!         .await
+//!         .unwrap();
+//!     docker.push_image(&target.name(), Some(PushImageOptions::from(&target)), None);
+//! };
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::Error;
+use crate::image::{CreateImageOptions, PushImageOptions, TagImageOptions};
+
+/// Registry of references without a registry: Docker Hub.
+pub const DEFAULT_DOMAIN: &str = "docker.io";
+
+/// Tag of references without a tag or digest.
+pub const DEFAULT_TAG: &str = "latest";
+
+const LEGACY_DEFAULT_DOMAIN: &str = "index.docker.io";
+const OFFICIAL_REPOSITORY_NAMESPACE: &str = "library";
+const NAME_TOTAL_LENGTH_MAX: usize = 255;
+const TAG_LENGTH_MAX: usize = 128;
+
+/// A normalized image reference.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reference {
+    domain: String,
+    path: String,
+    tag: Option<String>,
+    digest: Option<String>,
+}
+
+impl Reference {
+    /// Parse and normalize an image reference, e.g. `alpine:3` into
+    /// `docker.io/library/alpine:3`.
+    pub fn parse(reference: &str) -> Result<Reference, Error> {
+        let invalid = |message: &str| Error::InvalidReferenceError {
+            reference: String::from(reference),
+            message: String::from(message),
+        };
+
+        let (rest, digest) = match reference.split_once('@') {
+            Some((rest, digest)) => {
+                if !is_digest(digest) {
+                    return Err(invalid("invalid digest"));
+                }
+                (rest, Some(digest))
+            }
+            None => (reference, None),
+        };
+
+        let (name, tag) = match rest.rfind(':') {
+            Some(i) if !rest[i..].contains('/') => (&rest[..i], Some(&rest[i + 1..])),
+            _ => (rest, None),
+        };
+        if tag.is_some_and(|tag| !is_tag(tag)) {
+            return Err(invalid("invalid tag"));
+        }
+
+        let (domain, path) = match name.split_once('/') {
+            Some((first, path))
+                if first.contains(['.', ':'])
+                    || first == "localhost"
+                    || first.chars().any(|c| c.is_ascii_uppercase()) =>
+            {
+                (first, path)
+            }
+            _ => (DEFAULT_DOMAIN, name),
+        };
+        if !is_domain(domain) {
+            return Err(invalid("invalid registry"));
+        }
+        if path.chars().any(|c| c.is_ascii_uppercase()) {
+            return Err(invalid("repository name must be lowercase"));
+        }
+        if !path.split('/').all(is_path_component) {
+            return Err(invalid("invalid repository name"));
+        }
+
+        let domain = if domain == LEGACY_DEFAULT_DOMAIN {
+            DEFAULT_DOMAIN
+        } else {
+            domain
+        };
+        let path = if domain == DEFAULT_DOMAIN && !path.contains('/') {
+            format!("{OFFICIAL_REPOSITORY_NAMESPACE}/{path}")
+        } else {
+            String::from(path)
+        };
+        if domain.len() + 1 + path.len() > NAME_TOTAL_LENGTH_MAX {
+            return Err(invalid("repository name is too long"));
+        }
+
+        Ok(Reference {
+            domain: String::from(domain),
+            path,
+            tag: tag.map(String::from),
+            digest: digest.map(String::from),
+        })
+    }
+
+    /// The registry, e.g. `docker.io` or `localhost:5000`.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// The repository path in the registry, e.g. `library/alpine`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The tag, if the reference names one.
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    /// The digest, e.g. `sha256:...`, if the reference names one.
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    /// The full repository name, e.g. `docker.io/library/alpine`.
+    pub fn name(&self) -> String {
+        format!("{}/{}", self.domain, self.path)
+    }
+
+    /// The repository name as the Docker CLI shows it, e.g. `alpine` or `owner/image`.
+    pub fn familiar_name(&self) -> String {
+        if self.domain != DEFAULT_DOMAIN {
+            return self.name();
+        }
+        match self.path.strip_prefix("library/") {
+            Some(path) if !path.contains('/') => String::from(path),
+            _ => self.path.clone(),
+        }
+    }
+
+    /// The tag, or [`DEFAULT_TAG`] when the reference names neither a tag nor a digest.
+    pub fn tag_or_default(&self) -> Option<&str> {
+        match (&self.tag, &self.digest) {
+            (Some(tag), _) => Some(tag),
+            (None, None) => Some(DEFAULT_TAG),
+            (None, Some(_)) => None,
+        }
+    }
+
+    /// The same repository with the given tag, and without a digest.
+    pub fn with_tag(&self, tag: &str) -> Result<Reference, Error> {
+        if !is_tag(tag) {
+            return Err(Error::InvalidReferenceError {
+                reference: format!("{}:{tag}", self.name()),
+                message: String::from("invalid tag"),
+            });
+        }
+        Ok(Reference {
+            tag: Some(String::from(tag)),
+            digest: None,
+            ..self.clone()
+        })
+    }
+}
+
+impl FromStr for Reference {
+    type Err = Error;
+
+    fn from_str(reference: &str) -> Result<Self, Self::Err> {
+        Reference::parse(reference)
+    }
+}
+
+impl fmt::Display for Reference {
+    /// Format the normalized reference, with the default tag when it names neither a tag nor a
+    /// digest.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.domain, self.path)?;
+        if let Some(tag) = self.tag_or_default() {
+            write!(f, ":{tag}")?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{digest}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Reference> for CreateImageOptions<'_, String> {
+    /// Pull the image by digest when the reference names one, otherwise by tag.
+    fn from(reference: &Reference) -> Self {
+        CreateImageOptions {
+            from_image: reference.name(),
+            tag: String::from(
+                reference
+                    .digest()
+                    .or(reference.tag_or_default())
+                    .unwrap_or(DEFAULT_TAG),
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&Reference> for TagImageOptions<String> {
+    /// Tag as the repository and tag of the reference, or the default tag.
+    fn from(reference: &Reference) -> Self {
+        TagImageOptions {
+            repo: reference.name(),
+            tag: String::from(reference.tag().unwrap_or(DEFAULT_TAG)),
+        }
+    }
+}
+
+impl From<&Reference> for PushImageOptions<String> {
+    /// Push the tag of the reference, or the default tag, of the repository [named by the
+    /// reference](Reference::name()).
+    fn from(reference: &Reference) -> Self {
+        PushImageOptions {
+            tag: String::from(reference.tag().unwrap_or(DEFAULT_TAG)),
+        }
+    }
+}
+
+/// `[a-z0-9]+` components separated by `.`, `_`, `__` or any number of `-`.
+fn is_path_component(component: &str) -> bool {
+    let bytes = component.as_bytes();
+    let mut i = 0;
+    loop {
+        let start = i;
+        while i < bytes.len() && (bytes[i].is_ascii_lowercase() || bytes[i].is_ascii_digit()) {
+            i += 1;
+        }
+        if i == start {
+            return false;
+        }
+        if i == bytes.len() {
+            return true;
+        }
+        // a separator must be followed by another run of `[a-z0-9]`, checked on the next pass
+        let separator = &component[i..];
+        i += if separator.starts_with("__") {
+            2
+        } else if separator.starts_with(['.', '_']) {
+            1
+        } else {
+            bytes[i..].iter().take_while(|b| **b == b'-').count()
+        };
+    }
+}
+
+/// A host name or IP address, optionally followed by a port.
+fn is_domain(domain: &str) -> bool {
+    let (host, port) = if let Some(rest) = domain.strip_prefix('[') {
+        match rest.split_once(']') {
+            Some((address, port)) if address.parse::<std::net::Ipv6Addr>().is_ok() => ("", port),
+            _ => return false,
+        }
+    } else {
+        domain.split_at(domain.rfind(':').unwrap_or(domain.len()))
+    };
+
+    let valid_port = match port.strip_prefix(':') {
+        Some(port) => !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()),
+        None => port.is_empty(),
+    };
+    let valid_host = domain.starts_with('[')
+        || host.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        });
+
+    valid_port && valid_host
+}
+
+/// `[\w][\w.-]{0,127}`
+fn is_tag(tag: &str) -> bool {
+    let word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    match tag.as_bytes() {
+        [first, rest @ ..] if rest.len() < TAG_LENGTH_MAX => {
+            word(*first) && rest.iter().all(|b| word(*b) || *b == b'.' || *b == b'-')
+        }
+        _ => false,
+    }
+}
+
+/// `algorithm:encoded`, where the encoded part of a `sha256` digest is 64 hexadecimal digits.
+fn is_digest(digest: &str) -> bool {
+    match digest.split_once(':') {
+        Some((algorithm, encoded)) => {
+            algorithm.split(['-', '_', '+', '.']).all(|component| {
+                component.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && component.chars().all(|c| c.is_ascii_alphanumeric())
+            }) && encoded.len() >= 32
+                && encoded.bytes().all(|b| b.is_ascii_hexdigit())
+                && (algorithm != "sha256" || encoded.len() == 64)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Reference;
+    use crate::errors::Error;
+    use crate::image::{CreateImageOptions, PushImageOptions, TagImageOptions};
+
+    const DIGEST: &str = "sha256:1e42bbe2508154c9126d48c2b8a75420c3544343bf86fd041fb7527e017a4b4a";
+
+    #[test]
+    fn test_parse_reference() {
+        let reference = Reference::parse("alpine").unwrap();
+        assert_eq!(reference.domain(), "docker.io");
+        assert_eq!(reference.path(), "library/alpine");
+        assert_eq!(reference.tag(), None);
+        assert_eq!(reference.tag_or_default(), Some("latest"));
+        assert_eq!(reference.to_string(), "docker.io/library/alpine:latest");
+        assert_eq!(reference.familiar_name(), "alpine");
+
+        let reference = Reference::parse("fussybeaver/uhttpd:1.0").unwrap();
+        assert_eq!(reference.name(), "docker.io/fussybeaver/uhttpd");
+        assert_eq!(reference.tag(), Some("1.0"));
+        assert_eq!(reference.familiar_name(), "fussybeaver/uhttpd");
+
+        let reference = Reference::parse("localhost:5000/my_org/my-image:v1.2_rc").unwrap();
+        assert_eq!(reference.domain(), "localhost:5000");
+        assert_eq!(reference.path(), "my_org/my-image");
+        assert_eq!(reference.tag(), Some("v1.2_rc"));
+        assert_eq!(reference.familiar_name(), "localhost:5000/my_org/my-image");
+
+        let reference = Reference::parse(&format!("ghcr.io/owner/image@{DIGEST}")).unwrap();
+        assert_eq!(reference.domain(), "ghcr.io");
+        assert_eq!(reference.digest(), Some(DIGEST));
+        assert_eq!(reference.tag_or_default(), None);
+        assert_eq!(
+            reference.to_string(),
+            format!("ghcr.io/owner/image@{DIGEST}")
+        );
+
+        let reference = Reference::parse("index.docker.io/library/busybox:1").unwrap();
+        assert_eq!(reference.to_string(), "docker.io/library/busybox:1");
+        assert_eq!(
+            reference.with_tag("2").unwrap().to_string(),
+            "docker.io/library/busybox:2"
+        );
+
+        assert_eq!(
+            Reference::parse("[::1]:5000/image").unwrap().domain(),
+            "[::1]:5000"
+        );
+    }
+
+    #[test]
+    fn test_reference_into_options() {
+        let reference = Reference::parse("alpine").unwrap();
+        assert_eq!(
+            CreateImageOptions::from(&reference),
+            CreateImageOptions {
+                from_image: String::from("docker.io/library/alpine"),
+                tag: String::from("latest"),
+                ..Default::default()
+            }
+        );
+
+        let reference = Reference::parse(&format!("ghcr.io/owner/image:1.0@{DIGEST}")).unwrap();
+        assert_eq!(CreateImageOptions::from(&reference).tag, DIGEST);
+        assert_eq!(
+            TagImageOptions::from(&reference),
+            TagImageOptions {
+                repo: String::from("ghcr.io/owner/image"),
+                tag: String::from("1.0"),
+            }
+        );
+        assert_eq!(
+            PushImageOptions::from(&reference),
+            PushImageOptions {
+                tag: String::from("1.0"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_reference() {
+        for reference in [
+            "",
+            "Alpine",
+            "alpine:",
+            "alpine:-1",
+            "alpine@sha256:abc",
+            "my--org_/image",
+            "my__org..image",
+            "-registry.io/image",
+            "registry.io:port/image",
+            "a//b",
+        ] {
+            assert!(
+                matches!(
+                    Reference::parse(reference),
+                    Err(Error::InvalidReferenceError { .. })
+                ),
+                "{reference}"
+            );
+        }
+        assert!(Reference::parse("alpine").unwrap().with_tag(".1").is_err());
+    }
+}
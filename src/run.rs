@@ -0,0 +1,427 @@
+//! Run: create and start a container in one call, the way `docker run` does.
+//!
+//! [`run_container`](Docker::run_container()) pulls the image if it is missing, creates and
+//! starts the container, and returns a [`RunningContainer`] handle to follow its logs, wait for
+//! it to exit and remove it. [`create_container_with_pull`](Docker::create_container_with_pull())
+//! only creates the container, pulling the image if the daemon reports it missing.
+
+use futures_core::Stream;
+use futures_util::{StreamExt, TryStreamExt};
+use log::{debug, warn};
+
+use std::pin::Pin;
+
+use super::Docker;
+use crate::auth::DockerCredentials;
+use crate::container::{
+    AttachContainerOptions, AttachContainerResults, Config, CreateContainerOptions, LogOutput,
+    LogsOptions, RemoveContainerOptions, StartContainerOptions, WaitContainerOptions,
+};
+use crate::docker::spawn_cleanup;
+use crate::errors::{Error, ErrorKind};
+use crate::image::CreateImageOptions;
+use crate::models::ContainerCreateResponse;
+use crate::platform::Platform;
+use crate::reference::Reference;
+
+/// Whether the container could not be created because its image is missing, or only present for
+/// another platform, as opposed to another missing resource such as a network.
+fn is_missing_image(err: &Error) -> bool {
+    err.kind() == Some(ErrorKind::NotFound)
+        && err.daemon_error().is_some_and(|e| {
+            e.message.starts_with("No such image")
+                || e.message.contains("does not match the specified platform")
+        })
+}
+
+/// Parameters used in the [Run Container API](Docker::run_container())
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::container::Config;
+/// use bollard::run::RunOptions;
+///
+/// use std::default::Default;
+///
+/// RunOptions {
+///     config: Config {
+///         image: Some(String::from("alpine")),
+///         cmd: Some(vec![String::from("echo"), String::from("hello")]),
+///         ..Default::default()
+///     },
+///     wait: true,
+///     auto_remove: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Container configuration. The `image` field is required.
+    pub config: Config<String>,
+    /// Assign the specified name to the container.
+    pub name: Option<String>,
+    /// Platform used to pull and create the container.
+    pub platform: Option<Platform>,
+    /// Registry credentials used if the image is pulled.
+    pub credentials: Option<DockerCredentials>,
+    /// Always pull the image, rather than only when it is missing.
+    pub always_pull: bool,
+    /// Attach to the container's output before it is started, so no output is missed. The
+    /// attached streams are available through [`RunningContainer::take_attach`].
+    pub attach: bool,
+    /// Wait for the container to exit before returning. The exit code is available through
+    /// [`RunningContainer::exit_code`].
+    pub wait: bool,
+    /// Remove the container when the handle is dropped. Unlike the `auto_remove` host
+    /// configuration, the container's logs remain available until the handle is dropped.
+    pub auto_remove: bool,
+}
+
+/// A container started through the [Run Container API](Docker::run_container()).
+#[derive(Debug)]
+pub struct RunningContainer {
+    docker: Docker,
+    id: String,
+    attach: Option<AttachContainerResults>,
+    exit_code: Option<i64>,
+    auto_remove: bool,
+}
+
+impl RunningContainer {
+    /// The container's ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The container's exit code, if it was waited on.
+    pub fn exit_code(&self) -> Option<i64> {
+        self.exit_code
+    }
+
+    /// Take the streams attached to the container, if it was run with `attach`.
+    pub fn take_attach(&mut self) -> Option<AttachContainerResults> {
+        self.attach.take()
+    }
+
+    /// Stream the container's `stdout` and `stderr` logs, following them until the container
+    /// exits if `follow` is set.
+    pub fn logs(
+        &self,
+        follow: bool,
+    ) -> Pin<Box<dyn Stream<Item = Result<LogOutput, Error>> + Send>> {
+        Box::pin(self.docker.logs(
+            &self.id,
+            Some(LogsOptions::<String> {
+                follow,
+                stdout: true,
+                stderr: true,
+                tail: String::from("all"),
+                ..Default::default()
+            }),
+        ))
+    }
+
+    /// Wait for the container to exit, returning its exit code.
+    pub async fn wait(&mut self) -> Result<i64, Error> {
+        if let Some(code) = self.exit_code {
+            return Ok(code);
+        }
+
+        let mut stream = self.docker.wait_container(
+            &self.id,
+            Some(WaitContainerOptions {
+                condition: "not-running",
+            }),
+        );
+
+        let code = match stream.next().await {
+            Some(Ok(response)) => response.status_code,
+            Some(Err(Error::DockerContainerWaitError { code, .. })) => code,
+            Some(Err(e)) => return Err(e),
+            None => {
+                return Err(Error::DockerStreamError {
+                    error: String::from("container wait returned no response"),
+                })
+            }
+        };
+
+        self.exit_code = Some(code);
+        Ok(code)
+    }
+
+    /// Remove the container, killing it if it is running.
+    pub async fn remove(mut self) -> Result<(), Error> {
+        self.auto_remove = false;
+        match self
+            .docker
+            .remove_container(
+                &self.id,
+                Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }),
+            )
+            .await
+        {
+            Err(e) if e.kind() != Some(ErrorKind::NotFound) => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for RunningContainer {
+    fn drop(&mut self) {
+        if !self.auto_remove {
+            return;
+        }
+
+        let docker = self.docker.clone();
+        let id = self.id.clone();
+        spawn_cleanup("container", self.id.clone(), async move {
+            docker
+                .remove_container(
+                    &id,
+                    Some(RemoveContainerOptions {
+                        force: true,
+                        ..Default::default()
+                    }),
+                )
+                .await
+        });
+    }
+}
+
+impl Docker {
+    async fn pull_for_run(
+        &self,
+        reference: &Reference,
+        platform: Option<&Platform>,
+        credentials: Option<DockerCredentials>,
+    ) -> Result<(), Error> {
+        debug!("pulling image {reference}");
+
+        self.create_image(
+            Some(CreateImageOptions {
+                platform: platform.map(Platform::to_string).unwrap_or_default(),
+                ..CreateImageOptions::from(reference)
+            }),
+            None,
+            credentials,
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        Ok(())
+    }
+
+    /// ---
+    ///
+    /// # Create Container With Pull
+    ///
+    /// Create a container, and if the daemon reports its image missing, or only present for
+    /// another platform, pull the image for the platform of the options and retry once. Any other
+    /// error, including other missing resources such as networks, is returned as is.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [Create Container Options](CreateContainerOptions) struct.
+    ///  - Container [Config](Config) struct. The `image` field is required.
+    ///  - Optional [Docker Credentials](DockerCredentials) struct, used to pull the image.
+    ///
+    /// # Returns
+    ///
+    ///  - [ContainerCreateResponse](ContainerCreateResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::container::{Config, CreateContainerOptions};
+    ///
+    /// use std::default::Default;
+    ///
+    /// let options = Some(CreateContainerOptions {
+    ///     name: String::from("my-new-container"),
+    ///     platform: Some(String::from("linux/arm64")),
+    /// });
+    ///
+    /// let config = Config {
+    ///     image: Some(String::from("hello-world")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// docker.create_container_with_pull(options, config, None);
+    /// ```
+    pub async fn create_container_with_pull(
+        &self,
+        options: Option<CreateContainerOptions<String>>,
+        config: Config<String>,
+        credentials: Option<DockerCredentials>,
+    ) -> Result<ContainerCreateResponse, Error> {
+        let image = config.image.clone().ok_or(Error::MissingImageError {})?;
+
+        match self.create_container(options.clone(), config.clone()).await {
+            Err(e) if is_missing_image(&e) => {
+                debug!("{e}");
+                let reference = Reference::parse(&image)?;
+                let platform = options
+                    .as_ref()
+                    .and_then(|o| o.platform.as_deref())
+                    .map(Platform::parse)
+                    .transpose()?;
+                self.pull_for_run(&reference, platform.as_ref(), credentials)
+                    .await?;
+                self.create_container(options, config).await
+            }
+            res => res,
+        }
+    }
+
+    /// ---
+    ///
+    /// # Run Container
+    ///
+    /// Pull the image if it is missing, then create and start a container, optionally attaching
+    /// to its output and waiting for it to exit, like `docker run`.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Run Options](RunOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Running Container](RunningContainer) handle, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::Config;
+    /// use bollard::run::RunOptions;
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let options = RunOptions {
+    ///     config: Config {
+    ///         image: Some(String::from("alpine")),
+    ///         cmd: Some(vec![String::from("echo"), String::from("hello")]),
+    ///         ..Default::default()
+    ///     },
+    ///     wait: true,
+    ///     auto_remove: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// async move {
+    ///     let container = docker.run_container(options).await.unwrap();
+    ///     let logs = container.logs(false).try_collect::<Vec<_>>().await.unwrap();
+    ///     println!("exited with {:?}: {:?}", container.exit_code(), logs);
+    /// };
+    /// ```
+    pub async fn run_container(&self, options: RunOptions) -> Result<RunningContainer, Error> {
+        let image = options
+            .config
+            .image
+            .clone()
+            .ok_or(Error::MissingImageError {})?;
+
+        let create_options = (options.name.is_some() || options.platform.is_some()).then(|| {
+            CreateContainerOptions {
+                name: options.name.clone().unwrap_or_default(),
+                platform: options.platform.as_ref().map(Platform::to_string),
+            }
+        });
+
+        let response = if options.always_pull {
+            self.pull_for_run(
+                &Reference::parse(&image)?,
+                options.platform.as_ref(),
+                options.credentials.clone(),
+            )
+            .await?;
+            self.create_container(create_options, options.config.clone())
+                .await?
+        } else {
+            self.create_container_with_pull(
+                create_options,
+                options.config.clone(),
+                options.credentials.clone(),
+            )
+            .await?
+        };
+
+        for warning in &response.warnings {
+            warn!("{warning}");
+        }
+
+        let mut container = RunningContainer {
+            docker: self.clone(),
+            id: response.id,
+            attach: None,
+            exit_code: None,
+            auto_remove: options.auto_remove,
+        };
+
+        if options.attach {
+            container.attach = Some(
+                self.attach_container(
+                    &container.id,
+                    Some(AttachContainerOptions::<String> {
+                        stdin: options.config.open_stdin,
+                        stdout: Some(true),
+                        stderr: Some(true),
+                        stream: Some(true),
+                        ..Default::default()
+                    }),
+                )
+                .await?,
+            );
+        }
+
+        self.start_container(&container.id, None::<StartContainerOptions<String>>)
+            .await?;
+
+        if options.wait {
+            container.wait().await?;
+        }
+
+        Ok(container)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_missing_image;
+    use crate::errors::Error;
+
+    fn not_found(message: &str) -> Error {
+        Error::DockerResponseServerError {
+            status_code: 404,
+            message: String::from(message),
+            endpoint: None,
+        }
+    }
+
+    #[test]
+    fn test_is_missing_image() {
+        assert!(is_missing_image(&not_found("No such image: alpine:latest")));
+        assert!(is_missing_image(&not_found(
+            "image with reference alpine was found but does not match the specified platform: \
+             wanted linux/arm64, actual: linux/amd64"
+        )));
+        assert!(!is_missing_image(&not_found(
+            "network my-network not found"
+        )));
+        assert!(!is_missing_image(&Error::DockerResponseServerError {
+            status_code: 500,
+            message: String::from("No such image: alpine:latest"),
+            endpoint: None,
+        }));
+    }
+}
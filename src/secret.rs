@@ -160,7 +160,7 @@ impl Docker {
             url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(secret_spec)),
+            self.serialize_payload(Some(secret_spec)),
         );
 
         self.process_into_value(req).await
@@ -289,7 +289,7 @@ impl Docker {
             &url,
             Builder::new().method(Method::POST),
             Some(options),
-            Docker::serialize_payload(Some(secret_spec)),
+            self.serialize_payload(Some(secret_spec)),
         );
 
         self.process_into_unit(req).await
@@ -15,6 +15,8 @@ use serde_derive::Serialize;
 
 use std::{collections::HashMap, hash::Hash};
 
+pub mod tasks;
+
 /// Parameters used in the [List Service API](super::Docker::list_services())
 ///
 /// ## Examples
@@ -241,7 +243,7 @@ impl Docker {
                 .method(Method::POST)
                 .header(CONTENT_TYPE, "application/json"),
             None::<String>,
-            Docker::serialize_payload(Some(service_spec)),
+            self.serialize_payload(Some(service_spec)),
             DockerCredentialsHeader::Auth(credentials),
         );
 
@@ -403,7 +405,7 @@ impl Docker {
                 .method(Method::POST)
                 .header(CONTENT_TYPE, "application/json"),
             Some(options),
-            Docker::serialize_payload(Some(service_spec)),
+            self.serialize_payload(Some(service_spec)),
             DockerCredentialsHeader::Auth(credentials),
         );
 
@@ -0,0 +1,389 @@
+//! Tasks of a service, and a live stream of their state transitions to debug why a service does
+//! not converge.
+//!
+//! The swarm does not publish task events, so the [Watch Service Tasks
+//! API](crate::Docker::watch_service_tasks()) lists the tasks whenever the service or one of its
+//! local containers emits an event, and at least every [`SERVICE_TASKS_POLL_INTERVAL`] to catch
+//! tasks on other nodes. Watching the tasks requires the `system` feature, for the events.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use futures_util::stream::StreamExt;
+//!
+//! async move {
+//!     let mut transitions = Box::pin(docker.watch_service_tasks("my-service"));
+//!     while let Some(Ok(transition)) = transitions.next().await {
+//!         println!(
+//!             "{} on {:?}: {:?} -> {:?} {:?}",
+//!             transition.task_id,
+//!             transition.node_id,
+//!             transition.previous_state,
+//!             transition.state,
+//!             transition.error.or(transition.message)
+//!         );
+//!     }
+//! };
+//! ```
+
+use bytes::Bytes;
+#[cfg(feature = "system")]
+use futures_core::Stream;
+#[cfg(feature = "system")]
+use futures_util::future::ready;
+#[cfg(feature = "system")]
+use futures_util::stream::{self, StreamExt};
+use http::request::Builder;
+use http_body_util::Full;
+use hyper::Method;
+use serde_derive::Serialize;
+#[cfg(feature = "system")]
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+use std::collections::HashMap;
+#[cfg(feature = "system")]
+use std::time::Duration;
+
+use crate::docker::BodyType;
+use crate::errors::Error;
+use crate::models::Task;
+#[cfg(feature = "system")]
+use crate::models::{BollardDate, EventMessage, EventMessageTypeEnum, TaskState};
+#[cfg(feature = "system")]
+use crate::system::EventsOptions;
+use crate::Docker;
+
+/// Longest time the [Watch Service Tasks API](Docker::watch_service_tasks()) waits between two
+/// listings of the tasks.
+#[cfg(feature = "system")]
+pub const SERVICE_TASKS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[cfg(feature = "system")]
+const SERVICE_ID_LABEL: &str = "com.docker.swarm.service.id";
+#[cfg(feature = "system")]
+const SERVICE_NAME_LABEL: &str = "com.docker.swarm.service.name";
+
+#[derive(Debug, Clone, Serialize)]
+struct ListTasksQuery {
+    #[serde(serialize_with = "crate::docker::serialize_as_json")]
+    filters: HashMap<&'static str, Vec<String>>,
+}
+
+/// A change in the state of a task, returned by the [Watch Service Tasks
+/// API](Docker::watch_service_tasks()).
+#[cfg(feature = "system")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskTransition {
+    /// The ID of the task.
+    pub task_id: String,
+    /// The slot of the task in a replicated service.
+    pub slot: Option<i64>,
+    /// The ID of the node the task is placed on, once it is assigned.
+    pub node_id: Option<String>,
+    /// The state the task was last seen in, or `None` for a task that was not seen before.
+    /// States the task went through between two listings are not reported.
+    pub previous_state: Option<TaskState>,
+    /// The current state of the task.
+    pub state: Option<TaskState>,
+    /// The state the orchestrator wants the task to reach.
+    pub desired_state: Option<TaskState>,
+    /// The status message of the task, e.g. `starting`.
+    pub message: Option<String>,
+    /// The error that made the task fail or get rejected, e.g. `no suitable node`.
+    pub error: Option<String>,
+    /// Exit code of the container of the task, once it exited.
+    pub exit_code: Option<i64>,
+    /// When the task reached its current state.
+    pub timestamp: Option<BollardDate>,
+}
+
+#[cfg(feature = "system")]
+impl TaskTransition {
+    fn new(task: Task, previous_state: Option<TaskState>) -> Self {
+        let status = task.status.unwrap_or_default();
+        TaskTransition {
+            task_id: task.id.unwrap_or_default(),
+            slot: task.slot,
+            node_id: task.node_id,
+            previous_state,
+            state: status.state,
+            desired_state: task.desired_state,
+            message: status.message,
+            error: status.err,
+            exit_code: status
+                .container_status
+                .and_then(|container_status| container_status.exit_code),
+            timestamp: status.timestamp,
+        }
+    }
+}
+
+/// Transitions of the tasks whose state changed since the last listing, in the order the tasks
+/// reached their current state.
+#[cfg(feature = "system")]
+fn task_transitions(
+    known: &mut HashMap<String, Option<TaskState>>,
+    tasks: Vec<Task>,
+) -> Vec<TaskTransition> {
+    let mut transitions: Vec<TaskTransition> = tasks
+        .into_iter()
+        .filter_map(|task| {
+            let id = task.id.clone()?;
+            let state = task
+                .status
+                .as_ref()
+                .and_then(|status| status.state.as_ref());
+            if known
+                .get(&id)
+                .is_some_and(|previous_state| previous_state.as_ref() == state)
+            {
+                return None;
+            }
+            let previous_state = known.insert(id, state.cloned());
+            Some(TaskTransition::new(task, previous_state.flatten()))
+        })
+        .collect();
+
+    transitions.sort_by(|a, b| {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| a.task_id.cmp(&b.task_id))
+    });
+    transitions
+}
+
+/// Whether the event concerns the service or one of its containers.
+#[cfg(feature = "system")]
+fn is_service_event(event: &EventMessage, service_name: &str) -> bool {
+    let actor = event.actor.as_ref();
+    let attribute = |key: &str| {
+        actor
+            .and_then(|actor| actor.attributes.as_ref())
+            .and_then(|attributes| attributes.get(key))
+            .map(String::as_str)
+    };
+
+    match event.typ {
+        Some(EventMessageTypeEnum::SERVICE) => {
+            attribute("name") == Some(service_name)
+                || actor.and_then(|actor| actor.id.as_deref()) == Some(service_name)
+        }
+        Some(EventMessageTypeEnum::CONTAINER) => {
+            attribute(SERVICE_NAME_LABEL) == Some(service_name)
+                || attribute(SERVICE_ID_LABEL) == Some(service_name)
+        }
+        _ => false,
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # List Service Tasks
+    ///
+    /// List the tasks of a service, including those that already finished or failed.
+    ///
+    /// # Arguments
+    ///
+    ///  - Service name or ID as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Task](Task), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.list_service_tasks("my-service");
+    /// ```
+    pub async fn list_service_tasks(&self, service_name: &str) -> Result<Vec<Task>, Error> {
+        let url = "/tasks";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            Some(ListTasksQuery {
+                filters: HashMap::from([("service", vec![String::from(service_name)])]),
+            }),
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Watch Service Tasks
+    ///
+    /// Stream the state transitions of the tasks of a service, such as `assigned` to `preparing`
+    /// to `running` to `failed`, with the error messages and the placement of the tasks. The
+    /// current state of every task is reported first.
+    ///
+    /// # Arguments
+    ///
+    ///  - Service name or ID as string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Task Transition](TaskTransition) structs, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.watch_service_tasks("my-service");
+    /// ```
+    #[cfg(feature = "system")]
+    pub fn watch_service_tasks(
+        &self,
+        service_name: &str,
+    ) -> impl Stream<Item = Result<TaskTransition, Error>> {
+        let docker = self.clone();
+        let service_name = String::from(service_name);
+
+        let events_service_name = service_name.clone();
+        let events = self
+            .events(Some(EventsOptions::<String> {
+                filters: HashMap::from([(
+                    String::from("type"),
+                    vec![String::from("service"), String::from("container")],
+                )]),
+                ..Default::default()
+            }))
+            .filter_map(move |event| {
+                ready(match event {
+                    Ok(event) if is_service_event(&event, &events_service_name) => Some(Ok(())),
+                    Ok(_) => None,
+                    Err(e) => Some(Err(e)),
+                })
+            });
+
+        // the interval is created on first poll, as it needs a runtime
+        let ticks = stream::unfold(None, |ticks: Option<Interval>| async {
+            let mut ticks = ticks.unwrap_or_else(|| {
+                let mut ticks = interval(SERVICE_TASKS_POLL_INTERVAL);
+                ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                ticks
+            });
+            ticks.tick().await;
+            Some((Ok(()), Some(ticks)))
+        });
+
+        stream::select(ticks, events)
+            .then(move |trigger| {
+                let docker = docker.clone();
+                let service_name = service_name.clone();
+                async move {
+                    trigger?;
+                    docker.list_service_tasks(&service_name).await
+                }
+            })
+            .scan(HashMap::new(), |known, tasks| {
+                ready(Some(match tasks {
+                    Ok(tasks) => task_transitions(known, tasks).into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                }))
+            })
+            .flat_map(stream::iter)
+    }
+}
+
+#[cfg(all(test, feature = "system"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{is_service_event, task_transitions};
+    use crate::models::{EventMessage, Task, TaskState};
+
+    fn task(id: &str, state: &str, timestamp: &str) -> Task {
+        serde_json::from_value(serde_json::json!({
+            "ID": id,
+            "Slot": 1,
+            "NodeID": "node-1",
+            "DesiredState": "running",
+            "Status": {
+                "Timestamp": timestamp,
+                "State": state,
+                "Message": state,
+                "Err": if state == "failed" { "task: non-zero exit (1)" } else { "" },
+                "ContainerStatus": { "ExitCode": if state == "failed" { 1 } else { 0 } }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_task_transitions() {
+        let mut known = HashMap::new();
+
+        let transitions = task_transitions(
+            &mut known,
+            vec![
+                task("b", "running", "2024-01-01T00:00:02Z"),
+                task("a", "assigned", "2024-01-01T00:00:01Z"),
+            ],
+        );
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].task_id, "a");
+        assert_eq!(transitions[0].previous_state, None);
+        assert_eq!(transitions[0].state, Some(TaskState::ASSIGNED));
+        assert_eq!(transitions[1].task_id, "b");
+        assert_eq!(transitions[1].node_id.as_deref(), Some("node-1"));
+
+        let transitions = task_transitions(
+            &mut known,
+            vec![
+                task("b", "running", "2024-01-01T00:00:02Z"),
+                task("a", "failed", "2024-01-01T00:00:03Z"),
+            ],
+        );
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].task_id, "a");
+        assert_eq!(transitions[0].previous_state, Some(TaskState::ASSIGNED));
+        assert_eq!(transitions[0].state, Some(TaskState::FAILED));
+        assert_eq!(
+            transitions[0].error.as_deref(),
+            Some("task: non-zero exit (1)")
+        );
+        assert_eq!(transitions[0].exit_code, Some(1));
+        assert_eq!(transitions[0].desired_state, Some(TaskState::RUNNING));
+    }
+
+    #[test]
+    fn test_is_service_event() {
+        let event = |json| serde_json::from_value::<EventMessage>(json).unwrap();
+
+        assert!(is_service_event(
+            &event(serde_json::json!({
+                "Type": "service",
+                "Action": "update",
+                "Actor": { "ID": "t3zs1dy8n4uy", "Attributes": { "name": "web" } }
+            })),
+            "web"
+        ));
+        assert!(is_service_event(
+            &event(serde_json::json!({
+                "Type": "container",
+                "Action": "die",
+                "Actor": {
+                    "ID": "abc",
+                    "Attributes": { "com.docker.swarm.service.name": "web" }
+                }
+            })),
+            "web"
+        ));
+        assert!(!is_service_event(
+            &event(serde_json::json!({
+                "Type": "container",
+                "Action": "die",
+                "Actor": { "ID": "abc", "Attributes": { "name": "web" } }
+            })),
+            "web"
+        ));
+    }
+}
@@ -0,0 +1,544 @@
+//! Supervise: restart containers when they die, with backoff and a limit on the attempts, for
+//! agents that heal containers themselves rather than through the restart policy of the daemon.
+//!
+//! [`supervise`](Docker::supervise()) watches the `die` and `oom` events of the containers that
+//! match the filters, restarts them according to a [Supervise Policy](SupervisePolicy), and
+//! streams what it does as [Supervision Events](SupervisionEvent). Containers stopped or killed
+//! through the API, e.g. with `docker stop`, are not restarted, unless the signal they were sent
+//! does not stop them, such as a `SIGHUP` to reload their configuration.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::supervise::{SupervisePolicy, SupervisionEvent};
+//!
+//! use futures_util::stream::StreamExt;
+//!
+//! use std::collections::HashMap;
+//!
+//! let filters = HashMap::from([(
+//!     String::from("label"),
+//!     vec![String::from("com.example.supervised=true")],
+//! )]);
+//!
+//! async move {
+//!     let mut supervision = docker.supervise(filters, SupervisePolicy::default());
+//!     while let Some(Ok(event)) = supervision.next().await {
+//!         if let SupervisionEvent::GaveUp { container_id, attempts } = event {
+//!             eprintln!("{container_id} keeps dying after {attempts} restarts");
+//!         }
+//!     }
+//! };
+//! ```
+
+use futures_core::Stream;
+use futures_util::future::BoxFuture;
+use futures_util::stream::{BoxStream, FuturesUnordered, StreamExt};
+use log::debug;
+use tokio::time::{sleep, Instant};
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use super::Docker;
+use crate::container::signal::Signal;
+use crate::container::StartContainerOptions;
+use crate::errors::{Error, ErrorKind};
+use crate::models::{EventMessage, EventMessageTypeEnum};
+use crate::system::EventsOptions;
+
+/// How the [Supervise API](Docker::supervise()) restarts containers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SupervisePolicy {
+    /// Restart containers that exit with code `0`, not only those that fail.
+    pub restart_on_success: bool,
+    /// Restart containers killed because they ran out of memory.
+    pub restart_on_oom: bool,
+    /// Give up on a container after this many restarts in a row, or never with `None`.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first restart, doubled on every following attempt.
+    pub initial_backoff: Duration,
+    /// Longest delay before a restart.
+    pub max_backoff: Duration,
+    /// Time a container has to stay up after a restart for its attempts to be reset.
+    pub reset_after: Duration,
+}
+
+impl Default for SupervisePolicy {
+    /// Restart failed containers up to 5 times, after 1 second up to 1 minute, like the
+    /// `on-failure:5` restart policy of the daemon.
+    fn default() -> Self {
+        SupervisePolicy {
+            restart_on_success: false,
+            restart_on_oom: true,
+            max_attempts: Some(5),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            reset_after: Duration::from_secs(10),
+        }
+    }
+}
+
+impl SupervisePolicy {
+    /// Delay before the given attempt, starting at `1`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+/// What the [Supervise API](Docker::supervise()) observed or did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupervisionEvent {
+    /// A supervised container exited.
+    Exited {
+        /// The ID of the container.
+        container_id: String,
+        /// The name of the container.
+        name: Option<String>,
+        /// The exit code of the container.
+        exit_code: Option<i64>,
+        /// Whether the container ran out of memory.
+        oom_killed: bool,
+        /// Whether the container was stopped or killed through the API, and is left as it is.
+        stopped: bool,
+    },
+    /// A restart is scheduled after a backoff.
+    Restarting {
+        /// The ID of the container.
+        container_id: String,
+        /// The number of the attempt, starting at `1`.
+        attempt: u32,
+        /// Delay before the container is started.
+        backoff: Duration,
+    },
+    /// The container was started again.
+    Restarted {
+        /// The ID of the container.
+        container_id: String,
+        /// The number of the attempt.
+        attempt: u32,
+    },
+    /// Starting the container failed. It counts as an attempt, and is retried.
+    RestartFailed {
+        /// The ID of the container.
+        container_id: String,
+        /// The number of the attempt.
+        attempt: u32,
+        /// The error returned by the daemon.
+        message: String,
+    },
+    /// The container is no longer restarted, as it used up its attempts or was removed.
+    GaveUp {
+        /// The ID of the container.
+        container_id: String,
+        /// The number of restarts attempted.
+        attempts: u32,
+    },
+}
+
+/// Whether the signal of a `kill` event is one the API sends to stop a container, rather than
+/// e.g. a `SIGHUP` that leaves it running. Events without a known signal count as stopping it.
+fn is_stop_signal(signal: Option<&String>) -> bool {
+    match signal.map(|signal| signal.parse::<Signal>()) {
+        Some(Ok(signal)) => matches!(
+            signal,
+            Signal::Kill | Signal::Term | Signal::Int | Signal::Quit
+        ),
+        _ => true,
+    }
+}
+
+#[derive(Debug, Default)]
+struct SupervisedContainer {
+    attempts: u32,
+    restarted_at: Option<Instant>,
+    oom_killed: bool,
+    stopped: bool,
+}
+
+struct RestartOutcome {
+    container_id: String,
+    attempt: u32,
+    result: Result<(), Error>,
+}
+
+/// Stream of the [Supervise API](Docker::supervise()).
+struct Supervisor {
+    docker: Docker,
+    policy: SupervisePolicy,
+    events: BoxStream<'static, Result<EventMessage, Error>>,
+    events_ended: bool,
+    restarts: FuturesUnordered<BoxFuture<'static, RestartOutcome>>,
+    containers: HashMap<String, SupervisedContainer>,
+    restarting: HashSet<String>,
+    queued: VecDeque<SupervisionEvent>,
+}
+
+impl Supervisor {
+    fn handle_event(&mut self, event: EventMessage) {
+        if event.typ != Some(EventMessageTypeEnum::CONTAINER) {
+            return;
+        }
+        let Some(actor) = event.actor else {
+            return;
+        };
+        let Some(container_id) = actor.id else {
+            return;
+        };
+        let attributes = actor.attributes.unwrap_or_default();
+        let container = self.containers.entry(container_id.clone()).or_default();
+
+        match event.action.as_deref() {
+            Some("oom") => container.oom_killed = true,
+            Some("kill") if is_stop_signal(attributes.get("signal")) => container.stopped = true,
+            Some("destroy") => {
+                self.containers.remove(&container_id);
+            }
+            Some("die") => {
+                let oom_killed = std::mem::take(&mut container.oom_killed);
+                let stopped = std::mem::take(&mut container.stopped);
+                let exit_code = attributes
+                    .get("exitCode")
+                    .and_then(|exit_code| exit_code.parse().ok());
+                self.queued.push_back(SupervisionEvent::Exited {
+                    container_id: container_id.clone(),
+                    name: attributes.get("name").cloned(),
+                    exit_code,
+                    oom_killed,
+                    stopped,
+                });
+
+                let restart = !stopped
+                    && (exit_code != Some(0) || self.policy.restart_on_success)
+                    && (!oom_killed || self.policy.restart_on_oom);
+                if restart && !self.restarting.contains(&container_id) {
+                    self.schedule_restart(container_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn schedule_restart(&mut self, container_id: String) {
+        let container = self.containers.entry(container_id.clone()).or_default();
+        if container
+            .restarted_at
+            .is_some_and(|restarted_at| restarted_at.elapsed() >= self.policy.reset_after)
+        {
+            container.attempts = 0;
+        }
+
+        if self
+            .policy
+            .max_attempts
+            .is_some_and(|max_attempts| container.attempts >= max_attempts)
+        {
+            self.queued.push_back(SupervisionEvent::GaveUp {
+                container_id,
+                attempts: container.attempts,
+            });
+            return;
+        }
+
+        container.attempts += 1;
+        let attempt = container.attempts;
+        let backoff = self.policy.backoff(attempt);
+        self.queued.push_back(SupervisionEvent::Restarting {
+            container_id: container_id.clone(),
+            attempt,
+            backoff,
+        });
+
+        let docker = self.docker.clone();
+        self.restarting.insert(container_id.clone());
+        self.restarts.push(Box::pin(async move {
+            sleep(backoff).await;
+            debug!("Restarting supervised container {container_id}, attempt {attempt}");
+            let result = docker
+                .start_container(&container_id, None::<StartContainerOptions<String>>)
+                .await;
+            RestartOutcome {
+                container_id,
+                attempt,
+                result,
+            }
+        }));
+    }
+
+    fn handle_restart(&mut self, outcome: RestartOutcome) {
+        let RestartOutcome {
+            container_id,
+            attempt,
+            result,
+        } = outcome;
+        self.restarting.remove(&container_id);
+
+        match result {
+            Ok(()) => {
+                if let Some(container) = self.containers.get_mut(&container_id) {
+                    container.restarted_at = Some(Instant::now());
+                }
+                self.queued.push_back(SupervisionEvent::Restarted {
+                    container_id,
+                    attempt,
+                });
+            }
+            Err(e) if e.kind() == Some(ErrorKind::NotFound) => {
+                self.containers.remove(&container_id);
+                self.queued.push_back(SupervisionEvent::GaveUp {
+                    container_id,
+                    attempts: attempt,
+                });
+            }
+            Err(e) => {
+                self.queued.push_back(SupervisionEvent::RestartFailed {
+                    container_id: container_id.clone(),
+                    attempt,
+                    message: e.to_string(),
+                });
+                if self.containers.contains_key(&container_id) {
+                    self.schedule_restart(container_id);
+                }
+            }
+        }
+    }
+}
+
+impl Stream for Supervisor {
+    type Item = Result<SupervisionEvent, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.queued.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if let Poll::Ready(Some(outcome)) = self.restarts.poll_next_unpin(cx) {
+                self.handle_restart(outcome);
+                continue;
+            }
+            if self.events_ended {
+                // finish the scheduled restarts before ending
+                return if self.restarts.is_empty() {
+                    Poll::Ready(None)
+                } else {
+                    Poll::Pending
+                };
+            }
+            match self.events.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(event))) => self.handle_event(event),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => self.events_ended = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Supervise
+    ///
+    /// Restart the containers that match the filters whenever they die, according to a policy
+    /// with backoff and a limit on the restarts in a row. The containers are started again as
+    /// they are, with the [Start Container API](Docker::start_container()).
+    ///
+    /// # Arguments
+    ///
+    ///  - Filters of the [Events API](Docker::events()) that select the containers, e.g.
+    ///    `container=<name or id>`, `label=<key>=<value>` or `image=<image>`.
+    ///  - [Supervise Policy](SupervisePolicy) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - [Supervision Event](SupervisionEvent) enums, wrapped in a Stream. Dropping the stream
+    ///    stops the supervision.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::supervise::SupervisePolicy;
+    ///
+    /// use std::collections::HashMap;
+    ///
+    /// let filters = HashMap::from([(String::from("container"), vec![String::from("web")])]);
+    ///
+    /// docker.supervise(filters, SupervisePolicy::default());
+    /// ```
+    pub fn supervise(
+        &self,
+        filters: HashMap<String, Vec<String>>,
+        policy: SupervisePolicy,
+    ) -> impl Stream<Item = Result<SupervisionEvent, Error>> {
+        let mut filters = filters;
+        filters.insert(String::from("type"), vec![String::from("container")]);
+        filters.insert(
+            String::from("event"),
+            ["oom", "kill", "die", "destroy"].map(String::from).to_vec(),
+        );
+
+        Supervisor {
+            docker: self.clone(),
+            policy,
+            events: self
+                .events(Some(EventsOptions::<String> {
+                    filters,
+                    ..Default::default()
+                }))
+                .boxed(),
+            events_ended: false,
+            restarts: FuturesUnordered::new(),
+            containers: HashMap::new(),
+            restarting: HashSet::new(),
+            queued: VecDeque::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::TryStreamExt;
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::{is_stop_signal, SupervisePolicy, SupervisionEvent};
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    fn event(action: &str, attributes: serde_json::Value) -> String {
+        serde_json::json!({
+            "Type": "container",
+            "Action": action,
+            "Actor": { "ID": "abc", "Attributes": attributes }
+        })
+        .to_string()
+    }
+
+    async fn supervise(events: &[String]) -> Vec<SupervisionEvent> {
+        let body = events.join("\n");
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!(
+                "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let policy = SupervisePolicy {
+            initial_backoff: Duration::ZERO,
+            ..Default::default()
+        };
+        docker
+            .supervise(Default::default(), policy)
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_supervise_policy_backoff() {
+        let policy = SupervisePolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(40), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_is_stop_signal() {
+        assert!(is_stop_signal(Some(&String::from("15"))));
+        assert!(is_stop_signal(Some(&String::from("SIGKILL"))));
+        assert!(is_stop_signal(None));
+        assert!(!is_stop_signal(Some(&String::from("1"))));
+        assert!(!is_stop_signal(Some(&String::from("SIGUSR1"))));
+    }
+
+    #[tokio::test]
+    async fn test_supervise() {
+        let events = supervise(&[
+            event("oom", serde_json::json!({ "name": "web" })),
+            event(
+                "die",
+                serde_json::json!({ "name": "web", "exitCode": "137" }),
+            ),
+            event("kill", serde_json::json!({ "name": "web", "signal": "15" })),
+            event(
+                "die",
+                serde_json::json!({ "name": "web", "exitCode": "143" }),
+            ),
+        ])
+        .await;
+
+        assert_eq!(
+            events,
+            vec![
+                SupervisionEvent::Exited {
+                    container_id: String::from("abc"),
+                    name: Some(String::from("web")),
+                    exit_code: Some(137),
+                    oom_killed: true,
+                    stopped: false,
+                },
+                SupervisionEvent::Restarting {
+                    container_id: String::from("abc"),
+                    attempt: 1,
+                    backoff: Duration::ZERO,
+                },
+                SupervisionEvent::Exited {
+                    container_id: String::from("abc"),
+                    name: Some(String::from("web")),
+                    exit_code: Some(143),
+                    oom_killed: false,
+                    stopped: true,
+                },
+                SupervisionEvent::Restarted {
+                    container_id: String::from("abc"),
+                    attempt: 1,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_supervise_reload_signal() {
+        let events = supervise(&[
+            event("kill", serde_json::json!({ "name": "web", "signal": "1" })),
+            event("die", serde_json::json!({ "name": "web", "exitCode": "1" })),
+        ])
+        .await;
+
+        assert_eq!(
+            events,
+            vec![
+                SupervisionEvent::Exited {
+                    container_id: String::from("abc"),
+                    name: Some(String::from("web")),
+                    exit_code: Some(1),
+                    oom_killed: false,
+                    stopped: false,
+                },
+                SupervisionEvent::Restarting {
+                    container_id: String::from("abc"),
+                    attempt: 1,
+                    backoff: Duration::ZERO,
+                },
+                SupervisionEvent::Restarted {
+                    container_id: String::from("abc"),
+                    attempt: 1,
+                },
+            ]
+        );
+    }
+}
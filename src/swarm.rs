@@ -41,6 +41,79 @@ where
     pub join_token: T,
 }
 
+/// Parameters used in the [Update Swarm API](Docker::update_swarm())
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSwarmOptions {
+    /// The version number of the swarm object being updated. This is required to avoid
+    /// conflicting writes. This version number should be the value as currently set on the swarm
+    /// the spec was read from.
+    pub version: u64,
+    /// Rotate the worker join token.
+    pub rotate_worker_token: bool,
+    /// Rotate the manager join token.
+    pub rotate_manager_token: bool,
+    /// Rotate the manager unlock key.
+    pub rotate_manager_unlock_key: bool,
+}
+
+/// The version of a swarm, which an update of its spec has to be based on.
+fn swarm_version(swarm: &Swarm) -> Result<u64, Error> {
+    swarm
+        .version
+        .as_ref()
+        .and_then(|version| version.index)
+        .ok_or(Error::MissingSwarmVersionError {})
+}
+
+/// Listen address used by the [Init Swarm With Defaults](Docker::init_swarm_with_defaults()) and
+/// [Join As Worker](Docker::join_as_worker()) helpers.
+pub const SWARM_DEFAULT_LISTEN_ADDR: &str = "0.0.0.0:2377";
+
+/// The join tokens and certificate authority of a swarm.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SwarmJoinTokens {
+    /// The token workers can use to join the swarm.
+    pub worker: String,
+    /// The token managers can use to join the swarm.
+    pub manager: String,
+    /// The swarm's root CA certificate and issuer.
+    pub tls_info: Option<TlsInfo>,
+}
+
+impl From<Swarm> for SwarmJoinTokens {
+    fn from(swarm: Swarm) -> Self {
+        let join_tokens = swarm.join_tokens.unwrap_or_default();
+        SwarmJoinTokens {
+            worker: join_tokens.worker.unwrap_or_default(),
+            manager: join_tokens.manager.unwrap_or_default(),
+            tls_info: swarm.tls_info,
+        }
+    }
+}
+
+/// Result type for the [Init Swarm With Defaults](Docker::init_swarm_with_defaults()) helper
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InitSwarmResults {
+    /// The ID of the node that initialized the swarm.
+    pub node_id: String,
+    /// The join tokens and certificate authority of the new swarm.
+    pub join_tokens: SwarmJoinTokens,
+}
+
+/// Parameters used in the [Rotate Swarm CA API](Docker::rotate_swarm_ca())
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RotateSwarmCaOptions<T>
+where
+    T: Into<String>,
+{
+    /// The new signing CA certificate, in PEM format. When neither a certificate nor a key is
+    /// given, the swarm generates a new signing CA.
+    pub signing_ca_cert: Option<T>,
+    /// The key of the new signing CA certificate, in PEM format.
+    pub signing_ca_key: Option<T>,
+}
+
 /// Swam configuration used in the [Leave Swarm API](Docker::leave_swarm())
 #[derive(Debug, Copy, Clone, Default, Serialize)]
 pub struct LeaveSwarmOptions {
@@ -90,7 +163,7 @@ impl Docker {
             url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_value(req).await
@@ -164,7 +237,7 @@ impl Docker {
             url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_unit(req).await
@@ -200,4 +273,380 @@ impl Docker {
 
         self.process_into_unit(req).await
     }
+
+    /// ---
+    ///
+    /// # Update Swarm
+    ///
+    /// Update the swarm's configuration. The daemon rejects the update if the swarm changed since
+    /// the version given in the options, rather than overwriting the concurrent change.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Swarm Spec](SwarmSpec) struct.
+    ///  - [Update Swarm Options](UpdateSwarmOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::models::SwarmSpecTaskDefaults;
+    /// use bollard::swarm::UpdateSwarmOptions;
+    ///
+    /// async move {
+    ///     let swarm = docker.inspect_swarm().await.unwrap();
+    ///     let mut spec = swarm.spec.unwrap_or_default();
+    ///     spec.task_defaults = Some(SwarmSpecTaskDefaults::default());
+    ///
+    ///     let options = UpdateSwarmOptions {
+    ///         version: swarm.version.and_then(|version| version.index).unwrap(),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     docker.update_swarm(spec, options).await.unwrap();
+    /// };
+    /// ```
+    pub async fn update_swarm(
+        &self,
+        spec: SwarmSpec,
+        options: UpdateSwarmOptions,
+    ) -> Result<(), Error> {
+        let url = "/swarm/update";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            Some(options),
+            self.serialize_payload(Some(spec)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Init Swarm With Defaults
+    ///
+    /// Initialize a new swarm listening on [`SWARM_DEFAULT_LISTEN_ADDR`], and return the
+    /// tokens for other nodes to join it.
+    ///
+    /// # Arguments
+    ///
+    ///  - Externally reachable address advertised to other nodes, as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - [Init Swarm Results](InitSwarmResults), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async move {
+    ///     let results = docker.init_swarm_with_defaults("192.168.1.10").await.unwrap();
+    ///     println!("docker swarm join --token {} 192.168.1.10:2377", results.join_tokens.worker);
+    /// };
+    /// ```
+    pub async fn init_swarm_with_defaults(
+        &self,
+        advertise_addr: &str,
+    ) -> Result<InitSwarmResults, Error> {
+        let node_id = self
+            .init_swarm(InitSwarmOptions {
+                listen_addr: SWARM_DEFAULT_LISTEN_ADDR,
+                advertise_addr,
+            })
+            .await?;
+
+        Ok(InitSwarmResults {
+            node_id,
+            join_tokens: self.inspect_swarm().await?.into(),
+        })
+    }
+
+    /// ---
+    ///
+    /// # Join As Worker
+    ///
+    /// Join an existing swarm as a worker node, listening on [`SWARM_DEFAULT_LISTEN_ADDR`].
+    ///
+    /// # Arguments
+    ///
+    ///  - Address of a manager of the swarm, as a string slice.
+    ///  - Worker join token, as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.join_as_worker("192.168.1.10:2377", "SWMTKN-1-...");
+    /// ```
+    pub async fn join_as_worker(&self, manager_addr: &str, token: &str) -> Result<(), Error> {
+        let url = "/swarm/join";
+
+        let config = SwarmJoinRequest {
+            listen_addr: Some(String::from(SWARM_DEFAULT_LISTEN_ADDR)),
+            remote_addrs: Some(vec![String::from(manager_addr)]),
+            join_token: Some(String::from(token)),
+            ..Default::default()
+        };
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            self.serialize_payload(Some(config)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Rotate Join Tokens
+    ///
+    /// Replace the worker and manager join tokens of the swarm. Nodes that already joined the
+    /// swarm are not affected.
+    ///
+    /// # Returns
+    ///
+    ///  - The new [Swarm Join Tokens](SwarmJoinTokens), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.rotate_join_tokens();
+    /// ```
+    pub async fn rotate_join_tokens(&self) -> Result<SwarmJoinTokens, Error> {
+        let swarm = self.inspect_swarm().await?;
+        let version = swarm_version(&swarm)?;
+
+        self.update_swarm(
+            swarm.spec.unwrap_or_default(),
+            UpdateSwarmOptions {
+                version,
+                rotate_worker_token: true,
+                rotate_manager_token: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(self.inspect_swarm().await?.into())
+    }
+
+    /// ---
+    ///
+    /// # Get Unlock Key
+    ///
+    /// Get the unlock key of a swarm with autolock enabled, which encrypts the raft logs of the
+    /// managers at rest.
+    ///
+    /// # Returns
+    ///
+    ///  - [Unlock Key Response](UnlockKeyResponse), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.get_unlock_key();
+    /// ```
+    pub async fn get_unlock_key(&self) -> Result<UnlockKeyResponse, Error> {
+        let url = "/swarm/unlockkey";
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        self.process_into_value(req).await
+    }
+
+    /// ---
+    ///
+    /// # Unlock Swarm
+    ///
+    /// Unlock a manager of a swarm with autolock enabled, after the manager restarted.
+    ///
+    /// # Arguments
+    ///
+    ///  - Unlock key, as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.unlock_swarm("SWMKEY-1-...");
+    /// ```
+    pub async fn unlock_swarm(&self, unlock_key: &str) -> Result<(), Error> {
+        let url = "/swarm/unlock";
+
+        let config = SwarmUnlockRequest {
+            unlock_key: Some(String::from(unlock_key)),
+        };
+
+        let req = self.build_request(
+            url,
+            Builder::new().method(Method::POST),
+            None::<String>,
+            self.serialize_payload(Some(config)),
+        );
+
+        self.process_into_unit(req).await
+    }
+
+    /// ---
+    ///
+    /// # Rotate Swarm CA
+    ///
+    /// Rotate the signing CA of the swarm, either to the given certificate and key, or to a new
+    /// CA generated by the swarm. Node certificates are reissued in the background.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [Rotate Swarm CA Options](RotateSwarmCaOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.rotate_swarm_ca::<String>(None);
+    /// ```
+    pub async fn rotate_swarm_ca<T>(
+        &self,
+        options: Option<RotateSwarmCaOptions<T>>,
+    ) -> Result<(), Error>
+    where
+        T: Into<String>,
+    {
+        let swarm = self.inspect_swarm().await?;
+        let version = swarm_version(&swarm)?;
+        let mut spec = swarm.spec.unwrap_or_default();
+        let ca_config = spec.ca_config.get_or_insert_with(Default::default);
+
+        match options {
+            Some(RotateSwarmCaOptions {
+                signing_ca_cert,
+                signing_ca_key,
+            }) if signing_ca_cert.is_some() || signing_ca_key.is_some() => {
+                ca_config.signing_ca_cert = signing_ca_cert.map(Into::into);
+                ca_config.signing_ca_key = signing_ca_key.map(Into::into);
+            }
+            _ => {
+                ca_config.signing_ca_cert = None;
+                ca_config.signing_ca_key = None;
+                ca_config.force_rotate = Some(ca_config.force_rotate.unwrap_or_default() + 1);
+            }
+        }
+
+        self.update_swarm(
+            spec,
+            UpdateSwarmOptions {
+                version,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(not(windows))]
+#[cfg(test)]
+mod tests {
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::swarm_version;
+    use crate::errors::Error;
+    use crate::models::{ObjectVersion, Swarm};
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    #[test]
+    fn test_swarm_version() {
+        let swarm = Swarm {
+            version: Some(ObjectVersion { index: Some(42) }),
+            ..Default::default()
+        };
+        assert_eq!(swarm_version(&swarm).unwrap(), 42);
+        assert!(matches!(
+            swarm_version(&Swarm::default()),
+            Err(Error::MissingSwarmVersionError {})
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_join_tokens() {
+        let body = r#"{"ID":"abajmipo7b4xz5ip2nrla6b11","Version":{"Index":373531},"JoinTokens":{"Worker":"SWMTKN-1-worker","Manager":"SWMTKN-1-manager"},"TLSInfo":{"TrustRoot":"-----BEGIN CERTIFICATE-----"}}"#;
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!(
+                "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let tokens = docker.rotate_join_tokens().await.unwrap();
+
+        assert_eq!(tokens.worker, "SWMTKN-1-worker");
+        assert_eq!(tokens.manager, "SWMTKN-1-manager");
+        assert_eq!(
+            tokens.tls_info.unwrap().trust_root.as_deref(),
+            Some("-----BEGIN CERTIFICATE-----")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_unlock_key() {
+        let body = r#"{"UnlockKey":"SWMKEY-1-7c37Cc8654o6p38HnroywCi19pllOnGtbdZEgtKxZu8"}"#;
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            format!(
+                "HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Type:application/json\r\nContent-Length:{}\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let response = docker.get_unlock_key().await.unwrap();
+
+        assert_eq!(
+            response.unlock_key.as_deref(),
+            Some("SWMKEY-1-7c37Cc8654o6p38HnroywCi19pllOnGtbdZEgtKxZu8")
+        );
+    }
 }
@@ -2,19 +2,200 @@
 
 use bytes::Bytes;
 use futures_core::Stream;
+use futures_util::StreamExt;
 use http::request::Builder;
+use http::HeaderMap;
 use http_body_util::Full;
 use hyper::Method;
+use log::debug;
 use serde_derive::{Deserialize, Serialize};
 use serde_json::value::Value;
 
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::time::Duration;
 
-use super::Docker;
-use crate::docker::BodyType;
+use super::{ClientVersion, Docker};
+use crate::docker::{BodyType, MaybeClientVersion};
 use crate::errors::Error;
 use crate::models::*;
+use crate::read::IdleTimeout;
+
+/// Features of the daemon, derived from the [Version API](Docker::version()) and the [Info
+/// API](Docker::info()), to branch on without comparing strings.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::models::{SystemInfo, SystemInfoCgroupVersionEnum};
+/// use bollard::system::{DaemonCapabilities, Version};
+///
+/// let capabilities = DaemonCapabilities::new(
+///     Version {
+///         api_version: Some(String::from("1.46")),
+///         os: Some(String::from("linux")),
+///         ..Default::default()
+///     },
+///     SystemInfo {
+///         cgroup_version: Some(SystemInfoCgroupVersionEnum::_2),
+///         security_options: Some(vec![String::from("name=rootless")]),
+///         ..Default::default()
+///     },
+/// );
+///
+/// assert!(capabilities.supports_buildkit());
+/// assert!(capabilities.is_rootless());
+/// assert_eq!(capabilities.cgroup_version(), Some(2));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaemonCapabilities {
+    version: Version,
+    info: SystemInfo,
+}
+
+impl DaemonCapabilities {
+    /// Derive the capabilities from the responses of the version and info APIs.
+    pub fn new(version: Version, info: SystemInfo) -> Self {
+        DaemonCapabilities { version, info }
+    }
+
+    /// The response of the [Version API](Docker::version()).
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// The response of the [Info API](Docker::info()).
+    pub fn info(&self) -> &SystemInfo {
+        &self.info
+    }
+
+    /// The highest API version supported by the daemon.
+    pub fn max_api_version(&self) -> Option<ClientVersion> {
+        parse_api_version(self.version.api_version.as_deref())
+    }
+
+    /// The lowest API version supported by the daemon.
+    pub fn min_api_version(&self) -> Option<ClientVersion> {
+        parse_api_version(self.version.min_api_version.as_deref())
+    }
+
+    /// Whether the daemon accepts requests at the given API version.
+    pub fn supports_api_version(&self, version: &ClientVersion) -> bool {
+        self.max_api_version().is_some_and(|max| *version <= max)
+            && self.min_api_version().is_none_or(|min| *version >= min)
+    }
+
+    /// Whether the daemon can build images with buildkit, available on Linux daemons from API
+    /// version 1.39.
+    pub fn supports_buildkit(&self) -> bool {
+        let os = self.version.os.as_deref().or(self.info.os_type.as_deref());
+        os == Some("linux")
+            && self.supports_api_version(&ClientVersion {
+                major_version: 1,
+                minor_version: 39,
+            })
+    }
+
+    /// The cgroup version of the host, `1` or `2`.
+    pub fn cgroup_version(&self) -> Option<u8> {
+        match self.info.cgroup_version {
+            Some(SystemInfoCgroupVersionEnum::_1) => Some(1),
+            Some(SystemInfoCgroupVersionEnum::_2) => Some(2),
+            _ => None,
+        }
+    }
+
+    /// The security features enabled on the daemon.
+    pub fn security_options(&self) -> Vec<SecurityOption> {
+        self.info
+            .security_options
+            .iter()
+            .flatten()
+            .map(|option| SecurityOption::from(option.as_str()))
+            .collect()
+    }
+
+    /// Whether the daemon runs without root privileges.
+    pub fn is_rootless(&self) -> bool {
+        self.security_options().contains(&SecurityOption::Rootless)
+    }
+
+    /// Whether the daemon runs with experimental features enabled.
+    pub fn is_experimental(&self) -> bool {
+        self.info.experimental_build.unwrap_or_default()
+    }
+
+    /// The store holding the daemon's images.
+    pub fn image_store(&self) -> ImageStore {
+        ImageStore::from(&self.info)
+    }
+
+    /// The containerd snapshotter, if the daemon uses the containerd image store.
+    pub fn snapshotter_in_use(&self) -> Option<String> {
+        match self.image_store() {
+            ImageStore::Containerd { snapshotter } => Some(snapshotter),
+            _ => None,
+        }
+    }
+
+    /// Whether the daemon has the given container runtime, e.g. `runc` or `nvidia`.
+    pub fn has_runtime(&self, runtime: &str) -> bool {
+        self.info
+            .runtimes
+            .as_ref()
+            .is_some_and(|runtimes| runtimes.contains_key(runtime))
+    }
+
+    /// Whether the daemon resolves [CDI](crate::container::devices) device requests.
+    pub fn supports_cdi(&self) -> bool {
+        self.info
+            .cdi_spec_dirs
+            .as_ref()
+            .is_some_and(|dirs| !dirs.is_empty())
+    }
+}
+
+fn parse_api_version(version: Option<&str>) -> Option<ClientVersion> {
+    match MaybeClientVersion::from(version?) {
+        MaybeClientVersion::Some(version) => Some(version),
+        MaybeClientVersion::None => None,
+    }
+}
+
+/// Headers returned by the [Ping Info API](Docker::ping_info()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PingInfo {
+    /// The highest API version supported by the daemon, from the `Api-Version` header.
+    pub api_version: Option<String>,
+    /// The daemon's operating system, e.g. `linux`, from the `OSType` header.
+    pub os_type: Option<String>,
+    /// Whether the daemon runs with experimental features, from the `Docker-Experimental` header.
+    pub experimental: bool,
+    /// The default builder, `1` for the classic builder or `2` for buildkit, from the
+    /// `Builder-Version` header.
+    pub builder_version: Option<String>,
+    /// The node's swarm status, e.g. `inactive` or `active/manager`, from the `Swarm` header.
+    pub swarm: Option<String>,
+}
+
+impl From<&HeaderMap> for PingInfo {
+    fn from(headers: &HeaderMap) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+
+        PingInfo {
+            api_version: header("api-version"),
+            os_type: header("ostype"),
+            experimental: header("docker-experimental").as_deref() == Some("true"),
+            builder_version: header("builder-version"),
+            swarm: header("swarm"),
+        }
+    }
+}
 
 /// Response of Engine API: GET \"/version\"
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -113,7 +294,8 @@ pub struct VersionComponents {
 /// EventsOptions::<String>{
 ///     since: Some(OffsetDateTime::now_utc() - Duration::minutes(20)),
 ///     until: Some(OffsetDateTime::now_utc()),
-///     filters: HashMap::new()
+///     filters: HashMap::new(),
+///     idle_timeout: None,
 /// };
 /// # }
 /// ```
@@ -161,6 +343,275 @@ where
     ///  - `volume=<string>` volume name
     #[serde(serialize_with = "crate::docker::serialize_as_json")]
     pub filters: HashMap<T, Vec<T>>,
+    /// Abort the stream with a [Stream Stalled Error](crate::errors::Error::StreamStalledError)
+    /// when no data arrives for this long. Defaults to the timeout set with
+    /// [`with_idle_timeout`](Docker::with_idle_timeout()). Not sent to the daemon.
+    #[serde(skip)]
+    pub idle_timeout: Option<Duration>,
+}
+
+/// Position in the stream of the [Events API](Docker::events()), to resume the stream with
+/// [`events_from_cursor`](Docker::events_from_cursor()) after a restart without losing or
+/// duplicating events.
+///
+/// The daemon replays the events from the `since` timestamp inclusively, so the cursor keeps the
+/// `timeNano` of the last event along with the number of events seen at that time, and skips
+/// them when the stream is reopened. Persist the cursor, e.g. as JSON, after handling each event.
+///
+/// The daemon only keeps the most recent events in memory, so events older than its buffer are
+/// lost across a long interruption or a restart of the daemon.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::models::EventMessage;
+/// use bollard::system::EventCursor;
+///
+/// let mut cursor = EventCursor::at(1_700_000_000_000_000_000);
+/// let event = EventMessage {
+///     time_nano: Some(1_700_000_000_500_000_000),
+///     ..Default::default()
+/// };
+/// assert!(cursor.advance(&event));
+/// assert_eq!(cursor.time_nano(), Some(1_700_000_000_500_000_000));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursor {
+    time_nano: Option<i64>,
+    seen: u64,
+    /// The events at `time_nano` passed through [`advance`](EventCursor::advance()) since the
+    /// stream was opened.
+    #[serde(skip)]
+    replayed: u64,
+}
+
+impl EventCursor {
+    /// A cursor without a position, only streaming the events that occur from now on.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// A cursor replaying the events from the given Unix timestamp in nanoseconds, inclusively.
+    pub fn at(time_nano: i64) -> Self {
+        EventCursor {
+            time_nano: Some(time_nano),
+            ..Default::default()
+        }
+    }
+
+    /// The Unix timestamp in nanoseconds of the last event seen.
+    pub fn time_nano(&self) -> Option<i64> {
+        self.time_nano
+    }
+
+    /// The `since` parameter of the Events API, as `seconds.nanoseconds`.
+    fn since(&self) -> Option<String> {
+        self.time_nano.map(|time_nano| {
+            format!(
+                "{}.{:09}",
+                time_nano.div_euclid(1_000_000_000),
+                time_nano.rem_euclid(1_000_000_000)
+            )
+        })
+    }
+
+    /// Move the cursor past an event, returning false if the cursor already saw the event, i.e.
+    /// the event is replayed by the daemon on a reopened stream.
+    pub fn advance(&mut self, event: &EventMessage) -> bool {
+        let time_nano = match event
+            .time_nano
+            .or_else(|| event.time.map(|time| time * 1_000_000_000))
+        {
+            Some(time_nano) => time_nano,
+            None => return true,
+        };
+
+        match self.time_nano {
+            Some(last) if time_nano < last => false,
+            Some(last) if time_nano == last => {
+                self.replayed += 1;
+                if self.replayed <= self.seen {
+                    false
+                } else {
+                    self.seen = self.replayed;
+                    true
+                }
+            }
+            _ => {
+                self.time_nano = Some(time_nano);
+                self.seen = 1;
+                self.replayed = 1;
+                true
+            }
+        }
+    }
+}
+
+/// The query of the [Events API](Docker::events()) for a stream resumed from a cursor, replacing
+/// the `since` parameter of the options.
+fn cursor_query<T>(
+    cursor: &EventCursor,
+    options: Option<EventsOptions<T>>,
+) -> Result<Vec<(String, String)>, Error>
+where
+    T: Into<String> + Eq + Hash + serde::ser::Serialize,
+{
+    let mut query: Vec<(String, String)> = match options {
+        Some(options) => {
+            url::form_urlencoded::parse(serde_urlencoded::to_string(options)?.as_bytes())
+                .into_owned()
+                .filter(|(key, _)| key != "since")
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    if let Some(since) = cursor.since() {
+        query.push((String::from("since"), since));
+    }
+    Ok(query)
+}
+
+/// A security feature enabled on the daemon, parsed from the `SecurityOptions` field of the
+/// [Info API](Docker::info()).
+///
+/// The daemon reports each option as a comma-separated list of `key=value` pairs, e.g.
+/// `name=seccomp,profile=default`. Daemons before API version 1.30 report the bare name instead.
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::system::SecurityOption;
+///
+/// assert_eq!(
+///     SecurityOption::from("name=seccomp,profile=default"),
+///     SecurityOption::Seccomp {
+///         profile: Some(String::from("default"))
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SecurityOption {
+    /// Seccomp filtering, with the name of the default profile (`builtin`, `default`, or
+    /// `unconfined`).
+    Seccomp {
+        /// The default seccomp profile applied to containers.
+        profile: Option<String>,
+    },
+    /// AppArmor confinement.
+    AppArmor,
+    /// SELinux labelling.
+    SELinux,
+    /// User namespace remapping.
+    UserNamespaces,
+    /// The daemon runs without root privileges.
+    Rootless,
+    /// Containers run in a private cgroup namespace by default.
+    CgroupNamespaces,
+    /// Containers are started with the `no-new-privileges` flag by default.
+    NoNewPrivileges,
+    /// A security option this client does not know about.
+    Other {
+        /// The name of the option.
+        name: String,
+        /// Any further `key=value` pairs of the option.
+        details: HashMap<String, String>,
+    },
+}
+
+impl From<&str> for SecurityOption {
+    fn from(option: &str) -> Self {
+        let mut name = None;
+        let mut details = HashMap::new();
+        for field in option.split(',').filter(|field| !field.is_empty()) {
+            match field.split_once('=') {
+                Some(("name", value)) => name = Some(value.to_string()),
+                Some((key, value)) => {
+                    details.insert(key.to_string(), value.to_string());
+                }
+                None if name.is_none() => name = Some(field.to_string()),
+                None => {
+                    details.insert(field.to_string(), String::new());
+                }
+            }
+        }
+
+        match name.unwrap_or_default().as_str() {
+            "seccomp" => SecurityOption::Seccomp {
+                profile: details.remove("profile"),
+            },
+            "apparmor" => SecurityOption::AppArmor,
+            "selinux" => SecurityOption::SELinux,
+            "userns" => SecurityOption::UserNamespaces,
+            "rootless" => SecurityOption::Rootless,
+            "cgroupns" => SecurityOption::CgroupNamespaces,
+            "no-new-privileges" => SecurityOption::NoNewPrivileges,
+            name => SecurityOption::Other {
+                name: name.to_string(),
+                details,
+            },
+        }
+    }
+}
+
+/// The store holding the daemon's images, as reported by the [Info API](Docker::info()).
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::models::SystemInfo;
+/// use bollard::system::ImageStore;
+///
+/// let info = SystemInfo {
+///     driver: Some(String::from("overlayfs")),
+///     driver_status: Some(vec![vec![
+///         String::from("driver-type"),
+///         String::from("io.containerd.snapshotter.v1"),
+///     ]]),
+///     ..Default::default()
+/// };
+///
+/// assert!(ImageStore::from(&info).is_containerd());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ImageStore {
+    /// The containerd image store, used by default in recent releases of Docker. Image inspect
+    /// responses may omit the `GraphDriver`, and carry a `Descriptor` and `Manifests` instead.
+    Containerd {
+        /// The containerd snapshotter, e.g. `overlayfs`.
+        snapshotter: String,
+    },
+    /// The classic image store, backed by a graph driver.
+    GraphDriver {
+        /// The graph driver, e.g. `overlay2`.
+        driver: String,
+    },
+}
+
+impl ImageStore {
+    /// Whether this is the containerd image store.
+    pub fn is_containerd(&self) -> bool {
+        matches!(self, ImageStore::Containerd { .. })
+    }
+}
+
+impl From<&SystemInfo> for ImageStore {
+    fn from(info: &SystemInfo) -> Self {
+        let driver = info.driver.clone().unwrap_or_default();
+        let containerd = info.driver_status.iter().flatten().any(|status| {
+            matches!(status.as_slice(), [label, value]
+                if label == "driver-type" && value.starts_with("io.containerd.snapshotter"))
+        });
+
+        if containerd {
+            ImageStore::Containerd {
+                snapshotter: driver,
+            }
+        } else {
+            ImageStore::GraphDriver { driver }
+        }
+    }
 }
 
 impl Docker {
@@ -247,6 +698,85 @@ impl Docker {
         self.process_into_string(req).await
     }
 
+    /// ---
+    ///
+    /// # Ping Info
+    ///
+    /// Ping the server, returning the information it advertises in the response headers.
+    ///
+    /// # Returns
+    ///
+    ///  - [Ping Info](PingInfo), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// docker.ping_info();
+    /// ```
+    pub async fn ping_info(&self) -> Result<PingInfo, Error> {
+        let req = self.build_request(
+            "/_ping",
+            Builder::new().method(Method::GET),
+            None::<String>,
+            Ok(BodyType::Left(Full::new(Bytes::new()))),
+        );
+
+        let response = self.process_request(req).await?;
+
+        Ok(PingInfo::from(response.headers()))
+    }
+
+    /// ---
+    ///
+    /// # Ping Until Ready
+    ///
+    /// Ping the server until it responds, e.g. right after starting the daemon or a Docker in
+    /// Docker container, whose socket may not accept connections yet.
+    ///
+    /// # Arguments
+    ///
+    ///  - The time to wait for the server to respond.
+    ///  - The delay between attempts.
+    ///
+    /// # Returns
+    ///
+    ///  - [Ping Info](PingInfo), wrapped in a Future. If the server has not responded in time,
+    ///    the error of the last attempt.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use std::time::Duration;
+    ///
+    /// docker.ping_until_ready(Duration::from_secs(30), Duration::from_millis(500));
+    /// ```
+    pub async fn ping_until_ready(
+        &self,
+        timeout: Duration,
+        interval: Duration,
+    ) -> Result<PingInfo, Error> {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let err = match tokio::time::timeout_at(deadline, self.ping_info()).await {
+                Ok(Ok(info)) => return Ok(info),
+                Ok(Err(err)) => err,
+                Err(_) => return Err(Error::RequestTimeoutError),
+            };
+
+            if tokio::time::Instant::now() + interval >= deadline {
+                return Err(err);
+            }
+            debug!("daemon not ready: {err}");
+            tokio::time::sleep(interval).await;
+        }
+    }
+
     /// ---
     ///
     /// # Events
@@ -272,6 +802,7 @@ impl Docker {
     ///     since: Some(OffsetDateTime::now_utc() - Duration::minutes(20)),
     ///     until: Some(OffsetDateTime::now_utc()),
     ///     filters: HashMap::new(),
+    ///     idle_timeout: None,
     /// }));
     /// ```
     pub fn events<T>(
@@ -282,6 +813,10 @@ impl Docker {
         T: Into<String> + Eq + Hash + serde::ser::Serialize,
     {
         let url = "/events";
+        let idle_timeout = options
+            .as_ref()
+            .and_then(|options| options.idle_timeout)
+            .or(self.idle_timeout);
 
         let req = self.build_request(
             url,
@@ -290,7 +825,80 @@ impl Docker {
             Ok(BodyType::Left(Full::new(Bytes::new()))),
         );
 
-        self.process_into_stream(req)
+        IdleTimeout::new(self.process_into_stream(req), idle_timeout)
+    }
+
+    /// ---
+    ///
+    /// # Events From Cursor
+    ///
+    /// Stream events from the position of an [Event Cursor](EventCursor), replaying the events
+    /// the daemon still buffers since the cursor's last event, without repeating it.
+    ///
+    /// # Arguments
+    ///
+    ///  - The [Event Cursor](EventCursor) to resume from.
+    ///  - Optional [Events Options](EventsOptions) struct. Its `since` field is replaced by the
+    ///    cursor's position.
+    ///
+    /// # Returns
+    ///
+    ///  - [EventMessage](crate::models::EventMessage) along with the cursor positioned after it,
+    ///    to persist once the event is handled, wrapped in a Stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::system::{EventCursor, EventsOptions};
+    ///
+    /// use futures_util::stream::TryStreamExt;
+    ///
+    /// async move {
+    ///     let mut cursor = EventCursor::new();
+    ///     let mut events = Box::pin(
+    ///         docker.events_from_cursor(&cursor, None::<EventsOptions<String>>),
+    ///     );
+    ///     while let Some((event, position)) = events.try_next().await.unwrap() {
+    ///         println!("{:?}", event);
+    ///         cursor = position;
+    ///     }
+    /// };
+    /// ```
+    pub fn events_from_cursor<T>(
+        &self,
+        cursor: &EventCursor,
+        options: Option<EventsOptions<T>>,
+    ) -> impl Stream<Item = Result<(EventMessage, EventCursor), Error>>
+    where
+        T: Into<String> + Eq + Hash + serde::ser::Serialize,
+    {
+        let url = "/events";
+        let idle_timeout = options
+            .as_ref()
+            .and_then(|options| options.idle_timeout)
+            .or(self.idle_timeout);
+        let mut cursor = EventCursor {
+            replayed: 0,
+            ..*cursor
+        };
+
+        let req = cursor_query(&cursor, options).and_then(|query| {
+            self.build_request(
+                url,
+                Builder::new().method(Method::GET),
+                Some(query),
+                Ok(BodyType::Left(Full::new(Bytes::new()))),
+            )
+        });
+
+        IdleTimeout::new(self.process_into_stream(req), idle_timeout).filter_map(move |res| {
+            futures_util::future::ready(match res {
+                Ok(event) => cursor.advance(&event).then(|| Ok((event, cursor))),
+                Err(e) => Some(Err(e)),
+            })
+        })
     }
 
     /// ---
@@ -324,4 +932,289 @@ impl Docker {
 
         self.process_into_value(req).await
     }
+
+    /// ---
+    ///
+    /// # Security Options
+    ///
+    /// Returns the security features enabled on the daemon, such as seccomp, AppArmor, rootless
+    /// mode or cgroup namespaces.
+    ///
+    /// # Returns
+    ///
+    ///  - Vector of [Security Options](SecurityOption), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.security_options();
+    /// ```
+    pub async fn security_options(&self) -> Result<Vec<SecurityOption>, Error> {
+        let info = self.info().await?;
+
+        Ok(info
+            .security_options
+            .unwrap_or_default()
+            .iter()
+            .map(|option| SecurityOption::from(option.as_str()))
+            .collect())
+    }
+
+    /// ---
+    ///
+    /// # Image Store
+    ///
+    /// Returns the store holding the daemon's images: the containerd image store with its
+    /// snapshotter, or the classic store with its graph driver.
+    ///
+    /// # Returns
+    ///
+    ///  - An [Image Store](ImageStore), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// docker.image_store();
+    /// ```
+    pub async fn image_store(&self) -> Result<ImageStore, Error> {
+        Ok(ImageStore::from(&self.info().await?))
+    }
+
+    /// ---
+    ///
+    /// # Daemon Capabilities
+    ///
+    /// Returns the features of the daemon, derived from the version and info APIs.
+    ///
+    /// # Returns
+    ///
+    ///  - [Daemon Capabilities](DaemonCapabilities), wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async move {
+    ///     let capabilities = docker.daemon_capabilities().await.unwrap();
+    ///     if capabilities.supports_buildkit() {
+    ///         println!("snapshotter: {:?}", capabilities.snapshotter_in_use());
+    ///     }
+    /// };
+    /// ```
+    pub async fn daemon_capabilities(&self) -> Result<DaemonCapabilities, Error> {
+        let (version, info) = futures_util::try_join!(self.version(), self.info())?;
+
+        Ok(DaemonCapabilities::new(version, info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::models::SystemInfo;
+
+    use crate::models::SystemInfoCgroupVersionEnum;
+    use crate::ClientVersion;
+
+    use super::{
+        cursor_query, DaemonCapabilities, EventCursor, EventsOptions, ImageStore, PingInfo,
+        SecurityOption, Version,
+    };
+    use crate::models::EventMessage;
+
+    #[test]
+    fn test_daemon_capabilities() {
+        let capabilities = DaemonCapabilities::new(
+            Version {
+                api_version: Some(String::from("1.46")),
+                min_api_version: Some(String::from("1.24")),
+                os: Some(String::from("linux")),
+                ..Default::default()
+            },
+            SystemInfo {
+                cgroup_version: Some(SystemInfoCgroupVersionEnum::_1),
+                driver: Some(String::from("overlayfs")),
+                driver_status: Some(vec![vec![
+                    String::from("driver-type"),
+                    String::from("io.containerd.snapshotter.v1"),
+                ]]),
+                experimental_build: Some(true),
+                cdi_spec_dirs: Some(vec![String::from("/etc/cdi")]),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            capabilities.max_api_version(),
+            Some(ClientVersion {
+                major_version: 1,
+                minor_version: 46
+            })
+        );
+        assert!(!capabilities.supports_api_version(&ClientVersion {
+            major_version: 1,
+            minor_version: 12
+        }));
+        assert!(capabilities.supports_buildkit());
+        assert_eq!(capabilities.cgroup_version(), Some(1));
+        assert!(!capabilities.is_rootless());
+        assert!(capabilities.is_experimental());
+        assert_eq!(
+            capabilities.snapshotter_in_use().as_deref(),
+            Some("overlayfs")
+        );
+        assert!(!capabilities.has_runtime("runc"));
+        assert!(capabilities.supports_cdi());
+
+        let windows = DaemonCapabilities::new(
+            Version {
+                api_version: Some(String::from("1.46")),
+                os: Some(String::from("windows")),
+                ..Default::default()
+            },
+            SystemInfo::default(),
+        );
+        assert!(!windows.supports_buildkit());
+        assert_eq!(windows.cgroup_version(), None);
+        assert_eq!(windows.snapshotter_in_use(), None);
+    }
+
+    #[test]
+    fn test_ping_info() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("Api-Version", "1.46".parse().unwrap());
+        headers.insert("Ostype", "linux".parse().unwrap());
+        headers.insert("Docker-Experimental", "true".parse().unwrap());
+        headers.insert("Builder-Version", "2".parse().unwrap());
+        headers.insert("Swarm", "active/manager".parse().unwrap());
+
+        assert_eq!(
+            PingInfo::from(&headers),
+            PingInfo {
+                api_version: Some(String::from("1.46")),
+                os_type: Some(String::from("linux")),
+                experimental: true,
+                builder_version: Some(String::from("2")),
+                swarm: Some(String::from("active/manager")),
+            }
+        );
+        assert_eq!(PingInfo::from(&http::HeaderMap::new()), PingInfo::default());
+    }
+
+    #[test]
+    fn test_security_option() {
+        assert_eq!(
+            SecurityOption::from("name=seccomp,profile=builtin"),
+            SecurityOption::Seccomp {
+                profile: Some(String::from("builtin"))
+            }
+        );
+        assert_eq!(
+            SecurityOption::from("seccomp"),
+            SecurityOption::Seccomp { profile: None }
+        );
+        assert_eq!(
+            SecurityOption::from("name=apparmor"),
+            SecurityOption::AppArmor
+        );
+        assert_eq!(
+            SecurityOption::from("name=rootless"),
+            SecurityOption::Rootless
+        );
+        assert_eq!(
+            SecurityOption::from("name=cgroupns"),
+            SecurityOption::CgroupNamespaces
+        );
+
+        let mut details = HashMap::new();
+        details.insert(String::from("mode"), String::from("strict"));
+        assert_eq!(
+            SecurityOption::from("name=landlock,mode=strict"),
+            SecurityOption::Other {
+                name: String::from("landlock"),
+                details
+            }
+        );
+    }
+
+    #[test]
+    fn test_image_store() {
+        let info: SystemInfo = serde_json::from_str(
+            r#"{"Driver":"overlayfs","DriverStatus":[["driver-type","io.containerd.snapshotter.v1"]]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ImageStore::from(&info),
+            ImageStore::Containerd {
+                snapshotter: String::from("overlayfs")
+            }
+        );
+
+        let info: SystemInfo = serde_json::from_str(
+            r#"{"Driver":"overlay2","DriverStatus":[["Backing Filesystem","extfs"],["Supports d_type","true"]]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ImageStore::from(&info),
+            ImageStore::GraphDriver {
+                driver: String::from("overlay2")
+            }
+        );
+    }
+
+    #[test]
+    fn test_event_cursor() {
+        let event = |time_nano: i64, id: &str| EventMessage {
+            time_nano: Some(time_nano),
+            action: Some(String::from(id)),
+            ..Default::default()
+        };
+
+        let mut cursor = EventCursor::new();
+        assert!(cursor.advance(&event(100, "a")));
+        assert!(cursor.advance(&event(200, "b")));
+        assert!(cursor.advance(&event(200, "c")));
+        assert_eq!(cursor.time_nano(), Some(200));
+
+        // the reopened stream replays the events at the inclusive `since` boundary
+        let json = serde_json::to_string(&cursor).unwrap();
+        let mut resumed: EventCursor = serde_json::from_str(&json).unwrap();
+        assert!(!resumed.advance(&event(200, "b")));
+        assert!(!resumed.advance(&event(200, "c")));
+        assert!(resumed.advance(&event(200, "d")));
+        assert!(resumed.advance(&event(300, "e")));
+        assert!(!resumed.advance(&event(250, "f")));
+        assert_eq!(resumed.time_nano(), Some(300));
+    }
+
+    #[test]
+    fn test_cursor_query() {
+        let mut filters = HashMap::new();
+        filters.insert("type", vec!["container"]);
+        let options = EventsOptions {
+            filters,
+            ..Default::default()
+        };
+
+        let query =
+            cursor_query(&EventCursor::at(1_700_000_000_000_000_042), Some(options)).unwrap();
+        assert!(query.contains(&(String::from("since"), String::from("1700000000.000000042"))));
+        assert_eq!(query.iter().filter(|(key, _)| key == "since").count(), 1);
+        assert!(query.contains(&(
+            String::from("filters"),
+            String::from(r#"{"type":["container"]}"#)
+        )));
+
+        assert!(
+            cursor_query(&EventCursor::new(), None::<EventsOptions<String>>)
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,516 @@
+//! Temporary resources: containers, networks and volumes that are removed once they are no longer
+//! needed.
+//!
+//! Every resource created through this module carries the [`TEMP_OWNER_LABEL`] label, keyed on
+//! the current process id. Resources are removed by calling `close`, or on a best-effort basis
+//! in a background task when the guard is dropped. Resources that outlived their process (e.g.
+//! after a panic or a killed test run) can be removed with
+//! [`sweep_temp_resources`](Docker::sweep_temp_resources()).
+
+use log::debug;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Docker;
+#[cfg(feature = "container")]
+use crate::container::{
+    Config, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions,
+};
+use crate::docker::spawn_cleanup;
+use crate::errors::{Error, ErrorKind};
+use crate::models::*;
+use crate::network::{CreateNetworkOptions, ListNetworksOptions};
+use crate::volume::{CreateVolumeOptions, ListVolumesOptions, RemoveVolumeOptions};
+
+/// Label applied to all temporary resources, with the owning process id as its value.
+pub const TEMP_OWNER_LABEL: &str = "com.github.fussybeaver.bollard.temp-owner";
+
+/// The ownership label value used by this process.
+pub fn temp_owner() -> String {
+    std::process::id().to_string()
+}
+
+fn owner_labels<T>(labels: HashMap<T, T>) -> HashMap<String, String>
+where
+    T: Into<String> + Eq + Hash,
+{
+    let mut labels: HashMap<String, String> = labels
+        .into_iter()
+        .map(|(k, v)| (k.into(), v.into()))
+        .collect();
+    labels.insert(String::from(TEMP_OWNER_LABEL), temp_owner());
+    labels
+}
+
+/// A network that is removed when closed or dropped.
+///
+/// Construct a `TempNetwork` using [`create_temp_network`](Docker::create_temp_network()).
+#[derive(Debug)]
+pub struct TempNetwork {
+    docker: Docker,
+    id: String,
+    name: String,
+    closed: bool,
+}
+
+impl TempNetwork {
+    /// The network's ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The network's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Remove the network, waiting for the daemon to acknowledge the removal.
+    pub async fn close(mut self) -> Result<(), Error> {
+        self.closed = true;
+        match self.docker.remove_network(&self.id).await {
+            Err(e) if e.kind() != Some(ErrorKind::NotFound) => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for TempNetwork {
+    fn drop(&mut self) {
+        if !self.closed {
+            let docker = self.docker.clone();
+            let id = self.id.clone();
+            spawn_cleanup("temporary network", self.name.clone(), async move {
+                docker.remove_network(&id).await
+            });
+        }
+    }
+}
+
+/// A volume that is removed when closed or dropped.
+///
+/// Construct a `TempVolume` using [`create_temp_volume`](Docker::create_temp_volume()).
+#[derive(Debug)]
+pub struct TempVolume {
+    docker: Docker,
+    volume: Volume,
+    closed: bool,
+}
+
+impl TempVolume {
+    /// The volume's name.
+    pub fn name(&self) -> &str {
+        &self.volume.name
+    }
+
+    /// The volume, as returned by the daemon on creation.
+    pub fn volume(&self) -> &Volume {
+        &self.volume
+    }
+
+    /// Remove the volume, waiting for the daemon to acknowledge the removal.
+    pub async fn close(mut self) -> Result<(), Error> {
+        self.closed = true;
+        match self
+            .docker
+            .remove_volume(&self.volume.name, Some(RemoveVolumeOptions { force: true }))
+            .await
+        {
+            Err(e) if e.kind() != Some(ErrorKind::NotFound) => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for TempVolume {
+    fn drop(&mut self) {
+        if !self.closed {
+            let docker = self.docker.clone();
+            let name = self.volume.name.clone();
+            spawn_cleanup("temporary volume", name.clone(), async move {
+                docker
+                    .remove_volume(&name, Some(RemoveVolumeOptions { force: true }))
+                    .await
+            });
+        }
+    }
+}
+
+/// A container that is force-removed when cleaned up or dropped, together with the networks it
+/// owns and, optionally, its anonymous volumes.
+///
+/// Construct a `ScopedContainer` using
+/// [`create_scoped_container`](Docker::create_scoped_container()).
+#[cfg(feature = "container")]
+#[derive(Debug)]
+pub struct ScopedContainer {
+    docker: Docker,
+    id: String,
+    networks: Vec<TempNetwork>,
+    remove_volumes: bool,
+    closed: bool,
+}
+
+#[cfg(feature = "container")]
+impl ScopedContainer {
+    /// The container's ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Also remove the container's anonymous volumes on cleanup.
+    pub fn remove_volumes(&mut self, remove_volumes: bool) {
+        self.remove_volumes = remove_volumes;
+    }
+
+    /// Remove the network after the container on cleanup, so the network is not removed while
+    /// the container is still connected to it.
+    pub fn own_network(&mut self, network: TempNetwork) {
+        self.networks.push(network);
+    }
+
+    fn remove(&mut self) -> impl std::future::Future<Output = Result<(), Error>> + Send + 'static {
+        self.closed = true;
+        let docker = self.docker.clone();
+        let id = self.id.clone();
+        let networks = std::mem::take(&mut self.networks);
+        let v = self.remove_volumes;
+        async move {
+            match docker
+                .remove_container(
+                    &id,
+                    Some(RemoveContainerOptions {
+                        force: true,
+                        v,
+                        ..Default::default()
+                    }),
+                )
+                .await
+            {
+                Err(e) if e.kind() != Some(ErrorKind::NotFound) => return Err(e),
+                _ => (),
+            }
+            for network in networks {
+                network.close().await?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Force-remove the container and the networks it owns, waiting for the daemon to
+    /// acknowledge the removal.
+    pub async fn cleanup(mut self) -> Result<(), Error> {
+        self.remove().await
+    }
+}
+
+#[cfg(feature = "container")]
+impl Drop for ScopedContainer {
+    fn drop(&mut self) {
+        if !self.closed {
+            let id = self.id.clone();
+            spawn_cleanup("temporary container", id, self.remove());
+        }
+    }
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Create Scoped Container
+    ///
+    /// Create a new container, labelled with the [`TEMP_OWNER_LABEL`], which is force-removed
+    /// once the returned guard is cleaned up or dropped.
+    ///
+    /// Removal on drop is spawned on the current tokio runtime, so it does not complete if the
+    /// runtime shuts down first, e.g. when a `#[tokio::test]` panics. Use
+    /// [`sweep_temp_resources`](Docker::sweep_temp_resources()) to remove such leftovers.
+    ///
+    /// # Arguments
+    ///
+    ///  - Optional [Create Container Options](CreateContainerOptions) struct.
+    ///  - Container [Config](Config) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Scoped Container](ScopedContainer) guard, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::container::{Config, CreateContainerOptions};
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config = Config {
+    ///     image: Some(String::from("alpine")),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// async move {
+    ///     let mut container = docker
+    ///         .create_scoped_container(None::<bollard::container::CreateContainerOptions<String>>, config)
+    ///         .await
+    ///         .unwrap();
+    ///     container.remove_volumes(true);
+    ///     // ...
+    ///     container.cleanup().await.unwrap();
+    /// };
+    /// ```
+    #[cfg(feature = "container")]
+    pub async fn create_scoped_container<T>(
+        &self,
+        options: Option<CreateContainerOptions<T>>,
+        mut config: Config<String>,
+    ) -> Result<ScopedContainer, Error>
+    where
+        T: Into<String> + serde::ser::Serialize,
+    {
+        config.labels = Some(owner_labels(config.labels.unwrap_or_default()));
+
+        let response = self.create_container(options, config).await?;
+
+        Ok(ScopedContainer {
+            docker: self.clone(),
+            id: response.id,
+            networks: Vec::new(),
+            remove_volumes: false,
+            closed: false,
+        })
+    }
+
+    /// ---
+    ///
+    /// # Create Temporary Network
+    ///
+    /// Create a new network, labelled with the [`TEMP_OWNER_LABEL`], which is removed once the
+    /// returned guard is closed or dropped.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Create Network Options](CreateNetworkOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Temp Network](TempNetwork) guard, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::network::CreateNetworkOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config = CreateNetworkOptions {
+    ///     name: "scratch",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// async move {
+    ///     let network = docker.create_temp_network(config).await.unwrap();
+    ///     // ...
+    ///     network.close().await.unwrap();
+    /// };
+    /// ```
+    pub async fn create_temp_network<T>(
+        &self,
+        config: CreateNetworkOptions<T>,
+    ) -> Result<TempNetwork, Error>
+    where
+        T: Into<String> + Eq + Hash + serde::ser::Serialize,
+    {
+        let name: String = config.name.into();
+        let config = CreateNetworkOptions {
+            name: name.clone(),
+            check_duplicate: config.check_duplicate,
+            driver: config.driver.into(),
+            internal: config.internal,
+            attachable: config.attachable,
+            ingress: config.ingress,
+            ipam: config.ipam,
+            enable_ipv6: config.enable_ipv6,
+            options: config
+                .options
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+            labels: owner_labels(config.labels),
+        };
+
+        let response = self.create_network(config).await?;
+
+        Ok(TempNetwork {
+            docker: self.clone(),
+            id: response.id,
+            name,
+            closed: false,
+        })
+    }
+
+    /// ---
+    ///
+    /// # Create Temporary Volume
+    ///
+    /// Create a new volume, labelled with the [`TEMP_OWNER_LABEL`], which is removed once the
+    /// returned guard is closed or dropped.
+    ///
+    /// # Arguments
+    ///
+    ///  - [Create Volume Options](CreateVolumeOptions) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Temp Volume](TempVolume) guard, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::volume::CreateVolumeOptions;
+    ///
+    /// use std::default::Default;
+    ///
+    /// let config = CreateVolumeOptions {
+    ///     name: "scratch",
+    ///     ..Default::default()
+    /// };
+    ///
+    /// async move {
+    ///     let volume = docker.create_temp_volume(config).await.unwrap();
+    ///     // ...
+    ///     volume.close().await.unwrap();
+    /// };
+    /// ```
+    pub async fn create_temp_volume<T>(
+        &self,
+        config: CreateVolumeOptions<T>,
+    ) -> Result<TempVolume, Error>
+    where
+        T: Into<String> + Eq + Hash + serde::ser::Serialize,
+    {
+        let config = CreateVolumeOptions {
+            name: config.name.into(),
+            driver: config.driver.into(),
+            driver_opts: config
+                .driver_opts
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+            labels: owner_labels(config.labels),
+        };
+
+        let volume = self.create_volume(config).await?;
+
+        Ok(TempVolume {
+            docker: self.clone(),
+            volume,
+            closed: false,
+        })
+    }
+
+    /// ---
+    ///
+    /// # Sweep Temporary Resources
+    ///
+    /// Remove all containers, networks and volumes carrying the [`TEMP_OWNER_LABEL`]. Call this
+    /// before the process exits, or at startup to clean up resources orphaned by earlier runs.
+    ///
+    /// Liveness of the owning process is not checked, as the owner is a process id on the host
+    /// that created the resource, which need not be the host running this call. Only sweep the
+    /// owners known to have exited: passing `None` also removes resources still in use by other
+    /// processes sharing the daemon, such as concurrently running test binaries.
+    ///
+    /// # Arguments
+    ///
+    ///  - The owner whose resources to remove, e.g. [`temp_owner()`] for the current process.
+    ///    If `None`, the resources of all owners are removed, including those of live processes.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::temp::temp_owner;
+    ///
+    /// docker.sweep_temp_resources(Some(&temp_owner()));
+    /// ```
+    pub async fn sweep_temp_resources(&self, owner: Option<&str>) -> Result<(), Error> {
+        let label = match owner {
+            Some(owner) => format!("{TEMP_OWNER_LABEL}={owner}"),
+            None => String::from(TEMP_OWNER_LABEL),
+        };
+
+        let mut filters = HashMap::new();
+        filters.insert("label", vec![label.as_str()]);
+
+        #[cfg(feature = "container")]
+        {
+            let containers = self
+                .list_containers(Some(ListContainersOptions {
+                    all: true,
+                    filters: filters.clone(),
+                    ..Default::default()
+                }))
+                .await?;
+            for container in containers {
+                if let Some(id) = container.id {
+                    match self
+                        .remove_container(
+                            &id,
+                            Some(RemoveContainerOptions {
+                                force: true,
+                                ..Default::default()
+                            }),
+                        )
+                        .await
+                    {
+                        Err(e) if e.kind() != Some(ErrorKind::NotFound) => return Err(e),
+                        _ => debug!("swept temporary container {id}"),
+                    }
+                }
+            }
+        }
+
+        let networks = self
+            .list_networks(Some(ListNetworksOptions {
+                filters: filters.clone(),
+            }))
+            .await?;
+        for network in networks {
+            if let Some(id) = network.id {
+                match self.remove_network(&id).await {
+                    Err(e) if e.kind() != Some(ErrorKind::NotFound) => return Err(e),
+                    _ => debug!("swept temporary network {id}"),
+                }
+            }
+        }
+
+        let volumes = self
+            .list_volumes(Some(ListVolumesOptions { filters }))
+            .await?;
+        for volume in volumes.volumes.unwrap_or_default() {
+            match self
+                .remove_volume(&volume.name, Some(RemoveVolumeOptions { force: true }))
+                .await
+            {
+                Err(e) if e.kind() != Some(ErrorKind::NotFound) => return Err(e),
+                _ => debug!("swept temporary volume {}", volume.name),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+//! Terminal: propagate the size of the local terminal to the TTY of an attached container or
+//! exec, the way `docker run -it` and `docker exec -it` do.
+//!
+//! [`propagate_terminal_resize`](Docker::propagate_terminal_resize()) resizes the TTY to the
+//! size of the terminal on standard output, then again on every `SIGWINCH`, until a resize
+//! fails, e.g. because the session ended.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use bollard::Docker;
+//! # let docker = Docker::connect_with_http_defaults().unwrap();
+//! use bollard::terminal::TtySession;
+//!
+//! async move {
+//!     let resizes = tokio::spawn({
+//!         let docker = docker.clone();
+//!         async move {
+//!             docker
+//!                 .propagate_terminal_resize(TtySession::Exec(String::from("exec-id")))
+//!                 .await
+//!         }
+//!     });
+//!
+//!     // ... pipe the attached input and output ...
+//!
+//!     resizes.abort();
+//! };
+//! ```
+
+use tokio::signal::unix::{signal, SignalKind};
+
+use crate::container::ResizeContainerTtyOptions;
+use crate::errors::Error;
+use crate::exec::ResizeExecOptions;
+use crate::Docker;
+
+/// A TTY session to resize.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TtySession {
+    /// The TTY of a container started with `tty` and attached, by container name or ID.
+    Container(String),
+    /// The TTY of an exec started with `tty`, by exec ID.
+    Exec(String),
+}
+
+/// Size of a terminal in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TerminalSize {
+    /// Number of columns.
+    pub width: u16,
+    /// Number of rows.
+    pub height: u16,
+}
+
+/// The size of the terminal on standard output, or `None` when standard output is not a
+/// terminal.
+pub fn terminal_size() -> Option<TerminalSize> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+
+    // SAFETY: `TIOCGWINSZ` only writes a `winsize` through the pointer, which outlives the call.
+    let result = unsafe {
+        libc::ioctl(
+            libc::STDOUT_FILENO,
+            libc::TIOCGWINSZ,
+            std::ptr::addr_of_mut!(size),
+        )
+    };
+
+    (result == 0 && size.ws_col > 0 && size.ws_row > 0).then_some(TerminalSize {
+        width: size.ws_col,
+        height: size.ws_row,
+    })
+}
+
+impl Docker {
+    /// ---
+    ///
+    /// # Resize TTY
+    ///
+    /// Resize the TTY of a container or an exec, through the [Resize Container Tty
+    /// API](Docker::resize_container_tty()) or the [Resize Exec API](Docker::resize_exec()).
+    ///
+    /// # Arguments
+    ///
+    ///  - [TTY Session](TtySession) to resize.
+    ///  - [Terminal Size](TerminalSize) struct.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::terminal::{TerminalSize, TtySession};
+    ///
+    /// let session = TtySession::Container(String::from("hello-world"));
+    ///
+    /// async move {
+    ///     docker.resize_tty(&session, TerminalSize { width: 80, height: 24 }).await
+    /// };
+    /// ```
+    pub async fn resize_tty(&self, session: &TtySession, size: TerminalSize) -> Result<(), Error> {
+        match session {
+            TtySession::Container(container_name) => {
+                self.resize_container_tty(
+                    container_name,
+                    ResizeContainerTtyOptions {
+                        width: size.width,
+                        height: size.height,
+                    },
+                )
+                .await
+            }
+            TtySession::Exec(exec_id) => {
+                self.resize_exec(
+                    exec_id,
+                    ResizeExecOptions {
+                        height: size.height,
+                        width: size.width,
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    /// ---
+    ///
+    /// # Propagate Terminal Resize
+    ///
+    /// Resize the TTY of a container or an exec to the size of the local terminal, then again
+    /// whenever the terminal receives `SIGWINCH`. Nothing is resized while standard output is
+    /// not a terminal.
+    ///
+    /// Spawn the returned future next to the attached session and abort it once the session
+    /// ends, or use a client [with cancellation](Docker::with_cancellation()).
+    ///
+    /// # Arguments
+    ///
+    ///  - [TTY Session](TtySession) to resize.
+    ///
+    /// # Returns
+    ///
+    ///  - The error of the first resize that fails, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use bollard::terminal::TtySession;
+    ///
+    /// docker.propagate_terminal_resize(TtySession::Exec(String::from("exec-id")));
+    /// ```
+    pub async fn propagate_terminal_resize(&self, session: TtySession) -> Result<(), Error> {
+        // listen before the first resize, so that no change of size is missed
+        let mut window_changes = signal(SignalKind::window_change())?;
+
+        loop {
+            if let Some(size) = terminal_size() {
+                self.resize_tty(&session, size).await?;
+            }
+            if window_changes.recv().await.is_none() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yup_hyper_mock::HostToReplyConnector;
+
+    use super::{TerminalSize, TtySession};
+    use crate::{Docker, API_DEFAULT_VERSION};
+
+    #[tokio::test]
+    async fn test_resize_tty() {
+        let mut connector = HostToReplyConnector::default();
+        connector.m.insert(
+            String::from("http://127.0.0.1"),
+            String::from("HTTP/1.1 200 OK\r\nServer:mock1\r\nContent-Length:0\r\n\r\n"),
+        );
+
+        let docker =
+            Docker::connect_with_mock(connector, "127.0.0.1".to_string(), 5, API_DEFAULT_VERSION)
+                .unwrap();
+
+        let size = TerminalSize {
+            width: 80,
+            height: 24,
+        };
+        docker
+            .resize_tty(&TtySession::Container(String::from("hello-world")), size)
+            .await
+            .unwrap();
+        docker
+            .resize_tty(&TtySession::Exec(String::from("exec-id")), size)
+            .await
+            .unwrap();
+    }
+}
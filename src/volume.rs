@@ -4,6 +4,7 @@ use bytes::Bytes;
 use http::request::Builder;
 use http_body_util::Full;
 use hyper::Method;
+use serde::de::DeserializeOwned;
 use serde_derive::{Deserialize, Serialize};
 
 use std::cmp::Eq;
@@ -11,10 +12,69 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 use super::Docker;
+#[cfg(all(feature = "container", feature = "image"))]
+use crate::container::{
+    Config, DownloadFromContainerOptions, RemoveContainerOptions, UploadToContainerOptions,
+};
+#[cfg(all(feature = "container", feature = "image"))]
+use crate::docker::spawn_cleanup;
 use crate::docker::BodyType;
 use crate::errors::Error;
 use crate::models::*;
 
+/// Image used for the helper container of the [Backup Volume](Docker::backup_volume()) and
+/// [Restore Volume](Docker::restore_volume()) helpers, unless configured otherwise.
+pub const VOLUME_HELPER_IMAGE: &str = "busybox:latest";
+
+/// Path at which the volume is mounted inside the helper container.
+#[cfg(all(feature = "container", feature = "image"))]
+const VOLUME_HELPER_PATH: &str = "/volume";
+
+/// Helper container mounting a volume, force-removed in a background task if dropped before it
+/// is closed, e.g. when the backup or restore future is cancelled.
+#[cfg(all(feature = "container", feature = "image"))]
+struct VolumeHelper {
+    docker: Docker,
+    id: String,
+    closed: bool,
+}
+
+#[cfg(all(feature = "container", feature = "image"))]
+impl VolumeHelper {
+    fn remove(&mut self) -> impl std::future::Future<Output = Result<(), Error>> + Send + 'static {
+        self.closed = true;
+        let docker = self.docker.clone();
+        let id = self.id.clone();
+        async move {
+            docker
+                .remove_container(
+                    &id,
+                    Some(RemoveContainerOptions {
+                        force: true,
+                        ..Default::default()
+                    }),
+                )
+                .await
+        }
+    }
+
+    /// Remove the helper container, returning the result of the operation it was used for, or
+    /// else the result of the removal.
+    async fn close(mut self, result: Result<(), Error>) -> Result<(), Error> {
+        let removed = self.remove().await;
+        result.and(removed)
+    }
+}
+
+#[cfg(all(feature = "container", feature = "image"))]
+impl Drop for VolumeHelper {
+    fn drop(&mut self) {
+        if !self.closed {
+            spawn_cleanup("volume helper container", self.id.clone(), self.remove());
+        }
+    }
+}
+
 /// Parameters used in the [List Volume API](Docker::list_volumes())
 #[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct ListVolumesOptions<T>
@@ -95,6 +155,63 @@ where
     pub filters: HashMap<T, Vec<T>>,
 }
 
+/// Parameters used in the [Backup Volume](Docker::backup_volume()) and [Restore
+/// Volume](Docker::restore_volume()) helpers
+///
+/// ## Examples
+///
+/// ```rust
+/// use bollard::volume::VolumeArchiveOptions;
+///
+/// VolumeArchiveOptions {
+///     image: "alpine:3",
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeArchiveOptions<T>
+where
+    T: Into<String>,
+{
+    /// Image of the helper container that mounts the volume. The container is never started, so
+    /// any image will do. It is pulled if missing. Defaults to [`VOLUME_HELPER_IMAGE`].
+    pub image: T,
+}
+
+/// A container referencing a volume, reported by the [Volume Usage API](Docker::volume_usage()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeReference {
+    /// The container's ID.
+    pub id: String,
+    /// The container's name, without the leading `/`.
+    pub name: Option<String>,
+    /// The container's state, e.g. `running` or `exited`.
+    pub state: Option<String>,
+    /// The path the volume is mounted at inside the container.
+    pub destination: Option<String>,
+    /// Whether the volume is mounted read-write.
+    pub read_write: Option<bool>,
+}
+
+/// The space a volume uses and the containers referencing it, returned by the [Volume Usage
+/// API](Docker::volume_usage()).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeUsage {
+    /// The inspected volume.
+    pub volume: Volume,
+    /// Disk space used by the volume in bytes, if its driver reports it, as the `local` driver
+    /// does.
+    pub size: Option<i64>,
+    /// The containers referencing the volume, whether they are running or not.
+    pub containers: Vec<VolumeReference>,
+}
+
+impl VolumeUsage {
+    /// Whether a container references the volume, so that removing it fails without `force`.
+    pub fn is_in_use(&self) -> bool {
+        !self.containers.is_empty()
+    }
+}
+
 impl Docker {
     /// ---
     ///
@@ -189,7 +306,7 @@ impl Docker {
             url,
             Builder::new().method(Method::POST),
             None::<String>,
-            Docker::serialize_payload(Some(config)),
+            self.serialize_payload(Some(config)),
         );
 
         self.process_into_value(req).await
@@ -217,6 +334,44 @@ impl Docker {
     /// docker.inspect_volume("my_volume_name");
     /// ```
     pub async fn inspect_volume(&self, volume_name: &str) -> Result<Volume, Error> {
+        self.inspect_volume_as(volume_name).await
+    }
+
+    /// ---
+    ///
+    /// # Inspect Volume As
+    ///
+    /// Inspect a volume, like the [Inspect Volume API](Docker::inspect_volume()), deserialized into a type of the
+    /// caller, e.g. a slim struct with only the fields it needs.
+    ///
+    /// # Arguments
+    ///
+    ///  - Volume name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - The deserialized response, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Volume {
+    ///     name: String,
+    ///     mountpoint: String,
+    /// }
+    ///
+    /// docker.inspect_volume_as::<Volume>("my_volume_name");
+    /// ```
+    pub async fn inspect_volume_as<R>(&self, volume_name: &str) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+    {
         let url = format!("/volumes/{volume_name}");
 
         let req = self.build_request(
@@ -327,4 +482,251 @@ impl Docker {
 
         self.process_into_value(req).await
     }
+
+    /// ---
+    ///
+    /// # Backup Volume
+    ///
+    /// Stream a tar archive of the contents of a volume into a writer. The contents are read
+    /// through a temporary helper container mounting the volume, which is removed afterwards,
+    /// or in a background task if the returned future is dropped. The helper image is pulled if
+    /// missing.
+    ///
+    /// # Arguments
+    ///
+    ///  - Volume name as a string slice.
+    ///  - An optional [Volume Archive Options](VolumeArchiveOptions) struct.
+    ///  - The writer receiving the tar archive.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::volume::VolumeArchiveOptions;
+    ///
+    /// async move {
+    ///     let mut archive = Vec::new();
+    ///     docker
+    ///         .backup_volume("my_volume", None::<VolumeArchiveOptions<String>>, &mut archive)
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    #[cfg(all(feature = "container", feature = "image"))]
+    pub async fn backup_volume<T, W>(
+        &self,
+        volume_name: &str,
+        options: Option<VolumeArchiveOptions<T>>,
+        mut writer: W,
+    ) -> Result<(), Error>
+    where
+        T: Into<String>,
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use futures_util::StreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        let helper = self.create_volume_helper(volume_name, options).await?;
+
+        let result = async {
+            let mut stream = self.download_from_container(
+                &helper.id,
+                Some(DownloadFromContainerOptions {
+                    path: format!("{VOLUME_HELPER_PATH}/."),
+                }),
+            );
+            while let Some(chunk) = stream.next().await {
+                writer.write_all(&chunk?).await?;
+            }
+            writer.flush().await?;
+            Ok(())
+        }
+        .await;
+
+        helper.close(result).await
+    }
+
+    /// ---
+    ///
+    /// # Restore Volume
+    ///
+    /// Extract a tar archive, read from a reader, into a volume. The contents are written through
+    /// a temporary helper container mounting the volume, which is removed afterwards, or in a
+    /// background task if the returned future is dropped. The helper image is pulled if missing.
+    /// Archives produced by the [Backup Volume](Docker::backup_volume()) helper can be restored
+    /// as is.
+    ///
+    /// # Arguments
+    ///
+    ///  - Volume name as a string slice.
+    ///  - An optional [Volume Archive Options](VolumeArchiveOptions) struct.
+    ///  - The reader providing the tar archive.
+    ///
+    /// # Returns
+    ///
+    ///  - unit type `()`, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    ///
+    /// use bollard::volume::VolumeArchiveOptions;
+    ///
+    /// use tokio::fs::File;
+    ///
+    /// async move {
+    ///     let archive = File::open("my_volume.tar").await.unwrap();
+    ///     docker
+    ///         .restore_volume("my_volume", None::<VolumeArchiveOptions<String>>, archive)
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    #[cfg(all(feature = "container", feature = "image"))]
+    pub async fn restore_volume<T, R>(
+        &self,
+        volume_name: &str,
+        options: Option<VolumeArchiveOptions<T>>,
+        reader: R,
+    ) -> Result<(), Error>
+    where
+        T: Into<String>,
+        R: tokio::io::AsyncRead + Send + 'static,
+    {
+        let helper = self.create_volume_helper(volume_name, options).await?;
+
+        let result = self
+            .upload_to_container(
+                &helper.id,
+                Some(UploadToContainerOptions {
+                    path: VOLUME_HELPER_PATH,
+                    ..Default::default()
+                }),
+                crate::body_try_stream(tokio_util::io::ReaderStream::new(reader)),
+            )
+            .await;
+
+        helper.close(result).await
+    }
+
+    /// ---
+    ///
+    /// # Volume Usage
+    ///
+    /// Report the disk space a volume uses and the containers referencing it, running or not,
+    /// e.g. to check a volume can be removed safely. The space is taken from the [Data Usage
+    /// API](Docker::df()), which computes the size of every volume and container, so the call
+    /// can take a while on a busy daemon.
+    ///
+    /// # Arguments
+    ///
+    ///  - Volume name as a string slice.
+    ///
+    /// # Returns
+    ///
+    ///  - A [Volume Usage](VolumeUsage) struct, wrapped in a Future.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bollard::Docker;
+    /// # let docker = Docker::connect_with_http_defaults().unwrap();
+    /// async move {
+    ///     let usage = docker.volume_usage("my_volume_name").await.unwrap();
+    ///     for container in &usage.containers {
+    ///         println!("used by {:?} at {:?}", container.name, container.destination);
+    ///     }
+    /// };
+    /// ```
+    #[cfg(feature = "system")]
+    pub async fn volume_usage(&self, volume_name: &str) -> Result<VolumeUsage, Error> {
+        let volume = self.inspect_volume(volume_name).await?;
+        let df = self.df().await?;
+
+        let size = df
+            .volumes
+            .unwrap_or_default()
+            .into_iter()
+            .find(|v| v.name == volume.name)
+            .and_then(|v| v.usage_data)
+            .map(|usage| usage.size)
+            .filter(|size| *size >= 0);
+
+        let containers = df
+            .containers
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|container| {
+                let mount = container
+                    .mounts
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find(|mount| {
+                        mount.typ == Some(MountPointTypeEnum::VOLUME)
+                            && mount.name.as_deref() == Some(&volume.name)
+                    })?;
+                Some(VolumeReference {
+                    id: container.id?,
+                    name: container
+                        .names
+                        .and_then(|names| names.into_iter().next())
+                        .map(|name| String::from(name.trim_start_matches('/'))),
+                    state: container.state,
+                    destination: mount.destination,
+                    read_write: mount.rw,
+                })
+            })
+            .collect();
+
+        Ok(VolumeUsage {
+            volume,
+            size,
+            containers,
+        })
+    }
+
+    #[cfg(all(feature = "container", feature = "image"))]
+    async fn create_volume_helper<T>(
+        &self,
+        volume_name: &str,
+        options: Option<VolumeArchiveOptions<T>>,
+    ) -> Result<VolumeHelper, Error>
+    where
+        T: Into<String>,
+    {
+        let image = options
+            .map(|options| options.image.into())
+            .unwrap_or_else(|| String::from(VOLUME_HELPER_IMAGE));
+
+        let config = Config {
+            image: Some(image),
+            network_disabled: Some(true),
+            host_config: Some(HostConfig {
+                mounts: Some(vec![Mount {
+                    target: Some(String::from(VOLUME_HELPER_PATH)),
+                    source: Some(String::from(volume_name)),
+                    typ: Some(MountTypeEnum::VOLUME),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let response = self.create_container_with_pull(None, config, None).await?;
+
+        Ok(VolumeHelper {
+            docker: self.clone(),
+            id: response.id,
+            closed: false,
+        })
+    }
 }
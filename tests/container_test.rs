@@ -1,5 +1,8 @@
 #![type_length_limit = "2097152"]
 
+use bollard::container::changes::{ChangeKind, ChangesFilter, ContainerChange};
+use bollard::container::ports::{ContainerPort, PortBindings, Protocol};
+use bollard::container::update::UpdateContainerBuilder;
 use bollard::container::{
     AttachContainerOptions, AttachContainerResults, Config, CreateContainerOptions,
     DownloadFromContainerOptions, InspectContainerOptions, KillContainerOptions,
@@ -165,10 +168,17 @@ async fn top_processes_test(docker: Docker) -> Result<(), Error> {
     create_daemon(&docker, "integration_test_top_processes").await?;
 
     let result = &docker
-        .top_processes("integration_test_top_processes", top_options)
+        .top_processes("integration_test_top_processes", top_options.clone())
         .await?;
 
     assert_ne!(result.titles.as_ref().unwrap()[0].len(), 0);
+
+    let entries = &docker
+        .top_process_entries("integration_test_top_processes", top_options)
+        .await?;
+
+    assert!(!entries.is_empty());
+    assert!(entries.iter().all(|entry| entry.pid.is_some()));
     kill_container(&docker, "integration_test_top_processes").await?;
 
     Ok(())
@@ -243,6 +253,67 @@ async fn container_changes_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+#[cfg(unix)]
+async fn container_changed_files_test(docker: Docker) -> Result<(), Error> {
+    create_shell_daemon(&docker, "integration_test_container_changed_files").await?;
+
+    let readme = r#"Hello from Bollard!"#.as_bytes();
+
+    let mut header = tar::Header::new_gnu();
+    header.set_path("readme.txt").unwrap();
+    header.set_size(readme.len() as u64);
+    header.set_mode(0o744);
+    header.set_cksum();
+    let mut tar = tar::Builder::new(Vec::new());
+    tar.append(&header, readme).unwrap();
+
+    docker
+        .upload_to_container(
+            "integration_test_container_changed_files",
+            Some(UploadToContainerOptions {
+                path: "/tmp",
+                ..Default::default()
+            }),
+            body_full(tar.into_inner().unwrap().into()),
+        )
+        .await?;
+
+    let filter = ChangesFilter::new()
+        .include("/tmp/**")
+        .kind(ChangeKind::Added);
+
+    let changes = docker
+        .container_changes_filtered("integration_test_container_changed_files", filter.clone())
+        .await?;
+
+    assert!(changes.contains(&ContainerChange {
+        path: String::from("/tmp/readme.txt"),
+        kind: ChangeKind::Added,
+    }));
+
+    let files = docker
+        .container_changed_files("integration_test_container_changed_files", filter)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let file = files
+        .iter()
+        .find(|file| file.change.path == "/tmp/readme.txt")
+        .unwrap();
+
+    use std::io::Read;
+    let mut archive: tar::Archive<&[u8]> = tar::Archive::new(&file.archive[..]);
+    let mut entry = archive.entries().unwrap().next().unwrap().unwrap();
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents).unwrap();
+
+    assert_eq!("Hello from Bollard!", contents);
+
+    kill_container(&docker, "integration_test_container_changed_files").await?;
+
+    Ok(())
+}
+
 async fn stats_test(docker: Docker) -> Result<(), Error> {
     create_daemon(&docker, "integration_test_stats").await?;
 
@@ -260,6 +331,10 @@ async fn stats_test(docker: Docker) -> Result<(), Error> {
     let value = vec.first();
 
     assert_eq!(value.unwrap().name, "/integration_test_stats".to_string());
+
+    let value = docker.stats_once("integration_test_stats").await?;
+
+    assert_eq!(value.name, "/integration_test_stats".to_string());
     kill_container(&docker, "integration_test_stats")
         .await
         .unwrap_or(());
@@ -406,6 +481,24 @@ async fn update_container_test(docker: Docker) -> Result<(), Error> {
         result.host_config.as_ref().unwrap().memory.unwrap()
     );
 
+    let _ = &docker
+        .update_container(
+            "integration_test_update_container",
+            UpdateContainerBuilder::new().cpus(0.5).build()?,
+        )
+        .await?;
+    let result = &docker
+        .inspect_container(
+            "integration_test_update_container",
+            None::<InspectContainerOptions>,
+        )
+        .await?;
+
+    assert_eq!(
+        500000000,
+        result.host_config.as_ref().unwrap().nano_cpus.unwrap()
+    );
+
     let _ = &docker
         .kill_container(
             "integration_test_update_container",
@@ -824,6 +917,74 @@ async fn mount_volume_container_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn resolve_host_port_test(docker: Docker) -> Result<(), Error> {
+    let image = format!("{}fussybeaver/uhttpd", registry_http_addr());
+
+    let _ = &docker
+        .create_image(
+            Some(CreateImageOptions {
+                from_image: &image[..],
+                ..Default::default()
+            }),
+            None,
+            Some(integration_test_registry_credentials()),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let ports = PortBindings::new()
+        .publish(80, Protocol::Tcp)
+        .to_random_host_port();
+
+    let _ = &docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_resolve_host_port",
+                platform: None,
+            }),
+            Config {
+                cmd: Some(vec![
+                    "/usr/sbin/run_uhttpd".to_string(),
+                    "-f".to_string(),
+                    "-p".to_string(),
+                    "80".to_string(),
+                    "-h".to_string(),
+                    "/www".to_string(),
+                ]),
+                image: Some(image),
+                exposed_ports: Some(ports.exposed_ports()),
+                host_config: Some(HostConfig {
+                    port_bindings: Some(ports.port_map()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    docker
+        .start_container::<String>("integration_test_resolve_host_port", None)
+        .await?;
+
+    let addr = docker
+        .resolve_host_port("integration_test_resolve_host_port", ContainerPort::tcp(80))
+        .await?;
+
+    assert!(addr.ip().is_loopback());
+    assert_ne!(addr.port(), 0);
+
+    assert!(matches!(
+        docker
+            .resolve_host_port("integration_test_resolve_host_port", ContainerPort::udp(80))
+            .await,
+        Err(Error::PortNotPublishedError { .. })
+    ));
+
+    kill_container(&docker, "integration_test_resolve_host_port").await?;
+
+    Ok(())
+}
+
 async fn export_container_test(docker: Docker) -> Result<(), Error> {
     create_image_hello_world(&docker).await?;
 
@@ -891,6 +1052,12 @@ fn integration_test_container_changes() {
     connect_to_docker_and_run!(container_changes_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_container_changed_files() {
+    connect_to_docker_and_run!(container_changed_files_test);
+}
+
 #[test]
 fn integration_test_stats() {
     connect_to_docker_and_run!(stats_test);
@@ -951,6 +1118,13 @@ fn integration_test_resize_container_tty() {
     connect_to_docker_and_run!(resize_container_test);
 }
 
+// note: the uhttpd image is only available on linux
+#[test]
+#[cfg(not(windows))]
+fn integration_test_resolve_host_port() {
+    connect_to_docker_and_run!(resolve_host_port_test);
+}
+
 // note: container exports aren't supported on Windows
 #[test]
 #[cfg(not(windows))]
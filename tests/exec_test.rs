@@ -89,6 +89,75 @@ async fn start_exec_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+#[cfg(unix)]
+async fn exec_audit_hook_test(docker: Docker) -> Result<(), Error> {
+    use std::sync::{Arc, Mutex};
+
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let hook_records = Arc::clone(&records);
+    let docker = docker.with_exec_audit_hook(move |record| {
+        hook_records.lock().unwrap().push(record.clone());
+    });
+
+    create_daemon(&docker, "integration_test_exec_audit_hook").await?;
+
+    let message = &docker
+        .create_exec(
+            "integration_test_exec_audit_hook",
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                cmd: Some(vec!["/bin/cat", "/etc/config/uhttpd"]),
+                user: Some("root"),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    if let StartExecResults::Attached { output, .. } = docker
+        .start_exec(&message.id, None::<StartExecOptions>)
+        .await?
+    {
+        output.try_collect::<Vec<_>>().await?;
+    }
+
+    let records = records.lock().unwrap().clone();
+    assert_eq!(
+        records.iter().map(|r| r.event).collect::<Vec<_>>(),
+        vec![
+            ExecAuditEvent::Created,
+            ExecAuditEvent::Started,
+            ExecAuditEvent::Completed
+        ]
+    );
+    assert!(records.iter().all(|r| r.exec_id == message.id));
+    assert_eq!(records[0].cmd, vec!["/bin/cat", "/etc/config/uhttpd"]);
+    assert_eq!(records[0].user.as_deref(), Some("root"));
+
+    let _ = &docker
+        .kill_container(
+            "integration_test_exec_audit_hook",
+            None::<KillContainerOptions<String>>,
+        )
+        .await?;
+
+    let _ = &docker
+        .wait_container(
+            "integration_test_exec_audit_hook",
+            None::<WaitContainerOptions<String>>,
+        )
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let _ = &docker
+        .remove_container(
+            "integration_test_exec_audit_hook",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+
+    Ok(())
+}
+
 async fn inspect_exec_test(docker: Docker) -> Result<(), Error> {
     create_daemon(&docker, "integration_test_inspect_exec_test").await?;
 
@@ -335,3 +404,9 @@ fn integration_test_start_exec_output_capacity_short() {
 fn integration_test_start_exec_output_capacity_long() {
     connect_to_docker_and_run!(start_exec_output_capacity_test_long);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_exec_audit_hook() {
+    connect_to_docker_and_run!(exec_audit_hook_test);
+}
@@ -12,7 +12,7 @@ use std::io::Write;
 pub mod common;
 use crate::common::*;
 
-async fn export_buildkit_oci_test(docker: Docker) -> Result<(), Error> {
+fn export_build_context() -> Vec<u8> {
     let dockerfile = String::from(
         "FROM localhost:5000/alpine as builder1
         RUN touch bollard.txt
@@ -33,7 +33,11 @@ async fn export_buildkit_oci_test(docker: Docker) -> Result<(), Error> {
     let uncompressed = tar.into_inner().unwrap();
     let mut c = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
     c.write_all(&uncompressed).unwrap();
-    let compressed = c.finish().unwrap();
+    c.finish().unwrap()
+}
+
+async fn export_buildkit_oci_test(docker: Docker) -> Result<(), Error> {
+    let compressed = export_build_context();
 
     let frontend_opts = bollard::grpc::build::ImageBuildFrontendOptions::builder()
         .pull(true)
@@ -104,8 +108,71 @@ async fn export_buildkit_oci_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn export_buildkit_oci_writer_test(docker: Docker) -> Result<(), Error> {
+    let compressed = export_build_context();
+
+    let frontend_opts = bollard::grpc::build::ImageBuildFrontendOptions::builder()
+        .pull(true)
+        .build();
+
+    let (writer, mut reader) = tokio::io::duplex(64 * 1024);
+
+    let output = bollard::grpc::export::ImageExporterOutputBuilder::new(
+        "docker.io/library/bollard-oci-export-buildkit-example:latest",
+    )
+    .annotation("exporter", "Bollard")
+    .dest_writer(writer);
+
+    let buildkit_builder = DockerContainerBuilder::new(&docker);
+    let driver = buildkit_builder.bootstrap().await.unwrap();
+
+    let load_input =
+        bollard::grpc::build::ImageBuildLoadInput::Upload(bytes::Bytes::from(compressed));
+
+    let credentials = bollard::auth::DockerCredentials {
+        username: Some("bollard".to_string()),
+        password: std::env::var("REGISTRY_PASSWORD").ok(),
+        ..Default::default()
+    };
+    let mut creds_hsh = std::collections::HashMap::new();
+    creds_hsh.insert("localhost:5000", credentials);
+
+    let mut archive = Vec::new();
+    let (res, read) = tokio::join!(
+        bollard::grpc::driver::Export::export(
+            driver,
+            bollard::grpc::driver::ImageExporterEnum::OCI(output),
+            frontend_opts,
+            load_input,
+            Some(creds_hsh),
+        ),
+        tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut archive)
+    );
+
+    assert!(res.is_ok());
+    read?;
+
+    let mut oci_archive = tar::Archive::new(archive.as_slice());
+
+    let mut paths = vec![];
+    for entry in oci_archive.entries()? {
+        paths.push(entry?.path()?.display().to_string());
+    }
+
+    assert!(paths.contains(&String::from("index.json")));
+    assert!(paths.contains(&String::from("oci-layout")));
+
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "buildkit")]
 fn integration_test_export_buildkit_oci() {
     connect_to_docker_and_run!(export_buildkit_oci_test);
 }
+
+#[test]
+#[cfg(feature = "buildkit")]
+fn integration_test_export_buildkit_oci_writer() {
+    connect_to_docker_and_run!(export_buildkit_oci_writer_test);
+}
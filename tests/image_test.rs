@@ -104,6 +104,26 @@ async fn inspect_image_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn inspect_image_store_test(docker: Docker) -> Result<(), Error> {
+    use bollard::system::ImageStore;
+
+    let image = create_image_hello_world(&docker).await?;
+
+    let result = &docker.inspect_image(&image).await?;
+
+    match docker.image_store().await? {
+        ImageStore::Containerd { .. } => {
+            assert!(result.descriptor.is_some());
+        }
+        ImageStore::GraphDriver { driver } => {
+            assert_eq!(result.graph_driver.as_ref().unwrap().name, driver);
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
 async fn list_images_test(docker: Docker) -> Result<(), Error> {
     let image = if cfg!(windows) {
         format!("{}hello-world:nanoserver", registry_http_addr())
@@ -149,6 +169,22 @@ async fn image_history_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn diff_images_test(docker: Docker) -> Result<(), Error> {
+    let image = if cfg!(windows) {
+        format!("{}hello-world:nanoserver", registry_http_addr())
+    } else {
+        format!("{}hello-world:linux", registry_http_addr())
+    };
+
+    create_image_hello_world(&docker).await?;
+
+    let diff = docker.diff_images(&image, &image).await?;
+    assert!(diff.is_empty());
+    assert!(diff.common_layers > 0);
+
+    Ok(())
+}
+
 async fn prune_images_test(docker: Docker) -> Result<(), Error> {
     let mut filters = HashMap::new();
     filters.insert("label", vec!["maintainer=some_maintainer"]);
@@ -159,6 +195,21 @@ async fn prune_images_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn prune_build_cache_test(docker: Docker) -> Result<(), Error> {
+    let mut filters = HashMap::new();
+    filters.insert("until", vec!["24h"]);
+    let _ = &docker
+        .prune_build_cache(Some(PruneBuildCacheOptions {
+            filters,
+            ..Default::default()
+        }))
+        .await?;
+
+    let _ = &docker.list_build_cache().await?;
+
+    Ok(())
+}
+
 async fn remove_image_test(docker: Docker) -> Result<(), Error> {
     let image = if cfg!(windows) {
         format!("{}hello-world:nanoserver", registry_http_addr())
@@ -635,6 +686,78 @@ ENTRYPOINT ls buildkit-bollard.txt
     Ok(())
 }
 
+#[cfg(feature = "buildkit")]
+async fn build_image_secret_test(docker: Docker) -> Result<(), Error> {
+    use bollard::grpc::build::SecretSource;
+
+    let dockerfile = String::from(
+        "FROM alpine
+RUN --mount=type=secret,id=token,env=TOKEN test \"$TOKEN\" = \"bollard-secret\"
+",
+    );
+    let mut header = tar::Header::new_gnu();
+    header.set_path("Dockerfile").unwrap();
+    header.set_size(dockerfile.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    let mut tar = tar::Builder::new(Vec::new());
+    tar.append(&header, dockerfile.as_bytes()).unwrap();
+    let uncompressed = tar.into_inner().unwrap();
+
+    std::env::set_var("BOLLARD_BUILD_IMAGE_SECRET", "bollard-secret");
+    let mut secrets = HashMap::new();
+    secrets.insert(
+        String::from("token"),
+        SecretSource::Env(String::from("BOLLARD_BUILD_IMAGE_SECRET")),
+    );
+
+    let build = &docker
+        .build_image(
+            BuildImageOptions {
+                dockerfile: "Dockerfile".to_string(),
+                t: "integration_test_build_image_secret".to_string(),
+                version: BuilderVersion::BuilderV1,
+                secrets: secrets.clone(),
+                ..Default::default()
+            },
+            None,
+            Some(http_body_util::Either::Left(Full::new(
+                uncompressed.clone().into(),
+            ))),
+        )
+        .try_collect::<Vec<bollard::models::BuildInfo>>()
+        .await;
+
+    assert!(matches!(build, Err(Error::UnsupportedFeatureError { .. })));
+
+    let _ = &docker
+        .build_image(
+            BuildImageOptions {
+                dockerfile: "Dockerfile".to_string(),
+                t: "integration_test_build_image_secret".to_string(),
+                pull: true,
+                version: BuilderVersion::BuilderBuildKit,
+                session: Some(String::from("build_image_secret_test")),
+                secrets,
+                ..Default::default()
+            },
+            None,
+            Some(http_body_util::Either::Left(Full::new(uncompressed.into()))),
+        )
+        .try_collect::<Vec<bollard::models::BuildInfo>>()
+        .await?;
+
+    let _ = &docker
+        .remove_image(
+            "integration_test_build_image_secret",
+            None::<RemoveImageOptions>,
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
 #[cfg(feature = "buildkit")]
 async fn build_buildkit_secret_test(docker: Docker) -> Result<(), Error> {
     use bollard::grpc::build::SecretSource;
@@ -939,17 +1062,48 @@ async fn build_buildkit_named_context_test(docker: Docker) -> Result<(), Error>
 
 #[cfg(all(feature = "buildkit", feature = "test_sshforward"))]
 async fn build_buildkit_ssh_test(docker: Docker) -> Result<(), Error> {
+    build_buildkit_ssh(docker, None).await
+}
+
+#[cfg(all(feature = "buildkit", feature = "test_sshforward"))]
+async fn build_buildkit_ssh_keys_test(docker: Docker) -> Result<(), Error> {
+    let key = std::env::var("SSH_KEY_PATH").unwrap_or_else(|_| {
+        format!(
+            "{}/.ssh/id_ed25519",
+            std::env::var("HOME").unwrap_or_default()
+        )
+    });
+    build_buildkit_ssh(
+        docker,
+        Some(bollard::grpc::build::SshSource::Keys(vec![
+            std::path::PathBuf::from(key),
+        ])),
+    )
+    .await
+}
+
+#[cfg(all(feature = "buildkit", feature = "test_sshforward"))]
+async fn build_buildkit_ssh(
+    docker: Docker,
+    ssh_source: Option<bollard::grpc::build::SshSource>,
+) -> Result<(), Error> {
+    let mount_id = if ssh_source.is_some() {
+        "gitkey"
+    } else {
+        "default"
+    };
     let git_host = std::env::var("GIT_HTTP_HOST").unwrap_or_else(|_| "localhost".to_string());
     let git_port = std::env::var("GIT_HTTP_PORT").unwrap_or_else(|_| "2222".to_string());
     let dockerfile = format!(
         "FROM {}alpine as builder1
 RUN apk add --no-cache openssh-client git netcat-openbsd
 RUN mkdir -p -m 0600 ~/.ssh && ssh-keyscan -t rsa -p {} {} >> ~/.ssh/known_hosts
-RUN --mount=type=ssh git clone ssh://git@{}:{}/srv/git/config.git /config
+RUN --mount=type=ssh,id={} git clone ssh://git@{}:{}/srv/git/config.git /config
 ",
         &registry_http_addr(),
         &git_port,
         &git_host,
+        mount_id,
         &git_host,
         &git_port,
     );
@@ -975,8 +1129,12 @@ RUN --mount=type=ssh git clone ssh://git@{}:{}/srv/git/config.git /config
             host: String::from("gitserver"),
             ip: std::net::IpAddr::V4(std::net::Ipv4Addr::new(172, 17, 0, 3)),
         })
-        .enable_ssh(true)
-        .build();
+        .enable_ssh(true);
+    let frontend_opts = match &ssh_source {
+        Some(source) => frontend_opts.set_ssh(mount_id, source),
+        None => frontend_opts,
+    }
+    .build();
 
     let driver = bollard::grpc::driver::moby::Moby::new(&docker);
 
@@ -1052,6 +1210,109 @@ RUN --mount=type=ssh git clone ssh://git@{}:{}/srv/git/config.git /config
     Ok(())
 }
 
+#[cfg(feature = "buildkit")]
+async fn buildkit_builder_lifecycle_test(docker: Docker) -> Result<(), Error> {
+    use bollard::grpc::driver::docker_container::{
+        inspect_builder, list_builders, remove_builder, DockerContainerBuilder,
+    };
+
+    let name = "integration_test_buildkit_builder_lifecycle";
+
+    let mut builder = DockerContainerBuilder::new(&docker);
+    builder
+        .name(name)
+        .state_volume("integration_test_buildkit_builder_lifecycle_cache")
+        .tear_down(false);
+    let driver = builder.bootstrap().await.unwrap();
+    assert_eq!(driver.name(), name);
+
+    let builders = list_builders(&docker).await.unwrap();
+    assert!(builders.iter().any(|builder| builder.name == name));
+
+    let info = inspect_builder(&docker, name).await.unwrap();
+    assert!(info.running);
+    assert_eq!(
+        info.state_volume.as_deref(),
+        Some("integration_test_buildkit_builder_lifecycle_cache")
+    );
+
+    // bootstrapping the same name reuses the running builder
+    let mut builder = DockerContainerBuilder::new(&docker);
+    builder.name(name);
+    builder.bootstrap().await.unwrap();
+
+    remove_builder(&docker, name, true).await.unwrap();
+
+    assert!(inspect_builder(&docker, name).await.is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "buildkit")]
+async fn build_buildkit_pool_test(docker: Docker) -> Result<(), Error> {
+    use bollard::grpc::build::{BuildPoolOptions, BuildPoolOutput};
+    use bollard::grpc::driver::Pool;
+    use futures_util::StreamExt;
+
+    let credentials = bollard::auth::DockerCredentials {
+        username: Some("bollard".to_string()),
+        password: std::env::var("REGISTRY_PASSWORD").ok(),
+        ..Default::default()
+    };
+    let options = BuildPoolOptions::builder()
+        .max_parallel(1)
+        .credentials("localhost:5000", &credentials)
+        .build();
+
+    let driver = bollard::grpc::driver::moby::Moby::new(&docker);
+    let pool = driver.pool(options).await.unwrap();
+
+    let mut jobs = vec![];
+    for name in ["bollard-pool-one", "bollard-pool-two"] {
+        let dockerfile = format!("FROM localhost:5000/alpine\nRUN echo {name} > /name.txt\n");
+        let mut header = tar::Header::new_gnu();
+        header.set_path("Dockerfile").unwrap();
+        header.set_size(dockerfile.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        let mut tar = tar::Builder::new(Vec::new());
+        tar.append(&header, dockerfile.as_bytes()).unwrap();
+        let uncompressed = tar.into_inner().unwrap();
+        let mut c = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        c.write_all(&uncompressed).unwrap();
+        let compressed = c.finish().unwrap();
+
+        jobs.push(
+            pool.solve(
+                BuildPoolOutput::Moby(format!("{name}:latest")),
+                bollard::grpc::build::ImageBuildFrontendOptions::builder()
+                    .pull(true)
+                    .build(),
+                bollard::grpc::build::ImageBuildLoadInput::Upload(bytes::Bytes::from(compressed)),
+            ),
+        );
+    }
+
+    for mut job in jobs {
+        let progress = job.take_progress().unwrap();
+        let updates = progress.collect::<Vec<_>>().await;
+        assert!(!updates.is_empty());
+        job.wait().await.unwrap();
+    }
+
+    pool.close().await.unwrap();
+
+    for name in ["bollard-pool-one", "bollard-pool-two"] {
+        let image = docker.inspect_image(&format!("{name}:latest")).await?;
+        assert!(image.id.is_some());
+        docker
+            .remove_image(&format!("{name}:latest"), None, None)
+            .await?;
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "buildkit")]
 async fn build_buildkit_image_inline_driver_test(docker: Docker) -> Result<(), Error> {
     let dockerfile = String::from(
@@ -1587,6 +1848,35 @@ async fn import_image_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn import_image_loaded_test(docker: Docker) -> Result<(), Error> {
+    create_image_hello_world(&docker).await?;
+
+    let image = if cfg!(windows) {
+        format!("{}hello-world:nanoserver", registry_http_addr())
+    } else {
+        format!("{}hello-world:linux", registry_http_addr())
+    };
+
+    let mut res = docker.export_image(&image);
+
+    let mut buf = bytes::BytesMut::new();
+    while let Some(data) = res.next().await {
+        buf.put_slice(&data.unwrap());
+    }
+
+    let loaded = docker
+        .import_image_loaded(
+            ImportImageOptions { quiet: true },
+            body_full(buf.freeze()),
+            None,
+        )
+        .await?;
+
+    assert_eq!(loaded, vec![image]);
+
+    Ok(())
+}
+
 async fn import_image_test_stream(docker: Docker) -> Result<(), Error> {
     // round-trip test
     create_image_hello_world(&docker).await?;
@@ -1619,6 +1909,54 @@ async fn import_image_test_stream(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+#[cfg(feature = "image_builder")]
+async fn image_builder_test(docker: Docker) -> Result<(), Error> {
+    use bollard::image::builder::{ImageBuilder, LayerBuilder};
+
+    create_image_hello_world(&docker).await?;
+
+    let image = format!("{}hello-world:linux", registry_http_addr());
+
+    let id = ImageBuilder::from_image(&image)
+        .layer(LayerBuilder::new().file("/etc/motd", "hello from bollard"))
+        .env("GREETING", "hello")
+        .label("maintainer", "bollard-maintainer")
+        .load(&docker, "integration_test_image_builder:latest")
+        .await?;
+
+    let result = docker
+        .inspect_image("integration_test_image_builder:latest")
+        .await?;
+
+    assert_eq!(result.id.as_deref(), Some(id.as_str()));
+
+    let config = result.config.unwrap();
+    assert!(config
+        .env
+        .unwrap()
+        .contains(&String::from("GREETING=hello")));
+    assert_eq!(
+        config.labels.unwrap().get("maintainer"),
+        Some(&String::from("bollard-maintainer"))
+    );
+
+    let base = docker.inspect_image(&image).await?;
+    assert_eq!(
+        result.root_fs.unwrap().layers.unwrap().len(),
+        base.root_fs.unwrap().layers.unwrap().len() + 1
+    );
+
+    docker
+        .remove_image(
+            "integration_test_image_builder:latest",
+            None::<RemoveImageOptions>,
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
 // ND - Test sometimes hangs on appveyor.
 #[cfg(not(windows))]
 #[test]
@@ -1644,6 +1982,12 @@ fn integration_test_inspect_image() {
     connect_to_docker_and_run!(inspect_image_test);
 }
 
+#[test]
+#[cfg(not(windows))]
+fn integration_test_inspect_image_store() {
+    connect_to_docker_and_run!(inspect_image_store_test);
+}
+
 #[test]
 // ND - Test sometimes hangs on appveyor.
 #[cfg(not(windows))]
@@ -1656,11 +2000,22 @@ fn integration_test_image_history() {
     connect_to_docker_and_run!(image_history_test);
 }
 
+#[test]
+fn integration_test_diff_images() {
+    connect_to_docker_and_run!(diff_images_test);
+}
+
 #[test]
 fn integration_test_prune_images() {
     connect_to_docker_and_run!(prune_images_test);
 }
 
+#[test]
+#[cfg(not(windows))]
+fn integration_test_prune_build_cache() {
+    connect_to_docker_and_run!(prune_build_cache_test);
+}
+
 #[test]
 // ND - Test sometimes hangs on appveyor.
 #[cfg(not(windows))]
@@ -1692,6 +2047,12 @@ fn integration_test_buildkit_image_missing_session_test() {
     connect_to_docker_and_run!(buildkit_image_missing_session_test);
 }
 
+#[test]
+#[cfg(feature = "buildkit")]
+fn integration_test_build_image_secret() {
+    connect_to_docker_and_run!(build_image_secret_test);
+}
+
 #[test]
 #[cfg(feature = "buildkit")]
 fn integration_test_build_buildkit_secret() {
@@ -1716,6 +2077,24 @@ fn integration_test_build_buildkit_ssh() {
     connect_to_docker_and_run!(build_buildkit_ssh_test);
 }
 
+#[test]
+#[cfg(all(feature = "buildkit", feature = "test_sshforward"))]
+fn integration_test_build_buildkit_ssh_keys() {
+    connect_to_docker_and_run!(build_buildkit_ssh_keys_test);
+}
+
+#[test]
+#[cfg(feature = "buildkit")]
+fn integration_test_buildkit_builder_lifecycle() {
+    connect_to_docker_and_run!(buildkit_builder_lifecycle_test);
+}
+
+#[test]
+#[cfg(feature = "buildkit")]
+fn integration_test_build_buildkit_pool() {
+    connect_to_docker_and_run!(build_buildkit_pool_test);
+}
+
 #[test]
 #[cfg(feature = "buildkit")]
 fn integration_test_build_buildkit_inline_driver() {
@@ -1766,8 +2145,20 @@ fn integration_test_import_image() {
     connect_to_docker_and_run!(import_image_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_import_image_loaded() {
+    connect_to_docker_and_run!(import_image_loaded_test);
+}
+
 #[test]
 #[cfg(unix)]
 fn integration_test_import_image_stream() {
     connect_to_docker_and_run!(import_image_test_stream);
 }
+
+#[test]
+#[cfg(all(unix, feature = "image_builder"))]
+fn integration_test_image_builder() {
+    connect_to_docker_and_run!(image_builder_test);
+}
@@ -66,6 +66,36 @@ async fn create_network_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn create_network_builder_test(docker: Docker) -> Result<(), Error> {
+    use bollard::network::builder::NetworkCreateBuilder;
+
+    let options = NetworkCreateBuilder::new("integration_test_create_network_builder")
+        .subnet("10.10.20.0/24")
+        .gateway("10.10.20.1")
+        .ip_range("10.10.20.128/25")
+        .internal()
+        .label("maintainer", "bollard-maintainer")
+        .build()?;
+
+    let result = &docker.create_network(options).await?;
+
+    let result = &docker
+        .inspect_network(&result.id, None::<InspectNetworkOptions<String>>)
+        .await?;
+
+    let config = &result.ipam.as_ref().unwrap().config.as_ref().unwrap()[0];
+    assert_eq!(config.subnet.as_deref(), Some("10.10.20.0/24"));
+    assert_eq!(config.gateway.as_deref(), Some("10.10.20.1"));
+    assert_eq!(config.ip_range.as_deref(), Some("10.10.20.128/25"));
+    assert_eq!(result.internal, Some(true));
+
+    docker
+        .remove_network("integration_test_create_network_builder")
+        .await?;
+
+    Ok(())
+}
+
 async fn list_networks_test(docker: Docker) -> Result<(), Error> {
     let ipam_config = IpamConfig {
         subnet: Some(String::from("10.10.10.0/24")),
@@ -178,6 +208,13 @@ async fn connect_network_test(docker: Docker) -> Result<(), Error> {
         .iter()
         .any(|(_, container)| container.ipv4_address == Some("10.10.10.101/24".into())));
 
+    let topology = &docker.inspect_topology().await?;
+
+    assert!(topology
+        .networks_of("integration_test_connect_network_test")
+        .any(|(node, endpoint)| node.network.id.as_deref() == Some(id)
+            && endpoint.ipv4_address == Some("10.10.10.101".into())));
+
     let _ = &docker
         .disconnect_network(
             id,
@@ -207,6 +244,95 @@ async fn connect_network_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn create_container_with_networks_test(docker: Docker) -> Result<(), Error> {
+    let image = create_image_hello_world(&docker).await?;
+
+    for (name, subnet) in [
+        ("integration_test_networks_frontend", "10.10.11.0/24"),
+        ("integration_test_networks_backend", "10.10.12.0/24"),
+    ] {
+        docker
+            .create_network(CreateNetworkOptions {
+                name,
+                check_duplicate: true,
+                ipam: Ipam {
+                    config: Some(vec![IpamConfig {
+                        subnet: Some(String::from(subnet)),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await?;
+    }
+
+    docker
+        .create_container_with_networks(
+            Some(CreateContainerOptions {
+                name: "integration_test_create_container_with_networks",
+                platform: None,
+            }),
+            bollard::container::Config {
+                image: Some(image),
+                ..Default::default()
+            },
+            [
+                (
+                    "integration_test_networks_frontend",
+                    EndpointSettings {
+                        aliases: Some(vec![String::from("web")]),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "integration_test_networks_backend",
+                    EndpointSettings {
+                        ipam_config: Some(EndpointIpamConfig {
+                            ipv4_address: Some(String::from("10.10.12.101")),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        )
+        .await?;
+
+    let networks = docker
+        .inspect_container("integration_test_create_container_with_networks", None)
+        .await?
+        .network_settings
+        .and_then(|settings| settings.networks)
+        .unwrap();
+    assert!(networks
+        .get("integration_test_networks_frontend")
+        .and_then(|settings| settings.aliases.as_ref())
+        .is_some_and(|aliases| aliases.contains(&String::from("web"))));
+    assert_eq!(
+        networks
+            .get("integration_test_networks_backend")
+            .and_then(|settings| settings.ipam_config.as_ref())
+            .and_then(|ipam| ipam.ipv4_address.as_deref()),
+        Some("10.10.12.101")
+    );
+
+    docker
+        .remove_container(
+            "integration_test_create_container_with_networks",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+    docker
+        .remove_network("integration_test_networks_frontend")
+        .await?;
+    docker
+        .remove_network("integration_test_networks_backend")
+        .await?;
+
+    Ok(())
+}
+
 async fn prune_networks_test(docker: Docker) -> Result<(), Error> {
     let create_network_options = CreateNetworkOptions {
         name: "integration_test_prune_networks",
@@ -252,6 +378,12 @@ fn integration_test_create_network() {
     connect_to_docker_and_run!(create_network_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_create_network_builder() {
+    connect_to_docker_and_run!(create_network_builder_test);
+}
+
 #[test]
 #[cfg(unix)]
 // Hangs on Appveyor
@@ -266,6 +398,12 @@ fn integration_test_connect_network() {
     connect_to_docker_and_run!(connect_network_test);
 }
 
+#[test]
+#[cfg(unix)]
+fn integration_test_create_container_with_networks() {
+    connect_to_docker_and_run!(create_container_with_networks_test);
+}
+
 #[test]
 #[cfg(unix)]
 // Hangs on Appveyor
@@ -0,0 +1,78 @@
+extern crate bollard;
+extern crate hyper;
+extern crate tokio;
+
+use bollard::container::*;
+use bollard::errors::Error;
+use bollard::network::*;
+use bollard::project::*;
+use bollard::volume::*;
+use bollard::Docker;
+
+use tokio::runtime::Runtime;
+
+#[macro_use]
+pub mod common;
+use crate::common::*;
+
+async fn project_test(docker: Docker) -> Result<(), Error> {
+    let image = create_image_hello_world(&docker).await?;
+
+    let project = docker.project("integration_test_project");
+
+    project
+        .create_container(
+            "hello",
+            Config {
+                image: Some(image),
+                ..Default::default()
+            },
+        )
+        .await?;
+    project
+        .create_network(CreateNetworkOptions {
+            name: String::from("backend"),
+            ..Default::default()
+        })
+        .await?;
+    project
+        .create_volume(CreateVolumeOptions {
+            name: String::from("data"),
+            ..Default::default()
+        })
+        .await?;
+
+    let containers = project.list_containers().await?;
+    assert_eq!(containers.len(), 1);
+    assert_eq!(
+        containers[0].names.as_ref().unwrap()[0],
+        "/integration_test_project-hello-1"
+    );
+    assert_eq!(
+        containers[0].labels.as_ref().unwrap().get(SERVICE_LABEL),
+        Some(&String::from("hello"))
+    );
+
+    let networks = project.list_networks().await?;
+    assert_eq!(
+        networks[0].name.as_deref(),
+        Some("integration_test_project_backend")
+    );
+
+    let volumes = project.list_volumes().await?;
+    assert_eq!(volumes[0].name, "integration_test_project_data");
+
+    project.down(true).await?;
+
+    assert!(project.list_containers().await?.is_empty());
+    assert!(project.list_networks().await?.is_empty());
+    assert!(project.list_volumes().await?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn integration_test_project() {
+    connect_to_docker_and_run!(project_test);
+}
@@ -0,0 +1,75 @@
+extern crate bollard;
+extern crate hyper;
+extern crate tokio;
+
+use bollard::container::*;
+use bollard::errors::Error;
+use bollard::run::*;
+use bollard::Docker;
+
+use futures_util::stream::TryStreamExt;
+use tokio::runtime::Runtime;
+
+#[macro_use]
+pub mod common;
+use crate::common::*;
+
+async fn run_container_test(docker: Docker) -> Result<(), Error> {
+    let image = if cfg!(windows) {
+        format!("{}hello-world:nanoserver", registry_http_addr())
+    } else {
+        format!("{}hello-world:linux", registry_http_addr())
+    };
+
+    let mut container = docker
+        .run_container(RunOptions {
+            config: Config {
+                image: Some(image),
+                ..Default::default()
+            },
+            name: Some(String::from("integration_test_run_container")),
+            credentials: Some(integration_test_registry_credentials()),
+            ..Default::default()
+        })
+        .await?;
+
+    assert_eq!(container.wait().await?, 0);
+    assert_eq!(container.exit_code(), Some(0));
+
+    let logs = container.logs(false).try_collect::<Vec<_>>().await?;
+    assert!(logs
+        .iter()
+        .any(|log| log.to_string().contains("Hello from Docker!")));
+
+    container.remove().await?;
+
+    assert!(matches!(
+        docker
+            .inspect_container("integration_test_run_container", None)
+            .await,
+        Err(Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+async fn run_container_missing_image_test(docker: Docker) -> Result<(), Error> {
+    let result = docker.run_container(RunOptions::default()).await;
+
+    assert!(matches!(result, Err(Error::MissingImageError {})));
+
+    Ok(())
+}
+
+#[test]
+fn integration_test_run_container() {
+    connect_to_docker_and_run!(run_container_test);
+}
+
+#[test]
+fn integration_test_run_container_missing_image() {
+    connect_to_docker_and_run!(run_container_missing_image_test);
+}
@@ -229,6 +229,55 @@ async fn service_rollback_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn service_watch_tasks_test(docker: Docker) -> Result<(), Error> {
+    use bollard::service::tasks::TaskTransition;
+    use futures_util::stream::StreamExt;
+
+    let image = if cfg!(windows) {
+        format!("{}nanoserver/iis", registry_http_addr())
+    } else {
+        format!("{}fussybeaver/uhttpd", registry_http_addr())
+    };
+    let spec = ServiceSpec {
+        name: Some(String::from("integration_test_watch_service_tasks")),
+        task_template: Some(TaskSpec {
+            container_spec: Some(TaskSpecContainerSpec {
+                image: Some(image),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    docker.create_service(spec, None).await?;
+
+    let transitions: Vec<TaskTransition> = docker
+        .watch_service_tasks("integration_test_watch_service_tasks")
+        .take(1)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(transitions[0].previous_state, None);
+    assert_eq!(transitions[0].desired_state, Some(TaskState::RUNNING));
+
+    let tasks = docker
+        .list_service_tasks("integration_test_watch_service_tasks")
+        .await?;
+    assert!(tasks
+        .iter()
+        .any(|task| task.id.as_ref() == Some(&transitions[0].task_id)));
+
+    docker
+        .delete_service("integration_test_watch_service_tasks")
+        .await?;
+
+    Ok(())
+}
+
 #[test]
 #[cfg(unix)]
 fn integration_test_create_service() {
@@ -252,3 +301,9 @@ fn integration_test_update_service() {
 fn integration_test_rollback_service() {
     connect_to_docker_and_run!(service_rollback_test);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_watch_service_tasks() {
+    connect_to_docker_and_run!(service_watch_tasks_test);
+}
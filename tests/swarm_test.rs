@@ -32,6 +32,77 @@ async fn swarm_test(docker: bollard::Docker) -> Result<(), bollard::errors::Erro
     Ok(())
 }
 
+#[cfg(feature = "test_swarm")]
+async fn swarm_workflow_test(docker: bollard::Docker) -> Result<(), bollard::errors::Error> {
+    use bollard::swarm::*;
+
+    let results = &docker.init_swarm_with_defaults("127.0.0.1").await?;
+    assert!(!results.node_id.is_empty());
+    assert!(results.join_tokens.worker.starts_with("SWMTKN-"));
+    assert!(results
+        .join_tokens
+        .tls_info
+        .as_ref()
+        .and_then(|tls_info| tls_info.trust_root.as_ref())
+        .is_some());
+
+    let rotated = &docker.rotate_join_tokens().await?;
+    assert_ne!(rotated.worker, results.join_tokens.worker);
+    assert_ne!(rotated.manager, results.join_tokens.manager);
+
+    let swarm = docker.inspect_swarm().await?;
+    let mut spec = swarm.spec.unwrap_or_default();
+    spec.labels = Some([("maintainer".into(), "bollard-maintainer".into())].into());
+    let options = UpdateSwarmOptions {
+        version: swarm.version.and_then(|version| version.index).unwrap(),
+        ..Default::default()
+    };
+    docker.update_swarm(spec, options).await?;
+
+    let inspection_result = &docker.inspect_swarm().await?;
+    assert_eq!(
+        inspection_result
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.labels.as_ref())
+            .and_then(|labels| labels.get("maintainer"))
+            .map(String::as_str),
+        Some("bollard-maintainer")
+    );
+
+    let swarm = docker.inspect_swarm().await?;
+    let mut spec = swarm.spec.unwrap_or_default();
+    spec.encryption_config = Some(bollard::models::SwarmSpecEncryptionConfig {
+        auto_lock_managers: Some(true),
+    });
+    let options = UpdateSwarmOptions {
+        version: swarm.version.and_then(|version| version.index).unwrap(),
+        ..Default::default()
+    };
+    docker.update_swarm(spec, options).await?;
+
+    let unlock_key = &docker.get_unlock_key().await?;
+    assert!(unlock_key
+        .unlock_key
+        .as_ref()
+        .is_some_and(|key| key.starts_with("SWMKEY-")));
+
+    docker.rotate_swarm_ca::<String>(None).await?;
+    let inspection_result = &docker.inspect_swarm().await?;
+    assert_eq!(
+        inspection_result
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.ca_config.as_ref())
+            .and_then(|ca_config| ca_config.force_rotate),
+        Some(1)
+    );
+
+    let config = LeaveSwarmOptions { force: true };
+    let _ = &docker.leave_swarm(Some(config)).await?;
+    Ok(())
+}
+
 #[cfg(feature = "test_swarm")]
 #[test]
 fn integration_test_swarm() {
@@ -40,3 +111,12 @@ fn integration_test_swarm() {
     use tokio::runtime::Runtime;
     connect_to_docker_and_run!(swarm_test);
 }
+
+#[cfg(feature = "test_swarm")]
+#[test]
+fn integration_test_swarm_workflow() {
+    use crate::common::run_runtime;
+    use bollard::Docker;
+    use tokio::runtime::Runtime;
+    connect_to_docker_and_run!(swarm_workflow_test);
+}
@@ -11,6 +11,8 @@ use futures_util::stream::StreamExt;
 use futures_util::stream::TryStreamExt;
 use tokio::runtime::Runtime;
 
+use std::time::Duration;
+
 #[macro_use]
 pub mod common;
 use common::*;
@@ -151,6 +153,34 @@ async fn info_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn security_options_test(docker: Docker) -> Result<(), Error> {
+    let info = &docker.info().await?;
+    let options = &docker.security_options().await?;
+
+    assert_eq!(
+        info.security_options.as_ref().map(Vec::len).unwrap_or(0),
+        options.len()
+    );
+    if cfg!(unix) {
+        assert!(options
+            .iter()
+            .any(|option| matches!(option, SecurityOption::Seccomp { .. })));
+    }
+
+    Ok(())
+}
+
+async fn daemon_capabilities_test(docker: Docker) -> Result<(), Error> {
+    let capabilities = docker.daemon_capabilities().await?;
+    let max_api_version = capabilities.max_api_version().unwrap();
+    assert!(capabilities.supports_api_version(&max_api_version));
+    if !cfg!(windows) {
+        assert!(capabilities.cgroup_version().is_some());
+    }
+
+    Ok(())
+}
+
 async fn ping_test(docker: Docker) -> Result<(), Error> {
     let res = &docker.ping().await?;
     assert_eq!("OK", res);
@@ -158,6 +188,37 @@ async fn ping_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn ping_until_ready_test(docker: Docker) -> Result<(), Error> {
+    let info = docker
+        .ping_until_ready(Duration::from_secs(10), Duration::from_millis(100))
+        .await?;
+    assert!(info.api_version.is_some());
+    assert!(info.os_type.is_some());
+
+    Ok(())
+}
+
+async fn request_raw_test(docker: Docker) -> Result<(), Error> {
+    let response = docker
+        .request_raw(http::Method::GET, "/_ping", None::<String>, None)
+        .await?;
+    assert_eq!(response.status(), http::StatusCode::OK);
+    assert!(response.headers().contains_key("api-version"));
+    assert_eq!(&response.bytes().await?[..], b"OK");
+
+    let response = docker
+        .request_raw(
+            http::Method::GET,
+            "/containers/not-a-container/json",
+            None::<String>,
+            None,
+        )
+        .await?;
+    assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
 #[test]
 fn integration_test_events() {
     connect_to_docker_and_run!(events_test);
@@ -180,7 +241,27 @@ fn integration_test_info() {
     connect_to_docker_and_run!(info_test);
 }
 
+#[test]
+fn integration_test_security_options() {
+    connect_to_docker_and_run!(security_options_test);
+}
+
+#[test]
+fn integration_test_daemon_capabilities() {
+    connect_to_docker_and_run!(daemon_capabilities_test);
+}
+
 #[test]
 fn integration_test_ping() {
     connect_to_docker_and_run!(ping_test);
 }
+
+#[test]
+fn integration_test_ping_until_ready() {
+    connect_to_docker_and_run!(ping_until_ready_test);
+}
+
+#[test]
+fn integration_test_request_raw() {
+    connect_to_docker_and_run!(request_raw_test);
+}
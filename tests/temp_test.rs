@@ -0,0 +1,219 @@
+extern crate bollard;
+extern crate hyper;
+extern crate tokio;
+
+use bollard::container::*;
+use bollard::errors::Error;
+use bollard::network::*;
+use bollard::temp::*;
+use bollard::volume::*;
+use bollard::Docker;
+
+use tokio::runtime::Runtime;
+
+use std::collections::HashMap;
+
+#[macro_use]
+pub mod common;
+use crate::common::*;
+
+async fn temp_network_test(docker: Docker) -> Result<(), Error> {
+    let network = docker
+        .create_temp_network(CreateNetworkOptions {
+            name: "integration_test_temp_network",
+            ..Default::default()
+        })
+        .await?;
+
+    let result = docker
+        .inspect_network(network.id(), None::<InspectNetworkOptions<String>>)
+        .await?;
+
+    assert_eq!(
+        result.labels.as_ref().unwrap().get(TEMP_OWNER_LABEL),
+        Some(&temp_owner())
+    );
+
+    let id = network.id().to_owned();
+    network.close().await?;
+
+    assert!(matches!(
+        docker
+            .inspect_network(&id, None::<InspectNetworkOptions<String>>)
+            .await,
+        Err(Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+async fn temp_volume_test(docker: Docker) -> Result<(), Error> {
+    let volume = docker
+        .create_temp_volume(CreateVolumeOptions {
+            name: "integration_test_temp_volume",
+            ..Default::default()
+        })
+        .await?;
+
+    assert_eq!(
+        volume.volume().labels.get(TEMP_OWNER_LABEL),
+        Some(&temp_owner())
+    );
+
+    volume.close().await?;
+
+    assert!(matches!(
+        docker.inspect_volume("integration_test_temp_volume").await,
+        Err(Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+async fn scoped_container_test(docker: Docker) -> Result<(), Error> {
+    let image = if cfg!(windows) {
+        format!("{}hello-world:nanoserver", registry_http_addr())
+    } else {
+        format!("{}hello-world:linux", registry_http_addr())
+    };
+
+    let network = docker
+        .create_temp_network(CreateNetworkOptions {
+            name: "integration_test_scoped_container_network",
+            ..Default::default()
+        })
+        .await?;
+    let network_id = network.id().to_owned();
+
+    let mut container = docker
+        .create_scoped_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_scoped_container",
+                platform: None,
+            }),
+            Config {
+                image: Some(image.clone()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    container.remove_volumes(true);
+    container.own_network(network);
+
+    let result = docker
+        .inspect_container("integration_test_scoped_container", None)
+        .await?;
+    assert_eq!(
+        result.config.unwrap().labels.unwrap().get(TEMP_OWNER_LABEL),
+        Some(&temp_owner())
+    );
+
+    container.cleanup().await?;
+
+    assert!(matches!(
+        docker
+            .inspect_container("integration_test_scoped_container", None)
+            .await,
+        Err(Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+    assert!(matches!(
+        docker
+            .inspect_network(&network_id, None::<InspectNetworkOptions<String>>)
+            .await,
+        Err(Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+
+    let container = docker
+        .create_scoped_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_scoped_container_dropped",
+                platform: None,
+            }),
+            Config {
+                image: Some(image),
+                ..Default::default()
+            },
+        )
+        .await?;
+    drop(container);
+
+    // the removal runs in a background task
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    assert!(matches!(
+        docker
+            .inspect_container("integration_test_scoped_container_dropped", None)
+            .await,
+        Err(Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+async fn sweep_temp_resources_test(docker: Docker) -> Result<(), Error> {
+    // simulate a volume orphaned by another owner, so that the sweep leaves the resources of
+    // the tests running in this process alone
+    let owner = "integration_test_sweep_temp_owner";
+
+    let mut labels = HashMap::new();
+    labels.insert(TEMP_OWNER_LABEL, owner);
+
+    docker
+        .create_volume(CreateVolumeOptions {
+            name: "integration_test_sweep_temp_volume",
+            labels,
+            ..Default::default()
+        })
+        .await?;
+
+    docker.sweep_temp_resources(Some(owner)).await?;
+
+    let label = format!("{TEMP_OWNER_LABEL}={owner}");
+    let mut filters = HashMap::new();
+    filters.insert("label", vec![label.as_str()]);
+
+    let results = docker
+        .list_volumes(Some(ListVolumesOptions { filters }))
+        .await?;
+
+    assert!(results.volumes.unwrap_or_default().is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+// Hangs on Appveyor
+fn integration_test_temp_network() {
+    connect_to_docker_and_run!(temp_network_test);
+}
+
+#[test]
+fn integration_test_temp_volume() {
+    connect_to_docker_and_run!(temp_volume_test);
+}
+
+#[test]
+fn integration_test_scoped_container() {
+    connect_to_docker_and_run!(scoped_container_test);
+}
+
+#[test]
+fn integration_test_sweep_temp_resources() {
+    connect_to_docker_and_run!(sweep_temp_resources_test);
+}
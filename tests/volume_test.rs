@@ -166,6 +166,116 @@ async fn prune_volumes_test(docker: Docker) -> Result<(), Error> {
     Ok(())
 }
 
+async fn backup_restore_volume_test(docker: Docker) -> Result<(), Error> {
+    let image = create_image_hello_world(&docker).await?;
+
+    docker
+        .create_volume(CreateVolumeOptions {
+            name: "integration_test_backup_volume",
+            ..Default::default()
+        })
+        .await?;
+
+    let contents = b"backed up by bollard";
+    let mut header = tar::Header::new_gnu();
+    header.set_path("bollard.txt").unwrap();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    let mut archive = tar::Builder::new(Vec::new());
+    archive.append(&header, &contents[..]).unwrap();
+    let archive = archive.into_inner().unwrap();
+
+    docker
+        .restore_volume(
+            "integration_test_backup_volume",
+            Some(VolumeArchiveOptions { image: &image[..] }),
+            std::io::Cursor::new(archive),
+        )
+        .await?;
+
+    let mut backup = Vec::new();
+    docker
+        .backup_volume(
+            "integration_test_backup_volume",
+            Some(VolumeArchiveOptions { image: &image[..] }),
+            &mut backup,
+        )
+        .await?;
+
+    let mut entries = tar::Archive::new(&backup[..]);
+    assert!(entries.entries().unwrap().any(|entry| entry
+        .unwrap()
+        .path()
+        .unwrap()
+        .ends_with("bollard.txt")));
+
+    docker
+        .remove_volume(
+            "integration_test_backup_volume",
+            Some(RemoveVolumeOptions { force: true }),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(all(unix, feature = "system"))]
+async fn volume_usage_test(docker: Docker) -> Result<(), Error> {
+    use bollard::container::{Config, CreateContainerOptions, RemoveContainerOptions};
+    use bollard::models::HostConfig;
+
+    let image = create_image_hello_world(&docker).await?;
+
+    docker
+        .create_volume(CreateVolumeOptions {
+            name: "integration_test_volume_usage",
+            ..Default::default()
+        })
+        .await?;
+
+    let usage = docker.volume_usage("integration_test_volume_usage").await?;
+    assert!(!usage.is_in_use());
+
+    docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: "integration_test_volume_usage",
+                platform: None,
+            }),
+            Config {
+                image: Some(&image[..]),
+                host_config: Some(HostConfig {
+                    binds: Some(vec![String::from("integration_test_volume_usage:/data:ro")]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let usage = docker.volume_usage("integration_test_volume_usage").await?;
+    assert_eq!(usage.containers.len(), 1);
+    assert_eq!(
+        usage.containers[0].name.as_deref(),
+        Some("integration_test_volume_usage")
+    );
+    assert_eq!(usage.containers[0].destination.as_deref(), Some("/data"));
+    assert_eq!(usage.containers[0].read_write, Some(false));
+
+    docker
+        .remove_container(
+            "integration_test_volume_usage",
+            None::<RemoveContainerOptions>,
+        )
+        .await?;
+    docker
+        .remove_volume("integration_test_volume_usage", None)
+        .await?;
+
+    Ok(())
+}
+
 #[test]
 fn integration_test_list_volumes() {
     connect_to_docker_and_run!(list_volumes_test);
@@ -180,3 +290,15 @@ fn integration_test_create_volume() {
 fn integration_test_prune_volumes() {
     connect_to_docker_and_run!(prune_volumes_test);
 }
+
+#[test]
+#[cfg(unix)]
+fn integration_test_backup_restore_volume() {
+    connect_to_docker_and_run!(backup_restore_volume_test);
+}
+
+#[test]
+#[cfg(all(unix, feature = "system"))]
+fn integration_test_volume_usage() {
+    connect_to_docker_and_run!(volume_usage_test);
+}